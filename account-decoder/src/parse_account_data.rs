@@ -39,6 +39,10 @@ lazy_static! {
         m.insert(*CONFIG_PROGRAM_ID, ParsableAccount::Config);
         m.insert(*SYSTEM_PROGRAM_ID, ParsableAccount::Nonce);
         m.insert(spl_token::id(), ParsableAccount::SplToken);
+        m.insert(
+            solana_inline_spl::token::program_v3_4_0::id(),
+            ParsableAccount::SplToken,
+        );
         m.insert(spl_token_2022::id(), ParsableAccount::SplToken2022);
         m.insert(*STAKE_PROGRAM_ID, ParsableAccount::Stake);
         m.insert(*SYSVAR_PROGRAM_ID, ParsableAccount::Sysvar);
@@ -7,6 +7,7 @@ use {
         parse_account_data::{ParsableAccount, ParseAccountError, SplTokenAdditionalData},
         parse_token_extension::parse_extension,
     },
+    solana_inline_spl::token_programs::{is_known_token_program, KNOWN_TOKEN_PROGRAMS},
     solana_pubkey::Pubkey,
     spl_token_2022::{
         extension::{BaseStateWithExtensions, StateWithExtensions},
@@ -21,12 +22,15 @@ use {
 
 // Returns all known SPL Token program ids
 pub fn spl_token_ids() -> Vec<Pubkey> {
-    vec![spl_token::id(), spl_token_2022::id()]
+    KNOWN_TOKEN_PROGRAMS
+        .iter()
+        .map(|token_program| token_program.id)
+        .collect()
 }
 
 // Check if the provided program id as a known SPL Token program id
 pub fn is_known_spl_token_id(program_id: &Pubkey) -> bool {
-    *program_id == spl_token::id() || *program_id == spl_token_2022::id()
+    is_known_token_program(program_id)
 }
 
 #[deprecated(since = "2.0.0", note = "Use `parse_token_v2` instead")]
@@ -4,13 +4,16 @@ use {
     log::*,
     rand::{thread_rng, Rng},
     rayon::prelude::*,
+    serde_derive::{Deserialize, Serialize},
     solana_clap_utils::{
         hidden_unless_forced, input_parsers::pubkey_of, input_validators::is_url_or_moniker,
     },
     solana_cli_config::{ConfigInput, CONFIG_FILE},
     solana_client::{
-        rpc_client::SerializableTransaction, rpc_config::RpcBlockConfig,
-        rpc_request::MAX_GET_CONFIRMED_BLOCKS_RANGE, transaction_executor::TransactionExecutor,
+        rpc_client::SerializableTransaction,
+        rpc_config::RpcBlockConfig,
+        rpc_request::MAX_GET_CONFIRMED_BLOCKS_RANGE,
+        transaction_executor::{SignatureStatusCounts, TransactionExecutor},
     },
     solana_gossip::gossip_service::discover,
     solana_inline_spl::token,
@@ -35,7 +38,10 @@ use {
     std::{
         cmp::min,
         collections::VecDeque,
+        fs::File,
+        io,
         ops::Deref,
+        path::Path,
         process::exit,
         str::FromStr,
         sync::{
@@ -800,6 +806,103 @@ fn make_rpc_bench_threads(
         .collect()
 }
 
+/// Token bucket used to pace transaction submission to a target rate
+///
+/// Tokens refill continuously at `target_tps` per second, up to a burst of 100ms worth of
+/// transactions. Taking more tokens than are available puts the bucket into debt, which is
+/// paid back by waiting before the next submission.
+struct TpsPacer {
+    target_tps: f64,
+    burst: f64,
+    tokens: f64,
+    last_refill: Instant,
+}
+
+impl TpsPacer {
+    fn new(target_tps: u64, now: Instant) -> Self {
+        assert!(target_tps > 0);
+        let target_tps = target_tps as f64;
+        let burst = (target_tps / 10.0).max(1.0);
+        Self {
+            target_tps,
+            burst,
+            tokens: burst,
+            last_refill: now,
+        }
+    }
+
+    /// Largest number of transactions that should be submitted at once
+    fn max_chunk_size(&self) -> usize {
+        self.burst as usize
+    }
+
+    /// Takes `num_txs` tokens at `now` and returns how long to wait before submitting them
+    fn reserve(&mut self, num_txs: usize, now: Instant) -> Duration {
+        let elapsed = now
+            .saturating_duration_since(self.last_refill)
+            .as_secs_f64();
+        self.last_refill = self.last_refill.max(now);
+        self.tokens = (self.tokens + elapsed * self.target_tps).min(self.burst);
+        self.tokens -= num_txs as f64;
+        if self.tokens >= 0.0 {
+            Duration::ZERO
+        } else {
+            Duration::from_secs_f64(-self.tokens / self.target_tps)
+        }
+    }
+}
+
+/// Pushes `txs` to the executor, pacing them with `pacer` if there is one
+fn push_paced_transactions(
+    executor: &TransactionExecutor,
+    txs: Vec<Transaction>,
+    pacer: Option<&mut TpsPacer>,
+) -> Vec<u64> {
+    let Some(pacer) = pacer else {
+        return executor.push_transactions(txs);
+    };
+    let mut ids = Vec::with_capacity(txs.len());
+    let mut txs = txs.into_iter().peekable();
+    while txs.peek().is_some() {
+        let chunk: Vec<_> = txs.by_ref().take(pacer.max_chunk_size()).collect();
+        let delay = pacer.reserve(chunk.len(), Instant::now());
+        if !delay.is_zero() {
+            sleep(delay);
+        }
+        ids.extend(executor.push_transactions(chunk));
+    }
+    ids
+}
+
+/// Totals for a bench run, written as JSON by `--summary`
+#[derive(Clone, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+struct AccountsBenchSummary {
+    total_accounts_created: usize,
+    total_accounts_closed: u64,
+    tx_sent_count: usize,
+    /// Sent transactions that landed successfully, as seen by the signature clearing threads
+    success: u64,
+    /// Sent transactions that landed with an error
+    error: u64,
+    /// Sent transactions that didn't land within the clearing threads' timeout
+    timed_out: u64,
+    elapsed_ms: u64,
+}
+
+impl AccountsBenchSummary {
+    fn add_signature_status_counts(&mut self, counts: SignatureStatusCounts) {
+        self.success += counts.success;
+        self.error += counts.error;
+        self.timed_out += counts.timed_out;
+    }
+}
+
+fn write_summary(path: &Path, summary: &AccountsBenchSummary) -> io::Result<()> {
+    let file = File::create(path)?;
+    serde_json::to_writer_pretty(file, summary)?;
+    Ok(())
+}
+
 #[allow(clippy::too_many_arguments)]
 fn run_accounts_bench(
     client: Arc<RpcClient>,
@@ -815,12 +918,21 @@ fn run_accounts_bench(
     reclaim_accounts: bool,
     rpc_benches: Option<Vec<RpcBench>>,
     num_rpc_bench_threads: usize,
-) {
+    target_tps: Option<u64>,
+    exit_signal: Arc<AtomicBool>,
+) -> AccountsBenchSummary {
     assert!(num_instructions > 0);
     info!("Targeting {}", client.url());
+    let start = Instant::now();
+    let mut summary = AccountsBenchSummary::default();
+    if let Some(target_tps) = target_tps {
+        info!("Pacing submission to {target_tps} TPS");
+    }
 
     let mut latest_blockhash = Instant::now();
     let mut last_log = Instant::now();
+    let mut last_log_tx_sent_count = 0;
+    let mut pacer = target_tps.map(|target_tps| TpsPacer::new(target_tps, Instant::now()));
     let mut count = 0;
     let mut blockhash = poll_get_latest_blockhash(&client).expect("blockhash");
     let slot_height = Arc::new(AtomicU64::new(poll_slot_height(&client)));
@@ -894,7 +1006,8 @@ fn run_accounts_bench(
         Vec::new()
     };
 
-    loop {
+    let mut airdrop_failed = false;
+    'bench: loop {
         if latest_blockhash.elapsed().as_millis() > 10_000 {
             blockhash = poll_get_latest_blockhash(&client).expect("blockhash");
             slot_height.store(poll_slot_height(&client), Ordering::Relaxed);
@@ -919,7 +1032,8 @@ fn run_accounts_bench(
                     );
                     if !airdrop_lamports(&client, payer_keypairs[i], lamports * 100_000) {
                         warn!("failed airdrop, exiting");
-                        return;
+                        airdrop_failed = true;
+                        break 'bench;
                     }
                 }
             }
@@ -953,7 +1067,7 @@ fn run_accounts_bench(
                         balances[i] = balances[i].saturating_sub(lamports * txs.len() as u64);
                         info!("txs: {}", txs.len());
                         transaction_signature_tracker.track_transactions(&txs);
-                        let new_ids = executor.push_transactions(txs);
+                        let new_ids = push_paced_transactions(&executor, txs, pacer.as_mut());
                         info!("ids: {}", new_ids.len());
                         tx_sent_count += new_ids.len();
                         total_accounts_created += num_instructions * new_ids.len();
@@ -987,7 +1101,7 @@ fn run_accounts_bench(
                     balances[0] = balances[0].saturating_sub(fee * txs.len() as u64);
                     info!("close txs: {}", txs.len());
                     transaction_signature_tracker.track_transactions(&txs);
-                    let new_ids = executor.push_transactions(txs);
+                    let new_ids = push_paced_transactions(&executor, txs, pacer.as_mut());
                     info!("close ids: {}", new_ids.len());
                     tx_sent_count += new_ids.len();
                     total_accounts_closed += new_ids.len() as u64;
@@ -1017,6 +1131,15 @@ fn run_accounts_bench(
                 "total_accounts_created: {} total_accounts_closed: {} tx_sent_count: {} loop_count: {} balance(s): {:?}",
                 total_accounts_created, total_accounts_closed, tx_sent_count, count, balances
             );
+            let achieved_tps =
+                (tx_sent_count - last_log_tx_sent_count) as f64 / last_log.elapsed().as_secs_f64();
+            match target_tps {
+                Some(target_tps) => {
+                    info!("achieved_tps: {achieved_tps:.1} target_tps: {target_tps}")
+                }
+                None => info!("achieved_tps: {achieved_tps:.1}"),
+            }
+            last_log_tx_sent_count = tx_sent_count;
             last_log = Instant::now();
         }
         if iterations != 0 && count >= iterations {
@@ -1030,13 +1153,21 @@ fn run_accounts_bench(
             );
             break;
         }
+        if exit_signal.load(Ordering::Relaxed) {
+            info!("Exit signal received");
+            break;
+        }
         if executor.num_outstanding() >= batch_size {
             sleep(Duration::from_millis(500));
         }
     }
-    executor.close();
+    summary.add_signature_status_counts(executor.close());
+    if let Some(start_bench) = start_bench_barrier {
+        // The loop exited before unlocking the bench threads, unlock them so they can be joined
+        start_bench.wait();
+    }
 
-    if reclaim_accounts {
+    if reclaim_accounts && !airdrop_failed {
         let executor = TransactionExecutor::new_with_rpc_client(client.clone());
         loop {
             let max_closed_seed = seed_tracker.max_closed.load(Ordering::Relaxed);
@@ -1106,17 +1237,28 @@ fn run_accounts_bench(
             if max_closed_seed >= max_created_seed {
                 break;
             }
+            if exit_signal.load(Ordering::Relaxed) {
+                info!("Exit signal received, leaving remaining accounts open");
+                break;
+            }
             if executor.num_outstanding() >= batch_size {
                 sleep(Duration::from_millis(500));
             }
         }
-        executor.close();
+        summary.add_signature_status_counts(executor.close());
     }
 
     exit.store(true, Ordering::Relaxed);
     for t in rpc_bench_threads {
         t.join().unwrap();
     }
+
+    summary.total_accounts_created = total_accounts_created;
+    summary.total_accounts_closed = total_accounts_closed;
+    summary.tx_sent_count = tx_sent_count;
+    summary.elapsed_ms = start.elapsed().as_millis() as u64;
+    info!("Summary: {summary:?}");
+    summary
 }
 
 fn main() {
@@ -1267,6 +1409,25 @@ fn main() {
                 ])
                 .help("Spawn a thread which calls a specific RPC method in a loop to benchmark it"),
         )
+        .arg(
+            Arg::with_name("summary")
+                .long("summary")
+                .takes_value(true)
+                .value_name("PATH")
+                .help("Write a JSON summary of the run to this file on exit"),
+        )
+        .arg(
+            Arg::with_name("target_tps")
+                .long("target-tps")
+                .takes_value(true)
+                .value_name("TPS")
+                .validator(|value| match value.parse::<u64>() {
+                    Ok(0) | Err(_) => Err(format!("invalid target TPS: {value}")),
+                    Ok(_) => Ok(()),
+                })
+                .help("Pace transaction submission to approximately this many transactions per \
+                       second. Submission is uncapped if not set."),
+        )
         .get_matches();
 
     let skip_gossip = !matches.is_present("check_gossip");
@@ -1297,6 +1458,7 @@ fn main() {
     };
 
     let mint = pubkey_of(&matches, "mint");
+    let target_tps = value_t!(matches, "target_tps", u64).ok();
 
     let payer_keypairs: Vec<_> = values_t_or_exit!(matches, "identity", String)
         .iter()
@@ -1361,7 +1523,14 @@ fn main() {
         ))
     };
 
-    run_accounts_bench(
+    let exit_signal = Arc::new(AtomicBool::new(false));
+    {
+        let exit_signal = exit_signal.clone();
+        ctrlc::set_handler(move || exit_signal.store(true, Ordering::Relaxed))
+            .expect("Error setting Ctrl-C handler");
+    }
+
+    let summary = run_accounts_bench(
         client,
         &payer_keypair_refs,
         iterations,
@@ -1375,7 +1544,15 @@ fn main() {
         matches.is_present("reclaim_accounts"),
         rpc_benches,
         num_rpc_bench_threads,
+        target_tps,
+        exit_signal,
     );
+    if let Some(summary_path) = matches.value_of("summary") {
+        if let Err(err) = write_summary(Path::new(summary_path), &summary) {
+            eprintln!("Failed to write summary to {summary_path}: {err}");
+            exit(1);
+        }
+    }
 }
 
 #[cfg(test)]
@@ -1469,6 +1646,8 @@ pub mod test {
             reclaim_accounts,
             Some(vec![RpcBench::ProgramAccounts]),
             1,
+            None,
+            Arc::default(),
         );
         let post_txs = client.get_transaction_count().unwrap();
         start.stop();
@@ -1519,6 +1698,8 @@ pub mod test {
             reclaim_accounts,
             Some(vec![RpcBench::ProgramAccounts]),
             1,
+            None,
+            Arc::default(),
         );
         let post_txs = client.get_transaction_count().unwrap();
         start.stop();
@@ -1619,8 +1800,111 @@ pub mod test {
             true,
             None,
             0,
+            None,
+            Arc::default(),
         );
         start.stop();
         info!("{}", start);
     }
+
+    #[test]
+    fn test_tps_pacer() {
+        let target_tps = 50;
+        let run_duration = Duration::from_secs(2);
+        let start = Instant::now();
+        let mut pacer = TpsPacer::new(target_tps, start);
+        assert_eq!(pacer.max_chunk_size(), 5);
+
+        // Submit as fast as the pacer allows, in chunks of varying size, over a simulated run
+        let mut now = start;
+        let mut num_sent = 0;
+        for chunk_size in [1, 3, 5].into_iter().cycle() {
+            now += pacer.reserve(chunk_size, now);
+            if now.duration_since(start) >= run_duration {
+                break;
+            }
+            num_sent += chunk_size;
+        }
+        let expected = target_tps as f64 * run_duration.as_secs_f64();
+        let achieved = num_sent as f64;
+        assert!(
+            (achieved - expected).abs() <= expected * 0.1,
+            "sent {num_sent}, expected about {expected}"
+        );
+
+        // Idle time only builds up a single burst
+        now += Duration::from_secs(10);
+        assert_eq!(pacer.reserve(5, now), Duration::ZERO);
+        assert_eq!(pacer.reserve(1, now), Duration::from_millis(20));
+    }
+
+    #[test]
+    fn test_accounts_bench_summary() {
+        solana_logger::setup();
+        let mint_keypair = Keypair::new();
+        let mint_pubkey = mint_keypair.pubkey();
+        let faucet_addr = run_local_faucet(mint_keypair, None);
+        let test_validator = TestValidator::with_custom_fees(
+            mint_pubkey,
+            1,
+            Some(faucet_addr),
+            SocketAddrSpace::Unspecified,
+        );
+        let rpc_client = Arc::new(RpcClient::new_with_commitment(
+            test_validator.rpc_url(),
+            CommitmentConfig::processed(),
+        ));
+
+        let iterations = 3;
+        let batch_size = 10;
+        let num_instructions = 2;
+        let payer = Keypair::new();
+        let summary = run_accounts_bench(
+            rpc_client,
+            &[&payer],
+            iterations,
+            None,
+            batch_size,
+            0,
+            None,
+            num_instructions,
+            None,
+            None,
+            false,
+            None,
+            0,
+            None,
+            Arc::default(),
+        );
+
+        let summary_dir = tempfile::tempdir().unwrap();
+        let summary_path = summary_dir.path().join("summary.json");
+        write_summary(&summary_path, &summary).unwrap();
+        let parsed: AccountsBenchSummary =
+            serde_json::from_reader(File::open(&summary_path).unwrap()).unwrap();
+        assert_eq!(parsed, summary);
+        assert!(parsed.tx_sent_count > 0);
+        assert_eq!(
+            parsed.total_accounts_created,
+            num_instructions * parsed.tx_sent_count
+        );
+        assert_eq!(parsed.total_accounts_closed, 0);
+        assert!(parsed.success + parsed.error + parsed.timed_out <= parsed.tx_sent_count as u64);
+        assert!(parsed.elapsed_ms > 0);
+
+        // The summary is written with the field names documented for `--summary`
+        let json: serde_json::Value =
+            serde_json::from_reader(File::open(&summary_path).unwrap()).unwrap();
+        for field in [
+            "total_accounts_created",
+            "total_accounts_closed",
+            "tx_sent_count",
+            "success",
+            "error",
+            "timed_out",
+            "elapsed_ms",
+        ] {
+            assert!(json.get(field).is_some(), "missing {field}");
+        }
+    }
 }
@@ -16,6 +16,7 @@ use {
         },
         accounts_index::ScanConfig,
         ancestors::Ancestors,
+        append_vec::aligned_stored_size,
     },
     solana_sdk::{
         account::{Account, AccountSharedData, ReadableAccount},
@@ -342,6 +343,41 @@ fn bench_load_largest_accounts(b: &mut Bencher) {
     });
 }
 
+fn bench_load_by_program(b: &mut Bencher, collect_all_unsorted: bool) {
+    let accounts_db = new_accounts_db(Vec::new());
+    let accounts = Accounts::new(Arc::new(accounts_db));
+    let program_id = Pubkey::new_unique();
+    for i in 0..100_000 {
+        // half of the accounts are owned by the program
+        let owner = if i % 2 == 0 {
+            program_id
+        } else {
+            Pubkey::default()
+        };
+        let account = AccountSharedData::new(1, 0, &owner);
+        accounts.store_slow_uncached(0, &Pubkey::new_unique(), &account);
+    }
+    let ancestors = Ancestors::from(vec![0]);
+    let config = ScanConfig::new(collect_all_unsorted);
+    b.iter(|| {
+        test::black_box(
+            accounts
+                .load_by_program(&ancestors, 0, &program_id, &config)
+                .unwrap(),
+        )
+    });
+}
+
+#[bench]
+fn bench_load_by_program_unsorted(b: &mut Bencher) {
+    bench_load_by_program(b, true);
+}
+
+#[bench]
+fn bench_load_by_program_sorted(b: &mut Bencher) {
+    bench_load_by_program(b, false);
+}
+
 #[bench]
 fn bench_sort_and_remove_dups(b: &mut Bencher) {
     fn generate_sample_account_from_storage(i: u8) -> AccountFromStorage {
@@ -350,6 +386,7 @@ fn bench_sort_and_remove_dups(b: &mut Bencher) {
         AccountFromStorage {
             index_info: AccountInfo::new(StorageLocation::AppendVec(i as u32, offset), i as u64),
             data_len: i as u64,
+            stored_size: aligned_stored_size(i as usize),
             pubkey: Pubkey::new_from_array([i; 32]),
         }
     }
@@ -372,6 +409,7 @@ fn bench_sort_and_remove_dups_no_dups(b: &mut Bencher) {
         AccountFromStorage {
             index_info: AccountInfo::new(StorageLocation::AppendVec(i as u32, offset), i as u64),
             data_len: i as u64,
+            stored_size: aligned_stored_size(i as usize),
             pubkey: Pubkey::new_unique(),
         }
     }
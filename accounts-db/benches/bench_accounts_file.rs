@@ -1,6 +1,8 @@
 #![allow(clippy::arithmetic_side_effects)]
 use {
     criterion::{criterion_group, criterion_main, BatchSize, BenchmarkId, Criterion, Throughput},
+    rand::{Rng, SeedableRng},
+    rand_chacha::ChaChaRng,
     solana_accounts_db::{
         accounts_file::StorageAccess,
         append_vec::{self, AppendVec, SCAN_BUFFER_SIZE_WITHOUT_DATA},
@@ -13,6 +15,7 @@ use {
         account::{AccountSharedData, ReadableAccount},
         clock::Slot,
         pubkey::Pubkey,
+        rent::Rent,
         rent_collector::RENT_EXEMPT_RENT_EPOCH,
         system_instruction::MAX_PERMITTED_DATA_LENGTH,
     },
@@ -190,5 +193,82 @@ fn bench_scan_pubkeys(c: &mut Criterion) {
     }
 }
 
-criterion_group!(benches, bench_write_accounts_file, bench_scan_pubkeys);
+fn bench_data_compression(c: &mut Criterion) {
+    let mut group = c.benchmark_group("data_compression");
+    let temp_dir = tempfile::tempdir().unwrap();
+
+    // large accounts whose data is mostly zeroes and repeated records, like serialized markets,
+    // with a share of random bytes which do not compress
+    let accounts_count = 100;
+    let data_len = 256 * 1024;
+    let mut rng = ChaChaRng::seed_from_u64(255);
+    let accounts: Vec<_> = std::iter::repeat_with(|| {
+        let mut data = vec![0; data_len];
+        let record: [u8; 64] = std::array::from_fn(|_| rng.gen());
+        for chunk in data[..data_len / 2].chunks_mut(record.len()) {
+            chunk.copy_from_slice(&record);
+        }
+        rng.fill(&mut data[data_len * 7 / 8..]);
+        let mut account = AccountSharedData::new_rent_epoch(
+            Rent::default().minimum_balance(data_len),
+            0,
+            &Pubkey::new_unique(),
+            RENT_EXEMPT_RENT_EPOCH,
+        );
+        account.set_data(data);
+        (Pubkey::new_unique(), account)
+    })
+    .take(accounts_count)
+    .collect();
+    let storable_accounts = (Slot::MAX, accounts.as_slice());
+    let file_size = accounts
+        .iter()
+        .map(|(_, account)| append_vec::aligned_stored_size(account.data().len()))
+        .sum();
+    group.throughput(Throughput::Bytes((accounts_count * data_len) as u64));
+
+    for (name, threshold) in [("uncompressed", None), ("compressed", Some(data_len))] {
+        let path = temp_dir.path().join(format!("append_vec_{name}"));
+        let new_append_vec = || {
+            let mut append_vec = AppendVec::new(&path, true, file_size);
+            append_vec.set_data_compression_threshold(threshold);
+            append_vec
+        };
+        group.bench_function(BenchmarkId::new("write", name), |b| {
+            b.iter_batched_ref(
+                new_append_vec,
+                |append_vec| {
+                    let res = append_vec.append_accounts(&storable_accounts, 0).unwrap();
+                    assert_eq!(res.offsets.len(), accounts_count);
+                },
+                BatchSize::SmallInput,
+            );
+        });
+
+        let append_vec = new_append_vec();
+        let offsets = append_vec
+            .append_accounts(&storable_accounts, 0)
+            .unwrap()
+            .offsets;
+        group.bench_function(BenchmarkId::new("load", name), |b| {
+            b.iter(|| {
+                for offset in &offsets {
+                    let account = append_vec
+                        .get_stored_account_meta_callback(*offset, |account| {
+                            account.to_account_shared_data()
+                        })
+                        .unwrap();
+                    assert_eq!(account.data().len(), data_len);
+                }
+            });
+        });
+    }
+}
+
+criterion_group!(
+    benches,
+    bench_write_accounts_file,
+    bench_scan_pubkeys,
+    bench_data_compression
+);
 criterion_main!(benches);
@@ -0,0 +1,69 @@
+use {
+    criterion::{criterion_group, criterion_main, BatchSize, BenchmarkId, Criterion, Throughput},
+    solana_accounts_db::accounts_db::AccountsDb,
+    solana_sdk::{
+        account::AccountSharedData, clock::Slot, genesis_config::GenesisConfig, pubkey::Pubkey,
+    },
+};
+
+const NUM_SLOTS: Slot = 100;
+const ACCOUNTS_PER_SLOT: usize = 1_000;
+const NUM_THREADS: [usize; 4] = [1, 2, 4, 8];
+
+/// Returns an AccountsDb with NUM_SLOTS storages and an empty index, as after
+/// untarring a snapshot. Half of each slot's accounts are new, the other half
+/// overwrite accounts from the previous slot, so that the index has to pick
+/// the latest of several versions.
+fn accounts_db_with_storages(pubkeys: &[Pubkey]) -> AccountsDb {
+    // most accounts on mnb are 165-200 bytes, so use that here too
+    let space = 200;
+    let accounts_db = AccountsDb::new_single_for_tests();
+    for slot in 0..NUM_SLOTS {
+        let start = slot as usize * ACCOUNTS_PER_SLOT / 2;
+        let accounts = pubkeys[start..start + ACCOUNTS_PER_SLOT]
+            .iter()
+            .map(|pubkey| (pubkey, AccountSharedData::new(slot + 1, space, pubkey)))
+            .collect::<Vec<_>>();
+        let accounts = accounts
+            .iter()
+            .map(|(pubkey, account)| (*pubkey, account))
+            .collect::<Vec<_>>();
+        let storage =
+            accounts_db.create_and_insert_store(slot, 4 * 1024 * 1024, "bench_generate_index");
+        storage.accounts.append_accounts(&(slot, &accounts[..]), 0);
+    }
+    accounts_db
+}
+
+fn bench_generate_index(c: &mut Criterion) {
+    let num_pubkeys = (NUM_SLOTS as usize + 1) * ACCOUNTS_PER_SLOT / 2;
+    let pubkeys = std::iter::repeat_with(Pubkey::new_unique)
+        .take(num_pubkeys)
+        .collect::<Vec<_>>();
+    let genesis_config = GenesisConfig::default();
+
+    let mut group = c.benchmark_group("generate_index");
+    group.sample_size(10);
+    group.throughput(Throughput::Elements(NUM_SLOTS * ACCOUNTS_PER_SLOT as u64));
+    for num_threads in NUM_THREADS {
+        let thread_pool = rayon::ThreadPoolBuilder::new()
+            .num_threads(num_threads)
+            .build()
+            .unwrap();
+        group.bench_function(BenchmarkId::new("threads", num_threads), |b| {
+            b.iter_batched(
+                || accounts_db_with_storages(&pubkeys),
+                |accounts_db| {
+                    thread_pool.install(|| {
+                        accounts_db.generate_index(None, false, &genesis_config, false)
+                    });
+                    accounts_db
+                },
+                BatchSize::PerIteration,
+            );
+        });
+    }
+}
+
+criterion_group!(benches, bench_generate_index);
+criterion_main!(benches);
@@ -5,7 +5,7 @@ use {
             AccountStorageEntry, AccountsAddRootTiming, AccountsDb, LoadHint, LoadedAccount,
             ScanAccountStorageData, ScanStorageResult, VerifyAccountsHashAndLamportsConfig,
         },
-        accounts_index::{IndexKey, ScanConfig, ScanError, ScanResult},
+        accounts_index::{IndexKey, ScanConfig, ScanError, ScanPage, ScanResult},
         ancestors::Ancestors,
         storable_accounts::StorableAccounts,
     },
@@ -17,6 +17,7 @@ use {
         clock::{BankId, Slot},
         message::v0::LoadedAddresses,
         pubkey::Pubkey,
+        rent::Rent,
         slot_hashes::SlotHashes,
         transaction::{Result, SanitizedTransaction, TransactionError},
         transaction_context::TransactionAccount,
@@ -27,7 +28,7 @@ use {
     std::{
         cmp::Reverse,
         collections::{BinaryHeap, HashSet},
-        ops::RangeBounds,
+        ops::{Bound, RangeBounds},
         sync::{
             atomic::{AtomicUsize, Ordering},
             Arc, Mutex,
@@ -37,6 +38,17 @@ use {
 
 pub type PubkeyAccountSlot = (Pubkey, AccountSharedData, Slot);
 
+/// Totals over the accounts whose balance is below the rent exempt minimum
+#[derive(Debug, Default, PartialEq, Eq)]
+pub struct RentDebtReport {
+    /// Number of rent paying accounts
+    pub num_rent_paying_accounts: usize,
+    /// Lamports held by rent paying accounts
+    pub rent_paying_lamports: u64,
+    /// Lamports rent paying accounts are short of being rent exempt
+    pub rent_exempt_shortfall_lamports: u64,
+}
+
 pub struct TransactionAccountLocksIterator<'a, T: SVMMessage> {
     transaction: &'a T,
 }
@@ -341,6 +353,62 @@ impl Accounts {
         }
     }
 
+    /// Like `load_while_filtering`, but only keeps the accounts belonging to `page`.
+    /// Accounts must be visited in pubkey order; the scan is aborted through the page
+    /// scan's `config` once the page is full or the caller aborts.
+    fn load_page_while_filtering<F: Fn(&AccountSharedData) -> bool>(
+        collector: &mut Vec<TransactionAccount>,
+        some_account_tuple: Option<(&Pubkey, AccountSharedData, Slot)>,
+        page: &ScanPage,
+        caller_config: &ScanConfig,
+        config: &ScanConfig,
+        filter: F,
+    ) {
+        let Some((pubkey, account, _slot)) = some_account_tuple else {
+            return;
+        };
+        if collector.len() >= page.limit || caller_config.is_aborted() {
+            config.abort();
+            return;
+        }
+        if page.after.is_some_and(|after| *pubkey <= after) {
+            return;
+        }
+        if Self::is_loadable(account.lamports()) && filter(&account) {
+            collector.push((*pubkey, account));
+            if collector.len() >= page.limit {
+                config.abort();
+            }
+        }
+    }
+
+    /// The config of a page scan: ordered, and with its own abort flag to stop the
+    /// scan once the page is full, so that a full page doesn't abort other scans
+    /// sharing the caller's flag
+    fn page_scan_config() -> ScanConfig {
+        ScanConfig {
+            abort: Some(Arc::default()),
+            collect_all_unsorted: false,
+        }
+    }
+
+    /// A page scan aborts itself once the page is full, so only the caller aborting
+    /// before then is an error
+    fn finish_page_scan(
+        result: ScanResult<()>,
+        collector: Vec<TransactionAccount>,
+        page: &ScanPage,
+        caller_config: &ScanConfig,
+    ) -> ScanResult<Vec<TransactionAccount>> {
+        result?;
+        if collector.len() < page.limit && caller_config.is_aborted() {
+            return Err(ScanError::Aborted(
+                "The scan was aborted before the page was complete".to_string(),
+            ));
+        }
+        Ok(collector)
+    }
+
     fn load_with_slot(
         collector: &mut Vec<PubkeyAccountSlot>,
         some_account_tuple: Option<(&Pubkey, AccountSharedData, Slot)>,
@@ -398,6 +466,99 @@ impl Accounts {
             .map(|_| collector)
     }
 
+    /// Returns one page of the accounts owned by `program_id` which pass `filter`,
+    /// ordered by pubkey. The scan starts after `page.after` and stops once the page
+    /// is full, or fails if `config` is aborted first.
+    pub fn load_page_by_program_with_filter<F: Fn(&AccountSharedData) -> bool>(
+        &self,
+        ancestors: &Ancestors,
+        bank_id: BankId,
+        program_id: &Pubkey,
+        filter: F,
+        page: &ScanPage,
+        config: &ScanConfig,
+    ) -> ScanResult<Vec<TransactionAccount>> {
+        let caller_config = config;
+        let config = Self::page_scan_config();
+        let range = (
+            page.after.map_or(Bound::Unbounded, Bound::Excluded),
+            Bound::Unbounded,
+        );
+        let mut collector = Vec::new();
+        let result = self.accounts_db.scan_accounts_in_range(
+            ancestors,
+            bank_id,
+            range,
+            |some_account_tuple| {
+                Self::load_page_while_filtering(
+                    &mut collector,
+                    some_account_tuple,
+                    page,
+                    caller_config,
+                    &config,
+                    |account| account.owner() == program_id && filter(account),
+                )
+            },
+            &config,
+        );
+        Self::finish_page_scan(result, collector, page, caller_config)
+    }
+
+    /// Returns one page of the accounts found under `index_key` which pass `filter`,
+    /// ordered by pubkey. The scan stops once the page is full, and fails if `config`
+    /// is aborted first or the page exceeds `byte_limit_for_scan`.
+    pub fn load_page_by_index_key_with_filter<F: Fn(&AccountSharedData) -> bool>(
+        &self,
+        ancestors: &Ancestors,
+        bank_id: BankId,
+        index_key: &IndexKey,
+        filter: F,
+        page: &ScanPage,
+        config: &ScanConfig,
+        byte_limit_for_scan: Option<usize>,
+    ) -> ScanResult<Vec<TransactionAccount>> {
+        let sum = AtomicUsize::default();
+        let caller_config = config;
+        let config = Self::page_scan_config();
+        let mut collector = Vec::new();
+        let result = self
+            .accounts_db
+            .index_scan_accounts(
+                ancestors,
+                bank_id,
+                *index_key,
+                |some_account_tuple| {
+                    Self::load_page_while_filtering(
+                        &mut collector,
+                        some_account_tuple,
+                        page,
+                        caller_config,
+                        &config,
+                        |account| {
+                            let use_account = filter(account);
+                            if use_account
+                                && Self::accumulate_and_check_scan_result_size(
+                                    &sum,
+                                    account,
+                                    &byte_limit_for_scan,
+                                )
+                            {
+                                // total size of results exceeds size limit, so abort scan
+                                config.abort();
+                            }
+                            use_account
+                        },
+                    )
+                },
+                &config,
+            )
+            .map(|_| ());
+        if byte_limit_for_scan.is_some_and(|limit| sum.load(Ordering::Relaxed) > limit) {
+            return Self::maybe_abort_scan(result.map(|_| collector), &config);
+        }
+        Self::finish_page_scan(result, collector, page, caller_config)
+    }
+
     fn calc_scan_result_size(account: &AccountSharedData) -> usize {
         account.data().len()
             + std::mem::size_of::<AccountSharedData>()
@@ -519,6 +680,35 @@ impl Accounts {
         )
     }
 
+    /// Report the accounts, as of `ancestors`, that are not rent exempt under `rent`
+    pub fn rent_debt_report(
+        &self,
+        ancestors: &Ancestors,
+        bank_id: BankId,
+        rent: &Rent,
+    ) -> ScanResult<RentDebtReport> {
+        let mut report = RentDebtReport::default();
+        self.accounts_db
+            .scan_accounts(
+                ancestors,
+                bank_id,
+                |some_account_tuple| {
+                    let Some((_pubkey, account, _slot)) = some_account_tuple else {
+                        return;
+                    };
+                    let lamports = account.lamports();
+                    let minimum_balance = rent.minimum_balance(account.data().len());
+                    if Self::is_loadable(lamports) && lamports < minimum_balance {
+                        report.num_rent_paying_accounts += 1;
+                        report.rent_paying_lamports += lamports;
+                        report.rent_exempt_shortfall_lamports += minimum_balance - lamports;
+                    }
+                },
+                &ScanConfig::default(),
+            )
+            .map(|_| report)
+    }
+
     pub fn hold_range_in_memory<R>(
         &self,
         range: &R,
@@ -945,6 +1135,153 @@ mod tests {
         assert_eq!(loaded, vec![]);
     }
 
+    #[test]
+    fn test_load_page_by_program_with_filter() {
+        let accounts_db = AccountsDb::new_single_for_tests();
+        let accounts = Accounts::new(Arc::new(accounts_db));
+        let program_id = Pubkey::new_unique();
+
+        let mut expected = (0..100u64)
+            .map(|i| {
+                let pubkey = solana_pubkey::new_rand();
+                let account = AccountSharedData::new(i + 1, 0, &program_id);
+                accounts.store_slow_uncached(0, &pubkey, &account);
+                (pubkey, account)
+            })
+            .collect::<Vec<_>>();
+        // not owned by the program
+        accounts.store_slow_uncached(
+            0,
+            &solana_pubkey::new_rand(),
+            &AccountSharedData::new(1, 0, &Pubkey::new_unique()),
+        );
+        expected.sort_unstable_by_key(|(pubkey, _)| *pubkey);
+
+        let ancestors = vec![(0, 0)].into_iter().collect();
+        let load_page = |after, limit, filter: &dyn Fn(&AccountSharedData) -> bool| {
+            accounts
+                .load_page_by_program_with_filter(
+                    &ancestors,
+                    0,
+                    &program_id,
+                    filter,
+                    &ScanPage { after, limit },
+                    &ScanConfig::default(),
+                )
+                .unwrap()
+        };
+
+        // Pages continue from the last pubkey of the previous page
+        let mut after = None;
+        let mut loaded = vec![];
+        loop {
+            let page = load_page(after, 30, &|_| true);
+            assert!(page.len() <= 30);
+            let Some((last, _)) = page.last() else {
+                break;
+            };
+            after = Some(*last);
+            loaded.extend(page);
+        }
+        assert_eq!(loaded, expected);
+
+        // Filters apply before paging
+        let odd = |account: &AccountSharedData| account.lamports() % 2 == 1;
+        let page = load_page(None, 10, &odd);
+        let expected_odd = expected
+            .iter()
+            .filter(|(_, account)| odd(account))
+            .take(10)
+            .cloned()
+            .collect::<Vec<_>>();
+        assert_eq!(page, expected_odd);
+
+        assert_eq!(load_page(None, 0, &|_| true), vec![]);
+        assert_eq!(load_page(Some(expected[99].0), 10, &|_| true), vec![]);
+
+        // An abort before the page is complete fails the scan
+        let config = ScanConfig {
+            abort: Some(Arc::new(AtomicBool::new(true))),
+            collect_all_unsorted: true,
+        };
+        assert!(matches!(
+            accounts.load_page_by_program_with_filter(
+                &ancestors,
+                0,
+                &program_id,
+                |_| true,
+                &ScanPage {
+                    after: None,
+                    limit: 30
+                },
+                &config,
+            ),
+            Err(ScanError::Aborted(_))
+        ));
+
+        // A full page stops the scan without aborting the caller's scans
+        let config = ScanConfig {
+            abort: Some(Arc::default()),
+            collect_all_unsorted: true,
+        };
+        let page = accounts
+            .load_page_by_program_with_filter(
+                &ancestors,
+                0,
+                &program_id,
+                |_| true,
+                &ScanPage {
+                    after: None,
+                    limit: 30,
+                },
+                &config,
+            )
+            .unwrap();
+        assert_eq!(page, expected[..30]);
+        assert!(!config.is_aborted());
+    }
+
+    #[test]
+    fn test_rent_debt_report() {
+        let accounts_db = AccountsDb::new_single_for_tests();
+        let accounts = Accounts::new(Arc::new(accounts_db));
+        let rent = Rent::default();
+        let owner = Pubkey::new_unique();
+
+        let exempt_account = AccountSharedData::new(rent.minimum_balance(10), 10, &owner);
+        accounts.store_slow_uncached(0, &Pubkey::new_unique(), &exempt_account);
+        let rent_paying_account0 = AccountSharedData::new(1_000, 10, &owner);
+        accounts.store_slow_uncached(0, &Pubkey::new_unique(), &rent_paying_account0);
+        let rent_paying_account1 = AccountSharedData::new(2_000, 0, &owner);
+        accounts.store_slow_uncached(0, &Pubkey::new_unique(), &rent_paying_account1);
+        // zero lamport accounts are dead, so owe nothing
+        let dead_account = AccountSharedData::new(0, 10, &owner);
+        accounts.store_slow_uncached(0, &Pubkey::new_unique(), &dead_account);
+
+        let ancestors = vec![(0, 0)].into_iter().collect();
+        assert_eq!(
+            accounts.rent_debt_report(&ancestors, 0, &rent).unwrap(),
+            RentDebtReport {
+                num_rent_paying_accounts: 2,
+                rent_paying_lamports: 3_000,
+                rent_exempt_shortfall_lamports: rent.minimum_balance(10) - 1_000
+                    + rent.minimum_balance(0)
+                    - 2_000,
+            }
+        );
+
+        // the report follows the state as of the ancestors
+        accounts.store_slow_uncached(1, &Pubkey::new_unique(), &rent_paying_account0);
+        let ancestors = vec![(0, 0), (1, 0)].into_iter().collect();
+        assert_eq!(
+            accounts
+                .rent_debt_report(&ancestors, 0, &rent)
+                .unwrap()
+                .num_rent_paying_accounts,
+            3
+        );
+    }
+
     #[test]
     fn test_lock_accounts_with_duplicates() {
         let accounts_db = AccountsDb::new_single_for_tests();
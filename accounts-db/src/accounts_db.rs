@@ -507,12 +507,14 @@ pub const ACCOUNTS_DB_CONFIG_FOR_TESTING: AccountsDbConfig = AccountsDbConfig {
     ancient_storage_ideal_size: None,
     max_ancient_storages: None,
     skip_initial_hash_calc: false,
+    skip_accounts_hash_cache: false,
     exhaustively_verify_refcounts: false,
     create_ancient_storage: CreateAncientStorage::Pack,
     partitioned_epoch_rewards_config: DEFAULT_PARTITIONED_EPOCH_REWARDS_CONFIG,
     test_skip_rewrites_but_include_in_bank_hash: false,
     storage_access: StorageAccess::Mmap,
     scan_filter_for_shrinking: ScanFilter::OnlyAbnormalWithVerify,
+    append_vec_data_compression_threshold: None,
     enable_experimental_accumulator_hash: false,
     verify_experimental_accumulator_hash: false,
     snapshots_use_experimental_accumulator_hash: false,
@@ -534,12 +536,14 @@ pub const ACCOUNTS_DB_CONFIG_FOR_BENCHMARKS: AccountsDbConfig = AccountsDbConfig
     ancient_storage_ideal_size: None,
     max_ancient_storages: None,
     skip_initial_hash_calc: false,
+    skip_accounts_hash_cache: false,
     exhaustively_verify_refcounts: false,
     create_ancient_storage: CreateAncientStorage::Pack,
     partitioned_epoch_rewards_config: DEFAULT_PARTITIONED_EPOCH_REWARDS_CONFIG,
     test_skip_rewrites_but_include_in_bank_hash: false,
     storage_access: StorageAccess::Mmap,
     scan_filter_for_shrinking: ScanFilter::OnlyAbnormalWithVerify,
+    append_vec_data_compression_threshold: None,
     enable_experimental_accumulator_hash: false,
     verify_experimental_accumulator_hash: false,
     snapshots_use_experimental_accumulator_hash: false,
@@ -569,6 +573,9 @@ struct LoadAccountsIndexForShrink<'a, T: ShrinkCollectRefs<'a>> {
 pub struct AccountFromStorage {
     pub index_info: AccountInfo,
     pub data_len: u64,
+    /// size of the account in storage, aligned. This is less than `aligned_stored_size(data_len)`
+    /// if its data is stored compressed.
+    pub stored_size: usize,
     pub pubkey: Pubkey,
 }
 
@@ -583,7 +590,7 @@ impl AccountFromStorage {
         &self.pubkey
     }
     pub fn stored_size(&self) -> usize {
-        aligned_stored_size(self.data_len as usize)
+        self.stored_size
     }
     pub fn data_len(&self) -> usize {
         self.data_len as usize
@@ -600,6 +607,7 @@ impl AccountFromStorage {
             ),
             pubkey: *account.pubkey(),
             data_len: account.data_len() as u64,
+            stored_size: account.stored_size(),
         }
     }
 }
@@ -663,12 +671,18 @@ pub struct AccountsDbConfig {
     pub hash_calculation_pubkey_bins: Option<usize>,
     pub test_skip_rewrites_but_include_in_bank_hash: bool,
     pub skip_initial_hash_calc: bool,
+    /// if true, always scan the storages when calculating the accounts hash instead of reusing
+    /// the accounts hash cache files from a previous calculation
+    pub skip_accounts_hash_cache: bool,
     pub exhaustively_verify_refcounts: bool,
     /// how to create ancient storages
     pub create_ancient_storage: CreateAncientStorage,
     pub partitioned_epoch_rewards_config: PartitionedEpochRewardsConfig,
     pub storage_access: StorageAccess,
     pub scan_filter_for_shrinking: ScanFilter,
+    /// if Some, account data of at least this many bytes is stored lz4 compressed in new append
+    /// vecs. This is a local storage concern; accounts hashes are over the uncompressed data.
+    pub append_vec_data_compression_threshold: Option<usize>,
     pub enable_experimental_accumulator_hash: bool,
     pub verify_experimental_accumulator_hash: bool,
     pub snapshots_use_experimental_accumulator_hash: bool,
@@ -1593,6 +1607,9 @@ pub struct AccountsDb {
     /// debug feature to scan every append vec and verify refcounts are equal
     exhaustively_verify_refcounts: bool,
 
+    /// always scan the storages instead of reusing accounts hash cache files
+    skip_accounts_hash_cache: bool,
+
     /// storage format to use for new storages
     accounts_file_provider: AccountsFileProvider,
 
@@ -1602,6 +1619,9 @@ pub struct AccountsDb {
     /// index scan filtering for shrinking
     scan_filter_for_shrinking: ScanFilter,
 
+    /// minimum data size of accounts stored compressed in new append vecs, if any
+    append_vec_data_compression_threshold: Option<usize>,
+
     /// this will live here until the feature for partitioned epoch rewards is activated.
     /// At that point, this and other code can be deleted.
     pub partitioned_epoch_rewards_config: PartitionedEpochRewardsConfig,
@@ -2038,10 +2058,13 @@ impl AccountsDb {
             write_cache_limit_bytes: accounts_db_config.write_cache_limit_bytes,
             partitioned_epoch_rewards_config: accounts_db_config.partitioned_epoch_rewards_config,
             exhaustively_verify_refcounts: accounts_db_config.exhaustively_verify_refcounts,
+            skip_accounts_hash_cache: accounts_db_config.skip_accounts_hash_cache,
             test_skip_rewrites_but_include_in_bank_hash: accounts_db_config
                 .test_skip_rewrites_but_include_in_bank_hash,
             storage_access: accounts_db_config.storage_access,
             scan_filter_for_shrinking: accounts_db_config.scan_filter_for_shrinking,
+            append_vec_data_compression_threshold: accounts_db_config
+                .append_vec_data_compression_threshold,
             is_experimental_accumulator_hash_enabled: accounts_db_config
                 .enable_experimental_accumulator_hash
                 .into(),
@@ -2119,13 +2142,17 @@ impl AccountsDb {
     }
 
     fn new_storage_entry(&self, slot: Slot, path: &Path, size: u64) -> AccountStorageEntry {
-        AccountStorageEntry::new(
+        let mut storage = AccountStorageEntry::new(
             path,
             slot,
             self.next_id(),
             size,
             self.accounts_file_provider,
-        )
+        );
+        if let AccountsFile::AppendVec(append_vec) = &mut storage.accounts {
+            append_vec.set_data_compression_threshold(self.append_vec_data_compression_threshold);
+        }
+        storage
     }
 
     /// Returns if the experimental accounts lattice hash is enabled
@@ -3564,6 +3591,7 @@ impl AccountsDb {
                 ),
                 pubkey: info.index_info.pubkey,
                 data_len: info.index_info.data_len,
+                stored_size: info.stored_size_aligned,
             });
         });
 
@@ -4793,6 +4821,36 @@ impl AccountsDb {
         Ok(())
     }
 
+    /// Like `scan_accounts`, but only visits the accounts in `range`
+    pub fn scan_accounts_in_range<F, R>(
+        &self,
+        ancestors: &Ancestors,
+        bank_id: BankId,
+        range: R,
+        mut scan_func: F,
+        config: &ScanConfig,
+    ) -> ScanResult<()>
+    where
+        F: FnMut(Option<(&Pubkey, AccountSharedData, Slot)>),
+        R: RangeBounds<Pubkey> + std::fmt::Debug,
+    {
+        // This can error out if the slots being scanned over are aborted
+        self.accounts_index.scan_accounts_in_range(
+            ancestors,
+            bank_id,
+            range,
+            |pubkey, (account_info, slot)| {
+                let account_slot = self
+                    .get_account_accessor(slot, pubkey, &account_info.storage_location())
+                    .get_loaded_account(|loaded_account| {
+                        (pubkey, loaded_account.take_account(), slot)
+                    });
+                scan_func(account_slot)
+            },
+            config,
+        )
+    }
+
     pub fn unchecked_scan_accounts<F>(
         &self,
         metric_name: &'static str,
@@ -4815,6 +4873,21 @@ impl AccountsDb {
         );
     }
 
+    /// Calls `scan_func` with every pubkey visible from `ancestors` whose latest
+    /// version has non-zero lamports, without loading the accounts themselves.
+    pub fn scan_all_pubkeys(&self, ancestors: &Ancestors, mut scan_func: impl FnMut(&Pubkey)) {
+        self.accounts_index.unchecked_scan_accounts(
+            "scan_all_pubkeys",
+            ancestors,
+            |pubkey, (account_info, _slot)| {
+                if !account_info.is_zero_lamport() {
+                    scan_func(pubkey);
+                }
+            },
+            &ScanConfig::default(),
+        );
+    }
+
     /// Only guaranteed to be safe when called from rent collection
     pub fn range_scan_accounts<F, R>(
         &self,
@@ -5613,6 +5686,7 @@ impl AccountsDb {
         store
     }
 
+    #[cfg_attr(feature = "dev-context-only-utils", qualifiers(pub))]
     fn create_and_insert_store(
         &self,
         slot: Slot,
@@ -8395,6 +8469,75 @@ impl AccountsDb {
         }
     }
 
+    /// Recomputes the ref count of every pubkey from the storages and compares it to the ref
+    /// count in the accounts index.
+    ///
+    /// A pubkey's ref count is the number of storages containing it, so any difference
+    /// indicates the index and storages have diverged. Each discrepancy is returned as
+    /// `(slot, pubkey, expected, actual)`, where `slot` is the newest slot the pubkey was found
+    /// in. This is a read-only check, intended for diagnostics.
+    pub fn verify_ref_counts(&self) -> Result<(), Vec<(Slot, Pubkey, RefCount, RefCount)>> {
+        // pubkey => (number of storages containing it, newest slot containing it)
+        let mut expected = HashMap::<Pubkey, (RefCount, Slot)>::default();
+        let (storages, _slots) = self.get_storages(..);
+        for storage in storages {
+            let slot = storage.slot();
+            let mut pubkeys = HashSet::new();
+            storage.accounts.scan_pubkeys(|pubkey| {
+                pubkeys.insert(*pubkey);
+            });
+            for pubkey in pubkeys {
+                let (ref_count, newest_slot) = expected.entry(pubkey).or_default();
+                *ref_count += 1;
+                *newest_slot = (*newest_slot).max(slot);
+            }
+        }
+
+        let mut mismatches = vec![];
+        for bin in self.accounts_index.account_maps.iter() {
+            for pubkey in bin.keys() {
+                let (expected_ref_count, storage_slot) =
+                    expected.remove(&pubkey).unwrap_or_default();
+                let mismatch = self.accounts_index.get_and_then(&pubkey, |entry| {
+                    let mismatch = entry.and_then(|entry| {
+                        let actual_ref_count = entry.ref_count();
+                        (actual_ref_count != expected_ref_count).then(|| {
+                            let index_slot = entry
+                                .slot_list
+                                .read()
+                                .unwrap()
+                                .iter()
+                                .map(|(slot, _account_info)| *slot)
+                                .max()
+                                .unwrap_or_default();
+                            (
+                                storage_slot.max(index_slot),
+                                pubkey,
+                                expected_ref_count,
+                                actual_ref_count,
+                            )
+                        })
+                    });
+                    (false, mismatch)
+                });
+                mismatches.extend(mismatch);
+            }
+        }
+        // whatever remains is in a storage, but not in the index at all
+        mismatches.extend(
+            expected
+                .into_iter()
+                .map(|(pubkey, (ref_count, slot))| (slot, pubkey, ref_count, 0)),
+        );
+
+        if mismatches.is_empty() {
+            Ok(())
+        } else {
+            mismatches.sort_unstable();
+            Err(mismatches)
+        }
+    }
+
     /// Returns storages for `requested_slots`
     pub fn get_storages(
         &self,
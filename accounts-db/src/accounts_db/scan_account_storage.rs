@@ -211,7 +211,7 @@ impl AccountsDb {
             .filter_map(|chunk| {
                 let range_this_chunk = splitter.get_slot_range(chunk)?;
 
-                let mut load_from_cache = true;
+                let mut load_from_cache = !self.skip_accounts_hash_cache;
                 let mut hasher = DefaultHasher::new();
                 bin_range.start.hash(&mut hasher);
                 bin_range.end.hash(&mut hasher);
@@ -248,7 +248,7 @@ impl AccountsDb {
                 );
                 if load_from_cache {
                     if let Ok(mapped_file) =
-                        cache_hash_data.get_file_reference_to_map_later(&file_name)
+                        cache_hash_data.get_verified_file_reference_to_map_later(&file_name)
                     {
                         return Some(ScanAccountStorageResult::CacheFileAlreadyExists(
                             mapped_file,
@@ -575,6 +575,102 @@ mod tests {
         );
     }
 
+    #[test]
+    fn test_accountsdb_scan_account_storage_no_bank_reuses_cache() {
+        solana_logger::setup();
+
+        let tf = crate::append_vec::test_utils::get_append_vec_path(
+            "test_accountsdb_scan_account_storage_no_bank_reuses_cache",
+        );
+        let (_temp_dirs, paths) = get_temp_accounts_paths(1).unwrap();
+        let slot_expected: Slot = 0;
+        let mut data = AccountStorageEntry::new(
+            &paths[0],
+            slot_expected,
+            0,
+            123,
+            AccountsFileProvider::AppendVec,
+        );
+        data.accounts = AccountsFile::AppendVec(AppendVec::new(&tf.path, true, 1024 * 1024));
+        let storage = Arc::new(data);
+        let pubkey = solana_pubkey::new_rand();
+        let acc = AccountSharedData::new(1, 48, AccountSharedData::default().owner());
+        append_single_account_with_default_hash(&storage, &pubkey, &acc, false, None);
+
+        let temp_dir = TempDir::new().unwrap();
+        let accounts_hash_cache_path = temp_dir.path().to_path_buf();
+        let calls = Arc::new(AtomicU64::new(0));
+        // scans the storage with a fresh `CacheHashData`, as each accounts hash calculation does
+        let scan = |accounts_db: &AccountsDb| {
+            let cache_hash_data = CacheHashData::new(
+                accounts_hash_cache_path.clone(),
+                CacheHashDeletionPolicy::AllUnused,
+            );
+            let test_scan = TestScan {
+                calls: calls.clone(),
+                pubkey,
+                slot_expected,
+                accum: Vec::default(),
+                current_slot: 0,
+                value_to_use_for_lamports: 1,
+            };
+            let result = accounts_db
+                .scan_account_storage_no_bank(
+                    &cache_hash_data,
+                    &CalcAccountsHashConfig::default(),
+                    &get_storage_refs(&[storage.clone()]),
+                    test_scan,
+                    &Range { start: 0, end: 1 },
+                    &mut HashStats::default(),
+                )
+                .iter()
+                .map(|file| file.map().unwrap().get_cache_hash_data().to_vec())
+                .collect::<Vec<_>>();
+            let hits = cache_hash_data.stats.hits.load(Ordering::Relaxed);
+            let corrupted = cache_hash_data
+                .stats
+                .corrupted_cache_files
+                .load(Ordering::Relaxed);
+            (result, hits, corrupted)
+        };
+        let mut accounts_db = AccountsDb::new_single_for_tests();
+
+        // the first scan populates the cache
+        let (expected, hits, _) = scan(&accounts_db);
+        assert_eq!(calls.load(Ordering::Relaxed), 1);
+        assert_eq!(hits, 0);
+
+        // the second scan reuses it, without scanning the storage
+        assert_eq!(scan(&accounts_db), (expected.clone(), 1, 0));
+        assert_eq!(calls.load(Ordering::Relaxed), 1);
+
+        // a corrupted cache file is detected, and the storage is scanned again
+        let cache_file = std::fs::read_dir(&accounts_hash_cache_path)
+            .unwrap()
+            .flatten()
+            .find(|entry| entry.path().is_file())
+            .unwrap()
+            .path();
+        let mut contents = std::fs::read(&cache_file).unwrap();
+        *contents.last_mut().unwrap() ^= 1;
+        std::fs::write(&cache_file, &contents).unwrap();
+        assert_eq!(scan(&accounts_db), (expected.clone(), 0, 1));
+        assert_eq!(calls.load(Ordering::Relaxed), 2);
+
+        // the cache can be disabled
+        accounts_db.skip_accounts_hash_cache = true;
+        assert_eq!(scan(&accounts_db), (expected, 0, 0));
+        assert_eq!(calls.load(Ordering::Relaxed), 3);
+        accounts_db.skip_accounts_hash_cache = false;
+
+        // rewriting the storage, as shrinking does, invalidates the cache
+        append_single_account_with_default_hash(&storage, &pubkey, &acc, false, None);
+        let (result, hits, _) = scan(&accounts_db);
+        assert_eq!(hits, 0);
+        assert_eq!(result[0].len(), 2);
+        assert_eq!(calls.load(Ordering::Relaxed), 5);
+    }
+
     #[test]
     fn test_accountsdb_scan_multiple_account_storage_no_bank_one_slot() {
         solana_logger::setup();
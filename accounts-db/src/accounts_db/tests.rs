@@ -213,12 +213,100 @@ fn test_generate_index_for_single_ref_zero_lamport_slot() {
     );
 }
 
+#[test]
+fn test_generate_index_serial_matches_parallel() {
+    let num_slots = 40;
+    let pubkeys = (0..20).map(|_| Pubkey::new_unique()).collect::<Vec<_>>();
+    let genesis_config = GenesisConfig::default();
+
+    // every pubkey is written in most slots, so the index has to resolve many
+    // versions of each account regardless of how slots are split across threads
+    let generate_index = |num_threads| {
+        let db = AccountsDb::new_single_for_tests();
+        for slot in 0..num_slots {
+            let accounts = pubkeys
+                .iter()
+                .enumerate()
+                .filter(|(i, _)| (*i as u64 + slot) % 3 != 0)
+                .map(|(i, pubkey)| {
+                    (
+                        pubkey,
+                        AccountSharedData::new(slot + i as u64, i, &Pubkey::default()),
+                    )
+                })
+                .collect::<Vec<_>>();
+            let data = accounts
+                .iter()
+                .map(|(pubkey, account)| (*pubkey, account))
+                .collect::<Vec<_>>();
+            let storage = db.create_and_insert_store(slot, 100_000, "test");
+            storage.accounts.append_accounts(&(slot, &data[..]), 0);
+        }
+        let thread_pool = rayon::ThreadPoolBuilder::new()
+            .num_threads(num_threads)
+            .build()
+            .unwrap();
+        let result = thread_pool.install(|| db.generate_index(None, false, &genesis_config, true));
+        (db, result)
+    };
+
+    let (serial_db, serial_result) = generate_index(1);
+    let (parallel_db, parallel_result) = generate_index(4);
+
+    assert_eq!(
+        serial_result.accounts_data_len,
+        parallel_result.accounts_data_len
+    );
+    for pubkey in &pubkeys {
+        let serial_entry = serial_db.accounts_index.get_cloned(pubkey).unwrap();
+        let parallel_entry = parallel_db.accounts_index.get_cloned(pubkey).unwrap();
+        assert_eq!(serial_entry.ref_count(), parallel_entry.ref_count());
+        let mut serial_slot_list = serial_entry.slot_list.read().unwrap().clone();
+        let mut parallel_slot_list = parallel_entry.slot_list.read().unwrap().clone();
+        serial_slot_list.sort_unstable_by_key(|(slot, _)| *slot);
+        parallel_slot_list.sort_unstable_by_key(|(slot, _)| *slot);
+        assert_eq!(serial_slot_list, parallel_slot_list);
+    }
+    for slot in 0..num_slots {
+        let serial_storage = serial_db.storage.get_slot_storage_entry(slot).unwrap();
+        let parallel_storage = parallel_db.storage.get_slot_storage_entry(slot).unwrap();
+        assert_eq!(serial_storage.count(), parallel_storage.count());
+        assert_eq!(serial_storage.alive_bytes(), parallel_storage.alive_bytes());
+    }
+
+    // The bank hash is made from the accounts hashes, which must match too, as
+    // must the accounts lt hash verified from the storages at startup
+    let max_slot = num_slots - 1;
+    let ancestors = linear_ancestors(num_slots);
+    let config = CalcAccountsHashConfig {
+        ancestors: Some(&ancestors),
+        ..CalcAccountsHashConfig::default()
+    };
+    let serial_accounts_hash = serial_db.calculate_accounts_hash_from_index(max_slot, &config);
+    let parallel_accounts_hash = parallel_db.calculate_accounts_hash_from_index(max_slot, &config);
+    assert_eq!(serial_accounts_hash, parallel_accounts_hash);
+    let serial_lt_hash =
+        serial_db.calculate_accounts_lt_hash_at_startup_from_index(&ancestors, max_slot);
+    let parallel_lt_hash =
+        parallel_db.calculate_accounts_lt_hash_at_startup_from_index(&ancestors, max_slot);
+    assert_eq!(serial_lt_hash, parallel_lt_hash);
+    let (parallel_storages, _slots) = parallel_db.get_storages(..);
+    assert_eq!(
+        parallel_db.calculate_accounts_lt_hash_at_startup_from_storages(
+            &parallel_storages,
+            &parallel_result.duplicates_lt_hash.unwrap(),
+        ),
+        serial_lt_hash,
+    );
+}
+
 fn generate_sample_account_from_storage(i: u8) -> AccountFromStorage {
     // offset has to be 8 byte aligned
     let offset = (i as usize) * std::mem::size_of::<u64>();
     AccountFromStorage {
         index_info: AccountInfo::new(StorageLocation::AppendVec(i as u32, offset), i as u64),
         data_len: i as u64,
+        stored_size: aligned_stored_size(i as usize),
         pubkey: Pubkey::new_from_array([i; 32]),
     }
 }
@@ -2205,6 +2293,98 @@ fn test_accountsdb_scan_accounts() {
     assert_eq!(accounts.len(), 2);
 }
 
+#[test]
+fn test_scan_all_pubkeys() {
+    let db = AccountsDb::new_single_for_tests();
+    let owner = Pubkey::default();
+    let unchanged = solana_pubkey::new_rand();
+    let updated = solana_pubkey::new_rand();
+    let removed = solana_pubkey::new_rand();
+    let created = solana_pubkey::new_rand();
+    let account = AccountSharedData::new(1, 0, &owner);
+    let zero_lamport_account = AccountSharedData::new(0, 0, &owner);
+
+    db.store_for_tests(
+        0,
+        &[
+            (&unchanged, &account),
+            (&updated, &account),
+            (&removed, &account),
+        ],
+    );
+    db.store_for_tests(
+        1,
+        &[
+            (&updated, &AccountSharedData::new(2, 0, &owner)),
+            (&removed, &zero_lamport_account),
+            (&created, &account),
+        ],
+    );
+
+    let scan_all_pubkeys = |ancestors: &Ancestors| {
+        let mut pubkeys = HashSet::new();
+        db.scan_all_pubkeys(ancestors, |pubkey| assert!(pubkeys.insert(*pubkey)));
+        pubkeys
+    };
+
+    let ancestors = vec![(0, 0)].into_iter().collect();
+    assert_eq!(
+        scan_all_pubkeys(&ancestors),
+        HashSet::from([unchanged, updated, removed])
+    );
+
+    // The zero lamport store in slot 1 shadows `removed` from slot 0
+    let ancestors = vec![(1, 1), (0, 0)].into_iter().collect();
+    let live_pubkeys = HashSet::from([unchanged, updated, created]);
+    assert_eq!(scan_all_pubkeys(&ancestors), live_pubkeys);
+
+    // The visited pubkeys match those with accounts loaded by a full scan
+    let mut loaded_pubkeys = HashSet::new();
+    db.unchecked_scan_accounts(
+        "",
+        &ancestors,
+        |pubkey, account, _| {
+            if account.lamports() != 0 {
+                loaded_pubkeys.insert(*pubkey);
+            }
+        },
+        &ScanConfig::default(),
+    );
+    assert_eq!(loaded_pubkeys, live_pubkeys);
+}
+
+#[test]
+fn test_verify_ref_counts() {
+    let db = AccountsDb::new_single_for_tests();
+    let owner = Pubkey::default();
+    let pubkey0 = solana_pubkey::new_rand();
+    let pubkey1 = solana_pubkey::new_rand();
+    let account = AccountSharedData::new(1, 0, &owner);
+
+    db.store_for_tests(0, &[(&pubkey0, &account), (&pubkey1, &account)]);
+    db.add_root_and_flush_write_cache(0);
+    db.store_for_tests(1, &[(&pubkey1, &account)]);
+    db.add_root_and_flush_write_cache(1);
+    // unflushed accounts are not ref counted
+    db.store_for_tests(2, &[(&pubkey0, &account)]);
+    assert_eq!(db.ref_count_for_pubkey(&pubkey0), 1);
+    assert_eq!(db.ref_count_for_pubkey(&pubkey1), 2);
+    assert_eq!(db.verify_ref_counts(), Ok(()));
+
+    // corrupt the ref counts in the index
+    db.accounts_index.get_and_then(&pubkey0, |entry| {
+        entry.unwrap().unref();
+        (false, ())
+    });
+    db.accounts_index.get_and_then(&pubkey1, |entry| {
+        entry.unwrap().addref();
+        (false, ())
+    });
+    let mut expected = vec![(2, pubkey0, 1, 0), (1, pubkey1, 2, 3)];
+    expected.sort_unstable();
+    assert_eq!(db.verify_ref_counts(), Err(expected));
+}
+
 #[test]
 fn test_cleanup_key_not_removed() {
     solana_logger::setup();
@@ -8193,3 +8373,136 @@ fn test_clean_old_storages_with_reclaims_unrooted() {
     assert_eq!(slot_list.len(), slots.len());
     assert!(slot_list.iter().map(|(slot, _)| slot).eq(slots.iter()));
 }
+
+#[test]
+fn test_append_vec_data_compression_accounts_hash() {
+    let mut rng = thread_rng();
+    let owner = Pubkey::new_unique();
+    let accounts: Vec<_> = [0, 100, 4096, 64 * 1024]
+        .into_iter()
+        .flat_map(|data_len| {
+            let compressible: Vec<u8> = (0..data_len).map(|i| (i / 100) as u8).collect();
+            let random: Vec<u8> = (0..data_len).map(|_| rng.gen()).collect();
+            [compressible, random]
+        })
+        .map(|data| {
+            let mut account = AccountSharedData::new(1_000_000, 0, &owner);
+            account.set_data(data);
+            (Pubkey::new_unique(), account)
+        })
+        .collect();
+    let accounts_refs: Vec<_> = accounts
+        .iter()
+        .map(|(pubkey, account)| (pubkey, account))
+        .collect();
+
+    let slot = 1;
+    let dbs: Vec<_> = [None, Some(1024)]
+        .into_iter()
+        .map(|threshold| {
+            let db = AccountsDb::new_with_config(
+                Vec::new(),
+                Some(AccountsDbConfig {
+                    append_vec_data_compression_threshold: threshold,
+                    ..ACCOUNTS_DB_CONFIG_FOR_TESTING
+                }),
+                None,
+                Arc::default(),
+            );
+            db.store_for_tests(slot, &accounts_refs);
+            db.calculate_accounts_delta_hash(slot);
+            db.add_root_and_flush_write_cache(slot);
+            db
+        })
+        .collect();
+
+    // the compressible accounts take less space in storage
+    let storage_len = |db: &AccountsDb| {
+        db.storage
+            .get_slot_storage_entry(slot)
+            .unwrap()
+            .accounts
+            .len()
+    };
+    assert!(storage_len(&dbs[1]) < storage_len(&dbs[0]));
+
+    // but load and hash the same
+    let ancestors = linear_ancestors(slot);
+    let hashes: Vec<_> = dbs
+        .iter()
+        .map(|db| db.update_accounts_hash_for_tests(slot, &ancestors, true, true))
+        .collect();
+    assert_eq!(hashes[0], hashes[1]);
+    for db in &dbs {
+        for (pubkey, account) in &accounts {
+            assert_eq!(
+                db.load_without_fixed_root(&ancestors, pubkey),
+                Some((account.clone(), slot))
+            );
+        }
+    }
+}
+
+#[test]
+fn test_shrink_append_vec_compressed_data() {
+    let mut db = AccountsDb::new_with_config(
+        Vec::new(),
+        Some(AccountsDbConfig {
+            append_vec_data_compression_threshold: Some(1024),
+            ..ACCOUNTS_DB_CONFIG_FOR_TESTING
+        }),
+        None,
+        Arc::default(),
+    );
+    let owner = Pubkey::new_unique();
+    let accounts: Vec<_> = (0..3)
+        .map(|_| {
+            let mut account = AccountSharedData::new(1_000_000, 0, &owner);
+            account.set_data(vec![7; 64 * 1024]);
+            (Pubkey::new_unique(), account)
+        })
+        .collect();
+    let accounts_refs: Vec<_> = accounts
+        .iter()
+        .map(|(pubkey, account)| (pubkey, account))
+        .collect();
+    let slot = 1;
+    db.store_for_tests(slot, &accounts_refs);
+    db.calculate_accounts_delta_hash(slot);
+    db.add_root_and_flush_write_cache(slot);
+
+    // stored sizes are the compressed sizes, which alive bytes are tracked with
+    let storage = db.storage.get_slot_storage_entry(slot).unwrap();
+    assert!(storage.accounts.has_compressed_data());
+    let unique_accounts = db.get_unique_accounts_from_storage(&storage);
+    let stored_size = unique_accounts.stored_accounts[0].stored_size();
+    assert!(stored_size < aligned_stored_size(64 * 1024));
+    assert!(unique_accounts
+        .stored_accounts
+        .iter()
+        .all(|account| account.stored_size() == stored_size));
+    assert_eq!(storage.alive_bytes(), accounts.len() * stored_size);
+
+    // the first account is dead in `slot` once it is updated in a later root
+    db.store_for_tests(slot + 1, &accounts_refs[..1]);
+    db.calculate_accounts_delta_hash(slot + 1);
+    db.add_root_and_flush_write_cache(slot + 1);
+    db.clean_accounts_for_tests();
+    assert_eq!(storage.alive_bytes(), 2 * stored_size);
+
+    // shrinking keeps the accounts compressed, even with compression since turned off,
+    // so they fit in the new storage sized from the stored sizes
+    db.append_vec_data_compression_threshold = None;
+    db.shrink_slot_forced(slot);
+    let shrunk_storage = db.storage.get_slot_storage_entry(slot).unwrap();
+    assert_ne!(shrunk_storage.id(), storage.id());
+    assert!(shrunk_storage.accounts.has_compressed_data());
+    assert_eq!(shrunk_storage.alive_bytes(), 2 * stored_size);
+    let ancestors = linear_ancestors(slot + 1);
+    for (pubkey, account) in &accounts[1..] {
+        assert_eq!(
+            db.load_without_fixed_root(&ancestors, pubkey),
+            Some((account.clone(), slot))
+        );
+    }
+}
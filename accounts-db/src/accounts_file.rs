@@ -149,6 +149,15 @@ impl AccountsFile {
         }
     }
 
+    /// Returns true if any account data in this file is stored compressed.
+    /// Only append vecs compress account data.
+    pub fn has_compressed_data(&self) -> bool {
+        match self {
+            Self::AppendVec(av) => av.has_compressed_data(),
+            Self::TieredStorage(_) => false,
+        }
+    }
+
     pub fn file_name(slot: Slot, id: AccountsFileId) -> String {
         format!("{slot}.{id}")
     }
@@ -17,6 +17,7 @@ use {
         iter::{IntoParallelIterator, ParallelIterator},
         ThreadPool,
     },
+    solana_inline_spl::token_programs,
     solana_measure::measure::Measure,
     solana_sdk::{
         account::ReadableAccount,
@@ -54,6 +55,7 @@ pub const ACCOUNTS_INDEX_CONFIG_FOR_TESTING: AccountsIndexConfig = AccountsIndex
     index_limit_mb: IndexLimitMb::Unlimited,
     ages_to_stay_in_cache: None,
     scan_results_limit_bytes: None,
+    secondary_index_entry_soft_limit: None,
     started_from_validator: false,
 };
 pub const ACCOUNTS_INDEX_CONFIG_FOR_BENCHMARKS: AccountsIndexConfig = AccountsIndexConfig {
@@ -63,6 +65,7 @@ pub const ACCOUNTS_INDEX_CONFIG_FOR_BENCHMARKS: AccountsIndexConfig = AccountsIn
     index_limit_mb: IndexLimitMb::Unlimited,
     ages_to_stay_in_cache: None,
     scan_results_limit_bytes: None,
+    secondary_index_entry_soft_limit: None,
     started_from_validator: false,
 };
 pub type ScanResult<T> = Result<T, ScanError>;
@@ -114,6 +117,8 @@ pub struct ScanConfig {
 
     /// true to allow return of all matching items and allow them to be unsorted.
     /// This is more efficient.
+    /// When false, scans visit accounts in ascending pubkey order, whether they
+    /// are served by the primary index or by a secondary index.
     pub collect_all_unsorted: bool,
 }
 
@@ -159,6 +164,17 @@ impl ScanConfig {
     }
 }
 
+/// Restricts a scan to one page of its results: the `limit` matching accounts
+/// with the smallest pubkeys greater than `after`.
+///
+/// Pages are ordered by pubkey no matter which index served the scan, so the last
+/// pubkey of a page can be passed as `after` to continue from it.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ScanPage {
+    pub after: Option<Pubkey>,
+    pub limit: usize,
+}
+
 pub(crate) type AccountMapEntry<T> = Arc<AccountMapEntryInner<T>>;
 
 pub trait IsCached {
@@ -224,6 +240,9 @@ pub struct AccountsIndexConfig {
     pub index_limit_mb: IndexLimitMb,
     pub ages_to_stay_in_cache: Option<Age>,
     pub scan_results_limit_bytes: Option<usize>,
+    /// Number of accounts under a single secondary index key, e.g. a token owner, above
+    /// which the key is logged and reported. Keys are still indexed in full.
+    pub secondary_index_entry_soft_limit: Option<usize>,
     /// true if the accounts index is being created as a result of being started as a validator (as opposed to test, etc.)
     pub started_from_validator: bool,
 }
@@ -711,6 +730,9 @@ impl<T: IndexValue, U: DiskIndexValue + From<T> + Into<T>> AccountsIndex<T, U> {
         let scan_results_limit_bytes = config
             .as_ref()
             .and_then(|config| config.scan_results_limit_bytes);
+        let secondary_index_entry_soft_limit = config
+            .as_ref()
+            .and_then(|config| config.secondary_index_entry_soft_limit);
         let (account_maps, bin_calculator, storage) = Self::allocate_accounts_index(config, exit);
         Self {
             purge_older_root_entries_one_slot_list: AtomicUsize::default(),
@@ -718,12 +740,15 @@ impl<T: IndexValue, U: DiskIndexValue + From<T> + Into<T>> AccountsIndex<T, U> {
             bin_calculator,
             program_id_index: SecondaryIndex::<RwLockSecondaryIndexEntry>::new(
                 "program_id_index_stats",
+                secondary_index_entry_soft_limit,
             ),
             spl_token_mint_index: SecondaryIndex::<RwLockSecondaryIndexEntry>::new(
                 "spl_token_mint_index_stats",
+                secondary_index_entry_soft_limit,
             ),
             spl_token_owner_index: SecondaryIndex::<RwLockSecondaryIndexEntry>::new(
                 "spl_token_owner_index_stats",
+                secondary_index_entry_soft_limit,
             ),
             roots_tracker: RwLock::<RootsTracker>::default(),
             ongoing_scan_roots: RwLock::<BTreeMap<Slot, u64>>::default(),
@@ -1099,7 +1124,11 @@ impl<T: IndexValue, U: DiskIndexValue + From<T> + Into<T>> AccountsIndex<T, U> {
     ) where
         F: FnMut(&Pubkey, (&T, Slot)),
     {
-        for pubkey in index.get(index_key) {
+        let mut pubkeys = index.get(index_key);
+        if !config.collect_all_unsorted {
+            pubkeys.sort_unstable();
+        }
+        for pubkey in pubkeys {
             if config.is_aborted() {
                 break;
             }
@@ -1237,6 +1266,30 @@ impl<T: IndexValue, U: DiskIndexValue + From<T> + Into<T>> AccountsIndex<T, U> {
         )
     }
 
+    /// call func with every pubkey and index in `range` visible from a given set of ancestors
+    pub(crate) fn scan_accounts_in_range<F, R>(
+        &self,
+        ancestors: &Ancestors,
+        scan_bank_id: BankId,
+        range: R,
+        func: F,
+        config: &ScanConfig,
+    ) -> Result<(), ScanError>
+    where
+        F: FnMut(&Pubkey, (&T, Slot)),
+        R: RangeBounds<Pubkey> + std::fmt::Debug,
+    {
+        // Pass "" not to log metrics, so RPC doesn't get spammy
+        self.do_checked_scan_accounts(
+            "",
+            ancestors,
+            scan_bank_id,
+            func,
+            ScanTypes::Unindexed(Some(range)),
+            config,
+        )
+    }
+
     pub(crate) fn unchecked_scan_accounts<F>(
         &self,
         metric_name: &'static str,
@@ -1551,28 +1604,28 @@ impl<T: IndexValue, U: DiskIndexValue + From<T> + Into<T>> AccountsIndex<T, U> {
         max_root
     }
 
-    fn update_spl_token_secondary_indexes<G: solana_inline_spl::token::GenericTokenAccount>(
+    fn update_spl_token_secondary_indexes(
         &self,
-        token_id: &Pubkey,
         pubkey: &Pubkey,
         account_owner: &Pubkey,
         account_data: &[u8],
         account_indexes: &AccountSecondaryIndexes,
     ) {
-        if *account_owner == *token_id {
-            if account_indexes.contains(&AccountIndex::SplTokenOwner) {
-                if let Some(owner_key) = G::unpack_account_owner(account_data) {
-                    if account_indexes.include_key(owner_key) {
-                        self.spl_token_owner_index.insert(owner_key, pubkey);
-                    }
+        let Some(token_program) = token_programs::token_program(account_owner) else {
+            return;
+        };
+        if account_indexes.contains(&AccountIndex::SplTokenOwner) {
+            if let Some(owner_key) = token_program.unpack_account_owner(account_data) {
+                if account_indexes.include_key(owner_key) {
+                    self.spl_token_owner_index.insert(owner_key, pubkey);
                 }
             }
+        }
 
-            if account_indexes.contains(&AccountIndex::SplTokenMint) {
-                if let Some(mint_key) = G::unpack_account_mint(account_data) {
-                    if account_indexes.include_key(mint_key) {
-                        self.spl_token_mint_index.insert(mint_key, pubkey);
-                    }
+        if account_indexes.contains(&AccountIndex::SplTokenMint) {
+            if let Some(mint_key) = token_program.unpack_account_mint(account_data) {
+                if account_indexes.include_key(mint_key) {
+                    self.spl_token_mint_index.insert(mint_key, pubkey);
                 }
             }
         }
@@ -1594,6 +1647,14 @@ impl<T: IndexValue, U: DiskIndexValue + From<T> + Into<T>> AccountsIndex<T, U> {
         }
     }
 
+    pub fn get_secondary_index_usage(&self, index: &AccountIndex) -> SecondaryIndexUsage {
+        match index {
+            AccountIndex::ProgramId => self.program_id_index.usage(),
+            AccountIndex::SplTokenOwner => self.spl_token_owner_index.usage(),
+            AccountIndex::SplTokenMint => self.spl_token_mint_index.usage(),
+        }
+    }
+
     /// log any secondary index counts, if non-zero
     pub(crate) fn log_secondary_indexes(&self) {
         if !self.program_id_index.index.is_empty() {
@@ -1642,15 +1703,11 @@ impl<T: IndexValue, U: DiskIndexValue + From<T> + Into<T>> AccountsIndex<T, U> {
         // (as persisted tombstone for snapshots). This will then ultimately be
         // filtered out by post-scan filters, like in `get_filtered_spl_token_accounts_by_owner()`.
 
-        self.update_spl_token_secondary_indexes::<solana_inline_spl::token::Account>(
-            &solana_inline_spl::token::id(),
-            pubkey,
-            account_owner,
-            account_data,
-            account_indexes,
-        );
-        self.update_spl_token_secondary_indexes::<solana_inline_spl::token_2022::Account>(
-            &solana_inline_spl::token_2022::id(),
+        // Token accounts are indexed for every program in `KNOWN_TOKEN_PROGRAMS`. Since this
+        // runs on every store, and for every account when the index is generated at startup,
+        // accounts owned by a newly recognized token program are indexed on their first store
+        // after the node is upgraded, without a separate migration pass.
+        self.update_spl_token_secondary_indexes(
             pubkey,
             account_owner,
             account_data,
@@ -2091,6 +2148,7 @@ pub mod tests {
 
     const SPL_TOKENS: &[Pubkey] = &[
         solana_inline_spl::token::id(),
+        solana_inline_spl::token::program_v3_4_0::id(),
         solana_inline_spl::token_2022::id(),
     ];
 
@@ -3726,6 +3784,80 @@ pub mod tests {
         }
     }
 
+    #[test]
+    fn test_secondary_index_entry_soft_limit() {
+        let (key_start, key_end, secondary_indexes) =
+            create_spl_token_owner_secondary_index_state();
+        let token_account = |owner: &Pubkey| {
+            let mut account_data = make_empty_token_account_data();
+            account_data[key_start..key_end].clone_from_slice(&owner.to_bytes());
+            AccountSharedData::create(0, account_data, SPL_TOKENS[0], false, 0)
+        };
+        let farm_owner = Pubkey::new_unique();
+        let owner = Pubkey::new_unique();
+
+        for exclude_farm_owner in [false, true] {
+            let mut secondary_indexes = secondary_indexes.clone();
+            if exclude_farm_owner {
+                secondary_indexes.keys = Some(AccountSecondaryIndexesIncludeExclude {
+                    keys: HashSet::from([farm_owner]),
+                    exclude: true,
+                });
+            }
+            let index = AccountsIndex::<bool, bool>::new(
+                Some(AccountsIndexConfig {
+                    secondary_index_entry_soft_limit: Some(10),
+                    ..ACCOUNTS_INDEX_CONFIG_FOR_TESTING
+                }),
+                Arc::default(),
+            );
+            for _ in 0..25 {
+                index.update_secondary_indexes(
+                    &Pubkey::new_unique(),
+                    &token_account(&farm_owner),
+                    &secondary_indexes,
+                );
+            }
+            index.update_secondary_indexes(
+                &Pubkey::new_unique(),
+                &token_account(&owner),
+                &secondary_indexes,
+            );
+
+            let usage = index.get_secondary_index_usage(&AccountIndex::SplTokenOwner);
+            assert_eq!(
+                index.get_index_key_size(&AccountIndex::SplTokenOwner, &owner),
+                Some(1)
+            );
+            if exclude_farm_owner {
+                // The excluded key is not indexed at all, so scans for it fall back to
+                // scanning all accounts
+                assert_eq!(
+                    index.get_index_key_size(&AccountIndex::SplTokenOwner, &farm_owner),
+                    None
+                );
+                assert_eq!(usage.num_keys, 1);
+                assert_eq!(usage.num_inner_keys, 1);
+                assert_eq!(usage.num_soft_limit_warnings, 0);
+            } else {
+                // The key over the soft limit is still indexed in full, and reported at
+                // 11 and 21 accounts
+                assert_eq!(
+                    index.get_index_key_size(&AccountIndex::SplTokenOwner, &farm_owner),
+                    Some(25)
+                );
+                assert_eq!(usage.num_keys, 2);
+                assert_eq!(usage.num_inner_keys, 26);
+                assert_eq!(usage.num_soft_limit_warnings, 2);
+            }
+            assert!(usage.memory_estimate_bytes > 0);
+            assert_eq!(
+                index.get_secondary_index_usage(&AccountIndex::SplTokenMint),
+                SecondaryIndexUsage::default()
+            );
+        }
+    }
+
     fn run_test_secondary_indexes_same_slot_and_forks<
         SecondaryIndexEntryType: SecondaryIndexEntry + Default + Sync + Send,
     >(
@@ -15,19 +15,21 @@ use {
         accounts_index::ZeroLamport,
         buffered_reader::{BufferedReader, BufferedReaderStatus},
         file_io::read_into_buffer,
-        storable_accounts::StorableAccounts,
+        storable_accounts::{AccountForStorage, StorableAccounts},
         u64_align,
     },
     log::*,
     memmap2::MmapMut,
     solana_sdk::{
         account::{AccountSharedData, ReadableAccount, WritableAccount},
+        clock::Slot,
         hash::Hash,
         pubkey::Pubkey,
         stake_history::Epoch,
         system_instruction::MAX_PERMITTED_DATA_LENGTH,
     },
     std::{
+        borrow::Cow,
         convert::TryFrom,
         fs::{remove_file, File, OpenOptions},
         io::{Seek, SeekFrom, Write},
@@ -67,6 +69,46 @@ pub fn aligned_stored_size(data_len: usize) -> usize {
 
 pub const MAXIMUM_APPEND_VEC_FILE_SIZE: u64 = 16 * 1024 * 1024 * 1024; // 16 GiB
 
+/// Set in `StoredMeta::write_version_obsolete` if the account data was stored lz4 compressed.
+/// The remaining bits then hold the length of the uncompressed data, while `data_len` is the
+/// length of the compressed data. Write versions are no longer stored, and the ones stored
+/// in the past never came close to this bit.
+const COMPRESSED_DATA_FLAG: u64 = 1 << 63;
+
+/// Returns true if the account data of `stored_meta` was stored compressed
+fn is_data_compressed(stored_meta: &StoredMeta) -> bool {
+    stored_meta.write_version_obsolete & COMPRESSED_DATA_FLAG != 0
+}
+
+/// Returns the length of the account data of `stored_meta`, once decompressed
+fn uncompressed_data_len(stored_meta: &StoredMeta) -> u64 {
+    if is_data_compressed(stored_meta) {
+        stored_meta.write_version_obsolete & !COMPRESSED_DATA_FLAG
+    } else {
+        stored_meta.data_len
+    }
+}
+
+/// Returns the account data of `stored_meta`, decompressing `data` if it was stored compressed.
+/// Returns None if compressed data does not decompress to its original length.
+fn decompress_data<'a>(stored_meta: &StoredMeta, data: &'a [u8]) -> Option<Cow<'a, [u8]>> {
+    if !is_data_compressed(stored_meta) {
+        return Some(Cow::Borrowed(data));
+    }
+    let data_len = uncompressed_data_len(stored_meta);
+    if data_len > MAX_PERMITTED_DATA_LENGTH {
+        return None;
+    }
+    let data = lz4::block::decompress(data, Some(data_len as i32)).ok()?;
+    (data.len() as u64 == data_len).then_some(Cow::Owned(data))
+}
+
+/// Returns `data` lz4 compressed, or None if compressing does not save any space
+fn compress_data(data: &[u8]) -> Option<Vec<u8>> {
+    let compressed = lz4::block::compress(data, None, false).ok()?;
+    (aligned_stored_size(compressed.len()) < aligned_stored_size(data.len())).then_some(compressed)
+}
+
 #[derive(Error, Debug)]
 /// An enum for AppendVec related errors.
 pub enum AppendVecError {
@@ -81,6 +123,9 @@ pub enum AppendVecError {
 
     #[error("offset ({0}) is larger than file size ({1})")]
     OffsetOutOfBounds(usize, usize),
+
+    #[error("offset ({0}) is not aligned to the start of an account")]
+    MisalignedOffset(usize),
 }
 
 /// A slice whose contents are known to be valid.
@@ -138,7 +183,8 @@ impl<'append_vec> AppendVecStoredAccountMeta<'append_vec> {
     }
 
     pub fn data_len(&self) -> u64 {
-        self.meta.data_len
+        // `meta.data_len` is the stored length, which differs for compressed data
+        self.data.len() as u64
     }
 
     pub fn meta(&self) -> &StoredMeta {
@@ -266,6 +312,12 @@ pub struct AppendVec {
 
     /// if true, remove file when dropped
     remove_file_on_drop: AtomicBool,
+
+    /// if Some, account data of at least this many bytes is stored compressed
+    data_compression_threshold: Option<usize>,
+
+    /// true if any entry is known to hold compressed data, from appending it or from `scan_index`
+    has_compressed_data: AtomicBool,
 }
 
 const PAGE_SIZE: u64 = 4 * 1024;
@@ -385,9 +437,25 @@ impl AppendVec {
             current_len: AtomicUsize::new(initial_len),
             file_size: size as u64,
             remove_file_on_drop: AtomicBool::new(true),
+            data_compression_threshold: None,
+            has_compressed_data: AtomicBool::new(false),
         }
     }
 
+    /// Stores the data of subsequently appended accounts lz4 compressed if it is at least
+    /// `threshold` bytes long, or never if `threshold` is None.
+    /// Accounts are loaded the same either way; compressed data is decompressed on load.
+    pub fn set_data_compression_threshold(&mut self, threshold: Option<usize>) {
+        self.data_compression_threshold = threshold;
+    }
+
+    /// Returns true if any account data in this append vec is stored compressed.
+    /// Storages loaded from disk only know once `scan_index` has run over them, as generating
+    /// the index does.
+    pub fn has_compressed_data(&self) -> bool {
+        self.has_compressed_data.load(Ordering::Relaxed)
+    }
+
     fn sanitize_len_and_size(current_len: usize, file_size: usize) -> Result<()> {
         if file_size == 0 {
             Err(AccountsFileError::AppendVecError(
@@ -533,6 +601,8 @@ impl AppendVec {
                 current_len: AtomicUsize::new(current_len),
                 file_size,
                 remove_file_on_drop: AtomicBool::new(true),
+                data_compression_threshold: None,
+                has_compressed_data: AtomicBool::new(false),
             });
         }
 
@@ -556,6 +626,8 @@ impl AppendVec {
             current_len: AtomicUsize::new(current_len),
             file_size,
             remove_file_on_drop: AtomicBool::new(true),
+            data_compression_threshold: None,
+            has_compressed_data: AtomicBool::new(false),
         })
     }
 
@@ -683,12 +755,13 @@ impl AppendVec {
                 let (account_meta, next): (&AccountMeta, _) = Self::get_type(slice, next)?;
                 let (hash, next): (&AccountHash, _) = Self::get_type(slice, next)?;
                 let (data, next) = Self::get_slice(slice, next, meta.data_len as usize)?;
+                let data = decompress_data(meta, data)?;
                 let stored_size = next - offset;
                 Some(callback(StoredAccountMeta::AppendVec(
                     AppendVecStoredAccountMeta {
                         meta,
                         account_meta,
-                        data,
+                        data: &data,
                         offset,
                         stored_size,
                         hash,
@@ -708,11 +781,12 @@ impl AppendVec {
                 Some(if remaining_bytes_for_data >= data_len as usize {
                     // we already read enough data to load this account
                     let (data, next) = Self::get_slice(valid_bytes, next, meta.data_len as usize)?;
+                    let data = decompress_data(meta, data)?;
                     let stored_size = next;
                     let account = StoredAccountMeta::AppendVec(AppendVecStoredAccountMeta {
                         meta,
                         account_meta,
-                        data,
+                        data: &data,
                         offset,
                         stored_size,
                         hash,
@@ -730,11 +804,12 @@ impl AppendVec {
                         // eof or otherwise couldn't read all the data
                         return None;
                     }
+                    let data = decompress_data(meta, &data)?;
                     let stored_size = aligned_stored_size(data_len as usize);
                     let account = StoredAccountMeta::AppendVec(AppendVecStoredAccountMeta {
                         meta,
                         account_meta,
-                        data: &data[..],
+                        data: &data,
                         offset,
                         stored_size,
                         hash,
@@ -766,11 +841,12 @@ impl AppendVec {
                 Some(if remaining_bytes_for_data >= data_len as usize {
                     // we already read enough data to load this account
                     let (data, next) = Self::get_slice(valid_bytes, next, meta.data_len as usize)?;
+                    let data = decompress_data(meta, data)?;
                     let stored_size = next;
                     let account = StoredAccountMeta::AppendVec(AppendVecStoredAccountMeta {
                         meta,
                         account_meta,
-                        data,
+                        data: &data,
                         offset,
                         stored_size,
                         hash,
@@ -788,6 +864,11 @@ impl AppendVec {
                         // eof or otherwise couldn't read all the data
                         return None;
                     }
+                    let data = if is_data_compressed(meta) {
+                        decompress_data(meta, &data)?.into_owned()
+                    } else {
+                        data
+                    };
                     AccountSharedData::create(
                         account_meta.lamports,
                         data,
@@ -900,13 +981,16 @@ impl AppendVec {
                         // data doesn't fit, so don't include this account
                         break;
                     }
+                    if is_data_compressed(stored_meta) {
+                        self.has_compressed_data.store(true, Ordering::Relaxed);
+                    }
                     callback(IndexInfo {
                         index_info: {
                             IndexInfoInner {
                                 pubkey: stored_meta.pubkey,
                                 lamports: account_meta.lamports,
                                 offset,
-                                data_len: stored_meta.data_len,
+                                data_len: uncompressed_data_len(stored_meta),
                                 executable: account_meta.executable,
                                 rent_epoch: account_meta.rent_epoch,
                             }
@@ -933,13 +1017,16 @@ impl AppendVec {
                         // data doesn't fit, so don't include this account
                         break;
                     }
+                    if is_data_compressed(stored_meta) {
+                        self.has_compressed_data.store(true, Ordering::Relaxed);
+                    }
                     callback(IndexInfo {
                         index_info: {
                             IndexInfoInner {
                                 pubkey: stored_meta.pubkey,
                                 lamports: account_meta.lamports,
                                 offset,
-                                data_len: stored_meta.data_len,
+                                data_len: uncompressed_data_len(stored_meta),
                                 executable: account_meta.executable,
                                 rent_epoch: account_meta.rent_epoch,
                             }
@@ -986,11 +1073,15 @@ impl AppendVec {
                     if bytes_subset.len() - next >= data_len as usize {
                         // we already read enough data to load this account
                         let data = &bytes_subset.0[next..(next + data_len as usize)];
+                        let Some(data) = decompress_data(meta, data) else {
+                            // corrupt compressed data, so stop like on any other invalid entry
+                            break;
+                        };
                         let stored_size = u64_align!(next + (data_len as usize));
                         let account = StoredAccountMeta::AppendVec(AppendVecStoredAccountMeta {
                             meta,
                             account_meta,
-                            data,
+                            data: &data,
                             offset,
                             stored_size,
                             hash,
@@ -1125,6 +1216,25 @@ impl AppendVec {
                 break;
             }
             accounts.account_default_if_zero_lamport(i, |account| {
+                // Accounts rewritten from an append vec, as by shrink or ancient packing, keep
+                // the encoding they were stored with. Their stored sizes are what the rewrite
+                // was planned with, and lz4 reproduces them exactly.
+                let compress = match account {
+                    AccountForStorage::StoredAccountMeta(StoredAccountMeta::AppendVec(stored)) => {
+                        is_data_compressed(stored.meta)
+                    }
+                    _ => self
+                        .data_compression_threshold
+                        .is_some_and(|threshold| account.data().len() >= threshold),
+                };
+                let compressed_data = compress.then(|| compress_data(account.data())).flatten();
+                let (data, write_version_obsolete) = match &compressed_data {
+                    Some(compressed_data) => (
+                        compressed_data.as_slice(),
+                        COMPRESSED_DATA_FLAG | account.data().len() as u64,
+                    ),
+                    None => (account.data(), 0),
+                };
                 let account_meta = AccountMeta {
                     lamports: account.lamports(),
                     owner: *account.owner(),
@@ -1134,13 +1244,13 @@ impl AppendVec {
 
                 let stored_meta = StoredMeta {
                     pubkey: *account.pubkey(),
-                    data_len: account.data().len() as u64,
-                    write_version_obsolete: 0,
+                    data_len: data.len() as u64,
+                    write_version_obsolete,
                 };
                 let stored_meta_ptr = ptr::from_ref(&stored_meta).cast();
                 let account_meta_ptr = ptr::from_ref(&account_meta).cast();
                 let hash_ptr = bytemuck::bytes_of(&default_hash).as_ptr();
-                let data_ptr = account.data().as_ptr();
+                let data_ptr = data.as_ptr();
                 let ptrs = [
                     (stored_meta_ptr, mem::size_of::<StoredMeta>()),
                     (account_meta_ptr, mem::size_of::<AccountMeta>()),
@@ -1148,6 +1258,9 @@ impl AppendVec {
                     (data_ptr, stored_meta.data_len as usize),
                 ];
                 if let Some(start_offset) = self.append_ptrs_locked(&mut offset, &ptrs) {
+                    if compressed_data.is_some() {
+                        self.has_compressed_data.store(true, Ordering::Relaxed);
+                    }
                     offsets.push(start_offset)
                 } else {
                     stop = true;
@@ -1185,6 +1298,49 @@ impl AppendVec {
         })
     }
 
+    /// Copy only the accounts at `live_offsets` into a new append vec at `path`.
+    /// Dead accounts, i.e. those at any other offset, are dropped.
+    /// Returns the new append vec and the number of bytes saved by compacting.
+    /// Fails if any of `live_offsets` is not the start of an account in `self`.
+    pub fn compact(
+        &self,
+        live_offsets: &[usize],
+        path: impl Into<PathBuf>,
+    ) -> Result<(AppendVec, usize)> {
+        let mut accounts = Vec::with_capacity(live_offsets.len());
+        for &offset in live_offsets {
+            // account metadata is read in place, so the offset must be aligned and in bounds
+            if offset >= self.len() {
+                return Err(AppendVecError::OffsetOutOfBounds(offset, self.len()).into());
+            }
+            if u64_align!(offset) != offset {
+                return Err(AppendVecError::MisalignedOffset(offset).into());
+            }
+            let account = self
+                .get_stored_account_meta_callback(offset, |account| {
+                    (*account.pubkey(), account.to_account_shared_data())
+                })
+                .ok_or(AppendVecError::OffsetOutOfBounds(offset, self.len()))?;
+            accounts.push(account);
+        }
+
+        let size = accounts
+            .iter()
+            .map(|(_pubkey, account)| aligned_stored_size(account.data().len()))
+            .sum::<usize>();
+        // an append vec can't be empty, so make room for at least one account
+        let compacted = AppendVec::new(path, true, size.max(aligned_stored_size(0)));
+        if !accounts.is_empty() {
+            let slot_ignored = Slot::MAX;
+            let stored = compacted
+                .append_accounts(&(slot_ignored, accounts.as_slice()), 0)
+                .expect("the compacted append vec is sized to fit all live accounts");
+            assert_eq!(stored.offsets.len(), accounts.len());
+        }
+        let savings = self.len().saturating_sub(compacted.len());
+        Ok((compacted, savings))
+    }
+
     pub(crate) fn can_append(&self) -> bool {
         match &self.backing {
             AppendVecFileBacking::File(_file) => false,
@@ -1206,13 +1362,11 @@ impl AppendVec {
 pub mod tests {
     use {
         super::{test_utils::*, *},
+        crate::accounts_db::AccountsDb,
         assert_matches::assert_matches,
         memoffset::offset_of,
         rand::{thread_rng, Rng},
-        solana_sdk::{
-            account::{Account, AccountSharedData},
-            clock::Slot,
-        },
+        solana_sdk::account::{Account, AccountSharedData},
         std::{mem::ManuallyDrop, time::Instant},
         test_case::test_case,
     };
@@ -1471,6 +1625,77 @@ pub mod tests {
         assert_eq!(av.get_account_test(index1).unwrap(), account1);
     }
 
+    #[test]
+    fn test_compact() {
+        let path = get_append_vec_path("test_compact");
+        let av = AppendVec::new(&path.path, true, 1024 * 1024);
+        let accounts = (1..=6)
+            .map(|sample| {
+                let (mut meta, account) = create_test_account(sample * 50);
+                meta.pubkey = Pubkey::new_unique();
+                (meta, account)
+            })
+            .collect::<Vec<_>>();
+        let offsets = accounts
+            .iter()
+            .map(|account| av.append_account_test(account).unwrap())
+            .collect::<Vec<_>>();
+
+        // every other account has been overwritten elsewhere and is now dead
+        let live = [0, 2, 5];
+        let live_offsets = live.iter().map(|&i| offsets[i]).collect::<Vec<_>>();
+        let compacted_path = get_append_vec_path("test_compact_compacted");
+        let (compacted, savings) = av.compact(&live_offsets, &compacted_path.path).unwrap();
+
+        let dead_bytes = [1, 3, 4]
+            .iter()
+            .map(|&i| aligned_stored_size(accounts[i].1.data().len()))
+            .sum::<usize>();
+        assert_eq!(savings, dead_bytes);
+        assert_eq!(compacted.len() + savings, av.len());
+
+        let mut pubkeys = vec![];
+        compacted.scan_pubkeys(|pubkey| pubkeys.push(*pubkey));
+        assert_eq!(
+            pubkeys,
+            live.iter()
+                .map(|&i| accounts[i].0.pubkey)
+                .collect::<Vec<_>>()
+        );
+        let mut offset = 0;
+        for &i in &live {
+            assert_eq!(compacted.get_account_test(offset).unwrap(), accounts[i]);
+            offset += aligned_stored_size(accounts[i].1.data().len());
+        }
+
+        // compacting away everything leaves an empty append vec
+        let empty_path = get_append_vec_path("test_compact_empty");
+        let (empty, savings) = av.compact(&[], &empty_path.path).unwrap();
+        assert!(empty.is_empty());
+        assert_eq!(savings, av.len());
+
+        // offsets must point at the start of a stored account
+        let bad_path = get_append_vec_path("test_compact_bad");
+        assert_matches!(
+            av.compact(&[av.len()], &bad_path.path),
+            Err(AccountsFileError::AppendVecError(
+                AppendVecError::OffsetOutOfBounds(_, _)
+            ))
+        );
+        assert_matches!(
+            av.compact(&[offsets[0], usize::MAX - 7], &bad_path.path),
+            Err(AccountsFileError::AppendVecError(
+                AppendVecError::OffsetOutOfBounds(_, _)
+            ))
+        );
+        assert_matches!(
+            av.compact(&[offsets[0], offsets[1] + 1], &bad_path.path),
+            Err(AccountsFileError::AppendVecError(
+                AppendVecError::MisalignedOffset(_)
+            ))
+        );
+    }
+
     #[test]
     fn test_account_matches_owners() {
         let path = get_append_vec_path("test_append_data");
@@ -2143,4 +2368,155 @@ pub mod tests {
             size + mem::size_of::<StoredMeta>() + mem::size_of::<AccountMeta>()
         });
     }
+
+    #[test_case(StorageAccess::Mmap)]
+    #[test_case(StorageAccess::File)]
+    fn test_append_vec_data_compression(storage_access: StorageAccess) {
+        let threshold = 1024;
+        let mut rng = thread_rng();
+        let datas = [
+            // below the threshold
+            vec![7; 100],
+            // compresses well
+            vec![7; 10 * 1024],
+            // does not compress, so is stored as is
+            (0..4096).map(|_| rng.gen()).collect(),
+            // the largest account
+            (0..MAX_PERMITTED_DATA_LENGTH as usize)
+                .map(|i| (i / 1000) as u8)
+                .collect(),
+        ];
+        let accounts: Vec<_> = datas
+            .iter()
+            .map(|data| {
+                let mut account = AccountSharedData::new(100, 0, &Pubkey::new_unique());
+                account.set_data_from_slice(data);
+                (Pubkey::new_unique(), account)
+            })
+            .collect();
+        let size = accounts
+            .iter()
+            .map(|(_, account)| aligned_stored_size(account.data().len()))
+            .sum();
+
+        let files = [
+            get_append_vec_path("test_append_vec_uncompressed"),
+            get_append_vec_path("test_append_vec_compressed"),
+        ];
+        let loaded: Vec<_> = files
+            .iter()
+            .zip([None, Some(threshold)])
+            .map(|(file, threshold)| {
+                let accounts_len = {
+                    // wrap AppendVec in ManuallyDrop to ensure we do not remove the backing file when dropped
+                    let mut av = ManuallyDrop::new(AppendVec::new(&file.path, true, size));
+                    av.set_data_compression_threshold(threshold);
+                    let stored = av
+                        .append_accounts(&(Slot::MAX, accounts.as_slice()), 0)
+                        .unwrap();
+                    assert_eq!(stored.offsets.len(), accounts.len());
+                    av.flush().unwrap();
+                    av.len()
+                };
+                // loading sanitizes every account, compressed or not
+                let (av, num_accounts) =
+                    AppendVec::new_from_file(&file.path, accounts_len, storage_access).unwrap();
+                assert_eq!(num_accounts, accounts.len());
+
+                let mut scanned = vec![];
+                av.scan_accounts(|account| {
+                    let compressed = is_data_compressed(account.meta());
+                    assert_eq!(account.data_len(), account.data().len());
+                    scanned.push((
+                        *account.pubkey(),
+                        account.to_account_shared_data(),
+                        AccountsDb::hash_account(&account, account.pubkey()),
+                        compressed,
+                    ));
+                });
+                let mut index_infos = vec![];
+                av.scan_index(|index_info| index_infos.push(index_info.index_info));
+                for (index_info, (pubkey, account, ..)) in index_infos.iter().zip(&scanned) {
+                    assert_eq!(&index_info.pubkey, pubkey);
+                    assert_eq!(index_info.data_len, account.data().len() as u64);
+                    assert_eq!(
+                        av.get_account_shared_data(index_info.offset).as_ref(),
+                        Some(account)
+                    );
+                }
+                (accounts_len, scanned)
+            })
+            .collect();
+
+        let (uncompressed_len, uncompressed) = &loaded[0];
+        let (compressed_len, compressed) = &loaded[1];
+        assert!(compressed_len * 10 < *uncompressed_len);
+        // only the accounts above the threshold which compress are stored compressed
+        assert_eq!(
+            compressed
+                .iter()
+                .map(|(.., compressed)| *compressed)
+                .collect::<Vec<_>>(),
+            vec![false, true, false, true]
+        );
+        assert!(uncompressed.iter().all(|(.., compressed)| !compressed));
+        // but the loaded accounts and their hashes are the same
+        assert_eq!(uncompressed.len(), accounts.len());
+        for ((pubkey, account, hash, _), (expected_pubkey, expected_account)) in
+            compressed.iter().zip(&accounts)
+        {
+            assert_eq!(pubkey, expected_pubkey);
+            assert!(accounts_equal(account, expected_account));
+            assert_eq!(hash, &AccountsDb::hash_account(expected_account, pubkey));
+        }
+        for (compressed, uncompressed) in compressed.iter().zip(uncompressed) {
+            let (pubkey, account, hash, _) = compressed;
+            assert_eq!(
+                (pubkey, account, hash),
+                (&uncompressed.0, &uncompressed.1, &uncompressed.2)
+            );
+        }
+    }
+
+    #[test]
+    fn test_decompress_data() {
+        let data = vec![3; 2048];
+        let compressed = compress_data(&data).unwrap();
+        let stored_meta = StoredMeta {
+            write_version_obsolete: COMPRESSED_DATA_FLAG | data.len() as u64,
+            data_len: compressed.len() as u64,
+            pubkey: Pubkey::default(),
+        };
+        assert!(is_data_compressed(&stored_meta));
+        assert_eq!(uncompressed_data_len(&stored_meta), data.len() as u64);
+        assert_eq!(
+            decompress_data(&stored_meta, &compressed).unwrap().as_ref(),
+            data
+        );
+        // corrupt data, or data which does not decompress to the original length, fails to load
+        assert!(decompress_data(&stored_meta, &compressed[..compressed.len() / 2]).is_none());
+        let wrong_len = StoredMeta {
+            write_version_obsolete: COMPRESSED_DATA_FLAG | (data.len() as u64 - 1),
+            ..stored_meta.clone()
+        };
+        assert!(decompress_data(&wrong_len, &compressed).is_none());
+        let too_large = StoredMeta {
+            write_version_obsolete: COMPRESSED_DATA_FLAG | (MAX_PERMITTED_DATA_LENGTH + 1),
+            ..stored_meta
+        };
+        assert!(decompress_data(&too_large, &compressed).is_none());
+        // data which does not compress is not stored compressed
+        let data: Vec<u8> = (0..2048).map(|_| thread_rng().gen()).collect();
+        assert!(compress_data(&data).is_none());
+        // nor is uncompressed data touched on load
+        let stored_meta = StoredMeta {
+            write_version_obsolete: 0,
+            data_len: data.len() as u64,
+            pubkey: Pubkey::default(),
+        };
+        assert!(matches!(
+            decompress_data(&stored_meta, &data),
+            Some(Cow::Borrowed(loaded)) if loaded == data
+        ));
+    }
 }
@@ -4,6 +4,7 @@ use crate::pubkey_bins::PubkeyBinCalculator24;
 use {
     crate::{accounts_hash::CalculateHashIntermediate, cache_hash_data_stats::CacheHashDataStats},
     bytemuck_derive::{Pod, Zeroable},
+    log::*,
     memmap2::MmapMut,
     solana_clock::Slot,
     solana_measure::{measure::Measure, measure_us},
@@ -26,6 +27,8 @@ pub type SavedType = Vec<Vec<EntryType>>;
 #[derive(Debug, Clone, Copy, Pod, Zeroable)]
 pub struct Header {
     pub count: usize,
+    /// blake3 hash of the entries, to detect a corrupted cache file
+    pub checksum: [u8; blake3::OUT_LEN],
 }
 
 // In order to safely guarantee Header is Pod, it cannot have any padding
@@ -34,8 +37,8 @@ pub struct Header {
 // Additionally, we compare the header size with `u64` instead of `usize`
 // to ensure binary compatibility doesn't break.
 const _: () = assert!(
-    std::mem::size_of::<Header>() == std::mem::size_of::<u64>(),
-    "Header cannot have any padding and must be the same size as u64",
+    std::mem::size_of::<Header>() == std::mem::size_of::<u64>() + blake3::OUT_LEN,
+    "Header cannot have any padding and must be the same size as u64 plus the checksum",
 );
 
 /// cache hash data file to be mmapped later
@@ -107,6 +110,27 @@ impl CacheHashDataFileReference {
         Ok(cache_file)
     }
 
+    /// check that the file is complete and its entries match the checksum in its header
+    ///
+    /// Cache files from a previous run may have been truncated or corrupted on disk, and must
+    /// never be used to calculate an accounts hash.
+    fn verify(&self) -> Result<(), std::io::Error> {
+        let header_size = std::mem::size_of::<Header>();
+        if self.file_len < header_size as u64 {
+            return Err(std::io::Error::from(std::io::ErrorKind::UnexpectedEof));
+        }
+        let mmap = CacheHashDataFileReference::load_map(&self.file)?;
+        let (header, entries) = mmap.split_at(header_size);
+        let header: &Header = bytemuck::from_bytes(header);
+        if Some(entries.len()) != header.count.checked_mul(std::mem::size_of::<EntryType>()) {
+            return Err(std::io::Error::from(std::io::ErrorKind::InvalidData));
+        }
+        if blake3::hash(entries).as_bytes() != &header.checksum {
+            return Err(std::io::Error::from(std::io::ErrorKind::InvalidData));
+        }
+        Ok(())
+    }
+
     fn load_map(file: &File) -> Result<MmapMut, std::io::Error> {
         Ok(unsafe { MmapMut::map_mut(file).unwrap() })
     }
@@ -307,6 +331,32 @@ impl CacheHashData {
         })
     }
 
+    /// open a cache hash file left by a previous calculation, but don't map it.
+    ///
+    /// Unlike [`Self::get_file_reference_to_map_later`], the file's contents are checked against
+    /// its checksum first.  A corrupted file is deleted and an error is returned, so the caller
+    /// recalculates the data instead of using it.
+    pub(crate) fn get_verified_file_reference_to_map_later(
+        &self,
+        file_name: impl AsRef<Path>,
+    ) -> Result<CacheHashDataFileReference, std::io::Error> {
+        let reference = self.get_file_reference_to_map_later(&file_name)?;
+        let (result, verify_us) = measure_us!(reference.verify());
+        self.stats.verify_us.fetch_add(verify_us, Ordering::Relaxed);
+        if let Err(err) = result {
+            warn!(
+                "Ignoring corrupted accounts hash cache file {}: {err}",
+                reference.path.display()
+            );
+            self.stats
+                .corrupted_cache_files
+                .fetch_add(1, Ordering::Relaxed);
+            let _ = fs::remove_file(&reference.path);
+            return Err(err);
+        }
+        Ok(reference)
+    }
+
     fn pre_existing_cache_file_will_be_used(&self, file_name: impl AsRef<Path>) {
         self.pre_existing_cache_files
             .lock()
@@ -368,6 +418,8 @@ impl CacheHashData {
             })
         });
         assert_eq!(i, entries);
+        let checksum = blake3::hash(&cache_file.mmap[std::mem::size_of::<Header>()..]);
+        cache_file.get_header_mut().checksum = *checksum.as_bytes();
         m2.stop();
         // We must flush the mmap after writing, since we're about to turn around and load it for
         // reading *not* via the mmap.  If the mmap is never flushed to disk, it is possible the
@@ -535,6 +587,53 @@ mod tests {
         }
     }
 
+    #[test]
+    fn test_verified_file_reference() {
+        let tmpdir = tempfile::TempDir::new().unwrap();
+        let cache_dir = tmpdir.path().to_path_buf();
+        let bin_calculator = PubkeyBinCalculator24::new(1);
+        let (data, _total_points) = generate_test_data(5, 1, &bin_calculator);
+        let file_name = PathBuf::from("test");
+        let cache = CacheHashData::new(cache_dir.clone(), DeletionPolicy::AllUnused);
+        cache.save(&file_name, &data).unwrap();
+
+        // an intact file is verified and can be mapped
+        let reference = cache
+            .get_verified_file_reference_to_map_later(&file_name)
+            .unwrap();
+        assert_eq!(
+            reference.map().unwrap().get_cache_hash_data(),
+            data.concat().as_slice(),
+        );
+        assert_eq!(cache.stats.corrupted_cache_files.load(Ordering::Relaxed), 0);
+
+        // flipping a bit in an entry fails the checksum, and the file is removed
+        let path = cache_dir.join(&file_name);
+        let mut contents = fs::read(&path).unwrap();
+        *contents.last_mut().unwrap() ^= 1;
+        fs::write(&path, &contents).unwrap();
+        assert_eq!(
+            cache
+                .get_verified_file_reference_to_map_later(&file_name)
+                .err()
+                .unwrap()
+                .kind(),
+            std::io::ErrorKind::InvalidData,
+        );
+        assert_eq!(cache.stats.corrupted_cache_files.load(Ordering::Relaxed), 1);
+        assert!(!path.exists());
+
+        // so is a truncated file
+        cache.save(&file_name, &data).unwrap();
+        let contents = fs::read(&path).unwrap();
+        fs::write(&path, &contents[..contents.len() - 1]).unwrap();
+        assert!(cache
+            .get_verified_file_reference_to_map_later(&file_name)
+            .is_err());
+        assert_eq!(cache.stats.corrupted_cache_files.load(Ordering::Relaxed), 2);
+        assert!(!path.exists());
+    }
+
     fn bin_data(
         data: &mut SavedType,
         bin_calculator: &PubkeyBinCalculator24,
@@ -16,6 +16,10 @@ pub struct CacheHashDataStats {
     pub load_us: AtomicU64,
     pub read_us: AtomicU64,
     pub unused_cache_files: AtomicUsize,
+    /// time spent checking pre-existing hash data files against their checksums
+    pub verify_us: AtomicU64,
+    /// the number of pre-existing hash data files that failed their checksum and were ignored
+    pub corrupted_cache_files: AtomicUsize,
     /// the number of hash data files that were found in the cache and reused
     pub hits: AtomicUsize,
     /// the number of hash data files that were not found in the cache
@@ -81,6 +85,12 @@ impl CacheHashDataStats {
             ),
             ("hits", self.hits.load(Ordering::Relaxed), i64),
             ("misses", self.misses.load(Ordering::Relaxed), i64),
+            ("verify_us", self.verify_us.load(Ordering::Relaxed), i64),
+            (
+                "corrupted_cache_files",
+                self.corrupted_cache_files.load(Ordering::Relaxed),
+                i64
+            ),
         );
     }
 }
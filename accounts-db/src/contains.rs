@@ -10,6 +10,10 @@ pub trait Contains<'a, T: Eq + Hash> {
     type Iter: Iterator<Item = Self::Item>;
     fn contains(&self, key: &T) -> bool;
     fn contains_iter(&'a self) -> Self::Iter;
+    /// Whether each of `keys` is contained, in the same order as `keys`
+    fn contains_all(&self, keys: &[T]) -> Vec<bool> {
+        keys.iter().map(|key| self.contains(key)).collect()
+    }
 }
 
 impl<'a, T: 'a + Eq + Hash, U: 'a, S: BuildHasher> Contains<'a, T> for HashMap<T, U, S> {
@@ -47,3 +51,29 @@ impl<'a, T: 'a + Eq + Hash + Copy> Contains<'a, T> for T {
         std::iter::once(self)
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn check_contains_all<'a, C: Contains<'a, u64>>(set: &C, keys: &[u64]) {
+        let expected: Vec<_> = keys.iter().map(|key| set.contains(key)).collect();
+        assert_eq!(set.contains_all(keys), expected);
+    }
+
+    #[test]
+    fn test_contains_all() {
+        let keys = [0, 1, 2, 3, 5, 8, 13];
+        let set: HashSet<u64> = [1, 3, 5, 7].into_iter().collect();
+        assert_eq!(
+            set.contains_all(&keys),
+            vec![false, true, false, true, true, false, false]
+        );
+        check_contains_all(&set, &keys);
+        check_contains_all(&set, &[]);
+
+        let map: HashMap<u64, ()> = set.iter().map(|key| (*key, ())).collect();
+        check_contains_all(&map, &keys);
+        check_contains_all(&5u64, &keys);
+    }
+}
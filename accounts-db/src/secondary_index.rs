@@ -5,6 +5,7 @@ use {
     std::{
         collections::HashSet,
         fmt::Debug,
+        mem::size_of,
         sync::{
             atomic::{AtomicU64, Ordering},
             RwLock,
@@ -19,7 +20,8 @@ use {
 pub type SecondaryReverseIndexEntry = RwLock<Vec<Pubkey>>;
 
 pub trait SecondaryIndexEntry: Debug {
-    fn insert_if_not_exists(&self, key: &Pubkey, inner_keys_count: &AtomicU64);
+    // Inserts a value into the set. Returns whether the value was newly inserted.
+    fn insert_if_not_exists(&self, key: &Pubkey, inner_keys_count: &AtomicU64) -> bool;
     // Removes a value from the set. Returns whether the value was present in the set.
     fn remove_inner_key(&self, key: &Pubkey) -> bool;
     fn is_empty(&self) -> bool;
@@ -31,6 +33,21 @@ pub trait SecondaryIndexEntry: Debug {
 pub struct SecondaryIndexStats {
     last_report: AtomicInterval,
     num_inner_keys: AtomicU64,
+    num_soft_limit_warnings: AtomicU64,
+}
+
+/// Size of a secondary index, for catching indexes which grow without bound
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub struct SecondaryIndexUsage {
+    /// Number of index keys, e.g. token owners
+    pub num_keys: usize,
+    /// Number of (index key, account) pairs
+    pub num_inner_keys: u64,
+    pub num_reverse_index_keys: usize,
+    /// Rough estimate of the memory held by the index, ignoring the overhead of the maps
+    pub memory_estimate_bytes: u64,
+    /// Number of times an index key was reported for exceeding the soft limit
+    pub num_soft_limit_warnings: u64,
 }
 
 #[derive(Debug, Default)]
@@ -39,12 +56,15 @@ pub struct DashMapSecondaryIndexEntry {
 }
 
 impl SecondaryIndexEntry for DashMapSecondaryIndexEntry {
-    fn insert_if_not_exists(&self, key: &Pubkey, inner_keys_count: &AtomicU64) {
+    fn insert_if_not_exists(&self, key: &Pubkey, inner_keys_count: &AtomicU64) -> bool {
+        let mut was_newly_inserted = false;
         if self.account_keys.get(key).is_none() {
             self.account_keys.entry(*key).or_insert_with(|| {
                 inner_keys_count.fetch_add(1, Ordering::Relaxed);
+                was_newly_inserted = true;
             });
         }
+        was_newly_inserted
     }
 
     fn remove_inner_key(&self, key: &Pubkey) -> bool {
@@ -73,16 +93,17 @@ pub struct RwLockSecondaryIndexEntry {
 }
 
 impl SecondaryIndexEntry for RwLockSecondaryIndexEntry {
-    fn insert_if_not_exists(&self, key: &Pubkey, inner_keys_count: &AtomicU64) {
+    fn insert_if_not_exists(&self, key: &Pubkey, inner_keys_count: &AtomicU64) -> bool {
         if self.account_keys.read().unwrap().contains(key) {
             // the key already exists, so nothing to do here
-            return;
+            return false;
         }
 
         let was_newly_inserted = self.account_keys.write().unwrap().insert(*key);
         if was_newly_inserted {
             inner_keys_count.fetch_add(1, Ordering::Relaxed);
         }
+        was_newly_inserted
     }
 
     fn remove_inner_key(&self, key: &Pubkey) -> bool {
@@ -105,6 +126,9 @@ impl SecondaryIndexEntry for RwLockSecondaryIndexEntry {
 #[derive(Debug, Default)]
 pub struct SecondaryIndex<SecondaryIndexEntryType: SecondaryIndexEntry + Default + Sync + Send> {
     metrics_name: &'static str,
+    // Number of values per index key above which the key is reported. Keys are
+    // still indexed in full, the limit only flags runaway index keys.
+    entry_soft_limit: Option<usize>,
     // Map from index keys to index values
     pub index: DashMap<Pubkey, SecondaryIndexEntryType>,
     pub reverse_index: DashMap<Pubkey, SecondaryReverseIndexEntry>,
@@ -114,9 +138,10 @@ pub struct SecondaryIndex<SecondaryIndexEntryType: SecondaryIndexEntry + Default
 impl<SecondaryIndexEntryType: SecondaryIndexEntry + Default + Sync + Send>
     SecondaryIndex<SecondaryIndexEntryType>
 {
-    pub fn new(metrics_name: &'static str) -> Self {
+    pub fn new(metrics_name: &'static str, entry_soft_limit: Option<usize>) -> Self {
         Self {
             metrics_name,
+            entry_soft_limit,
             ..Self::default()
         }
     }
@@ -128,7 +153,11 @@ impl<SecondaryIndexEntryType: SecondaryIndexEntry + Default + Sync + Send>
                 .get(key)
                 .unwrap_or_else(|| self.index.entry(*key).or_default().downgrade());
 
-            pubkeys_map.insert_if_not_exists(inner_key, &self.stats.num_inner_keys);
+            let was_newly_inserted =
+                pubkeys_map.insert_if_not_exists(inner_key, &self.stats.num_inner_keys);
+            if let Some(entry_soft_limit) = self.entry_soft_limit.filter(|_| was_newly_inserted) {
+                self.check_entry_soft_limit(key, pubkeys_map.len(), entry_soft_limit);
+            }
         }
 
         {
@@ -162,10 +191,65 @@ impl<SecondaryIndexEntryType: SecondaryIndexEntry + Default + Sync + Send>
                     self.reverse_index.len() as i64,
                     i64
                 ),
+                (
+                    "memory_estimate_bytes",
+                    self.memory_estimate_bytes() as i64,
+                    i64
+                ),
+                (
+                    "num_soft_limit_warnings",
+                    self.stats.num_soft_limit_warnings.load(Ordering::Relaxed) as i64,
+                    i64
+                ),
             );
         }
     }
 
+    // Reports `key` when its number of values first exceeds the soft limit, and
+    // again each time it grows by another multiple of the limit, so a runaway key
+    // is visible without logging on every insert.
+    fn check_entry_soft_limit(&self, key: &Pubkey, num_values: usize, entry_soft_limit: usize) {
+        let entry_soft_limit = entry_soft_limit.max(1);
+        if num_values <= entry_soft_limit || (num_values - 1) % entry_soft_limit != 0 {
+            return;
+        }
+        self.stats
+            .num_soft_limit_warnings
+            .fetch_add(1, Ordering::Relaxed);
+        warn!(
+            "{}: key {key} has {num_values} values, exceeding the soft limit of \
+             {entry_soft_limit}",
+            self.metrics_name,
+        );
+        datapoint_warn!(
+            "secondary_index_entry_soft_limit_exceeded",
+            ("index", self.metrics_name, String),
+            ("key", key.to_string(), String),
+            ("num_values", num_values as i64, i64),
+        );
+    }
+
+    fn memory_estimate_bytes(&self) -> u64 {
+        // Every value is held once by the index and once by the reverse index
+        let num_inner_keys = self.stats.num_inner_keys.load(Ordering::Relaxed) as usize;
+        let index_bytes =
+            self.index.len() * (size_of::<Pubkey>() + size_of::<SecondaryIndexEntryType>());
+        let reverse_index_bytes = self.reverse_index.len()
+            * (size_of::<Pubkey>() + size_of::<SecondaryReverseIndexEntry>());
+        let value_bytes = 2 * num_inner_keys * size_of::<Pubkey>();
+        (index_bytes + reverse_index_bytes + value_bytes) as u64
+    }
+
+    pub fn usage(&self) -> SecondaryIndexUsage {
+        SecondaryIndexUsage {
+            num_keys: self.index.len(),
+            num_inner_keys: self.stats.num_inner_keys.load(Ordering::Relaxed),
+            num_reverse_index_keys: self.reverse_index.len(),
+            memory_estimate_bytes: self.memory_estimate_bytes(),
+            num_soft_limit_warnings: self.stats.num_soft_limit_warnings.load(Ordering::Relaxed),
+        }
+    }
+
     // Only safe to call from `remove_by_inner_key()` due to asserts
     fn remove_index_entries(&self, outer_key: &Pubkey, removed_inner_key: &Pubkey) {
         let is_outer_key_empty = {
@@ -241,3 +325,62 @@ impl<SecondaryIndexEntryType: SecondaryIndexEntry + Default + Sync + Send>
             .for_each(|(v, k)| info!("owner: {}, accounts: {}", k, v));
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn run_test_entry_soft_limit<
+        SecondaryIndexEntryType: SecondaryIndexEntry + Default + Sync + Send,
+    >() {
+        let index = SecondaryIndex::<SecondaryIndexEntryType>::new("test_index_stats", Some(10));
+        let key = Pubkey::new_unique();
+        let inner_keys: Vec<_> = (0..35).map(|_| Pubkey::new_unique()).collect();
+        for (i, inner_key) in inner_keys.iter().enumerate() {
+            index.insert(&key, inner_key);
+            // Reported when exceeding the limit, then at every further multiple of it
+            let num_inner_keys = i + 1;
+            assert_eq!(
+                index.usage().num_soft_limit_warnings,
+                ((num_inner_keys - 1) / 10) as u64
+            );
+        }
+        // Inserting an existing value is not counted again
+        index.insert(&key, &inner_keys[0]);
+        let other_key = Pubkey::new_unique();
+        index.insert(&other_key, &inner_keys[0]);
+
+        // Nothing is evicted from the key over the limit
+        assert_eq!(index.get(&key).len(), inner_keys.len());
+        let usage = index.usage();
+        assert_eq!(usage.num_keys, 2);
+        assert_eq!(usage.num_inner_keys, 36);
+        assert_eq!(usage.num_reverse_index_keys, 35);
+        assert_eq!(usage.num_soft_limit_warnings, 3);
+        assert!(usage.memory_estimate_bytes >= 2 * 36 * size_of::<Pubkey>() as u64);
+
+        for inner_key in &inner_keys {
+            index.remove_by_inner_key(inner_key);
+        }
+        assert_eq!(
+            index.usage(),
+            SecondaryIndexUsage {
+                num_soft_limit_warnings: 3,
+                ..SecondaryIndexUsage::default()
+            }
+        );
+
+        // Without a limit nothing is reported
+        let index = SecondaryIndex::<SecondaryIndexEntryType>::new("test_index_stats", None);
+        for inner_key in &inner_keys {
+            index.insert(&key, inner_key);
+        }
+        assert_eq!(index.usage().num_soft_limit_warnings, 0);
+    }
+
+    #[test]
+    fn test_entry_soft_limit() {
+        run_test_entry_soft_limit::<RwLockSecondaryIndexEntry>();
+        run_test_entry_soft_limit::<DashMapSecondaryIndexEntry>();
+    }
+}
@@ -55,7 +55,9 @@ impl SharedBuffer {
     pub fn new<T: 'static + Read + std::marker::Send>(reader: T) -> Self {
         Self::new_with_sizes(TOTAL_BUFFER_BUDGET_DEFAULT, CHUNK_SIZE_DEFAULT, reader)
     }
-    fn new_with_sizes<T: 'static + Read + std::marker::Send>(
+    /// Read ahead at most `total_buffer_budget` bytes, in chunks of `chunk_size` bytes.
+    /// Faster disks benefit from reading further ahead than the default.
+    pub fn new_with_sizes<T: 'static + Read + std::marker::Send>(
         total_buffer_budget: usize,
         chunk_size: usize,
         reader: T,
@@ -83,6 +85,28 @@ impl SharedBuffer {
         *instance.bg_reader_join_handle.lock().unwrap() = Some(handle.unwrap());
         Self { instance }
     }
+    /// Number of bytes read ahead and held in memory, including chunks that not every
+    /// reader has finished with yet
+    pub fn bytes_buffered(&self) -> usize {
+        let data_bytes: usize = self
+            .instance
+            .data
+            .read()
+            .unwrap()
+            .iter()
+            .map(|buffer| buffer.len())
+            .sum();
+        let newly_read_bytes: usize = self
+            .instance
+            .bg_reader_data
+            .newly_read_data
+            .read()
+            .unwrap()
+            .iter()
+            .map(|buffer| buffer.len())
+            .sum();
+        data_bytes + newly_read_bytes
+    }
 }
 
 pub struct SharedBufferReader {
@@ -547,6 +571,42 @@ pub mod tests {
         SharedBuffer::new_with_sizes(1, 0, file);
     }
 
+    #[test]
+    fn test_shared_buffer_sizes() {
+        solana_logger::setup();
+        let size = 1_000_000;
+        let source = (0..size).map(|i| (i % 251) as u8).collect::<Vec<_>>();
+        for (total_buffer_budget, chunk_size) in [(1_000, 100), (10_000_000, 2_000_000)] {
+            let shared_buffer = SharedBuffer::new_with_sizes(
+                total_buffer_budget,
+                chunk_size,
+                std::io::Cursor::new(source.clone()),
+            );
+            let mut reader = SharedBufferReader::new(&shared_buffer);
+            let mut data = Vec::new();
+            reader.read_to_end(&mut data).unwrap();
+            assert_eq!(data, source);
+        }
+    }
+
+    #[test]
+    fn test_shared_buffer_bytes_buffered() {
+        solana_logger::setup();
+        let size = 1_000;
+        let source = vec![7u8; size];
+        // the whole source fits in a single chunk
+        let shared_buffer =
+            SharedBuffer::new_with_sizes(size * 2, size * 2, std::io::Cursor::new(source));
+        let mut reader = SharedBufferReader::new(&shared_buffer);
+        let mut data = [0u8; 1];
+        assert_eq!(reader.read(&mut data).unwrap(), 1);
+        assert_eq!(shared_buffer.bytes_buffered(), size);
+
+        // once the only reader is done, the chunk is released
+        drop(reader);
+        assert_eq!(shared_buffer.bytes_buffered(), 0);
+    }
+
     #[test]
     #[should_panic(expected = "SharedBufferReaders must all be created before the first one reads")]
     fn test_shared_buffer_start_too_late() {
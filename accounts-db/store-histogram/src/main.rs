@@ -1,7 +1,15 @@
 #![allow(clippy::arithmetic_side_effects)]
 use {
     clap::{crate_description, crate_name, value_t, value_t_or_exit, App, Arg},
-    std::{fmt::Display, fs, path::PathBuf, str::FromStr},
+    std::{
+        fmt::{self, Display},
+        fs::{self, File},
+        io::{self, Read},
+        ops::RangeInclusive,
+        path::{Path, PathBuf},
+        process::exit,
+        str::FromStr,
+    },
 };
 
 struct Bin {
@@ -255,6 +263,112 @@ fn normal_ancient(offset: i64) -> Vec<usize> {
     bin_widths
 }
 
+// Layout of the append vec account header, see accounts-db's append_vec.rs
+const STORE_META_OVERHEAD: usize = 136;
+const DATA_LEN_OFFSET: usize = 8;
+const EXECUTABLE_OFFSET: usize = 96;
+const MAX_PERMITTED_DATA_LENGTH: u64 = 10 * 1024 * 1024;
+
+/// Why a storage file looks corrupt
+#[derive(Debug, PartialEq, Eq)]
+enum SuspiciousStorage {
+    Empty,
+    /// Too short for the first account
+    Truncated,
+    InvalidHeader(String),
+    Unreadable(String),
+}
+
+impl Display for SuspiciousStorage {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Self::Empty => write!(f, "zero length"),
+            Self::Truncated => write!(f, "truncated"),
+            Self::InvalidHeader(reason) => write!(f, "invalid header: {reason}"),
+            Self::Unreadable(err) => write!(f, "unreadable: {err}"),
+        }
+    }
+}
+
+/// Checks the header of the first account in a storage file of `len` bytes
+fn check_storage_header(header: &[u8], len: usize) -> Option<SuspiciousStorage> {
+    if len == 0 {
+        return Some(SuspiciousStorage::Empty);
+    }
+    if header.len() < STORE_META_OVERHEAD {
+        return Some(SuspiciousStorage::Truncated);
+    }
+    let data_len = u64::from_le_bytes(
+        header[DATA_LEN_OFFSET..DATA_LEN_OFFSET + 8]
+            .try_into()
+            .unwrap(),
+    );
+    if data_len > MAX_PERMITTED_DATA_LENGTH {
+        return Some(SuspiciousStorage::InvalidHeader(format!(
+            "data length {data_len}"
+        )));
+    }
+    if header[EXECUTABLE_OFFSET] > 1 {
+        return Some(SuspiciousStorage::InvalidHeader(format!(
+            "executable byte {}",
+            header[EXECUTABLE_OFFSET]
+        )));
+    }
+    if STORE_META_OVERHEAD as u64 + data_len > len as u64 {
+        return Some(SuspiciousStorage::Truncated);
+    }
+    None
+}
+
+/// Returns the (name, size) of the storage files in `path`, and the names of
+/// the ones which look corrupt
+#[allow(clippy::type_complexity)]
+fn read_storages(
+    path: &Path,
+) -> io::Result<(Vec<(String, usize)>, Vec<(String, SuspiciousStorage)>)> {
+    let mut files = Vec::default();
+    let mut suspicious = Vec::default();
+    for entry in fs::read_dir(path)?.flatten() {
+        if let Some(name) = entry.path().file_name() {
+            let name = name.to_str().unwrap().to_string();
+            let len = match fs::metadata(entry.path()) {
+                Ok(meta) => meta.len() as usize,
+                Err(_) => {
+                    // skip when metadata fails. This can happen when you are running this tool while a validator is running.
+                    // It could clean something away and delete it after getting the dir but before opening the file.
+                    continue;
+                }
+            };
+            let mut header = Vec::with_capacity(STORE_META_OVERHEAD);
+            let checked = File::open(entry.path()).and_then(|file| {
+                file.take(STORE_META_OVERHEAD as u64)
+                    .read_to_end(&mut header)
+            });
+            match checked.map(|_| check_storage_header(&header, len)) {
+                Ok(None) => files.push((name, len)),
+                Ok(Some(reason)) => suspicious.push((name, reason)),
+                Err(err) => suspicious.push((name, SuspiciousStorage::Unreadable(err.to_string()))),
+            }
+        }
+    }
+    suspicious.sort_unstable_by(|a, b| a.0.cmp(&b.0));
+    Ok((files, suspicious))
+}
+
+/// Returns the (slot, size) of the storage files in `files` whose slot is within `slots`
+///
+/// Storage files are named `<slot>.<id>`.
+fn storages_in_range<'a>(
+    files: impl IntoIterator<Item = (&'a str, usize)>,
+    slots: &RangeInclusive<usize>,
+) -> Vec<(usize, usize)> {
+    files
+        .into_iter()
+        .map(|(name, size)| (name.split_once(".").unwrap().0.parse().unwrap(), size))
+        .filter(|(slot, _size)| slots.contains(slot))
+        .collect()
+}
+
 fn main() {
     let matches = App::new(crate_name!())
         .about(crate_description!())
@@ -274,41 +388,76 @@ fn main() {
                 .validator(is_parsable::<i64>)
                 .help("ancient offset"),
         )
+        .arg(
+            Arg::with_name("min_slot")
+                .long("min-slot")
+                .takes_value(true)
+                .value_name("SLOT")
+                .validator(is_parsable::<usize>)
+                .help("only include storages from this slot on"),
+        )
+        .arg(
+            Arg::with_name("max_slot")
+                .long("max-slot")
+                .takes_value(true)
+                .value_name("SLOT")
+                .validator(is_parsable::<usize>)
+                .help("only include storages up to and including this slot"),
+        )
         .get_matches();
 
     let ledger = value_t_or_exit!(matches, "ledger", String);
     let offset = value_t!(matches, "offset", i64).unwrap_or(100_000);
+    let min_slot = value_t!(matches, "min_slot", usize).unwrap_or(0);
+    let max_slot = value_t!(matches, "max_slot", usize).unwrap_or(usize::MAX);
+    if min_slot > max_slot {
+        eprintln!("--min-slot {min_slot} must not be greater than --max-slot {max_slot}");
+        exit(1);
+    }
     let path: PathBuf = [&ledger, "accounts", "run"].iter().collect();
 
     if path.is_dir() {
-        let dir = fs::read_dir(&path);
-        if let Ok(dir) = dir {
-            let mut info = Vec::default();
-            for entry in dir.flatten() {
-                if let Some(name) = entry.path().file_name() {
-                    let name = name.to_str().unwrap().split_once(".").unwrap().0;
-                    match fs::metadata(entry.path()) {
-                        Ok(meta) => {
-                            info.push((name.parse::<usize>().unwrap(), meta.len() as usize));
-                        }
-                        Err(_) => {
-                            // skip when metadata fails. This can happen when you are running this tool while a validator is running.
-                            // It could clean something away and delete it after getting the dir but before opening the file.
-                            continue;
-                        }
-                    }
-                    // eprintln!("{name}, {len}");
-                }
+        let storages = read_storages(&path);
+        if let Ok((files, suspicious)) = storages {
+            let info = storages_in_range(
+                files.iter().map(|(name, size)| (name.as_str(), *size)),
+                &(min_slot..=max_slot),
+            );
+            let suspicious: Vec<_> = suspicious
+                .into_iter()
+                .filter(|(name, _reason)| {
+                    // names of corrupt storages may not even parse
+                    name.split_once(".")
+                        .and_then(|(slot, _id)| slot.parse().ok())
+                        .map_or(true, |slot| (min_slot..=max_slot).contains(&slot))
+                })
+                .collect();
+            if info.is_empty() {
+                eprintln!("no storages in slots {min_slot}..={max_slot}");
+            } else {
+                eprintln!("======== Normal Histogram");
+                calc(&info, normal_bin_widths(), offset);
+                eprintln!("========");
+
+                eprintln!("\n======== Normal Ancient Histogram");
+                calc(&info, normal_ancient(offset), offset);
+                eprintln!("========");
             }
-            eprintln!("======== Normal Histogram");
-            calc(&info, normal_bin_widths(), offset);
-            eprintln!("========");
 
-            eprintln!("\n======== Normal Ancient Histogram");
-            calc(&info, normal_ancient(offset), offset);
-            eprintln!("========");
+            if !suspicious.is_empty() {
+                eprintln!("\n======== Suspicious Storages");
+                eprintln!("count {}", suspicious.len());
+                for (name, reason) in &suspicious {
+                    eprintln!("{name}: {reason}");
+                }
+                eprintln!("========");
+            }
+            // still report suspicious storages in the range, but fail without any to histogram
+            if info.is_empty() {
+                exit(1);
+            }
         } else {
-            panic!("couldn't read folder: {path:?}, {:?}", dir);
+            panic!("couldn't read folder: {path:?}, {:?}", storages);
         }
     } else {
         panic!("not a folder: {:?}", path);
@@ -340,4 +489,70 @@ pub mod tests {
         calc(&info, normal_bin_widths(), offset);
         calc(&info, normal_ancient(offset), offset);
     }
+
+    #[test]
+    fn test_storages_in_range() {
+        let files = [
+            ("99.1", 10),
+            ("100.2", 20),
+            ("150.3", 30),
+            ("200.4", 40),
+            ("201.5", 50),
+            ("1000.6", 60),
+        ];
+        let mut info = storages_in_range(files, &(100..=200));
+        info.sort_unstable();
+        assert_eq!(info, vec![(100, 20), (150, 30), (200, 40)]);
+
+        // The whole range includes everything
+        assert_eq!(
+            storages_in_range(files, &(0..=usize::MAX)).len(),
+            files.len()
+        );
+        // A single slot
+        assert_eq!(storages_in_range(files, &(1000..=1000)), vec![(1000, 60)]);
+        // Nothing in range
+        assert!(storages_in_range(files, &(300..=999)).is_empty());
+    }
+
+    #[test]
+    fn test_read_storages() {
+        let dir = tempfile::tempdir().unwrap();
+        let write = |name: &str, data: &[u8]| fs::write(dir.path().join(name), data).unwrap();
+        // An empty account is a valid header
+        let valid = vec![0; STORE_META_OVERHEAD * 2];
+        write("6.2", &valid);
+        write("5.1", &[]);
+        write("7.3", &valid[..100]);
+        let mut too_long = valid.clone();
+        too_long[DATA_LEN_OFFSET..DATA_LEN_OFFSET + 8]
+            .copy_from_slice(&(STORE_META_OVERHEAD as u64).to_le_bytes());
+        write("8.4", &too_long);
+        let mut bad_data_len = valid.clone();
+        bad_data_len[DATA_LEN_OFFSET..DATA_LEN_OFFSET + 8].copy_from_slice(&u64::MAX.to_le_bytes());
+        write("9.5", &bad_data_len);
+        let mut bad_executable = valid.clone();
+        bad_executable[EXECUTABLE_OFFSET] = 2;
+        write("10.6", &bad_executable);
+
+        let (files, suspicious) = read_storages(dir.path()).unwrap();
+        // Suspicious storages are reported rather than counted in the histogram
+        assert_eq!(files, vec![("6.2".to_string(), valid.len())]);
+        assert_eq!(
+            suspicious,
+            vec![
+                (
+                    "10.6".to_string(),
+                    SuspiciousStorage::InvalidHeader("executable byte 2".to_string())
+                ),
+                ("5.1".to_string(), SuspiciousStorage::Empty),
+                ("7.3".to_string(), SuspiciousStorage::Truncated),
+                ("8.4".to_string(), SuspiciousStorage::Truncated),
+                (
+                    "9.5".to_string(),
+                    SuspiciousStorage::InvalidHeader(format!("data length {}", u64::MAX))
+                ),
+            ]
+        );
+    }
 }
@@ -7,7 +7,7 @@ use {
     rayon::prelude::*,
     solana_client::connection_cache::ConnectionCache,
     solana_core::{
-        banking_stage::BankingStage,
+        banking_stage::{forward_tracker::ForwarderConfig, BankingStage},
         banking_trace::{
             BankingPacketBatch, BankingTracer, Channels, BANKING_TRACE_DIR_DEFAULT_BYTE_LIMIT,
         },
@@ -27,6 +27,7 @@ use {
     solana_runtime::{
         bank::Bank, bank_forks::BankForks, prioritization_fee_cache::PrioritizationFeeCache,
     },
+    solana_sanitize::Sanitize,
     solana_sdk::{
         compute_budget::ComputeBudgetInstruction,
         hash::Hash,
@@ -41,6 +42,8 @@ use {
     solana_tpu_client::tpu_client::DEFAULT_TPU_CONNECTION_POOL_SIZE,
     std::{
         collections::HashSet,
+        fs,
+        path::Path,
         sync::{atomic::Ordering, Arc, RwLock},
         thread::sleep,
         time::{Duration, Instant},
@@ -194,6 +197,64 @@ fn make_transfer_transaction_with_compute_unit_price(
     Transaction::new(&[from_keypair], message, recent_blockhash)
 }
 
+/// Loads a bincode-serialized `Vec<Transaction>`, such as one captured from a
+/// running cluster, checking that every transaction is well formed
+fn load_transactions_from_file(path: &Path) -> Result<Vec<Transaction>, String> {
+    let bytes =
+        fs::read(path).map_err(|err| format!("failed to read {}: {err}", path.display()))?;
+    let transactions: Vec<Transaction> = bincode::deserialize(&bytes)
+        .map_err(|err| format!("failed to deserialize {}: {err}", path.display()))?;
+    if transactions.is_empty() {
+        return Err(format!("{} contains no transactions", path.display()));
+    }
+    for (i, tx) in transactions.iter().enumerate() {
+        tx.sanitize()
+            .map_err(|err| format!("transaction {i} in {} is invalid: {err}", path.display()))?;
+    }
+    Ok(transactions)
+}
+
+/// Takes `total_num_transactions` transactions from `replay_txs`, starting at
+/// `offset` and cycling if there are fewer than needed
+///
+/// Each transaction gets `hash` as its blockhash and a random signature, so
+/// that repeats of the same transaction aren't rejected as duplicates.
+fn make_replay_txs(
+    replay_txs: &[Transaction],
+    total_num_transactions: usize,
+    offset: usize,
+    hash: Hash,
+) -> Vec<Transaction> {
+    replay_txs
+        .iter()
+        .cycle()
+        .skip(offset % replay_txs.len())
+        .take(total_num_transactions)
+        .map(|tx| {
+            let mut tx = tx.clone();
+            tx.message.recent_blockhash = hash;
+            let sig: [u8; 64] = std::array::from_fn(|_| thread_rng().gen::<u8>());
+            tx.signatures[0] = Signature::from(sig);
+            tx
+        })
+        .collect()
+}
+
+/// Funds each distinct fee payer of `transactions` from `mint_keypair`,
+/// splitting half of the mint's balance between them
+fn fund_payers(bank: &Bank, mint_keypair: &Keypair, transactions: &[Transaction]) {
+    let payers: HashSet<Pubkey> = transactions
+        .iter()
+        .map(|tx| tx.message.account_keys[0])
+        .collect();
+    let lamports = bank.get_balance(&mint_keypair.pubkey()) / 2 / payers.len() as u64;
+    for payer in payers {
+        let fund =
+            system_transaction::transfer(mint_keypair, &payer, lamports, bank.last_blockhash());
+        bank.process_transaction(&fund).unwrap();
+    }
+}
+
 struct PacketsPerIteration {
     packet_batches: Vec<PacketBatch>,
     transactions: Vec<Transaction>,
@@ -218,7 +279,31 @@ impl PacketsPerIteration {
             simulate_mint,
             mint_txs_percentage,
         );
+        Self::from_transactions(transactions, packets_per_batch, batches_per_iteration)
+    }
 
+    fn new_replay(
+        replay_txs: &[Transaction],
+        chunk_index: usize,
+        packets_per_batch: usize,
+        batches_per_iteration: usize,
+        genesis_hash: Hash,
+    ) -> Self {
+        let total_num_transactions = packets_per_batch * batches_per_iteration;
+        let transactions = make_replay_txs(
+            replay_txs,
+            total_num_transactions,
+            chunk_index * total_num_transactions,
+            genesis_hash,
+        );
+        Self::from_transactions(transactions, packets_per_batch, batches_per_iteration)
+    }
+
+    fn from_transactions(
+        transactions: Vec<Transaction>,
+        packets_per_batch: usize,
+        batches_per_iteration: usize,
+    ) -> Self {
         let packet_batches: Vec<PacketBatch> = to_packet_batches(&transactions, packets_per_batch);
         assert_eq!(packet_batches.len(), batches_per_iteration);
         Self {
@@ -323,6 +408,19 @@ fn main() {
                 .requires("simulate_mint")
                 .help("In simulating mint, number of mint transactions out of 100."),
         )
+        .arg(
+            Arg::new("tx_file")
+                .long("tx-file")
+                .takes_value(true)
+                .value_name("PATH")
+                .conflicts_with_all(&["write_lock_contention", "simulate_mint"])
+                .help(
+                    "Replay the bincode-serialized transactions in this file instead of \
+                     synthetic transfers, cycling through them if there are fewer than needed. \
+                     Replayed transactions are not sanity checked, since captured transactions \
+                     may fail against the bench's bank.",
+                ),
+        )
         .get_matches();
 
     let block_production_method = matches
@@ -346,6 +444,14 @@ fn main() {
     let mint_txs_percentage = matches
         .value_of_t::<usize>("mint_txs_percentage")
         .unwrap_or(99);
+    let replay_txs = matches.value_of("tx_file").map(|tx_file| {
+        let replay_txs = load_transactions_from_file(Path::new(tx_file)).unwrap_or_else(|err| {
+            eprintln!("{err}");
+            std::process::exit(1);
+        });
+        info!("replaying {} transactions from {tx_file}", replay_txs.len());
+        replay_txs
+    });
 
     let mint_total = 1_000_000_000_000;
     let GenesisConfigInfo {
@@ -364,18 +470,25 @@ fn main() {
         .unwrap()
         .set_limits(u64::MAX, u64::MAX, u64::MAX);
 
-    let mut all_packets: Vec<PacketsPerIteration> = std::iter::from_fn(|| {
-        Some(PacketsPerIteration::new(
-            packets_per_batch,
-            batches_per_iteration,
-            genesis_config.hash(),
-            write_lock_contention,
-            matches.is_present("simulate_mint"),
-            mint_txs_percentage,
-        ))
-    })
-    .take(num_chunks)
-    .collect();
+    let mut all_packets: Vec<PacketsPerIteration> = (0..num_chunks)
+        .map(|chunk_index| match &replay_txs {
+            Some(replay_txs) => PacketsPerIteration::new_replay(
+                replay_txs,
+                chunk_index,
+                packets_per_batch,
+                batches_per_iteration,
+                genesis_config.hash(),
+            ),
+            None => PacketsPerIteration::new(
+                packets_per_batch,
+                batches_per_iteration,
+                genesis_config.hash(),
+                write_lock_contention,
+                matches.is_present("simulate_mint"),
+                mint_txs_percentage,
+            ),
+        })
+        .collect();
 
     let total_num_transactions: u64 = all_packets
         .iter()
@@ -387,25 +500,31 @@ fn main() {
     );
 
     // fund all the accounts
-    all_packets.iter().for_each(|packets_for_single_iteration| {
-        packets_for_single_iteration
-            .transactions
-            .iter()
-            .for_each(|tx| {
-                let mut fund = system_transaction::transfer(
-                    &mint_keypair,
-                    &tx.message.account_keys[0],
-                    mint_total / total_num_transactions,
-                    genesis_config.hash(),
-                );
-                // Ignore any pesky duplicate signature errors in the case we are using single-payer
-                let sig: [u8; 64] = std::array::from_fn(|_| thread_rng().gen::<u8>());
-                fund.signatures = vec![Signature::from(sig)];
-                bank.process_transaction(&fund).unwrap();
-            });
-    });
+    if let Some(replay_txs) = &replay_txs {
+        // every replayed transaction is a copy of a loaded one, so funding the
+        // payers of the loaded transactions covers them all
+        fund_payers(&bank, &mint_keypair, replay_txs);
+    } else {
+        all_packets.iter().for_each(|packets_for_single_iteration| {
+            packets_for_single_iteration
+                .transactions
+                .iter()
+                .for_each(|tx| {
+                    let mut fund = system_transaction::transfer(
+                        &mint_keypair,
+                        &tx.message.account_keys[0],
+                        mint_total / total_num_transactions,
+                        genesis_config.hash(),
+                    );
+                    // Ignore any pesky duplicate signature errors in the case we are using single-payer
+                    let sig: [u8; 64] = std::array::from_fn(|_| thread_rng().gen::<u8>());
+                    fund.signatures = vec![Signature::from(sig)];
+                    bank.process_transaction(&fund).unwrap();
+                });
+        });
+    }
 
-    let skip_sanity = matches.is_present("skip_sanity");
+    let skip_sanity = matches.is_present("skip_sanity") || replay_txs.is_some();
     if !skip_sanity {
         all_packets.iter().for_each(|packets_for_single_iteration| {
             //sanity check, make sure all the transactions can execute sequentially
@@ -491,6 +610,7 @@ fn main() {
         bank_forks.clone(),
         &Arc::new(PrioritizationFeeCache::new(0u64)),
         false,
+        ForwarderConfig::default(),
         HashSet::default(),
         BundleAccountLocker::default(),
     );
@@ -644,3 +764,81 @@ fn main() {
         tracer_thread.join().unwrap().unwrap();
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_replay_transactions_from_file() {
+        let hash = Hash::new_unique();
+        let payer = Keypair::new();
+        let replay_txs: Vec<_> = (1..=2)
+            .map(|lamports| {
+                system_transaction::transfer(&payer, &pubkey::new_rand(), lamports, hash)
+            })
+            .collect();
+        let tx_file = tempfile::NamedTempFile::new().unwrap();
+        fs::write(tx_file.path(), bincode::serialize(&replay_txs).unwrap()).unwrap();
+
+        let loaded_txs = load_transactions_from_file(tx_file.path()).unwrap();
+        assert_eq!(loaded_txs, replay_txs);
+
+        // Both transactions are sent, cycling to fill each iteration, with the
+        // chunk's blockhash
+        let genesis_hash = Hash::new_unique();
+        let packets = PacketsPerIteration::new_replay(&loaded_txs, 1, 3, 2, genesis_hash);
+        assert_eq!(packets.packet_batches.len(), 2);
+        assert_eq!(packets.transactions.len(), 6);
+        let sent_txs: Vec<Transaction> = packets
+            .packet_batches
+            .iter()
+            .flat_map(|batch| batch.iter())
+            .map(|packet| packet.deserialize_slice(..).unwrap())
+            .collect();
+        for (i, tx) in sent_txs.iter().enumerate() {
+            let replay_tx = &replay_txs[(6 + i) % 2];
+            assert_eq!(tx.message.recent_blockhash, genesis_hash);
+            assert_eq!(tx.message.instructions, replay_tx.message.instructions);
+            assert_eq!(tx.message.account_keys, replay_tx.message.account_keys);
+        }
+        // Repeats of the same transaction get distinct signatures
+        let signatures: HashSet<_> = sent_txs.iter().map(|tx| tx.signatures[0]).collect();
+        assert_eq!(signatures.len(), sent_txs.len());
+
+        // Files that don't deserialize are rejected
+        fs::write(tx_file.path(), b"not transactions").unwrap();
+        assert!(load_transactions_from_file(tx_file.path()).is_err());
+    }
+
+    #[test]
+    fn test_replayed_transactions_execute() {
+        let GenesisConfigInfo {
+            genesis_config,
+            mint_keypair,
+            ..
+        } = create_genesis_config(1_000_000_000_000);
+        let bank_forks = BankForks::new_rw_arc(Bank::new_for_tests(&genesis_config));
+        let bank = bank_forks.read().unwrap().working_bank();
+
+        // Captured transactions come from payers unknown to the bench's bank
+        let payers = [Keypair::new(), Keypair::new()];
+        let replay_txs: Vec<_> = payers
+            .iter()
+            .map(|payer| {
+                system_transaction::transfer(
+                    payer,
+                    &pubkey::new_rand(),
+                    1_000_000,
+                    Hash::new_unique(),
+                )
+            })
+            .collect();
+        let packets = PacketsPerIteration::new_replay(&replay_txs, 0, 3, 2, genesis_config.hash());
+
+        fund_payers(&bank, &mint_keypair, &replay_txs);
+        for tx in &packets.transactions {
+            assert_eq!(bank.process_transaction(tx), Ok(()));
+        }
+    }
+}
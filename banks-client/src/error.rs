@@ -30,6 +30,17 @@ pub enum BanksClientError {
         units_consumed: u64,
         return_data: Option<TransactionReturnData>,
     },
+
+    #[error(
+        "transaction consumed {units_consumed} compute units, more than the maximum of {max_units}"
+    )]
+    ComputeUnitsExceeded { units_consumed: u64, max_units: u64 },
+
+    #[error("requested {num_addresses} addresses, more than the maximum of {max_addresses}")]
+    TooManyAddresses {
+        num_addresses: usize,
+        max_addresses: usize,
+    },
 }
 
 impl BanksClientError {
@@ -54,6 +65,10 @@ impl From<BanksClientError> for io::Error {
             BanksClientError::SimulationError { err, .. } => {
                 Self::new(io::ErrorKind::Other, err.to_string())
             }
+            err @ (BanksClientError::ComputeUnitsExceeded { .. }
+            | BanksClientError::TooManyAddresses { .. }) => {
+                Self::new(io::ErrorKind::Other, err.to_string())
+            }
         }
     }
 }
@@ -72,6 +87,8 @@ impl From<BanksClientError> for TransportError {
             }
             BanksClientError::TransactionError(err) => Self::TransactionError(err),
             BanksClientError::SimulationError { err, .. } => Self::TransactionError(err),
+            err @ (BanksClientError::ComputeUnitsExceeded { .. }
+            | BanksClientError::TooManyAddresses { .. }) => Self::Custom(err.to_string()),
         }
     }
 }
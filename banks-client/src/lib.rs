@@ -7,24 +7,36 @@
 
 pub use {
     crate::error::BanksClientError,
-    solana_banks_interface::{BanksClient as TarpcClient, TransactionStatus},
+    solana_banks_interface::{
+        AdvanceSlotError, BanksClient as TarpcClient, MaxUnitsPreflight, TransactionStatus,
+    },
 };
 use {
     borsh::BorshDeserialize,
     futures::future::join_all,
     solana_banks_interface::{
-        BanksRequest, BanksResponse, BanksTransactionResultWithMetadata,
-        BanksTransactionResultWithSimulation,
+        BanksRequest, BanksResponse, BanksTransactionResultWithMaxUnits,
+        BanksTransactionResultWithMetadata, BanksTransactionResultWithSimulation, MaxUnitsExceeded,
+        MAX_MULTIPLE_ACCOUNTS,
     },
     solana_program::{
-        clock::Slot, hash::Hash, program_pack::Pack, pubkey::Pubkey, rent::Rent, sysvar::Sysvar,
+        clock::{Clock, Slot},
+        hash::Hash,
+        program_pack::Pack,
+        pubkey::Pubkey,
+        rent::Rent,
+        sysvar::Sysvar,
     },
     solana_sdk::{
         account::{from_account, Account},
+        client::NonblockingClient,
         commitment_config::CommitmentLevel,
+        epoch_info::EpochInfo,
+        epoch_schedule::EpochSchedule,
         message::Message,
         signature::Signature,
-        transaction::{self, VersionedTransaction},
+        transaction::{self, Transaction, VersionedTransaction},
+        transport,
     },
     tarpc::{
         client::{self, NewClient, RequestDispatch},
@@ -69,6 +81,17 @@ impl BanksClient {
             .map_err(Into::into)
     }
 
+    pub async fn send_transaction_with_result_and_context(
+        &self,
+        ctx: Context,
+        transaction: impl Into<VersionedTransaction>,
+    ) -> Result<(), BanksClientError> {
+        self.inner
+            .send_transaction_with_result_and_context(ctx, transaction.into())
+            .await?
+            .map_err(Into::into)
+    }
+
     pub async fn get_transaction_status_with_context(
         &self,
         ctx: Context,
@@ -102,6 +125,37 @@ impl BanksClient {
             .map_err(Into::into)
     }
 
+    pub async fn get_epoch_info_with_context(
+        &self,
+        ctx: Context,
+        commitment: CommitmentLevel,
+    ) -> Result<EpochInfo, BanksClientError> {
+        self.inner
+            .get_epoch_info_with_context(ctx, commitment)
+            .await
+            .map_err(Into::into)
+    }
+
+    pub async fn get_epoch_schedule_with_context(
+        &self,
+        ctx: Context,
+    ) -> Result<EpochSchedule, BanksClientError> {
+        self.inner
+            .get_epoch_schedule_with_context(ctx)
+            .await
+            .map_err(Into::into)
+    }
+
+    pub async fn get_genesis_hash_with_context(
+        &self,
+        ctx: Context,
+    ) -> Result<Hash, BanksClientError> {
+        self.inner
+            .get_genesis_hash_with_context(ctx)
+            .await
+            .map_err(Into::into)
+    }
+
     pub async fn process_transaction_with_commitment_and_context(
         &self,
         ctx: Context,
@@ -130,6 +184,24 @@ impl BanksClient {
             .map_err(Into::into)
     }
 
+    pub async fn process_transaction_with_max_units_and_commitment_and_context(
+        &self,
+        ctx: Context,
+        transaction: impl Into<VersionedTransaction>,
+        commitment: CommitmentLevel,
+        max_units: MaxUnitsPreflight,
+    ) -> Result<BanksTransactionResultWithMaxUnits, BanksClientError> {
+        self.inner
+            .process_transaction_with_max_units_and_commitment_and_context(
+                ctx,
+                transaction.into(),
+                commitment,
+                max_units,
+            )
+            .await
+            .map_err(Into::into)
+    }
+
     pub async fn process_transaction_with_metadata_and_context(
         &self,
         ctx: Context,
@@ -165,6 +237,32 @@ impl BanksClient {
             .map_err(Into::into)
     }
 
+    pub async fn get_accounts_with_commitment_and_context(
+        &self,
+        ctx: Context,
+        addresses: Vec<Pubkey>,
+        commitment: CommitmentLevel,
+    ) -> Result<Vec<Option<Account>>, BanksClientError> {
+        let num_addresses = addresses.len();
+        self.inner
+            .get_accounts_with_commitment_and_context(ctx, addresses, commitment)
+            .await?
+            .ok_or(BanksClientError::TooManyAddresses {
+                num_addresses,
+                max_addresses: MAX_MULTIPLE_ACCOUNTS,
+            })
+    }
+
+    pub async fn advance_slot_with_context(
+        &self,
+        ctx: Context,
+    ) -> Result<Result<Slot, AdvanceSlotError>, BanksClientError> {
+        self.inner
+            .advance_slot_with_context(ctx)
+            .await
+            .map_err(Into::into)
+    }
+
     /// Send a transaction and return immediately. The server will resend the
     /// transaction until either it is accepted by the cluster or the transaction's
     /// blockhash expires.
@@ -176,6 +274,18 @@ impl BanksClient {
             .await
     }
 
+    /// Send a transaction and return once the server has accepted it. Transactions
+    /// the server rejects up front, such as oversized ones, ones with an unknown
+    /// blockhash or ones whose durable nonce can't be advanced, fail with the reason.
+    /// Requires a server that supports it.
+    pub async fn send_transaction_with_result(
+        &self,
+        transaction: impl Into<VersionedTransaction>,
+    ) -> Result<(), BanksClientError> {
+        self.send_transaction_with_result_and_context(context::current(), transaction.into())
+            .await
+    }
+
     /// Return the cluster Sysvar
     pub async fn get_sysvar<T: Sysvar>(&self) -> Result<T, BanksClientError> {
         let sysvar = self
@@ -192,8 +302,16 @@ impl BanksClient {
         self.get_sysvar::<Rent>().await
     }
 
+    /// Return the cluster clock
+    pub async fn get_clock(&self) -> Result<Clock, BanksClientError> {
+        self.get_sysvar::<Clock>().await
+    }
+
     /// Send a transaction and return after the transaction has been rejected or
     /// reached the given level of commitment.
+    ///
+    /// If the server has a compute unit ceiling, transactions over it fail with
+    /// `TransactionError::WouldExceedMaxBlockCostLimit`.
     pub async fn process_transaction_with_commitment(
         &self,
         transaction: impl Into<VersionedTransaction>,
@@ -211,6 +329,65 @@ impl BanksClient {
         }
     }
 
+    /// Send a transaction and return after the transaction has been rejected or
+    /// reached the given level of commitment.
+    ///
+    /// With `max_units`, the server rejects the transaction before processing it
+    /// if it consumes more compute units than its configured ceiling, or than the
+    /// given one, and reports the units it consumed. Requires a server that supports
+    /// the ceiling.
+    pub async fn process_transaction_with_max_units_and_commitment(
+        &self,
+        transaction: impl Into<VersionedTransaction>,
+        commitment: CommitmentLevel,
+        max_units: MaxUnitsPreflight,
+    ) -> Result<(), BanksClientError> {
+        let ctx = context::current();
+        match self
+            .process_transaction_with_max_units_and_commitment_and_context(
+                ctx,
+                transaction,
+                commitment,
+                max_units,
+            )
+            .await?
+        {
+            BanksTransactionResultWithMaxUnits {
+                result: _,
+                max_units_exceeded:
+                    Some(MaxUnitsExceeded {
+                        units_consumed,
+                        max_units,
+                    }),
+            } => Err(BanksClientError::ComputeUnitsExceeded {
+                units_consumed,
+                max_units,
+            }),
+            BanksTransactionResultWithMaxUnits { result: None, .. } => Err(
+                BanksClientError::ClientError("invalid blockhash or fee-payer"),
+            ),
+            BanksTransactionResultWithMaxUnits {
+                result: Some(transaction_result),
+                ..
+            } => Ok(transaction_result?),
+        }
+    }
+
+    /// Send a transaction and return until the transaction has been finalized or
+    /// rejected, checking it against a compute unit ceiling first.
+    pub async fn process_transaction_with_max_units(
+        &self,
+        transaction: impl Into<VersionedTransaction>,
+        max_units: MaxUnitsPreflight,
+    ) -> Result<(), BanksClientError> {
+        self.process_transaction_with_max_units_and_commitment(
+            transaction,
+            CommitmentLevel::default(),
+            max_units,
+        )
+        .await
+    }
+
     /// Process a transaction and return the result with metadata.
     pub async fn process_transaction_with_metadata(
         &self,
@@ -343,6 +520,33 @@ impl BanksClient {
             .await
     }
 
+    /// Return information about the current epoch, read from the bank at the slot
+    /// corresponding to the given commitment level.
+    pub async fn get_epoch_info_with_commitment(
+        &self,
+        commitment: CommitmentLevel,
+    ) -> Result<EpochInfo, BanksClientError> {
+        self.get_epoch_info_with_context(context::current(), commitment)
+            .await
+    }
+
+    /// Return information about the epoch of the most recent root slot.
+    pub async fn get_epoch_info(&self) -> Result<EpochInfo, BanksClientError> {
+        self.get_epoch_info_with_commitment(CommitmentLevel::default())
+            .await
+    }
+
+    /// Return the cluster's epoch schedule.
+    pub async fn get_epoch_schedule(&self) -> Result<EpochSchedule, BanksClientError> {
+        self.get_epoch_schedule_with_context(context::current())
+            .await
+    }
+
+    /// Return the hash of the genesis config the cluster was started from.
+    pub async fn get_genesis_hash(&self) -> Result<Hash, BanksClientError> {
+        self.get_genesis_hash_with_context(context::current()).await
+    }
+
     /// Return the account at the given address at the slot corresponding to the given
     /// commitment level. If the account is not found, None is returned.
     pub async fn get_account_with_commitment(
@@ -361,6 +565,35 @@ impl BanksClient {
             .await
     }
 
+    /// Return the accounts at the given addresses, in the same order, read from the
+    /// single bank corresponding to the given commitment level. Accounts that are
+    /// not found are returned as None. Requesting more than `MAX_MULTIPLE_ACCOUNTS`
+    /// addresses at once fails with `BanksClientError::TooManyAddresses`.
+    pub async fn get_accounts_with_commitment(
+        &self,
+        addresses: Vec<Pubkey>,
+        commitment: CommitmentLevel,
+    ) -> Result<Vec<Option<Account>>, BanksClientError> {
+        if addresses.len() > MAX_MULTIPLE_ACCOUNTS {
+            return Err(BanksClientError::TooManyAddresses {
+                num_addresses: addresses.len(),
+                max_addresses: MAX_MULTIPLE_ACCOUNTS,
+            });
+        }
+        self.get_accounts_with_commitment_and_context(context::current(), addresses, commitment)
+            .await
+    }
+
+    /// Return the accounts at the given addresses at the time of the most recent root
+    /// slot. Accounts that are not found are returned as None.
+    pub async fn get_accounts(
+        &self,
+        addresses: Vec<Pubkey>,
+    ) -> Result<Vec<Option<Account>>, BanksClientError> {
+        self.get_accounts_with_commitment(addresses, CommitmentLevel::default())
+            .await
+    }
+
     /// Return the unpacked account data at the given address
     /// If the account is not found, an error is returned
     pub async fn get_packed_account_data<T: Pack>(
@@ -506,6 +739,44 @@ impl BanksClient {
             .await
             .map_err(Into::into)
     }
+
+    /// Freeze and root the working bank and continue in a child bank at the next slot, returning
+    /// the new slot. Only available from test servers, such as the one started by `ProgramTest`.
+    pub async fn advance_slot(&self) -> Result<Slot, BanksClientError> {
+        self.advance_slot_with_context(context::current())
+            .await?
+            .map_err(|err| {
+                BanksClientError::ClientError(match err {
+                    AdvanceSlotError::NotEnabled => "advancing slots is not enabled on the server",
+                    AdvanceSlotError::SlotOverflow => "the working bank is at the last slot",
+                })
+            })
+    }
+}
+
+impl NonblockingClient for BanksClient {
+    async fn get_latest_blockhash(&self) -> transport::Result<Hash> {
+        Ok(BanksClient::get_latest_blockhash(self).await?)
+    }
+
+    async fn get_minimum_balance_for_rent_exemption(
+        &self,
+        data_len: usize,
+    ) -> transport::Result<u64> {
+        Ok(self.get_rent().await?.minimum_balance(data_len))
+    }
+
+    async fn get_account(&self, pubkey: &Pubkey) -> transport::Result<Option<Account>> {
+        Ok(BanksClient::get_account(self, *pubkey).await?)
+    }
+
+    async fn process_transaction(&self, transaction: Transaction) -> transport::Result<()> {
+        Ok(BanksClient::process_transaction(self, transaction).await?)
+    }
+
+    async fn advance_slot(&self) -> transport::Result<Slot> {
+        Ok(BanksClient::advance_slot(self).await?)
+    }
 }
 
 pub async fn start_client<C>(transport: C) -> Result<BanksClient, BanksClientError>
@@ -528,13 +799,21 @@ pub async fn start_tcp_client<T: ToSocketAddrs>(addr: T) -> Result<BanksClient,
 mod tests {
     use {
         super::*,
-        solana_banks_server::banks_server::start_local_server,
+        assert_matches::assert_matches,
+        solana_banks_server::banks_server::{
+            start_local_server, start_local_server_with_config, BanksServerConfig,
+        },
         solana_runtime::{
             bank::Bank, bank_forks::BankForks, commitment::BlockCommitmentCache,
             genesis_utils::create_genesis_config,
         },
         solana_sdk::{
-            message::Message, signature::Signer, system_instruction, transaction::Transaction,
+            instruction::{AccountMeta, Instruction},
+            message::Message,
+            packet::PACKET_DATA_SIZE,
+            signature::Signer,
+            system_instruction,
+            transaction::{Transaction, TransactionError},
         },
         std::sync::{Arc, RwLock},
         tarpc::transport,
@@ -571,9 +850,13 @@ mod tests {
         let message = Message::new(&[instruction], Some(&mint_pubkey));
 
         Runtime::new()?.block_on(async {
-            let client_transport =
-                start_local_server(bank_forks, block_commitment_cache, Duration::from_millis(1))
-                    .await;
+            let client_transport = start_local_server(
+                bank_forks,
+                block_commitment_cache,
+                Duration::from_millis(1),
+                genesis.genesis_config.hash(),
+            )
+            .await;
             let banks_client = start_client(client_transport).await?;
 
             let recent_blockhash = banks_client.get_latest_blockhash().await?;
@@ -610,9 +893,13 @@ mod tests {
         let message = Message::new(&[instruction], Some(mint_pubkey));
 
         Runtime::new()?.block_on(async {
-            let client_transport =
-                start_local_server(bank_forks, block_commitment_cache, Duration::from_millis(1))
-                    .await;
+            let client_transport = start_local_server(
+                bank_forks,
+                block_commitment_cache,
+                Duration::from_millis(1),
+                genesis.genesis_config.hash(),
+            )
+            .await;
             let banks_client = start_client(client_transport).await?;
             let (recent_blockhash, last_valid_block_height) = banks_client
                 .get_latest_blockhash_with_commitment(CommitmentLevel::default())
@@ -637,4 +924,211 @@ mod tests {
             Ok(())
         })
     }
+
+    #[test]
+    #[allow(clippy::result_large_err)]
+    fn test_banks_server_get_accounts() -> Result<(), BanksClientError> {
+        let genesis = create_genesis_config(10);
+        let bank = Bank::new_for_tests(&genesis.genesis_config);
+        let slot = bank.slot();
+        let block_commitment_cache = Arc::new(RwLock::new(
+            BlockCommitmentCache::new_for_tests_with_slots(slot, slot),
+        ));
+        let bank_forks = BankForks::new_rw_arc(bank);
+
+        let mint_pubkey = genesis.mint_keypair.pubkey();
+        let missing_pubkey = solana_sdk::pubkey::new_rand();
+
+        Runtime::new()?.block_on(async {
+            let client_transport = start_local_server(
+                bank_forks,
+                block_commitment_cache,
+                Duration::from_millis(1),
+                genesis.genesis_config.hash(),
+            )
+            .await;
+            let banks_client = start_client(client_transport).await?;
+
+            let accounts = banks_client
+                .get_accounts(vec![
+                    missing_pubkey,
+                    mint_pubkey,
+                    missing_pubkey,
+                    solana_sdk::sysvar::rent::id(),
+                ])
+                .await?;
+            assert_eq!(accounts.len(), 4);
+            assert_eq!(accounts[0], None);
+            assert_eq!(accounts[1], banks_client.get_account(mint_pubkey).await?);
+            assert_eq!(accounts[1].as_ref().unwrap().lamports, 10);
+            assert_eq!(accounts[2], None);
+            assert_eq!(
+                accounts[3].as_ref().unwrap().owner,
+                solana_sdk::sysvar::id()
+            );
+
+            // Rejected by the client, and by the server when the client doesn't check
+            let too_many_addresses = vec![mint_pubkey; MAX_MULTIPLE_ACCOUNTS + 1];
+            assert_matches!(
+                banks_client.get_accounts(too_many_addresses.clone()).await,
+                Err(BanksClientError::TooManyAddresses {
+                    num_addresses,
+                    max_addresses: MAX_MULTIPLE_ACCOUNTS,
+                }) if num_addresses == MAX_MULTIPLE_ACCOUNTS + 1
+            );
+            assert_matches!(
+                banks_client
+                    .get_accounts_with_commitment_and_context(
+                        context::current(),
+                        too_many_addresses,
+                        CommitmentLevel::default(),
+                    )
+                    .await,
+                Err(BanksClientError::TooManyAddresses { .. })
+            );
+            Ok(())
+        })
+    }
+
+    #[test]
+    #[allow(clippy::result_large_err)]
+    fn test_banks_server_rejects_oversized_transaction() -> Result<(), BanksClientError> {
+        let genesis = create_genesis_config(10);
+        let bank = Bank::new_for_tests(&genesis.genesis_config);
+        let slot = bank.slot();
+        let block_commitment_cache = Arc::new(RwLock::new(
+            BlockCommitmentCache::new_for_tests_with_slots(slot, slot),
+        ));
+        let bank_forks = BankForks::new_rw_arc(bank);
+
+        let mint_pubkey = genesis.mint_keypair.pubkey();
+        let instruction = Instruction::new_with_bytes(
+            solana_sdk::system_program::id(),
+            &[0; PACKET_DATA_SIZE],
+            vec![AccountMeta::new(mint_pubkey, true)],
+        );
+        let message = Message::new(&[instruction], Some(&mint_pubkey));
+
+        Runtime::new()?.block_on(async {
+            let client_transport = start_local_server(
+                bank_forks,
+                block_commitment_cache,
+                Duration::from_millis(1),
+                genesis.genesis_config.hash(),
+            )
+            .await;
+            let banks_client = start_client(client_transport).await?;
+
+            let recent_blockhash = banks_client.get_latest_blockhash().await?;
+            let transaction = Transaction::new(&[&genesis.mint_keypair], message, recent_blockhash);
+            // The original method can't report the rejection, and drops the transaction
+            banks_client.send_transaction(transaction.clone()).await?;
+            assert_matches!(
+                banks_client
+                    .send_transaction_with_result(transaction.clone())
+                    .await,
+                Err(BanksClientError::TransactionError(
+                    TransactionError::SanitizeFailure
+                ))
+            );
+            assert_matches!(
+                banks_client.process_transaction(transaction).await,
+                Err(BanksClientError::TransactionError(
+                    TransactionError::SanitizeFailure
+                ))
+            );
+            Ok(())
+        })
+    }
+
+    #[test]
+    #[allow(clippy::result_large_err)]
+    fn test_banks_server_rejects_unknown_blockhash() -> Result<(), BanksClientError> {
+        let genesis = create_genesis_config(10);
+        let bank = Bank::new_for_tests(&genesis.genesis_config);
+        let slot = bank.slot();
+        let block_commitment_cache = Arc::new(RwLock::new(
+            BlockCommitmentCache::new_for_tests_with_slots(slot, slot),
+        ));
+        let bank_forks = BankForks::new_rw_arc(bank);
+
+        let mint_pubkey = genesis.mint_keypair.pubkey();
+        let instruction =
+            system_instruction::transfer(&mint_pubkey, &solana_sdk::pubkey::new_rand(), 1);
+        let message = Message::new(&[instruction], Some(&mint_pubkey));
+
+        Runtime::new()?.block_on(async {
+            let client_transport = start_local_server(
+                bank_forks,
+                block_commitment_cache,
+                Duration::from_millis(1),
+                genesis.genesis_config.hash(),
+            )
+            .await;
+            let banks_client = start_client(client_transport).await?;
+
+            let transaction =
+                Transaction::new(&[&genesis.mint_keypair], message, Hash::new_unique());
+            assert_matches!(
+                banks_client.send_transaction_with_result(transaction).await,
+                Err(BanksClientError::TransactionError(
+                    TransactionError::BlockhashNotFound
+                ))
+            );
+            Ok(())
+        })
+    }
+
+    #[test]
+    #[allow(clippy::result_large_err)]
+    fn test_banks_server_advance_slot() -> Result<(), BanksClientError> {
+        let genesis = create_genesis_config(10);
+        let bank = Bank::new_for_tests(&genesis.genesis_config);
+        let slot = bank.slot();
+        let bank_forks = BankForks::new_rw_arc(bank);
+        let new_block_commitment_cache = || {
+            Arc::new(RwLock::new(BlockCommitmentCache::new_for_tests_with_slots(
+                slot, slot,
+            )))
+        };
+
+        Runtime::new()?.block_on(async {
+            // Only allowed if the server enables it
+            let client_transport = start_local_server(
+                bank_forks.clone(),
+                new_block_commitment_cache(),
+                Duration::from_millis(1),
+                genesis.genesis_config.hash(),
+            )
+            .await;
+            let banks_client = start_client(client_transport).await?;
+            assert_matches!(
+                banks_client.advance_slot().await,
+                Err(BanksClientError::ClientError(_))
+            );
+            assert_eq!(bank_forks.read().unwrap().working_bank().slot(), slot);
+
+            let client_transport = start_local_server_with_config(
+                bank_forks.clone(),
+                new_block_commitment_cache(),
+                Duration::from_millis(1),
+                genesis.genesis_config.hash(),
+                BanksServerConfig {
+                    enable_advance_slot: true,
+                    ..BanksServerConfig::default()
+                },
+            )
+            .await;
+            let banks_client = start_client(client_transport).await?;
+            assert_eq!(banks_client.advance_slot().await?, slot + 1);
+            assert_eq!(banks_client.get_root_slot().await?, slot + 1);
+            assert_eq!(banks_client.advance_slot().await?, slot + 2);
+            assert_eq!(banks_client.get_root_slot().await?, slot + 2);
+            let bank_forks = bank_forks.read().unwrap();
+            assert!(bank_forks[slot + 1].is_frozen());
+            assert_eq!(bank_forks.root(), slot + 1);
+            assert_eq!(bank_forks.working_bank().slot(), slot + 2);
+            Ok(())
+        })
+    }
 }
@@ -1,11 +1,16 @@
 #![allow(deprecated)]
+#![cfg_attr(feature = "frozen-abi", feature(min_specialization))]
 
+#[cfg(feature = "frozen-abi")]
+use solana_frozen_abi_macro::{AbiEnumVisitor, AbiExample};
 use {
     serde_derive::{Deserialize, Serialize},
     solana_sdk::{
         account::Account,
         clock::Slot,
         commitment_config::CommitmentLevel,
+        epoch_info::EpochInfo,
+        epoch_schedule::EpochSchedule,
         hash::Hash,
         inner_instruction::InnerInstructions,
         message::Message,
@@ -16,6 +21,10 @@ use {
     },
 };
 
+/// Maximum number of addresses accepted by `get_accounts_with_commitment_and_context`
+pub const MAX_MULTIPLE_ACCOUNTS: usize = 100;
+
+#[cfg_attr(feature = "frozen-abi", derive(AbiExample, AbiEnumVisitor))]
 #[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
 pub enum TransactionConfirmationStatus {
     Processed,
@@ -23,6 +32,7 @@ pub enum TransactionConfirmationStatus {
     Finalized,
 }
 
+#[cfg_attr(feature = "frozen-abi", derive(AbiExample))]
 #[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
 pub struct TransactionStatus {
     pub slot: Slot,
@@ -31,6 +41,7 @@ pub struct TransactionStatus {
     pub confirmation_status: Option<TransactionConfirmationStatus>,
 }
 
+#[cfg_attr(feature = "frozen-abi", derive(AbiExample))]
 #[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
 #[serde(rename_all = "camelCase")]
 pub struct TransactionSimulationDetails {
@@ -40,6 +51,7 @@ pub struct TransactionSimulationDetails {
     pub inner_instructions: Option<Vec<InnerInstructions>>,
 }
 
+#[cfg_attr(feature = "frozen-abi", derive(AbiExample))]
 #[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
 #[serde(rename_all = "camelCase")]
 pub struct TransactionMetadata {
@@ -48,20 +60,64 @@ pub struct TransactionMetadata {
     pub return_data: Option<TransactionReturnData>,
 }
 
+#[cfg_attr(feature = "frozen-abi", derive(AbiExample))]
 #[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
 pub struct BanksTransactionResultWithSimulation {
     pub result: Option<transaction::Result<()>>,
     pub simulation_details: Option<TransactionSimulationDetails>,
 }
 
+/// Compute unit ceiling checked by simulating a transaction before processing it
+#[cfg_attr(feature = "frozen-abi", derive(AbiExample, AbiEnumVisitor))]
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub enum MaxUnitsPreflight {
+    /// Use the ceiling configured on the server, if any
+    #[default]
+    ServerDefault,
+    /// Skip the check, and the extra simulation it costs
+    Disabled,
+    /// Reject the transaction if it consumes more than this many units
+    Limit(u64),
+}
+
+#[cfg_attr(feature = "frozen-abi", derive(AbiExample))]
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct MaxUnitsExceeded {
+    pub units_consumed: u64,
+    pub max_units: u64,
+}
+
+#[cfg_attr(feature = "frozen-abi", derive(AbiExample))]
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct BanksTransactionResultWithMaxUnits {
+    /// `None` if the transaction was rejected by the compute unit preflight, or if its
+    /// blockhash expired before it landed
+    pub result: Option<transaction::Result<()>>,
+    pub max_units_exceeded: Option<MaxUnitsExceeded>,
+}
+
+#[cfg_attr(feature = "frozen-abi", derive(AbiExample))]
 #[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
 pub struct BanksTransactionResultWithMetadata {
     pub result: transaction::Result<()>,
     pub metadata: Option<TransactionMetadata>,
 }
 
+/// Why a server did not advance to the next slot
+#[cfg_attr(feature = "frozen-abi", derive(AbiExample, AbiEnumVisitor))]
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub enum AdvanceSlotError {
+    /// The server does not allow clients to advance slots, as only test servers do
+    NotEnabled,
+    /// The working bank is at the last slot
+    SlotOverflow,
+}
+
 #[tarpc::service]
 pub trait Banks {
+    // Requests and responses are serialized by the index of their method in this trait, so
+    // new methods go at the end, or older clients and servers misread every later one
     async fn send_transaction_with_context(transaction: VersionedTransaction);
     async fn get_transaction_status_with_context(signature: Signature)
         -> Option<TransactionStatus>;
@@ -94,8 +150,134 @@ pub trait Banks {
         message: Message,
         commitment: CommitmentLevel,
     ) -> Option<u64>;
+    /// Returns `None` if more than `MAX_MULTIPLE_ACCOUNTS` addresses are requested
+    async fn get_accounts_with_commitment_and_context(
+        addresses: Vec<Pubkey>,
+        commitment: CommitmentLevel,
+    ) -> Option<Vec<Option<Account>>>;
+    /// Like `send_transaction_with_context`, but reports transactions rejected before they are
+    /// forwarded
+    async fn send_transaction_with_result_and_context(
+        transaction: VersionedTransaction,
+    ) -> transaction::Result<()>;
+    async fn get_epoch_info_with_context(commitment: CommitmentLevel) -> EpochInfo;
+    async fn get_epoch_schedule_with_context() -> EpochSchedule;
+    async fn get_genesis_hash_with_context() -> Hash;
+    /// Simulates the transaction first if a compute unit ceiling applies, and rejects it without
+    /// committing if it consumes more units than the ceiling
+    async fn process_transaction_with_max_units_and_commitment_and_context(
+        transaction: VersionedTransaction,
+        commitment: CommitmentLevel,
+        max_units: MaxUnitsPreflight,
+    ) -> BanksTransactionResultWithMaxUnits;
+    /// Freezes and roots the working bank and makes a child bank at the next slot the working
+    /// bank, returning the new slot
+    async fn advance_slot_with_context() -> Result<Slot, AdvanceSlotError>;
+}
+
+// `tarpc::service` generates `BanksRequest` and `BanksResponse` without a way to derive anything
+// else for them, so their ABI examples are implemented by hand from a list of the `Banks` methods.
+// The exhaustive matches stop compiling until a new method is added to the list.
+#[cfg(feature = "frozen-abi")]
+macro_rules! impl_abi_for_banks_messages {
+    ($($method:ident { $($arg:ident),* }),+ $(,)?) => {
+        impl solana_frozen_abi::abi_example::AbiExample for BanksRequest {
+            fn example() -> Self {
+                Self::abi_variants().swap_remove(0)
+            }
+        }
+
+        impl solana_frozen_abi::abi_example::AbiEnumVisitor for BanksRequest {
+            fn visit_for_abi(
+                &self,
+                digester: &mut solana_frozen_abi::abi_digester::AbiDigester,
+            ) -> solana_frozen_abi::abi_digester::DigestResult {
+                match self {
+                    $(Self::$method { .. })|+ => (),
+                }
+                visit_abi_variants("BanksRequest", Self::abi_variants(), digester)
+            }
+        }
+
+        impl BanksRequest {
+            fn abi_variants() -> Vec<Self> {
+                use solana_frozen_abi::abi_example::AbiExample;
+                vec![$(Self::$method { $($arg: AbiExample::example()),* }),+]
+            }
+        }
+
+        impl solana_frozen_abi::abi_example::AbiExample for BanksResponse {
+            fn example() -> Self {
+                Self::abi_variants().swap_remove(0)
+            }
+        }
+
+        impl solana_frozen_abi::abi_example::AbiEnumVisitor for BanksResponse {
+            fn visit_for_abi(
+                &self,
+                digester: &mut solana_frozen_abi::abi_digester::AbiDigester,
+            ) -> solana_frozen_abi::abi_digester::DigestResult {
+                match self {
+                    $(Self::$method(_))|+ => (),
+                }
+                visit_abi_variants("BanksResponse", Self::abi_variants(), digester)
+            }
+        }
+
+        impl BanksResponse {
+            fn abi_variants() -> Vec<Self> {
+                use solana_frozen_abi::abi_example::AbiExample;
+                vec![$(Self::$method(AbiExample::example())),+]
+            }
+        }
+    };
+}
+
+#[cfg(feature = "frozen-abi")]
+impl_abi_for_banks_messages! {
+    SendTransactionWithContext { transaction },
+    GetTransactionStatusWithContext { signature },
+    GetSlotWithContext { commitment },
+    GetBlockHeightWithContext { commitment },
+    ProcessTransactionWithPreflightAndCommitmentAndContext { transaction, commitment },
+    ProcessTransactionWithCommitmentAndContext { transaction, commitment },
+    ProcessTransactionWithMetadataAndContext { transaction },
+    SimulateTransactionWithCommitmentAndContext { transaction, commitment },
+    GetAccountWithCommitmentAndContext { address, commitment },
+    GetLatestBlockhashWithContext {},
+    GetLatestBlockhashWithCommitmentAndContext { commitment },
+    GetFeeForMessageWithCommitmentAndContext { message, commitment },
+    GetAccountsWithCommitmentAndContext { addresses, commitment },
+    SendTransactionWithResultAndContext { transaction },
+    GetEpochInfoWithContext { commitment },
+    GetEpochScheduleWithContext {},
+    GetGenesisHashWithContext {},
+    ProcessTransactionWithMaxUnitsAndCommitmentAndContext { transaction, commitment, max_units },
+    AdvanceSlotWithContext {},
+}
+
+/// Digests every variant of a message enum, as `#[derive(AbiEnumVisitor)]` would
+#[cfg(feature = "frozen-abi")]
+fn visit_abi_variants<T: serde::Serialize>(
+    enum_name: &str,
+    variants: Vec<T>,
+    digester: &mut solana_frozen_abi::abi_digester::AbiDigester,
+) -> solana_frozen_abi::abi_digester::DigestResult {
+    digester.update_with_string(format!("enum {enum_name} (variants = {})", variants.len()));
+    for variant in variants {
+        variant.serialize(digester.create_enum_child()?)?;
+    }
+    digester.create_child()
 }
 
+#[cfg(all(test, feature = "frozen-abi"))]
+#[solana_frozen_abi_macro::frozen_abi(digest = "HuyKdB5rAzH8pEiRSUY45Npc4y48qWfRWeN7e34RukmB")]
+type BanksRequestAbi = BanksRequest;
+
+#[cfg(all(test, feature = "frozen-abi"))]
+#[solana_frozen_abi_macro::frozen_abi(digest = "AoHXuTgHcvhCLgVroL47pLESTCAGg6NXeSDPXi9xkatM")]
+type BanksResponseAbi = BanksResponse;
+
 #[cfg(test)]
 mod tests {
     use {
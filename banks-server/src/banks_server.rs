@@ -2,15 +2,18 @@ use {
     bincode::{deserialize, serialize},
     crossbeam_channel::{unbounded, Receiver, Sender},
     futures::{future, prelude::stream::StreamExt},
+    solana_accounts_db::epoch_accounts_hash::EpochAccountsHash,
     solana_banks_interface::{
-        Banks, BanksRequest, BanksResponse, BanksTransactionResultWithMetadata,
-        BanksTransactionResultWithSimulation, TransactionConfirmationStatus, TransactionMetadata,
-        TransactionSimulationDetails, TransactionStatus,
+        AdvanceSlotError, Banks, BanksRequest, BanksResponse, BanksTransactionResultWithMaxUnits,
+        BanksTransactionResultWithMetadata, BanksTransactionResultWithSimulation, MaxUnitsExceeded,
+        MaxUnitsPreflight, TransactionConfirmationStatus, TransactionMetadata,
+        TransactionSimulationDetails, TransactionStatus, MAX_MULTIPLE_ACCOUNTS,
     },
     solana_client::connection_cache::ConnectionCache,
     solana_feature_set::{move_precompile_verification_to_svm, FeatureSet},
     solana_gossip::cluster_info::ClusterInfo,
     solana_runtime::{
+        accounts_background_service::{AbsRequestSender, SnapshotRequestKind},
         bank::{Bank, TransactionSimulationResult},
         bank_forks::BankForks,
         commitment::BlockCommitmentCache,
@@ -21,11 +24,20 @@ use {
         account::Account,
         clock::Slot,
         commitment_config::CommitmentLevel,
+        epoch_info::EpochInfo,
+        epoch_schedule::EpochSchedule,
         hash::Hash,
+        instruction::InstructionError,
         message::{Message, SanitizedMessage},
+        nonce::state::DurableNonce,
+        nonce_account::verify_nonce_account,
+        packet::PACKET_DATA_SIZE,
         pubkey::Pubkey,
         signature::Signature,
-        transaction::{self, MessageHash, SanitizedTransaction, VersionedTransaction},
+        system_instruction::SystemError,
+        transaction::{
+            self, MessageHash, SanitizedTransaction, TransactionError, VersionedTransaction,
+        },
     },
     solana_send_transaction_service::{
         send_transaction_service::{SendTransactionService, TransactionInfo},
@@ -50,12 +62,26 @@ use {
     tokio_serde::formats::Bincode,
 };
 
+#[derive(Clone, Debug, Default)]
+pub struct BanksServerConfig {
+    /// Reject transactions that consume more than this many compute units, after simulating
+    /// them. Simulation doubles the cost of processing a transaction, so this is off by default.
+    /// Methods whose response can't describe the ceiling fail such transactions with
+    /// `TransactionError::WouldExceedMaxBlockCostLimit`.
+    pub max_units_per_transaction: Option<u64>,
+    /// Allow clients to step the working bank forward a slot at a time, rooting each bank they
+    /// leave. Only for test servers.
+    pub enable_advance_slot: bool,
+}
+
 #[derive(Clone)]
 struct BanksServer {
     bank_forks: Arc<RwLock<BankForks>>,
     block_commitment_cache: Arc<RwLock<BlockCommitmentCache>>,
     transaction_sender: Sender<TransactionInfo>,
     poll_signature_status_sleep_duration: Duration,
+    genesis_hash: Hash,
+    config: BanksServerConfig,
 }
 
 impl BanksServer {
@@ -68,12 +94,16 @@ impl BanksServer {
         block_commitment_cache: Arc<RwLock<BlockCommitmentCache>>,
         transaction_sender: Sender<TransactionInfo>,
         poll_signature_status_sleep_duration: Duration,
+        genesis_hash: Hash,
+        config: BanksServerConfig,
     ) -> Self {
         Self {
             bank_forks,
             block_commitment_cache,
             transaction_sender,
             poll_signature_status_sleep_duration,
+            genesis_hash,
+            config,
         }
     }
 
@@ -107,6 +137,8 @@ impl BanksServer {
         bank_forks: Arc<RwLock<BankForks>>,
         block_commitment_cache: Arc<RwLock<BlockCommitmentCache>>,
         poll_signature_status_sleep_duration: Duration,
+        genesis_hash: Hash,
+        config: BanksServerConfig,
     ) -> Self {
         let (transaction_sender, transaction_receiver) = unbounded();
         let bank = bank_forks.read().unwrap().working_bank();
@@ -126,6 +158,8 @@ impl BanksServer {
             block_commitment_cache,
             transaction_sender,
             poll_signature_status_sleep_duration,
+            genesis_hash,
+            config,
         )
     }
 
@@ -160,6 +194,100 @@ impl BanksServer {
         }
         status
     }
+
+    /// Send `transaction` to the bank and wait for it to reach `commitment`, without checking
+    /// it against a compute unit ceiling
+    async fn process_transaction(
+        self,
+        transaction: VersionedTransaction,
+        commitment: CommitmentLevel,
+    ) -> Option<transaction::Result<()>> {
+        let wire_transaction = match serialize_transaction(&transaction) {
+            Ok(wire_transaction) => wire_transaction,
+            Err(err) => return Some(Err(err)),
+        };
+        let bank = self.bank(commitment);
+        let sanitized_transaction = match SanitizedTransaction::try_create(
+            transaction.clone(),
+            MessageHash::Compute,
+            Some(false), // is_simple_vote_tx
+            bank.as_ref(),
+            bank.get_reserved_account_keys(),
+        ) {
+            Ok(tx) => tx,
+            Err(err) => return Some(Err(err)),
+        };
+
+        if let Err(err) = verify_transaction(&sanitized_transaction, &bank.feature_set) {
+            return Some(Err(err));
+        }
+
+        let blockhash = transaction.message.recent_blockhash();
+        let last_valid_block_height = match last_valid_block_height(&bank, &sanitized_transaction) {
+            Ok(last_valid_block_height) => last_valid_block_height,
+            Err(err) => return Some(Err(err)),
+        };
+        let signature = sanitized_transaction.signature();
+        let info = TransactionInfo::new(
+            *signature,
+            wire_transaction,
+            last_valid_block_height,
+            None,
+            None,
+            None,
+        );
+        self.transaction_sender.send(info).unwrap();
+        self.poll_signature_status(signature, blockhash, last_valid_block_height, commitment)
+            .await
+    }
+}
+
+/// Serialize a transaction for the send transaction service, rejecting any
+/// that couldn't fit in a packet
+fn serialize_transaction(transaction: &VersionedTransaction) -> transaction::Result<Vec<u8>> {
+    let wire_transaction = serialize(transaction).map_err(|_| TransactionError::SanitizeFailure)?;
+    if wire_transaction.len() > PACKET_DATA_SIZE {
+        return Err(TransactionError::SanitizeFailure);
+    }
+    Ok(wire_transaction)
+}
+
+/// Return the block height after which `transaction` can no longer land.
+///
+/// Durable nonce transactions don't expire, so they are accepted only if their
+/// nonce can still be advanced in `bank`, and are then waited on for as long as
+/// a transaction using the latest blockhash. A nonce already advanced earlier in
+/// the slot is rejected here rather than left to time out.
+fn last_valid_block_height(
+    bank: &Bank,
+    transaction: &SanitizedTransaction,
+) -> transaction::Result<u64> {
+    let blockhash = transaction.message().recent_blockhash();
+    if let Some(last_valid_block_height) = bank.get_blockhash_last_valid_block_height(blockhash) {
+        return Ok(last_valid_block_height);
+    }
+    let Some(nonce_address) = transaction.get_durable_nonce() else {
+        return Err(TransactionError::BlockhashNotFound);
+    };
+
+    // A nonce that can't be advanced fails the way the transaction's AdvanceNonceAccount
+    // instruction would, so it isn't mistaken for an expired blockhash
+    let nonce_error = |err: SystemError| {
+        TransactionError::InstructionError(0, InstructionError::Custom(err as u32))
+    };
+    let next_durable_nonce = DurableNonce::from_blockhash(&bank.last_blockhash());
+    if blockhash == next_durable_nonce.as_hash() {
+        return Err(nonce_error(SystemError::NonceBlockhashNotExpired));
+    }
+    if bank
+        .get_account(nonce_address)
+        .and_then(|nonce_account| verify_nonce_account(&nonce_account, blockhash))
+        .is_none()
+    {
+        return Err(nonce_error(SystemError::NonceUnexpectedBlockhashValue));
+    }
+    bank.get_blockhash_last_valid_block_height(&bank.last_blockhash())
+        .ok_or(TransactionError::BlockhashNotFound)
 }
 
 fn verify_transaction(
@@ -217,27 +345,58 @@ fn simulate_transaction(
     }
 }
 
+/// Simulate `transaction` if there is a compute unit ceiling, and return how many units it
+/// consumed if that is more than the ceiling. Transactions that fail to sanitize aren't
+/// simulated, and fail the same way when they are processed.
+fn check_max_units(
+    bank: &Bank,
+    transaction: &VersionedTransaction,
+    max_units: Option<u64>,
+) -> Option<MaxUnitsExceeded> {
+    let max_units = max_units?;
+    let units_consumed = simulate_transaction(bank, transaction.clone())
+        .simulation_details?
+        .units_consumed;
+    (units_consumed > max_units).then_some(MaxUnitsExceeded {
+        units_consumed,
+        max_units,
+    })
+}
+
 #[tarpc::server]
 impl Banks for BanksServer {
-    async fn send_transaction_with_context(self, _: Context, transaction: VersionedTransaction) {
-        let blockhash = transaction.message.recent_blockhash();
-        let last_valid_block_height = self
-            .bank_forks
-            .read()
-            .unwrap()
-            .root_bank()
-            .get_blockhash_last_valid_block_height(blockhash)
-            .unwrap();
-        let signature = transaction.signatures.first().cloned().unwrap_or_default();
+    async fn send_transaction_with_context(self, ctx: Context, transaction: VersionedTransaction) {
+        // This method can't report rejected transactions, so they are dropped
+        let _ = self
+            .send_transaction_with_result_and_context(ctx, transaction)
+            .await;
+    }
+
+    async fn send_transaction_with_result_and_context(
+        self,
+        _: Context,
+        transaction: VersionedTransaction,
+    ) -> transaction::Result<()> {
+        let wire_transaction = serialize_transaction(&transaction)?;
+        let bank = self.bank_forks.read().unwrap().root_bank();
+        let sanitized_transaction = SanitizedTransaction::try_create(
+            transaction,
+            MessageHash::Compute,
+            Some(false), // is_simple_vote_tx
+            bank.as_ref(),
+            bank.get_reserved_account_keys(),
+        )?;
+        let last_valid_block_height = last_valid_block_height(&bank, &sanitized_transaction)?;
         let info = TransactionInfo::new(
-            signature,
-            serialize(&transaction).unwrap(),
+            *sanitized_transaction.signature(),
+            wire_transaction,
             last_valid_block_height,
             None,
             None,
             None,
         );
         self.transaction_sender.send(info).unwrap();
+        Ok(())
     }
 
     async fn get_transaction_status_with_context(
@@ -284,6 +443,27 @@ impl Banks for BanksServer {
         self.bank(commitment).block_height()
     }
 
+    async fn get_epoch_info_with_context(
+        self,
+        _: Context,
+        commitment: CommitmentLevel,
+    ) -> EpochInfo {
+        self.bank(commitment).get_epoch_info()
+    }
+
+    async fn get_epoch_schedule_with_context(self, _: Context) -> EpochSchedule {
+        self.bank_forks
+            .read()
+            .unwrap()
+            .root_bank()
+            .epoch_schedule()
+            .clone()
+    }
+
+    async fn get_genesis_hash_with_context(self, _: Context) -> Hash {
+        self.genesis_hash
+    }
+
     async fn process_transaction_with_preflight_and_commitment_and_context(
         self,
         ctx: Context,
@@ -317,39 +497,39 @@ impl Banks for BanksServer {
         transaction: VersionedTransaction,
         commitment: CommitmentLevel,
     ) -> Option<transaction::Result<()>> {
-        let bank = self.bank(commitment);
-        let sanitized_transaction = match SanitizedTransaction::try_create(
-            transaction.clone(),
-            MessageHash::Compute,
-            Some(false), // is_simple_vote_tx
-            bank.as_ref(),
-            bank.get_reserved_account_keys(),
-        ) {
-            Ok(tx) => tx,
-            Err(err) => return Some(Err(err)),
-        };
-
-        if let Err(err) = verify_transaction(&sanitized_transaction, &bank.feature_set) {
-            return Some(Err(err));
+        // This method's response can't describe the ceiling, so a transaction over it fails with
+        // the error a cluster returns for transactions that cost too much
+        let max_units = self.config.max_units_per_transaction;
+        if check_max_units(&self.bank(commitment), &transaction, max_units).is_some() {
+            return Some(Err(TransactionError::WouldExceedMaxBlockCostLimit));
         }
+        self.process_transaction(transaction, commitment).await
+    }
 
-        let blockhash = transaction.message.recent_blockhash();
-        let last_valid_block_height = self
-            .bank(commitment)
-            .get_blockhash_last_valid_block_height(blockhash)
-            .unwrap();
-        let signature = sanitized_transaction.signature();
-        let info = TransactionInfo::new(
-            *signature,
-            serialize(&transaction).unwrap(),
-            last_valid_block_height,
-            None,
-            None,
-            None,
-        );
-        self.transaction_sender.send(info).unwrap();
-        self.poll_signature_status(signature, blockhash, last_valid_block_height, commitment)
-            .await
+    async fn process_transaction_with_max_units_and_commitment_and_context(
+        self,
+        _: Context,
+        transaction: VersionedTransaction,
+        commitment: CommitmentLevel,
+        max_units: MaxUnitsPreflight,
+    ) -> BanksTransactionResultWithMaxUnits {
+        let max_units = match max_units {
+            MaxUnitsPreflight::ServerDefault => self.config.max_units_per_transaction,
+            MaxUnitsPreflight::Disabled => None,
+            MaxUnitsPreflight::Limit(max_units) => Some(max_units),
+        };
+        if let Some(max_units_exceeded) =
+            check_max_units(&self.bank(commitment), &transaction, max_units)
+        {
+            return BanksTransactionResultWithMaxUnits {
+                result: None,
+                max_units_exceeded: Some(max_units_exceeded),
+            };
+        }
+        BanksTransactionResultWithMaxUnits {
+            result: self.process_transaction(transaction, commitment).await,
+            max_units_exceeded: None,
+        }
     }
 
     async fn process_transaction_with_metadata_and_context(
@@ -358,6 +538,12 @@ impl Banks for BanksServer {
         transaction: VersionedTransaction,
     ) -> BanksTransactionResultWithMetadata {
         let bank = self.bank_forks.read().unwrap().working_bank();
+        if check_max_units(&bank, &transaction, self.config.max_units_per_transaction).is_some() {
+            return BanksTransactionResultWithMetadata {
+                result: Err(TransactionError::WouldExceedMaxBlockCostLimit),
+                metadata: None,
+            };
+        }
         match bank.process_transaction_with_metadata(transaction) {
             Err(error) => BanksTransactionResultWithMetadata {
                 result: Err(error),
@@ -384,6 +570,24 @@ impl Banks for BanksServer {
         bank.get_account(&address).map(Account::from)
     }
 
+    async fn get_accounts_with_commitment_and_context(
+        self,
+        _: Context,
+        addresses: Vec<Pubkey>,
+        commitment: CommitmentLevel,
+    ) -> Option<Vec<Option<Account>>> {
+        if addresses.len() > MAX_MULTIPLE_ACCOUNTS {
+            return None;
+        }
+        let bank = self.bank(commitment);
+        Some(
+            addresses
+                .iter()
+                .map(|address| bank.get_account(address).map(Account::from))
+                .collect(),
+        )
+    }
+
     async fn get_latest_blockhash_with_context(self, _: Context) -> Hash {
         let bank = self.bank(CommitmentLevel::default());
         bank.last_blockhash()
@@ -412,17 +616,93 @@ impl Banks for BanksServer {
                 .ok()?;
         bank.get_fee_for_message(&sanitized_message)
     }
+
+    async fn advance_slot_with_context(self, _: Context) -> Result<Slot, AdvanceSlotError> {
+        if !self.config.enable_advance_slot {
+            return Err(AdvanceSlotError::NotEnabled);
+        }
+        let mut bank_forks = self.bank_forks.write().unwrap();
+        let bank = bank_forks.working_bank();
+        let parent_slot = bank.slot();
+        let slot = parent_slot
+            .checked_add(1)
+            .ok_or(AdvanceSlotError::SlotOverflow)?;
+        // Fill ticks so that the child bank has a new blockhash, otherwise retried transactions
+        // will have the same signature
+        bank.fill_bank_with_ticks_for_tests();
+        bank.freeze();
+        set_root_for_tests(&mut bank_forks, parent_slot);
+        bank_forks.insert(Bank::new_from_parent(bank, &Pubkey::default(), slot));
+        // As after a warp, the new bank is served at every commitment level, otherwise
+        // finalized requests would wait for a root which only moves with the next advance
+        self.block_commitment_cache
+            .write()
+            .unwrap()
+            .set_all_slots(slot, slot);
+        Ok(slot)
+    }
+}
+
+/// Roots `root`, like `ProgramTest::warp_to_slot` does. Nothing calculates epoch accounts hashes
+/// for a test server, so the requests sent while rooting are answered with a made up hash.
+/// Otherwise later rooted banks would hang in `Bank::freeze()` waiting for the calculation.
+fn set_root_for_tests(bank_forks: &mut BankForks, root: Slot) {
+    let (snapshot_request_sender, snapshot_request_receiver) = unbounded();
+    bank_forks
+        .set_root(
+            root,
+            &AbsRequestSender::new(snapshot_request_sender),
+            Some(root),
+        )
+        .expect("the snapshot request receiver is alive");
+    snapshot_request_receiver
+        .try_iter()
+        .filter(|snapshot_request| {
+            snapshot_request.request_kind == SnapshotRequestKind::EpochAccountsHash
+        })
+        .for_each(|snapshot_request| {
+            snapshot_request
+                .snapshot_root_bank
+                .rc
+                .accounts
+                .accounts_db
+                .epoch_accounts_hash_manager
+                .set_valid(
+                    EpochAccountsHash::new(Hash::new_unique()),
+                    snapshot_request.snapshot_root_bank.slot(),
+                )
+        });
 }
 
 pub async fn start_local_server(
     bank_forks: Arc<RwLock<BankForks>>,
     block_commitment_cache: Arc<RwLock<BlockCommitmentCache>>,
     poll_signature_status_sleep_duration: Duration,
+    genesis_hash: Hash,
+) -> UnboundedChannel<Response<BanksResponse>, ClientMessage<BanksRequest>> {
+    start_local_server_with_config(
+        bank_forks,
+        block_commitment_cache,
+        poll_signature_status_sleep_duration,
+        genesis_hash,
+        BanksServerConfig::default(),
+    )
+    .await
+}
+
+pub async fn start_local_server_with_config(
+    bank_forks: Arc<RwLock<BankForks>>,
+    block_commitment_cache: Arc<RwLock<BlockCommitmentCache>>,
+    poll_signature_status_sleep_duration: Duration,
+    genesis_hash: Hash,
+    config: BanksServerConfig,
 ) -> UnboundedChannel<Response<BanksResponse>, ClientMessage<BanksRequest>> {
     let banks_server = BanksServer::new_loopback(
         bank_forks,
         block_commitment_cache,
         poll_signature_status_sleep_duration,
+        genesis_hash,
+        config,
     );
     let (client_transport, server_transport) = transport::channel::unbounded();
     let server = server::BaseChannel::with_defaults(server_transport).execute(banks_server.serve());
@@ -436,6 +716,7 @@ pub async fn start_tcp_server(
     bank_forks: Arc<RwLock<BankForks>>,
     block_commitment_cache: Arc<RwLock<BlockCommitmentCache>>,
     connection_cache: Arc<ConnectionCache>,
+    genesis_hash: Hash,
     exit: Arc<AtomicBool>,
 ) -> io::Result<()> {
     // Note: These settings are copied straight from the tarpc example.
@@ -471,6 +752,8 @@ pub async fn start_tcp_server(
                 block_commitment_cache.clone(),
                 sender,
                 Duration::from_millis(200),
+                genesis_hash,
+                BanksServerConfig::default(),
             );
             chan.execute(server.serve())
         })
@@ -4,7 +4,7 @@ use solana_pubkey::Pubkey;
 /// The type of target determines whether the program should have a program
 /// account or not, which is checked before migration.
 #[allow(dead_code)] // Remove after first migration is configured.
-#[derive(Debug, PartialEq)]
+#[derive(Clone, Debug, PartialEq)]
 pub enum CoreBpfMigrationTargetType {
     /// A standard (stateful) builtin program must have a program account.
     Builtin,
@@ -13,7 +13,7 @@ pub enum CoreBpfMigrationTargetType {
 }
 
 /// Configuration for migrating a built-in program to Core BPF.
-#[derive(Debug, PartialEq)]
+#[derive(Clone, Debug, PartialEq)]
 pub struct CoreBpfMigrationConfig {
     /// The address of the source buffer account to be used to replace the
     /// builtin.
@@ -54,6 +54,7 @@ pub static BUILTINS: &[BuiltinPrototype] = &[
         core_bpf_migration_config: None,
         name: system_program,
         enable_feature_id: None,
+        disable_feature_id: None,
         program_id: solana_system_program::id(),
         entrypoint: solana_system_program::system_processor::Entrypoint::vm,
     }),
@@ -61,6 +62,7 @@ pub static BUILTINS: &[BuiltinPrototype] = &[
         core_bpf_migration_config: None,
         name: vote_program,
         enable_feature_id: None,
+        disable_feature_id: None,
         program_id: solana_vote_program::id(),
         entrypoint: solana_vote_program::vote_processor::Entrypoint::vm,
     }),
@@ -74,6 +76,7 @@ pub static BUILTINS: &[BuiltinPrototype] = &[
         }),
         name: "stake_program",
         enable_feature_id: None,
+        disable_feature_id: None,
         program_id: solana_stake_program::id(),
         entrypoint: solana_stake_program::stake_instruction::Entrypoint::vm,
     },
@@ -87,6 +90,7 @@ pub static BUILTINS: &[BuiltinPrototype] = &[
         }),
         name: "config_program",
         enable_feature_id: None,
+        disable_feature_id: None,
         program_id: solana_config_program::id(),
         entrypoint: solana_config_program::config_processor::Entrypoint::vm,
     },
@@ -94,6 +98,7 @@ pub static BUILTINS: &[BuiltinPrototype] = &[
         core_bpf_migration_config: None,
         name: solana_bpf_loader_deprecated_program,
         enable_feature_id: None,
+        disable_feature_id: None,
         program_id: bpf_loader_deprecated::id(),
         entrypoint: solana_bpf_loader_program::Entrypoint::vm,
     }),
@@ -101,6 +106,7 @@ pub static BUILTINS: &[BuiltinPrototype] = &[
         core_bpf_migration_config: None,
         name: solana_bpf_loader_program,
         enable_feature_id: None,
+        disable_feature_id: None,
         program_id: bpf_loader::id(),
         entrypoint: solana_bpf_loader_program::Entrypoint::vm,
     }),
@@ -108,6 +114,7 @@ pub static BUILTINS: &[BuiltinPrototype] = &[
         core_bpf_migration_config: None,
         name: solana_bpf_loader_upgradeable_program,
         enable_feature_id: None,
+        disable_feature_id: None,
         program_id: bpf_loader_upgradeable::id(),
         entrypoint: solana_bpf_loader_program::Entrypoint::vm,
     }),
@@ -115,6 +122,7 @@ pub static BUILTINS: &[BuiltinPrototype] = &[
         core_bpf_migration_config: None,
         name: compute_budget_program,
         enable_feature_id: None,
+        disable_feature_id: None,
         program_id: solana_sdk_ids::compute_budget::id(),
         entrypoint: solana_compute_budget_program::Entrypoint::vm,
     }),
@@ -128,6 +136,7 @@ pub static BUILTINS: &[BuiltinPrototype] = &[
         }),
         name: "address_lookup_table_program",
         enable_feature_id: None,
+        disable_feature_id: None,
         program_id: solana_sdk_ids::address_lookup_table::id(),
         entrypoint: solana_address_lookup_table_program::processor::Entrypoint::vm,
     },
@@ -135,6 +144,7 @@ pub static BUILTINS: &[BuiltinPrototype] = &[
         core_bpf_migration_config: None,
         name: zk_token_proof_program,
         enable_feature_id: Some(feature_set::zk_token_sdk_enabled::id()),
+        disable_feature_id: None,
         program_id: solana_sdk_ids::zk_token_proof_program::id(),
         entrypoint: solana_zk_token_proof_program::Entrypoint::vm,
     }),
@@ -142,6 +152,7 @@ pub static BUILTINS: &[BuiltinPrototype] = &[
         core_bpf_migration_config: None,
         name: loader_v4,
         enable_feature_id: Some(feature_set::enable_program_runtime_v2_and_loader_v4::id()),
+        disable_feature_id: None,
         program_id: solana_sdk_ids::loader_v4::id(),
         entrypoint: solana_loader_v4_program::Entrypoint::vm,
     }),
@@ -149,6 +160,7 @@ pub static BUILTINS: &[BuiltinPrototype] = &[
         core_bpf_migration_config: None,
         name: zk_elgamal_proof_program,
         enable_feature_id: Some(feature_set::zk_elgamal_proof_program_enabled::id()),
+        disable_feature_id: None,
         program_id: solana_sdk_ids::zk_elgamal_proof_program::id(),
         entrypoint: solana_zk_elgamal_proof_program::Entrypoint::vm,
     }),
@@ -6,12 +6,16 @@ use {
 };
 
 /// Transitions of built-in programs at epoch boundaries when features are activated.
+#[derive(Clone)]
 pub struct BuiltinPrototype {
     /// Configurations for migrating the builtin to Core BPF.
     pub core_bpf_migration_config: Option<CoreBpfMigrationConfig>,
     /// Feature ID that enables the builtin program.
     /// If None, the built-in program is always enabled.
     pub enable_feature_id: Option<Pubkey>,
+    /// Feature ID that disables the builtin program.
+    /// If None, the built-in program is never disabled.
+    pub disable_feature_id: Option<Pubkey>,
     /// The program's ID.
     pub program_id: Pubkey,
     /// The program's name, ie "system_program".
@@ -26,6 +30,7 @@ impl std::fmt::Debug for BuiltinPrototype {
         builder.field("program_id", &self.program_id);
         builder.field("name", &self.name);
         builder.field("enable_feature_id", &self.enable_feature_id);
+        builder.field("disable_feature_id", &self.disable_feature_id);
         builder.field("core_bpf_migration_config", &self.core_bpf_migration_config);
         builder.finish()
     }
@@ -376,6 +376,7 @@ pub fn load_and_execute_bundle<'a>(
                     account_overrides: Some(account_overrides),
                     check_program_modification_slot: bank.check_program_modification_slot(),
                     compute_budget: bank.compute_budget(),
+                    max_instruction_stack_depth: bank.max_instruction_stack_depth(),
                     log_messages_bytes_limit: *log_messages_bytes_limit,
                     limit_to_load_programs: true,
                     recording_config: ExecutionRecordingConfig::new_single_setting(
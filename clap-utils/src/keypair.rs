@@ -401,6 +401,7 @@ const SIGNER_SOURCE_FILEPATH: &str = "file";
 const SIGNER_SOURCE_USB: &str = "usb";
 const SIGNER_SOURCE_STDIN: &str = "stdin";
 const SIGNER_SOURCE_PUBKEY: &str = "pubkey";
+const SIGNER_SOURCE_ENV: &str = "env";
 
 pub(crate) enum SignerSourceKind {
     Prompt,
@@ -408,6 +409,7 @@ pub(crate) enum SignerSourceKind {
     Usb(RemoteWalletLocator),
     Stdin,
     Pubkey(Pubkey),
+    Env(String),
 }
 
 impl AsRef<str> for SignerSourceKind {
@@ -418,6 +420,7 @@ impl AsRef<str> for SignerSourceKind {
             Self::Usb(_) => SIGNER_SOURCE_USB,
             Self::Stdin => SIGNER_SOURCE_STDIN,
             Self::Pubkey(_) => SIGNER_SOURCE_PUBKEY,
+            Self::Env(_) => SIGNER_SOURCE_ENV,
         }
     }
 }
@@ -484,6 +487,9 @@ pub(crate) fn parse_signer_source<S: AsRef<str>>(
                         legacy: false,
                     }),
                     SIGNER_SOURCE_STDIN => Ok(SignerSource::new(SignerSourceKind::Stdin)),
+                    SIGNER_SOURCE_ENV => Ok(SignerSource::new(SignerSourceKind::Env(
+                        uri.path().to_string(),
+                    ))),
                     _ => {
                         #[cfg(target_family = "windows")]
                         // On Windows, an absolute path's drive letter will be parsed as the URI
@@ -575,6 +581,10 @@ pub struct SignerFromPathConfig {
 ///
 ///   Non-scheme parts of the URI are ignored.
 ///
+/// - `env:` &mdash; Read the keypair from an environment variable, in the JSON
+///   format used by the keypair file. The path portion of the URI is the name
+///   of the variable. Example: `env:CI_KEYPAIR`.
+///
 /// - `prompt:` &mdash; The user will be prompted at the command line
 ///   for their seed phrase and passphrase.
 ///
@@ -786,6 +796,7 @@ pub fn signer_from_path_with_config(
             let mut stdin = std::io::stdin();
             Ok(Box::new(read_keypair(&mut stdin)?))
         }
+        SignerSourceKind::Env(var) => Ok(Box::new(read_keypair_env(&var)?)),
         SignerSourceKind::Usb(locator) => {
             if wallet_manager.is_none() {
                 *wallet_manager = maybe_wallet_manager()?;
@@ -913,6 +924,8 @@ pub fn resolve_signer_from_path(
             // path on disk or to a device
             read_keypair(&mut stdin).map(|_| None)
         }
+        // Likewise, there is no path for a keypair read from the environment
+        SignerSourceKind::Env(var) => read_keypair_env(&var).map(|_| None),
         SignerSourceKind::Usb(locator) => {
             if wallet_manager.is_none() {
                 *wallet_manager = maybe_wallet_manager()?;
@@ -935,6 +948,32 @@ pub fn resolve_signer_from_path(
     }
 }
 
+// Reads a JSON keypair from the environment variable `var`. The variable's
+// value is never included in errors, since it holds a secret key.
+fn read_keypair_env(var: &str) -> Result<Keypair, Box<dyn error::Error>> {
+    let value = std::env::var(var).map_err(|err| {
+        // `VarError::NotUnicode` carries the raw value, so don't display `err`
+        let reason = match err {
+            std::env::VarError::NotPresent => "is not set",
+            std::env::VarError::NotUnicode(_) => "is not valid unicode",
+        };
+        std::io::Error::new(
+            std::io::ErrorKind::Other,
+            format!("could not read keypair from environment variable \"{var}\": it {reason}"),
+        )
+    })?;
+    read_keypair(&mut value.as_bytes()).map_err(|_| {
+        std::io::Error::new(
+            std::io::ErrorKind::Other,
+            format!(
+                "environment variable \"{var}\" does not contain a keypair in the JSON \
+                 format used by keypair files"
+            ),
+        )
+        .into()
+    })
+}
+
 // Keyword used to indicate that the user should be prompted for a keypair seed phrase
 pub const ASK_KEYWORD: &str = "ASK";
 
@@ -1033,6 +1072,7 @@ pub fn keypair_from_path(
             let mut stdin = std::io::stdin();
             Ok(read_keypair(&mut stdin)?)
         }
+        SignerSourceKind::Env(var) => read_keypair_env(&var),
         _ => Err(std::io::Error::new(
             std::io::ErrorKind::Other,
             format!("signer of type `{kind:?}` does not support Keypair output"),
@@ -1263,6 +1303,12 @@ mod tests {
             Err(SignerSourceError::IoError(_))
         );
 
+        assert_matches!(parse_signer_source("env:CI_KEYPAIR").unwrap(), SignerSource {
+            kind: SignerSourceKind::Env(v),
+            derivation_path: None,
+            legacy: false,
+        } if v == "CI_KEYPAIR");
+
         let prompt = "prompt:".to_string();
         assert_matches!(
             parse_signer_source(prompt).unwrap(),
@@ -1288,6 +1334,39 @@ mod tests {
         );
     }
 
+    #[test]
+    fn signer_from_path_with_env() {
+        let keypair = Keypair::new();
+        let var = "SOLANA_CLAP_UTILS_TEST_SIGNER_FROM_PATH_WITH_ENV";
+        std::env::set_var(var, format!("{:?}", keypair.to_bytes()));
+        let path = format!("env:{var}");
+
+        let clap_matches = App::new("my-program").get_matches_from(vec!["program"]);
+        let signer = signer_from_path(&clap_matches, &path, "signer", &mut None).unwrap();
+        assert_eq!(signer.pubkey(), keypair.pubkey());
+        let signer_keypair = keypair_from_path(&clap_matches, &path, "signer", false).unwrap();
+        assert_eq!(signer_keypair.pubkey(), keypair.pubkey());
+        assert_eq!(
+            resolve_signer_from_path(&clap_matches, &path, "signer", &mut None).unwrap(),
+            None
+        );
+
+        // The value is not echoed back when it doesn't parse
+        std::env::set_var(var, "not a keypair");
+        let err = signer_from_path(&clap_matches, &path, "signer", &mut None)
+            .unwrap_err()
+            .to_string();
+        assert!(err.contains(var), "{err}");
+        assert!(!err.contains("not a keypair"), "{err}");
+
+        std::env::remove_var(var);
+        let err = signer_from_path(&clap_matches, &path, "signer", &mut None)
+            .unwrap_err()
+            .to_string();
+        assert!(err.contains(var), "{err}");
+        assert!(err.contains("is not set"), "{err}");
+    }
+
     #[test]
     fn signer_from_path_with_file() -> Result<(), Box<dyn std::error::Error>> {
         let dir = TempDir::new()?;
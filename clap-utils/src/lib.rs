@@ -36,3 +36,4 @@ pub mod keypair;
 pub mod memo;
 pub mod nonce;
 pub mod offline;
+pub mod signer_set;
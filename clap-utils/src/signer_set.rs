@@ -0,0 +1,277 @@
+//! Assembling the signatures of a transaction from several signing sources.
+//!
+//! Offline and multisig flows combine [Signer]s which are available locally
+//! with signatures produced elsewhere, usually passed around as base58
+//! strings, and with signers which are knowingly left out, as when signing
+//! with `--sign-only`. [`SignerSet`] checks each of these against the
+//! [`Message`] and produces the signatures in the order the message requires.
+
+use {
+    solana_presigner::Presigner,
+    solana_program::message::Message,
+    solana_pubkey::Pubkey,
+    solana_signature::Signature,
+    solana_signer::{Signer, SignerError},
+    std::{
+        collections::{HashMap, HashSet},
+        str::FromStr,
+    },
+    thiserror::Error,
+};
+
+#[derive(Debug, Error, PartialEq, Eq)]
+pub enum SignerSetError {
+    #[error("{0} is not a required signer of the message")]
+    UnexpectedSigner(Pubkey),
+    #[error("unable to parse the signature of {0}")]
+    InvalidSignature(Pubkey),
+    #[error("the signature of {0} does not verify the message")]
+    BadPresignature(Pubkey),
+    #[error("missing signatures for {0:?}")]
+    MissingSigners(Vec<Pubkey>),
+    #[error("unable to sign for {0}: {1}")]
+    Signer(Pubkey, SignerError),
+}
+
+/// The signers of a message, gathered from local signers, signatures made
+/// elsewhere and signers marked as absent.
+pub struct SignerSet<'a> {
+    message: &'a Message,
+    message_data: Vec<u8>,
+    signers: HashMap<Pubkey, &'a dyn Signer>,
+    presigners: HashMap<Pubkey, Presigner>,
+    absent_signers: HashSet<Pubkey>,
+}
+
+impl<'a> SignerSet<'a> {
+    /// Create a `SignerSet` for `message`.
+    ///
+    /// `signers` and `presignatures` may be given in any order. Each must be a
+    /// required signer of `message`, and each of the base58 encoded
+    /// `presignatures` must verify the message.
+    pub fn new(
+        message: &'a Message,
+        signers: &[&'a dyn Signer],
+        presignatures: &HashMap<Pubkey, String>,
+    ) -> Result<Self, SignerSetError> {
+        let mut signer_set = Self {
+            message,
+            message_data: message.serialize(),
+            signers: HashMap::new(),
+            presigners: HashMap::new(),
+            absent_signers: HashSet::new(),
+        };
+        for signer in signers {
+            let pubkey = signer.pubkey();
+            signer_set.check_required(&pubkey)?;
+            signer_set.signers.entry(pubkey).or_insert(*signer);
+        }
+        for (pubkey, signature) in presignatures {
+            signer_set.check_required(pubkey)?;
+            let signature = Signature::from_str(signature)
+                .map_err(|_| SignerSetError::InvalidSignature(*pubkey))?;
+            if !signature.verify(pubkey.as_ref(), &signer_set.message_data) {
+                return Err(SignerSetError::BadPresignature(*pubkey));
+            }
+            signer_set
+                .presigners
+                .insert(*pubkey, Presigner::new(pubkey, &signature));
+        }
+        Ok(signer_set)
+    }
+
+    /// Mark a required signer as knowingly left out, so that its signature is
+    /// left as the default signature as by a `NullSigner`
+    pub fn mark_absent(&mut self, pubkey: &Pubkey) -> Result<(), SignerSetError> {
+        self.check_required(pubkey)?;
+        self.absent_signers.insert(*pubkey);
+        Ok(())
+    }
+
+    /// The required signers which are neither available nor marked absent, in
+    /// message order
+    pub fn unsigned(&self) -> Vec<Pubkey> {
+        self.required_signers()
+            .filter(|pubkey| {
+                !self.signers.contains_key(pubkey)
+                    && !self.presigners.contains_key(pubkey)
+                    && !self.absent_signers.contains(pubkey)
+            })
+            .copied()
+            .collect()
+    }
+
+    /// The required signers which were marked absent, in message order
+    pub fn absent(&self) -> Vec<Pubkey> {
+        self.required_signers()
+            .filter(|pubkey| {
+                !self.signers.contains_key(pubkey)
+                    && !self.presigners.contains_key(pubkey)
+                    && self.absent_signers.contains(pubkey)
+            })
+            .copied()
+            .collect()
+    }
+
+    /// Sign the message, returning a signature for each required signer in
+    /// the order expected by `Transaction::signatures`
+    pub fn signatures(&self) -> Result<Vec<Signature>, SignerSetError> {
+        let unsigned = self.unsigned();
+        if !unsigned.is_empty() {
+            return Err(SignerSetError::MissingSigners(unsigned));
+        }
+        self.required_signers()
+            .map(|pubkey| {
+                let signature = if let Some(presigner) = self.presigners.get(pubkey) {
+                    presigner.try_sign_message(&self.message_data)
+                } else if let Some(signer) = self.signers.get(pubkey) {
+                    signer.try_sign_message(&self.message_data)
+                } else {
+                    Ok(Signature::default())
+                };
+                signature.map_err(|err| SignerSetError::Signer(*pubkey, err))
+            })
+            .collect()
+    }
+
+    fn required_signers(&self) -> impl Iterator<Item = &Pubkey> {
+        self.message
+            .account_keys
+            .iter()
+            .take(usize::from(self.message.header.num_required_signatures))
+    }
+
+    fn check_required(&self, pubkey: &Pubkey) -> Result<(), SignerSetError> {
+        if self.required_signers().any(|signer| signer == pubkey) {
+            Ok(())
+        } else {
+            Err(SignerSetError::UnexpectedSigner(*pubkey))
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use {
+        super::*,
+        solana_keypair::Keypair,
+        solana_program::instruction::{AccountMeta, Instruction},
+    };
+
+    fn multisig_message(payer: &Pubkey, signers: &[Pubkey]) -> Message {
+        let instruction = Instruction::new_with_bytes(
+            Pubkey::new_unique(),
+            &[],
+            signers
+                .iter()
+                .map(|signer| AccountMeta::new_readonly(*signer, true))
+                .collect(),
+        );
+        Message::new(&[instruction], Some(payer))
+    }
+
+    fn verify_signatures(message: &Message, signatures: &[Signature]) -> bool {
+        let message_data = message.serialize();
+        signatures.len() == usize::from(message.header.num_required_signatures)
+            && signatures
+                .iter()
+                .zip(&message.account_keys)
+                .all(|(signature, pubkey)| signature.verify(pubkey.as_ref(), &message_data))
+    }
+
+    #[test]
+    fn test_signer_set_assembles_in_message_order() {
+        let payer = Keypair::new();
+        let local = Keypair::new();
+        let remote = Keypair::new();
+        let message = multisig_message(&payer.pubkey(), &[local.pubkey(), remote.pubkey()]);
+        let presignatures = HashMap::from([(
+            remote.pubkey(),
+            remote.sign_message(&message.serialize()).to_string(),
+        )]);
+
+        // signers given in the opposite order to the message
+        let signer_set = SignerSet::new(&message, &[&local, &payer], &presignatures).unwrap();
+        assert!(signer_set.unsigned().is_empty());
+        assert!(signer_set.absent().is_empty());
+        let signatures = signer_set.signatures().unwrap();
+        assert!(verify_signatures(&message, &signatures));
+    }
+
+    #[test]
+    fn test_signer_set_missing_and_absent_signers() {
+        let payer = Keypair::new();
+        let first = Pubkey::new_unique();
+        let second = Pubkey::new_unique();
+        let message = multisig_message(&payer.pubkey(), &[first, second]);
+
+        let mut signer_set = SignerSet::new(&message, &[&payer], &HashMap::new()).unwrap();
+        assert_eq!(signer_set.unsigned(), vec![first, second]);
+        assert_eq!(
+            signer_set.signatures(),
+            Err(SignerSetError::MissingSigners(vec![first, second]))
+        );
+
+        signer_set.mark_absent(&second).unwrap();
+        assert_eq!(signer_set.unsigned(), vec![first]);
+        assert_eq!(signer_set.absent(), vec![second]);
+        assert_eq!(
+            signer_set.signatures(),
+            Err(SignerSetError::MissingSigners(vec![first]))
+        );
+
+        signer_set.mark_absent(&first).unwrap();
+        let signatures = signer_set.signatures().unwrap();
+        assert!(signatures[0].verify(payer.pubkey().as_ref(), &message.serialize()));
+        assert_eq!(
+            signatures[1..],
+            [Signature::default(), Signature::default()]
+        );
+
+        let unexpected = Pubkey::new_unique();
+        assert_eq!(
+            signer_set.mark_absent(&unexpected),
+            Err(SignerSetError::UnexpectedSigner(unexpected))
+        );
+    }
+
+    #[test]
+    fn test_signer_set_bad_presignatures() {
+        let payer = Keypair::new();
+        let remote = Keypair::new();
+        let message = multisig_message(&payer.pubkey(), &[remote.pubkey()]);
+
+        // signed a different message
+        let wrong_message = multisig_message(&remote.pubkey(), &[]);
+        let presignatures = HashMap::from([(
+            remote.pubkey(),
+            remote.sign_message(&wrong_message.serialize()).to_string(),
+        )]);
+        assert_eq!(
+            SignerSet::new(&message, &[&payer], &presignatures).err(),
+            Some(SignerSetError::BadPresignature(remote.pubkey()))
+        );
+
+        // signed by a different key
+        let presignatures = HashMap::from([(
+            remote.pubkey(),
+            payer.sign_message(&message.serialize()).to_string(),
+        )]);
+        assert_eq!(
+            SignerSet::new(&message, &[&payer], &presignatures).err(),
+            Some(SignerSetError::BadPresignature(remote.pubkey()))
+        );
+
+        let presignatures = HashMap::from([(remote.pubkey(), "not a signature".to_string())]);
+        assert_eq!(
+            SignerSet::new(&message, &[&payer], &presignatures).err(),
+            Some(SignerSetError::InvalidSignature(remote.pubkey()))
+        );
+
+        let stranger = Keypair::new();
+        assert_eq!(
+            SignerSet::new(&message, &[&payer, &stranger], &HashMap::new()).err(),
+            Some(SignerSetError::UnexpectedSigner(stranger.pubkey()))
+        );
+    }
+}
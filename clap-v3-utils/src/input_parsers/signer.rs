@@ -23,6 +23,7 @@ const SIGNER_SOURCE_FILEPATH: &str = "file";
 const SIGNER_SOURCE_USB: &str = "usb";
 const SIGNER_SOURCE_STDIN: &str = "stdin";
 const SIGNER_SOURCE_PUBKEY: &str = "pubkey";
+const SIGNER_SOURCE_ENV: &str = "env";
 
 #[derive(Debug, Error)]
 pub enum SignerSourceError {
@@ -45,6 +46,7 @@ pub enum SignerSourceKind {
     Usb(RemoteWalletLocator),
     Stdin,
     Pubkey(Pubkey),
+    Env(String),
 }
 
 impl AsRef<str> for SignerSourceKind {
@@ -55,6 +57,7 @@ impl AsRef<str> for SignerSourceKind {
             Self::Usb(_) => SIGNER_SOURCE_USB,
             Self::Stdin => SIGNER_SOURCE_STDIN,
             Self::Pubkey(_) => SIGNER_SOURCE_PUBKEY,
+            Self::Env(_) => SIGNER_SOURCE_ENV,
         }
     }
 }
@@ -237,6 +240,9 @@ impl SignerSource {
                             legacy: false,
                         }),
                         SIGNER_SOURCE_STDIN => Ok(SignerSource::new(SignerSourceKind::Stdin)),
+                        SIGNER_SOURCE_ENV => Ok(SignerSource::new(SignerSourceKind::Env(
+                            uri.path().to_string(),
+                        ))),
                         _ => {
                             #[cfg(target_family = "windows")]
                             // On Windows, an absolute path's drive letter will be parsed as the URI
@@ -274,6 +280,7 @@ pub struct SignerSourceParserBuilder {
     allow_usb: bool,
     allow_stdin: bool,
     allow_pubkey: bool,
+    allow_env: bool,
     allow_legacy: bool,
 }
 
@@ -284,6 +291,7 @@ impl SignerSourceParserBuilder {
         self.allow_usb = true;
         self.allow_stdin = true;
         self.allow_pubkey = true;
+        self.allow_env = true;
         self.allow_legacy = true;
         self
     }
@@ -313,6 +321,11 @@ impl SignerSourceParserBuilder {
         self
     }
 
+    pub fn allow_env(mut self) -> Self {
+        self.allow_env = true;
+        self
+    }
+
     pub fn allow_legacy(mut self) -> Self {
         self.allow_legacy = true;
         self
@@ -331,6 +344,7 @@ impl SignerSourceParserBuilder {
                     SignerSourceKind::Usb(_) if self.allow_usb => Ok(signer_source),
                     SignerSourceKind::Stdin if self.allow_stdin => Ok(signer_source),
                     SignerSourceKind::Pubkey(_) if self.allow_pubkey => Ok(signer_source),
+                    SignerSourceKind::Env(_) if self.allow_env => Ok(signer_source),
                     _ => Err(SignerSourceError::UnsupportedSource),
                 }
             },
@@ -633,6 +647,12 @@ mod tests {
             Err(SignerSourceError::IoError(_))
         );
 
+        assert_matches!(SignerSource::parse("env:CI_KEYPAIR").unwrap(), SignerSource {
+            kind: SignerSourceKind::Env(v),
+            derivation_path: None,
+            legacy: false,
+        } if v == "CI_KEYPAIR");
+
         let prompt = "prompt:".to_string();
         assert_matches!(
             SignerSource::parse(prompt).unwrap(),
@@ -431,6 +431,10 @@ pub struct SignerFromPathConfig {
 ///
 ///   Non-scheme parts of the URI are ignored.
 ///
+/// - `env:` &mdash; Read the keypair from an environment variable, in the JSON
+///   format used by the keypair file. The path portion of the URI is the name
+///   of the variable. Example: `env:CI_KEYPAIR`.
+///
 /// - `prompt:` &mdash; The user will be prompted at the command line
 ///   for their seed phrase and passphrase.
 ///
@@ -663,6 +667,7 @@ pub fn signer_from_source_with_config(
             let mut stdin = std::io::stdin();
             Ok(Box::new(read_keypair(&mut stdin)?))
         }
+        SignerSourceKind::Env(var) => Ok(Box::new(read_encodable_key_env::<Keypair>(var)?)),
         SignerSourceKind::Usb(locator) => {
             if wallet_manager.is_none() {
                 *wallet_manager = maybe_wallet_manager()?;
@@ -810,6 +815,8 @@ pub fn resolve_signer_from_source(
             // path on disk or to a device
             read_keypair(&mut stdin).map(|_| None)
         }
+        // Likewise, there is no path for a keypair read from the environment
+        SignerSourceKind::Env(var) => read_encodable_key_env::<Keypair>(var).map(|_| None),
         SignerSourceKind::Usb(locator) => {
             if wallet_manager.is_none() {
                 *wallet_manager = maybe_wallet_manager()?;
@@ -833,6 +840,32 @@ pub fn resolve_signer_from_source(
     }
 }
 
+// Reads a JSON key from the environment variable `var`. The variable's value
+// is never included in errors, since it holds a secret key.
+fn read_encodable_key_env<K: EncodableKey>(var: &str) -> Result<K, Box<dyn error::Error>> {
+    let value = std::env::var(var).map_err(|err| {
+        // `VarError::NotUnicode` carries the raw value, so don't display `err`
+        let reason = match err {
+            std::env::VarError::NotPresent => "is not set",
+            std::env::VarError::NotUnicode(_) => "is not valid unicode",
+        };
+        std::io::Error::new(
+            std::io::ErrorKind::Other,
+            format!("could not read keypair from environment variable \"{var}\": it {reason}"),
+        )
+    })?;
+    K::read(&mut value.as_bytes()).map_err(|_| {
+        std::io::Error::new(
+            std::io::ErrorKind::Other,
+            format!(
+                "environment variable \"{var}\" does not contain a keypair in the JSON \
+                 format used by keypair files"
+            ),
+        )
+        .into()
+    })
+}
+
 // Keyword used to indicate that the user should be prompted for a keypair seed phrase
 pub const ASK_KEYWORD: &str = "ASK";
 
@@ -1085,6 +1118,7 @@ fn encodable_key_from_source<K: EncodableKey + SeedDerivable>(
             let mut stdin = std::io::stdin();
             Ok(K::read(&mut stdin)?)
         }
+        SignerSourceKind::Env(var) => read_encodable_key_env(var),
         _ => Err(std::io::Error::new(
             std::io::ErrorKind::Other,
             format!("signer of type `{kind:?}` does not support Keypair output"),
@@ -1256,6 +1290,39 @@ mod tests {
         assert_eq!(signer_pubkeys, expect);
     }
 
+    #[test]
+    fn signer_from_source_with_env() {
+        let keypair = Keypair::new();
+        let var = "SOLANA_CLAP_V3_UTILS_TEST_SIGNER_FROM_SOURCE_WITH_ENV";
+        std::env::set_var(var, format!("{:?}", keypair.to_bytes()));
+        let source = SignerSource::parse(format!("env:{var}")).unwrap();
+
+        let clap_matches = Command::new("my-program").get_matches_from(vec!["program"]);
+        let signer = signer_from_source(&clap_matches, &source, "signer", &mut None).unwrap();
+        assert_eq!(signer.pubkey(), keypair.pubkey());
+        let signer_keypair = keypair_from_source(&clap_matches, &source, "signer", false).unwrap();
+        assert_eq!(signer_keypair.pubkey(), keypair.pubkey());
+        assert_eq!(
+            resolve_signer_from_source(&clap_matches, &source, "signer", &mut None).unwrap(),
+            None
+        );
+
+        // The value is not echoed back when it doesn't parse
+        std::env::set_var(var, "not a keypair");
+        let err = signer_from_source(&clap_matches, &source, "signer", &mut None)
+            .unwrap_err()
+            .to_string();
+        assert!(err.contains(var), "{err}");
+        assert!(!err.contains("not a keypair"), "{err}");
+
+        std::env::remove_var(var);
+        let err = signer_from_source(&clap_matches, &source, "signer", &mut None)
+            .unwrap_err()
+            .to_string();
+        assert!(err.contains(var), "{err}");
+        assert!(err.contains("is not set"), "{err}");
+    }
+
     #[test]
     #[allow(deprecated)]
     fn signer_from_path_with_file() -> Result<(), Box<dyn std::error::Error>> {
@@ -22,8 +22,8 @@ use {
     },
     solana_clap_utils::keypair::SignOnly,
     solana_rpc_client_api::response::{
-        RpcAccountBalance, RpcContactInfo, RpcInflationGovernor, RpcInflationRate, RpcKeyedAccount,
-        RpcSupply, RpcVoteAccountInfo,
+        RpcAccountBalance, RpcContactInfo, RpcForks, RpcInflationGovernor, RpcInflationRate,
+        RpcKeyedAccount, RpcSupply, RpcVoteAccountInfo,
     },
     solana_sdk::{
         account::ReadableAccount,
@@ -1805,6 +1805,79 @@ pub struct CliLeaderScheduleEntry {
     pub leader: String,
 }
 
+#[derive(Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct CliForks {
+    #[serde(flatten)]
+    pub forks: RpcForks,
+}
+
+impl QuietDisplay for CliForks {}
+impl VerboseDisplay for CliForks {}
+
+impl CliForks {
+    fn write_fork(&self, f: &mut fmt::Formatter, slot: Slot, depth: usize) -> fmt::Result {
+        let Some(fork) = self.forks.forks.iter().find(|fork| fork.slot == slot) else {
+            return Ok(());
+        };
+        let stake_percent = if self.forks.total_stake > 0 {
+            100f64 * fork.stake as f64 / self.forks.total_stake as f64
+        } else {
+            0f64
+        };
+        let mut labels = vec![];
+        if fork.is_heaviest {
+            labels.push("heaviest");
+        }
+        if fork.is_locked_out {
+            labels.push("locked out");
+        }
+        if self.forks.last_vote_slot == Some(slot) {
+            labels.push("last vote");
+        }
+        write!(
+            f,
+            "{:indent$}{} {:>6.2}%",
+            "",
+            slot,
+            stake_percent,
+            indent = depth * 2
+        )?;
+        if labels.is_empty() {
+            writeln!(f)?;
+        } else {
+            writeln!(f, " ({})", labels.join(", "))?;
+        }
+        for child in self
+            .forks
+            .forks
+            .iter()
+            .filter(|fork| fork.parent_slot == Some(slot))
+        {
+            self.write_fork(f, child.slot, depth + 1)?;
+        }
+        Ok(())
+    }
+}
+
+impl fmt::Display for CliForks {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        writeln_name_value(f, "Root Slot:", &self.forks.root.to_string())?;
+        writeln_name_value(f, "Heaviest Slot:", &self.forks.heaviest_slot.to_string())?;
+        writeln_name_value(
+            f,
+            "Last Vote Slot:",
+            &self
+                .forks
+                .last_vote_slot
+                .map(|slot| slot.to_string())
+                .unwrap_or_else(|| "none".to_string()),
+        )?;
+        writeln!(f)?;
+        self.write_fork(f, self.forks.root, 0)
+    }
+}
+
 #[derive(Serialize, Deserialize)]
 #[serde(rename_all = "camelCase")]
 pub struct CliInflation {
@@ -2704,52 +2777,135 @@ pub struct CliBlock {
     pub encoded_confirmed_block: EncodedConfirmedBlock,
     #[serde(skip_serializing)]
     pub slot: Slot,
+    /// If true, every transaction is displayed in full, rather than summarized. This needs the
+    /// block to be fetched with full transaction details.
+    #[serde(skip)]
+    pub show_transactions: bool,
 }
 
 impl QuietDisplay for CliBlock {}
-impl VerboseDisplay for CliBlock {}
+impl VerboseDisplay for CliBlock {
+    fn write_str(&self, w: &mut dyn std::fmt::Write) -> fmt::Result {
+        self.write_summary(w)?;
+        self.write_rewards(w)?;
+        if self.show_transactions {
+            return self.write_transactions(w);
+        }
+        if !self.encoded_confirmed_block.transactions.is_empty() {
+            writeln!(w, "Transactions:")?;
+            writeln!(
+                w,
+                "  {:<88}  {:<15}  {:<6}  Programs",
+                "Signature", "Fee", "Status"
+            )?;
+        }
+        for transaction_with_meta in &self.encoded_confirmed_block.transactions {
+            let meta = transaction_with_meta.meta.as_ref();
+            let program_ids = meta
+                .and_then(|meta| Option::<&Vec<String>>::from(meta.log_messages.as_ref()))
+                .map(|log_messages| invoked_program_ids(log_messages).join(", "))
+                .filter(|program_ids| !program_ids.is_empty())
+                .unwrap_or_else(|| "-".to_string());
+            writeln!(
+                w,
+                "  {:<88}  {:<15}  {:<6}  {}",
+                transaction_signature(&transaction_with_meta.transaction)
+                    .unwrap_or_else(|| "-".to_string()),
+                meta.map(|meta| format!("◎{}", lamports_to_sol(meta.fee)))
+                    .unwrap_or_else(|| "-".to_string()),
+                match meta.map(|meta| &meta.err) {
+                    Some(None) => "Ok",
+                    Some(Some(_)) => "Failed",
+                    None => "-",
+                },
+                program_ids,
+            )?;
+            if let Some(err) = meta.and_then(|meta| meta.err.as_ref()) {
+                writeln!(w, "    Error: {err}")?;
+            }
+        }
+        Ok(())
+    }
+}
 
-impl fmt::Display for CliBlock {
-    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
-        writeln!(f, "Slot: {}", self.slot)?;
+impl CliBlock {
+    /// Writes the block's summary: its position, blockhashes, and transaction, fee and reward
+    /// totals
+    fn write_summary(&self, w: &mut dyn fmt::Write) -> fmt::Result {
+        writeln!(w, "Slot: {}", self.slot)?;
         writeln!(
-            f,
+            w,
             "Parent Slot: {}",
             self.encoded_confirmed_block.parent_slot
         )?;
-        writeln!(f, "Blockhash: {}", self.encoded_confirmed_block.blockhash)?;
+        writeln!(w, "Blockhash: {}", self.encoded_confirmed_block.blockhash)?;
         writeln!(
-            f,
+            w,
             "Previous Blockhash: {}",
             self.encoded_confirmed_block.previous_blockhash
         )?;
         if let Some(block_time) = self.encoded_confirmed_block.block_time {
             writeln!(
-                f,
+                w,
                 "Block Time: {:?}",
                 Local.timestamp_opt(block_time, 0).unwrap()
             )?;
         }
         if let Some(block_height) = self.encoded_confirmed_block.block_height {
-            writeln!(f, "Block Height: {block_height:?}")?;
+            writeln!(w, "Block Height: {block_height:?}")?;
+        }
+        let transactions = &self.encoded_confirmed_block.transactions;
+        let num_vote_transactions = transactions
+            .iter()
+            .filter(|transaction_with_meta| is_vote_transaction(&transaction_with_meta.transaction))
+            .count();
+        writeln!(
+            w,
+            "Transactions: {} ({} vote, {} non-vote)",
+            transactions.len(),
+            num_vote_transactions,
+            transactions.len() - num_vote_transactions,
+        )?;
+        let total_fees: u64 = transactions
+            .iter()
+            .filter_map(|transaction_with_meta| transaction_with_meta.meta.as_ref())
+            .map(|meta| meta.fee)
+            .sum();
+        writeln!(w, "Total Fees: ◎{}", lamports_to_sol(total_fees))?;
+        if !self.encoded_confirmed_block.rewards.is_empty() {
+            let total_rewards: i64 = self
+                .encoded_confirmed_block
+                .rewards
+                .iter()
+                .map(|reward| reward.lamports)
+                .sum();
+            let sign = if total_rewards < 0 { "-" } else { "" };
+            writeln!(
+                w,
+                "Total Rewards: {}◎{:<12.9}",
+                sign,
+                lamports_to_sol(total_rewards.unsigned_abs())
+            )?;
         }
+        Ok(())
+    }
+
+    /// Writes the table of rewards paid in the block
+    fn write_rewards(&self, w: &mut dyn fmt::Write) -> fmt::Result {
         if !self.encoded_confirmed_block.rewards.is_empty() {
             let mut rewards = self.encoded_confirmed_block.rewards.clone();
             rewards.sort_by(|a, b| a.pubkey.cmp(&b.pubkey));
-            let mut total_rewards = 0;
-            writeln!(f, "Rewards:")?;
+            writeln!(w, "Rewards:")?;
             writeln!(
-                f,
+                w,
                 "  {:<44}  {:^15}  {:<15}  {:<20}  {:>14}  {:>10}",
                 "Address", "Type", "Amount", "New Balance", "Percent Change", "Commission"
             )?;
             for reward in rewards {
                 let sign = if reward.lamports < 0 { "-" } else { "" };
-
-                total_rewards += reward.lamports;
                 #[allow(clippy::format_in_format_args)]
                 writeln!(
-                    f,
+                    w,
                     "  {:<44}  {:^15}  {:>15}  {}  {}",
                     reward.pubkey,
                     if let Some(reward_type) = reward.reward_type {
@@ -2779,32 +2935,90 @@ impl fmt::Display for CliBlock {
                         .unwrap_or_else(|| "    -".to_string())
                 )?;
             }
-
-            let sign = if total_rewards < 0 { "-" } else { "" };
-            writeln!(
-                f,
-                "Total Rewards: {}◎{:<12.9}",
-                sign,
-                lamports_to_sol(total_rewards.unsigned_abs())
-            )?;
         }
+        Ok(())
+    }
+
+    /// Writes every transaction of the block in full
+    fn write_transactions(&self, w: &mut dyn fmt::Write) -> fmt::Result {
         for (index, transaction_with_meta) in
             self.encoded_confirmed_block.transactions.iter().enumerate()
         {
-            writeln!(f, "Transaction {index}:")?;
-            writeln_transaction(
-                f,
-                &transaction_with_meta.transaction.decode().unwrap(),
-                transaction_with_meta.meta.as_ref(),
-                "  ",
-                None,
-                None,
-            )?;
+            writeln!(w, "Transaction {index}:")?;
+            match transaction_with_meta.transaction.decode() {
+                Some(transaction) => writeln_transaction(
+                    w,
+                    &transaction,
+                    transaction_with_meta.meta.as_ref(),
+                    "  ",
+                    None,
+                    None,
+                )?,
+                // only fully fetched transactions can be decoded
+                None => writeln!(w, "  Unable to decode transaction")?,
+            }
+        }
+        Ok(())
+    }
+}
+
+impl fmt::Display for CliBlock {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        self.write_summary(f)?;
+        self.write_rewards(f)?;
+        if self.show_transactions {
+            self.write_transactions(f)?;
         }
         Ok(())
     }
 }
 
+/// Returns the first signature of `transaction`, which identifies it
+fn transaction_signature(transaction: &EncodedTransaction) -> Option<String> {
+    match transaction {
+        EncodedTransaction::Accounts(accounts_list) => accounts_list.signatures.first().cloned(),
+        EncodedTransaction::Json(ui_transaction) => ui_transaction.signatures.first().cloned(),
+        _ => transaction
+            .decode()
+            .and_then(|transaction| transaction.signatures.first().map(ToString::to_string)),
+    }
+}
+
+/// Returns true if `transaction` references the vote program
+fn is_vote_transaction(transaction: &EncodedTransaction) -> bool {
+    let vote_program_id = solana_vote_program::id();
+    match transaction {
+        EncodedTransaction::Accounts(accounts_list) => accounts_list
+            .account_keys
+            .iter()
+            .any(|account| account.pubkey == vote_program_id.to_string()),
+        _ => transaction.decode().is_some_and(|transaction| {
+            transaction
+                .message
+                .static_account_keys()
+                .contains(&vote_program_id)
+        }),
+    }
+}
+
+/// Returns the ids of the programs invoked according to `log_messages`, in order of first
+/// invocation
+fn invoked_program_ids(log_messages: &[String]) -> Vec<&str> {
+    let mut program_ids = vec![];
+    for log_message in log_messages {
+        let program_id = log_message
+            .strip_prefix("Program ")
+            .and_then(|rest| rest.split_once(" invoke ["))
+            .map(|(program_id, _)| program_id);
+        if let Some(program_id) = program_id {
+            if !program_ids.contains(&program_id) {
+                program_ids.push(program_id);
+            }
+        }
+    }
+    program_ids
+}
+
 #[derive(Serialize, Deserialize)]
 #[serde(rename_all = "camelCase")]
 pub struct CliTransaction {
@@ -2845,6 +3059,11 @@ impl fmt::Display for CliTransaction {
 #[serde(rename_all = "camelCase")]
 pub struct CliTransactionConfirmation {
     pub confirmation_status: Option<TransactionConfirmationStatus>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub slot: Option<Slot>,
+    /// Number of blocks since the transaction's slot, `None` once it is rooted
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub confirmations: Option<usize>,
     #[serde(flatten, skip_serializing_if = "Option::is_none")]
     pub transaction: Option<CliTransaction>,
     #[serde(skip_serializing)]
@@ -2882,16 +3101,23 @@ impl VerboseDisplay for CliTransactionConfirmation {
 
 impl fmt::Display for CliTransactionConfirmation {
     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
-        match &self.confirmation_status {
-            None => write!(f, "Not found"),
-            Some(confirmation_status) => {
-                if let Some(err) = &self.err {
-                    write!(f, "Transaction failed: {err}")
-                } else {
-                    write!(f, "{confirmation_status:?}")
-                }
-            }
+        let Some(confirmation_status) = &self.confirmation_status else {
+            return write!(
+                f,
+                "Not found: no transaction with this signature has been processed"
+            );
+        };
+        write!(f, "{confirmation_status:?}")?;
+        if let Some(slot) = self.slot {
+            write!(f, " in slot {slot}")?;
+        }
+        if let Some(confirmations) = self.confirmations {
+            write!(f, " ({confirmations} confirmations)")?;
         }
+        if let Some(err) = &self.err {
+            write!(f, ", transaction failed: {err}")?;
+        }
+        Ok(())
     }
 }
 
@@ -3224,12 +3450,19 @@ mod tests {
         super::*,
         clap::{App, Arg},
         solana_sdk::{
+            instruction::Instruction,
             message::Message,
             pubkey::Pubkey,
+            reward_type::RewardType,
             signature::{keypair_from_seed, NullSigner, Signature, Signer, SignerError},
             system_instruction,
             transaction::Transaction,
         },
+        solana_transaction_status::{
+            parse_accounts::parse_legacy_message_accounts, Encodable,
+            EncodedTransactionWithStatusMeta, Reward, TransactionStatusMeta, UiAccountsList,
+            UiTransactionEncoding,
+        },
     };
 
     #[test]
@@ -3461,4 +3694,218 @@ mod tests {
         assert_eq!(s, "Account Balance: 0.00001 SOL\nValidator Identity: 11111111111111111111111111111111\nVote Authority: None\nWithdraw Authority: \nCredits: 0\nCommission: 0%\nRoot Slot: ~\nRecent Timestamp: 1970-01-01T00:00:00Z from slot 0\nEpoch Rewards:\nEpoch,Reward Slot,Time,Amount,New Balance,Percent Change,APR,Commission\n1,100,1970-01-01 00:00:00 UTC,0.00000001,0.0000001,11%,10.00%,1%\n2,200,1970-01-12 13:46:40 UTC,0.000000012,0.0000001,11%,13.00%,1%\n");
         println!("{s}");
     }
+
+    #[test]
+    fn test_cli_transaction_confirmation() {
+        let confirmation = |confirmation_status: Option<TransactionConfirmationStatus>,
+                            confirmations,
+                            err| CliTransactionConfirmation {
+            slot: confirmation_status.as_ref().map(|_| 42),
+            confirmation_status,
+            confirmations,
+            transaction: None,
+            get_transaction_error: None,
+            err,
+        };
+
+        let not_found = confirmation(None, None, None);
+        assert_eq!(
+            not_found.to_string(),
+            "Not found: no transaction with this signature has been processed"
+        );
+        assert_eq!(
+            serde_json::to_value(&not_found).unwrap(),
+            serde_json::json!({"confirmationStatus": null})
+        );
+
+        let processed = confirmation(
+            Some(TransactionConfirmationStatus::Processed),
+            Some(0),
+            None,
+        );
+        assert_eq!(
+            processed.to_string(),
+            "Processed in slot 42 (0 confirmations)"
+        );
+        let confirmed = confirmation(
+            Some(TransactionConfirmationStatus::Confirmed),
+            Some(3),
+            None,
+        );
+        assert_eq!(
+            confirmed.to_string(),
+            "Confirmed in slot 42 (3 confirmations)"
+        );
+        assert_eq!(
+            serde_json::to_value(&confirmed).unwrap(),
+            serde_json::json!({"confirmationStatus": "confirmed", "slot": 42, "confirmations": 3})
+        );
+
+        let failed = confirmation(
+            Some(TransactionConfirmationStatus::Finalized),
+            None,
+            Some(TransactionError::AccountInUse),
+        );
+        assert_eq!(
+            failed.to_string(),
+            format!(
+                "Finalized in slot 42, transaction failed: {}",
+                TransactionError::AccountInUse
+            )
+        );
+        assert_eq!(
+            serde_json::to_value(&failed).unwrap(),
+            serde_json::json!({
+                "confirmationStatus": "finalized",
+                "slot": 42,
+                "err": "AccountInUse",
+            })
+        );
+    }
+
+    #[test]
+    fn test_cli_block() {
+        let payer = Pubkey::new_unique();
+        let vote_message = Message::new(
+            &[Instruction::new_with_bytes(
+                solana_vote_program::id(),
+                &[],
+                vec![],
+            )],
+            Some(&payer),
+        );
+        let transfer_message = Message::new(
+            &[system_instruction::transfer(
+                &payer,
+                &Pubkey::new_unique(),
+                1,
+            )],
+            Some(&payer),
+        );
+        let transfer = Transaction::new_unsigned(transfer_message);
+        let system_program_id = solana_sdk::system_program::id();
+        let meta = |fee, status, log_messages| {
+            Some(UiTransactionStatusMeta::from(TransactionStatusMeta {
+                status,
+                fee,
+                log_messages,
+                ..TransactionStatusMeta::default()
+            }))
+        };
+        let transactions = vec![
+            // as fetched for the summary
+            EncodedTransactionWithStatusMeta {
+                transaction: EncodedTransaction::Accounts(UiAccountsList {
+                    signatures: vec![Signature::default().to_string()],
+                    account_keys: parse_legacy_message_accounts(&vote_message),
+                }),
+                meta: meta(5000, Ok(()), None),
+                version: None,
+            },
+            // in full
+            EncodedTransactionWithStatusMeta {
+                transaction: transfer.encode(UiTransactionEncoding::Base64),
+                meta: meta(
+                    10000,
+                    Err(TransactionError::AccountInUse),
+                    Some(vec![
+                        format!("Program {system_program_id} invoke [1]"),
+                        format!("Program {system_program_id} failed"),
+                    ]),
+                ),
+                version: None,
+            },
+        ];
+        let reward_address = Pubkey::new_unique();
+        let block = CliBlock {
+            encoded_confirmed_block: EncodedConfirmedBlock {
+                previous_blockhash: Hash::default().to_string(),
+                blockhash: Hash::new_unique().to_string(),
+                parent_slot: 41,
+                transactions,
+                rewards: vec![Reward {
+                    pubkey: reward_address.to_string(),
+                    lamports: 7500,
+                    post_balance: 1_000_007_500,
+                    reward_type: Some(RewardType::Fee),
+                    commission: None,
+                }],
+                num_partitions: None,
+                block_time: None,
+                block_height: Some(40),
+            },
+            slot: 42,
+            show_transactions: false,
+        };
+        let blockhash = &block.encoded_confirmed_block.blockhash;
+
+        let summary = format!(
+            "Slot: 42\n\
+             Parent Slot: 41\n\
+             Blockhash: {blockhash}\n\
+             Previous Blockhash: {}\n\
+             Block Height: 40\n\
+             Transactions: 2 (1 vote, 1 non-vote)\n\
+             Total Fees: ◎0.000015\n\
+             Total Rewards: ◎0.000007500 \n",
+            Hash::default(),
+        );
+        let display = OutputFormat::Display.formatted_string(&block);
+        let rewards: Vec<_> = display.strip_prefix(&summary).unwrap().lines().collect();
+        assert_eq!(rewards[0], "Rewards:");
+        assert!(rewards[2].starts_with(&format!("  {reward_address}")));
+        assert_eq!(rewards.len(), 3);
+
+        let verbose = OutputFormat::DisplayVerbose.formatted_string(&block);
+        let details = verbose.strip_prefix(&display).unwrap();
+        let lines: Vec<_> = details.lines().collect();
+        assert_eq!(lines[0], "Transactions:");
+        assert_eq!(
+            lines[2].split_whitespace().collect::<Vec<_>>(),
+            vec![
+                Signature::default().to_string().as_str(),
+                "◎0.000005",
+                "Ok",
+                "-"
+            ]
+        );
+        assert_eq!(
+            lines[3].split_whitespace().collect::<Vec<_>>(),
+            vec![
+                transfer.signatures[0].to_string().as_str(),
+                "◎0.00001",
+                "Failed",
+                system_program_id.to_string().as_str(),
+            ]
+        );
+        assert_eq!(
+            lines[4],
+            format!("    Error: {}", TransactionError::AccountInUse)
+        );
+        assert_eq!(lines.len(), 5);
+
+        // json passes the block through as fetched
+        let json: EncodedConfirmedBlock =
+            serde_json::from_str(&OutputFormat::Json.formatted_string(&block)).unwrap();
+        assert_eq!(json, block.encoded_confirmed_block);
+
+        // transactions can still be shown in full, where they were fetched in full
+        let summary_and_rewards = display;
+        let block = CliBlock {
+            show_transactions: true,
+            ..block
+        };
+        let display = OutputFormat::Display.formatted_string(&block);
+        let transactions = display.strip_prefix(&summary_and_rewards).unwrap();
+        let transaction_1 = transactions
+            .strip_prefix("Transaction 0:\n  Unable to decode transaction\nTransaction 1:\n")
+            .unwrap();
+        assert!(transaction_1.contains(&transfer.signatures[0].to_string()));
+        assert!(transaction_1.contains(&format!("Status: {}", TransactionError::AccountInUse)));
+
+        assert_eq!(
+            OutputFormat::DisplayVerbose.formatted_string(&block),
+            display
+        );
+    }
 }
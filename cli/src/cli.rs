@@ -70,8 +70,10 @@ pub enum CliCommand {
         program_id: Pubkey,
     },
     FirstAvailableBlock,
+    Forks,
     GetBlock {
         slot: Option<Slot>,
+        show_transactions: bool,
     },
     GetRecentPrioritizationFees {
         accounts: Vec<Pubkey>,
@@ -172,7 +174,7 @@ pub enum CliCommand {
         nonce_authority: SignerIndex,
         memo: Option<String>,
         destination_account_pubkey: Pubkey,
-        lamports: u64,
+        amount: SpendAmount,
         compute_unit_price: Option<u64>,
     },
     UpgradeNonceAccount {
@@ -315,6 +317,21 @@ pub enum CliCommand {
         fee_payer: SignerIndex,
         compute_unit_price: Option<u64>,
     },
+    CloseStakeAccount {
+        stake_account_pubkey: Pubkey,
+        destination_account_pubkey: Pubkey,
+        balance: Option<u64>,
+        withdraw_authority: SignerIndex,
+        custodian: Option<SignerIndex>,
+        sign_only: bool,
+        dump_transaction_message: bool,
+        blockhash_query: BlockhashQuery,
+        nonce_account: Option<Pubkey>,
+        nonce_authority: SignerIndex,
+        memo: Option<String>,
+        fee_payer: SignerIndex,
+        compute_unit_price: Option<u64>,
+    },
     // Validator Info Commands
     GetValidatorInfo(Option<Pubkey>),
     SetValidatorInfo {
@@ -323,6 +340,10 @@ pub enum CliCommand {
         info_pubkey: Option<Pubkey>,
         compute_unit_price: Option<u64>,
     },
+    RemoveValidatorInfo {
+        info_pubkey: Option<Pubkey>,
+        compute_unit_price: Option<u64>,
+    },
     // Vote Commands
     CreateVoteAccount {
         vote_account: SignerIndex,
@@ -365,6 +386,13 @@ pub enum CliCommand {
         vote_account_pubkey: Pubkey,
         destination_account_pubkey: Pubkey,
         withdraw_authority: SignerIndex,
+        allow_active_delegations: bool,
+        balance: Option<u64>,
+        sign_only: bool,
+        dump_transaction_message: bool,
+        blockhash_query: BlockhashQuery,
+        nonce_account: Option<Pubkey>,
+        nonce_authority: SignerIndex,
         memo: Option<String>,
         fee_payer: SignerIndex,
         compute_unit_price: Option<u64>,
@@ -552,6 +580,17 @@ impl CliConfig<'_> {
         }
     }
 
+    /// The commitment for RPC methods which don't serve `processed` data,
+    /// such as getTransaction: the global `--commitment`, raised to
+    /// `confirmed` if necessary
+    pub fn confirmed_commitment(&self) -> CommitmentConfig {
+        if self.commitment.is_at_least_confirmed() {
+            self.commitment
+        } else {
+            CommitmentConfig::confirmed()
+        }
+    }
+
     pub fn recent_for_tests() -> Self {
         Self {
             commitment: CommitmentConfig::processed(),
@@ -641,6 +680,7 @@ pub fn parse_command(
         ("first-available-block", Some(_matches)) => Ok(CliCommandInfo::without_signers(
             CliCommand::FirstAvailableBlock,
         )),
+        ("forks", Some(_matches)) => Ok(CliCommandInfo::without_signers(CliCommand::Forks)),
         ("genesis-hash", Some(_matches)) => {
             Ok(CliCommandInfo::without_signers(CliCommand::GetGenesisHash))
         }
@@ -727,6 +767,9 @@ pub fn parse_command(
         ("withdraw-stake", Some(matches)) => {
             parse_stake_withdraw_stake(matches, default_signer, wallet_manager)
         }
+        ("close-stake-account", Some(matches)) => {
+            parse_close_stake_account(matches, default_signer, wallet_manager)
+        }
         ("deactivate-stake", Some(matches)) => {
             parse_stake_deactivate_stake(matches, default_signer, wallet_manager)
         }
@@ -756,6 +799,9 @@ pub fn parse_command(
             ("publish", Some(matches)) => {
                 parse_validator_info_command(matches, default_signer, wallet_manager)
             }
+            ("remove", Some(matches)) => {
+                parse_remove_validator_info_command(matches, default_signer, wallet_manager)
+            }
             ("get", Some(matches)) => parse_get_validator_info_command(matches),
             _ => unreachable!(),
         },
@@ -912,7 +958,11 @@ pub fn process_command(config: &CliConfig) -> ProcessResult {
             process_find_program_derived_address(config, seeds, program_id)
         }
         CliCommand::FirstAvailableBlock => process_first_available_block(&rpc_client),
-        CliCommand::GetBlock { slot } => process_get_block(&rpc_client, config, *slot),
+        CliCommand::Forks => process_forks(&rpc_client, config),
+        CliCommand::GetBlock {
+            slot,
+            show_transactions,
+        } => process_get_block(&rpc_client, config, *slot, *show_transactions),
         CliCommand::GetBlockTime { slot } => process_get_block_time(&rpc_client, config, *slot),
         CliCommand::GetRecentPrioritizationFees {
             accounts,
@@ -1122,7 +1172,7 @@ pub fn process_command(config: &CliConfig) -> ProcessResult {
             nonce_authority,
             memo,
             destination_account_pubkey,
-            lamports,
+            amount,
             compute_unit_price,
         } => process_withdraw_from_nonce_account(
             &rpc_client,
@@ -1131,7 +1181,7 @@ pub fn process_command(config: &CliConfig) -> ProcessResult {
             *nonce_authority,
             memo.as_ref(),
             destination_account_pubkey,
-            *lamports,
+            *amount,
             *compute_unit_price,
         ),
         // Upgrade nonce account out of blockhash domain.
@@ -1432,6 +1482,37 @@ pub fn process_command(config: &CliConfig) -> ProcessResult {
             *fee_payer,
             *compute_unit_price,
         ),
+        CliCommand::CloseStakeAccount {
+            stake_account_pubkey,
+            destination_account_pubkey,
+            balance,
+            withdraw_authority,
+            custodian,
+            sign_only,
+            dump_transaction_message,
+            blockhash_query,
+            ref nonce_account,
+            nonce_authority,
+            memo,
+            fee_payer,
+            compute_unit_price,
+        } => process_close_stake_account(
+            &rpc_client,
+            config,
+            stake_account_pubkey,
+            destination_account_pubkey,
+            *balance,
+            *withdraw_authority,
+            *custodian,
+            *sign_only,
+            *dump_transaction_message,
+            blockhash_query,
+            nonce_account.as_ref(),
+            *nonce_authority,
+            memo.as_ref(),
+            *fee_payer,
+            *compute_unit_price,
+        ),
         CliCommand::StakeMinimumDelegation { use_lamports_unit } => {
             process_stake_minimum_delegation(&rpc_client, config, *use_lamports_unit)
         }
@@ -1456,6 +1537,11 @@ pub fn process_command(config: &CliConfig) -> ProcessResult {
             *info_pubkey,
             *compute_unit_price,
         ),
+        // Clear published validator info
+        CliCommand::RemoveValidatorInfo {
+            info_pubkey,
+            compute_unit_price,
+        } => process_remove_validator_info(&rpc_client, config, *info_pubkey, *compute_unit_price),
 
         // Vote Commands
 
@@ -1541,6 +1627,13 @@ pub fn process_command(config: &CliConfig) -> ProcessResult {
             vote_account_pubkey,
             withdraw_authority,
             destination_account_pubkey,
+            allow_active_delegations,
+            balance,
+            sign_only,
+            dump_transaction_message,
+            blockhash_query,
+            nonce_account,
+            nonce_authority,
             memo,
             fee_payer,
             compute_unit_price,
@@ -1550,6 +1643,13 @@ pub fn process_command(config: &CliConfig) -> ProcessResult {
             vote_account_pubkey,
             *withdraw_authority,
             destination_account_pubkey,
+            *allow_active_delegations,
+            *balance,
+            *sign_only,
+            *dump_transaction_message,
+            blockhash_query,
+            nonce_account.as_ref(),
+            *nonce_authority,
             memo.as_ref(),
             *fee_payer,
             *compute_unit_price,
@@ -1788,6 +1888,7 @@ where
 mod tests {
     use {
         super::*,
+        base64::{prelude::BASE64_STANDARD, Engine},
         serde_json::json,
         solana_rpc_client::mock_sender_for_cli::SIGNATURE,
         solana_rpc_client_api::{
@@ -1800,7 +1901,7 @@ mod tests {
             signature::{
                 keypair_from_seed, read_keypair_file, write_keypair_file, Keypair, Presigner,
             },
-            stake, system_program,
+            stake, system_program, system_transaction,
             transaction::TransactionError,
         },
         solana_transaction_status::TransactionConfirmationStatus,
@@ -2348,7 +2449,10 @@ mod tests {
             .unwrap()
             .unwrap();
         config.command = CliCommand::Confirm(missing_signature);
-        assert_eq!(process_command(&config).unwrap(), "Not found");
+        assert_eq!(
+            process_command(&config).unwrap(),
+            "Not found: no transaction with this signature has been processed"
+        );
 
         // Tx error case
         config.rpc_client = Some(Arc::new(RpcClient::new_mock("account_in_use".to_string())));
@@ -2360,7 +2464,10 @@ mod tests {
         config.command = CliCommand::Confirm(any_signature);
         assert_eq!(
             process_command(&config).unwrap(),
-            format!("Transaction failed: {}", TransactionError::AccountInUse)
+            format!(
+                "Finalized in slot 1, transaction failed: {}",
+                TransactionError::AccountInUse
+            )
         );
 
         // Failure cases
@@ -2725,6 +2832,75 @@ mod tests {
         );
     }
 
+    #[test]
+    fn test_confirmed_commitment() {
+        let mut config = CliConfig::default();
+        for commitment in [CommitmentConfig::confirmed(), CommitmentConfig::finalized()] {
+            config.commitment = commitment;
+            assert_eq!(config.confirmed_commitment(), commitment);
+        }
+        // Commands which can't serve processed data still require confirmed
+        config.commitment = CommitmentConfig::processed();
+        assert_eq!(config.confirmed_commitment(), CommitmentConfig::confirmed());
+    }
+
+    #[test]
+    fn test_decode_transaction() {
+        let test_commands = get_clap_app("test", "desc", "version");
+        let default_signer = DefaultSigner::new("", make_tmp_path("keypair_file"));
+
+        let from = keypair_from_seed(&[1u8; 32]).unwrap();
+        let to = Pubkey::new_from_array([2u8; 32]);
+        let blockhash = Hash::new_from_array([3u8; 32]);
+        let transaction = system_transaction::transfer(&from, &to, 42, blockhash);
+        let serialized = bincode::serialize(&transaction).unwrap();
+
+        for (data, encoding) in [
+            (bs58::encode(&serialized).into_string(), "base58"),
+            (BASE64_STANDARD.encode(&serialized), "base64"),
+        ] {
+            let matches = test_commands.clone().get_matches_from(vec![
+                "test",
+                "decode-transaction",
+                &data,
+                encoding,
+            ]);
+            let command_info = parse_command(&matches, &default_signer, &mut None).unwrap();
+            let decoded = VersionedTransaction::from(transaction.clone());
+            assert_eq!(
+                command_info,
+                CliCommandInfo::without_signers(CliCommand::DecodeTransaction(decoded.clone()))
+            );
+
+            let config = CliConfig {
+                command: CliCommand::DecodeTransaction(decoded),
+                ..CliConfig::default()
+            };
+            let output = process_command(&config).unwrap();
+            for expected in [
+                format!("Recent Blockhash: {blockhash}"),
+                format!("Signature 0: {} (pass)", transaction.signatures[0]),
+                format!("Account 0: srw- {} (fee payer)", from.pubkey()),
+                format!("Account 1: -rw- {to}"),
+                format!("Account 2: -r-x {}", system_program::id()),
+                "Instruction 0".to_string(),
+                format!("  Program:   {} (2)", system_program::id()),
+                format!("  Account 0: {} (0)", from.pubkey()),
+                format!("  Account 1: {to} (1)"),
+                "  Transfer { lamports: 42 }".to_string(),
+            ] {
+                assert!(output.contains(&expected), "{expected:?} not in {output}");
+            }
+        }
+
+        let matches = test_commands.clone().get_matches_from(vec![
+            "test",
+            "decode-transaction",
+            "not a transaction",
+        ]);
+        assert!(parse_command(&matches, &default_signer, &mut None).is_err());
+    }
+
     #[test]
     fn test_cli_completions() {
         let mut clap_app = get_clap_app("test", "desc", "version");
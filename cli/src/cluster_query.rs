@@ -9,7 +9,7 @@ use {
     },
     clap::{value_t, value_t_or_exit, App, AppSettings, Arg, ArgMatches, SubCommand},
     console::style,
-    crossbeam_channel::unbounded,
+    crossbeam_channel::{unbounded, RecvTimeoutError},
     serde::{Deserialize, Serialize},
     solana_clap_utils::{
         compute_budget::{compute_unit_price_arg, ComputeUnitLimit, COMPUTE_UNIT_PRICE_ARG},
@@ -38,7 +38,9 @@ use {
         },
         filter::{Memcmp, RpcFilterType},
         request::DELINQUENT_VALIDATOR_SLOT_DISTANCE,
-        response::{RpcPerfSample, RpcPrioritizationFee, SlotInfo},
+        response::{
+            Response as RpcResponse, RpcLogsResponse, RpcPerfSample, RpcPrioritizationFee, SlotInfo,
+        },
     },
     solana_sdk::{
         account::from_account,
@@ -62,7 +64,8 @@ use {
     },
     solana_tps_client::TpsClient,
     solana_transaction_status::{
-        EncodableWithMeta, EncodedConfirmedTransactionWithStatusMeta, UiTransactionEncoding,
+        EncodableWithMeta, EncodedConfirmedTransactionWithStatusMeta, TransactionDetails,
+        UiTransactionEncoding,
     },
     solana_vote_program::vote_state::VoteState,
     std::{
@@ -97,6 +100,12 @@ impl ClusterQuerySubCommands for App<'_, '_> {
                         .value_name("SLOT")
                         .takes_value(true)
                         .index(1),
+                )
+                .arg(
+                    Arg::with_name("show_transactions")
+                        .long("show-transactions")
+                        .takes_value(false)
+                        .help("Display every transaction in full"),
                 ),
         )
         .subcommand(
@@ -176,6 +185,10 @@ impl ClusterQuerySubCommands for App<'_, '_> {
             SubCommand::with_name("first-available-block")
                 .about("Get the first available block in the storage"),
         )
+        .subcommand(
+            SubCommand::with_name("forks")
+                .about("Show the node's fork choice tree, with the stake voted on each fork"),
+        )
         .subcommand(
             SubCommand::with_name("block-time")
                 .about("Get estimated production time of a block")
@@ -575,8 +588,10 @@ pub fn parse_cluster_ping(
 
 pub fn parse_get_block(matches: &ArgMatches<'_>) -> Result<CliCommandInfo, CliError> {
     let slot = value_of(matches, "slot");
+    let show_transactions = matches.is_present("show_transactions");
     Ok(CliCommandInfo::without_signers(CliCommand::GetBlock {
         slot,
+        show_transactions,
     }))
 }
 
@@ -975,6 +990,13 @@ pub fn process_first_available_block(rpc_client: &RpcClient) -> ProcessResult {
     Ok(format!("{first_available_block}"))
 }
 
+pub fn process_forks(rpc_client: &RpcClient, config: &CliConfig) -> ProcessResult {
+    let forks = rpc_client
+        .get_forks()?
+        .ok_or("Fork choice is not available yet, the node has not replayed a bank")?;
+    Ok(config.output_format.formatted_string(&CliForks { forks }))
+}
+
 pub fn parse_leader_schedule(matches: &ArgMatches<'_>) -> Result<CliCommandInfo, CliError> {
     let epoch = value_of(matches, "epoch");
     Ok(CliCommandInfo::without_signers(
@@ -1072,6 +1094,7 @@ pub fn process_get_block(
     rpc_client: &RpcClient,
     config: &CliConfig,
     slot: Option<Slot>,
+    show_transactions: bool,
 ) -> ProcessResult {
     let slot = if let Some(slot) = slot {
         slot
@@ -1079,20 +1102,30 @@ pub fn process_get_block(
         rpc_client.get_slot_with_commitment(CommitmentConfig::finalized())?
     };
 
+    // The summary only needs the fee and account keys of each transaction, so don't fetch
+    // megabytes of full transactions for it
+    let transaction_details = match config.output_format {
+        OutputFormat::Display | OutputFormat::DisplayQuiet if !show_transactions => {
+            TransactionDetails::Accounts
+        }
+        _ => TransactionDetails::Full,
+    };
     let encoded_confirmed_block = rpc_client
         .get_block_with_config(
             slot,
             RpcBlockConfig {
                 encoding: Some(UiTransactionEncoding::Base64),
-                commitment: Some(CommitmentConfig::confirmed()),
+                transaction_details: Some(transaction_details),
+                rewards: Some(true),
+                commitment: Some(config.confirmed_commitment()),
                 max_supported_transaction_version: Some(0),
-                ..RpcBlockConfig::default()
             },
         )?
         .into();
     let cli_block = CliBlock {
         encoded_confirmed_block,
         slot,
+        show_transactions,
     };
     Ok(config.output_format.formatted_string(&cli_block))
 }
@@ -1667,47 +1700,131 @@ pub fn parse_logs(
     Ok(CliCommandInfo::without_signers(CliCommand::Logs { filter }))
 }
 
+/// Delay before resubscribing after the logs websocket drops, doubled on each
+/// consecutive failure up to `MAX_LOGS_RECONNECT_DELAY`
+const LOGS_RECONNECT_DELAY: Duration = Duration::from_millis(500);
+const MAX_LOGS_RECONNECT_DELAY: Duration = Duration::from_secs(30);
+
 pub fn process_logs(config: &CliConfig, filter: &RpcTransactionLogsFilter) -> ProcessResult {
-    println!(
-        "Streaming transaction logs{}. {:?} commitment",
-        match filter {
-            RpcTransactionLogsFilter::All => "".into(),
-            RpcTransactionLogsFilter::AllWithVotes => " (including votes)".into(),
-            RpcTransactionLogsFilter::Mentions(addresses) =>
-                format!(" mentioning {}", addresses.join(",")),
-        },
-        config.commitment.commitment
+    let output_json = matches!(
+        config.output_format,
+        OutputFormat::Json | OutputFormat::JsonCompact
     );
+    if !output_json {
+        println!(
+            "Streaming transaction logs{}. {:?} commitment",
+            match filter {
+                RpcTransactionLogsFilter::All => "".into(),
+                RpcTransactionLogsFilter::AllWithVotes => " (including votes)".into(),
+                RpcTransactionLogsFilter::Mentions(addresses) =>
+                    format!(" mentioning {}", addresses.join(",")),
+            },
+            config.commitment.commitment
+        );
+    }
+
+    let exit = Arc::new(AtomicBool::new(false));
+    let handler = {
+        let exit = exit.clone();
+        move || exit.store(true, Ordering::Relaxed)
+    };
+    match ctrlc::try_set_handler(handler) {
+        // It's possible to set the ctrl-c handler more than once in testing
+        // situations, so let that case through
+        Err(ctrlc::Error::MultipleHandlers) => {}
+        result => result.expect("Error setting Ctrl-C handler"),
+    }
 
-    let (_client, receiver) = PubsubClient::logs_subscribe(
+    stream_logs(
         &config.websocket_url,
-        filter.clone(),
-        RpcTransactionLogsConfig {
-            commitment: Some(config.commitment),
+        filter,
+        config.commitment,
+        &exit,
+        |logs| {
+            if output_json {
+                println!("{}", serde_json::to_string(&logs).unwrap());
+                return;
+            }
+            println!("Transaction executed in slot {}:", logs.context.slot);
+            println!("  Signature: {}", logs.value.signature);
+            println!(
+                "  Status: {}",
+                logs.value
+                    .err
+                    .map(|err| err.to_string())
+                    .unwrap_or_else(|| "Ok".to_string())
+            );
+            println!("  Log Messages:");
+            for log in logs.value.logs {
+                println!("    {log}");
+            }
         },
-    )?;
+    );
+    Ok("".to_string())
+}
 
-    loop {
-        match receiver.recv() {
-            Ok(logs) => {
-                println!("Transaction executed in slot {}:", logs.context.slot);
-                println!("  Signature: {}", logs.value.signature);
-                println!(
-                    "  Status: {}",
-                    logs.value
-                        .err
-                        .map(|err| err.to_string())
-                        .unwrap_or_else(|| "Ok".to_string())
-                );
-                println!("  Log Messages:");
-                for log in logs.value.logs {
-                    println!("    {log}");
-                }
-            }
+/// Stream transaction logs matching `filter` to `on_logs` until `exit` is set,
+/// resubscribing with backoff whenever the websocket drops
+pub fn stream_logs<F>(
+    websocket_url: &str,
+    filter: &RpcTransactionLogsFilter,
+    commitment: CommitmentConfig,
+    exit: &AtomicBool,
+    mut on_logs: F,
+) where
+    F: FnMut(RpcResponse<RpcLogsResponse>),
+{
+    let mut reconnect_delay = LOGS_RECONNECT_DELAY;
+    while !exit.load(Ordering::Relaxed) {
+        let (client, receiver) = match PubsubClient::logs_subscribe(
+            websocket_url,
+            filter.clone(),
+            RpcTransactionLogsConfig {
+                commitment: Some(commitment),
+            },
+        ) {
+            Ok(subscription) => subscription,
             Err(err) => {
-                return Ok(format!("Disconnected: {err}"));
+                eprintln!("Unable to subscribe: {err}. Retrying in {reconnect_delay:?}");
+                sleep_unless_exit(reconnect_delay, exit);
+                reconnect_delay = reconnect_delay
+                    .saturating_mul(2)
+                    .min(MAX_LOGS_RECONNECT_DELAY);
+                continue;
+            }
+        };
+
+        while !exit.load(Ordering::Relaxed) {
+            match receiver.recv_timeout(Duration::from_millis(100)) {
+                Ok(logs) => {
+                    reconnect_delay = LOGS_RECONNECT_DELAY;
+                    on_logs(logs);
+                }
+                Err(RecvTimeoutError::Timeout) => {}
+                Err(RecvTimeoutError::Disconnected) => {
+                    eprintln!("Disconnected. Reconnecting in {reconnect_delay:?}");
+                    sleep_unless_exit(reconnect_delay, exit);
+                    break;
+                }
             }
         }
+
+        // Unsubscribing blocks until the server sends another message, which may
+        // never happen for a quiet filter, so don't hold up the caller on it
+        std::thread::spawn(move || drop(client));
+    }
+}
+
+/// Sleep for `duration`, returning early once `exit` is set
+fn sleep_unless_exit(duration: Duration, exit: &AtomicBool) {
+    const EXIT_POLL_INTERVAL: Duration = Duration::from_millis(100);
+    let deadline = Instant::now() + duration;
+    while !exit.load(Ordering::Relaxed) {
+        let remaining = deadline.saturating_duration_since(Instant::now());
+        if remaining.is_zero() {
+            break;
+        }
+        sleep(remaining.min(EXIT_POLL_INTERVAL));
     }
 }
 
@@ -2142,7 +2259,7 @@ pub fn process_transaction_history(
             before,
             until,
             limit: Some(limit),
-            commitment: Some(CommitmentConfig::confirmed()),
+            commitment: Some(config.confirmed_commitment()),
         },
     )?;
 
@@ -2179,7 +2296,7 @@ pub fn process_transaction_history(
                     &signature,
                     RpcTransactionConfig {
                         encoding: Some(UiTransactionEncoding::Base64),
-                        commitment: Some(CommitmentConfig::confirmed()),
+                        commitment: Some(config.confirmed_commitment()),
                         max_supported_transaction_version: Some(0),
                     },
                 ) {
@@ -2210,6 +2327,8 @@ pub fn process_transaction_history(
                 };
                 cli_transactions.push(CliTransactionConfirmation {
                     confirmation_status: result.confirmation_status,
+                    slot: Some(result.slot),
+                    confirmations: None,
                     transaction,
                     get_transaction_error,
                     err: result.err,
@@ -2378,6 +2497,14 @@ mod tests {
             CliCommandInfo::without_signers(CliCommand::GetEpochInfo)
         );
 
+        let test_forks = test_commands
+            .clone()
+            .get_matches_from(vec!["test", "forks"]);
+        assert_eq!(
+            parse_command(&test_forks, &default_signer, &mut None).unwrap(),
+            CliCommandInfo::without_signers(CliCommand::Forks)
+        );
+
         let test_get_genesis_hash = test_commands
             .clone()
             .get_matches_from(vec!["test", "genesis-hash"]);
@@ -2438,4 +2565,25 @@ mod tests {
             }
         );
     }
+
+    #[test]
+    fn test_sleep_unless_exit() {
+        let exit = Arc::new(AtomicBool::new(false));
+        let start = Instant::now();
+        sleep_unless_exit(Duration::from_millis(200), &exit);
+        assert!(start.elapsed() >= Duration::from_millis(200));
+
+        // Ctrl-C during the backoff doesn't wait for the rest of it
+        let setter = {
+            let exit = exit.clone();
+            std::thread::spawn(move || {
+                sleep(Duration::from_millis(100));
+                exit.store(true, Ordering::Relaxed);
+            })
+        };
+        let start = Instant::now();
+        sleep_unless_exit(MAX_LOGS_RECONNECT_DELAY, &exit);
+        assert!(start.elapsed() < MAX_LOGS_RECONNECT_DELAY);
+        setter.join().unwrap();
+    }
 }
@@ -265,3 +265,65 @@ fn do_main(matches: &ArgMatches<'_>) -> Result<(), Box<dyn error::Error>> {
     };
     Ok(())
 }
+
+#[cfg(test)]
+mod tests {
+    use {
+        super::*,
+        solana_sdk::{commitment_config::CommitmentConfig, pubkey::Pubkey, signature::Signature},
+    };
+
+    #[test]
+    fn test_parse_args_commitment() {
+        let config_dir = tempfile::tempdir().unwrap();
+        let config_file = config_dir.path().join("config.yml");
+        let config_file = config_file.to_str().unwrap();
+        Config {
+            commitment: "finalized".to_string(),
+            ..Config::default()
+        }
+        .save(config_file)
+        .unwrap();
+        let pubkey = Pubkey::new_unique().to_string();
+        let signature = Signature::new_unique().to_string();
+
+        let parse = |args: &[&str]| {
+            let matches = get_clap_app("test", "desc", "version").get_matches_from(
+                ["test", "--config", config_file]
+                    .iter()
+                    .chain(args)
+                    .collect::<Vec<_>>(),
+            );
+            let (config, _) = parse_args(&matches, &mut None).unwrap();
+            assert_eq!(
+                config.send_transaction_config.preflight_commitment,
+                Some(config.commitment.commitment)
+            );
+            config
+        };
+        let commitment = |args: &[&str]| parse(args).commitment;
+
+        // Commands use the configured commitment by default
+        assert_eq!(
+            commitment(&["balance", &pubkey]),
+            CommitmentConfig::finalized()
+        );
+        // The global --commitment overrides it, wherever it is given
+        assert_eq!(
+            commitment(&["--commitment", "processed", "balance", &pubkey]),
+            CommitmentConfig::processed()
+        );
+        assert_eq!(
+            commitment(&["balance", &pubkey, "--commitment", "confirmed"]),
+            CommitmentConfig::confirmed()
+        );
+
+        // Commands which require confirmed data still follow a stricter
+        // global commitment, but their own minimum wins over processed
+        let config = parse(&["confirm", &signature, "--commitment", "finalized"]);
+        assert_eq!(config.confirmed_commitment(), CommitmentConfig::finalized());
+        let config = parse(&["--commitment", "processed", "confirm", "-v", &signature]);
+        assert_eq!(config.commitment, CommitmentConfig::processed());
+        assert_eq!(config.confirmed_commitment(), CommitmentConfig::confirmed());
+    }
+}
@@ -9,7 +9,10 @@ use {
             simulate_and_update_compute_unit_limit, ComputeUnitConfig, WithComputeUnitConfig,
         },
         memo::WithMemo,
-        spend_utils::{resolve_spend_tx_and_check_account_balance, SpendAmount},
+        spend_utils::{
+            check_remaining_balance, resolve_spend_tx_and_check_account_balance,
+            resolve_spend_tx_and_check_account_balances, SpendAmount,
+        },
     },
     clap::{App, Arg, ArgMatches, SubCommand},
     solana_clap_utils::{
@@ -178,8 +181,11 @@ impl NonceSubCommands for App<'_, '_> {
                         .value_name("AMOUNT")
                         .takes_value(true)
                         .required(true)
-                        .validator(is_amount)
-                        .help("The amount to withdraw from the nonce account, in SOL"),
+                        .validator(is_amount_or_all)
+                        .help(
+                            "The amount to withdraw from the nonce account, in SOL; accepts \
+                             keyword ALL, which empties and closes the account",
+                        ),
                 )
                 .arg(nonce_authority_arg())
                 .arg(memo_arg())
@@ -333,7 +339,7 @@ pub fn parse_withdraw_from_nonce_account(
     let nonce_account = pubkey_of_signer(matches, "nonce_account_pubkey", wallet_manager)?.unwrap();
     let destination_account_pubkey =
         pubkey_of_signer(matches, "destination_account_pubkey", wallet_manager)?.unwrap();
-    let lamports = lamports_of_sol(matches, "amount").unwrap();
+    let amount = SpendAmount::new_from_matches(matches, "amount");
     let memo = matches.value_of(MEMO_ARG.name).map(String::from);
     let (nonce_authority, nonce_authority_pubkey) =
         signer_of(matches, NONCE_AUTHORITY_ARG.name, wallet_manager)?;
@@ -352,7 +358,7 @@ pub fn parse_withdraw_from_nonce_account(
             nonce_authority: signer_info.index_of(nonce_authority_pubkey).unwrap(),
             memo,
             destination_account_pubkey,
-            lamports,
+            amount,
             compute_unit_price,
         },
         signers: signer_info.signers,
@@ -651,26 +657,47 @@ pub fn process_withdraw_from_nonce_account(
     nonce_authority: SignerIndex,
     memo: Option<&String>,
     destination_account_pubkey: &Pubkey,
-    lamports: u64,
+    amount: SpendAmount,
     compute_unit_price: Option<u64>,
 ) -> ProcessResult {
     let latest_blockhash = rpc_client.get_latest_blockhash()?;
 
     let nonce_authority = config.signers[nonce_authority];
     let compute_unit_limit = ComputeUnitLimit::Simulated;
-    let ixs = vec![withdraw_nonce_account(
+    let build_message = |lamports| {
+        let ixs = vec![withdraw_nonce_account(
+            nonce_account,
+            &nonce_authority.pubkey(),
+            destination_account_pubkey,
+            lamports,
+        )]
+        .with_memo(memo)
+        .with_compute_unit_config(&ComputeUnitConfig {
+            compute_unit_price,
+            compute_unit_limit,
+        });
+        Message::new(&ixs, Some(&config.signers[0].pubkey()))
+    };
+    let (message, lamports) = resolve_spend_tx_and_check_account_balances(
+        rpc_client,
+        false,
+        amount,
+        &latest_blockhash,
         nonce_account,
-        &nonce_authority.pubkey(),
-        destination_account_pubkey,
-        lamports,
-    )]
-    .with_memo(memo)
-    .with_compute_unit_config(&ComputeUnitConfig {
-        compute_unit_price,
+        &config.signers[0].pubkey(),
         compute_unit_limit,
-    });
-    let mut message = Message::new(&ixs, Some(&config.signers[0].pubkey()));
-    simulate_and_update_compute_unit_limit(&compute_unit_limit, rpc_client, &mut message)?;
+        build_message,
+        config.commitment,
+    )?;
+    if let SpendAmount::Some(_) = amount {
+        let balance = rpc_client.get_balance(nonce_account)?;
+        let minimum_balance = rpc_client.get_minimum_balance_for_rent_exemption(State::size())?;
+        check_remaining_balance(
+            nonce_account,
+            balance.saturating_sub(lamports),
+            minimum_balance,
+        )?;
+    }
     let mut tx = Transaction::new_unsigned(message);
     tx.try_sign(&config.signers, latest_blockhash)?;
     check_account_for_fee_with_commitment(
@@ -953,7 +980,7 @@ mod tests {
                     nonce_authority: 0,
                     memo: None,
                     destination_account_pubkey: nonce_account_pubkey,
-                    lamports: 42_000_000_000,
+                    amount: SpendAmount::Some(42_000_000_000),
                     compute_unit_price: None,
                 },
                 signers: vec![Box::new(read_keypair_file(&default_keypair_file).unwrap())],
@@ -983,7 +1010,7 @@ mod tests {
                     nonce_authority: 1,
                     memo: None,
                     destination_account_pubkey: nonce_account_pubkey,
-                    lamports: 42_000_000_000,
+                    amount: SpendAmount::Some(42_000_000_000),
                     compute_unit_price: None,
                 },
                 signers: vec![
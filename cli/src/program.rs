@@ -468,14 +468,17 @@ impl ProgramSubCommands for App<'_, '_> {
                                      --skip-new-upgrade-authority-signer-check if you are really \
                                      confident that you are setting the correct authority. \
                                      Alternatively, If you wish to make the program immutable, \
-                                     you should ignore this arg and pass the --final flag.",
+                                     you should pass `none` or ignore this arg, along with the \
+                                     --final flag.",
                                 ),
                         )
                         .arg(
                             Arg::with_name("final")
                                 .long("final")
-                                .conflicts_with("new_upgrade_authority")
-                                .help("The program will not be upgradeable"),
+                                .help(
+                                    "The program will not be upgradeable. Required to confirm \
+                                     a new upgrade authority of `none`",
+                                ),
                         )
                         .arg(
                             Arg::with_name("skip_new_upgrade_authority_signer_check")
@@ -854,6 +857,25 @@ pub fn parse_program_subcommand(
                 signer_of(matches, "upgrade_authority", wallet_manager)?;
             let program_pubkey = pubkey_of(matches, "program_id").unwrap();
             let is_final = matches.is_present("final");
+            match matches.value_of("new_upgrade_authority") {
+                Some(new_upgrade_authority)
+                    if new_upgrade_authority.eq_ignore_ascii_case("none") =>
+                {
+                    if !is_final {
+                        return Err(CliError::BadParameter(
+                            "Setting the upgrade authority to none makes the program immutable, \
+                             pass --final to confirm"
+                                .to_string(),
+                        ));
+                    }
+                }
+                Some(_) if is_final => {
+                    return Err(CliError::BadParameter(
+                        "--final can't be used with a new upgrade authority".to_string(),
+                    ));
+                }
+                _ => {}
+            }
             let new_upgrade_authority = if is_final {
                 None
             } else {
@@ -3903,6 +3925,58 @@ mod tests {
                 ],
             }
         );
+
+        let program_pubkey = Pubkey::new_unique();
+        let blockhash = Hash::new_unique();
+        let test_command = test_commands.clone().get_matches_from(vec![
+            "test",
+            "program",
+            "set-upgrade-authority",
+            &program_pubkey.to_string(),
+            "--new-upgrade-authority",
+            "none",
+            "--final",
+            "--sign-only",
+            "--blockhash",
+            blockhash.to_string().as_str(),
+        ]);
+        assert_eq!(
+            parse_command(&test_command, &default_signer, &mut None).unwrap(),
+            CliCommandInfo {
+                command: CliCommand::Program(ProgramCliCommand::SetUpgradeAuthority {
+                    program_pubkey,
+                    upgrade_authority_index: Some(0),
+                    new_upgrade_authority: None,
+                    sign_only: true,
+                    dump_transaction_message: false,
+                    blockhash_query: BlockhashQuery::new(Some(blockhash), true, None),
+                }),
+                signers: vec![Box::new(read_keypair_file(&keypair_file).unwrap())],
+            }
+        );
+
+        // `none` must be confirmed with --final
+        let test_command = test_commands.clone().get_matches_from(vec![
+            "test",
+            "program",
+            "set-upgrade-authority",
+            &program_pubkey.to_string(),
+            "--new-upgrade-authority",
+            "none",
+        ]);
+        assert!(parse_command(&test_command, &default_signer, &mut None).is_err());
+
+        // --final can't be combined with a new upgrade authority
+        let test_command = test_commands.clone().get_matches_from(vec![
+            "test",
+            "program",
+            "set-upgrade-authority",
+            &program_pubkey.to_string(),
+            "--new-upgrade-authority",
+            &Pubkey::new_unique().to_string(),
+            "--final",
+        ]);
+        assert!(parse_command(&test_command, &default_signer, &mut None).is_err());
     }
 
     #[test]
@@ -5,9 +5,7 @@ use {
         compute_budget::{simulate_and_update_compute_unit_limit, UpdateComputeUnitLimitResult},
     },
     clap::ArgMatches,
-    solana_clap_utils::{
-        compute_budget::ComputeUnitLimit, input_parsers::lamports_of_sol, offline::SIGN_ONLY_ARG,
-    },
+    solana_clap_utils::{compute_budget::ComputeUnitLimit, input_parsers::lamports_of_sol},
     solana_rpc_client::rpc_client::RpcClient,
     solana_sdk::{
         commitment_config::CommitmentConfig, hash::Hash, message::Message,
@@ -17,10 +15,14 @@ use {
 
 #[derive(Debug, PartialEq, Eq, Clone, Copy)]
 pub enum SpendAmount {
+    /// Everything in the source account, emptying it
     All,
     Some(u64),
-    RentExempt,
-    AllForAccountCreation { create_account_min_balance: u64 },
+    /// Everything in excess of the rent-exempt minimum of the source account, keeping it open
+    AllMinusRentExempt,
+    AllForAccountCreation {
+        create_account_min_balance: u64,
+    },
 }
 
 impl Default for SpendAmount {
@@ -30,21 +32,55 @@ impl Default for SpendAmount {
 }
 
 impl SpendAmount {
-    pub fn new(amount: Option<u64>, sign_only: bool) -> Self {
+    pub fn new(amount: Option<u64>) -> Self {
         match amount {
             Some(lamports) => Self::Some(lamports),
-            None if !sign_only => Self::All,
-            _ => panic!("ALL amount not supported for sign-only operations"),
+            None => Self::All,
         }
     }
 
     pub fn new_from_matches(matches: &ArgMatches<'_>, name: &str) -> Self {
-        let amount = lamports_of_sol(matches, name);
-        let sign_only = matches.is_present(SIGN_ONLY_ARG.name);
-        SpendAmount::new(amount, sign_only)
+        SpendAmount::new(lamports_of_sol(matches, name))
+    }
+
+    /// Whether the amount depends on the balance of the source account
+    pub fn is_all(&self) -> bool {
+        !matches!(self, Self::Some(_))
+    }
+
+    /// Resolves the lamports spent from a source account holding `from_balance`.
+    ///
+    /// `from_fee` is the part of the transaction fee paid by the source account: the whole fee if
+    /// it is also the fee payer, and zero otherwise. `from_minimum_balance` is the balance the
+    /// source account must keep for `AllMinusRentExempt`; the other variants empty the account.
+    fn lamports(&self, from_balance: u64, from_fee: u64, from_minimum_balance: u64) -> u64 {
+        match self {
+            Self::Some(lamports) => *lamports,
+            Self::All | Self::AllForAccountCreation { .. } => from_balance.saturating_sub(from_fee),
+            Self::AllMinusRentExempt => from_balance
+                .saturating_sub(from_fee)
+                .saturating_sub(from_minimum_balance),
+        }
     }
 }
 
+/// Checks that an account left with `remaining_balance` after a transaction is either emptied,
+/// which closes it, or still holds at least `minimum_balance`.
+pub fn check_remaining_balance(
+    pubkey: &Pubkey,
+    remaining_balance: u64,
+    minimum_balance: u64,
+) -> Result<(), CliError> {
+    if remaining_balance != 0 && remaining_balance < minimum_balance {
+        return Err(CliError::BadParameter(format!(
+            "Amount too large. The balance of {pubkey} must be at least {} SOL to remain rent \
+             exempt, or zero to close the account",
+            lamports_to_sol(minimum_balance)
+        )));
+    }
+    Ok(())
+}
+
 struct SpendAndFee {
     spend: u64,
     fee: u64,
@@ -91,6 +127,13 @@ where
     F: Fn(u64) -> Message,
 {
     if sign_only {
+        if amount.is_all() {
+            return Err(CliError::BadParameter(
+                "ALL amount not supported for sign-only operations, as the balance of the \
+                 source account can't be fetched"
+                    .to_string(),
+            ));
+        }
         let (message, SpendAndFee { spend, fee: _ }) = resolve_spend_message(
             rpc_client,
             amount,
@@ -107,7 +150,7 @@ where
         let from_balance = rpc_client
             .get_balance_with_commitment(from_pubkey, commitment)?
             .value;
-        let from_rent_exempt_minimum = if amount == SpendAmount::RentExempt {
+        let from_minimum_balance = if amount == SpendAmount::AllMinusRentExempt {
             let data = rpc_client.get_account_data(from_pubkey)?;
             rpc_client.get_minimum_balance_for_rent_exemption(data.len())?
         } else {
@@ -120,10 +163,20 @@ where
             from_balance,
             from_pubkey,
             fee_pubkey,
-            from_rent_exempt_minimum,
+            from_minimum_balance,
             compute_unit_limit,
             build_message,
         )?;
+        let from_fee = if from_pubkey == fee_pubkey { fee } else { 0 };
+        if amount.is_all() && spend == 0 {
+            return Err(CliError::BadParameter(format!(
+                "Account {from_pubkey} has nothing to spend: balance {} SOL, fee {} SOL, minimum \
+                 balance {} SOL",
+                lamports_to_sol(from_balance),
+                lamports_to_sol(from_fee),
+                lamports_to_sol(from_minimum_balance),
+            )));
+        }
         if from_pubkey == fee_pubkey {
             if from_balance == 0 || from_balance < spend.saturating_add(fee) {
                 return Err(CliError::InsufficientFundsForSpendAndFee(
@@ -147,6 +200,11 @@ where
                 ));
             }
         }
+        check_remaining_balance(
+            from_pubkey,
+            from_balance - spend - from_fee,
+            from_minimum_balance,
+        )?;
         Ok((message, spend))
     }
 }
@@ -158,7 +216,7 @@ fn resolve_spend_message<F>(
     from_balance: u64,
     from_pubkey: &Pubkey,
     fee_pubkey: &Pubkey,
-    from_rent_exempt_minimum: u64,
+    from_minimum_balance: u64,
     compute_unit_limit: ComputeUnitLimit,
     build_message: F,
 ) -> Result<(Message, SpendAndFee), CliError>
@@ -169,30 +227,24 @@ where
         Some(blockhash) => {
             // If the from account is the same as the fee payer, it's impossible
             // to give a correct amount for the simulation with `SpendAmount::All`
-            // or `SpendAmount::RentExempt`.
+            // or `SpendAmount::AllMinusRentExempt`.
             // To know how much to transfer, we need to know the transaction fee,
             // but the transaction fee is dependent on the amount of compute
             // units used, which requires simulation.
             // To get around this limitation, we simulate against an amount of
             // `0`, since there are few situations in which `SpendAmount` can
-            // be `All` or `RentExempt` *and also* the from account is the fee
-            // payer.
+            // be `All` or `AllMinusRentExempt` *and also* the from account is
+            // the fee payer.
             let lamports = if from_pubkey == fee_pubkey {
                 match amount {
                     SpendAmount::Some(lamports) => lamports,
                     SpendAmount::AllForAccountCreation {
                         create_account_min_balance,
                     } => create_account_min_balance,
-                    SpendAmount::All | SpendAmount::RentExempt => 0,
+                    SpendAmount::All | SpendAmount::AllMinusRentExempt => 0,
                 }
             } else {
-                match amount {
-                    SpendAmount::Some(lamports) => lamports,
-                    SpendAmount::AllForAccountCreation { .. } | SpendAmount::All => from_balance,
-                    SpendAmount::RentExempt => {
-                        from_balance.saturating_sub(from_rent_exempt_minimum)
-                    }
-                }
+                amount.lamports(from_balance, 0, from_minimum_balance)
             };
             let mut dummy_message = build_message(lamports);
 
@@ -217,43 +269,12 @@ where
         None => (0, None), // Offline, cannot calculate fee
     };
 
-    let (mut message, spend_and_fee) = match amount {
-        SpendAmount::Some(lamports) => (
-            build_message(lamports),
-            SpendAndFee {
-                spend: lamports,
-                fee,
-            },
-        ),
-        SpendAmount::All | SpendAmount::AllForAccountCreation { .. } => {
-            let lamports = if from_pubkey == fee_pubkey {
-                from_balance.saturating_sub(fee)
-            } else {
-                from_balance
-            };
-            (
-                build_message(lamports),
-                SpendAndFee {
-                    spend: lamports,
-                    fee,
-                },
-            )
-        }
-        SpendAmount::RentExempt => {
-            let mut lamports = if from_pubkey == fee_pubkey {
-                from_balance.saturating_sub(fee)
-            } else {
-                from_balance
-            };
-            lamports = lamports.saturating_sub(from_rent_exempt_minimum);
-            (
-                build_message(lamports),
-                SpendAndFee {
-                    spend: lamports,
-                    fee,
-                },
-            )
-        }
+    let from_fee = if from_pubkey == fee_pubkey { fee } else { 0 };
+    let lamports = amount.lamports(from_balance, from_fee, from_minimum_balance);
+    let mut message = build_message(lamports);
+    let spend_and_fee = SpendAndFee {
+        spend: lamports,
+        fee,
     };
     // After build message, update with correct compute units
     if let Some((ix_index, ix_data)) = compute_unit_info {
@@ -261,3 +282,223 @@ where
     }
     Ok((message, spend_and_fee))
 }
+
+#[cfg(test)]
+mod tests {
+    use {
+        super::*,
+        serde_json::json,
+        solana_account_decoder::{encode_ui_account, UiAccountEncoding},
+        solana_rpc_client_api::{
+            request::RpcRequest,
+            response::{Response, RpcResponseContext},
+        },
+        solana_sdk::{account::Account, system_instruction},
+        std::collections::HashMap,
+    };
+
+    fn response(value: serde_json::Value) -> serde_json::Value {
+        json!(Response {
+            context: RpcResponseContext {
+                slot: 1,
+                api_version: None
+            },
+            value,
+        })
+    }
+
+    fn resolve_spend(
+        amount: SpendAmount,
+        sign_only: bool,
+        balance: u64,
+        fee: u64,
+        minimum_balance: u64,
+    ) -> Result<u64, CliError> {
+        let from_pubkey = Pubkey::new_unique();
+        let to_pubkey = Pubkey::new_unique();
+        let account = Account::new(balance, 42, &Pubkey::new_unique());
+        let mut mocks = HashMap::new();
+        mocks.insert(RpcRequest::GetBalance, response(json!(balance)));
+        mocks.insert(
+            RpcRequest::GetAccountInfo,
+            response(json!(Some(encode_ui_account(
+                &from_pubkey,
+                &account,
+                UiAccountEncoding::Base64,
+                None,
+                None,
+            )))),
+        );
+        mocks.insert(
+            RpcRequest::GetMinimumBalanceForRentExemption,
+            json!(minimum_balance),
+        );
+        mocks.insert(RpcRequest::GetFeeForMessage, response(json!(fee)));
+        let rpc_client = RpcClient::new_mock_with_mocks("succeeds".to_string(), mocks);
+
+        resolve_spend_tx_and_check_account_balance(
+            &rpc_client,
+            sign_only,
+            amount,
+            &Hash::new_unique(),
+            &from_pubkey,
+            ComputeUnitLimit::Default,
+            |lamports| {
+                let ix = system_instruction::transfer(&from_pubkey, &to_pubkey, lamports);
+                Message::new(&[ix], Some(&from_pubkey))
+            },
+            CommitmentConfig::default(),
+        )
+        .map(|(_message, spend)| spend)
+    }
+
+    #[test]
+    fn test_spend_amount_lamports() {
+        let (fee, minimum_balance) = (5, 100);
+        for balance in [0, fee, minimum_balance, minimum_balance + fee, 1_000] {
+            assert_eq!(
+                SpendAmount::Some(42).lamports(balance, fee, minimum_balance),
+                42
+            );
+            assert_eq!(
+                SpendAmount::All.lamports(balance, fee, minimum_balance),
+                balance.saturating_sub(fee)
+            );
+            assert_eq!(
+                SpendAmount::AllForAccountCreation {
+                    create_account_min_balance: minimum_balance
+                }
+                .lamports(balance, fee, minimum_balance),
+                balance.saturating_sub(fee)
+            );
+        }
+
+        // The rent-exempt minimum is kept on top of the fee
+        let all_minus_rent_exempt = SpendAmount::AllMinusRentExempt;
+        assert_eq!(all_minus_rent_exempt.lamports(0, fee, minimum_balance), 0);
+        assert_eq!(
+            all_minus_rent_exempt.lamports(minimum_balance, fee, minimum_balance),
+            0
+        );
+        assert_eq!(
+            all_minus_rent_exempt.lamports(minimum_balance + fee, fee, minimum_balance),
+            0
+        );
+        assert_eq!(
+            all_minus_rent_exempt.lamports(minimum_balance + fee + 1, fee, minimum_balance),
+            1
+        );
+        assert_eq!(
+            all_minus_rent_exempt.lamports(minimum_balance + 1, 0, minimum_balance),
+            1
+        );
+    }
+
+    #[test]
+    fn test_check_remaining_balance() {
+        let pubkey = Pubkey::new_unique();
+        let minimum_balance = 100;
+        assert!(check_remaining_balance(&pubkey, 0, minimum_balance).is_ok());
+        assert!(check_remaining_balance(&pubkey, 1, minimum_balance).is_err());
+        assert!(check_remaining_balance(&pubkey, minimum_balance - 1, minimum_balance).is_err());
+        assert!(check_remaining_balance(&pubkey, minimum_balance, minimum_balance).is_ok());
+        assert!(check_remaining_balance(&pubkey, minimum_balance + 1, minimum_balance).is_ok());
+        assert!(check_remaining_balance(&pubkey, 1, 0).is_ok());
+    }
+
+    #[test]
+    fn test_resolve_spend_all() {
+        let (fee, minimum_balance) = (5, 100);
+
+        // The source account is also the fee payer, so ALL leaves just enough for the fee
+        assert_eq!(
+            resolve_spend(SpendAmount::All, false, 1_000, fee, minimum_balance).unwrap(),
+            1_000 - fee
+        );
+        assert!(resolve_spend(SpendAmount::All, false, fee, fee, minimum_balance).is_err());
+        assert_eq!(
+            resolve_spend(SpendAmount::All, false, fee + 1, fee, minimum_balance).unwrap(),
+            1
+        );
+    }
+
+    #[test]
+    fn test_resolve_spend_all_minus_rent_exempt() {
+        let (fee, minimum_balance) = (5, 100);
+        let amount = SpendAmount::AllMinusRentExempt;
+
+        assert_eq!(
+            resolve_spend(amount, false, 1_000, fee, minimum_balance).unwrap(),
+            1_000 - fee - minimum_balance
+        );
+        // Nothing is left to spend at or below the rent-exempt minimum plus the fee
+        assert!(resolve_spend(amount, false, minimum_balance - 1, fee, minimum_balance).is_err());
+        assert!(resolve_spend(amount, false, minimum_balance, fee, minimum_balance).is_err());
+        assert!(resolve_spend(amount, false, minimum_balance + fee, fee, minimum_balance).is_err());
+        assert_eq!(
+            resolve_spend(
+                amount,
+                false,
+                minimum_balance + fee + 1,
+                fee,
+                minimum_balance
+            )
+            .unwrap(),
+            1
+        );
+    }
+
+    #[test]
+    fn test_resolve_spend_some() {
+        let (fee, minimum_balance) = (5, 100);
+        let balance = 1_000;
+
+        assert_eq!(
+            resolve_spend(SpendAmount::Some(42), false, balance, fee, minimum_balance).unwrap(),
+            42
+        );
+        assert_eq!(
+            resolve_spend(
+                SpendAmount::Some(balance - fee),
+                false,
+                balance,
+                fee,
+                minimum_balance
+            )
+            .unwrap(),
+            balance - fee
+        );
+        assert!(matches!(
+            resolve_spend(
+                SpendAmount::Some(balance - fee + 1),
+                false,
+                balance,
+                fee,
+                minimum_balance
+            ),
+            Err(CliError::InsufficientFundsForSpendAndFee(..))
+        ));
+    }
+
+    #[test]
+    fn test_resolve_spend_sign_only() {
+        // The balance can't be fetched when signing offline, so only explicit amounts are
+        // supported
+        assert_eq!(
+            resolve_spend(SpendAmount::Some(42), true, 0, 0, 0).unwrap(),
+            42
+        );
+        for amount in [
+            SpendAmount::All,
+            SpendAmount::AllMinusRentExempt,
+            SpendAmount::AllForAccountCreation {
+                create_account_min_balance: 1,
+            },
+        ] {
+            assert!(matches!(
+                resolve_spend(amount, true, 1_000, 0, 0),
+                Err(CliError::BadParameter(_))
+            ));
+        }
+    }
+}
@@ -45,7 +45,6 @@ use {
         clock::{Clock, UnixTimestamp, SECONDS_PER_DAY},
         commitment_config::CommitmentConfig,
         epoch_schedule::EpochSchedule,
-        message::Message,
         native_token::Sol,
         pubkey::Pubkey,
         stake::{
@@ -60,8 +59,8 @@ use {
         system_instruction::{self, SystemError},
         system_program,
         sysvar::{clock, stake_history},
-        transaction::Transaction,
     },
+    solana_transaction::builder::{check_message_size, TransactionBuilder},
     std::{ops::Deref, rc::Rc},
 };
 
@@ -596,6 +595,46 @@ impl StakeSubCommands for App<'_, '_> {
                 .arg(memo_arg())
                 .arg(compute_unit_price_arg()),
         )
+        .subcommand(
+            SubCommand::with_name("close-stake-account")
+                .about(
+                    "Close an inactive stake account, withdrawing its entire balance including \
+                     the rent-exempt reserve",
+                )
+                .arg(pubkey!(
+                    Arg::with_name("stake_account_pubkey")
+                        .index(1)
+                        .value_name("STAKE_ACCOUNT_ADDRESS")
+                        .required(true),
+                    "Stake account to be closed."
+                ))
+                .arg(pubkey!(
+                    Arg::with_name("destination_account_pubkey")
+                        .index(2)
+                        .value_name("RECIPIENT_ADDRESS")
+                        .required(true),
+                    "Recipient of all withdrawn SOL."
+                ))
+                .arg(
+                    Arg::with_name("balance")
+                        .long("balance")
+                        .value_name("AMOUNT")
+                        .takes_value(true)
+                        .validator(is_amount)
+                        .requires(SIGN_ONLY_ARG.name)
+                        .help(
+                            "The balance of the stake account, in SOL. Required with \
+                             --sign-only, as the balance can't be fetched",
+                        ),
+                )
+                .arg(withdraw_authority_arg())
+                .offline_args()
+                .nonce_args(false)
+                .arg(fee_payer_arg())
+                .arg(custodian_arg())
+                .arg(memo_arg())
+                .arg(compute_unit_price_arg()),
+        )
         .subcommand(
             SubCommand::with_name("stake-set-lockup")
                 .about("Set Lockup for the stake account")
@@ -1255,6 +1294,64 @@ pub fn parse_stake_withdraw_stake(
     })
 }
 
+pub fn parse_close_stake_account(
+    matches: &ArgMatches<'_>,
+    default_signer: &DefaultSigner,
+    wallet_manager: &mut Option<Rc<RemoteWalletManager>>,
+) -> Result<CliCommandInfo, CliError> {
+    let stake_account_pubkey =
+        pubkey_of_signer(matches, "stake_account_pubkey", wallet_manager)?.unwrap();
+    let destination_account_pubkey =
+        pubkey_of_signer(matches, "destination_account_pubkey", wallet_manager)?.unwrap();
+    let balance = lamports_of_sol(matches, "balance");
+    let sign_only = matches.is_present(SIGN_ONLY_ARG.name);
+    if sign_only && balance.is_none() {
+        return Err(CliError::BadParameter(
+            "--balance is required with --sign-only".to_string(),
+        ));
+    }
+    let dump_transaction_message = matches.is_present(DUMP_TRANSACTION_MESSAGE.name);
+    let blockhash_query = BlockhashQuery::new_from_matches(matches);
+    let nonce_account = pubkey_of(matches, NONCE_ARG.name);
+    let memo = matches.value_of(MEMO_ARG.name).map(String::from);
+    let (withdraw_authority, withdraw_authority_pubkey) =
+        signer_of(matches, WITHDRAW_AUTHORITY_ARG.name, wallet_manager)?;
+    let (nonce_authority, nonce_authority_pubkey) =
+        signer_of(matches, NONCE_AUTHORITY_ARG.name, wallet_manager)?;
+    let (fee_payer, fee_payer_pubkey) = signer_of(matches, FEE_PAYER_ARG.name, wallet_manager)?;
+    let (custodian, custodian_pubkey) = signer_of(matches, "custodian", wallet_manager)?;
+
+    let mut bulk_signers = vec![withdraw_authority, fee_payer];
+    if nonce_account.is_some() {
+        bulk_signers.push(nonce_authority);
+    }
+    if custodian.is_some() {
+        bulk_signers.push(custodian);
+    }
+    let signer_info =
+        default_signer.generate_unique_signers(bulk_signers, matches, wallet_manager)?;
+    let compute_unit_price = value_of(matches, COMPUTE_UNIT_PRICE_ARG.name);
+
+    Ok(CliCommandInfo {
+        command: CliCommand::CloseStakeAccount {
+            stake_account_pubkey,
+            destination_account_pubkey,
+            balance,
+            withdraw_authority: signer_info.index_of(withdraw_authority_pubkey).unwrap(),
+            sign_only,
+            dump_transaction_message,
+            blockhash_query,
+            nonce_account,
+            nonce_authority: signer_info.index_of(nonce_authority_pubkey).unwrap(),
+            memo,
+            fee_payer: signer_info.index_of(fee_payer_pubkey).unwrap(),
+            custodian: custodian_pubkey.and_then(|_| signer_info.index_of(custodian_pubkey)),
+            compute_unit_price,
+        },
+        signers: signer_info.signers,
+    })
+}
+
 pub fn parse_stake_set_lockup(
     matches: &ArgMatches<'_>,
     default_signer: &DefaultSigner,
@@ -1409,6 +1506,12 @@ pub fn process_create_stake_account(
         BlockhashQuery::None(_) | BlockhashQuery::FeeCalculator(_, _) => ComputeUnitLimit::Default,
         BlockhashQuery::All(_) => ComputeUnitLimit::Simulated,
     };
+    let mut builder = TransactionBuilder::new()
+        .fee_payer(fee_payer.pubkey())
+        .signers(&config.signers);
+    if let Some(nonce_account) = &nonce_account {
+        builder = builder.nonce(nonce_account, &nonce_authority.pubkey());
+    }
     let build_message = |lamports| {
         let authorized = Authorized {
             staker: staker.unwrap_or(from.pubkey()),
@@ -1454,16 +1557,7 @@ pub fn process_create_stake_account(
             compute_unit_price,
             compute_unit_limit,
         });
-        if let Some(nonce_account) = &nonce_account {
-            Message::new_with_nonce(
-                ixs,
-                Some(&fee_payer.pubkey()),
-                nonce_account,
-                &nonce_authority.pubkey(),
-            )
-        } else {
-            Message::new(&ixs, Some(&fee_payer.pubkey()))
-        }
+        builder.clone().add_instructions(ixs).message()
     };
 
     let recent_blockhash = blockhash_query.get_blockhash(rpc_client, config.commitment)?;
@@ -1476,6 +1570,8 @@ pub fn process_create_stake_account(
         };
     }
 
+    // The amount doesn't change the size, so check it before simulating
+    check_message_size(&build_message(0))?;
     let (message, lamports) = resolve_spend_tx_and_check_account_balances(
         rpc_client,
         sign_only,
@@ -1518,9 +1614,8 @@ pub fn process_create_stake_account(
         }
     }
 
-    let mut tx = Transaction::new_unsigned(message);
     if sign_only {
-        tx.try_partial_sign(&config.signers, recent_blockhash)?;
+        let tx = builder.partial_sign_message(message, recent_blockhash)?;
         return_signers_with_config(
             &tx,
             &config.output_format,
@@ -1529,7 +1624,7 @@ pub fn process_create_stake_account(
             },
         )
     } else {
-        tx.try_sign(&config.signers, recent_blockhash)?;
+        let tx = builder.sign_message(message, recent_blockhash)?;
         let result = rpc_client.send_and_confirm_transaction_with_spinner_and_config(
             &tx,
             config.commitment,
@@ -1636,21 +1731,19 @@ pub fn process_stake_authorize(
     let nonce_authority = config.signers[nonce_authority];
     let fee_payer = config.signers[fee_payer];
 
-    let mut message = if let Some(nonce_account) = &nonce_account {
-        Message::new_with_nonce(
-            ixs,
-            Some(&fee_payer.pubkey()),
-            nonce_account,
-            &nonce_authority.pubkey(),
-        )
-    } else {
-        Message::new(&ixs, Some(&fee_payer.pubkey()))
-    };
+    let mut builder = TransactionBuilder::new()
+        .add_instructions(ixs)
+        .fee_payer(fee_payer.pubkey())
+        .signers(&config.signers);
+    if let Some(nonce_account) = &nonce_account {
+        builder = builder.nonce(nonce_account, &nonce_authority.pubkey());
+    }
+    let mut message = builder.message();
+    check_message_size(&message)?;
     simulate_and_update_compute_unit_limit(&compute_unit_limit, rpc_client, &mut message)?;
-    let mut tx = Transaction::new_unsigned(message);
 
     if sign_only {
-        tx.try_partial_sign(&config.signers, recent_blockhash)?;
+        let tx = builder.partial_sign_message(message, recent_blockhash)?;
         return_signers_with_config(
             &tx,
             &config.output_format,
@@ -1659,7 +1752,7 @@ pub fn process_stake_authorize(
             },
         )
     } else {
-        tx.try_sign(&config.signers, recent_blockhash)?;
+        let tx = builder.sign_message(message, recent_blockhash)?;
         if let Some(nonce_account) = &nonce_account {
             let nonce_account = solana_rpc_client_nonce_utils::get_account_with_commitment(
                 rpc_client,
@@ -1791,21 +1884,19 @@ pub fn process_deactivate_stake_account(
     let nonce_authority = config.signers[nonce_authority];
     let fee_payer = config.signers[fee_payer];
 
-    let mut message = if let Some(nonce_account) = &nonce_account {
-        Message::new_with_nonce(
-            ixs,
-            Some(&fee_payer.pubkey()),
-            nonce_account,
-            &nonce_authority.pubkey(),
-        )
-    } else {
-        Message::new(&ixs, Some(&fee_payer.pubkey()))
-    };
+    let mut builder = TransactionBuilder::new()
+        .add_instructions(ixs)
+        .fee_payer(fee_payer.pubkey())
+        .signers(&config.signers);
+    if let Some(nonce_account) = &nonce_account {
+        builder = builder.nonce(nonce_account, &nonce_authority.pubkey());
+    }
+    let mut message = builder.message();
+    check_message_size(&message)?;
     simulate_and_update_compute_unit_limit(&compute_unit_limit, rpc_client, &mut message)?;
-    let mut tx = Transaction::new_unsigned(message);
 
     if sign_only {
-        tx.try_partial_sign(&config.signers, recent_blockhash)?;
+        let tx = builder.partial_sign_message(message, recent_blockhash)?;
         return_signers_with_config(
             &tx,
             &config.output_format,
@@ -1814,7 +1905,7 @@ pub fn process_deactivate_stake_account(
             },
         )
     } else {
-        tx.try_sign(&config.signers, recent_blockhash)?;
+        let tx = builder.sign_message(message, recent_blockhash)?;
         if let Some(nonce_account) = &nonce_account {
             let nonce_account = solana_rpc_client_nonce_utils::get_account_with_commitment(
                 rpc_client,
@@ -1875,6 +1966,12 @@ pub fn process_withdraw_stake(
         BlockhashQuery::None(_) | BlockhashQuery::FeeCalculator(_, _) => ComputeUnitLimit::Default,
         BlockhashQuery::All(_) => ComputeUnitLimit::Simulated,
     };
+    let mut builder = TransactionBuilder::new()
+        .fee_payer(fee_payer.pubkey())
+        .signers(&config.signers);
+    if let Some(nonce_account) = &nonce_account {
+        builder = builder.nonce(nonce_account, &nonce_authority.pubkey());
+    }
     let build_message = |lamports| {
         let ixs = vec![stake_instruction::withdraw(
             &stake_account_address,
@@ -1889,18 +1986,11 @@ pub fn process_withdraw_stake(
             compute_unit_limit,
         });
 
-        if let Some(nonce_account) = &nonce_account {
-            Message::new_with_nonce(
-                ixs,
-                Some(&fee_payer.pubkey()),
-                nonce_account,
-                &nonce_authority.pubkey(),
-            )
-        } else {
-            Message::new(&ixs, Some(&fee_payer.pubkey()))
-        }
+        builder.clone().add_instructions(ixs).message()
     };
 
+    // The amount doesn't change the size, so check it before simulating
+    check_message_size(&build_message(0))?;
     let (message, _) = resolve_spend_tx_and_check_account_balances(
         rpc_client,
         sign_only,
@@ -1913,10 +2003,8 @@ pub fn process_withdraw_stake(
         config.commitment,
     )?;
 
-    let mut tx = Transaction::new_unsigned(message);
-
     if sign_only {
-        tx.try_partial_sign(&config.signers, recent_blockhash)?;
+        let tx = builder.partial_sign_message(message, recent_blockhash)?;
         return_signers_with_config(
             &tx,
             &config.output_format,
@@ -1925,7 +2013,7 @@ pub fn process_withdraw_stake(
             },
         )
     } else {
-        tx.try_sign(&config.signers, recent_blockhash)?;
+        let tx = builder.sign_message(message, recent_blockhash)?;
         if let Some(nonce_account) = &nonce_account {
             let nonce_account = solana_rpc_client_nonce_utils::get_account_with_commitment(
                 rpc_client,
@@ -1949,6 +2037,109 @@ pub fn process_withdraw_stake(
     }
 }
 
+/// Returns an error unless the stake account can be closed: it must never have
+/// been delegated, or its stake must be fully deactivated
+fn check_stake_account_inactive(
+    rpc_client: &RpcClient,
+    stake_account_pubkey: &Pubkey,
+    commitment_config: CommitmentConfig,
+) -> Result<(), Box<dyn std::error::Error>> {
+    let stake = match get_stake_account_state(rpc_client, stake_account_pubkey, commitment_config)?
+    {
+        StakeStateV2::Uninitialized | StakeStateV2::Initialized(_) => return Ok(()),
+        StakeStateV2::Stake(_, stake, _) => stake,
+        StakeStateV2::RewardsPool => {
+            return Err(CliError::BadParameter(format!(
+                "{stake_account_pubkey} is a rewards pool and cannot be closed"
+            ))
+            .into())
+        }
+    };
+    if stake.delegation.deactivation_epoch == u64::MAX {
+        return Err(CliError::BadParameter(format!(
+            "Stake account {stake_account_pubkey} is delegated to {}; deactivate it first",
+            stake.delegation.voter_pubkey
+        ))
+        .into());
+    }
+
+    let stake_history_account = rpc_client.get_account(&stake_history::id())?;
+    let stake_history = from_account(&stake_history_account).ok_or_else(|| {
+        CliError::RpcRequestError("Failed to deserialize stake history".to_string())
+    })?;
+    let clock_account = rpc_client.get_account(&clock::id())?;
+    let clock: Clock = from_account(&clock_account).ok_or_else(|| {
+        CliError::RpcRequestError("Failed to deserialize clock sysvar".to_string())
+    })?;
+    let new_rate_activation_epoch = get_feature_activation_epoch(
+        rpc_client,
+        &solana_feature_set::reduce_stake_warmup_cooldown::id(),
+    )?;
+    let StakeActivationStatus {
+        effective,
+        activating,
+        deactivating,
+    } = stake.delegation.stake_activating_and_deactivating(
+        clock.epoch,
+        &stake_history,
+        new_rate_activation_epoch,
+    );
+    if effective > 0 || activating > 0 || deactivating > 0 {
+        return Err(CliError::BadParameter(format!(
+            "Stake account {stake_account_pubkey} is not fully deactivated: {} is still \
+             effective in epoch {}",
+            Sol(effective.max(activating)),
+            clock.epoch,
+        ))
+        .into());
+    }
+    Ok(())
+}
+
+#[allow(clippy::too_many_arguments)]
+pub fn process_close_stake_account(
+    rpc_client: &RpcClient,
+    config: &CliConfig,
+    stake_account_pubkey: &Pubkey,
+    destination_account_pubkey: &Pubkey,
+    balance: Option<u64>,
+    withdraw_authority: SignerIndex,
+    custodian: Option<SignerIndex>,
+    sign_only: bool,
+    dump_transaction_message: bool,
+    blockhash_query: &BlockhashQuery,
+    nonce_account: Option<&Pubkey>,
+    nonce_authority: SignerIndex,
+    memo: Option<&String>,
+    fee_payer: SignerIndex,
+    compute_unit_price: Option<u64>,
+) -> ProcessResult {
+    if !sign_only {
+        check_stake_account_inactive(rpc_client, stake_account_pubkey, config.commitment)?;
+    }
+    // Withdrawing the entire balance leaves a zero-lamport account, which the
+    // runtime purges
+    let amount = balance.map_or(SpendAmount::All, SpendAmount::Some);
+    process_withdraw_stake(
+        rpc_client,
+        config,
+        stake_account_pubkey,
+        destination_account_pubkey,
+        amount,
+        withdraw_authority,
+        custodian,
+        sign_only,
+        dump_transaction_message,
+        blockhash_query,
+        nonce_account,
+        nonce_authority,
+        memo,
+        None,
+        fee_payer,
+        compute_unit_price,
+    )
+}
+
 #[allow(clippy::too_many_arguments)]
 pub fn process_split_stake(
     rpc_client: &RpcClient,
@@ -2098,21 +2289,19 @@ pub fn process_split_stake(
 
     let nonce_authority = config.signers[nonce_authority];
 
-    let mut message = if let Some(nonce_account) = &nonce_account {
-        Message::new_with_nonce(
-            ixs,
-            Some(&fee_payer.pubkey()),
-            nonce_account,
-            &nonce_authority.pubkey(),
-        )
-    } else {
-        Message::new(&ixs, Some(&fee_payer.pubkey()))
-    };
+    let mut builder = TransactionBuilder::new()
+        .add_instructions(ixs)
+        .fee_payer(fee_payer.pubkey())
+        .signers(&config.signers);
+    if let Some(nonce_account) = &nonce_account {
+        builder = builder.nonce(nonce_account, &nonce_authority.pubkey());
+    }
+    let mut message = builder.message();
+    check_message_size(&message)?;
     simulate_and_update_compute_unit_limit(&compute_unit_limit, rpc_client, &mut message)?;
-    let mut tx = Transaction::new_unsigned(message);
 
     if sign_only {
-        tx.try_partial_sign(&config.signers, recent_blockhash)?;
+        let tx = builder.partial_sign_message(message, recent_blockhash)?;
         return_signers_with_config(
             &tx,
             &config.output_format,
@@ -2121,7 +2310,7 @@ pub fn process_split_stake(
             },
         )
     } else {
-        tx.try_sign(&config.signers, recent_blockhash)?;
+        let tx = builder.sign_message(message, recent_blockhash)?;
         if let Some(nonce_account) = &nonce_account {
             let nonce_account = solana_rpc_client_nonce_utils::get_account_with_commitment(
                 rpc_client,
@@ -2216,21 +2405,19 @@ pub fn process_merge_stake(
 
     let nonce_authority = config.signers[nonce_authority];
 
-    let mut message = if let Some(nonce_account) = &nonce_account {
-        Message::new_with_nonce(
-            ixs,
-            Some(&fee_payer.pubkey()),
-            nonce_account,
-            &nonce_authority.pubkey(),
-        )
-    } else {
-        Message::new(&ixs, Some(&fee_payer.pubkey()))
-    };
+    let mut builder = TransactionBuilder::new()
+        .add_instructions(ixs)
+        .fee_payer(fee_payer.pubkey())
+        .signers(&config.signers);
+    if let Some(nonce_account) = &nonce_account {
+        builder = builder.nonce(nonce_account, &nonce_authority.pubkey());
+    }
+    let mut message = builder.message();
+    check_message_size(&message)?;
     simulate_and_update_compute_unit_limit(&compute_unit_limit, rpc_client, &mut message)?;
-    let mut tx = Transaction::new_unsigned(message);
 
     if sign_only {
-        tx.try_partial_sign(&config.signers, recent_blockhash)?;
+        let tx = builder.partial_sign_message(message, recent_blockhash)?;
         return_signers_with_config(
             &tx,
             &config.output_format,
@@ -2239,7 +2426,7 @@ pub fn process_merge_stake(
             },
         )
     } else {
-        tx.try_sign(&config.signers, recent_blockhash)?;
+        let tx = builder.sign_message(message, recent_blockhash)?;
         if let Some(nonce_account) = &nonce_account {
             let nonce_account = solana_rpc_client_nonce_utils::get_account_with_commitment(
                 rpc_client,
@@ -2319,21 +2506,19 @@ pub fn process_stake_set_lockup(
         }
     }
 
-    let mut message = if let Some(nonce_account) = &nonce_account {
-        Message::new_with_nonce(
-            ixs,
-            Some(&fee_payer.pubkey()),
-            nonce_account,
-            &nonce_authority.pubkey(),
-        )
-    } else {
-        Message::new(&ixs, Some(&fee_payer.pubkey()))
-    };
+    let mut builder = TransactionBuilder::new()
+        .add_instructions(ixs)
+        .fee_payer(fee_payer.pubkey())
+        .signers(&config.signers);
+    if let Some(nonce_account) = &nonce_account {
+        builder = builder.nonce(nonce_account, &nonce_authority.pubkey());
+    }
+    let mut message = builder.message();
+    check_message_size(&message)?;
     simulate_and_update_compute_unit_limit(&compute_unit_limit, rpc_client, &mut message)?;
-    let mut tx = Transaction::new_unsigned(message);
 
     if sign_only {
-        tx.try_partial_sign(&config.signers, recent_blockhash)?;
+        let tx = builder.partial_sign_message(message, recent_blockhash)?;
         return_signers_with_config(
             &tx,
             &config.output_format,
@@ -2342,7 +2527,7 @@ pub fn process_stake_set_lockup(
             },
         )
     } else {
-        tx.try_sign(&config.signers, recent_blockhash)?;
+        let tx = builder.sign_message(message, recent_blockhash)?;
         if let Some(nonce_account) = &nonce_account {
             let nonce_account = solana_rpc_client_nonce_utils::get_account_with_commitment(
                 rpc_client,
@@ -2806,21 +2991,19 @@ pub fn process_delegate_stake(
     let nonce_authority = config.signers[nonce_authority];
     let fee_payer = config.signers[fee_payer];
 
-    let mut message = if let Some(nonce_account) = &nonce_account {
-        Message::new_with_nonce(
-            ixs,
-            Some(&fee_payer.pubkey()),
-            nonce_account,
-            &nonce_authority.pubkey(),
-        )
-    } else {
-        Message::new(&ixs, Some(&fee_payer.pubkey()))
-    };
+    let mut builder = TransactionBuilder::new()
+        .add_instructions(ixs)
+        .fee_payer(fee_payer.pubkey())
+        .signers(&config.signers);
+    if let Some(nonce_account) = &nonce_account {
+        builder = builder.nonce(nonce_account, &nonce_authority.pubkey());
+    }
+    let mut message = builder.message();
+    check_message_size(&message)?;
     simulate_and_update_compute_unit_limit(&compute_unit_limit, rpc_client, &mut message)?;
-    let mut tx = Transaction::new_unsigned(message);
 
     if sign_only {
-        tx.try_partial_sign(&config.signers, recent_blockhash)?;
+        let tx = builder.partial_sign_message(message, recent_blockhash)?;
         return_signers_with_config(
             &tx,
             &config.output_format,
@@ -2829,7 +3012,7 @@ pub fn process_delegate_stake(
             },
         )
     } else {
-        tx.try_sign(&config.signers, recent_blockhash)?;
+        let tx = builder.sign_message(message, recent_blockhash)?;
         if let Some(nonce_account) = &nonce_account {
             let nonce_account = solana_rpc_client_nonce_utils::get_account_with_commitment(
                 rpc_client,
@@ -4657,6 +4840,88 @@ mod tests {
             }
         );
 
+        // Test CloseStakeAccount Subcommand
+        let test_close_stake_account = test_commands.clone().get_matches_from(vec![
+            "test",
+            "close-stake-account",
+            &stake_account_string,
+            &stake_account_string,
+            "--withdraw-authority",
+            &stake_authority_keypair_file,
+        ]);
+
+        assert_eq!(
+            parse_command(&test_close_stake_account, &default_signer, &mut None).unwrap(),
+            CliCommandInfo {
+                command: CliCommand::CloseStakeAccount {
+                    stake_account_pubkey,
+                    destination_account_pubkey: stake_account_pubkey,
+                    balance: None,
+                    withdraw_authority: 1,
+                    custodian: None,
+                    sign_only: false,
+                    dump_transaction_message: false,
+                    blockhash_query: BlockhashQuery::All(blockhash_query::Source::Cluster),
+                    nonce_account: None,
+                    nonce_authority: 0,
+                    memo: None,
+                    fee_payer: 0,
+                    compute_unit_price: None,
+                },
+                signers: vec![
+                    Box::new(read_keypair_file(&default_keypair_file).unwrap()),
+                    Box::new(read_keypair_file(&stake_authority_keypair_file).unwrap())
+                ],
+            }
+        );
+
+        // Test CloseStakeAccount Subcommand signed offline
+        let test_close_stake_account = test_commands.clone().get_matches_from(vec![
+            "test",
+            "close-stake-account",
+            &stake_account_string,
+            &stake_account_string,
+            "--balance",
+            "42",
+            "--blockhash",
+            &nonce_hash_string,
+            "--sign-only",
+        ]);
+
+        assert_eq!(
+            parse_command(&test_close_stake_account, &default_signer, &mut None).unwrap(),
+            CliCommandInfo {
+                command: CliCommand::CloseStakeAccount {
+                    stake_account_pubkey,
+                    destination_account_pubkey: stake_account_pubkey,
+                    balance: Some(42_000_000_000),
+                    withdraw_authority: 0,
+                    custodian: None,
+                    sign_only: true,
+                    dump_transaction_message: false,
+                    blockhash_query: BlockhashQuery::None(nonce_hash),
+                    nonce_account: None,
+                    nonce_authority: 0,
+                    memo: None,
+                    fee_payer: 0,
+                    compute_unit_price: None,
+                },
+                signers: vec![Box::new(read_keypair_file(&default_keypair_file).unwrap())],
+            }
+        );
+
+        // The balance can't be fetched when signing offline
+        let test_close_stake_account = test_commands.clone().get_matches_from(vec![
+            "test",
+            "close-stake-account",
+            &stake_account_string,
+            &stake_account_string,
+            "--blockhash",
+            &nonce_hash_string,
+            "--sign-only",
+        ]);
+        assert!(parse_command(&test_close_stake_account, &default_signer, &mut None).is_err());
+
         // Test DeactivateStake Subcommand
         let test_deactivate_stake = test_commands.clone().get_matches_from(vec![
             "test",
@@ -32,6 +32,9 @@ use {
     std::{error, rc::Rc},
 };
 
+const MIN_KEYBASE_USERNAME_LENGTH: usize = 2;
+const MAX_KEYBASE_USERNAME_LENGTH: usize = 16;
+
 // Return an error if a validator details are longer than the max length.
 pub fn check_details_length(string: String) -> Result<(), String> {
     if string.len() > MAX_LONG_FIELD_LENGTH {
@@ -79,25 +82,97 @@ pub fn is_short_field(string: String) -> Result<(), String> {
     }
 }
 
+// Return the canonical form of a keybase username, without surrounding
+// whitespace, a leading `@` or upper case letters, or an error if it isn't a
+// valid keybase username.
+pub fn normalize_keybase_username(username: &str) -> Result<String, String> {
+    let username = username.trim();
+    let username = username
+        .strip_prefix('@')
+        .unwrap_or(username)
+        .to_ascii_lowercase();
+    if !(MIN_KEYBASE_USERNAME_LENGTH..=MAX_KEYBASE_USERNAME_LENGTH).contains(&username.len()) {
+        Err(format!(
+            "keybase username must be {MIN_KEYBASE_USERNAME_LENGTH} to \
+             {MAX_KEYBASE_USERNAME_LENGTH} characters long"
+        ))
+    } else if !username
+        .chars()
+        .all(|c| c.is_ascii_alphanumeric() || c == '_')
+    {
+        Err("keybase username may only contain letters, digits and underscores".to_string())
+    } else {
+        Ok(username)
+    }
+}
+
+// Return an error if a keybase username is invalid.
+pub fn is_keybase_username(string: String) -> Result<(), String> {
+    normalize_keybase_username(&string).map(|_| ())
+}
+
+// Return an error if any field of the validator info is longer than its max length.
+pub fn check_field_lengths(validator_info: &Value) -> Result<(), String> {
+    for (field, max_length) in [
+        ("name", MAX_SHORT_FIELD_LENGTH),
+        ("website", MAX_SHORT_FIELD_LENGTH),
+        ("iconUrl", MAX_SHORT_FIELD_LENGTH),
+        ("keybaseUsername", MAX_SHORT_FIELD_LENGTH),
+        ("details", MAX_LONG_FIELD_LENGTH),
+    ] {
+        let Some(value) = validator_info.get(field) else {
+            continue;
+        };
+        let value = value
+            .as_str()
+            .ok_or_else(|| format!("validator {field} must be a string"))?;
+        if value.len() > max_length {
+            return Err(format!(
+                "validator {field} longer than {max_length:?}-byte limit"
+            ));
+        }
+    }
+    Ok(())
+}
+
+fn keybase_proof_url(validator_pubkey: &Pubkey, keybase_username: &str) -> String {
+    format!("https://keybase.pub/{keybase_username}/solana/validator-{validator_pubkey:?}")
+}
+
 fn verify_keybase(
     validator_pubkey: &Pubkey,
     keybase_username: &Value,
 ) -> Result<(), Box<dyn error::Error>> {
-    if let Some(keybase_username) = keybase_username.as_str() {
-        let url =
-            format!("https://keybase.pub/{keybase_username}/solana/validator-{validator_pubkey:?}");
-        let client = Client::new();
-        if client.head(&url).send()?.status().is_success() {
-            Ok(())
-        } else {
-            Err(format!(
-                "keybase_username could not be confirmed at: {url}. Please add this pubkey file \
-                 to your keybase profile to connect"
-            )
-            .into())
-        }
+    verify_keybase_with(validator_pubkey, keybase_username, |url| {
+        Ok(Client::new().head(url).send()?.status().is_success())
+    })
+}
+
+// Check that the keybase proof for `validator_pubkey` exists, using `proof_exists`
+// to look up the proof URL.
+fn verify_keybase_with<F>(
+    validator_pubkey: &Pubkey,
+    keybase_username: &Value,
+    proof_exists: F,
+) -> Result<(), Box<dyn error::Error>>
+where
+    F: FnOnce(&str) -> Result<bool, Box<dyn error::Error>>,
+{
+    let Some(keybase_username) = keybase_username.as_str() else {
+        return Err(
+            format!("keybase_username could not be parsed as String: {keybase_username}").into(),
+        );
+    };
+    let keybase_username = normalize_keybase_username(keybase_username)?;
+    let url = keybase_proof_url(validator_pubkey, &keybase_username);
+    if proof_exists(&url)? {
+        Ok(())
     } else {
-        Err(format!("keybase_username could not be parsed as String: {keybase_username}").into())
+        Err(format!(
+            "keybase_username could not be confirmed at: {url}. Please add this pubkey file to \
+             your keybase profile to connect"
+        )
+        .into())
     }
 }
 
@@ -118,9 +193,13 @@ fn parse_args(matches: &ArgMatches<'_>) -> Value {
         map.insert("details".to_string(), Value::String(details.to_string()));
     }
     if let Some(keybase_username) = matches.value_of("keybase_username") {
+        let keybase_username = normalize_keybase_username(keybase_username).unwrap_or_else(|_| {
+            // Already checked by the argument's validator
+            keybase_username.to_string()
+        });
         map.insert(
             "keybaseUsername".to_string(),
-            Value::String(keybase_username.to_string()),
+            Value::String(keybase_username),
         );
     }
     Value::Object(map)
@@ -137,13 +216,40 @@ fn parse_validator_info(
     if !key_list.keys.is_empty() {
         let (validator_pubkey, _) = key_list.keys[1];
         let validator_info_string: String = deserialize(get_config_data(&account.data)?)?;
-        let validator_info: Map<_, _> = serde_json::from_str(&validator_info_string)?;
+        // Removed validator info is stored as an empty string
+        let validator_info = if validator_info_string.is_empty() {
+            Map::new()
+        } else {
+            serde_json::from_str(&validator_info_string)?
+        };
         Ok((validator_pubkey, validator_info))
     } else {
         Err(format!("{pubkey} could not be parsed as a validator info account").into())
     }
 }
 
+// Return the validator info account published by `validator_pubkey`, if any.
+fn find_validator_info_account(
+    rpc_client: &RpcClient,
+    validator_pubkey: &Pubkey,
+) -> Result<Option<(Pubkey, Account)>, Box<dyn error::Error>> {
+    let all_config = rpc_client.get_program_accounts(&solana_config_program::id())?;
+    Ok(all_config
+        .into_iter()
+        .filter(|(_, account)| is_validator_info_account(account))
+        .find(|(pubkey, account)| {
+            parse_validator_info(pubkey, account)
+                .is_ok_and(|(info_validator_pubkey, _)| info_validator_pubkey == *validator_pubkey)
+        }))
+}
+
+fn is_validator_info_account(account: &Account) -> bool {
+    match deserialize::<ConfigKeys>(&account.data) {
+        Ok(key_list) => key_list.keys.contains(&(validator_info::id(), false)),
+        Err(_) => false,
+    }
+}
+
 pub trait ValidatorInfoSubCommands {
     fn validator_info_subcommands(self) -> Self;
 }
@@ -152,7 +258,7 @@ impl ValidatorInfoSubCommands for App<'_, '_> {
     fn validator_info_subcommands(self) -> Self {
         self.subcommand(
             SubCommand::with_name("validator-info")
-                .about("Publish/get Validator info on Solana")
+                .about("Publish/get/remove Validator info on Solana")
                 .setting(AppSettings::SubcommandRequiredElseHelp)
                 .subcommand(
                     SubCommand::with_name("publish")
@@ -199,7 +305,7 @@ impl ValidatorInfoSubCommands for App<'_, '_> {
                                 .long("keybase")
                                 .value_name("USERNAME")
                                 .takes_value(true)
-                                .validator(is_short_field)
+                                .validator(is_keybase_username)
                                 .hidden(hidden_unless_forced()) // Being phased out
                                 .help("Validator Keybase username"),
                         )
@@ -221,6 +327,23 @@ impl ValidatorInfoSubCommands for App<'_, '_> {
                         )
                         .arg(compute_unit_price_arg()),
                 )
+                .subcommand(
+                    SubCommand::with_name("remove")
+                        .about("Remove published Validator info")
+                        .arg(
+                            Arg::with_name("info_pubkey")
+                                .short("p")
+                                .long("info-pubkey")
+                                .value_name("PUBKEY")
+                                .takes_value(true)
+                                .validator(is_pubkey)
+                                .help(
+                                    "The pubkey of the Validator info account to clear [default: \
+                                     the account published by the validator identity]",
+                                ),
+                        )
+                        .arg(compute_unit_price_arg()),
+                )
                 .subcommand(
                     SubCommand::with_name("get")
                         .about("Get and parse Solana Validator info")
@@ -260,6 +383,22 @@ pub fn parse_validator_info_command(
     })
 }
 
+pub fn parse_remove_validator_info_command(
+    matches: &ArgMatches<'_>,
+    default_signer: &DefaultSigner,
+    wallet_manager: &mut Option<Rc<RemoteWalletManager>>,
+) -> Result<CliCommandInfo, CliError> {
+    let info_pubkey = pubkey_of(matches, "info_pubkey");
+    let compute_unit_price = value_of(matches, COMPUTE_UNIT_PRICE_ARG.name);
+    Ok(CliCommandInfo {
+        command: CliCommand::RemoveValidatorInfo {
+            info_pubkey,
+            compute_unit_price,
+        },
+        signers: vec![default_signer.signer_from_path(matches, wallet_manager)?],
+    })
+}
+
 pub fn parse_get_validator_info_command(
     matches: &ArgMatches<'_>,
 ) -> Result<CliCommandInfo, CliError> {
@@ -277,6 +416,9 @@ pub fn process_set_validator_info(
     info_pubkey: Option<Pubkey>,
     compute_unit_price: Option<u64>,
 ) -> ProcessResult {
+    check_field_lengths(validator_info)
+        .map_err(|err| CliError::BadParameter(format!("Invalid validator info: {err}")))?;
+
     // Validate keybase username
     if let Some(string) = validator_info.get("keybaseUsername") {
         if force_keybase {
@@ -303,19 +445,7 @@ pub fn process_set_validator_info(
     }
 
     // Check for existing validator-info account
-    let all_config = rpc_client.get_program_accounts(&solana_config_program::id())?;
-    let existing_account = all_config
-        .iter()
-        .filter(
-            |(_, account)| match deserialize::<ConfigKeys>(&account.data) {
-                Ok(key_list) => key_list.keys.contains(&(validator_info::id(), false)),
-                Err(_) => false,
-            },
-        )
-        .find(|(pubkey, account)| {
-            let (validator_pubkey, _) = parse_validator_info(pubkey, account).unwrap();
-            validator_pubkey == config.signers[0].pubkey()
-        });
+    let existing_account = find_validator_info_account(rpc_client, &config.signers[0].pubkey())?;
 
     // Create validator-info keypair to use if info_pubkey not provided or does not exist
     let info_keypair = Keypair::new();
@@ -419,6 +549,75 @@ pub fn process_set_validator_info(
     Ok("".to_string())
 }
 
+/// Clear the validator info published by the validator identity.
+///
+/// The config program can't close accounts, so the info account and its
+/// lamports remain, still owned by the validator so that info can be published
+/// to it again. Its contents are zeroed so it no longer shows up in
+/// `validator-info get`.
+pub fn process_remove_validator_info(
+    rpc_client: &RpcClient,
+    config: &CliConfig,
+    info_pubkey: Option<Pubkey>,
+    compute_unit_price: Option<u64>,
+) -> ProcessResult {
+    let validator_pubkey = config.signers[0].pubkey();
+    let (info_pubkey, info_account) = if let Some(info_pubkey) = info_pubkey {
+        (info_pubkey, rpc_client.get_account(&info_pubkey)?)
+    } else {
+        find_validator_info_account(rpc_client, &validator_pubkey)?.ok_or_else(|| {
+            CliError::BadParameter(format!("No validator info published by {validator_pubkey}"))
+        })?
+    };
+    let (info_validator_pubkey, _) = parse_validator_info(&info_pubkey, &info_account)?;
+    if info_validator_pubkey != validator_pubkey {
+        return Err(CliError::BadParameter(format!(
+            "Validator info {info_pubkey} was published by {info_validator_pubkey}, not \
+             {validator_pubkey}"
+        ))
+        .into());
+    }
+
+    let keys = vec![(validator_info::id(), false), (validator_pubkey, true)];
+    let mut store_instruction =
+        config_instruction::store(&info_pubkey, false, keys, &ValidatorInfo::default());
+    // Overwrite the rest of the previous info with zeroes
+    store_instruction.data.resize(info_account.data.len(), 0);
+
+    let compute_unit_limit = ComputeUnitLimit::Simulated;
+    let build_message = |_lamports| {
+        let instructions =
+            vec![store_instruction.clone()].with_compute_unit_config(&ComputeUnitConfig {
+                compute_unit_price,
+                compute_unit_limit,
+            });
+        Message::new(&instructions, Some(&validator_pubkey))
+    };
+
+    let latest_blockhash = rpc_client.get_latest_blockhash()?;
+    let (message, _) = resolve_spend_tx_and_check_account_balance(
+        rpc_client,
+        false,
+        SpendAmount::Some(0),
+        &latest_blockhash,
+        &validator_pubkey,
+        compute_unit_limit,
+        build_message,
+        config.commitment,
+    )?;
+    let mut tx = Transaction::new_unsigned(message);
+    tx.try_sign(&config.signers, latest_blockhash)?;
+    let signature_str = rpc_client.send_and_confirm_transaction_with_spinner_and_config(
+        &tx,
+        config.commitment,
+        config.send_transaction_config,
+    )?;
+
+    println!("Success! Validator info removed from: {info_pubkey:?}");
+    println!("{signature_str}");
+    Ok("".to_string())
+}
+
 pub fn process_get_validator_info(
     rpc_client: &RpcClient,
     config: &CliConfig,
@@ -433,14 +632,10 @@ pub fn process_get_validator_info(
         let all_config = rpc_client.get_program_accounts(&solana_config_program::id())?;
         all_config
             .into_iter()
-            .filter(|(_, validator_info_account)| {
-                match deserialize::<ConfigKeys>(&validator_info_account.data) {
-                    Ok(key_list) => key_list.keys.contains(&(validator_info::id(), false)),
-                    Err(_) => false,
-                }
-            })
+            .filter(|(_, validator_info_account)| is_validator_info_account(validator_info_account))
             .collect()
     };
+    let listing_all = pubkey.is_none();
 
     let mut validator_info_list: Vec<CliValidatorInfo> = vec![];
     if validator_info.is_empty() {
@@ -448,7 +643,19 @@ pub fn process_get_validator_info(
     }
     for (validator_info_pubkey, validator_info_account) in validator_info.iter() {
         let (validator_pubkey, validator_info) =
-            parse_validator_info(validator_info_pubkey, validator_info_account)?;
+            match parse_validator_info(validator_info_pubkey, validator_info_account) {
+                Ok(parsed) => parsed,
+                // Don't let one malformed record hide all the others
+                Err(err) if listing_all => {
+                    eprintln!("Skipping validator info {validator_info_pubkey}: {err}");
+                    continue;
+                }
+                Err(err) => return Err(err),
+            };
+        if listing_all && validator_info.is_empty() {
+            // Removed validator info
+            continue;
+        }
         validator_info_list.push(CliValidatorInfo {
             identity_pubkey: validator_pubkey.to_string(),
             info_pubkey: validator_info_pubkey.to_string(),
@@ -503,6 +710,92 @@ mod tests {
         assert!(is_short_field(long_name.to_string()).is_err());
     }
 
+    #[test]
+    fn test_normalize_keybase_username() {
+        assert_eq!(
+            normalize_keybase_username(" @Alice_Validator "),
+            Ok("alice_validator".to_string())
+        );
+        assert_eq!(normalize_keybase_username("ab"), Ok("ab".to_string()));
+        assert!(normalize_keybase_username("a").is_err());
+        assert!(normalize_keybase_username("@a").is_err());
+        let max_username = "a".repeat(MAX_KEYBASE_USERNAME_LENGTH);
+        assert_eq!(normalize_keybase_username(&max_username), Ok(max_username));
+        assert!(normalize_keybase_username(&"a".repeat(MAX_KEYBASE_USERNAME_LENGTH + 1)).is_err());
+        assert!(normalize_keybase_username("alice-validator").is_err());
+        assert!(normalize_keybase_username("alice/../bob").is_err());
+        assert!(is_keybase_username("alice".to_string()).is_ok());
+        assert!(is_keybase_username("".to_string()).is_err());
+    }
+
+    #[test]
+    fn test_check_field_lengths() {
+        let short_field = "X".repeat(MAX_SHORT_FIELD_LENGTH);
+        let long_field = "X".repeat(MAX_LONG_FIELD_LENGTH);
+        let info = json!({
+            "name": short_field,
+            "website": short_field,
+            "iconUrl": short_field,
+            "keybaseUsername": short_field,
+            "details": long_field,
+        });
+        assert_eq!(check_field_lengths(&info), Ok(()));
+        assert_eq!(check_field_lengths(&json!({})), Ok(()));
+
+        for field in ["name", "website", "iconUrl", "keybaseUsername"] {
+            let mut info = info.clone();
+            info[field] = Value::String("X".repeat(MAX_SHORT_FIELD_LENGTH + 1));
+            assert_eq!(
+                check_field_lengths(&info),
+                Err(format!(
+                    "validator {field} longer than {MAX_SHORT_FIELD_LENGTH:?}-byte limit"
+                ))
+            );
+        }
+        let mut info = info.clone();
+        info["details"] = Value::String("X".repeat(MAX_LONG_FIELD_LENGTH + 1));
+        assert_eq!(
+            check_field_lengths(&info),
+            Err(format!(
+                "validator details longer than {MAX_LONG_FIELD_LENGTH:?}-byte limit"
+            ))
+        );
+
+        assert_eq!(
+            check_field_lengths(&json!({"name": 42})),
+            Err("validator name must be a string".to_string())
+        );
+    }
+
+    #[test]
+    fn test_verify_keybase_with_proof() {
+        let pubkey = solana_pubkey::new_rand();
+        let expected_url = format!("https://keybase.pub/alice/solana/validator-{pubkey:?}");
+
+        let mut requested_url = None;
+        verify_keybase_with(&pubkey, &json!("@Alice"), |url| {
+            requested_url = Some(url.to_string());
+            Ok(true)
+        })
+        .unwrap();
+        assert_eq!(requested_url, Some(expected_url.clone()));
+
+        let err = verify_keybase_with(&pubkey, &json!("alice"), |_| Ok(false)).unwrap_err();
+        assert!(err.to_string().starts_with(&format!(
+            "keybase_username could not be confirmed at: {expected_url}"
+        )));
+
+        let err =
+            verify_keybase_with(&pubkey, &json!("alice"), |_| Err("offline".into())).unwrap_err();
+        assert_eq!(err.to_string(), "offline");
+
+        // Invalid usernames are rejected without looking up a proof
+        assert!(verify_keybase_with(&pubkey, &json!("not a username"), |_| {
+            panic!("proof must not be looked up")
+        })
+        .is_err());
+    }
+
     #[test]
     fn test_verify_keybase_username_not_string() {
         let pubkey = solana_pubkey::new_rand();
@@ -594,6 +887,38 @@ mod tests {
         );
     }
 
+    #[test]
+    fn test_parse_validator_info_tolerates_removed_and_oversized_info() {
+        let pubkey = solana_pubkey::new_rand();
+        let keys = vec![(validator_info::id(), false), (pubkey, true)];
+        let parse = |info: String| {
+            let mut data =
+                serialize(&(ConfigKeys { keys: keys.clone() }, ValidatorInfo { info })).unwrap();
+            // Unused account space is zeroed
+            data.resize(data.len() + 64, 0);
+            parse_validator_info(
+                &Pubkey::default(),
+                &Account {
+                    owner: solana_config_program::id(),
+                    data,
+                    ..Account::default()
+                },
+            )
+        };
+
+        // Removed info
+        assert_eq!(parse(String::new()).unwrap(), (pubkey, Map::new()));
+
+        // Records published before field lengths were enforced
+        let mut info = Map::new();
+        info.insert(
+            "name".to_string(),
+            Value::String("X".repeat(MAX_SHORT_FIELD_LENGTH * 2)),
+        );
+        let info_string = serde_json::to_string(&Value::Object(info.clone())).unwrap();
+        assert_eq!(parse(info_string).unwrap(), (pubkey, info));
+    }
+
     #[test]
     fn test_parse_validator_info_not_validator_info_account() {
         assert!(parse_validator_info(
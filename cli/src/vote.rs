@@ -10,7 +10,9 @@ use {
         },
         memo::WithMemo,
         nonce::check_nonce_account,
-        spend_utils::{resolve_spend_tx_and_check_account_balances, SpendAmount},
+        spend_utils::{
+            check_remaining_balance, resolve_spend_tx_and_check_account_balances, SpendAmount,
+        },
         stake::check_current_authority,
     },
     clap::{value_t_or_exit, App, Arg, ArgMatches, SubCommand},
@@ -30,11 +32,19 @@ use {
     },
     solana_remote_wallet::remote_wallet::RemoteWalletManager,
     solana_rpc_client::rpc_client::RpcClient,
-    solana_rpc_client_api::config::RpcGetVoteAccountsConfig,
+    solana_rpc_client_api::{
+        config::{RpcAccountInfoConfig, RpcGetVoteAccountsConfig, RpcProgramAccountsConfig},
+        filter::{Memcmp, RpcFilterType},
+    },
     solana_rpc_client_nonce_utils::blockhash_query::BlockhashQuery,
     solana_sdk::{
-        account::Account, commitment_config::CommitmentConfig, message::Message,
-        native_token::lamports_to_sol, pubkey::Pubkey, system_instruction::SystemError,
+        account::Account,
+        account_utils::StateMut,
+        commitment_config::CommitmentConfig,
+        message::Message,
+        pubkey::Pubkey,
+        stake::{self, state::StakeStateV2},
+        system_instruction::SystemError,
         transaction::Transaction,
     },
     solana_vote_program::{
@@ -442,6 +452,29 @@ impl VoteSubCommands for App<'_, '_> {
                         .validator(is_valid_signer)
                         .help("Authorized withdrawer [default: cli config keypair]"),
                 )
+                .arg(
+                    Arg::with_name("allow_active_delegations")
+                        .long("allow-active-delegations")
+                        .takes_value(false)
+                        .help(
+                            "Close the vote account even though stake accounts are still \
+                             delegated to it, leaving their delegators without a validator",
+                        ),
+                )
+                .arg(
+                    Arg::with_name("balance")
+                        .long("balance")
+                        .value_name("AMOUNT")
+                        .takes_value(true)
+                        .validator(is_amount)
+                        .requires(SIGN_ONLY_ARG.name)
+                        .help(
+                            "The balance of the vote account, in SOL. Required with --sign-only, \
+                             as the balance can't be fetched",
+                        ),
+                )
+                .offline_args()
+                .nonce_args(false)
                 .arg(fee_payer_arg())
                 .arg(memo_arg())
                 .arg(compute_unit_price_arg()),
@@ -711,7 +744,7 @@ pub fn parse_withdraw_from_vote_account(
     // excess of the rent-exempt minimum. In order to close the account with this subcommand, a
     // validator must specify the withdrawal amount precisely.
     if withdraw_amount == SpendAmount::All {
-        withdraw_amount = SpendAmount::RentExempt;
+        withdraw_amount = SpendAmount::AllMinusRentExempt;
     }
 
     let (withdraw_authority, withdraw_authority_pubkey) =
@@ -763,15 +796,30 @@ pub fn parse_close_vote_account(
     let destination_account_pubkey =
         pubkey_of_signer(matches, "destination_account_pubkey", wallet_manager)?.unwrap();
 
+    let allow_active_delegations = matches.is_present("allow_active_delegations");
+    let balance = lamports_of_sol(matches, "balance");
+    let sign_only = matches.is_present(SIGN_ONLY_ARG.name);
+    if sign_only && balance.is_none() {
+        return Err(CliError::BadParameter(
+            "--balance is required with --sign-only".to_string(),
+        ));
+    }
+    let dump_transaction_message = matches.is_present(DUMP_TRANSACTION_MESSAGE.name);
+    let blockhash_query = BlockhashQuery::new_from_matches(matches);
+    let nonce_account = pubkey_of(matches, NONCE_ARG.name);
+
     let (withdraw_authority, withdraw_authority_pubkey) =
         signer_of(matches, "authorized_withdrawer", wallet_manager)?;
+    let (nonce_authority, nonce_authority_pubkey) =
+        signer_of(matches, NONCE_AUTHORITY_ARG.name, wallet_manager)?;
     let (fee_payer, fee_payer_pubkey) = signer_of(matches, FEE_PAYER_ARG.name, wallet_manager)?;
 
-    let signer_info = default_signer.generate_unique_signers(
-        vec![fee_payer, withdraw_authority],
-        matches,
-        wallet_manager,
-    )?;
+    let mut bulk_signers = vec![fee_payer, withdraw_authority];
+    if nonce_account.is_some() {
+        bulk_signers.push(nonce_authority);
+    }
+    let signer_info =
+        default_signer.generate_unique_signers(bulk_signers, matches, wallet_manager)?;
     let memo = matches.value_of(MEMO_ARG.name).map(String::from);
     let compute_unit_price = value_of(matches, COMPUTE_UNIT_PRICE_ARG.name);
 
@@ -780,6 +828,13 @@ pub fn parse_close_vote_account(
             vote_account_pubkey,
             destination_account_pubkey,
             withdraw_authority: signer_info.index_of(withdraw_authority_pubkey).unwrap(),
+            allow_active_delegations,
+            balance,
+            sign_only,
+            dump_transaction_message,
+            blockhash_query,
+            nonce_account,
+            nonce_authority: signer_info.index_of(nonce_authority_pubkey).unwrap(),
             memo,
             fee_payer: signer_info.index_of(fee_payer_pubkey).unwrap(),
             compute_unit_price,
@@ -1429,15 +1484,11 @@ pub fn process_withdraw_from_vote_account(
         let minimum_balance =
             rpc_client.get_minimum_balance_for_rent_exemption(VoteState::size_of())?;
         if let SpendAmount::Some(withdraw_amount) = withdraw_amount {
-            let balance_remaining = current_balance.saturating_sub(withdraw_amount);
-            if balance_remaining < minimum_balance && balance_remaining != 0 {
-                return Err(CliError::BadParameter(format!(
-                    "Withdraw amount too large. The vote account balance must be at least {} SOL \
-                     to remain rent exempt",
-                    lamports_to_sol(minimum_balance)
-                ))
-                .into());
-            }
+            check_remaining_balance(
+                vote_account_pubkey,
+                current_balance.saturating_sub(withdraw_amount),
+                minimum_balance,
+            )?;
         }
     }
 
@@ -1477,71 +1528,121 @@ pub fn process_withdraw_from_vote_account(
     }
 }
 
+/// Returns the stake accounts delegated to the vote account which have not
+/// been deactivated
+fn get_delegated_stake_accounts(
+    rpc_client: &RpcClient,
+    vote_account_pubkey: &Pubkey,
+    commitment: CommitmentConfig,
+) -> Result<Vec<Pubkey>, Box<dyn std::error::Error>> {
+    let config = RpcProgramAccountsConfig {
+        filters: Some(vec![
+            // Filter by `StakeStateV2::Stake(_, _)`
+            RpcFilterType::Memcmp(Memcmp::new_base58_encoded(0, &[2, 0, 0, 0])),
+            // Filter by `Delegation::voter_pubkey`, which begins at byte offset 124
+            RpcFilterType::Memcmp(Memcmp::new_base58_encoded(
+                124,
+                vote_account_pubkey.as_ref(),
+            )),
+        ]),
+        account_config: RpcAccountInfoConfig {
+            encoding: Some(solana_account_decoder::UiAccountEncoding::Base64),
+            commitment: Some(commitment),
+            ..RpcAccountInfoConfig::default()
+        },
+        ..RpcProgramAccountsConfig::default()
+    };
+    let stake_accounts =
+        rpc_client.get_program_accounts_with_config(&stake::program::id(), config)?;
+    Ok(stake_accounts
+        .into_iter()
+        .filter_map(
+            |(stake_pubkey, stake_account)| match stake_account.state() {
+                Ok(StakeStateV2::Stake(_, stake, _))
+                    if stake.delegation.deactivation_epoch == u64::MAX =>
+                {
+                    Some(stake_pubkey)
+                }
+                _ => None,
+            },
+        )
+        .collect())
+}
+
+#[allow(clippy::too_many_arguments)]
 pub fn process_close_vote_account(
     rpc_client: &RpcClient,
     config: &CliConfig,
     vote_account_pubkey: &Pubkey,
     withdraw_authority: SignerIndex,
     destination_account_pubkey: &Pubkey,
+    allow_active_delegations: bool,
+    balance: Option<u64>,
+    sign_only: bool,
+    dump_transaction_message: bool,
+    blockhash_query: &BlockhashQuery,
+    nonce_account: Option<&Pubkey>,
+    nonce_authority: SignerIndex,
     memo: Option<&String>,
     fee_payer: SignerIndex,
     compute_unit_price: Option<u64>,
 ) -> ProcessResult {
-    let vote_account_status =
-        rpc_client.get_vote_accounts_with_config(RpcGetVoteAccountsConfig {
-            vote_pubkey: Some(vote_account_pubkey.to_string()),
-            ..RpcGetVoteAccountsConfig::default()
-        })?;
+    if !sign_only {
+        let vote_account_status =
+            rpc_client.get_vote_accounts_with_config(RpcGetVoteAccountsConfig {
+                vote_pubkey: Some(vote_account_pubkey.to_string()),
+                ..RpcGetVoteAccountsConfig::default()
+            })?;
+
+        if let Some(vote_account) = vote_account_status
+            .current
+            .into_iter()
+            .chain(vote_account_status.delinquent)
+            .next()
+        {
+            if vote_account.activated_stake != 0 {
+                return Err(format!(
+                    "Cannot close a vote account with active stake: {vote_account_pubkey}"
+                )
+                .into());
+            }
+        }
 
-    if let Some(vote_account) = vote_account_status
-        .current
-        .into_iter()
-        .chain(vote_account_status.delinquent)
-        .next()
-    {
-        if vote_account.activated_stake != 0 {
-            return Err(format!(
-                "Cannot close a vote account with active stake: {vote_account_pubkey}"
-            )
+        let delegated_stake_accounts =
+            get_delegated_stake_accounts(rpc_client, vote_account_pubkey, config.commitment)?;
+        eprintln!(
+            "Found {} stake account(s) delegated to {vote_account_pubkey}",
+            delegated_stake_accounts.len()
+        );
+        if !delegated_stake_accounts.is_empty() && !allow_active_delegations {
+            return Err(CliError::BadParameter(format!(
+                "Closing {vote_account_pubkey} would leave {} delegated stake account(s) without \
+                 a validator. Deactivate them first, or pass --allow-active-delegations",
+                delegated_stake_accounts.len()
+            ))
             .into());
         }
     }
 
-    let latest_blockhash = rpc_client.get_latest_blockhash()?;
-    let withdraw_authority = config.signers[withdraw_authority];
-    let fee_payer = config.signers[fee_payer];
-
-    let current_balance = rpc_client.get_balance(vote_account_pubkey)?;
-
-    let compute_unit_limit = ComputeUnitLimit::Simulated;
-    let ixs = vec![withdraw(
+    // Withdrawing the entire balance leaves a zero-lamport account, which the
+    // runtime purges
+    let withdraw_amount = balance.map_or(SpendAmount::All, SpendAmount::Some);
+    process_withdraw_from_vote_account(
+        rpc_client,
+        config,
         vote_account_pubkey,
-        &withdraw_authority.pubkey(),
-        current_balance,
+        withdraw_authority,
+        withdraw_amount,
         destination_account_pubkey,
-    )]
-    .with_memo(memo)
-    .with_compute_unit_config(&ComputeUnitConfig {
+        sign_only,
+        dump_transaction_message,
+        blockhash_query,
+        nonce_account,
+        nonce_authority,
+        memo,
+        fee_payer,
         compute_unit_price,
-        compute_unit_limit,
-    });
-
-    let mut message = Message::new(&ixs, Some(&fee_payer.pubkey()));
-    simulate_and_update_compute_unit_limit(&compute_unit_limit, rpc_client, &mut message)?;
-    let mut tx = Transaction::new_unsigned(message);
-    tx.try_sign(&config.signers, latest_blockhash)?;
-    check_account_for_fee_with_commitment(
-        rpc_client,
-        &tx.message.account_keys[0],
-        &tx.message,
-        config.commitment,
-    )?;
-    let result = rpc_client.send_and_confirm_transaction_with_spinner_and_config(
-        &tx,
-        config.commitment,
-        config.send_transaction_config,
-    );
-    log_instruction_custom_error::<VoteError>(result, config)
+    )
 }
 
 #[cfg(test)]
@@ -2176,7 +2277,7 @@ mod tests {
                     vote_account_pubkey: read_keypair_file(&keypair_file).unwrap().pubkey(),
                     destination_account_pubkey: pubkey,
                     withdraw_authority: 0,
-                    withdraw_amount: SpendAmount::RentExempt,
+                    withdraw_amount: SpendAmount::AllMinusRentExempt,
                     sign_only: false,
                     dump_transaction_message: false,
                     blockhash_query: BlockhashQuery::All(blockhash_query::Source::Cluster),
@@ -2323,6 +2424,13 @@ mod tests {
                     vote_account_pubkey: read_keypair_file(&keypair_file).unwrap().pubkey(),
                     destination_account_pubkey: pubkey,
                     withdraw_authority: 0,
+                    allow_active_delegations: false,
+                    balance: None,
+                    sign_only: false,
+                    dump_transaction_message: false,
+                    blockhash_query: BlockhashQuery::All(blockhash_query::Source::Cluster),
+                    nonce_account: None,
+                    nonce_authority: 0,
                     memo: None,
                     fee_payer: 0,
                     compute_unit_price: None,
@@ -2350,6 +2458,13 @@ mod tests {
                     vote_account_pubkey: read_keypair_file(&keypair_file).unwrap().pubkey(),
                     destination_account_pubkey: pubkey,
                     withdraw_authority: 1,
+                    allow_active_delegations: false,
+                    balance: None,
+                    sign_only: false,
+                    dump_transaction_message: false,
+                    blockhash_query: BlockhashQuery::All(blockhash_query::Source::Cluster),
+                    nonce_account: None,
+                    nonce_authority: 0,
                     memo: None,
                     fee_payer: 0,
                     compute_unit_price: None,
@@ -2382,6 +2497,13 @@ mod tests {
                     vote_account_pubkey: read_keypair_file(&keypair_file).unwrap().pubkey(),
                     destination_account_pubkey: pubkey,
                     withdraw_authority: 1,
+                    allow_active_delegations: false,
+                    balance: None,
+                    sign_only: false,
+                    dump_transaction_message: false,
+                    blockhash_query: BlockhashQuery::All(blockhash_query::Source::Cluster),
+                    nonce_account: None,
+                    nonce_authority: 0,
                     memo: None,
                     fee_payer: 0,
                     compute_unit_price: Some(99),
@@ -2392,5 +2514,52 @@ mod tests {
                 ],
             }
         );
+
+        // Test CloseVoteAccount subcommand signed offline
+        let test_close_vote_account = test_commands.clone().get_matches_from(vec![
+            "test",
+            "close-vote-account",
+            &keypair_file,
+            &pubkey_string,
+            "--allow-active-delegations",
+            "--balance",
+            "1.5",
+            "--blockhash",
+            &blockhash_string,
+            "--sign-only",
+        ]);
+        assert_eq!(
+            parse_command(&test_close_vote_account, &default_signer, &mut None).unwrap(),
+            CliCommandInfo {
+                command: CliCommand::CloseVoteAccount {
+                    vote_account_pubkey: read_keypair_file(&keypair_file).unwrap().pubkey(),
+                    destination_account_pubkey: pubkey,
+                    withdraw_authority: 0,
+                    allow_active_delegations: true,
+                    balance: Some(1_500_000_000),
+                    sign_only: true,
+                    dump_transaction_message: false,
+                    blockhash_query: BlockhashQuery::None(blockhash),
+                    nonce_account: None,
+                    nonce_authority: 0,
+                    memo: None,
+                    fee_payer: 0,
+                    compute_unit_price: None,
+                },
+                signers: vec![Box::new(read_keypair_file(&default_keypair_file).unwrap())],
+            }
+        );
+
+        // The balance can't be fetched when signing offline
+        let test_close_vote_account = test_commands.clone().get_matches_from(vec![
+            "test",
+            "close-vote-account",
+            &keypair_file,
+            &pubkey_string,
+            "--blockhash",
+            &blockhash_string,
+            "--sign-only",
+        ]);
+        assert!(parse_command(&test_close_vote_account, &default_signer, &mut None).is_err());
     }
 }
@@ -33,16 +33,15 @@ use {
     solana_rpc_client_api::config::RpcTransactionConfig,
     solana_rpc_client_nonce_utils::blockhash_query::BlockhashQuery,
     solana_sdk::{
-        commitment_config::CommitmentConfig,
-        message::Message,
         offchain_message::OffchainMessage,
         pubkey::Pubkey,
         signature::Signature,
         stake,
         system_instruction::{self, SystemError},
         system_program,
-        transaction::{Transaction, VersionedTransaction},
+        transaction::VersionedTransaction,
     },
+    solana_transaction::builder::{check_message_size, TransactionBuilder},
     solana_transaction_status::{
         EncodableWithMeta, EncodedConfirmedTransactionWithStatusMeta, EncodedTransaction,
         TransactionBinaryEncoding, UiTransactionEncoding,
@@ -762,7 +761,7 @@ pub fn process_confirm(
                         signature,
                         RpcTransactionConfig {
                             encoding: Some(UiTransactionEncoding::Base64),
-                            commitment: Some(CommitmentConfig::confirmed()),
+                            commitment: Some(config.confirmed_commitment()),
                             max_supported_transaction_version: Some(0),
                         },
                     ) {
@@ -794,6 +793,8 @@ pub fn process_confirm(
                 }
                 CliTransactionConfirmation {
                     confirmation_status: Some(transaction_status.confirmation_status()),
+                    slot: Some(transaction_status.slot),
+                    confirmations: transaction_status.confirmations,
                     transaction,
                     get_transaction_error,
                     err: transaction_status.err.clone(),
@@ -801,6 +802,8 @@ pub fn process_confirm(
             } else {
                 CliTransactionConfirmation {
                     confirmation_status: None,
+                    slot: None,
+                    confirmations: None,
                     transaction: None,
                     get_transaction_error: None,
                     err: None,
@@ -916,6 +919,12 @@ pub fn process_transfer(
     } else {
         ComputeUnitLimit::Simulated
     };
+    let mut builder = TransactionBuilder::new()
+        .fee_payer(fee_payer.pubkey())
+        .signers(&config.signers);
+    if let Some(nonce_account) = &nonce_account {
+        builder = builder.nonce(nonce_account, &nonce_authority.pubkey());
+    }
     let build_message = |lamports| {
         let ixs = if let Some((base_pubkey, seed, program_id, from_pubkey)) = with_seed.as_ref() {
             vec![system_instruction::transfer_with_seed(
@@ -939,19 +948,11 @@ pub fn process_transfer(
                     compute_unit_limit,
                 })
         };
-
-        if let Some(nonce_account) = &nonce_account {
-            Message::new_with_nonce(
-                ixs,
-                Some(&fee_payer.pubkey()),
-                nonce_account,
-                &nonce_authority.pubkey(),
-            )
-        } else {
-            Message::new(&ixs, Some(&fee_payer.pubkey()))
-        }
+        builder.clone().add_instructions(ixs).message()
     };
 
+    // The amount doesn't change the size, so check it before simulating
+    check_message_size(&build_message(0))?;
     let (message, _) = resolve_spend_tx_and_check_account_balances(
         rpc_client,
         sign_only,
@@ -963,10 +964,9 @@ pub fn process_transfer(
         build_message,
         config.commitment,
     )?;
-    let mut tx = Transaction::new_unsigned(message);
 
     if sign_only {
-        tx.try_partial_sign(&config.signers, recent_blockhash)?;
+        let tx = builder.partial_sign_message(message, recent_blockhash)?;
         return_signers_with_config(
             &tx,
             &config.output_format,
@@ -984,7 +984,7 @@ pub fn process_transfer(
             check_nonce_account(&nonce_account, &nonce_authority.pubkey(), &recent_blockhash)?;
         }
 
-        tx.try_sign(&config.signers, recent_blockhash)?;
+        let tx = builder.sign_message(message, recent_blockhash)?;
         let result = if no_wait {
             rpc_client.send_transaction_with_config(&tx, config.send_transaction_config)
         } else {
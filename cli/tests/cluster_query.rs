@@ -2,19 +2,29 @@ use {
     solana_cli::{
         check_balance,
         cli::{process_command, request_and_confirm_airdrop, CliCommand, CliConfig},
+        cluster_query::stream_logs,
         test_utils::check_ready,
     },
     solana_faucet::faucet::run_local_faucet,
     solana_rpc_client::rpc_client::RpcClient,
+    solana_rpc_client_api::config::RpcTransactionLogsFilter,
     solana_sdk::{
         commitment_config::CommitmentConfig,
         fee::FeeStructure,
         native_token::sol_to_lamports,
         signature::{Keypair, Signer},
+        transaction::Transaction,
     },
     solana_streamer::socket::SocketAddrSpace,
     solana_test_validator::TestValidator,
-    std::time::Duration,
+    std::{
+        sync::{
+            atomic::{AtomicBool, Ordering},
+            Arc,
+        },
+        thread,
+        time::Duration,
+    },
     test_case::test_case,
 };
 
@@ -59,3 +69,65 @@ fn test_ping(compute_unit_price: Option<u64>) {
     };
     process_command(&config).unwrap();
 }
+
+#[test]
+fn test_logs() {
+    solana_logger::setup();
+    let mint_keypair = Keypair::new();
+    let mint_pubkey = mint_keypair.pubkey();
+    let faucet_addr = run_local_faucet(mint_keypair, None);
+    let test_validator =
+        TestValidator::with_no_fees(mint_pubkey, Some(faucet_addr), SocketAddrSpace::Unspecified);
+
+    let rpc_client =
+        RpcClient::new_with_commitment(test_validator.rpc_url(), CommitmentConfig::processed());
+
+    let default_signer = Keypair::new();
+    let mut config = CliConfig::recent_for_tests();
+    config.json_rpc_url = test_validator.rpc_url();
+    config.signers = vec![&default_signer];
+    request_and_confirm_airdrop(
+        &rpc_client,
+        &config,
+        &default_signer.pubkey(),
+        sol_to_lamports(1.0),
+    )
+    .unwrap();
+
+    let exit = Arc::new(AtomicBool::new(false));
+    let (logs_sender, logs_receiver) = crossbeam_channel::unbounded();
+    let websocket_url = test_validator.rpc_pubsub_url();
+    let logs_thread = {
+        let exit = exit.clone();
+        thread::spawn(move || {
+            stream_logs(
+                &websocket_url,
+                &RpcTransactionLogsFilter::Mentions(vec![spl_memo::id().to_string()]),
+                CommitmentConfig::processed(),
+                &exit,
+                |logs| logs_sender.send(logs).unwrap(),
+            )
+        })
+    };
+    // Give the subscription time to be established
+    thread::sleep(Duration::from_secs(1));
+
+    let memo = "solana logs test";
+    let transaction = Transaction::new_signed_with_payer(
+        &[spl_memo::build_memo(memo.as_bytes(), &[])],
+        Some(&default_signer.pubkey()),
+        &[&default_signer],
+        rpc_client.get_latest_blockhash().unwrap(),
+    );
+    let signature = rpc_client
+        .send_and_confirm_transaction(&transaction)
+        .unwrap();
+
+    let logs = logs_receiver.recv_timeout(Duration::from_secs(10)).unwrap();
+    assert_eq!(logs.value.signature, signature.to_string());
+    assert!(logs.value.err.is_none());
+    assert!(logs.value.logs.iter().any(|log| log.contains(memo)));
+
+    exit.store(true, Ordering::Relaxed);
+    logs_thread.join().unwrap();
+}
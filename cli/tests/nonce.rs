@@ -145,7 +145,7 @@ fn test_nonce(seed: Option<String>, use_nonce_authority: bool, compute_unit_pric
         nonce_authority: index,
         memo: None,
         destination_account_pubkey: payee_pubkey,
-        lamports: sol_to_lamports(100.0),
+        amount: SpendAmount::Some(sol_to_lamports(100.0)),
         compute_unit_price,
     };
     process_command(&config_payer).unwrap();
@@ -200,7 +200,7 @@ fn test_nonce(seed: Option<String>, use_nonce_authority: bool, compute_unit_pric
         nonce_authority: 1,
         memo: None,
         destination_account_pubkey: payee_pubkey,
-        lamports: sol_to_lamports(100.0),
+        amount: SpendAmount::Some(sol_to_lamports(100.0)),
         compute_unit_price,
     };
     process_command(&config_payer).unwrap();
@@ -901,6 +901,35 @@ fn test_cli_program_deploy_with_authority() {
         new_upgrade_authority.pubkey()
     );
 
+    // Upgrade with the old authority
+    config.signers = vec![&keypair, &upgrade_authority];
+    config.command = CliCommand::Program(ProgramCliCommand::Deploy {
+        program_location: Some(noop_path.to_str().unwrap().to_string()),
+        fee_payer_signer_index: 0,
+        program_signer_index: None,
+        program_pubkey: Some(program_pubkey),
+        buffer_signer_index: None,
+        buffer_pubkey: None,
+        upgrade_authority_signer_index: 1,
+        is_final: false,
+        max_len: None,
+        skip_fee_check: false,
+        compute_unit_price: None,
+        max_sign_attempts: 5,
+        auto_extend: true,
+        use_rpc: false,
+        skip_feature_verification: true,
+    });
+    expect_command_failure(
+        &config,
+        "Upgrade with the old authority is not allowed",
+        &format!(
+            "Program's authority {:?} does not match authority provided {:?}",
+            Some(new_upgrade_authority.pubkey()),
+            upgrade_authority.pubkey(),
+        ),
+    );
+
     // Upgrade with new authority
     config.signers = vec![&keypair, &new_upgrade_authority];
     config.command = CliCommand::Program(ProgramCliCommand::Deploy {
@@ -385,6 +385,120 @@ fn test_stake_delegation_and_deactivation(compute_unit_price: Option<u64>) {
     process_command(&config_validator).unwrap();
 }
 
+#[test]
+fn test_close_stake_account() {
+    solana_logger::setup();
+
+    let mint_keypair = Keypair::new();
+    let mint_pubkey = mint_keypair.pubkey();
+    let faucet_addr = run_local_faucet(mint_keypair, None);
+    let test_validator =
+        TestValidator::with_no_fees(mint_pubkey, Some(faucet_addr), SocketAddrSpace::Unspecified);
+
+    let rpc_client =
+        RpcClient::new_with_commitment(test_validator.rpc_url(), CommitmentConfig::processed());
+    let validator_keypair = Keypair::new();
+
+    let mut config_validator = CliConfig::recent_for_tests();
+    config_validator.json_rpc_url = test_validator.rpc_url();
+    config_validator.signers = vec![&validator_keypair];
+
+    let stake_keypair = Keypair::new();
+    let stake_account_pubkey = stake_keypair.pubkey();
+
+    request_and_confirm_airdrop(
+        &rpc_client,
+        &config_validator,
+        &config_validator.signers[0].pubkey(),
+        100_000_000_000,
+    )
+    .unwrap();
+
+    // Create stake account
+    config_validator.signers.push(&stake_keypair);
+    config_validator.command = CliCommand::CreateStakeAccount {
+        stake_account: 1,
+        seed: None,
+        staker: None,
+        withdrawer: None,
+        withdrawer_signer: None,
+        lockup: Lockup::default(),
+        amount: SpendAmount::Some(50_000_000_000),
+        sign_only: false,
+        dump_transaction_message: false,
+        blockhash_query: BlockhashQuery::All(blockhash_query::Source::Cluster),
+        nonce_account: None,
+        nonce_authority: 0,
+        memo: None,
+        fee_payer: 0,
+        from: 0,
+        compute_unit_price: None,
+    };
+    process_command(&config_validator).unwrap();
+
+    // Delegate stake
+    config_validator.signers.pop();
+    config_validator.command = CliCommand::DelegateStake {
+        stake_account_pubkey,
+        vote_account_pubkey: test_validator.vote_account_address(),
+        stake_authority: 0,
+        force: true,
+        sign_only: false,
+        dump_transaction_message: false,
+        blockhash_query: BlockhashQuery::default(),
+        nonce_account: None,
+        nonce_authority: 0,
+        memo: None,
+        fee_payer: 0,
+        compute_unit_price: None,
+    };
+    process_command(&config_validator).unwrap();
+
+    // Closing a delegated stake account is refused
+    let destination_account = solana_pubkey::new_rand();
+    let close_stake_account = || CliCommand::CloseStakeAccount {
+        stake_account_pubkey,
+        destination_account_pubkey: destination_account,
+        balance: None,
+        withdraw_authority: 0,
+        custodian: None,
+        sign_only: false,
+        dump_transaction_message: false,
+        blockhash_query: BlockhashQuery::All(blockhash_query::Source::Cluster),
+        nonce_account: None,
+        nonce_authority: 0,
+        memo: None,
+        fee_payer: 0,
+        compute_unit_price: None,
+    };
+    config_validator.command = close_stake_account();
+    process_command(&config_validator).unwrap_err();
+    check_balance!(50_000_000_000, &rpc_client, &stake_account_pubkey);
+
+    // Deactivate stake. Stake deactivated in the epoch it was delegated in
+    // never becomes effective, so the account can be closed straight away
+    config_validator.command = CliCommand::DeactivateStake {
+        stake_account_pubkey,
+        stake_authority: 0,
+        sign_only: false,
+        deactivate_delinquent: false,
+        dump_transaction_message: false,
+        blockhash_query: BlockhashQuery::default(),
+        nonce_account: None,
+        nonce_authority: 0,
+        memo: None,
+        seed: None,
+        fee_payer: 0,
+        compute_unit_price: None,
+    };
+    process_command(&config_validator).unwrap();
+
+    config_validator.command = close_stake_account();
+    process_command(&config_validator).unwrap();
+    check_balance!(0, &rpc_client, &stake_account_pubkey);
+    check_balance!(50_000_000_000, &rpc_client, &destination_account);
+}
+
 #[test_case(None; "base")]
 #[test_case(Some(1_000_000); "with_compute_unit_price")]
 fn test_offline_stake_delegation_and_deactivation(compute_unit_price: Option<u64>) {
@@ -1,5 +1,6 @@
 #![allow(clippy::arithmetic_side_effects)]
 use {
+    assert_matches::assert_matches,
     solana_cli::{
         check_balance,
         cli::{process_command, request_and_confirm_airdrop, CliCommand, CliConfig},
@@ -13,6 +14,7 @@ use {
         account_utils::StateMut,
         commitment_config::CommitmentConfig,
         signature::{Keypair, NullSigner, Signer},
+        stake::state::{Lockup, StakeStateV2},
     },
     solana_streamer::socket::SocketAddrSpace,
     solana_test_validator::TestValidator,
@@ -216,6 +218,13 @@ fn test_vote_authorize_and_withdraw(compute_unit_price: Option<u64>) {
         vote_account_pubkey,
         withdraw_authority: 1,
         destination_account_pubkey: destination_account,
+        allow_active_delegations: false,
+        balance: None,
+        sign_only: false,
+        dump_transaction_message: false,
+        blockhash_query: BlockhashQuery::All(blockhash_query::Source::Cluster),
+        nonce_account: None,
+        nonce_authority: 0,
         memo: None,
         fee_payer: 0,
         compute_unit_price,
@@ -225,6 +234,130 @@ fn test_vote_authorize_and_withdraw(compute_unit_price: Option<u64>) {
     check_balance!(expected_balance, &rpc_client, &destination_account);
 }
 
+#[test]
+fn test_close_vote_account_with_delegations() {
+    let mint_keypair = Keypair::new();
+    let mint_pubkey = mint_keypair.pubkey();
+    let faucet_addr = run_local_faucet(mint_keypair, None);
+    let test_validator =
+        TestValidator::with_no_fees(mint_pubkey, Some(faucet_addr), SocketAddrSpace::Unspecified);
+
+    let rpc_client =
+        RpcClient::new_with_commitment(test_validator.rpc_url(), CommitmentConfig::processed());
+    let default_signer = Keypair::new();
+
+    let mut config = CliConfig::recent_for_tests();
+    config.json_rpc_url = test_validator.rpc_url();
+    config.signers = vec![&default_signer];
+
+    request_and_confirm_airdrop(
+        &rpc_client,
+        &config,
+        &config.signers[0].pubkey(),
+        100_000_000_000,
+    )
+    .unwrap();
+
+    // Create vote account
+    let vote_account_keypair = Keypair::new();
+    let vote_account_pubkey = vote_account_keypair.pubkey();
+    config.signers = vec![&default_signer, &vote_account_keypair];
+    config.command = CliCommand::CreateVoteAccount {
+        vote_account: 1,
+        seed: None,
+        identity_account: 0,
+        authorized_voter: None,
+        authorized_withdrawer: config.signers[0].pubkey(),
+        commission: 0,
+        sign_only: false,
+        dump_transaction_message: false,
+        blockhash_query: BlockhashQuery::All(blockhash_query::Source::Cluster),
+        nonce_account: None,
+        nonce_authority: 0,
+        memo: None,
+        fee_payer: 0,
+        compute_unit_price: None,
+    };
+    process_command(&config).unwrap();
+    let expected_balance = rpc_client
+        .get_minimum_balance_for_rent_exemption(VoteState::size_of())
+        .unwrap()
+        .max(1);
+
+    // Create a stake account and delegate it to the vote account
+    let stake_account_keypair = Keypair::new();
+    config.signers = vec![&default_signer, &stake_account_keypair];
+    config.command = CliCommand::CreateStakeAccount {
+        stake_account: 1,
+        seed: None,
+        staker: None,
+        withdrawer: None,
+        withdrawer_signer: None,
+        lockup: Lockup::default(),
+        amount: SpendAmount::Some(50_000_000_000),
+        sign_only: false,
+        dump_transaction_message: false,
+        blockhash_query: BlockhashQuery::All(blockhash_query::Source::Cluster),
+        nonce_account: None,
+        nonce_authority: 0,
+        memo: None,
+        fee_payer: 0,
+        from: 0,
+        compute_unit_price: None,
+    };
+    process_command(&config).unwrap();
+
+    config.signers = vec![&default_signer];
+    config.command = CliCommand::DelegateStake {
+        stake_account_pubkey: stake_account_keypair.pubkey(),
+        vote_account_pubkey,
+        stake_authority: 0,
+        force: true,
+        sign_only: false,
+        dump_transaction_message: false,
+        blockhash_query: BlockhashQuery::default(),
+        nonce_account: None,
+        nonce_authority: 0,
+        memo: None,
+        fee_payer: 0,
+        compute_unit_price: None,
+    };
+    process_command(&config).unwrap();
+
+    // Closing is refused while a stake account is delegated to the vote account
+    let destination_account = solana_pubkey::new_rand();
+    let close_vote_account = |allow_active_delegations| CliCommand::CloseVoteAccount {
+        vote_account_pubkey,
+        withdraw_authority: 0,
+        destination_account_pubkey: destination_account,
+        allow_active_delegations,
+        balance: None,
+        sign_only: false,
+        dump_transaction_message: false,
+        blockhash_query: BlockhashQuery::All(blockhash_query::Source::Cluster),
+        nonce_account: None,
+        nonce_authority: 0,
+        memo: None,
+        fee_payer: 0,
+        compute_unit_price: None,
+    };
+    config.command = close_vote_account(false);
+    process_command(&config).unwrap_err();
+    check_balance!(expected_balance, &rpc_client, &vote_account_pubkey);
+
+    // ...unless the delegations are explicitly allowed
+    config.command = close_vote_account(true);
+    process_command(&config).unwrap();
+    check_balance!(0, &rpc_client, &vote_account_pubkey);
+    check_balance!(expected_balance, &rpc_client, &destination_account);
+    let stake_state: StakeStateV2 = rpc_client
+        .get_account(&stake_account_keypair.pubkey())
+        .unwrap()
+        .state()
+        .unwrap();
+    assert_matches!(stake_state, StakeStateV2::Stake(..));
+}
+
 #[test_case(None; "base")]
 #[test_case(Some(1_000_000); "with_compute_unit_price")]
 fn test_offline_vote_authorize_and_withdraw(compute_unit_price: Option<u64>) {
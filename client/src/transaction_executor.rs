@@ -21,6 +21,31 @@ use {
 // signature, timestamp, id
 type PendingQueue = Vec<(Signature, u64, u64)>;
 
+/// Outcomes of the transactions cleared by the signature clearing thread
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct SignatureStatusCounts {
+    pub success: u64,
+    pub error: u64,
+    pub timed_out: u64,
+}
+
+#[derive(Debug, Default)]
+struct SignatureStatusCounters {
+    success: AtomicU64,
+    error: AtomicU64,
+    timed_out: AtomicU64,
+}
+
+impl SignatureStatusCounters {
+    fn load(&self) -> SignatureStatusCounts {
+        SignatureStatusCounts {
+            success: self.success.load(Ordering::Relaxed),
+            error: self.error.load(Ordering::Relaxed),
+            timed_out: self.timed_out.load(Ordering::Relaxed),
+        }
+    }
+}
+
 pub struct TransactionExecutor {
     sig_clear_t: JoinHandle<()>,
     sigs: Arc<RwLock<PendingQueue>>,
@@ -28,6 +53,7 @@ pub struct TransactionExecutor {
     exit: Arc<AtomicBool>,
     counter: AtomicU64,
     client: Arc<RpcClient>,
+    status_counters: Arc<SignatureStatusCounters>,
 }
 
 impl TransactionExecutor {
@@ -51,7 +77,9 @@ impl TransactionExecutor {
         let sigs = Arc::new(RwLock::new(Vec::new()));
         let cleared = Arc::new(RwLock::new(Vec::new()));
         let exit = Arc::new(AtomicBool::new(false));
-        let sig_clear_t = Self::start_sig_clear_thread(exit.clone(), &sigs, &cleared, &client);
+        let status_counters = Arc::<SignatureStatusCounters>::default();
+        let sig_clear_t =
+            Self::start_sig_clear_thread(exit.clone(), &sigs, &cleared, &client, &status_counters);
         Self {
             sigs,
             cleared,
@@ -59,6 +87,7 @@ impl TransactionExecutor {
             exit,
             counter: AtomicU64::new(0),
             client,
+            status_counters,
         }
     }
 
@@ -90,9 +119,16 @@ impl TransactionExecutor {
         std::mem::take(&mut *self.cleared.write().unwrap())
     }
 
-    pub fn close(self) {
+    /// Counts of the transactions cleared so far
+    pub fn signature_status_counts(&self) -> SignatureStatusCounts {
+        self.status_counters.load()
+    }
+
+    /// Stops the signature clearing thread, returning the final counts of cleared transactions
+    pub fn close(self) -> SignatureStatusCounts {
         self.exit.store(true, Ordering::Relaxed);
         self.sig_clear_t.join().unwrap();
+        self.status_counters.load()
     }
 
     fn start_sig_clear_thread(
@@ -100,10 +136,12 @@ impl TransactionExecutor {
         sigs: &Arc<RwLock<PendingQueue>>,
         cleared: &Arc<RwLock<Vec<u64>>>,
         client: &Arc<RpcClient>,
+        status_counters: &Arc<SignatureStatusCounters>,
     ) -> JoinHandle<()> {
         let sigs = sigs.clone();
         let cleared = cleared.clone();
         let client = client.clone();
+        let status_counters = status_counters.clone();
         Builder::new()
             .name("solSigClear".to_string())
             .spawn(move || {
@@ -157,6 +195,11 @@ impl TransactionExecutor {
                         }
                         let final_sigs_len = sigs_w.len();
                         drop(sigs_w);
+                        status_counters.success.store(success, Ordering::Relaxed);
+                        status_counters.error.store(error_count, Ordering::Relaxed);
+                        status_counters
+                            .timed_out
+                            .store(timed_out, Ordering::Relaxed);
                         cleared.write().unwrap().extend(new_ids);
                         start.stop();
                         debug!(
@@ -18,6 +18,7 @@ use {
         banking_stage::{
             committer::Committer,
             consumer::Consumer,
+            forward_tracker::ForwarderConfig,
             leader_slot_metrics::LeaderSlotMetricsTracker,
             qos_service::QosService,
             unprocessed_packet_batches::*,
@@ -325,6 +326,7 @@ fn bench_banking(bencher: &mut Bencher, tx_type: TransactionType) {
         bank_forks,
         &Arc::new(PrioritizationFeeCache::new(0u64)),
         false,
+        ForwarderConfig::default(),
         HashSet::default(),
         BundleAccountLocker::default(),
     );
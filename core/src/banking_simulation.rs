@@ -1,7 +1,7 @@
 #![cfg(feature = "dev-context-only-utils")]
 use {
     crate::{
-        banking_stage::{BankingStage, LikeClusterInfo},
+        banking_stage::{forward_tracker::ForwarderConfig, BankingStage, LikeClusterInfo},
         banking_trace::{
             BankingPacketBatch, BankingTracer, ChannelLabel, Channels, TimedTracedEvent,
             TracedEvent, TracedSender, TracerThread, BANKING_TRACE_DIR_DEFAULT_BYTE_LIMIT,
@@ -819,6 +819,7 @@ impl BankingSimulator {
             bank_forks.clone(),
             prioritization_fee_cache,
             false,
+            ForwarderConfig::default(),
             collections::HashSet::default(),
             BundleAccountLocker::default(),
         );
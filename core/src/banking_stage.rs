@@ -7,6 +7,7 @@ use {
         committer::Committer,
         consumer::Consumer,
         decision_maker::{BufferedPacketsDecision, DecisionMaker},
+        forward_tracker::ForwarderConfig,
         forwarder::Forwarder,
         latest_unprocessed_votes::{LatestUnprocessedVotes, VoteSource},
         leader_slot_metrics::LeaderSlotMetricsTracker,
@@ -71,6 +72,7 @@ pub mod unprocessed_transaction_storage;
 mod consume_worker;
 pub(crate) mod decision_maker;
 mod forward_packet_batches_by_accounts;
+pub mod forward_tracker;
 pub(crate) mod immutable_deserialized_packet;
 mod latest_unprocessed_votes;
 pub(crate) mod leader_slot_timing_metrics;
@@ -364,6 +366,7 @@ impl BankingStage {
         bank_forks: Arc<RwLock<BankForks>>,
         prioritization_fee_cache: &Arc<PrioritizationFeeCache>,
         enable_forwarding: bool,
+        forwarder_config: ForwarderConfig,
         blacklisted_accounts: HashSet<Pubkey>,
         bundle_account_locker: BundleAccountLocker,
     ) -> Self {
@@ -382,6 +385,7 @@ impl BankingStage {
             bank_forks,
             prioritization_fee_cache,
             enable_forwarding,
+            forwarder_config,
             blacklisted_accounts,
             bundle_account_locker,
         )
@@ -403,6 +407,7 @@ impl BankingStage {
         bank_forks: Arc<RwLock<BankForks>>,
         prioritization_fee_cache: &Arc<PrioritizationFeeCache>,
         enable_forwarding: bool,
+        forwarder_config: ForwarderConfig,
         blacklisted_accounts: HashSet<Pubkey>,
         bundle_account_locker: BundleAccountLocker,
     ) -> Self {
@@ -421,6 +426,7 @@ impl BankingStage {
                 bank_forks,
                 prioritization_fee_cache,
                 enable_forwarding,
+                forwarder_config,
                 blacklisted_accounts,
                 bundle_account_locker,
             ),
@@ -442,6 +448,7 @@ impl BankingStage {
         bank_forks: Arc<RwLock<BankForks>>,
         prioritization_fee_cache: &Arc<PrioritizationFeeCache>,
         enable_forwarding: bool,
+        forwarder_config: ForwarderConfig,
         blacklisted_accounts: HashSet<Pubkey>,
         bundle_account_locker: BundleAccountLocker,
     ) -> Self {
@@ -532,12 +539,13 @@ impl BankingStage {
         }
 
         let forwarder = enable_forwarding.then(|| {
-            Forwarder::new(
+            Forwarder::new_with_config(
                 poh_recorder.clone(),
                 bank_forks.clone(),
                 cluster_info.clone(),
                 connection_cache.clone(),
                 data_budget.clone(),
+                forwarder_config,
             )
         });
 
@@ -838,6 +846,7 @@ mod tests {
                 bank_forks,
                 &Arc::new(PrioritizationFeeCache::new(0u64)),
                 false,
+                ForwarderConfig::default(),
                 HashSet::default(),
                 BundleAccountLocker::default(),
             );
@@ -900,6 +909,7 @@ mod tests {
                 bank_forks,
                 &Arc::new(PrioritizationFeeCache::new(0u64)),
                 false,
+                ForwarderConfig::default(),
                 HashSet::default(),
                 BundleAccountLocker::default(),
             );
@@ -991,6 +1001,7 @@ mod tests {
                 bank_forks.clone(), // keep a local-copy of bank-forks so worker threads do not lose weak access to bank-forks
                 &Arc::new(PrioritizationFeeCache::new(0u64)),
                 false,
+                ForwarderConfig::default(),
                 HashSet::default(),
                 BundleAccountLocker::default(),
             );
@@ -1168,6 +1179,7 @@ mod tests {
                     bank_forks,
                     &Arc::new(PrioritizationFeeCache::new(0u64)),
                     false,
+                    ForwarderConfig::default(),
                     HashSet::default(),
                     BundleAccountLocker::default(),
                 );
@@ -1375,6 +1387,7 @@ mod tests {
                 bank_forks,
                 &Arc::new(PrioritizationFeeCache::new(0u64)),
                 false,
+                ForwarderConfig::default(),
                 HashSet::default(),
                 BundleAccountLocker::default(),
             );
@@ -334,6 +334,7 @@ impl ConsumeWorkerMetrics {
             too_many_account_locks,
             account_loaded_twice,
             account_not_found,
+            account_purged_by_rent,
             blockhash_not_found,
             blockhash_too_old,
             call_chain_too_deep,
@@ -370,6 +371,9 @@ impl ConsumeWorkerMetrics {
         self.error_metrics
             .account_not_found
             .fetch_add(account_not_found.0, Ordering::Relaxed);
+        self.error_metrics
+            .account_purged_by_rent
+            .fetch_add(account_purged_by_rent.0, Ordering::Relaxed);
         self.error_metrics
             .blockhash_not_found
             .fetch_add(blockhash_not_found.0, Ordering::Relaxed);
@@ -606,6 +610,7 @@ struct ConsumeWorkerTransactionErrorMetrics {
     too_many_account_locks: AtomicUsize,
     account_loaded_twice: AtomicUsize,
     account_not_found: AtomicUsize,
+    account_purged_by_rent: AtomicUsize,
     blockhash_not_found: AtomicUsize,
     blockhash_too_old: AtomicUsize,
     call_chain_too_deep: AtomicUsize,
@@ -653,6 +658,11 @@ impl ConsumeWorkerTransactionErrorMetrics {
                 self.account_not_found.swap(0, Ordering::Relaxed),
                 i64
             ),
+            (
+                "account_purged_by_rent",
+                self.account_purged_by_rent.swap(0, Ordering::Relaxed),
+                i64
+            ),
             (
                 "blockhash_not_found",
                 self.blockhash_not_found.swap(0, Ordering::Relaxed),
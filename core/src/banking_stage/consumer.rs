@@ -647,6 +647,7 @@ impl Consumer {
                     account_overrides: None,
                     check_program_modification_slot: bank.check_program_modification_slot(),
                     compute_budget: bank.compute_budget(),
+                    max_instruction_stack_depth: bank.max_instruction_stack_depth(),
                     log_messages_bytes_limit: self.log_messages_bytes_limit,
                     limit_to_load_programs: true,
                     recording_config: ExecutionRecordingConfig::new_single_setting(
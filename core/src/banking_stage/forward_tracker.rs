@@ -0,0 +1,282 @@
+//! Bookkeeping for the packets a [`Forwarder`](super::forwarder::Forwarder) sends to upcoming
+//! leaders: drops packets already forwarded to the same leader, caps the number forwarded per
+//! slot, and samples forwarded signatures to measure how many are included soon after.
+
+use {
+    solana_perf::packet::Packet,
+    solana_runtime::bank::Bank,
+    solana_sdk::{
+        clock::Slot,
+        pubkey::Pubkey,
+        signature::{Signature, SIGNATURE_BYTES},
+    },
+    std::collections::{HashSet, VecDeque},
+};
+
+/// Upper bound on the number of sampled signatures awaiting an inclusion check
+const MAX_PENDING_SAMPLES: usize = 10_000;
+
+#[derive(Clone, Debug)]
+pub struct ForwarderConfig {
+    /// Maximum number of packets forwarded per slot, 0 for no limit
+    pub max_forwarded_packets_per_slot: usize,
+
+    /// One in this many forwarded packets has its signature sampled for inclusion tracking
+    pub inclusion_sample_rate: usize,
+
+    /// A sampled transaction counts as included if it lands within this many slots of
+    /// being forwarded
+    pub inclusion_slots: Slot,
+}
+
+impl Default for ForwarderConfig {
+    fn default() -> Self {
+        Self {
+            max_forwarded_packets_per_slot: 0,
+            inclusion_sample_rate: 64,
+            inclusion_slots: 4,
+        }
+    }
+}
+
+#[derive(Debug, Default, PartialEq, Eq)]
+struct ForwardMetrics {
+    forwarded: usize,
+    dropped_duplicate: usize,
+    dropped_cap: usize,
+    sampled: usize,
+    sampled_included: usize,
+    sampled_not_included: usize,
+}
+
+pub(crate) struct ForwardTracker {
+    config: ForwarderConfig,
+    /// Slot the per-slot cap and metrics currently apply to
+    slot: Option<Slot>,
+    /// Leader the dedup set currently applies to
+    leader: Option<Pubkey>,
+    forwarded_signatures: HashSet<Signature>,
+    forwarded_in_slot: usize,
+    /// Sampled signatures and the slot they were forwarded in, oldest first
+    pending_samples: VecDeque<(Slot, Signature)>,
+    metrics: ForwardMetrics,
+}
+
+impl ForwardTracker {
+    pub(crate) fn new(config: ForwarderConfig) -> Self {
+        Self {
+            config,
+            slot: None,
+            leader: None,
+            forwarded_signatures: HashSet::new(),
+            forwarded_in_slot: 0,
+            pending_samples: VecDeque::new(),
+            metrics: ForwardMetrics::default(),
+        }
+    }
+
+    /// Start forwarding to `leader` while `bank` is the working bank.
+    ///
+    /// A new slot resets the per-slot cap and reports the previous slot's metrics, and a new
+    /// leader forgets which packets have already been forwarded.
+    pub(crate) fn begin(&mut self, bank: &Bank, leader: Pubkey) {
+        if self.slot != Some(bank.slot()) {
+            self.check_inclusion(bank);
+            if let Some(slot) = self.slot {
+                self.report_metrics(slot);
+            }
+            self.slot = Some(bank.slot());
+            self.forwarded_in_slot = 0;
+        }
+        if self.leader != Some(leader) {
+            self.leader = Some(leader);
+            self.forwarded_signatures.clear();
+        }
+    }
+
+    /// Returns whether `packet` should be forwarded, counting it as dropped if it was already
+    /// forwarded to the current leader or the per-slot cap has been reached
+    pub(crate) fn should_forward(&mut self, packet: &Packet) -> bool {
+        if packet_signature(packet)
+            .is_some_and(|signature| self.forwarded_signatures.contains(&signature))
+        {
+            self.metrics.dropped_duplicate += 1;
+            return false;
+        }
+        if self.config.max_forwarded_packets_per_slot > 0
+            && self.forwarded_in_slot >= self.config.max_forwarded_packets_per_slot
+        {
+            self.metrics.dropped_cap += 1;
+            return false;
+        }
+        true
+    }
+
+    /// Record that `packet` is being forwarded
+    pub(crate) fn record_forwarded(&mut self, packet: &Packet) {
+        self.forwarded_in_slot += 1;
+        self.metrics.forwarded += 1;
+        let Some(signature) = packet_signature(packet) else {
+            return;
+        };
+        self.forwarded_signatures.insert(signature);
+        if self.metrics.forwarded % self.config.inclusion_sample_rate.max(1) == 0
+            && self.pending_samples.len() < MAX_PENDING_SAMPLES
+        {
+            if let Some(slot) = self.slot {
+                self.metrics.sampled += 1;
+                self.pending_samples.push_back((slot, signature));
+            }
+        }
+    }
+
+    /// Resolve the samples whose inclusion window has passed as of `bank`
+    fn check_inclusion(&mut self, bank: &Bank) {
+        while let Some((forwarded_slot, signature)) = self.pending_samples.front() {
+            let last_inclusion_slot = forwarded_slot.saturating_add(self.config.inclusion_slots);
+            if last_inclusion_slot >= bank.slot() {
+                break;
+            }
+            match bank.get_signature_status_slot(signature) {
+                Some((slot, _)) if slot <= last_inclusion_slot => {
+                    self.metrics.sampled_included += 1
+                }
+                _ => self.metrics.sampled_not_included += 1,
+            }
+            self.pending_samples.pop_front();
+        }
+    }
+
+    fn report_metrics(&mut self, slot: Slot) {
+        let metrics = std::mem::take(&mut self.metrics);
+        if metrics == ForwardMetrics::default() {
+            return;
+        }
+        datapoint_info!(
+            "banking_stage-forwarded_packets",
+            ("slot", slot, i64),
+            ("forwarded", metrics.forwarded, i64),
+            ("dropped_duplicate", metrics.dropped_duplicate, i64),
+            ("dropped_cap", metrics.dropped_cap, i64),
+            ("sampled", metrics.sampled, i64),
+            ("sampled_included", metrics.sampled_included, i64),
+            ("sampled_not_included", metrics.sampled_not_included, i64),
+        );
+    }
+}
+
+/// The first signature of the transaction in `packet`.
+///
+/// Forwardable packets have already been deserialized, and a transaction that fits in a packet
+/// has fewer than 128 signatures, so the signature count is a single byte.
+fn packet_signature(packet: &Packet) -> Option<Signature> {
+    packet
+        .data(1..1 + SIGNATURE_BYTES)
+        .and_then(|bytes| Signature::try_from(bytes).ok())
+}
+
+#[cfg(test)]
+mod tests {
+    use {
+        super::*,
+        solana_runtime::genesis_utils::create_genesis_config,
+        solana_sdk::{
+            hash::Hash, native_token::LAMPORTS_PER_SOL, signature::Keypair, system_transaction,
+        },
+        std::sync::Arc,
+    };
+
+    fn transfer_packet(keypair: &Keypair, blockhash: Hash) -> Packet {
+        let tx = system_transaction::transfer(keypair, &Pubkey::new_unique(), 1, blockhash);
+        Packet::from_data(None, tx).unwrap()
+    }
+
+    #[test]
+    fn test_dedup_per_leader() {
+        let bank = Bank::default_for_tests();
+        let mut tracker = ForwardTracker::new(ForwarderConfig::default());
+        let (leader0, leader1) = (Pubkey::new_unique(), Pubkey::new_unique());
+        let packet = transfer_packet(&Keypair::new(), Hash::default());
+
+        tracker.begin(&bank, leader0);
+        assert!(tracker.should_forward(&packet));
+        tracker.record_forwarded(&packet);
+        assert!(!tracker.should_forward(&packet));
+        assert_eq!(tracker.metrics.dropped_duplicate, 1);
+
+        // The next leader hasn't seen the packet
+        tracker.begin(&bank, leader1);
+        assert!(tracker.should_forward(&packet));
+    }
+
+    #[test]
+    fn test_cap_per_slot() {
+        let bank0 = Arc::new(Bank::default_for_tests());
+        let bank1 = Bank::new_from_parent(bank0.clone(), &Pubkey::default(), 1);
+        let mut tracker = ForwardTracker::new(ForwarderConfig {
+            max_forwarded_packets_per_slot: 2,
+            ..ForwarderConfig::default()
+        });
+        let leader = Pubkey::new_unique();
+        let keypair = Keypair::new();
+
+        tracker.begin(&bank0, leader);
+        for _ in 0..2 {
+            let packet = transfer_packet(&keypair, Hash::new_unique());
+            assert!(tracker.should_forward(&packet));
+            tracker.record_forwarded(&packet);
+        }
+        let packet = transfer_packet(&keypair, Hash::new_unique());
+        assert!(!tracker.should_forward(&packet));
+        assert_eq!(
+            tracker.metrics,
+            ForwardMetrics {
+                forwarded: 2,
+                dropped_cap: 1,
+                ..ForwardMetrics::default()
+            }
+        );
+
+        // The cap resets, and the metrics are reported, in the next slot
+        tracker.begin(&bank1, leader);
+        assert!(tracker.should_forward(&packet));
+        assert_eq!(tracker.metrics, ForwardMetrics::default());
+    }
+
+    #[test]
+    fn test_inclusion() {
+        let genesis_config_info = create_genesis_config(LAMPORTS_PER_SOL);
+        let bank0 = Arc::new(Bank::new_for_tests(&genesis_config_info.genesis_config));
+        let mut tracker = ForwardTracker::new(ForwarderConfig {
+            inclusion_sample_rate: 1,
+            inclusion_slots: 1,
+            ..ForwarderConfig::default()
+        });
+        let leader = Pubkey::new_unique();
+
+        let included_tx = system_transaction::transfer(
+            &genesis_config_info.mint_keypair,
+            &Pubkey::new_unique(),
+            genesis_config_info.genesis_config.rent.minimum_balance(0),
+            bank0.last_blockhash(),
+        );
+        let included_packet = Packet::from_data(None, &included_tx).unwrap();
+        let dropped_packet = transfer_packet(&Keypair::new(), bank0.last_blockhash());
+        tracker.begin(&bank0, leader);
+        tracker.record_forwarded(&included_packet);
+        tracker.record_forwarded(&dropped_packet);
+        assert_eq!(tracker.pending_samples.len(), 2);
+        bank0.process_transaction(&included_tx).unwrap();
+
+        // Samples are only resolved once their inclusion window has passed
+        let bank1 = Arc::new(Bank::new_from_parent(bank0, &Pubkey::default(), 1));
+        tracker.begin(&bank1, leader);
+        assert_eq!(tracker.pending_samples.len(), 2);
+
+        let bank2 = Bank::new_from_parent(bank1, &Pubkey::default(), 2);
+        tracker.check_inclusion(&bank2);
+        assert!(tracker.pending_samples.is_empty());
+        assert_eq!(tracker.metrics.sampled_included, 1);
+        assert_eq!(tracker.metrics.sampled_not_included, 1);
+    }
+}
@@ -1,9 +1,10 @@
 use {
     super::{
         forward_packet_batches_by_accounts::ForwardPacketBatchesByAccounts,
+        forward_tracker::{ForwardTracker, ForwarderConfig},
         leader_slot_metrics::LeaderSlotMetricsTracker,
-        unprocessed_transaction_storage::UnprocessedTransactionStorage, BankingStageStats,
-        ForwardOption,
+        unprocessed_transaction_storage::UnprocessedTransactionStorage,
+        BankingStageStats, ForwardOption,
     },
     crate::{
         banking_stage::{
@@ -25,7 +26,7 @@ use {
     std::{
         iter::repeat,
         net::{SocketAddr, UdpSocket},
-        sync::{atomic::Ordering, Arc, RwLock},
+        sync::{atomic::Ordering, Arc, Mutex, RwLock},
     },
 };
 
@@ -37,6 +38,7 @@ pub struct Forwarder<T: LikeClusterInfo> {
     connection_cache: Arc<ConnectionCache>,
     data_budget: Arc<DataBudget>,
     forward_packet_batches_by_accounts: ForwardPacketBatchesByAccounts,
+    forward_tracker: Mutex<ForwardTracker>,
 }
 
 impl<T: LikeClusterInfo> Forwarder<T> {
@@ -46,6 +48,24 @@ impl<T: LikeClusterInfo> Forwarder<T> {
         cluster_info: T,
         connection_cache: Arc<ConnectionCache>,
         data_budget: Arc<DataBudget>,
+    ) -> Self {
+        Self::new_with_config(
+            poh_recorder,
+            bank_forks,
+            cluster_info,
+            connection_cache,
+            data_budget,
+            ForwarderConfig::default(),
+        )
+    }
+
+    pub fn new_with_config(
+        poh_recorder: Arc<RwLock<PohRecorder>>,
+        bank_forks: Arc<RwLock<BankForks>>,
+        cluster_info: T,
+        connection_cache: Arc<ConnectionCache>,
+        data_budget: Arc<DataBudget>,
+        config: ForwarderConfig,
     ) -> Self {
         Self {
             poh_recorder,
@@ -56,6 +76,7 @@ impl<T: LikeClusterInfo> Forwarder<T> {
             data_budget,
             forward_packet_batches_by_accounts:
                 ForwardPacketBatchesByAccounts::new_with_default_batch_limits(),
+            forward_tracker: Mutex::new(ForwardTracker::new(config)),
         }
     }
 
@@ -188,12 +209,25 @@ impl<T: LikeClusterInfo> Forwarder<T> {
         };
 
         self.update_data_budget();
+        let mut forward_tracker = self.forward_tracker.lock().unwrap();
+        forward_tracker.begin(
+            &self.bank_forks.read().unwrap().working_bank(),
+            leader_pubkey,
+        );
+        // Collect everything into a single batch so it goes out in as few sends as possible
         let packet_vec: Vec<_> = forwardable_packets
             .filter(|p| !p.meta().forwarded())
             .filter(|p| p.meta().is_from_staked_node())
-            .filter(|p| self.data_budget.take(p.meta().size))
-            .filter_map(|p| p.data(..).map(|data| data.to_vec()))
+            .filter_map(|p| {
+                if !forward_tracker.should_forward(p) || !self.data_budget.take(p.meta().size) {
+                    return None;
+                }
+                let data = p.data(..)?.to_vec();
+                forward_tracker.record_forwarded(p);
+                Some(data)
+            })
             .collect();
+        drop(forward_tracker);
 
         let packet_vec_len = packet_vec.len();
         // TODO: see https://github.com/solana-labs/solana/issues/23819
@@ -414,6 +448,12 @@ mod tests {
                 break;
             }
         }
+        // Give packets beyond the expected number a chance to arrive, so that forwarding too
+        // many is caught rather than ignored
+        sleep(Duration::from_millis(200)).await;
+        while let Ok(packets) = receiver.try_recv() {
+            total_packets += packets.len();
+        }
         assert_eq!(total_packets, expected_num_packets);
 
         exit.store(true, Ordering::Relaxed);
@@ -489,6 +529,69 @@ mod tests {
         poh_service.join().unwrap();
     }
 
+    #[test]
+    fn test_forwarder_cap() {
+        let TestSetup {
+            blockhash,
+            rent_min_balance,
+            bank_forks,
+            poh_recorder,
+            exit,
+            poh_service,
+            cluster_info,
+            local_node,
+            ..
+        } = setup();
+
+        let packets: Vec<_> = (0..3)
+            .map(|_| {
+                let tx = system_transaction::transfer(
+                    &Keypair::new(),
+                    &solana_pubkey::new_rand(),
+                    rent_min_balance,
+                    blockhash,
+                );
+                let mut packet = Packet::from_data(None, tx).unwrap();
+                packet.meta_mut().set_from_staked_node(true);
+                DeserializedPacket::new(packet).unwrap()
+            })
+            .collect();
+        let expected_packet_size = packets[0].immutable_section().original_packet().meta().size;
+
+        let mut forwarder = Forwarder::new_with_config(
+            poh_recorder,
+            bank_forks,
+            cluster_info,
+            Arc::new(ConnectionCache::new("connection_cache_test")),
+            Arc::new(DataBudget::default()),
+            ForwarderConfig {
+                max_forwarded_packets_per_slot: 2,
+                ..ForwarderConfig::default()
+            },
+        );
+        forwarder.handle_forwarding(
+            &mut UnprocessedTransactionStorage::new_transaction_storage(
+                UnprocessedPacketBatches::from_iter(packets, 3),
+                ThreadType::Transactions,
+            ),
+            true,
+            &mut LeaderSlotMetricsTracker::new(0),
+            &BankingStageStats::default(),
+        );
+
+        // Exactly two of the three packets fit under the cap
+        let recv_socket = &local_node.sockets.tpu_forwards_quic[0];
+        rt("solQuicTestRt".to_string()).block_on(check_all_received(
+            (*recv_socket).try_clone().unwrap(),
+            2,
+            expected_packet_size,
+            &blockhash,
+        ));
+
+        exit.store(true, Ordering::Relaxed);
+        poh_service.join().unwrap();
+    }
+
     #[test]
     fn test_handle_forwarding() {
         let TestSetup {
@@ -416,6 +416,11 @@ fn report_transaction_error_metrics(errors: &TransactionErrorMetrics, id: &str,
             i64
         ),
         ("account_not_found", errors.account_not_found.0 as i64, i64),
+        (
+            "account_purged_by_rent",
+            errors.account_purged_by_rent.0 as i64,
+            i64
+        ),
         ("blockhash_not_found", errors.blockhash_not_found.0 as i64, i64),
         ("blockhash_too_old", errors.blockhash_too_old.0 as i64, i64),
         ("call_chain_too_deep", errors.call_chain_too_deep.0 as i64, i64),
@@ -11,7 +11,10 @@ use {
     },
     std::{
         collections::{BTreeMap, HashMap},
-        sync::{Arc, Mutex, RwLock},
+        sync::{
+            atomic::{AtomicU64, Ordering},
+            Arc, Mutex, RwLock,
+        },
     },
 };
 
@@ -21,20 +24,100 @@ const CLUSTER_SLOTS_TRIM_SIZE: usize = 524_288; // 512K
 
 pub(crate) type SlotPubkeys = HashMap</*node:*/ Pubkey, /*stake:*/ u64>;
 
+/// Tracks which nodes have completed which slots, as advertised in their
+/// EpochSlots, along with the stake of those nodes.
+///
+/// Each slot's nodes sit behind their own lock, and the per-slot stake totals
+/// are kept separately, so readers only contend with gossip ingestion for as
+/// long as it takes to look a slot up.
 #[derive(Default)]
 pub struct ClusterSlots {
     cluster_slots: RwLock<BTreeMap<Slot, Arc<RwLock<SlotPubkeys>>>>,
+    // Total stake of the nodes in `cluster_slots`, maintained incrementally.
+    slot_stakes: RwLock<BTreeMap<Slot, u64>>,
     validator_stakes: RwLock<Arc<NodeIdToVoteAccounts>>,
+    total_stake: AtomicU64,
     epoch: RwLock<Option<u64>>,
+    // Number of slots at or before the root that are retained.
+    root_retention: Slot,
     cursor: Mutex<Cursor>,
     last_report: AtomicInterval,
 }
 
 impl ClusterSlots {
+    /// Retain slots up to `root_retention` slots at or before the root, in
+    /// addition to the slots past the root. The default retains none.
+    pub fn new(root_retention: Slot) -> Self {
+        Self {
+            root_retention,
+            ..Self::default()
+        }
+    }
+
     pub(crate) fn lookup(&self, slot: Slot) -> Option<Arc<RwLock<SlotPubkeys>>> {
         self.cluster_slots.read().unwrap().get(&slot).cloned()
     }
 
+    /// Returns the nodes which have completed `slot`, along with their stake.
+    pub fn peers_with_slot(&self, slot: Slot) -> Vec<(Pubkey, u64)> {
+        self.lookup(slot)
+            .map(|slot_pubkeys| {
+                let slot_pubkeys = slot_pubkeys.read().unwrap();
+                slot_pubkeys
+                    .iter()
+                    .map(|(pubkey, stake)| (*pubkey, *stake))
+                    .collect()
+            })
+            .unwrap_or_default()
+    }
+
+    /// Returns the fraction of the root epoch's stake which has completed `slot`.
+    pub fn availability(&self, slot: Slot) -> f64 {
+        let total_stake = self.total_stake.load(Ordering::Relaxed);
+        if total_stake == 0 {
+            return 0.0;
+        }
+        let slot_stake = self
+            .slot_stakes
+            .read()
+            .unwrap()
+            .get(&slot)
+            .copied()
+            .unwrap_or_default();
+        slot_stake as f64 / total_stake as f64
+    }
+
+    // Returns the first slot which is retained given the current root.
+    fn first_retained_slot(&self, root: Slot) -> Slot {
+        root.saturating_sub(self.root_retention).saturating_add(1)
+    }
+
+    // Inserts the nodes into the slot's pubkeys and returns the resulting
+    // change to the slot's total stake, as (added, removed).
+    fn insert_nodes_stakes(
+        slot_pubkeys: &RwLock<SlotPubkeys>,
+        nodes_stakes: impl IntoIterator<Item = (Pubkey, u64)>,
+    ) -> (u64, u64) {
+        let mut slot_pubkeys = slot_pubkeys.write().unwrap();
+        nodes_stakes
+            .into_iter()
+            .fold((0, 0), |(added, removed), (node, stake)| {
+                let old_stake = slot_pubkeys.insert(node, stake).unwrap_or_default();
+                (
+                    added.saturating_add(stake),
+                    removed.saturating_add(old_stake),
+                )
+            })
+    }
+
+    fn update_slot_stakes(&self, deltas: impl IntoIterator<Item = (Slot, (u64, u64))>) {
+        let mut slot_stakes = self.slot_stakes.write().unwrap();
+        for (slot, (added, removed)) in deltas {
+            let slot_stake = slot_stakes.entry(slot).or_default();
+            *slot_stake = slot_stake.saturating_add(added).saturating_sub(removed);
+        }
+    }
+
     pub(crate) fn update(&self, root_bank: &Bank, cluster_info: &ClusterInfo) {
         self.update_peers(root_bank);
         let epoch_slots = {
@@ -60,8 +143,9 @@ impl ClusterSlots {
                 })
                 .collect()
         };
-        // Discard slots at or before current root or epochs ahead.
-        let slot_range = (root + 1)
+        // Discard slots too far behind the current root or epochs ahead.
+        let first_retained_slot = self.first_retained_slot(root);
+        let slot_range = first_retained_slot
             ..root.saturating_add(
                 num_epoch_slots
                     .max(DEFAULT_SLOTS_PER_EPOCH)
@@ -71,7 +155,7 @@ impl ClusterSlots {
             .into_iter()
             .flat_map(|(epoch_slots, stake)| {
                 epoch_slots
-                    .to_slots(root)
+                    .to_slots(first_retained_slot)
                     .into_iter()
                     .filter(|slot| slot_range.contains(slot))
                     .zip(std::iter::repeat((epoch_slots.from, stake)))
@@ -83,22 +167,34 @@ impl ClusterSlots {
                 .into_iter()
                 .map(|(slot, nodes_stakes)| {
                     let slot_nodes = cluster_slots.entry(slot).or_default().clone();
-                    (slot_nodes, nodes_stakes)
+                    (slot, slot_nodes, nodes_stakes)
                 })
                 .collect()
         };
-        for (slot_nodes, nodes_stakes) in slot_nodes_stakes {
-            slot_nodes.write().unwrap().extend(nodes_stakes);
-        }
+        let slot_stake_deltas: Vec<_> = slot_nodes_stakes
+            .into_iter()
+            .map(|(slot, slot_nodes, nodes_stakes)| {
+                (slot, Self::insert_nodes_stakes(&slot_nodes, nodes_stakes))
+            })
+            .collect();
+        self.update_slot_stakes(slot_stake_deltas);
         {
             let mut cluster_slots = self.cluster_slots.write().unwrap();
-            *cluster_slots = cluster_slots.split_off(&(root + 1));
+            *cluster_slots = cluster_slots.split_off(&first_retained_slot);
             // Allow 10% overshoot so that the computation cost is amortized
             // down. The slots furthest away from the root are discarded.
+            let mut trim_key = None;
             if 10 * cluster_slots.len() > 11 * CLUSTER_SLOTS_TRIM_SIZE {
                 warn!("trimming cluster slots");
                 let key = *cluster_slots.keys().nth(CLUSTER_SLOTS_TRIM_SIZE).unwrap();
                 cluster_slots.split_off(&key);
+                trim_key = Some(key);
+            }
+            drop(cluster_slots);
+            let mut slot_stakes = self.slot_stakes.write().unwrap();
+            *slot_stakes = slot_stakes.split_off(&first_retained_slot);
+            if let Some(key) = trim_key {
+                slot_stakes.split_off(&key);
             }
         }
         self.report_cluster_slots_size();
@@ -142,6 +238,11 @@ impl ClusterSlots {
             .get(&node_id)
             .map(|v| v.total_stake)
             .unwrap_or(0);
+        self.insert_node_stake(slot, node_id, balance);
+    }
+
+    #[cfg(test)]
+    pub(crate) fn insert_node_stake(&self, slot: Slot, node_id: Pubkey, balance: u64) {
         let slot_pubkeys = self
             .cluster_slots
             .write()
@@ -149,7 +250,8 @@ impl ClusterSlots {
             .entry(slot)
             .or_default()
             .clone();
-        slot_pubkeys.write().unwrap().insert(node_id, balance);
+        let delta = Self::insert_nodes_stakes(&slot_pubkeys, [(node_id, balance)]);
+        self.update_slot_stakes([(slot, delta)]);
     }
 
     fn update_peers(&self, root_bank: &Bank) {
@@ -157,13 +259,14 @@ impl ClusterSlots {
         let my_epoch = *self.epoch.read().unwrap();
 
         if Some(root_epoch) != my_epoch {
-            let validator_stakes = root_bank
+            let epoch_stakes = root_bank
                 .epoch_stakes(root_epoch)
-                .expect("Bank must have epoch stakes for its own epoch")
-                .node_id_to_vote_accounts()
-                .clone();
+                .expect("Bank must have epoch stakes for its own epoch");
+            let validator_stakes = epoch_stakes.node_id_to_vote_accounts().clone();
 
             *self.validator_stakes.write().unwrap() = validator_stakes;
+            self.total_stake
+                .store(epoch_stakes.total_stake(), Ordering::Relaxed);
             *self.epoch.write().unwrap() = Some(root_epoch);
         }
     }
@@ -391,4 +494,120 @@ mod tests {
             Some(&1)
         );
     }
+
+    fn new_validator_stakes(stakes: &[(Pubkey, u64)]) -> Arc<NodeIdToVoteAccounts> {
+        Arc::new(
+            stakes
+                .iter()
+                .map(|(node_id, stake)| {
+                    (
+                        *node_id,
+                        NodeVoteAccounts {
+                            total_stake: *stake,
+                            vote_accounts: vec![Pubkey::new_unique()],
+                        },
+                    )
+                })
+                .collect(),
+        )
+    }
+
+    fn new_epoch_slots(from: Pubkey, slots: &[Slot]) -> EpochSlots {
+        let mut epoch_slots = EpochSlots {
+            from,
+            ..EpochSlots::default()
+        };
+        epoch_slots.fill(slots, 0);
+        epoch_slots
+    }
+
+    #[test]
+    fn test_update_purges_outside_root_retention() {
+        let cs = ClusterSlots::new(2);
+        let node = Pubkey::new_unique();
+        let slots: Vec<_> = (1..=10).collect();
+        cs.update_internal(
+            5,
+            vec![new_epoch_slots(node, &slots)],
+            DEFAULT_SLOTS_PER_EPOCH,
+        );
+        // Slots 4 and 5 are retained behind the root
+        assert!(cs.lookup(3).is_none());
+        assert_eq!(cs.peers_with_slot(4), vec![(node, 0)]);
+        assert_eq!(cs.peers_with_slot(10), vec![(node, 0)]);
+        assert_eq!(*cs.slot_stakes.read().unwrap().keys().next().unwrap(), 4);
+
+        // Advancing the root purges the slots which fall out of the window
+        cs.update_internal(8, vec![], DEFAULT_SLOTS_PER_EPOCH);
+        assert!(cs.lookup(6).is_none());
+        assert!(cs.peers_with_slot(6).is_empty());
+        assert!(cs.lookup(7).is_some());
+        assert_eq!(cs.cluster_slots.read().unwrap().len(), 4);
+        assert_eq!(
+            cs.slot_stakes
+                .read()
+                .unwrap()
+                .keys()
+                .copied()
+                .collect::<Vec<_>>(),
+            vec![7, 8, 9, 10]
+        );
+
+        // Without any retention, slots at or before the root are dropped
+        let cs = ClusterSlots::default();
+        cs.update_internal(
+            5,
+            vec![new_epoch_slots(node, &slots)],
+            DEFAULT_SLOTS_PER_EPOCH,
+        );
+        assert!(cs.lookup(5).is_none());
+        assert!(cs.lookup(6).is_some());
+    }
+
+    #[test]
+    fn test_availability() {
+        let cs = ClusterSlots::default();
+        let (a, b, c) = (
+            Pubkey::new_unique(),
+            Pubkey::new_unique(),
+            Pubkey::new_unique(),
+        );
+        *cs.validator_stakes.write().unwrap() = new_validator_stakes(&[(a, 10), (b, 20), (c, 70)]);
+        cs.total_stake.store(100, Ordering::Relaxed);
+        assert_eq!(cs.availability(1), 0.0);
+
+        cs.update_internal(
+            0,
+            vec![
+                new_epoch_slots(a, &[1, 2]),
+                new_epoch_slots(b, &[2]),
+                new_epoch_slots(c, &[3]),
+            ],
+            DEFAULT_SLOTS_PER_EPOCH,
+        );
+        assert_eq!(cs.availability(1), 0.1);
+        assert_eq!(cs.availability(2), 0.3);
+        assert_eq!(cs.availability(3), 0.7);
+        assert_eq!(cs.availability(4), 0.0);
+        let mut peers = cs.peers_with_slot(2);
+        peers.sort_unstable();
+        let mut expected = vec![(a, 10), (b, 20)];
+        expected.sort_unstable();
+        assert_eq!(peers, expected);
+
+        // Repeated updates from the same node are not double counted
+        cs.update_internal(
+            0,
+            vec![new_epoch_slots(a, &[1, 2])],
+            DEFAULT_SLOTS_PER_EPOCH,
+        );
+        assert_eq!(cs.availability(1), 0.1);
+        assert_eq!(cs.availability(2), 0.3);
+
+        // A node's stake changing is reflected in the totals once it reports again
+        *cs.validator_stakes.write().unwrap() = new_validator_stakes(&[(a, 30), (b, 20), (c, 50)]);
+        cs.update_internal(0, vec![new_epoch_slots(a, &[2])], DEFAULT_SLOTS_PER_EPOCH);
+        assert_eq!(cs.availability(1), 0.1);
+        assert_eq!(cs.availability(2), 0.5);
+    }
 }
@@ -562,6 +562,39 @@ impl HeaviestSubtreeForkChoice {
             .map(|(slot_hash, fork_info)| (slot_hash, fork_info.stake_voted_subtree))
     }
 
+    /// Returns the shape of the tree, made up of the root, every slot with more than one
+    /// child and every leaf, sorted by slot. Each slot is paired with its nearest ancestor
+    /// that is also returned, and the stake voted on its subtree.
+    pub fn fork_points(&self) -> Vec<(SlotHashKey, Option<SlotHashKey>, u64)> {
+        let mut fork_points = vec![];
+        let mut pending = vec![(self.tree_root, None)];
+        while let Some((slot_hash_key, nearest_fork_point)) = pending.pop() {
+            let fork_info = self
+                .fork_infos
+                .get(&slot_hash_key)
+                .expect("children must exist in the tree");
+            let nearest_fork_point =
+                if slot_hash_key == self.tree_root || fork_info.children.len() != 1 {
+                    fork_points.push((
+                        slot_hash_key,
+                        nearest_fork_point,
+                        fork_info.stake_voted_subtree,
+                    ));
+                    Some(slot_hash_key)
+                } else {
+                    nearest_fork_point
+                };
+            pending.extend(
+                fork_info
+                    .children
+                    .iter()
+                    .map(|child| (*child, nearest_fork_point)),
+            );
+        }
+        fork_points.sort_unstable();
+        fork_points
+    }
+
     pub fn slots_iter(&self) -> impl Iterator<Item = Slot> + '_ {
         self.fork_infos.iter().map(|((slot, _), _)| slot).copied()
     }
@@ -2509,6 +2542,48 @@ mod test {
         assert_eq!(heaviest_subtree_fork_choice.best_overall_slot().0, 4)
     }
 
+    #[test]
+    fn test_fork_points() {
+        let mut heaviest_subtree_fork_choice = setup_forks();
+        let stake = 100;
+        let (bank, vote_pubkeys) = bank_utils::setup_bank_and_vote_pubkeys_for_tests(3, stake);
+        let pubkey_votes: Vec<(Pubkey, SlotHashKey)> = vec![
+            (vote_pubkeys[0], (6, Hash::default())),
+            (vote_pubkeys[1], (5, Hash::default())),
+            (vote_pubkeys[2], (4, Hash::default())),
+        ];
+        heaviest_subtree_fork_choice.add_votes(
+            pubkey_votes.iter(),
+            bank.epoch_stakes_map(),
+            bank.epoch_schedule(),
+        );
+
+        // Slots 2, 3 and 5 each have a single child, so are left out
+        let key = |slot| (slot, Hash::default());
+        assert_eq!(
+            heaviest_subtree_fork_choice.fork_points(),
+            vec![
+                (key(0), None, 3 * stake),
+                (key(1), Some(key(0)), 3 * stake),
+                (key(4), Some(key(1)), stake),
+                (key(6), Some(key(1)), stake),
+            ]
+        );
+
+        heaviest_subtree_fork_choice.add_new_leaf_slot(key(7), Some(key(5)));
+        assert_eq!(
+            heaviest_subtree_fork_choice.fork_points(),
+            vec![
+                (key(0), None, 3 * stake),
+                (key(1), Some(key(0)), 3 * stake),
+                (key(4), Some(key(1)), stake),
+                (key(5), Some(key(1)), 2 * stake),
+                (key(6), Some(key(5)), stake),
+                (key(7), Some(key(5)), 0),
+            ]
+        );
+    }
+
     #[test]
     fn test_add_votes_duplicate_tie() {
         let (
@@ -756,24 +756,24 @@ impl RepairService {
         slot: u64,
     ) -> Vec<(Pubkey, SocketAddr)> {
         // Find the repair peers that have this slot frozen.
-        let Some(peers_with_slot) = cluster_slots.lookup(slot) else {
+        let peers_with_slot = cluster_slots.peers_with_slot(slot);
+        if peers_with_slot.is_empty() {
             warn!("No repair peers have frozen slot: {slot}");
             return vec![];
-        };
-        let peers_with_slot = peers_with_slot.read().unwrap();
+        }
 
         // Filter out any peers that don't have a valid repair socket.
         let repair_peers: Vec<(Pubkey, SocketAddr, u32)> = peers_with_slot
-            .iter()
+            .into_iter()
             .filter_map(|(pubkey, stake)| {
                 let peer_repair_addr = cluster_info
-                    .lookup_contact_info(pubkey, |node| node.serve_repair(Protocol::UDP));
+                    .lookup_contact_info(&pubkey, |node| node.serve_repair(Protocol::UDP));
                 if let Some(Some(peer_repair_addr)) = peer_repair_addr {
                     trace!("Repair peer {pubkey} has a valid repair socket: {peer_repair_addr:?}");
                     Some((
-                        *pubkey,
+                        pubkey,
                         peer_repair_addr,
-                        (*stake / solana_sdk::native_token::LAMPORTS_PER_SOL) as u32,
+                        (stake / solana_sdk::native_token::LAMPORTS_PER_SOL) as u32,
                     ))
                 } else {
                     None
@@ -1076,6 +1076,7 @@ mod test {
         solana_net_utils::{bind_to_localhost, bind_to_unspecified},
         solana_runtime::bank::Bank,
         solana_sdk::{
+            native_token::LAMPORTS_PER_SOL,
             signature::{Keypair, Signer},
             timing::timestamp,
         },
@@ -1533,6 +1534,31 @@ mod test {
         assert!(duplicate_slot_repair_statuses.is_empty());
     }
 
+    #[test]
+    fn test_get_repair_peers() {
+        let cluster_info = Arc::new(new_test_cluster_info());
+        let cluster_slots = Arc::new(ClusterSlots::default());
+        let slot = 5;
+        assert!(
+            RepairService::get_repair_peers(cluster_info.clone(), cluster_slots.clone(), slot)
+                .is_empty()
+        );
+
+        // Only peers which have a repair socket in gossip can be selected
+        let repair_peer = Node::new_localhost().info;
+        let repair_addr = repair_peer.serve_repair(Protocol::UDP).unwrap();
+        let unknown_peer = Pubkey::new_unique();
+        cluster_slots.insert_node_stake(slot, *repair_peer.pubkey(), 10 * LAMPORTS_PER_SOL);
+        cluster_slots.insert_node_stake(slot, unknown_peer, 1_000 * LAMPORTS_PER_SOL);
+        cluster_info.insert_info(repair_peer.clone());
+        assert_eq!(cluster_slots.availability(slot), 0.0);
+        assert_eq!(
+            RepairService::get_repair_peers(cluster_info.clone(), cluster_slots.clone(), slot),
+            vec![(*repair_peer.pubkey(), repair_addr)]
+        );
+        assert!(RepairService::get_repair_peers(cluster_info, cluster_slots, slot + 1).is_empty());
+    }
+
     #[test]
     pub fn test_update_duplicate_slot_repair_addr() {
         let GenesisConfigInfo { genesis_config, .. } = create_genesis_config(10_000);
@@ -51,11 +51,12 @@ use {
     solana_poh::poh_recorder::{PohLeaderStatus, PohRecorder, GRACE_TICKS_FACTOR, MAX_GRACE_SLOTS},
     solana_rpc::{
         cache_block_meta_service::CacheBlockMetaSender,
+        latest_forks::LatestForks,
         optimistically_confirmed_bank_tracker::{BankNotification, BankNotificationSenderConfig},
         rpc_subscriptions::RpcSubscriptions,
         slot_status_notifier::SlotStatusNotifier,
     },
-    solana_rpc_client_api::response::SlotUpdate,
+    solana_rpc_client_api::response::{RpcFork, RpcForks, SlotUpdate},
     solana_runtime::{
         accounts_background_service::AbsRequestSender,
         bank::{bank_hash_details, Bank, NewBankOptions},
@@ -269,6 +270,7 @@ pub struct ReplayStageConfig {
     pub log_messages_bytes_limit: Option<usize>,
     pub prioritization_fee_cache: Arc<PrioritizationFeeCache>,
     pub banking_tracer: Arc<BankingTracer>,
+    pub latest_forks: Arc<LatestForks>,
 }
 
 pub struct ReplaySenders {
@@ -560,6 +562,7 @@ impl ReplayStage {
             log_messages_bytes_limit,
             prioritization_fee_cache,
             banking_tracer,
+            latest_forks,
         } = config;
 
         let ReplaySenders {
@@ -1022,6 +1025,20 @@ impl ReplayStage {
                 }
                 voting_time.stop();
 
+                // Only snapshot the fork tree for `getForks` when it changed, ie a bank
+                // was frozen into it or voting set a new root
+                if did_complete_bank
+                    || bank_forks.read().unwrap().root() != forks_root
+                    || latest_forks.get().is_none()
+                {
+                    latest_forks.update(Self::rpc_forks(
+                        &heaviest_subtree_fork_choice,
+                        &progress,
+                        &tower,
+                        heaviest_bank.slot(),
+                    ));
+                }
+
                 let mut reset_bank_time = Measure::start("reset_bank");
                 // Reset onto a fork
                 if let Some(reset_bank) = reset_bank {
@@ -1272,6 +1289,39 @@ impl ReplayStage {
         }
     }
 
+    /// Snapshot the fork tree for the `getForks` RPC
+    fn rpc_forks(
+        heaviest_subtree_fork_choice: &HeaviestSubtreeForkChoice,
+        progress: &ProgressMap,
+        tower: &Tower,
+        heaviest_slot: Slot,
+    ) -> RpcForks {
+        let forks = heaviest_subtree_fork_choice
+            .fork_points()
+            .into_iter()
+            .map(|((slot, _hash), parent, stake)| RpcFork {
+                slot,
+                parent_slot: parent.map(|(parent_slot, _hash)| parent_slot),
+                stake,
+                is_heaviest: slot == heaviest_slot,
+                is_locked_out: progress
+                    .get_fork_stats(slot)
+                    .map(|fork_stats| fork_stats.is_locked_out)
+                    .unwrap_or_default(),
+            })
+            .collect();
+        RpcForks {
+            root: heaviest_subtree_fork_choice.tree_root().0,
+            heaviest_slot,
+            last_vote_slot: tower.last_voted_slot(),
+            total_stake: progress
+                .get_fork_stats(heaviest_slot)
+                .map(|fork_stats| fork_stats.total_stake)
+                .unwrap_or_default(),
+            forks,
+        }
+    }
+
     fn check_for_vote_only_mode(
         heaviest_bank_slot: Slot,
         forks_root: Slot,
@@ -8660,6 +8710,52 @@ pub(crate) mod tests {
         assert!(!in_vote_only_mode.load(Ordering::Relaxed));
     }
 
+    #[test]
+    fn test_rpc_forks() {
+        // Fork structure:
+        //        0
+        //        |
+        //        1
+        //      /   \
+        //     2     3
+        //     |
+        //     4
+        let forks = tr(0) / (tr(1) / (tr(2) / tr(4)) / tr(3));
+        let heaviest_subtree_fork_choice = HeaviestSubtreeForkChoice::new_from_tree(forks);
+        let mut progress = ProgressMap::default();
+        for slot in [3, 4] {
+            progress.insert(slot, ForkProgress::new(Hash::default(), None, None, 0, 0));
+        }
+        progress.get_fork_stats_mut(3).unwrap().is_locked_out = true;
+        progress.get_fork_stats_mut(4).unwrap().total_stake = 100;
+        let mut tower = Tower::default();
+        tower.record_vote(2, Hash::default());
+
+        let rpc_forks = ReplayStage::rpc_forks(&heaviest_subtree_fork_choice, &progress, &tower, 4);
+        assert_eq!(rpc_forks.root, 0);
+        assert_eq!(rpc_forks.heaviest_slot, 4);
+        assert_eq!(rpc_forks.last_vote_slot, Some(2));
+        assert_eq!(rpc_forks.total_stake, 100);
+        assert_eq!(
+            rpc_forks
+                .forks
+                .iter()
+                .map(|fork| (
+                    fork.slot,
+                    fork.parent_slot,
+                    fork.is_heaviest,
+                    fork.is_locked_out
+                ))
+                .collect::<Vec<_>>(),
+            vec![
+                (0, None, false, false),
+                (1, Some(0), false, false),
+                (3, Some(1), false, true),
+                (4, Some(1), true, false),
+            ]
+        );
+    }
+
     #[test]
     fn test_tower_sync_from_bank_failed_switch() {
         solana_logger::setup_with_default(
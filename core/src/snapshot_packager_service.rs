@@ -8,7 +8,8 @@ use {
     solana_perf::thread::renice_this_thread,
     solana_runtime::{
         snapshot_config::SnapshotConfig, snapshot_hash::StartingSnapshotHashes,
-        snapshot_package::SnapshotPackage, snapshot_utils,
+        snapshot_package::SnapshotPackage, snapshot_runtime_info::SnapshotRuntimeInfo,
+        snapshot_utils,
     },
     std::{
         sync::{
@@ -22,6 +23,7 @@ use {
 
 pub struct SnapshotPackagerService {
     t_snapshot_packager: JoinHandle<()>,
+    snapshot_runtime_info: Arc<SnapshotRuntimeInfo>,
 }
 
 impl SnapshotPackagerService {
@@ -36,6 +38,8 @@ impl SnapshotPackagerService {
         snapshot_config: SnapshotConfig,
         enable_gossip_push: bool,
     ) -> Self {
+        let snapshot_runtime_info = Arc::<SnapshotRuntimeInfo>::default();
+        let snapshot_runtime_info_clone = snapshot_runtime_info.clone();
         let t_snapshot_packager = Builder::new()
             .name("solSnapshotPkgr".to_string())
             .spawn(move || {
@@ -71,14 +75,22 @@ impl SnapshotPackagerService {
                             snapshot_package,
                             &snapshot_config,
                         ));
-                    if let Err(err) = archive_result {
-                        error!(
-                            "Stopping SnapshotPackagerService! Fatal error while archiving \
-                             snapshot package: {err}"
-                        );
-                        exit.store(true, Ordering::Relaxed);
-                        break;
-                    }
+                    let snapshot_archive_info = match archive_result {
+                        Ok(snapshot_archive_info) => snapshot_archive_info,
+                        Err(err) => {
+                            error!(
+                                "Stopping SnapshotPackagerService! Fatal error while archiving \
+                                 snapshot package: {err}"
+                            );
+                            exit.store(true, Ordering::Relaxed);
+                            break;
+                        }
+                    };
+                    snapshot_runtime_info_clone.record_generation(
+                        snapshot_kind,
+                        &snapshot_archive_info,
+                        Duration::from_micros(archive_time_us),
+                    );
 
                     if let Some(snapshot_gossip_manager) = snapshot_gossip_manager.as_mut() {
                         snapshot_gossip_manager
@@ -123,9 +135,15 @@ impl SnapshotPackagerService {
 
         Self {
             t_snapshot_packager,
+            snapshot_runtime_info,
         }
     }
 
+    /// How long the most recent snapshots took to generate, and how big they are
+    pub fn snapshot_runtime_info(&self) -> &Arc<SnapshotRuntimeInfo> {
+        &self.snapshot_runtime_info
+    }
+
     pub fn join(self) -> thread::Result<()> {
         self.t_snapshot_packager.join()
     }
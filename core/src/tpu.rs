@@ -4,7 +4,7 @@
 pub use solana_sdk::net::DEFAULT_TPU_COALESCE;
 use {
     crate::{
-        banking_stage::BankingStage,
+        banking_stage::{forward_tracker::ForwarderConfig, BankingStage},
         banking_trace::{BankingTracer, Channels, TracerThread},
         bundle_stage::{bundle_account_locker::BundleAccountLocker, BundleStage},
         cluster_info_vote_listener::{
@@ -135,6 +135,7 @@ impl Tpu {
         prioritization_fee_cache: &Arc<PrioritizationFeeCache>,
         block_production_method: BlockProductionMethod,
         enable_block_production_forwarding: bool,
+        forwarder_config: ForwarderConfig,
         _generator_config: Option<GeneratorConfig>, /* vestigial code for replay invalidator */
         block_engine_config: Arc<Mutex<BlockEngineConfig>>,
         relayer_config: Arc<Mutex<RelayerConfig>>,
@@ -348,6 +349,7 @@ impl Tpu {
             bank_forks.clone(),
             prioritization_fee_cache,
             enable_block_production_forwarding,
+            forwarder_config,
             blacklisted_accounts,
             bundle_account_locker.clone(),
         );
@@ -35,8 +35,8 @@ use {
     },
     solana_poh::poh_recorder::PohRecorder,
     solana_rpc::{
-        cache_block_meta_service::CacheBlockMetaSender, max_slots::MaxSlots,
-        optimistically_confirmed_bank_tracker::BankNotificationSenderConfig,
+        cache_block_meta_service::CacheBlockMetaSender, latest_forks::LatestForks,
+        max_slots::MaxSlots, optimistically_confirmed_bank_tracker::BankNotificationSenderConfig,
         rpc_subscriptions::RpcSubscriptions, slot_status_notifier::SlotStatusNotifier,
     },
     solana_runtime::{
@@ -143,6 +143,7 @@ impl Tvu {
         duplicate_confirmed_slots_receiver: DuplicateConfirmedSlotsReceiver,
         tvu_config: TvuConfig,
         max_slots: &Arc<MaxSlots>,
+        latest_forks: Arc<LatestForks>,
         block_metadata_notifier: Option<BlockMetadataNotifierArc>,
         wait_to_vote_slot: Option<Slot>,
         accounts_background_request_sender: AbsRequestSender,
@@ -323,6 +324,7 @@ impl Tvu {
             log_messages_bytes_limit,
             prioritization_fee_cache: prioritization_fee_cache.clone(),
             banking_tracer,
+            latest_forks,
         };
 
         let voting_service = VotingService::new(
@@ -566,6 +568,7 @@ pub mod tests {
             gossip_confirmed_slots_receiver,
             TvuConfig::default(),
             &Arc::new(MaxSlots::default()),
+            Arc::new(LatestForks::default()),
             None,
             None,
             AbsRequestSender::default(),
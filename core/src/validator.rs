@@ -5,6 +5,7 @@ use {
     crate::{
         accounts_hash_verifier::AccountsHashVerifier,
         admin_rpc_post_init::AdminRpcRequestMetadataPostInit,
+        banking_stage::forward_tracker::ForwarderConfig,
         banking_trace::{self, BankingTracer, TraceError},
         cluster_info_vote_listener::VoteTracker,
         completed_data_sets_service::CompletedDataSetsService,
@@ -45,7 +46,7 @@ use {
         utils::{move_and_async_delete_path, move_and_async_delete_path_contents},
     },
     solana_client::connection_cache::{ConnectionCache, Protocol},
-    solana_entry::poh::compute_hash_time,
+    solana_entry::{entry::PohVerifyDevice, poh::compute_hash_time},
     solana_geyser_plugin_manager::{
         geyser_plugin_service::GeyserPluginService, GeyserPluginManagerRequest,
     },
@@ -84,6 +85,7 @@ use {
     solana_rayon_threadlimit::{get_max_thread_count, get_thread_count},
     solana_rpc::{
         cache_block_meta_service::{CacheBlockMetaSender, CacheBlockMetaService},
+        latest_forks::LatestForks,
         max_slots::MaxSlots,
         optimistically_confirmed_bank_tracker::{
             BankNotificationSenderConfig, OptimisticallyConfirmedBank,
@@ -245,12 +247,16 @@ pub struct ValidatorConfig {
     pub known_validators: Option<HashSet<Pubkey>>, // None = trust all
     pub repair_validators: Option<HashSet<Pubkey>>, // None = repair from all
     pub repair_whitelist: Arc<RwLock<HashSet<Pubkey>>>, // Empty = repair with all
+    /// Number of slots at or before the root for which cluster slots are retained
+    pub cluster_slots_root_retention: Slot,
     pub gossip_validators: Option<HashSet<Pubkey>>, // None = gossip with all
     pub accounts_hash_interval_slots: u64,
     pub max_genesis_archive_unpacked_size: u64,
     /// Run PoH, transaction signature and other transaction verifications during blockstore
     /// processing.
     pub run_verification: bool,
+    /// Where to verify the PoH of entries while processing the blockstore at startup
+    pub poh_verify_device: PohVerifyDevice,
     pub require_tower: bool,
     pub tower_storage: Arc<dyn TowerStorage>,
     pub debug_keys: Option<Arc<HashSet<Pubkey>>>,
@@ -280,6 +286,7 @@ pub struct ValidatorConfig {
     pub block_verification_method: BlockVerificationMethod,
     pub block_production_method: BlockProductionMethod,
     pub enable_block_production_forwarding: bool,
+    pub forwarder_config: ForwarderConfig,
     pub generator_config: Option<GeneratorConfig>,
     pub use_snapshot_archives_at_startup: UseSnapshotArchivesAtStartup,
     pub wen_restart_proto_path: Option<PathBuf>,
@@ -325,10 +332,12 @@ impl Default for ValidatorConfig {
             known_validators: None,
             repair_validators: None,
             repair_whitelist: Arc::new(RwLock::new(HashSet::default())),
+            cluster_slots_root_retention: 0,
             gossip_validators: None,
             accounts_hash_interval_slots: u64::MAX,
             max_genesis_archive_unpacked_size: MAX_GENESIS_ARCHIVE_UNPACKED_SIZE,
             run_verification: true,
+            poh_verify_device: PohVerifyDevice::default(),
             require_tower: false,
             tower_storage: Arc::new(NullTowerStorage::default()),
             debug_keys: None,
@@ -358,6 +367,7 @@ impl Default for ValidatorConfig {
             block_verification_method: BlockVerificationMethod::default(),
             block_production_method: BlockProductionMethod::default(),
             enable_block_production_forwarding: false,
+            forwarder_config: ForwarderConfig::default(),
             generator_config: None,
             use_snapshot_archives_at_startup: UseSnapshotArchivesAtStartup::default(),
             wen_restart_proto_path: None,
@@ -1013,6 +1023,7 @@ impl Validator {
         }
 
         let max_slots = Arc::new(MaxSlots::default());
+        let latest_forks = Arc::new(LatestForks::default());
 
         let startup_verification_complete;
         let (poh_recorder, entry_receiver, record_receiver) = {
@@ -1127,6 +1138,7 @@ impl Validator {
                 optimistically_confirmed_bank.clone(),
                 config.send_transaction_service_config.clone(),
                 max_slots.clone(),
+                latest_forks.clone(),
                 leader_schedule_cache.clone(),
                 connection_cache.clone(),
                 max_complete_transaction_status_slot,
@@ -1430,8 +1442,11 @@ impl Validator {
 
         let outstanding_repair_requests =
             Arc::<RwLock<repair::repair_service::OutstandingShredRepairs>>::default();
-        let cluster_slots =
-            Arc::new(crate::cluster_slots_service::cluster_slots::ClusterSlots::default());
+        let cluster_slots = Arc::new(
+            crate::cluster_slots_service::cluster_slots::ClusterSlots::new(
+                config.cluster_slots_root_retention,
+            ),
+        );
 
         let tvu = Tvu::new(
             vote_account,
@@ -1476,6 +1491,7 @@ impl Validator {
                 shred_sigverify_threads: config.tvu_shred_sigverify_threads,
             },
             &max_slots,
+            latest_forks,
             block_metadata_notifier,
             config.wait_to_vote_slot,
             accounts_background_request_sender.clone(),
@@ -1561,6 +1577,7 @@ impl Validator {
             &prioritization_fee_cache,
             config.block_production_method.clone(),
             config.enable_block_production_forwarding,
+            config.forwarder_config.clone(),
             config.generator_config.clone(),
             config.block_engine_config.clone(),
             config.relayer_config.clone(),
@@ -2005,6 +2022,7 @@ fn load_blockstore(
 
     let process_options = blockstore_processor::ProcessOptions {
         run_verification: config.run_verification,
+        poh_verify_device: config.poh_verify_device,
         halt_at_slot,
         new_hard_forks: config.new_hard_forks.clone(),
         debug_keys: config.debug_keys.clone(),
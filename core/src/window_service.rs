@@ -64,6 +64,7 @@ struct WindowServiceMetrics {
     shred_receiver_elapsed_us: u64,
     prune_shreds_elapsed_us: u64,
     num_shreds_pruned_invalid_repair: usize,
+    num_repair_nonce_mismatch: usize,
     num_errors: u64,
     num_errors_blockstore: u64,
     num_errors_cross_beam_recv_timeout: u64,
@@ -101,6 +102,11 @@ impl WindowServiceMetrics {
                 self.num_shreds_pruned_invalid_repair,
                 i64
             ),
+            (
+                "num_repair_nonce_mismatch",
+                self.num_repair_nonce_mismatch,
+                i64
+            ),
             ("num_errors", self.num_errors, i64),
             ("num_errors_blockstore", self.num_errors_blockstore, i64),
             ("num_errors_other", self.num_errors_other, i64),
@@ -239,24 +245,31 @@ fn verify_repair(
         .unwrap_or(true)
 }
 
+// Returns the number of repair responses dropped because their nonce does not
+// match an outstanding repair request for the shred.
 fn prune_shreds_by_repair_status(
     shreds: &mut Vec<Shred>,
     repair_infos: &mut Vec<Option<RepairMeta>>,
     outstanding_requests: &RwLock<OutstandingShredRepairs>,
     accept_repairs_only: bool,
-) {
+) -> usize {
     assert_eq!(shreds.len(), repair_infos.len());
     let mut i = 0;
     let mut removed = HashSet::new();
+    let mut num_nonce_mismatch = 0;
     {
         let mut outstanding_requests = outstanding_requests.write().unwrap();
         shreds.retain(|shred| {
-            let should_keep = (
-                (!accept_repairs_only || repair_infos[i].is_some())
-                    && verify_repair(&mut outstanding_requests, shred, &repair_infos[i]),
-                i += 1,
-            )
-                .0;
+            let repair_info = &repair_infos[i];
+            i += 1;
+            let should_keep = if accept_repairs_only && repair_info.is_none() {
+                false
+            } else if verify_repair(&mut outstanding_requests, shred, repair_info) {
+                true
+            } else {
+                num_nonce_mismatch += 1;
+                false
+            };
             if !should_keep {
                 removed.insert(i - 1);
             }
@@ -266,6 +279,7 @@ fn prune_shreds_by_repair_status(
     i = 0;
     repair_infos.retain(|_repair_info| (!removed.contains(&i), i += 1).0);
     assert_eq!(shreds.len(), repair_infos.len());
+    num_nonce_mismatch
 }
 
 #[allow(clippy::too_many_arguments)]
@@ -328,7 +342,7 @@ where
 
     let mut prune_shreds_elapsed = Measure::start("prune_shreds_elapsed");
     let num_shreds = shreds.len();
-    prune_shreds_by_repair_status(
+    ws_metrics.num_repair_nonce_mismatch += prune_shreds_by_repair_status(
         &mut shreds,
         &mut repair_infos,
         outstanding_requests,
@@ -771,7 +785,15 @@ mod test {
             .add_request(repair_type, timestamp());
         let repair_meta1 = RepairMeta { nonce };
         let mut repair_infos = vec![None, Some(repair_meta), Some(repair_meta1)];
-        prune_shreds_by_repair_status(&mut shreds, &mut repair_infos, &outstanding_requests, false);
+        assert_eq!(
+            prune_shreds_by_repair_status(
+                &mut shreds,
+                &mut repair_infos,
+                &outstanding_requests,
+                false
+            ),
+            1
+        );
         assert_eq!(shreds.len(), 2);
         assert_eq!(repair_infos.len(), 2);
         assert!(repair_infos[0].is_none());
@@ -782,7 +804,15 @@ mod test {
         let repair_meta3 = RepairMeta { nonce };
         repair_infos = vec![None, Some(repair_meta2), Some(repair_meta3)];
         // In wen_restart, we discard all Turbine shreds and only keep valid repair shreds.
-        prune_shreds_by_repair_status(&mut shreds, &mut repair_infos, &outstanding_requests, true);
+        assert_eq!(
+            prune_shreds_by_repair_status(
+                &mut shreds,
+                &mut repair_infos,
+                &outstanding_requests,
+                true
+            ),
+            1
+        );
         assert_eq!(shreds.len(), 1);
         assert_eq!(repair_infos.len(), 1);
         assert!(repair_infos[0].is_some());
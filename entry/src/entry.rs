@@ -31,7 +31,9 @@ use {
     std::{
         cmp,
         ffi::OsStr,
-        iter::repeat_with,
+        fmt,
+        iter::{self, repeat_with},
+        str::FromStr,
         sync::{Arc, Mutex, Once},
         thread::{self, JoinHandle},
         time::Instant,
@@ -909,6 +911,171 @@ impl EntrySlice for [Entry] {
     }
 }
 
+/// Where [`verify_entry_batch`] computes the PoH hashes of a batch
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum PohVerifyDevice {
+    /// Use the GPU if the perf libs are loaded, otherwise the CPU
+    #[default]
+    Auto,
+    Cpu,
+    /// Use the GPU, falling back to the CPU if the perf libs are not loaded
+    Gpu,
+}
+
+impl PohVerifyDevice {
+    pub const fn cli_names() -> &'static [&'static str] {
+        &["auto", "cpu", "gpu"]
+    }
+}
+
+impl FromStr for PohVerifyDevice {
+    type Err = String;
+
+    fn from_str(s: &str) -> std::result::Result<Self, Self::Err> {
+        match s {
+            "auto" => Ok(Self::Auto),
+            "cpu" => Ok(Self::Cpu),
+            "gpu" => Ok(Self::Gpu),
+            _ => Err(format!("unknown PoH verify device: {s}")),
+        }
+    }
+}
+
+impl fmt::Display for PohVerifyDevice {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let name = match self {
+            Self::Auto => "auto",
+            Self::Cpu => "cpu",
+            Self::Gpu => "gpu",
+        };
+        write!(f, "{name}")
+    }
+}
+
+/// Verifies the PoH hashes of the entries of several slots as a single batch.
+///
+/// Each item of `slices` is the entries of one slot along with the hash they chain from,
+/// which is the last entry hash of the parent slot. Entries of different slots don't depend
+/// on each other, so verifying them together keeps all threads (or the GPU) busy even when
+/// the slots are small. Returns whether each slice is valid, in the order of `slices`.
+pub fn verify_entry_batch(
+    slices: &[(Hash, &[Entry])],
+    device: PohVerifyDevice,
+    thread_pool: &ThreadPool,
+    recyclers: &VerifyRecyclers,
+) -> Vec<bool> {
+    let start = Instant::now();
+    let (start_hashes, entries): (Vec<_>, Vec<_>) = slices
+        .iter()
+        .flat_map(|(start_hash, entries)| {
+            iter::once(*start_hash)
+                .chain(entries.iter().map(|entry| entry.hash))
+                .zip(entries.iter())
+        })
+        .unzip();
+
+    let api = match device {
+        PohVerifyDevice::Auto => perf_libs::api(),
+        PohVerifyDevice::Cpu => None,
+        PohVerifyDevice::Gpu => {
+            let api = perf_libs::api();
+            if api.is_none() {
+                static WARN_ONCE: Once = Once::new();
+                WARN_ONCE.call_once(|| {
+                    warn!("GPU PoH verification requested, but perf libs are not loaded");
+                });
+            }
+            api
+        }
+    };
+    let entries_valid: Vec<bool> = match api {
+        Some(api) => verify_entry_batch_gpu(api, &start_hashes, &entries, thread_pool, recyclers),
+        None => thread_pool.install(|| {
+            start_hashes
+                .par_iter()
+                .zip(&entries)
+                .map(|(start_hash, entry)| entry.verify(start_hash))
+                .collect()
+        }),
+    };
+
+    let mut offset = 0;
+    let slices_valid: Vec<bool> = slices
+        .iter()
+        .map(|(_, slice_entries)| {
+            let end = offset + slice_entries.len();
+            let valid = entries_valid[offset..end].iter().all(|valid| *valid);
+            offset = end;
+            valid
+        })
+        .collect();
+
+    let elapsed_us = start.elapsed().as_micros() as u64;
+    let entries_per_sec = if elapsed_us > 0 {
+        entries.len() as u64 * 1_000_000 / elapsed_us
+    } else {
+        0
+    };
+    datapoint_info!(
+        "entry-verify-batch",
+        ("num_slices", slices.len(), i64),
+        (
+            "num_failed_slices",
+            slices_valid.iter().filter(|valid| !**valid).count(),
+            i64
+        ),
+        ("num_entries", entries.len(), i64),
+        ("gpu", api.is_some(), bool),
+        ("elapsed_us", elapsed_us, i64),
+        ("entries_per_sec", entries_per_sec, i64),
+    );
+    slices_valid
+}
+
+fn verify_entry_batch_gpu(
+    api: &Container<perf_libs::Api>,
+    start_hashes: &[Hash],
+    entries: &[&Entry],
+    thread_pool: &ThreadPool,
+    recyclers: &VerifyRecyclers,
+) -> Vec<bool> {
+    let mut hashes = recyclers.hash_recycler.allocate("poh_verify_hash");
+    hashes.set_pinnable();
+    hashes.resize(start_hashes.len(), Hash::default());
+    hashes.copy_from_slice(start_hashes);
+
+    let mut num_hashes_vec = recyclers
+        .tick_count_recycler
+        .allocate("poh_verify_num_hashes");
+    num_hashes_vec.reserve_and_pin(cmp::max(1, entries.len()));
+    for entry in entries {
+        num_hashes_vec.push(entry.num_hashes.saturating_sub(1));
+    }
+
+    let gpu_wait = Instant::now();
+    let res = unsafe {
+        (api.poh_verify_many)(
+            hashes.as_mut_ptr() as *mut u8,
+            num_hashes_vec.as_ptr(),
+            entries.len(),
+            1,
+        )
+    };
+    assert!(res == 0, "GPU PoH verify many failed");
+    inc_new_counter_info!(
+        "entry_verify-gpu_thread",
+        gpu_wait.elapsed().as_micros() as usize
+    );
+
+    thread_pool.install(|| {
+        hashes
+            .par_iter()
+            .zip(entries)
+            .map(|(hash, entry)| compare_hashes(*hash, entry))
+            .collect()
+    })
+}
+
 pub fn next_entry_mut(start: &mut Hash, num_hashes: u64, transactions: Vec<Transaction>) -> Entry {
     let entry = Entry::new(start, num_hashes, transactions);
     *start = entry.hash;
@@ -1286,6 +1453,66 @@ mod tests {
         assert!(!bad_ticks.verify(&one, &thread_pool));
     }
 
+    #[test]
+    fn test_verify_entry_batch() {
+        solana_logger::setup();
+        let thread_pool = thread_pool_for_tests();
+        let recyclers = VerifyRecyclers::default();
+
+        let keypair = Keypair::new();
+        let mut last_hash = hash(Hash::default().as_ref());
+        let slots: Vec<_> = (0..3)
+            .map(|_| {
+                let start_hash = last_hash;
+                let tx =
+                    system_transaction::transfer(&keypair, &Pubkey::new_unique(), 1, last_hash);
+                let mut entries = vec![next_entry_mut(&mut last_hash, 2, vec![tx])];
+                entries.extend(create_ticks(4, 3, last_hash));
+                last_hash = entries.last().unwrap().hash;
+                (start_hash, entries)
+            })
+            .collect();
+
+        for device in [
+            PohVerifyDevice::Auto,
+            PohVerifyDevice::Cpu,
+            PohVerifyDevice::Gpu,
+        ] {
+            // base case
+            assert!(verify_entry_batch(&[], device, &thread_pool, &recyclers).is_empty());
+
+            let batch: Vec<_> = slots
+                .iter()
+                .map(|(start_hash, entries)| (*start_hash, &entries[..]))
+                .collect();
+            assert_eq!(
+                verify_entry_batch(&batch, device, &thread_pool, &recyclers),
+                vec![true, true, true]
+            );
+
+            // A bad entry only fails the slice it belongs to
+            let mut bad_slots = slots.clone();
+            bad_slots[1].1[2].hash = Hash::new_unique();
+            let batch: Vec<_> = bad_slots
+                .iter()
+                .map(|(start_hash, entries)| (*start_hash, &entries[..]))
+                .collect();
+            assert_eq!(
+                verify_entry_batch(&batch, device, &thread_pool, &recyclers),
+                vec![true, false, true]
+            );
+        }
+    }
+
+    #[test]
+    fn test_poh_verify_device_from_str() {
+        for name in PohVerifyDevice::cli_names() {
+            let device = PohVerifyDevice::from_str(name).unwrap();
+            assert_eq!(&device.to_string(), name);
+        }
+        assert!(PohVerifyDevice::from_str("tpu").is_err());
+    }
+
     #[test]
     fn test_verify_tick_hash_count() {
         let hashes_per_tick = 10;
@@ -4,7 +4,10 @@ pub mod genesis_accounts;
 pub mod stakes;
 pub mod unlocks;
 
-use serde::{Deserialize, Serialize};
+use {
+    serde::{Deserialize, Serialize},
+    solana_sdk::genesis_config::{ClusterType, GenesisConfig},
+};
 
 /// An account where the data is encoded as a Base64 string.
 #[derive(Serialize, Deserialize, Debug)]
@@ -14,3 +17,32 @@ pub struct Base64Account {
     pub data: String,
     pub executable: bool,
 }
+
+/// A machine-readable summary of a genesis config.
+#[derive(Serialize, Deserialize, Debug, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub struct GenesisSummary {
+    pub genesis_hash: String,
+    pub cluster_type: ClusterType,
+    pub hashes_per_tick: Option<u64>,
+    pub slots_per_epoch: u64,
+    pub capitalization: u64,
+    pub num_accounts: usize,
+}
+
+impl GenesisSummary {
+    pub fn new(genesis_config: &GenesisConfig) -> Self {
+        Self {
+            genesis_hash: genesis_config.hash().to_string(),
+            cluster_type: genesis_config.cluster_type,
+            hashes_per_tick: genesis_config.poh_config.hashes_per_tick,
+            slots_per_epoch: genesis_config.epoch_schedule.slots_per_epoch,
+            capitalization: genesis_config
+                .accounts
+                .values()
+                .map(|account| account.lamports)
+                .sum(),
+            num_accounts: genesis_config.accounts.len(),
+        }
+    }
+}
@@ -3,7 +3,9 @@
 
 use {
     base64::{prelude::BASE64_STANDARD, Engine},
-    clap::{crate_description, crate_name, value_t, value_t_or_exit, App, Arg, ArgMatches},
+    clap::{
+        crate_description, crate_name, value_t, value_t_or_exit, values_t, App, Arg, ArgMatches,
+    },
     itertools::Itertools,
     solana_accounts_db::hardened_unpack::MAX_GENESIS_ARCHIVE_UNPACKED_SIZE,
     solana_clap_utils::{
@@ -16,7 +18,7 @@ use {
         },
     },
     solana_entry::poh::compute_hashes_per_tick,
-    solana_genesis::{genesis_accounts::add_genesis_accounts, Base64Account},
+    solana_genesis::{genesis_accounts::add_genesis_accounts, Base64Account, GenesisSummary},
     solana_ledger::{blockstore::create_new_ledger, blockstore_options::LedgerColumnOptions},
     solana_rpc_client::rpc_client::RpcClient,
     solana_rpc_client_api::request::MAX_MULTIPLE_ACCOUNTS,
@@ -27,7 +29,7 @@ use {
         commitment_config::CommitmentConfig,
         epoch_schedule::EpochSchedule,
         feature,
-        feature_set::FEATURE_NAMES,
+        feature_set::{FeatureSet, FEATURE_NAMES},
         fee_calculator::FeeRateGovernor,
         genesis_config::{ClusterType, GenesisConfig},
         inflation::Inflation,
@@ -47,7 +49,7 @@ use {
         error,
         fs::File,
         io::{self, Read},
-        path::PathBuf,
+        path::{Path, PathBuf},
         process,
         str::FromStr,
         time::Duration,
@@ -176,6 +178,106 @@ fn features_to_deactivate_for_cluster(
     Ok(features_to_deactivate)
 }
 
+fn write_genesis_summary(path: &Path, genesis_config: &GenesisConfig) -> io::Result<()> {
+    let file = File::create(path)?;
+    serde_json::to_writer_pretty(file, &GenesisSummary::new(genesis_config))?;
+    Ok(())
+}
+
+// Returns the vote commission of each bootstrap validator. A single --bootstrap-vote-commission
+// applies to every bootstrap validator.
+fn bootstrap_vote_commissions(
+    commissions: Option<Vec<u8>>,
+    num_bootstrap_validators: usize,
+    default_commission: u8,
+) -> Result<Vec<u8>, String> {
+    match commissions {
+        None => Ok(vec![default_commission; num_bootstrap_validators]),
+        Some(commissions) if commissions.len() == 1 => {
+            Ok(vec![commissions[0]; num_bootstrap_validators])
+        }
+        Some(commissions) if commissions.len() == num_bootstrap_validators => Ok(commissions),
+        Some(commissions) => Err(format!(
+            "--bootstrap-vote-commission must be specified once or once per \
+             --bootstrap-validator, got {} for {num_bootstrap_validators} bootstrap validators",
+            commissions.len(),
+        )),
+    }
+}
+
+// Feature set as of the genesis slot, built from the feature accounts activated at genesis
+fn genesis_feature_set(genesis_config: &GenesisConfig) -> FeatureSet {
+    let mut feature_set = FeatureSet::default();
+    for (pubkey, account) in &genesis_config.accounts {
+        if let Some(activated_at) = feature::from_account(account).and_then(|f| f.activated_at) {
+            feature_set.activate(pubkey, activated_at);
+        }
+    }
+    feature_set
+}
+
+// The bootstrap stake account delegates everything above its rent-exempt reserve, which
+// must meet the stake program's minimum delegation or the bootstrap validator's stake
+// cannot be redelegated once the cluster is running.
+fn check_bootstrap_stake_minimum_delegation(
+    genesis_config: &GenesisConfig,
+    bootstrap_validator_stake_lamports: u64,
+) -> Result<(), String> {
+    let minimum_delegation =
+        solana_stake_program::get_minimum_delegation(&genesis_feature_set(genesis_config));
+    let rent_exempt_reserve = genesis_config.rent.minimum_balance(StakeStateV2::size_of());
+    let delegation = bootstrap_validator_stake_lamports.saturating_sub(rent_exempt_reserve);
+    if delegation < minimum_delegation {
+        Err(format!(
+            "bootstrap validator stake of {bootstrap_validator_stake_lamports} lamports delegates \
+             {delegation} lamports, below the minimum delegation of {minimum_delegation}; \
+             raise --bootstrap-validator-stake-lamports to at least {} or pass \
+             --allow-bootstrap-stake-below-minimum-delegation",
+            rent_exempt_reserve.saturating_add(minimum_delegation),
+        ))
+    } else {
+        Ok(())
+    }
+}
+
+#[allow(clippy::too_many_arguments)]
+fn add_bootstrap_validator(
+    genesis_config: &mut GenesisConfig,
+    identity_pubkey: &Pubkey,
+    vote_pubkey: &Pubkey,
+    stake_pubkey: &Pubkey,
+    stake_authorized_pubkey: &Pubkey,
+    lamports: u64,
+    stake_lamports: u64,
+    commission: u8,
+) {
+    genesis_config.add_account(
+        *identity_pubkey,
+        AccountSharedData::new(lamports, 0, &system_program::id()),
+    );
+
+    let vote_account = vote_state::create_account_with_authorized(
+        identity_pubkey,
+        identity_pubkey,
+        identity_pubkey,
+        commission,
+        VoteState::get_rent_exempt_reserve(&genesis_config.rent).max(1),
+    );
+
+    genesis_config.add_account(
+        *stake_pubkey,
+        stake_state::create_account(
+            stake_authorized_pubkey,
+            vote_pubkey,
+            &vote_account,
+            &genesis_config.rent,
+            stake_lamports,
+        ),
+    );
+
+    genesis_config.add_account(*vote_pubkey, vote_account);
+}
+
 #[allow(clippy::cognitive_complexity)]
 fn main() -> Result<(), Box<dyn error::Error>> {
     let default_faucet_pubkey = solana_cli_config::Config::default().keypair_path;
@@ -210,7 +312,7 @@ fn main() -> Result<(), Box<dyn error::Error>> {
         .max(VoteState::get_rent_exempt_reserve(&rent))
         .to_string();
     // stake account
-    let default_bootstrap_validator_stake_lamports = &sol_to_lamports(0.5)
+    let default_bootstrap_validator_stake_lamports = &sol_to_lamports(1.5)
         .max(rent.minimum_balance(StakeStateV2::size_of()))
         .to_string();
 
@@ -297,6 +399,15 @@ fn main() -> Result<(), Box<dyn error::Error>> {
                 .default_value(default_bootstrap_validator_stake_lamports)
                 .help("Number of lamports to assign to the bootstrap validator's stake account"),
         )
+        .arg(
+            Arg::with_name("allow_bootstrap_stake_below_minimum_delegation")
+                .long("allow-bootstrap-stake-below-minimum-delegation")
+                .takes_value(false)
+                .help(
+                    "Allow the bootstrap validator stake to delegate less than the stake \
+                     program's minimum delegation",
+                ),
+        )
         .arg(
             Arg::with_name("target_lamports_per_signature")
                 .long("target-lamports-per-signature")
@@ -357,6 +468,20 @@ fn main() -> Result<(), Box<dyn error::Error>> {
                 .help("percentage of vote commission")
                 .validator(is_valid_percentage),
         )
+        .arg(
+            Arg::with_name("bootstrap_vote_commission")
+                .long("bootstrap-vote-commission")
+                .value_name("PERCENTAGE")
+                .takes_value(true)
+                .multiple(true)
+                .number_of_values(1)
+                .validator(is_valid_percentage)
+                .help(
+                    "Commission of the bootstrap validators' vote accounts. Specify once to \
+                     apply to every bootstrap validator, or once per --bootstrap-validator in \
+                     the same order [default: --vote-commission-percentage]",
+                ),
+        )
         .arg(
             Arg::with_name("target_signatures_per_slot")
                 .long("target-signatures-per-slot")
@@ -477,6 +602,16 @@ fn main() -> Result<(), Box<dyn error::Error>> {
                 .possible_values(&["pico", "full", "none"])
                 .help("Selects inflation"),
         )
+        .arg(
+            Arg::with_name("summary_output")
+                .long("summary-output")
+                .value_name("PATH")
+                .takes_value(true)
+                .help(
+                    "Write a JSON summary of the genesis config to this file once the ledger \
+                     is created",
+                ),
+        )
         .arg(
             Arg::with_name("json_rpc_url")
                 .short("u")
@@ -627,42 +762,35 @@ fn main() -> Result<(), Box<dyn error::Error>> {
     }
 
     let commission = value_t_or_exit!(matches, "vote_commission_percentage", u8);
-
-    let mut bootstrap_validator_pubkeys_iter = bootstrap_validator_pubkeys.iter();
-    loop {
-        let Some(identity_pubkey) = bootstrap_validator_pubkeys_iter.next() else {
-            break;
+    let bootstrap_vote_commissions = bootstrap_vote_commissions(
+        values_t!(matches, "bootstrap_vote_commission", u8).ok(),
+        bootstrap_validator_pubkeys.len() / 3,
+        commission,
+    )
+    .unwrap_or_else(|err| {
+        eprintln!("Error: {err}");
+        process::exit(1);
+    });
+
+    for (pubkeys, commission) in bootstrap_validator_pubkeys
+        .chunks(3)
+        .zip(bootstrap_vote_commissions)
+    {
+        let [identity_pubkey, vote_pubkey, stake_pubkey] = pubkeys else {
+            unreachable!();
         };
-        let vote_pubkey = bootstrap_validator_pubkeys_iter.next().unwrap();
-        let stake_pubkey = bootstrap_validator_pubkeys_iter.next().unwrap();
-
-        genesis_config.add_account(
-            *identity_pubkey,
-            AccountSharedData::new(bootstrap_validator_lamports, 0, &system_program::id()),
-        );
-
-        let vote_account = vote_state::create_account_with_authorized(
-            identity_pubkey,
-            identity_pubkey,
+        add_bootstrap_validator(
+            &mut genesis_config,
             identity_pubkey,
+            vote_pubkey,
+            stake_pubkey,
+            bootstrap_stake_authorized_pubkey
+                .as_ref()
+                .unwrap_or(identity_pubkey),
+            bootstrap_validator_lamports,
+            bootstrap_validator_stake_lamports,
             commission,
-            VoteState::get_rent_exempt_reserve(&genesis_config.rent).max(1),
         );
-
-        genesis_config.add_account(
-            *stake_pubkey,
-            stake_state::create_account(
-                bootstrap_stake_authorized_pubkey
-                    .as_ref()
-                    .unwrap_or(identity_pubkey),
-                vote_pubkey,
-                &vote_account,
-                &genesis_config.rent,
-                bootstrap_validator_stake_lamports,
-            ),
-        );
-
-        genesis_config.add_account(*vote_pubkey, vote_account);
     }
 
     if let Some(creation_time) = unix_timestamp_from_rfc3339_datetime(&matches, "creation_time") {
@@ -691,6 +819,16 @@ fn main() -> Result<(), Box<dyn error::Error>> {
         }
     }
 
+    if !matches.is_present("allow_bootstrap_stake_below_minimum_delegation") {
+        if let Err(err) = check_bootstrap_stake_minimum_delegation(
+            &genesis_config,
+            bootstrap_validator_stake_lamports,
+        ) {
+            eprintln!("Error: {err}");
+            process::exit(1);
+        }
+    }
+
     let max_genesis_archive_unpacked_size =
         value_t_or_exit!(matches, "max_genesis_archive_unpacked_size", u64);
 
@@ -802,6 +940,10 @@ fn main() -> Result<(), Box<dyn error::Error>> {
         LedgerColumnOptions::default(),
     )?;
 
+    if let Some(path) = matches.value_of("summary_output") {
+        write_genesis_summary(Path::new(path), &genesis_config)?;
+    }
+
     println!("{genesis_config}");
     Ok(())
 }
@@ -814,6 +956,109 @@ mod tests {
         std::{collections::HashMap, fs::remove_file, io::Write, path::Path},
     };
 
+    #[test]
+    fn test_check_bootstrap_stake_minimum_delegation() {
+        let mut genesis_config = GenesisConfig::default();
+        solana_runtime::genesis_utils::activate_all_features(&mut genesis_config);
+        let rent_exempt_reserve = genesis_config.rent.minimum_balance(StakeStateV2::size_of());
+        let minimum_delegation = sol_to_lamports(1.0);
+
+        let err = check_bootstrap_stake_minimum_delegation(&genesis_config, sol_to_lamports(0.5))
+            .unwrap_err();
+        assert!(err.contains("--bootstrap-validator-stake-lamports"));
+        assert!(check_bootstrap_stake_minimum_delegation(
+            &genesis_config,
+            rent_exempt_reserve + minimum_delegation - 1,
+        )
+        .is_err());
+        assert!(check_bootstrap_stake_minimum_delegation(
+            &genesis_config,
+            rent_exempt_reserve + minimum_delegation,
+        )
+        .is_ok());
+        assert!(
+            check_bootstrap_stake_minimum_delegation(&genesis_config, sol_to_lamports(2.0)).is_ok()
+        );
+
+        // Without the raised minimum, a single lamport above the reserve is delegable
+        solana_runtime::genesis_utils::deactivate_features(
+            &mut genesis_config,
+            &vec![solana_sdk::feature_set::stake_raise_minimum_delegation_to_1_sol::id()],
+        );
+        assert!(
+            check_bootstrap_stake_minimum_delegation(&genesis_config, sol_to_lamports(0.5)).is_ok()
+        );
+        assert!(
+            check_bootstrap_stake_minimum_delegation(&genesis_config, rent_exempt_reserve).is_err()
+        );
+    }
+
+    #[test]
+    fn test_write_genesis_summary() {
+        let mut genesis_config = GenesisConfig::default();
+        genesis_config.add_account(
+            Pubkey::new_unique(),
+            AccountSharedData::new(42, 0, &system_program::id()),
+        );
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("summary.json");
+        write_genesis_summary(&path, &genesis_config).unwrap();
+
+        let summary: GenesisSummary = serde_json::from_reader(File::open(&path).unwrap()).unwrap();
+        assert_eq!(summary.genesis_hash, genesis_config.hash().to_string());
+        assert_eq!(summary.cluster_type, genesis_config.cluster_type);
+        assert_eq!(
+            summary.hashes_per_tick,
+            genesis_config.poh_config.hashes_per_tick
+        );
+        assert_eq!(
+            summary.slots_per_epoch,
+            genesis_config.epoch_schedule.slots_per_epoch
+        );
+        assert_eq!(summary.capitalization, 42);
+        assert_eq!(summary.num_accounts, 1);
+    }
+
+    #[test]
+    fn test_bootstrap_vote_commissions() {
+        assert_eq!(bootstrap_vote_commissions(None, 2, 100), Ok(vec![100, 100]));
+        assert_eq!(
+            bootstrap_vote_commissions(Some(vec![5]), 3, 100),
+            Ok(vec![5, 5, 5])
+        );
+        assert_eq!(
+            bootstrap_vote_commissions(Some(vec![5, 0, 42]), 3, 100),
+            Ok(vec![5, 0, 42])
+        );
+        assert!(bootstrap_vote_commissions(Some(vec![5, 42]), 3, 100).is_err());
+    }
+
+    #[test]
+    fn test_add_bootstrap_validator_commission() {
+        let mut genesis_config = GenesisConfig::default();
+        let stake_lamports = genesis_config.rent.minimum_balance(StakeStateV2::size_of());
+        let identity_pubkey = Pubkey::new_unique();
+        let vote_pubkey = Pubkey::new_unique();
+        let stake_pubkey = Pubkey::new_unique();
+        add_bootstrap_validator(
+            &mut genesis_config,
+            &identity_pubkey,
+            &vote_pubkey,
+            &stake_pubkey,
+            &identity_pubkey,
+            sol_to_lamports(500.0),
+            stake_lamports,
+            42,
+        );
+
+        let vote_account = genesis_config.accounts.get(&vote_pubkey).unwrap();
+        let vote_state = VoteState::deserialize(&vote_account.data).unwrap();
+        assert_eq!(vote_state.commission, 42);
+        assert_eq!(vote_state.node_pubkey, identity_pubkey);
+        assert!(genesis_config.accounts.contains_key(&identity_pubkey));
+        assert!(genesis_config.accounts.contains_key(&stake_pubkey));
+    }
+
     #[test]
     fn test_append_primordial_accounts_to_genesis() {
         // Test invalid file returns error
@@ -41,6 +41,7 @@ use {
             DUPLICATE_SHRED_MAX_PAYLOAD_SIZE, MAX_INCREMENTAL_SNAPSHOT_HASHES,
             MAX_PRUNE_DATA_NODES, PULL_RESPONSE_MIN_SERIALIZED_SIZE, PUSH_MESSAGE_MAX_PAYLOAD_SIZE,
         },
+        pull_response_rate_limiter::PullResponseRateLimiter,
         restart_crds_values::{
             RestartHeaviestFork, RestartLastVotedForkSlots, RestartLastVotedForkSlotsError,
         },
@@ -122,6 +123,12 @@ pub(crate) const CRDS_UNIQUE_PUBKEY_CAPACITY: usize = 8192;
 const MIN_STAKE_FOR_GOSSIP: u64 = solana_sdk::native_token::LAMPORTS_PER_SOL;
 /// Minimum number of staked nodes for enforcing stakes in gossip.
 const MIN_NUM_STAKED_NODES: usize = 500;
+/// Rate limit on pull-response bytes sent to each unstaked address.
+const PULL_RESPONSE_BYTES_PER_SEC_PER_ADDR: u64 = 128 * 1024;
+/// Pull-response bytes which can build up for an address, so that an
+/// occasional large pull-request is still served in full.
+const PULL_RESPONSE_BURST_BYTES_PER_ADDR: u64 = 4 * PULL_RESPONSE_BYTES_PER_SEC_PER_ADDR;
+const PULL_RESPONSE_RATE_LIMITER_CAPACITY: usize = 8192;
 
 // Must have at least one socket to monitor the TVU port
 // The unsafes are safe because we're using fixed, known non-zero values
@@ -152,6 +159,7 @@ pub struct ClusterInfo {
     outbound_budget: DataBudget,
     my_contact_info: RwLock<ContactInfo>,
     ping_cache: Mutex<PingCache>,
+    pull_response_rate_limiter: Mutex<PullResponseRateLimiter>,
     stats: GossipStats,
     local_message_pending_push_queue: Mutex<Vec<CrdsValue>>,
     contact_debug_interval: u64, // milliseconds, 0 = disabled
@@ -223,6 +231,11 @@ impl ClusterInfo {
                 GOSSIP_PING_CACHE_RATE_LIMIT_DELAY,
                 GOSSIP_PING_CACHE_CAPACITY,
             )),
+            pull_response_rate_limiter: Mutex::new(PullResponseRateLimiter::new(
+                PULL_RESPONSE_BYTES_PER_SEC_PER_ADDR,
+                PULL_RESPONSE_BURST_BYTES_PER_ADDR,
+                PULL_RESPONSE_RATE_LIMITER_CAPACITY,
+            )),
             stats: GossipStats::default(),
             local_message_pending_push_queue: Mutex::default(),
             contact_debug_interval: DEFAULT_CONTACT_DEBUG_INTERVAL_MILLIS,
@@ -1777,6 +1790,8 @@ impl ClusterInfo {
 
     // Pull requests take an incoming bloom filter of contained entries from a node
     // and tries to send back to them the values it detects are missing.
+    // Addresses which have not responded to a ping are sent a ping instead,
+    // and responses to unstaked nodes are rate limited per address.
     fn handle_pull_requests(
         &self,
         thread_pool: &ThreadPool,
@@ -1797,7 +1812,12 @@ impl ClusterInfo {
             requests
                 .into_iter()
                 .filter(check_pull_request)
-                .map(|r| ((r.caller, r.filter), r.from_addr))
+                .map(|r| {
+                    // (Sufficiently) staked nodes are exempt from the per
+                    // address rate limit.
+                    let staked = stakes.get(&r.caller.pubkey()) >= Some(&MIN_STAKE_FOR_GOSSIP);
+                    ((r.caller, r.filter), (r.from_addr, staked))
+                })
                 .unzip()
         };
         let now = timestamp();
@@ -1846,12 +1866,21 @@ impl ClusterInfo {
         let shuffle = WeightedShuffle::new("handle-pull-requests", &scores).shuffle(&mut rng);
         let mut total_bytes = 0;
         let mut sent = 0;
-        for (addr, response) in shuffle.map(|i| &responses[i]) {
+        let mut throttled_addrs = HashSet::<SocketAddr>::new();
+        let mut rate_limiter = self.pull_response_rate_limiter.lock().unwrap();
+        for ((addr, staked), response) in shuffle.map(|i| &responses[i]) {
+            if throttled_addrs.contains(addr) {
+                continue;
+            }
             let response = vec![response.clone()];
             let response = Protocol::PullResponse(self_id, response);
             match Packet::from_data(Some(addr), response) {
                 Err(err) => error!("failed to write pull-response packet: {:?}", err),
                 Ok(packet) => {
+                    if !staked && !rate_limiter.take(*addr, packet.meta().size, Instant::now()) {
+                        throttled_addrs.insert(*addr);
+                        continue;
+                    }
                     if self.outbound_budget.take(packet.meta().size) {
                         total_bytes += packet.meta().size;
                         packet_batch.push(packet);
@@ -1863,7 +1892,13 @@ impl ClusterInfo {
                 }
             }
         }
+        drop(rate_limiter);
         time.stop();
+        if !throttled_addrs.is_empty() {
+            self.stats
+                .gossip_pull_request_throttled_addrs
+                .add_relaxed(throttled_addrs.len() as u64);
+        }
         let dropped_responses = responses.len() - sent;
         self.stats
             .gossip_pull_request_sent_requests
@@ -3183,8 +3218,11 @@ mod tests {
             protocol::tests::new_rand_remote_node,
             socketaddr,
         },
+        assert_matches::assert_matches,
         bincode::serialize,
+        crossbeam_channel::unbounded,
         itertools::izip,
+        solana_bloom::bloom::Bloom,
         solana_ledger::shred::Shredder,
         solana_net_utils::bind_to,
         solana_sdk::signature::{Keypair, Signer},
@@ -3215,6 +3253,64 @@ mod tests {
         ));
     }
 
+    #[test]
+    fn test_process_packets_duplicate_instance() {
+        let mut rng = rand::thread_rng();
+        let thread_pool = ThreadPoolBuilder::new().build().unwrap();
+        let recycler = PacketBatchRecycler::default();
+        let (response_sender, _response_receiver) = unbounded();
+        let keypair = Arc::new(Keypair::new());
+        let cluster_info = ClusterInfo::new(
+            ContactInfo::new_localhost(&keypair.pubkey(), timestamp()),
+            keypair.clone(),
+            SocketAddrSpace::Unspecified,
+        );
+        let process_packets = |value: CrdsData, should_check_duplicate_instance| {
+            let value = CrdsValue::new_signed(value, &keypair);
+            let packets = VecDeque::from([(
+                socketaddr!(Ipv4Addr::LOCALHOST, 8000),
+                Protocol::PushMessage(keypair.pubkey(), vec![value]),
+            )]);
+            cluster_info.process_packets(
+                packets,
+                &thread_pool,
+                &recycler,
+                &response_sender,
+                &HashMap::default(), // stakes
+                None,                // feature_set
+                Duration::from_secs(48 * 3600),
+                should_check_duplicate_instance,
+            )
+        };
+        let now = timestamp();
+        *cluster_info.instance.write().unwrap() =
+            NodeInstance::new(&mut rng, keypair.pubkey(), now);
+        // A restarted node receives the instance of its previous run, which
+        // is not a duplicate.
+        let old_instance = NodeInstance::new(&mut rng, keypair.pubkey(), now - 1);
+        assert_matches!(
+            process_packets(CrdsData::NodeInstance(old_instance), true),
+            Ok(())
+        );
+        // Another instance started later with the same pubkey is.
+        let new_instance = NodeInstance::new(&mut rng, keypair.pubkey(), now + 1);
+        assert_matches!(
+            process_packets(CrdsData::NodeInstance(new_instance.clone()), true),
+            Err(GossipError::DuplicateNodeInstance)
+        );
+        assert_matches!(
+            process_packets(CrdsData::NodeInstance(new_instance), false),
+            Ok(())
+        );
+        // As is its contact-info, which has a more recent outset.
+        std::thread::sleep(Duration::from_millis(1));
+        let node = ContactInfo::new_localhost(&keypair.pubkey(), timestamp());
+        assert_matches!(
+            process_packets(CrdsData::ContactInfo(node), true),
+            Err(GossipError::DuplicateNodeInstance)
+        );
+    }
+
     #[test]
     fn test_handle_pull() {
         solana_logger::setup();
@@ -3339,6 +3435,106 @@ mod tests {
         }
     }
 
+    #[test]
+    fn test_handle_pull_requests_ping_and_rate_limit() {
+        let mut rng = rand::thread_rng();
+        let thread_pool = ThreadPoolBuilder::new().build().unwrap();
+        let recycler = PacketBatchRecycler::default();
+        let this_node = Arc::new(Keypair::new());
+        let cluster_info = ClusterInfo::new(
+            ContactInfo::new_localhost(&this_node.pubkey(), timestamp()),
+            this_node.clone(),
+            SocketAddrSpace::Unspecified,
+        );
+        {
+            let mut gossip_crds = cluster_info.gossip.crds.write().unwrap();
+            for _ in 0..64 {
+                let node = ContactInfo::new_localhost(&solana_pubkey::new_rand(), timestamp());
+                let node = CrdsValue::new_unsigned(CrdsData::ContactInfo(node));
+                gossip_crds
+                    .insert(node, timestamp(), GossipRoute::LocalMessage)
+                    .unwrap();
+            }
+        }
+        let num_values = cluster_info.gossip.crds.read().unwrap().len();
+        // Few enough staked nodes that stakes are not enforced, but enough
+        // for the outbound data budget not to limit responses.
+        let mut stakes: HashMap<_, _> = repeat_with(|| (solana_pubkey::new_rand(), 1))
+            .take(MIN_NUM_STAKED_NODES - 100)
+            .collect();
+        let (unstaked_node, unstaked_addr) = new_rand_remote_node(&mut rng);
+        let (staked_node, staked_addr) = new_rand_remote_node(&mut rng);
+        stakes.insert(staked_node.pubkey(), MIN_STAKE_FOR_GOSSIP);
+        let make_request = |node: &Keypair, addr: SocketAddr| {
+            let mut filter = CrdsFilter::default();
+            filter.filter = Bloom::random(1024, 0.1, 8 * 1024);
+            let caller = ContactInfo::new_localhost(&node.pubkey(), timestamp());
+            PullData {
+                from_addr: addr,
+                caller: CrdsValue::new_unsigned(CrdsData::ContactInfo(caller)),
+                filter,
+            }
+        };
+        let handle_pull_request = |node: &Keypair, addr: SocketAddr| {
+            let packets = cluster_info.handle_pull_requests(
+                &thread_pool,
+                &recycler,
+                vec![make_request(node, addr)],
+                &stakes,
+            );
+            packets
+                .iter()
+                .map(|packet| {
+                    assert_eq!(packet.meta().socket_addr(), addr);
+                    packet.deserialize_slice(..).unwrap()
+                })
+                .collect::<Vec<Protocol>>()
+        };
+
+        // An unverified address is sent a ping and no data.
+        let messages = handle_pull_request(&unstaked_node, unstaked_addr);
+        assert_matches!(&messages[..], [Protocol::PingMessage(_)]);
+
+        // Once verified, it is sent data.
+        cluster_info.ping_cache.lock().unwrap().mock_pong(
+            unstaked_node.pubkey(),
+            unstaked_addr,
+            Instant::now(),
+        );
+        let messages = handle_pull_request(&unstaked_node, unstaked_addr);
+        assert_eq!(messages.len(), num_values);
+        assert!(messages
+            .iter()
+            .all(|message| matches!(message, Protocol::PullResponse(..))));
+
+        // But a flooding unstaked address is throttled, once its bytes run
+        // out and with practically no refill.
+        *cluster_info.pull_response_rate_limiter.lock().unwrap() = PullResponseRateLimiter::new(
+            1,    // bytes_per_sec
+            2048, // capacity
+            PULL_RESPONSE_RATE_LIMITER_CAPACITY,
+        );
+        let messages = handle_pull_request(&unstaked_node, unstaked_addr);
+        assert!(!messages.is_empty());
+        assert!(messages.len() < num_values);
+        assert!(messages
+            .iter()
+            .all(|message| matches!(message, Protocol::PullResponse(..))));
+        let messages = handle_pull_request(&unstaked_node, unstaked_addr);
+        assert!(messages.is_empty());
+
+        // A verified staked node is exempt from the rate limit.
+        cluster_info.ping_cache.lock().unwrap().mock_pong(
+            staked_node.pubkey(),
+            staked_addr,
+            Instant::now(),
+        );
+        for _ in 0..3 {
+            let messages = handle_pull_request(&staked_node, staked_addr);
+            assert_eq!(messages.len(), num_values);
+        }
+    }
+
     fn test_crds_values(pubkey: Pubkey) -> Vec<CrdsValue> {
         let entrypoint = ContactInfo::new_localhost(&pubkey, timestamp());
         let entrypoint_crdsvalue = CrdsValue::new_unsigned(CrdsData::ContactInfo(entrypoint));
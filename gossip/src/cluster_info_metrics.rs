@@ -109,6 +109,7 @@ pub struct GossipStats {
     pub(crate) gossip_pull_request_dropped_requests: Counter,
     pub(crate) gossip_pull_request_no_budget: Counter,
     pub(crate) gossip_pull_request_sent_requests: Counter,
+    pub(crate) gossip_pull_request_throttled_addrs: Counter,
     pub(crate) gossip_transmit_loop_iterations_since_last_report: Counter,
     pub(crate) gossip_transmit_loop_time: Counter,
     pub(crate) handle_batch_ping_messages_time: Counter,
@@ -374,6 +375,11 @@ pub(crate) fn submit_gossip_stats(
             stats.gossip_pull_request_sent_requests.clear(),
             i64
         ),
+        (
+            "gossip_pull_request_throttled_addrs",
+            stats.gossip_pull_request_throttled_addrs.clear(),
+            i64
+        ),
         (
             "gossip_pull_request_dropped_requests",
             stats.gossip_pull_request_dropped_requests.clear(),
@@ -789,7 +789,12 @@ mod tests {
             signature::{Keypair, Signer},
             timing::timestamp,
         },
-        std::{collections::HashSet, iter::repeat_with, net::Ipv4Addr, time::Duration},
+        std::{
+            collections::HashSet,
+            iter::repeat_with,
+            net::{Ipv4Addr, SocketAddr},
+            time::Duration,
+        },
     };
 
     #[test]
@@ -933,6 +938,40 @@ mod tests {
         }
     }
 
+    #[test]
+    fn test_upsert_contact_info_restart() {
+        let now = timestamp();
+        let mut crds = Crds::default();
+        let pubkey = Pubkey::new_unique();
+        // The old instance's clock is ahead.
+        let node = ContactInfo::new_localhost(&pubkey, now + 100);
+        let stale = CrdsValue::new_unsigned(CrdsData::ContactInfo(node.clone()));
+        assert_eq!(crds.insert(stale, now, GossipRoute::LocalMessage), Ok(()));
+        // The node restarts with the same pubkey and new ports. Its
+        // contact-info replaces the old one on receipt despite the older
+        // wallclock.
+        std::thread::sleep(Duration::from_millis(1));
+        let mut restarted = ContactInfo::new_localhost(&pubkey, now);
+        restarted.set_gossip((Ipv4Addr::LOCALHOST, 9000)).unwrap();
+        restarted.set_tvu((Ipv4Addr::LOCALHOST, 9001)).unwrap();
+        let value = CrdsValue::new_unsigned(CrdsData::ContactInfo(restarted.clone()));
+        assert_eq!(crds.insert(value, now, GossipRoute::PushMessage), Ok(()));
+        assert_eq!(crds.get::<&ContactInfo>(pubkey), Some(&restarted));
+        // Values of the old instance still in flight are rejected even with
+        // more recent wallclocks, so peers converge to the new ports.
+        let mut node = node;
+        node.set_wallclock(now + 200);
+        let stale = CrdsValue::new_unsigned(CrdsData::ContactInfo(node));
+        assert_eq!(
+            crds.insert(stale, now, GossipRoute::PushMessage),
+            Err(CrdsError::InsertFailed)
+        );
+        assert_eq!(
+            crds.get::<&ContactInfo>(pubkey).unwrap().gossip(),
+            Some(SocketAddr::from((Ipv4Addr::LOCALHOST, 9000)))
+        );
+    }
+
     #[test]
     fn test_find_old_records_default() {
         let thread_pool = ThreadPoolBuilder::new().build().unwrap();
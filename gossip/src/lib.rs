@@ -24,6 +24,7 @@ pub mod gossip_service;
 mod legacy_contact_info;
 pub mod ping_pong;
 mod protocol;
+mod pull_response_rate_limiter;
 mod push_active_set;
 mod received_cache;
 pub mod restart_crds_values;
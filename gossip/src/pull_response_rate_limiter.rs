@@ -0,0 +1,94 @@
+use {
+    lru::LruCache,
+    std::{net::SocketAddr, time::Instant},
+};
+
+// Token bucket per remote address limiting the bytes of pull-responses sent
+// to it. Pull-requests are small while pull-responses can be large, so
+// without a per-address limit a node which passed the ping/pong check once can
+// still have this node flood the address with responses.
+pub(crate) struct PullResponseRateLimiter {
+    bytes_per_sec: u64,
+    // Maximum number of bytes which can build up in a bucket.
+    capacity: u64,
+    buckets: LruCache<SocketAddr, TokenBucket>,
+}
+
+struct TokenBucket {
+    bytes: u64,
+    last_refill: Instant,
+}
+
+impl PullResponseRateLimiter {
+    pub(crate) fn new(bytes_per_sec: u64, capacity: u64, num_buckets: usize) -> Self {
+        Self {
+            bytes_per_sec,
+            capacity,
+            buckets: LruCache::new(num_buckets),
+        }
+    }
+
+    // Returns true and deducts size bytes from the address's bucket if it
+    // has enough bytes left. New addresses start with a full bucket.
+    pub(crate) fn take(&mut self, addr: SocketAddr, size: usize, now: Instant) -> bool {
+        let size = size as u64;
+        if let Some(bucket) = self.buckets.get_mut(&addr) {
+            let elapsed = now.saturating_duration_since(bucket.last_refill);
+            let refill =
+                (elapsed.as_micros() as u64).saturating_mul(self.bytes_per_sec) / 1_000_000;
+            if refill > 0 {
+                bucket.bytes = bucket.bytes.saturating_add(refill).min(self.capacity);
+                bucket.last_refill = now;
+            }
+            if bucket.bytes < size {
+                return false;
+            }
+            bucket.bytes -= size;
+            return true;
+        }
+        if self.capacity < size {
+            return false;
+        }
+        let bucket = TokenBucket {
+            bytes: self.capacity - size,
+            last_refill: now,
+        };
+        self.buckets.put(addr, bucket);
+        true
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use {super::*, std::time::Duration};
+
+    #[test]
+    fn test_pull_response_rate_limiter() {
+        let now = Instant::now();
+        let mut limiter = PullResponseRateLimiter::new(
+            1000, // bytes_per_sec
+            2000, // capacity
+            2,    // num_buckets
+        );
+        let addr = SocketAddr::from(([127, 0, 0, 1], 8001));
+        let other = SocketAddr::from(([127, 0, 0, 1], 8002));
+        // A new address starts with a full bucket.
+        assert!(limiter.take(addr, 1500, now));
+        assert!(!limiter.take(addr, 1000, now));
+        assert!(limiter.take(addr, 500, now));
+        assert!(!limiter.take(addr, 1, now));
+        // Buckets are independent.
+        assert!(limiter.take(other, 2000, now));
+        // The bucket refills at bytes_per_sec.
+        let now = now + Duration::from_millis(500);
+        assert!(!limiter.take(addr, 501, now));
+        assert!(limiter.take(addr, 500, now));
+        // But does not build up beyond capacity.
+        let now = now + Duration::from_secs(10);
+        assert!(!limiter.take(addr, 2001, now));
+        assert!(limiter.take(addr, 2000, now));
+        // Responses larger than the capacity are never sent.
+        let new_addr = SocketAddr::from(([127, 0, 0, 1], 8003));
+        assert!(!limiter.take(new_addr, 2001, now));
+    }
+}
@@ -1,3 +1,4 @@
 pub mod associated_token_account;
 pub mod token;
 pub mod token_2022;
+pub mod token_programs;
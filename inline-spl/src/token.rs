@@ -21,7 +21,8 @@ pub mod program_v3_4_0 {
 */
 pub const SPL_TOKEN_ACCOUNT_MINT_OFFSET: usize = 0;
 pub const SPL_TOKEN_ACCOUNT_OWNER_OFFSET: usize = 32;
-const SPL_TOKEN_ACCOUNT_LENGTH: usize = 165;
+pub const SPL_TOKEN_ACCOUNT_AMOUNT_OFFSET: usize = 64;
+pub const SPL_TOKEN_ACCOUNT_LENGTH: usize = 165;
 
 pub trait GenericTokenAccount {
     fn valid_account_data(account_data: &[u8]) -> bool;
@@ -36,6 +37,11 @@ pub trait GenericTokenAccount {
         Self::unpack_pubkey_unchecked(account_data, SPL_TOKEN_ACCOUNT_MINT_OFFSET)
     }
 
+    // Call after account length has already been verified
+    fn unpack_account_amount_unchecked(account_data: &[u8]) -> u64 {
+        unpack_u64_unchecked(account_data, SPL_TOKEN_ACCOUNT_AMOUNT_OFFSET)
+    }
+
     // Call after account length has already been verified
     fn unpack_pubkey_unchecked(account_data: &[u8], offset: usize) -> &Pubkey {
         bytemuck::from_bytes(&account_data[offset..offset.wrapping_add(PUBKEY_BYTES)])
@@ -56,6 +62,21 @@ pub trait GenericTokenAccount {
             None
         }
     }
+
+    fn unpack_account_amount(account_data: &[u8]) -> Option<u64> {
+        if Self::valid_account_data(account_data) {
+            Some(Self::unpack_account_amount_unchecked(account_data))
+        } else {
+            None
+        }
+    }
+}
+
+// Call after account length has already been verified
+fn unpack_u64_unchecked(data: &[u8], offset: usize) -> u64 {
+    let mut bytes = [0u8; 8];
+    bytes.copy_from_slice(&data[offset..offset.wrapping_add(8)]);
+    u64::from_le_bytes(bytes)
 }
 
 pub struct Account;
@@ -71,6 +92,55 @@ impl GenericTokenAccount for Account {
     }
 }
 
+/*
+    spl_token::state::Mint {
+        mint_authority: COption<Pubkey>,
+        supply: u64,
+        decimals: u8,
+        is_initialized: bool,
+        freeze_authority: COption<Pubkey>,
+    }
+*/
+pub const SPL_TOKEN_MINT_SUPPLY_OFFSET: usize = 36;
+pub const SPL_TOKEN_MINT_DECIMALS_OFFSET: usize = 44;
+const SPL_TOKEN_MINT_LENGTH: usize = 82;
+
+pub trait GenericTokenMint {
+    fn valid_mint_data(mint_data: &[u8]) -> bool;
+
+    fn unpack_mint_supply(mint_data: &[u8]) -> Option<u64> {
+        if Self::valid_mint_data(mint_data) {
+            Some(unpack_u64_unchecked(
+                mint_data,
+                SPL_TOKEN_MINT_SUPPLY_OFFSET,
+            ))
+        } else {
+            None
+        }
+    }
+
+    fn unpack_mint_decimals(mint_data: &[u8]) -> Option<u8> {
+        if Self::valid_mint_data(mint_data) {
+            Some(mint_data[SPL_TOKEN_MINT_DECIMALS_OFFSET])
+        } else {
+            None
+        }
+    }
+}
+
+pub struct Mint;
+impl Mint {
+    pub fn get_packed_len() -> usize {
+        SPL_TOKEN_MINT_LENGTH
+    }
+}
+
+impl GenericTokenMint for Mint {
+    fn valid_mint_data(mint_data: &[u8]) -> bool {
+        mint_data.len() == SPL_TOKEN_MINT_LENGTH
+    }
+}
+
 pub mod native_mint {
     solana_pubkey::declare_id!("So11111111111111111111111111111111111111112");
 
@@ -89,3 +159,71 @@ pub mod native_mint {
         0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0,
     ];
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn token_account_data(mint: &Pubkey, owner: &Pubkey, amount: u64) -> Vec<u8> {
+        let mut data = Vec::with_capacity(SPL_TOKEN_ACCOUNT_LENGTH);
+        data.extend_from_slice(mint.as_ref());
+        data.extend_from_slice(owner.as_ref());
+        data.extend_from_slice(&amount.to_le_bytes());
+        data.extend_from_slice(&[0; 4]); // delegate: COption::None
+        data.extend_from_slice(&[0; 32]);
+        data.push(1); // state: AccountState::Initialized
+        data.extend_from_slice(&[0; 12]); // is_native: COption::None
+        data.extend_from_slice(&[0; 8]); // delegated_amount
+        data.extend_from_slice(&[0; 36]); // close_authority: COption::None
+        assert_eq!(data.len(), SPL_TOKEN_ACCOUNT_LENGTH);
+        data
+    }
+
+    #[test]
+    fn test_unpack_account() {
+        let mint = Pubkey::new_unique();
+        let owner = Pubkey::new_unique();
+        let data = token_account_data(&mint, &owner, 42_000_000_000);
+
+        assert_eq!(Account::unpack_account_mint(&data), Some(&mint));
+        assert_eq!(Account::unpack_account_owner(&data), Some(&owner));
+        assert_eq!(Account::unpack_account_amount(&data), Some(42_000_000_000));
+
+        for len in [
+            0,
+            SPL_TOKEN_ACCOUNT_LENGTH - 1,
+            SPL_TOKEN_ACCOUNT_LENGTH + 1,
+        ] {
+            let mut data = data.clone();
+            data.resize(len, 0);
+            assert_eq!(Account::unpack_account_mint(&data), None);
+            assert_eq!(Account::unpack_account_owner(&data), None);
+            assert_eq!(Account::unpack_account_amount(&data), None);
+        }
+    }
+
+    #[test]
+    fn test_unpack_mint() {
+        assert_eq!(
+            Mint::unpack_mint_supply(&native_mint::ACCOUNT_DATA),
+            Some(0)
+        );
+        assert_eq!(
+            Mint::unpack_mint_decimals(&native_mint::ACCOUNT_DATA),
+            Some(9)
+        );
+
+        let mut data = native_mint::ACCOUNT_DATA;
+        data[SPL_TOKEN_MINT_SUPPLY_OFFSET..SPL_TOKEN_MINT_DECIMALS_OFFSET]
+            .copy_from_slice(&1_000_000u64.to_le_bytes());
+        data[SPL_TOKEN_MINT_DECIMALS_OFFSET] = 6;
+        assert_eq!(Mint::unpack_mint_supply(&data), Some(1_000_000));
+        assert_eq!(Mint::unpack_mint_decimals(&data), Some(6));
+
+        assert_eq!(
+            Mint::unpack_mint_supply(&data[..SPL_TOKEN_MINT_LENGTH - 1]),
+            None
+        );
+        assert_eq!(Mint::unpack_mint_decimals(&[]), None);
+    }
+}
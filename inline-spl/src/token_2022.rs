@@ -1,8 +1,10 @@
 /// Partial SPL Token declarations inlined to avoid an external dependency on the spl-token-2022 crate
-use crate::token::{self, GenericTokenAccount};
+use crate::token::{self, GenericTokenAccount, GenericTokenMint};
 
 solana_pubkey::declare_id!("TokenzQdBNbLqP5VEhdkAS6EPFLC1PHnBqCXEpPxuEb");
 
+// `spl_token_program_2022::extension::AccountType::Mint` ordinal value
+pub const ACCOUNTTYPE_MINT: u8 = 1;
 // `spl_token_program_2022::extension::AccountType::Account` ordinal value
 pub const ACCOUNTTYPE_ACCOUNT: u8 = 2;
 
@@ -16,3 +18,16 @@ impl GenericTokenAccount for Account {
                     .unwrap_or(&0)
     }
 }
+
+pub struct Mint;
+impl GenericTokenMint for Mint {
+    fn valid_mint_data(mint_data: &[u8]) -> bool {
+        // Mints with extensions are padded to the length of an account, so the
+        // account type can't be confused with that of a token account
+        token::Mint::valid_mint_data(mint_data)
+            || ACCOUNTTYPE_MINT
+                == *mint_data
+                    .get(token::Account::get_packed_len())
+                    .unwrap_or(&0)
+    }
+}
@@ -0,0 +1,172 @@
+//! Layouts of the token programs whose accounts are recognized by the runtime
+//!
+//! Token program upgrades deploy under a new program id, so the set of token ids
+//! with a known account layout grows over time. Anything which indexes or parses
+//! token accounts by owner program should consult this set rather than a single id.
+
+use {
+    crate::{token, token_2022},
+    solana_pubkey::{Pubkey, PUBKEY_BYTES},
+};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct TokenProgram {
+    pub id: Pubkey,
+    /// Length of the base token account state
+    pub account_len: usize,
+    pub mint_offset: usize,
+    pub owner_offset: usize,
+    pub amount_offset: usize,
+    /// Whether accounts may be longer than `account_len` when followed by an account
+    /// type byte and extensions, as in spl-token-2022
+    pub extensions: bool,
+}
+
+/// The known token programs, legacy first
+pub const KNOWN_TOKEN_PROGRAMS: &[TokenProgram] = &[
+    // spl-token v2.0
+    TokenProgram {
+        id: token::id(),
+        account_len: token::SPL_TOKEN_ACCOUNT_LENGTH,
+        mint_offset: token::SPL_TOKEN_ACCOUNT_MINT_OFFSET,
+        owner_offset: token::SPL_TOKEN_ACCOUNT_OWNER_OFFSET,
+        amount_offset: token::SPL_TOKEN_ACCOUNT_AMOUNT_OFFSET,
+        extensions: false,
+    },
+    // spl-token v3.4.0, deployed under a new id with an unchanged account layout
+    TokenProgram {
+        id: token::program_v3_4_0::id(),
+        account_len: token::SPL_TOKEN_ACCOUNT_LENGTH,
+        mint_offset: token::SPL_TOKEN_ACCOUNT_MINT_OFFSET,
+        owner_offset: token::SPL_TOKEN_ACCOUNT_OWNER_OFFSET,
+        amount_offset: token::SPL_TOKEN_ACCOUNT_AMOUNT_OFFSET,
+        extensions: false,
+    },
+    TokenProgram {
+        id: token_2022::id(),
+        account_len: token::SPL_TOKEN_ACCOUNT_LENGTH,
+        mint_offset: token::SPL_TOKEN_ACCOUNT_MINT_OFFSET,
+        owner_offset: token::SPL_TOKEN_ACCOUNT_OWNER_OFFSET,
+        amount_offset: token::SPL_TOKEN_ACCOUNT_AMOUNT_OFFSET,
+        extensions: true,
+    },
+];
+
+/// Returns the layout of the token program with the given id, if it is known
+pub fn token_program(program_id: &Pubkey) -> Option<&'static TokenProgram> {
+    KNOWN_TOKEN_PROGRAMS
+        .iter()
+        .find(|token_program| token_program.id == *program_id)
+}
+
+pub fn is_known_token_program(program_id: &Pubkey) -> bool {
+    token_program(program_id).is_some()
+}
+
+impl TokenProgram {
+    pub fn valid_account_data(&self, account_data: &[u8]) -> bool {
+        account_data.len() == self.account_len
+            || (self.extensions
+                && account_data.get(self.account_len) == Some(&token_2022::ACCOUNTTYPE_ACCOUNT))
+    }
+
+    pub fn unpack_account_owner<'a>(&self, account_data: &'a [u8]) -> Option<&'a Pubkey> {
+        self.valid_account_data(account_data)
+            .then(|| unpack_pubkey_unchecked(account_data, self.owner_offset))
+    }
+
+    pub fn unpack_account_mint<'a>(&self, account_data: &'a [u8]) -> Option<&'a Pubkey> {
+        self.valid_account_data(account_data)
+            .then(|| unpack_pubkey_unchecked(account_data, self.mint_offset))
+    }
+
+    pub fn unpack_account_amount(&self, account_data: &[u8]) -> Option<u64> {
+        self.valid_account_data(account_data).then(|| {
+            let mut bytes = [0u8; 8];
+            bytes.copy_from_slice(&account_data[self.amount_offset..self.amount_offset + 8]);
+            u64::from_le_bytes(bytes)
+        })
+    }
+}
+
+// Call after account length has already been verified
+fn unpack_pubkey_unchecked(account_data: &[u8], offset: usize) -> &Pubkey {
+    bytemuck::from_bytes(&account_data[offset..offset.wrapping_add(PUBKEY_BYTES)])
+}
+
+#[cfg(test)]
+mod tests {
+    use {
+        super::*,
+        crate::token::{GenericTokenAccount, SPL_TOKEN_ACCOUNT_LENGTH},
+    };
+
+    fn token_account_data(mint: &Pubkey, owner: &Pubkey, amount: u64) -> Vec<u8> {
+        let mut data = Vec::with_capacity(SPL_TOKEN_ACCOUNT_LENGTH);
+        data.extend_from_slice(mint.as_ref());
+        data.extend_from_slice(owner.as_ref());
+        data.extend_from_slice(&amount.to_le_bytes());
+        data.extend_from_slice(&[0; 4]); // delegate: COption::None
+        data.extend_from_slice(&[0; 32]);
+        data.push(1); // state: AccountState::Initialized
+        data.extend_from_slice(&[0; 12]); // is_native: COption::None
+        data.extend_from_slice(&[0; 8]); // delegated_amount
+        data.extend_from_slice(&[0; 36]); // close_authority: COption::None
+        assert_eq!(data.len(), SPL_TOKEN_ACCOUNT_LENGTH);
+        data
+    }
+
+    #[test]
+    fn test_known_token_programs() {
+        for token_program in KNOWN_TOKEN_PROGRAMS {
+            assert_eq!(super::token_program(&token_program.id), Some(token_program));
+            assert!(is_known_token_program(&token_program.id));
+        }
+        assert!(!is_known_token_program(&Pubkey::new_unique()));
+        assert!(!is_known_token_program(&token::native_mint::id()));
+    }
+
+    #[test]
+    fn test_token_program_layouts() {
+        let mint = Pubkey::new_unique();
+        let owner = Pubkey::new_unique();
+        let data = token_account_data(&mint, &owner, 42);
+
+        for token_program in KNOWN_TOKEN_PROGRAMS {
+            assert_eq!(token_program.account_len, token::Account::get_packed_len());
+            assert_eq!(token_program.unpack_account_mint(&data), Some(&mint));
+            assert_eq!(token_program.unpack_account_owner(&data), Some(&owner));
+            assert_eq!(token_program.unpack_account_amount(&data), Some(42));
+            // The table agrees with the per-program unpackers
+            let (expected_owner, expected_mint) = if token_program.extensions {
+                (
+                    token_2022::Account::unpack_account_owner(&data),
+                    token_2022::Account::unpack_account_mint(&data),
+                )
+            } else {
+                (
+                    token::Account::unpack_account_owner(&data),
+                    token::Account::unpack_account_mint(&data),
+                )
+            };
+            assert_eq!(token_program.unpack_account_owner(&data), expected_owner);
+            assert_eq!(token_program.unpack_account_mint(&data), expected_mint);
+
+            let mut short = data.clone();
+            short.pop();
+            assert_eq!(token_program.unpack_account_owner(&short), None);
+
+            // Longer accounts are only valid with a token-2022 account type byte
+            let mut extended = data.clone();
+            extended.push(token_2022::ACCOUNTTYPE_ACCOUNT);
+            extended.extend_from_slice(&[0; 8]);
+            assert_eq!(
+                token_program.unpack_account_owner(&extended),
+                token_program.extensions.then_some(&owner)
+            );
+            let mut mint_data = data.clone();
+            mint_data.push(token_2022::ACCOUNTTYPE_MINT);
+            assert_eq!(token_program.unpack_account_owner(&mint_data), None);
+        }
+    }
+}
@@ -103,6 +103,13 @@ pub fn accounts_db_args<'a, 'b>() -> Box<[Arg<'a, 'b>]> {
             .long("accounts-db-skip-initial-hash-calculation")
             .help("Do not verify accounts hash at startup.")
             .hidden(hidden_unless_forced()),
+        Arg::with_name("accounts_db_skip_hash_cache")
+            .long("accounts-db-skip-hash-cache")
+            .help(
+                "Always scan the account storages when calculating the accounts hash, instead \
+                 of reusing the accounts hash cache files from a previous calculation.",
+            )
+            .hidden(hidden_unless_forced()),
         Arg::with_name("accounts_db_ancient_append_vecs")
             .long("accounts-db-ancient-append-vecs")
             .value_name("SLOT-OFFSET")
@@ -382,6 +389,7 @@ pub fn get_accounts_db_config(
         .ok(),
         exhaustively_verify_refcounts: arg_matches.is_present("accounts_db_verify_refcounts"),
         skip_initial_hash_calc: arg_matches.is_present("accounts_db_skip_initial_hash_calculation"),
+        skip_accounts_hash_cache: arg_matches.is_present("accounts_db_skip_hash_cache"),
         test_skip_rewrites_but_include_in_bank_hash: arg_matches
             .is_present("accounts_db_test_skip_rewrites"),
         create_ancient_storage,
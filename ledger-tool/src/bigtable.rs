@@ -149,6 +149,7 @@ async fn block(
         let cli_block = CliBlock {
             encoded_confirmed_block: encoded_block,
             slot,
+            show_transactions: true,
         };
         println!("{}", output_format.formatted_string(&cli_block));
     }
@@ -516,6 +517,8 @@ async fn confirm(
     }
     let cli_transaction = CliTransactionConfirmation {
         confirmation_status: Some(transaction_status.confirmation_status()),
+        slot: Some(transaction_status.slot),
+        confirmations: transaction_status.confirmations,
         transaction,
         get_transaction_error,
         err: transaction_status.err.clone(),
@@ -336,7 +336,7 @@ pub fn blockstore_subcommands<'a, 'b>(hidden: bool) -> Vec<App<'a, 'b>> {
                     .help("Target db"),
             ),
         SubCommand::with_name("dead-slots")
-            .about("Print all the dead slots in the ledger")
+            .about("Print all the dead slots in the ledger, along with how complete each slot is")
             .settings(&hidden)
             .arg(&starting_slot_arg),
         SubCommand::with_name("duplicate-slots")
@@ -531,6 +531,7 @@ pub fn blockstore_subcommands<'a, 'b>(hidden: bool) -> Vec<App<'a, 'b>> {
             .arg(
                 Arg::with_name("start_root")
                     .long("before")
+                    .alias("start")
                     .value_name("NUM")
                     .takes_value(true)
                     .help("Recent root after the range to repair"),
@@ -538,6 +539,7 @@ pub fn blockstore_subcommands<'a, 'b>(hidden: bool) -> Vec<App<'a, 'b>> {
             .arg(
                 Arg::with_name("end_root")
                     .long("until")
+                    .alias("end")
                     .value_name("NUM")
                     .takes_value(true)
                     .help("Earliest slot to check for root repair"),
@@ -550,6 +552,15 @@ pub fn blockstore_subcommands<'a, 'b>(hidden: bool) -> Vec<App<'a, 'b>> {
                     .default_value("2000")
                     .required(true)
                     .help("Override the maximum number of slots to check for root repair"),
+            )
+            .arg(
+                Arg::with_name("dry_run")
+                    .long("dry-run")
+                    .takes_value(false)
+                    .help(
+                        "Print the slots that would be marked as roots without modifying the \
+                         ledger",
+                    ),
             ),
         SubCommand::with_name("set-dead-slot")
             .about("Mark one or more slots dead")
@@ -683,8 +694,22 @@ fn do_blockstore_process_command(ledger_path: &Path, matches: &ArgMatches<'_>) -
             let blockstore =
                 crate::open_blockstore(&ledger_path, arg_matches, AccessType::Secondary);
             let starting_slot = value_t_or_exit!(arg_matches, "starting_slot", Slot);
+            // The reason a slot was marked dead is not persisted, so describe
+            // what the blockstore holds for the slot instead
             for slot in blockstore.dead_slots_iterator(starting_slot)? {
-                println!("{slot}");
+                match blockstore.meta(slot)? {
+                    Some(meta) if meta.is_full() => {
+                        println!("{slot}: full, {} shreds", meta.consumed)
+                    }
+                    Some(meta) => println!(
+                        "{slot}: incomplete, {} of {} shreds received",
+                        meta.consumed,
+                        meta.last_index
+                            .map(|index| (index + 1).to_string())
+                            .unwrap_or_else(|| "unknown".to_string()),
+                    ),
+                    None => println!("{slot}: no slot meta"),
+                }
             }
         }
         ("duplicate-slots", Some(arg_matches)) => {
@@ -951,12 +976,23 @@ fn do_blockstore_process_command(ledger_path: &Path, matches: &ArgMatches<'_>) -
                 )));
             }
 
-            let num_repaired_roots = blockstore.scan_and_fix_roots(
+            let roots_to_fix = blockstore.find_missing_roots(
                 Some(start_root),
                 Some(end_root),
                 &AtomicBool::new(false),
             )?;
-            println!("Successfully repaired {num_repaired_roots} roots");
+            let dry_run = arg_matches.is_present("dry_run");
+            for slot in roots_to_fix.iter().rev() {
+                if dry_run {
+                    println!("Would mark slot {slot} as root");
+                } else {
+                    println!("Marking slot {slot} as root");
+                }
+            }
+            if !dry_run {
+                blockstore.set_roots(roots_to_fix.iter())?;
+                println!("Successfully repaired {} roots", roots_to_fix.len());
+            }
         }
         ("set-dead-slot", Some(arg_matches)) => {
             let slots = values_t_or_exit!(arg_matches, "slots", Slot);
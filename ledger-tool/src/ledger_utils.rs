@@ -136,6 +136,32 @@ pub fn load_and_process_ledger(
     process_options: ProcessOptions,
     transaction_status_sender: Option<TransactionStatusSender>,
     ignore_halt_at_slot_for_snapshot_loading: bool,
+) -> Result<LoadAndProcessLedgerOutput, LoadAndProcessLedgerError> {
+    let snapshot_halt_at_slot = if ignore_halt_at_slot_for_snapshot_loading {
+        None
+    } else {
+        process_options.halt_at_slot
+    };
+    load_and_process_ledger_from_snapshot_at_or_before(
+        arg_matches,
+        genesis_config,
+        blockstore,
+        process_options,
+        transaction_status_sender,
+        snapshot_halt_at_slot,
+    )
+}
+
+/// Like `load_and_process_ledger()`, but loads the highest snapshot at or
+/// before `snapshot_halt_at_slot` (or the highest snapshot if `None`)
+/// regardless of where processing halts
+pub fn load_and_process_ledger_from_snapshot_at_or_before(
+    arg_matches: &ArgMatches,
+    genesis_config: &GenesisConfig,
+    blockstore: Arc<Blockstore>,
+    process_options: ProcessOptions,
+    transaction_status_sender: Option<TransactionStatusSender>,
+    snapshot_halt_at_slot: Option<Slot>,
 ) -> Result<LoadAndProcessLedgerOutput, LoadAndProcessLedgerError> {
     let bank_snapshots_dir = if blockstore.is_primary_access() {
         blockstore.ledger_path().join("snapshot")
@@ -146,12 +172,6 @@ pub fn load_and_process_ledger(
             .join("snapshot")
     };
 
-    let snapshot_halt_at_slot = if ignore_halt_at_slot_for_snapshot_loading {
-        None
-    } else {
-        process_options.halt_at_slot
-    };
-
     let mut starting_slot = 0; // default start check with genesis
     let snapshot_config = if arg_matches.is_present("no_snapshot") {
         None
@@ -356,12 +376,16 @@ pub fn load_and_process_ledger(
             blockstore.as_ref(),
             account_paths,
             snapshot_config.as_ref(),
-            &process_options,
+            // Snapshot selection is bounded by halt_at_slot
+            &ProcessOptions {
+                halt_at_slot: snapshot_halt_at_slot,
+                ..process_options.clone()
+            },
             cache_block_meta_sender.as_ref(),
             None, // Maybe support this later, though
             accounts_update_notifier,
             exit.clone(),
-            ignore_halt_at_slot_for_snapshot_loading,
+            false, // ignore_halt_at_slot_for_snapshot_loading
         )
         .map_err(LoadAndProcessLedgerError::LoadBankForks)?;
     let block_verification_method = value_t!(
@@ -11,6 +11,7 @@ use {
             SlotBankHash,
         },
         program::*,
+        replay_range::TraceWriter,
     },
     clap::{
         crate_description, crate_name, value_t, value_t_or_exit, values_t_or_exit, App,
@@ -108,6 +109,7 @@ mod ledger_path;
 mod ledger_utils;
 mod output;
 mod program;
+mod replay_range;
 
 fn parse_encoding_format(matches: &ArgMatches<'_>) -> UiAccountEncoding {
     match matches.value_of("encoding") {
@@ -1215,6 +1217,55 @@ fn main() {
                         ),
                 ),
         )
+        .subcommand(
+            SubCommand::with_name("replay-range")
+                .about(
+                    "Replay a range of slots from the nearest snapshot before it and trace the \
+                     replayed transactions",
+                )
+                .arg(&load_genesis_config_arg)
+                .args(&accounts_db_config_args)
+                .args(&snapshot_config_args)
+                .arg(&hard_forks_arg)
+                .arg(&allow_dead_slots_arg)
+                .arg(&debug_key_arg)
+                .arg(&log_messages_bytes_limit_arg)
+                .arg(
+                    Arg::with_name("start")
+                        .long("start")
+                        .value_name("SLOT")
+                        .takes_value(true)
+                        .required(true)
+                        .validator(is_slot)
+                        .help("First slot to trace"),
+                )
+                .arg(
+                    Arg::with_name("end")
+                        .long("end")
+                        .value_name("SLOT")
+                        .takes_value(true)
+                        .required(true)
+                        .validator(is_slot)
+                        .help("Last slot to trace; replay halts after this slot"),
+                )
+                .arg(
+                    Arg::with_name("trace_output")
+                        .long("trace-output")
+                        .value_name("DIR")
+                        .takes_value(true)
+                        .required(true)
+                        .help(
+                            "Directory to write a <SLOT>.jsonl file into for each traced slot, \
+                             with one line per transaction",
+                        ),
+                )
+                .arg(
+                    Arg::with_name("skip_verification")
+                        .long("skip-poh-verify")
+                        .takes_value(false)
+                        .help("Skip ledger PoH and transaction verification."),
+                ),
+        )
         .subcommand(
             SubCommand::with_name("graph")
                 .about("Create a Graphviz rendering of the ledger")
@@ -1839,6 +1890,57 @@ fn main() {
                     exit_signal.store(true, Ordering::Relaxed);
                     system_monitor_service.join().unwrap();
                 }
+                ("replay-range", Some(arg_matches)) => {
+                    let start = value_t_or_exit!(arg_matches, "start", Slot);
+                    let end = value_t_or_exit!(arg_matches, "end", Slot);
+                    if end < start {
+                        eprintln!("error: --end {end} must not be less than --start {start}");
+                        exit(1);
+                    }
+                    let trace_dir =
+                        PathBuf::from(value_t_or_exit!(arg_matches, "trace_output", String));
+                    let trace_writer = TraceWriter::new(trace_dir.clone(), start..=end)
+                        .unwrap_or_else(|err| {
+                            eprintln!("Unable to write traces to {}: {err}", trace_dir.display());
+                            exit(1);
+                        });
+
+                    let mut process_options = parse_process_options(&ledger_path, arg_matches);
+                    process_options.halt_at_slot = Some(end);
+                    process_options.slot_callback = Some(trace_writer.slot_callback());
+
+                    let genesis_config = open_genesis_config_by(&ledger_path, arg_matches);
+                    let blockstore = open_blockstore(
+                        &ledger_path,
+                        arg_matches,
+                        get_access_type(&process_options),
+                    );
+                    // Load a snapshot strictly before start so that start
+                    // itself is replayed and traced
+                    let LoadAndProcessLedgerOutput { bank_forks, .. } =
+                        load_and_process_ledger_from_snapshot_at_or_before(
+                            arg_matches,
+                            &genesis_config,
+                            Arc::new(blockstore),
+                            process_options,
+                            Some(trace_writer.transaction_status_sender()),
+                            Some(start.saturating_sub(1)),
+                        )
+                        .unwrap_or_else(|err| {
+                            eprintln!("Exiting. Failed to load and process ledger: {err}");
+                            exit(1);
+                        });
+
+                    trace_writer.join().unwrap_or_else(|err| {
+                        eprintln!("Failed to write traces to {}: {err}", trace_dir.display());
+                        exit(1);
+                    });
+                    println!(
+                        "Replayed up to slot {}, traces written to {}",
+                        bank_forks.read().unwrap().working_bank().slot(),
+                        trace_dir.display()
+                    );
+                }
                 ("graph", Some(arg_matches)) => {
                     let output_file = value_t_or_exit!(arg_matches, "graph_filename", String);
                     let graph_config = GraphConfig {
@@ -0,0 +1,209 @@
+//! Tracing of the transactions replayed by the `replay-range` command
+//!
+//! Transaction status batches are streamed from the replay threads to a
+//! writer thread, which appends a JSON line per transaction to
+//! `<trace dir>/<slot>.jsonl`. A slot's file is closed once the slot has been
+//! frozen, so only the slots currently being replayed are held open.
+use {
+    crossbeam_channel::{select, unbounded, Receiver, Sender},
+    log::*,
+    serde_derive::Serialize,
+    solana_ledger::blockstore_processor::{
+        ProcessSlotCallback, TransactionStatusBatch, TransactionStatusMessage,
+        TransactionStatusSender,
+    },
+    solana_runtime::bank::Bank,
+    solana_sdk::{clock::Slot, transaction::Result as TransactionResult},
+    std::{
+        collections::{hash_map::Entry, HashMap},
+        fs::File,
+        io::{self, BufWriter, Write},
+        ops::RangeInclusive,
+        path::{Path, PathBuf},
+        sync::Arc,
+        thread::{Builder, JoinHandle},
+    },
+};
+
+#[derive(Debug, Serialize)]
+pub struct TransactionTrace {
+    pub slot: Slot,
+    pub index: usize,
+    pub signature: String,
+    pub fee: u64,
+    pub status: TransactionResult<()>,
+    pub log_messages: Option<Vec<String>>,
+    pub writable_accounts: Vec<WritableAccountTrace>,
+}
+
+#[derive(Debug, Serialize)]
+pub struct WritableAccountTrace {
+    pub pubkey: String,
+    pub pre_balance: u64,
+    pub post_balance: u64,
+}
+
+pub fn trace_file_path(trace_dir: &Path, slot: Slot) -> PathBuf {
+    trace_dir.join(format!("{slot}.jsonl"))
+}
+
+pub struct TraceWriter {
+    transaction_status_sender: TransactionStatusSender,
+    exit_sender: Sender<()>,
+    thread: JoinHandle<io::Result<()>>,
+}
+
+impl TraceWriter {
+    pub fn new(trace_dir: PathBuf, slots: RangeInclusive<Slot>) -> io::Result<Self> {
+        std::fs::create_dir_all(&trace_dir)?;
+        let (sender, receiver) = unbounded();
+        let (exit_sender, exit_receiver) = unbounded();
+        let thread = Builder::new()
+            .name("solReplayTrace".to_string())
+            .spawn(move || write_traces(&trace_dir, slots, receiver, exit_receiver))?;
+        Ok(Self {
+            transaction_status_sender: TransactionStatusSender { sender },
+            exit_sender,
+            thread,
+        })
+    }
+
+    pub fn transaction_status_sender(&self) -> TransactionStatusSender {
+        self.transaction_status_sender.clone()
+    }
+
+    /// Returns a slot callback which tells the writer that a slot is complete
+    pub fn slot_callback(&self) -> ProcessSlotCallback {
+        let sender = self.transaction_status_sender.sender.clone();
+        Arc::new(move |bank: &Bank| {
+            let _ = sender.send(TransactionStatusMessage::Freeze(bank.slot()));
+        })
+    }
+
+    /// Writes out everything sent so far and waits for the writer to exit.
+    ///
+    /// Senders may still be held elsewhere (e.g. by the scheduler pool), so
+    /// the writer is told to exit rather than waiting for the channel to be
+    /// disconnected.
+    pub fn join(self) -> io::Result<()> {
+        let _ = self.exit_sender.send(());
+        self.thread.join().unwrap()
+    }
+}
+
+fn write_traces(
+    trace_dir: &Path,
+    slots: RangeInclusive<Slot>,
+    receiver: Receiver<TransactionStatusMessage>,
+    exit_receiver: Receiver<()>,
+) -> io::Result<()> {
+    let mut files = HashMap::<Slot, BufWriter<File>>::new();
+    loop {
+        let message = select! {
+            recv(receiver) -> message => message.ok(),
+            recv(exit_receiver) -> _ => None,
+        };
+        let Some(message) = message else {
+            break;
+        };
+        handle_message(trace_dir, &slots, &mut files, message)?;
+    }
+    for message in receiver.try_iter() {
+        handle_message(trace_dir, &slots, &mut files, message)?;
+    }
+    for (slot, mut file) in files {
+        warn!("slot {slot} was not frozen, its trace may be incomplete");
+        file.flush()?;
+    }
+    Ok(())
+}
+
+fn handle_message(
+    trace_dir: &Path,
+    slots: &RangeInclusive<Slot>,
+    files: &mut HashMap<Slot, BufWriter<File>>,
+    message: TransactionStatusMessage,
+) -> io::Result<()> {
+    match message {
+        TransactionStatusMessage::Batch(batch) if slots.contains(&batch.slot) => {
+            let file = match files.entry(batch.slot) {
+                Entry::Occupied(entry) => entry.into_mut(),
+                Entry::Vacant(entry) => {
+                    let path = trace_file_path(trace_dir, batch.slot);
+                    entry.insert(BufWriter::new(File::create(path)?))
+                }
+            };
+            for trace in transaction_traces(batch) {
+                serde_json::to_writer(&mut *file, &trace)?;
+                writeln!(file)?;
+            }
+        }
+        TransactionStatusMessage::Batch(_) => (),
+        TransactionStatusMessage::Freeze(slot) => {
+            if let Some(mut file) = files.remove(&slot) {
+                file.flush()?;
+            } else if slots.contains(&slot) {
+                // Write an empty trace for slots without transactions
+                File::create(trace_file_path(trace_dir, slot))?;
+            }
+        }
+    }
+    Ok(())
+}
+
+fn transaction_traces(batch: TransactionStatusBatch) -> impl Iterator<Item = TransactionTrace> {
+    let TransactionStatusBatch {
+        slot,
+        transactions,
+        commit_results,
+        balances,
+        transaction_indexes,
+        ..
+    } = batch;
+    transactions
+        .into_iter()
+        .zip(commit_results)
+        .zip(transaction_indexes)
+        .zip(
+            balances
+                .pre_balances
+                .into_iter()
+                .zip(balances.post_balances),
+        )
+        .map(
+            move |(((tx, commit_result), index), (pre_balances, post_balances))| {
+                let message = tx.message();
+                let writable_accounts = message
+                    .account_keys()
+                    .iter()
+                    .zip(pre_balances.into_iter().zip(post_balances))
+                    .enumerate()
+                    .filter(|(i, _)| message.is_writable(*i))
+                    .map(
+                        |(_, (pubkey, (pre_balance, post_balance)))| WritableAccountTrace {
+                            pubkey: pubkey.to_string(),
+                            pre_balance,
+                            post_balance,
+                        },
+                    )
+                    .collect();
+                let (fee, status, log_messages) = match commit_result {
+                    Ok(committed_tx) => (
+                        committed_tx.fee_details.total_fee(),
+                        committed_tx.status,
+                        committed_tx.log_messages,
+                    ),
+                    Err(err) => (0, Err(err), None),
+                };
+                TransactionTrace {
+                    slot,
+                    index,
+                    signature: tx.signature().to_string(),
+                    fee,
+                    status,
+                    log_messages,
+                    writable_accounts,
+                }
+            },
+        )
+}
@@ -1,12 +1,22 @@
 use {
     assert_cmd::prelude::*,
+    solana_entry::entry::{create_ticks, next_entry_mut},
     solana_ledger::{
-        blockstore, blockstore::Blockstore, create_new_tmp_ledger_auto_delete,
-        genesis_utils::create_genesis_config, get_tmp_ledger_path_auto_delete,
+        blockstore,
+        blockstore::Blockstore,
+        create_new_tmp_ledger_auto_delete,
+        genesis_utils::{create_genesis_config, GenesisConfigInfo},
+        get_tmp_ledger_path_auto_delete,
+    },
+    solana_sdk::{
+        signature::{Keypair, Signer},
+        system_transaction,
     },
     std::{
+        fs,
         path::Path,
         process::{Command, Output},
+        sync::Arc,
     },
 };
 
@@ -87,3 +97,182 @@ fn ledger_tool_copy_test() {
         assert!(!src_slot_output.stdout.is_empty());
     }
 }
+
+#[test]
+fn ledger_tool_repair_roots_test() {
+    let genesis_config = create_genesis_config(100).genesis_config;
+    let (ledger_path, _blockhash) = create_new_tmp_ledger_auto_delete!(&genesis_config);
+    insert_test_shreds(ledger_path.path(), 10);
+
+    // Only root the ends of the chain, leaving a gap in the roots column
+    let rooted_slots = |ledger_path: &Path| {
+        let blockstore = Blockstore::open(ledger_path).unwrap();
+        blockstore
+            .rooted_slot_iterator(0)
+            .unwrap()
+            .collect::<Vec<_>>()
+    };
+    {
+        let blockstore = Blockstore::open(ledger_path.path()).unwrap();
+        blockstore.set_roots([0, 9].iter()).unwrap();
+    }
+    let repair_roots = |extra_args: &[&str]| {
+        let mut args = vec![
+            "-l",
+            ledger_path.path().to_str().unwrap(),
+            "repair-roots",
+            "--start",
+            "9",
+            "--end",
+            "1",
+        ];
+        args.extend_from_slice(extra_args);
+        run_ledger_tool(&args)
+    };
+
+    let output = repair_roots(&["--dry-run"]);
+    assert!(output.status.success());
+    assert!(String::from_utf8_lossy(&output.stdout).contains("Would mark slot 1 as root"));
+    assert_eq!(rooted_slots(ledger_path.path()), vec![0, 9]);
+
+    let output = repair_roots(&[]);
+    assert!(output.status.success());
+    assert_eq!(
+        rooted_slots(ledger_path.path()),
+        (0..=9).collect::<Vec<_>>()
+    );
+}
+
+#[test]
+fn ledger_tool_repair_roots_broken_chain_test() {
+    let genesis_config = create_genesis_config(100).genesis_config;
+    let (ledger_path, _blockhash) = create_new_tmp_ledger_auto_delete!(&genesis_config);
+    insert_test_shreds(ledger_path.path(), 5);
+
+    // Slot 7 chains to slot 6, which is missing from the ledger
+    {
+        let blockstore = Blockstore::open(ledger_path.path()).unwrap();
+        let (shreds, _) = blockstore::make_slot_entries(7, 6, 10, true);
+        blockstore.insert_shreds(shreds, None, false).unwrap();
+        blockstore.set_roots([0, 7].iter()).unwrap();
+    }
+
+    let output = run_ledger_tool(&[
+        "-l",
+        ledger_path.path().to_str().unwrap(),
+        "repair-roots",
+        "--start",
+        "7",
+        "--end",
+        "1",
+    ]);
+    assert!(!output.status.success());
+    let blockstore = Blockstore::open(ledger_path.path()).unwrap();
+    assert_eq!(
+        blockstore
+            .rooted_slot_iterator(0)
+            .unwrap()
+            .collect::<Vec<_>>(),
+        vec![0, 7]
+    );
+    assert!(!blockstore.is_root(6));
+}
+
+#[test]
+fn ledger_tool_replay_range_test() {
+    let GenesisConfigInfo {
+        mut genesis_config,
+        mint_keypair,
+        ..
+    } = create_genesis_config(1_000_000_000);
+    let hashes_per_tick = 10;
+    genesis_config.poh_config.hashes_per_tick = Some(hashes_per_tick);
+    let (ledger_path, mut last_entry_hash) = create_new_tmp_ledger_auto_delete!(&genesis_config);
+
+    // Slot 1 transfers from the mint to a new account
+    let recipient = Keypair::new();
+    let tx = system_transaction::transfer(
+        &mint_keypair,
+        &recipient.pubkey(),
+        42,
+        genesis_config.hash(),
+    );
+    let signature = tx.signatures[0];
+    let mut entries = vec![next_entry_mut(&mut last_entry_hash, 1, vec![tx])];
+    entries.push(next_entry_mut(
+        &mut last_entry_hash,
+        hashes_per_tick - 1,
+        vec![],
+    ));
+    entries.extend(create_ticks(
+        genesis_config.ticks_per_slot - 1,
+        hashes_per_tick,
+        last_entry_hash,
+    ));
+    {
+        let blockstore = Blockstore::open(ledger_path.path()).unwrap();
+        blockstore
+            .write_entries(
+                1,
+                0,
+                0,
+                genesis_config.ticks_per_slot,
+                None,
+                true,
+                &Arc::new(Keypair::new()),
+                entries,
+                0,
+            )
+            .unwrap();
+    }
+
+    let trace_dir = ledger_path.path().join("trace");
+    let output = run_ledger_tool(&[
+        "-l",
+        ledger_path.path().to_str().unwrap(),
+        "replay-range",
+        "--start",
+        "1",
+        "--end",
+        "1",
+        "--trace-output",
+        trace_dir.to_str().unwrap(),
+    ]);
+    assert!(
+        output.status.success(),
+        "{}",
+        String::from_utf8_lossy(&output.stderr)
+    );
+
+    let trace = fs::read_to_string(trace_dir.join("1.jsonl")).unwrap();
+    let traces: Vec<serde_json::Value> = trace
+        .lines()
+        .map(|line| serde_json::from_str(line).unwrap())
+        .collect();
+    assert_eq!(traces.len(), 1);
+    let trace = &traces[0];
+    assert_eq!(trace["slot"], 1);
+    assert_eq!(trace["signature"], signature.to_string());
+    assert_eq!(trace["status"], serde_json::json!({ "Ok": null }));
+    assert!(!trace["log_messages"].as_array().unwrap().is_empty());
+    let fee = trace["fee"].as_u64().unwrap();
+
+    // Both the payer and the recipient are writable
+    let writable_accounts = trace["writable_accounts"].as_array().unwrap();
+    assert_eq!(writable_accounts.len(), 2);
+    let balances = |pubkey: String| {
+        let account = writable_accounts
+            .iter()
+            .find(|account| account["pubkey"] == pubkey)
+            .unwrap();
+        (
+            account["pre_balance"].as_u64().unwrap(),
+            account["post_balance"].as_u64().unwrap(),
+        )
+    };
+    let (mint_pre_balance, mint_post_balance) = balances(mint_keypair.pubkey().to_string());
+    assert_eq!(mint_pre_balance - mint_post_balance, 42 + fee);
+    assert_eq!(balances(recipient.pubkey().to_string()), (0, 42));
+    // Slot 0 is loaded from genesis rather than replayed, so it isn't traced
+    assert!(!trace_dir.join("0.jsonl").exists());
+}
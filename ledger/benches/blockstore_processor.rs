@@ -8,7 +8,12 @@ use {
     },
     solana_feature_set::apply_cost_tracker_during_replay,
     solana_ledger::{
-        blockstore_processor::{execute_batch, TransactionBatchWithIndexes},
+        blockstore::Blockstore,
+        blockstore_processor::{
+            execute_batch, fill_blockstore_slot_with_ticks, test_process_blockstore,
+            ProcessOptions, TransactionBatchWithIndexes,
+        },
+        create_new_tmp_ledger_auto_delete,
         genesis_utils::{create_genesis_config, GenesisConfigInfo},
     },
     solana_runtime::{
@@ -198,3 +203,31 @@ fn bench_execute_batch_half_batch_disable_tx_cost_update(bencher: &mut Bencher)
 fn bench_execute_batch_full_batch_disable_tx_cost_update(bencher: &mut Bencher) {
     bench_execute_batch(bencher, 64, false);
 }
+
+#[bench]
+fn bench_process_blockstore_10k_slots(bencher: &mut Bencher) {
+    const NUM_SLOTS: u64 = 10_000;
+    let GenesisConfigInfo { genesis_config, .. } = create_genesis_config(10_000);
+    let ticks_per_slot = genesis_config.ticks_per_slot;
+    let (ledger_path, mut last_entry_hash) = create_new_tmp_ledger_auto_delete!(&genesis_config);
+    let blockstore = Blockstore::open(ledger_path.path()).unwrap();
+    for slot in 1..=NUM_SLOTS {
+        last_entry_hash = fill_blockstore_slot_with_ticks(
+            &blockstore,
+            ticks_per_slot,
+            slot,
+            slot - 1,
+            last_entry_hash,
+        );
+    }
+
+    let opts = ProcessOptions {
+        run_verification: true,
+        ..ProcessOptions::default()
+    };
+    bencher.iter(|| {
+        let (bank_forks, _leader_schedule_cache) =
+            test_process_blockstore(&genesis_config, &blockstore, &opts, Arc::default());
+        assert_eq!(bank_forks.read().unwrap().working_bank().slot(), NUM_SLOTS);
+    });
+}
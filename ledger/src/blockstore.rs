@@ -4261,12 +4261,8 @@ impl Blockstore {
     /// the ancestor of a root is also inherently a root. Returns the
     /// number of slots that were actually updated.
     ///
-    /// Arguments:
-    ///  - `start_root`: The root to start scan from, or the highest root in
-    ///    the blockstore if this value is `None`. This slot must be a root.
-    ///  - `end_slot``: The slot to stop the scan at; the scan will continue to
-    ///    the earliest slot in the Blockstore if this value is `None`.
-    ///  - `exit`: Exit early if this flag is set to `true`.
+    /// See [`Self::find_missing_roots`] for how the arguments are interpreted
+    /// and how a broken chain of parents is handled.
     pub fn scan_and_fix_roots(
         &self,
         start_root: Option<Slot>,
@@ -4281,26 +4277,9 @@ impl Blockstore {
         //  - Marked as root via Self::set_root() by this this thread
         let lowest_cleanup_slot = self.lowest_cleanup_slot.read().unwrap();
 
-        let start_root = if let Some(slot) = start_root {
-            if !self.is_root(slot) {
-                return Err(BlockstoreError::SlotNotRooted);
-            }
-            slot
-        } else {
-            self.max_root()
-        };
-        let end_slot = end_slot.unwrap_or(*lowest_cleanup_slot);
-        let ancestor_iterator =
-            AncestorIterator::new(start_root, self).take_while(|&slot| slot >= end_slot);
-
         let mut find_missing_roots = Measure::start("find_missing_roots");
-        let mut roots_to_fix = vec![];
-        for slot in ancestor_iterator.filter(|slot| !self.is_root(*slot)) {
-            if exit.load(Ordering::Relaxed) {
-                return Ok(0);
-            }
-            roots_to_fix.push(slot);
-        }
+        let roots_to_fix =
+            self.do_find_missing_roots(start_root, end_slot, *lowest_cleanup_slot, exit)?;
         find_missing_roots.stop();
         let mut fix_roots = Measure::start("fix_roots");
         if !roots_to_fix.is_empty() {
@@ -4314,7 +4293,7 @@ impl Blockstore {
                 self.set_roots(chunk.iter())?;
             }
         } else {
-            debug!("No missing roots found in range {start_root} to {end_slot}");
+            debug!("No missing roots found in range {start_root:?} to {end_slot:?}");
         }
         fix_roots.stop();
         datapoint_info!(
@@ -4330,6 +4309,73 @@ impl Blockstore {
         Ok(roots_to_fix.len())
     }
 
+    /// Find the ancestors of the supplied `start_root` that are not marked as
+    /// roots themselves, without modifying the Blockstore. Slots are returned
+    /// in descending order.
+    ///
+    /// The scan stops at the first ancestor that is an orphan, ie a slot whose
+    /// parent is unknown, as such a slot cannot be shown to chain to a root and
+    /// is never marked as one. If `end_slot` is specified, the chain of parents
+    /// must reach it intact, otherwise [`BlockstoreError::BrokenParentChain`] is
+    /// returned with the orphan slot.
+    ///
+    /// Arguments:
+    ///  - `start_root`: The root to start scan from, or the highest root in
+    ///    the blockstore if this value is `None`. This slot must be a root.
+    ///  - `end_slot``: The slot to stop the scan at; the scan will continue to
+    ///    the earliest slot in the Blockstore if this value is `None`.
+    ///  - `exit`: Exit early if this flag is set to `true`.
+    pub fn find_missing_roots(
+        &self,
+        start_root: Option<Slot>,
+        end_slot: Option<Slot>,
+        exit: &AtomicBool,
+    ) -> Result<Vec<Slot>> {
+        let lowest_cleanup_slot = self.lowest_cleanup_slot.read().unwrap();
+        self.do_find_missing_roots(start_root, end_slot, *lowest_cleanup_slot, exit)
+    }
+
+    fn do_find_missing_roots(
+        &self,
+        start_root: Option<Slot>,
+        end_slot: Option<Slot>,
+        lowest_cleanup_slot: Slot,
+        exit: &AtomicBool,
+    ) -> Result<Vec<Slot>> {
+        let start_root = if let Some(slot) = start_root {
+            if !self.is_root(slot) {
+                return Err(BlockstoreError::SlotNotRooted);
+            }
+            slot
+        } else {
+            self.max_root()
+        };
+        let require_intact_chain = end_slot.is_some();
+        let end_slot = end_slot.unwrap_or(lowest_cleanup_slot);
+
+        let mut roots_to_fix = vec![];
+        for slot in AncestorIterator::new(start_root, self).take_while(|&slot| slot >= end_slot) {
+            if exit.load(Ordering::Relaxed) {
+                return Ok(vec![]);
+            }
+            let is_orphan = slot != 0
+                && self
+                    .meta(slot)?
+                    .map_or(true, |slot_meta| slot_meta.is_orphan());
+            if is_orphan {
+                if require_intact_chain {
+                    return Err(BlockstoreError::BrokenParentChain(slot));
+                }
+                warn!("Stopping root scan at orphan slot {slot}");
+                break;
+            }
+            if !self.is_root(slot) {
+                roots_to_fix.push(slot);
+            }
+        }
+        Ok(roots_to_fix)
+    }
+
     /// Mark a root `slot` as connected, traverse `slot`'s children and update
     /// the children's connected status if appropriate.
     ///
@@ -6622,6 +6668,60 @@ pub mod tests {
         assert_eq!(&roots, &blockstore_roots(&blockstore));
     }
 
+    #[test]
+    fn test_find_missing_roots_orphan() {
+        let ledger_path = get_tmp_ledger_path_auto_delete!();
+        let blockstore = Blockstore::open(ledger_path.path()).unwrap();
+
+        // Produce the chain 0 -> 1 -> 2 -> 3 and 5 -> 6, where the parent of
+        // slot 5 is the orphan slot 4
+        let (shreds, _) = make_many_slot_entries(0, 4, 1);
+        blockstore.insert_shreds(shreds, None, false).unwrap();
+        for (slot, parent_slot) in [(5, 4), (6, 5)] {
+            let (shreds, _) = make_slot_entries(slot, parent_slot, 1, true);
+            blockstore.insert_shreds(shreds, None, false).unwrap();
+        }
+        assert!(blockstore.meta(4).unwrap().unwrap().is_orphan());
+        blockstore.set_roots([0, 6].iter()).unwrap();
+
+        // The chain is intact down to slot 5
+        assert_eq!(
+            blockstore
+                .find_missing_roots(Some(6), Some(5), &AtomicBool::new(false))
+                .unwrap(),
+            vec![5]
+        );
+
+        // A range that includes the orphan is refused
+        assert_matches!(
+            blockstore.find_missing_roots(Some(6), Some(2), &AtomicBool::new(false)),
+            Err(BlockstoreError::BrokenParentChain(4))
+        );
+        assert_eq!(
+            blockstore
+                .rooted_slot_iterator(0)
+                .unwrap()
+                .collect::<Vec<_>>(),
+            vec![0, 6]
+        );
+
+        // Without an end slot, the scan stops short of the orphan
+        assert_eq!(
+            blockstore
+                .scan_and_fix_roots(Some(6), None, &AtomicBool::new(false))
+                .unwrap(),
+            1
+        );
+        assert!(!blockstore.is_root(4));
+        assert_eq!(
+            blockstore
+                .rooted_slot_iterator(0)
+                .unwrap()
+                .collect::<Vec<_>>(),
+            vec![0, 5, 6]
+        );
+    }
+
     #[test]
     fn test_set_and_chain_connected_on_root_and_next_slots() {
         solana_logger::setup();
@@ -159,6 +159,8 @@ pub enum BlockstoreError {
     LegacyShred(Slot, u64),
     #[error("unable to read merkle root slot {0}, index {1}")]
     MissingMerkleRoot(Slot, u64),
+    #[error("parent chain broken at orphan slot {0}")]
+    BrokenParentChain(Slot),
 }
 pub type Result<T> = std::result::Result<T, BlockstoreError>;
 
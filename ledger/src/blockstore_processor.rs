@@ -21,7 +21,8 @@ use {
     },
     solana_cost_model::cost_model::CostModel,
     solana_entry::entry::{
-        self, create_ticks, Entry, EntrySlice, EntryType, EntryVerificationStatus, VerifyRecyclers,
+        self, create_ticks, Entry, EntrySlice, EntryType, EntryVerificationStatus, PohVerifyDevice,
+        VerifyRecyclers,
     },
     solana_measure::{measure::Measure, measure_us},
     solana_metrics::datapoint_error,
@@ -61,7 +62,7 @@ use {
     solana_transaction_status::token_balances::TransactionTokenBalancesSet,
     solana_vote::vote_account::VoteAccountsHashMap,
     std::{
-        collections::{HashMap, HashSet},
+        collections::{HashMap, HashSet, VecDeque},
         num::Saturating,
         ops::{Index, Range},
         path::PathBuf,
@@ -853,6 +854,8 @@ pub struct ProcessOptions {
     pub hash_overrides: Option<HashOverrides>,
     pub abort_on_invalid_block: bool,
     pub no_block_cost_limits: bool,
+    /// Where to verify the PoH of entries loaded from the blockstore
+    pub poh_verify_device: PohVerifyDevice,
 }
 
 pub fn test_process_blockstore(
@@ -1159,25 +1162,75 @@ fn confirm_full_slot(
     replay_vote_sender: Option<&ReplayVoteSender>,
     timing: &mut ExecuteTimings,
 ) -> result::Result<(), BlockstoreProcessorError> {
-    let mut confirmation_timing = ConfirmationTiming::default();
-    let skip_verification = !opts.run_verification;
-    let ignored_prioritization_fee_cache = PrioritizationFeeCache::new(0u64);
-
-    confirm_slot(
+    confirm_full_slot_inner(
         blockstore,
         bank,
         replay_tx_thread_pool,
-        &mut confirmation_timing,
+        opts,
+        recyclers,
         progress,
-        skip_verification,
         transaction_status_sender,
         entry_notification_sender,
         replay_vote_sender,
-        recyclers,
-        opts.allow_dead_slots,
-        opts.runtime_config.log_messages_bytes_limit,
-        &ignored_prioritization_fee_cache,
-    )?;
+        timing,
+        None,
+    )
+}
+
+#[allow(clippy::too_many_arguments)]
+fn confirm_full_slot_inner(
+    blockstore: &Blockstore,
+    bank: &BankWithScheduler,
+    replay_tx_thread_pool: &ThreadPool,
+    opts: &ProcessOptions,
+    recyclers: &VerifyRecyclers,
+    progress: &mut ConfirmationProgress,
+    transaction_status_sender: Option<&TransactionStatusSender>,
+    entry_notification_sender: Option<&EntryNotifierSender>,
+    replay_vote_sender: Option<&ReplayVoteSender>,
+    timing: &mut ExecuteTimings,
+    poh_verified_entries: Option<PohVerifiedSlotEntries>,
+) -> result::Result<(), BlockstoreProcessorError> {
+    let mut confirmation_timing = ConfirmationTiming::default();
+    let skip_verification = !opts.run_verification;
+    let ignored_prioritization_fee_cache = PrioritizationFeeCache::new(0u64);
+
+    match poh_verified_entries {
+        Some(PohVerifiedSlotEntries {
+            slot_entries_load_result,
+            poh_verified,
+            ..
+        }) => confirm_slot_entries(
+            bank,
+            replay_tx_thread_pool,
+            slot_entries_load_result,
+            &mut confirmation_timing,
+            progress,
+            skip_verification,
+            Some(poh_verified),
+            transaction_status_sender,
+            entry_notification_sender,
+            replay_vote_sender,
+            recyclers,
+            opts.runtime_config.log_messages_bytes_limit,
+            &ignored_prioritization_fee_cache,
+        ),
+        None => confirm_slot(
+            blockstore,
+            bank,
+            replay_tx_thread_pool,
+            &mut confirmation_timing,
+            progress,
+            skip_verification,
+            transaction_status_sender,
+            entry_notification_sender,
+            replay_vote_sender,
+            recyclers,
+            opts.allow_dead_slots,
+            opts.runtime_config.log_messages_bytes_limit,
+            &ignored_prioritization_fee_cache,
+        ),
+    }?;
 
     timing.accumulate(&confirmation_timing.batch_execute.totals);
 
@@ -1536,6 +1589,7 @@ pub fn confirm_slot(
         timing,
         progress,
         skip_verification,
+        None,
         transaction_status_sender,
         entry_notification_sender,
         replay_vote_sender,
@@ -1545,6 +1599,8 @@ pub fn confirm_slot(
     )
 }
 
+/// `poh_verified` is the result of verifying the PoH of the entries ahead of time as part of a
+/// batch, if they were.
 #[allow(clippy::too_many_arguments)]
 fn confirm_slot_entries(
     bank: &BankWithScheduler,
@@ -1553,6 +1609,7 @@ fn confirm_slot_entries(
     timing: &mut ConfirmationTiming,
     progress: &mut ConfirmationProgress,
     skip_verification: bool,
+    poh_verified: Option<bool>,
     transaction_status_sender: Option<&TransactionStatusSender>,
     entry_notification_sender: Option<&EntryNotifierSender>,
     replay_vote_sender: Option<&ReplayVoteSender>,
@@ -1633,7 +1690,15 @@ fn confirm_slot_entries(
     }
 
     let last_entry_hash = entries.last().map(|e| e.hash);
-    let verifier = if !skip_verification {
+    let verifier = if skip_verification {
+        None
+    } else if let Some(poh_verified) = poh_verified {
+        if !poh_verified {
+            warn!("Ledger proof of history failed at slot: {}", slot);
+            return Err(BlockError::InvalidEntryHash.into());
+        }
+        None
+    } else {
         datapoint_debug!("verify-batch-size", ("size", num_entries as i64, i64));
         let entry_state = entries.start_verify(
             &progress.last_entry,
@@ -1645,8 +1710,6 @@ fn confirm_slot_entries(
             return Err(BlockError::InvalidEntryHash.into());
         }
         Some(entry_state)
-    } else {
-        None
     };
 
     let verify_transaction = {
@@ -1842,6 +1905,94 @@ fn process_next_slots(
     Ok(())
 }
 
+/// Maximum number of slots whose entries are PoH verified together during startup
+const POH_VERIFY_BATCH_MAX_SLOTS: usize = 32;
+/// Stop adding slots to a PoH verification batch once it holds this many entries
+const POH_VERIFY_BATCH_MAX_ENTRIES: usize = 32 * 1024;
+
+/// The entries of a slot, loaded and PoH verified ahead of replay
+struct PohVerifiedSlotEntries {
+    /// The hash the entries were verified to chain from
+    start_hash: Hash,
+    slot_entries_load_result: (Vec<Entry>, u64, bool),
+    poh_verified: bool,
+}
+
+/// Loads the entries of `start_slot` and of its descendants, up to the batch limits, and
+/// verifies their PoH together.
+///
+/// Slots whose entries fail to load are left out, so that the error is reported when the slot
+/// is replayed.
+fn load_and_verify_poh_batch(
+    blockstore: &Blockstore,
+    start_slot: Slot,
+    start_hash: Hash,
+    replay_tx_thread_pool: &ThreadPool,
+    opts: &ProcessOptions,
+    recyclers: &VerifyRecyclers,
+) -> HashMap<Slot, PohVerifiedSlotEntries> {
+    let mut pending_slots = VecDeque::from([(start_slot, start_hash)]);
+    let mut batch = vec![];
+    let mut num_entries = 0;
+    while let Some((slot, start_hash)) = pending_slots.pop_front() {
+        if batch.len() >= POH_VERIFY_BATCH_MAX_SLOTS || num_entries >= POH_VERIFY_BATCH_MAX_ENTRIES
+        {
+            break;
+        }
+        if opts
+            .halt_at_slot
+            .is_some_and(|halt_at_slot| slot > halt_at_slot)
+        {
+            continue;
+        }
+        let Ok(slot_entries_load_result) =
+            blockstore.get_slot_entries_with_shred_info(slot, 0, opts.allow_dead_slots)
+        else {
+            continue;
+        };
+        let (entries, _num_shreds, slot_full) = &slot_entries_load_result;
+        if *slot_full {
+            let last_entry_hash = entries.last().map_or(start_hash, |entry| entry.hash);
+            if let Ok(Some(meta)) = blockstore.meta(slot) {
+                pending_slots.extend(
+                    meta.next_slots
+                        .iter()
+                        .map(|next_slot| (*next_slot, last_entry_hash)),
+                );
+            }
+        }
+        num_entries += entries.len();
+        batch.push((slot, start_hash, slot_entries_load_result));
+    }
+
+    let slices: Vec<_> = batch
+        .iter()
+        .map(|(_, start_hash, (entries, ..))| (*start_hash, &entries[..]))
+        .collect();
+    let poh_verified = entry::verify_entry_batch(
+        &slices,
+        opts.poh_verify_device,
+        replay_tx_thread_pool,
+        recyclers,
+    );
+    batch
+        .into_iter()
+        .zip(poh_verified)
+        .map(
+            |((slot, start_hash, slot_entries_load_result), poh_verified)| {
+                (
+                    slot,
+                    PohVerifiedSlotEntries {
+                        start_hash,
+                        slot_entries_load_result,
+                        poh_verified,
+                    },
+                )
+            },
+        )
+        .collect()
+}
+
 /// Starting with the root slot corresponding to `start_slot_meta`, iteratively
 /// find and process children slots from the blockstore.
 ///
@@ -1892,6 +2043,7 @@ fn load_frozen_forks(
     if Some(bank_forks.read().unwrap().root()) != opts.halt_at_slot {
         let recyclers = VerifyRecyclers::default();
         let mut all_banks = HashMap::new();
+        let mut poh_verified_slots = HashMap::new();
 
         const STATUS_REPORT_INTERVAL: Duration = Duration::from_secs(2);
         let mut last_status_report = Instant::now();
@@ -1929,10 +2081,30 @@ fn load_frozen_forks(
                 voting_us = 0;
             }
 
+            // Verify the PoH of this slot and the slots that follow it in one batch, as the
+            // entries of different slots don't depend on each other
+            let poh_verified_entries = if opts.run_verification {
+                if !poh_verified_slots.contains_key(&slot) {
+                    poh_verified_slots = load_and_verify_poh_batch(
+                        blockstore,
+                        slot,
+                        last_entry_hash,
+                        replay_tx_thread_pool,
+                        opts,
+                        &recyclers,
+                    );
+                }
+                poh_verified_slots
+                    .remove(&slot)
+                    .filter(|entries| entries.start_hash == last_entry_hash)
+            } else {
+                None
+            };
+
             let mut progress = ConfirmationProgress::new(last_entry_hash);
             let mut m = Measure::start("process_single_slot");
             let bank = bank_forks.write().unwrap().insert_from_ledger(bank);
-            if let Err(error) = process_single_slot(
+            if let Err(error) = process_single_slot_inner(
                 blockstore,
                 &bank,
                 replay_tx_thread_pool,
@@ -1944,6 +2116,7 @@ fn load_frozen_forks(
                 entry_notification_sender,
                 None,
                 timing,
+                poh_verified_entries,
             ) {
                 assert!(bank_forks.write().unwrap().remove(bank.slot()).is_some());
                 if opts.abort_on_invalid_block {
@@ -2136,11 +2309,42 @@ pub fn process_single_slot(
     entry_notification_sender: Option<&EntryNotifierSender>,
     replay_vote_sender: Option<&ReplayVoteSender>,
     timing: &mut ExecuteTimings,
+) -> result::Result<(), BlockstoreProcessorError> {
+    process_single_slot_inner(
+        blockstore,
+        bank,
+        replay_tx_thread_pool,
+        opts,
+        recyclers,
+        progress,
+        transaction_status_sender,
+        cache_block_meta_sender,
+        entry_notification_sender,
+        replay_vote_sender,
+        timing,
+        None,
+    )
+}
+
+#[allow(clippy::too_many_arguments)]
+fn process_single_slot_inner(
+    blockstore: &Blockstore,
+    bank: &BankWithScheduler,
+    replay_tx_thread_pool: &ThreadPool,
+    opts: &ProcessOptions,
+    recyclers: &VerifyRecyclers,
+    progress: &mut ConfirmationProgress,
+    transaction_status_sender: Option<&TransactionStatusSender>,
+    cache_block_meta_sender: Option<&CacheBlockMetaSender>,
+    entry_notification_sender: Option<&EntryNotifierSender>,
+    replay_vote_sender: Option<&ReplayVoteSender>,
+    timing: &mut ExecuteTimings,
+    poh_verified_entries: Option<PohVerifiedSlotEntries>,
 ) -> result::Result<(), BlockstoreProcessorError> {
     let slot = bank.slot();
     // Mark corrupt slots as dead so validators don't replay this slot and
     // see AlreadyProcessed errors later in ReplayStage
-    confirm_full_slot(
+    confirm_full_slot_inner(
         blockstore,
         bank,
         replay_tx_thread_pool,
@@ -2151,6 +2355,7 @@ pub fn process_single_slot(
         entry_notification_sender,
         replay_vote_sender,
         timing,
+        poh_verified_entries,
     )
     .and_then(|()| {
         if let Some((result, completed_timings)) = bank.wait_for_completed_scheduler() {
@@ -2889,6 +3094,66 @@ pub mod tests {
         verify_fork_infos(&bank_forks);
     }
 
+    #[test]
+    fn test_process_blockstore_with_invalid_entry_in_poh_batch() {
+        solana_logger::setup();
+
+        let GenesisConfigInfo { genesis_config, .. } = create_genesis_config(10_000);
+        let ticks_per_slot = genesis_config.ticks_per_slot;
+        let (ledger_path, blockhash) = create_new_tmp_ledger_auto_delete!(&genesis_config);
+
+        /*
+            slot 0 - slot 1 - slot 2 - slot 3 (invalid entry) - slot 4 - slot 5
+                                 \
+                                  slot 6
+        */
+        let blockstore = Blockstore::open(ledger_path.path()).unwrap();
+        let slot1_blockhash =
+            fill_blockstore_slot_with_ticks(&blockstore, ticks_per_slot, 1, 0, blockhash);
+        let slot2_blockhash =
+            fill_blockstore_slot_with_ticks(&blockstore, ticks_per_slot, 2, 1, slot1_blockhash);
+        let mut entries = create_ticks(ticks_per_slot, 0, slot2_blockhash);
+        let slot3_blockhash = entries.last().unwrap().hash;
+        entries[ticks_per_slot as usize / 2].hash = Hash::new_unique();
+        blockstore
+            .write_entries(
+                3,
+                0,
+                0,
+                ticks_per_slot,
+                Some(2),
+                true,
+                &Arc::new(Keypair::new()),
+                entries,
+                0,
+            )
+            .unwrap();
+        let slot4_blockhash =
+            fill_blockstore_slot_with_ticks(&blockstore, ticks_per_slot, 4, 3, slot3_blockhash);
+        fill_blockstore_slot_with_ticks(&blockstore, ticks_per_slot, 5, 4, slot4_blockhash);
+        fill_blockstore_slot_with_ticks(&blockstore, ticks_per_slot, 6, 2, slot2_blockhash);
+
+        let opts = ProcessOptions {
+            run_verification: true,
+            poh_verify_device: PohVerifyDevice::Cpu,
+            ..ProcessOptions::default()
+        };
+        let (bank_forks, ..) =
+            test_process_blockstore(&genesis_config, &blockstore, &opts, Arc::default());
+        let bank_forks = bank_forks.read().unwrap();
+
+        // All slots are verified in a single batch, but only the slot with the invalid entry is
+        // marked dead. Its descendants are never replayed.
+        assert_eq!(frozen_bank_slots(&bank_forks), vec![0, 1, 2, 6]);
+        assert_eq!(
+            (1..=6)
+                .filter(|slot| blockstore.is_dead(*slot))
+                .collect::<Vec<_>>(),
+            vec![3]
+        );
+        verify_fork_infos(&bank_forks);
+    }
+
     #[test]
     fn test_process_blockstore_with_dead_child() {
         solana_logger::setup();
@@ -4771,6 +5036,7 @@ pub mod tests {
             None,
             None,
             None,
+            None,
             &VerifyRecyclers::default(),
             None,
             &PrioritizationFeeCache::new(0u64),
@@ -4861,6 +5127,7 @@ pub mod tests {
             &mut timing,
             &mut progress,
             false,
+            None,
             Some(&transaction_status_sender),
             None,
             None,
@@ -4906,6 +5173,7 @@ pub mod tests {
             &mut timing,
             &mut progress,
             false,
+            None,
             Some(&transaction_status_sender),
             None,
             None,
@@ -29,10 +29,12 @@ pub fn safe_clone_config(config: &ValidatorConfig) -> ValidatorConfig {
         known_validators: config.known_validators.clone(),
         repair_validators: config.repair_validators.clone(),
         repair_whitelist: config.repair_whitelist.clone(),
+        cluster_slots_root_retention: config.cluster_slots_root_retention,
         gossip_validators: config.gossip_validators.clone(),
         accounts_hash_interval_slots: config.accounts_hash_interval_slots,
         max_genesis_archive_unpacked_size: config.max_genesis_archive_unpacked_size,
         run_verification: config.run_verification,
+        poh_verify_device: config.poh_verify_device,
         require_tower: config.require_tower,
         tower_storage: config.tower_storage.clone(),
         debug_keys: config.debug_keys.clone(),
@@ -62,6 +64,7 @@ pub fn safe_clone_config(config: &ValidatorConfig) -> ValidatorConfig {
         block_verification_method: config.block_verification_method.clone(),
         block_production_method: config.block_production_method.clone(),
         enable_block_production_forwarding: config.enable_block_production_forwarding,
+        forwarder_config: config.forwarder_config.clone(),
         generator_config: config.generator_config.clone(),
         use_snapshot_archives_at_startup: config.use_snapshot_archives_at_startup,
         wen_restart_proto_path: config.wen_restart_proto_path.clone(),
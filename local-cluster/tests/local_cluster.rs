@@ -81,6 +81,7 @@ use {
     solana_streamer::socket::SocketAddrSpace,
     solana_turbine::broadcast_stage::{
         broadcast_duplicates_run::{BroadcastDuplicatesConfig, ClusterPartition},
+        fault_injection_broadcast_run::{BroadcastFault, FaultInjectionConfig, FaultSlots},
         BroadcastStageType,
     },
     solana_vote::vote_parser,
@@ -1550,6 +1551,64 @@ fn test_fake_shreds_broadcast_leader() {
     );
 }
 
+#[test]
+#[serial]
+#[ignore]
+#[allow(unused_attributes)]
+fn test_partial_broadcast_leader() {
+    solana_logger::setup_with_default(RUST_LOG_FILTER);
+    let node_stakes = vec![300, 100, 100];
+    let (cluster, _) = test_faulty_node(
+        BroadcastStageType::BroadcastFaultInjection(FaultInjectionConfig {
+            fault: BroadcastFault::PartialBroadcast {
+                partition: ClusterPartition::Stake(100),
+                withhold_percent: 50,
+            },
+            slots: FaultSlots::Rate {
+                percent: 50,
+                seed: 0,
+            },
+        }),
+        node_stakes,
+        None,
+        None,
+    );
+    // The partition has to repair the withheld shreds to make progress.
+    cluster.check_for_new_roots(
+        16,
+        "test_partial_broadcast_leader",
+        SocketAddrSpace::Unspecified,
+    );
+}
+
+#[test]
+#[serial]
+#[ignore]
+#[allow(unused_attributes)]
+fn test_delayed_broadcast_leader() {
+    solana_logger::setup_with_default(RUST_LOG_FILTER);
+    let node_stakes = vec![300, 100];
+    let (cluster, _) = test_faulty_node(
+        BroadcastStageType::BroadcastFaultInjection(FaultInjectionConfig {
+            fault: BroadcastFault::DelayedBroadcast {
+                delay: Duration::from_millis(200),
+            },
+            slots: FaultSlots::Rate {
+                percent: 20,
+                seed: 0,
+            },
+        }),
+        node_stakes,
+        None,
+        None,
+    );
+    cluster.check_for_new_roots(
+        16,
+        "test_delayed_broadcast_leader",
+        SocketAddrSpace::Unspecified,
+    );
+}
+
 #[test]
 #[serial]
 fn test_wait_for_max_stake() {
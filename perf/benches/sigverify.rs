@@ -5,6 +5,7 @@ extern crate test;
 use {
     log::*,
     rand::{thread_rng, Rng},
+    rayon::prelude::*,
     solana_perf::{
         packet::{to_packet_batches, Packet, PacketBatch},
         recycler::Recycler,
@@ -186,3 +187,71 @@ fn bench_get_offsets(bencher: &mut Bencher) {
         let _ans = sigverify::generate_offsets(&mut batches, &recycler, false);
     })
 }
+
+// The per-packet implementation `ed25519_verify_cpu` used before it chunked
+// packets adaptively, kept as a baseline for the benchmarks below
+fn ed25519_verify_cpu_per_packet(batches: &mut [PacketBatch]) {
+    batches.par_iter_mut().flatten().for_each(|packet| {
+        if !packet.meta().discard() && !sigverify::verify_packet(packet, false) {
+            packet.meta_mut().set_discard(true);
+        }
+    });
+}
+
+fn bench_sigverify_cpu(bencher: &mut Bencher, num_packets: usize) {
+    let mut batches = gen_batches(false, LARGE_BATCH_PACKET_COUNT, num_packets);
+    bencher.iter(|| {
+        sigverify::ed25519_verify_cpu(&mut batches, false, num_packets);
+    })
+}
+
+fn bench_sigverify_cpu_per_packet(bencher: &mut Bencher, num_packets: usize) {
+    let mut batches = gen_batches(false, LARGE_BATCH_PACKET_COUNT, num_packets);
+    bencher.iter(|| {
+        ed25519_verify_cpu_per_packet(&mut batches);
+    })
+}
+
+#[bench]
+fn bench_sigverify_cpu_1(bencher: &mut Bencher) {
+    bench_sigverify_cpu(bencher, 1);
+}
+
+#[bench]
+fn bench_sigverify_cpu_per_packet_1(bencher: &mut Bencher) {
+    bench_sigverify_cpu_per_packet(bencher, 1);
+}
+
+#[bench]
+fn bench_sigverify_cpu_100(bencher: &mut Bencher) {
+    bench_sigverify_cpu(bencher, 100);
+}
+
+#[bench]
+fn bench_sigverify_cpu_per_packet_100(bencher: &mut Bencher) {
+    bench_sigverify_cpu_per_packet(bencher, 100);
+}
+
+#[bench]
+#[ignore]
+fn bench_sigverify_cpu_1k(bencher: &mut Bencher) {
+    bench_sigverify_cpu(bencher, 1_000);
+}
+
+#[bench]
+#[ignore]
+fn bench_sigverify_cpu_per_packet_1k(bencher: &mut Bencher) {
+    bench_sigverify_cpu_per_packet(bencher, 1_000);
+}
+
+#[bench]
+#[ignore]
+fn bench_sigverify_cpu_10k(bencher: &mut Bencher) {
+    bench_sigverify_cpu(bencher, 10_000);
+}
+
+#[bench]
+#[ignore]
+fn bench_sigverify_cpu_per_packet_10k(bencher: &mut Bencher) {
+    bench_sigverify_cpu_per_packet(bencher, 10_000);
+}
@@ -12,6 +12,7 @@ use {
     },
     rayon::{prelude::*, ThreadPool},
     solana_hash::Hash,
+    solana_measure::measure::Measure,
     solana_message::{MESSAGE_HEADER_LENGTH, MESSAGE_VERSION_PREFIX},
     solana_pubkey::Pubkey,
     solana_rayon_threadlimit::get_thread_count,
@@ -476,15 +477,63 @@ pub fn shrink_batches(batches: &mut Vec<PacketBatch>) {
     batches.truncate(last_valid_batch);
 }
 
+/// Returns the number of packets each rayon task verifies for a call covering
+/// `packet_count` packets. Small batches get a single chunk so they are verified
+/// on the calling thread, larger ones are split evenly across the thread pool but
+/// never into chunks smaller than `VERIFY_PACKET_CHUNK_SIZE`.
+fn verify_chunk_size(packet_count: usize) -> usize {
+    packet_count
+        .div_ceil(get_thread_count())
+        .max(VERIFY_PACKET_CHUNK_SIZE)
+}
+
+/// Verifies `packets` in place, marking every packet that fails as discarded.
+/// Returns the number of packets that verified.
+fn verify_packets(packets: &mut [&mut Packet], reject_non_vote: bool) -> usize {
+    let mut num_verified = 0;
+    for packet in packets.iter_mut() {
+        if packet.meta().discard() {
+            continue;
+        }
+        if verify_packet(packet, reject_non_vote) {
+            num_verified += 1;
+        } else {
+            packet.meta_mut().set_discard(true);
+        }
+    }
+    num_verified
+}
+
 pub fn ed25519_verify_cpu(batches: &mut [PacketBatch], reject_non_vote: bool, packet_count: usize) {
     debug!("CPU ECDSA for {}", packet_count);
-    PAR_THREAD_POOL.install(|| {
-        batches.par_iter_mut().flatten().for_each(|packet| {
-            if !packet.meta().discard() && !verify_packet(packet, reject_non_vote) {
-                packet.meta_mut().set_discard(true);
-            }
-        });
-    });
+    let mut verify_time = Measure::start("ed25519_verify_cpu");
+    // Only references are collected, the packet data itself is never copied
+    let mut packets: Vec<&mut Packet> = batches
+        .iter_mut()
+        .flat_map(|batch| batch.iter_mut())
+        .collect();
+    let chunk_size = verify_chunk_size(packets.len());
+    let num_verified = if packets.len() <= chunk_size {
+        verify_packets(&mut packets, reject_non_vote)
+    } else {
+        PAR_THREAD_POOL.install(|| {
+            packets
+                .par_chunks_mut(chunk_size)
+                .map(|chunk| verify_packets(chunk, reject_non_vote))
+                .sum()
+        })
+    };
+    verify_time.stop();
+    let verified_per_second =
+        (num_verified as u64).saturating_mul(1_000_000) / verify_time.as_us().max(1);
+    datapoint_debug!(
+        "sigverify-cpu",
+        ("packets", packets.len(), i64),
+        ("verified", num_verified, i64),
+        ("chunk_size", chunk_size, i64),
+        ("verify_us", verify_time.as_us(), i64),
+        ("verified_per_second", verified_per_second, i64),
+    );
 }
 
 pub fn ed25519_verify_disabled(batches: &mut [PacketBatch]) {
@@ -1209,6 +1258,69 @@ mod tests {
         }
     }
 
+    #[test]
+    fn test_verify_chunk_size() {
+        assert_eq!(verify_chunk_size(0), VERIFY_PACKET_CHUNK_SIZE);
+        assert_eq!(verify_chunk_size(1), VERIFY_PACKET_CHUNK_SIZE);
+        assert_eq!(
+            verify_chunk_size(VERIFY_PACKET_CHUNK_SIZE),
+            VERIFY_PACKET_CHUNK_SIZE
+        );
+        let packet_count = VERIFY_PACKET_CHUNK_SIZE * get_thread_count() * 4;
+        assert_eq!(
+            verify_chunk_size(packet_count),
+            VERIFY_PACKET_CHUNK_SIZE * 4
+        );
+        assert!(verify_chunk_size(packet_count + 1) * get_thread_count() > packet_count);
+    }
+
+    #[test]
+    fn test_verify_cpu_discards_exactly_corrupted_packets() {
+        solana_logger::setup();
+
+        let mut packet = Packet::from_data(None, test_multisig_tx()).unwrap();
+        let offsets = get_packet_offsets(&mut packet, 0, false);
+        let sig_start = offsets.sig_start as usize;
+        let sig_end = sig_start + offsets.sig_len as usize * size_of::<Signature>();
+        let pubkey_start = offsets.pubkey_start as usize;
+        let pubkey_end = pubkey_start + offsets.sig_len as usize * size_of::<Pubkey>();
+        let msg_start = offsets.msg_start as usize;
+        let msg_end = packet.meta().size;
+
+        let mut rng = thread_rng();
+        for num_packets in [1, 2, 71, VERIFY_PACKET_CHUNK_SIZE + 1, 1_000] {
+            let packets_per_batch = rng.gen_range(1..=PACKETS_PER_BATCH);
+            let mut batches =
+                to_packet_batches(&vec![test_multisig_tx(); num_packets], packets_per_batch);
+            let mut expected = vec![];
+            for packet in batches.iter_mut().flat_map(|batch| batch.iter_mut()) {
+                // Corrupt a signature, one of the signing pubkeys or the rest of
+                // the message, or leave the packet intact
+                let range = match rng.gen_range(0..4) {
+                    0 => sig_start..sig_end,
+                    1 => pubkey_start..pubkey_end,
+                    2 => msg_start..msg_end,
+                    _ => {
+                        expected.push(false);
+                        continue;
+                    }
+                };
+                let offset = rng.gen_range(range);
+                let add = rng.gen_range(1..=u8::MAX);
+                packet.buffer_mut()[offset] = packet.data(offset).unwrap().wrapping_add(add);
+                expected.push(true);
+            }
+
+            ed25519_verify_cpu(&mut batches, false, num_packets);
+            let discards: Vec<_> = batches
+                .iter()
+                .flat_map(|batch| batch.iter())
+                .map(|packet| packet.meta().discard())
+                .collect();
+            assert_eq!(discards, expected, "num_packets: {num_packets}");
+        }
+    }
+
     #[test]
     fn test_verify_fail() {
         test_verify_n(5, true);
@@ -0,0 +1,121 @@
+//! Recording of the accounts written by each transaction, for golden testing
+
+use {
+    solana_accounts_db::{
+        account_storage::meta::StoredAccountMeta,
+        accounts_update_notifier_interface::AccountsUpdateNotifierInterface,
+    },
+    solana_sdk::{
+        account::{Account, AccountSharedData},
+        clock::Slot,
+        pubkey::Pubkey,
+        signature::Signature,
+        transaction::SanitizedTransaction,
+    },
+    std::{collections::HashMap, sync::Mutex},
+};
+
+/// The state of a writable account before and after a transaction
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct AccountDelta {
+    pub pubkey: Pubkey,
+    /// `None` if the account did not exist before the transaction
+    pub before: Option<Account>,
+    pub after: Account,
+}
+
+impl AccountDelta {
+    /// Whether the transaction changed the account
+    pub fn is_changed(&self) -> bool {
+        self.before.as_ref() != Some(&self.after)
+    }
+
+    /// The change in the account's balance
+    pub fn lamports_delta(&self) -> i128 {
+        let before = self.before.as_ref().map_or(0, |account| account.lamports);
+        i128::from(self.after.lamports) - i128::from(before)
+    }
+}
+
+/// The writable accounts stored by a processed transaction, in the order they were stored
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct TransactionAccountDeltas {
+    pub signature: Signature,
+    pub slot: Slot,
+    pub accounts: Vec<AccountDelta>,
+}
+
+impl TransactionAccountDeltas {
+    /// The accounts the transaction actually changed
+    pub fn changed(&self) -> impl Iterator<Item = &AccountDelta> {
+        self.accounts.iter().filter(|delta| delta.is_changed())
+    }
+}
+
+#[derive(Debug, Default)]
+struct RecorderState {
+    /// The latest state of every account stored since genesis
+    accounts: HashMap<Pubkey, Account>,
+    transactions: Vec<TransactionAccountDeltas>,
+}
+
+/// Records account deltas from the account stores of the test bank
+///
+/// Every store is seen, so the state before a transaction is the latest state stored by
+/// genesis, the runtime, or a previous transaction.
+#[derive(Debug, Default)]
+pub(crate) struct AccountDeltaRecorder {
+    state: Mutex<RecorderState>,
+}
+
+impl AccountDeltaRecorder {
+    pub(crate) fn transactions(&self) -> Vec<TransactionAccountDeltas> {
+        self.state.lock().unwrap().transactions.clone()
+    }
+}
+
+impl AccountsUpdateNotifierInterface for AccountDeltaRecorder {
+    fn snapshot_notifications_enabled(&self) -> bool {
+        false
+    }
+
+    fn notify_account_update(
+        &self,
+        slot: Slot,
+        account: &AccountSharedData,
+        txn: &Option<&SanitizedTransaction>,
+        pubkey: &Pubkey,
+        _write_version: u64,
+    ) {
+        let after = Account::from(account.clone());
+        let mut state = self.state.lock().unwrap();
+        let before = state.accounts.insert(*pubkey, after.clone());
+        let Some(txn) = txn else {
+            return;
+        };
+        let delta = AccountDelta {
+            pubkey: *pubkey,
+            before,
+            after,
+        };
+        let signature = txn.signature();
+        // A transaction's accounts are stored together, so look for it from the most recent
+        match state
+            .transactions
+            .iter_mut()
+            .rev()
+            .find(|deltas| deltas.slot == slot && &deltas.signature == signature)
+        {
+            Some(deltas) => deltas.accounts.push(delta),
+            None => state.transactions.push(TransactionAccountDeltas {
+                signature: *signature,
+                slot,
+                accounts: vec![delta],
+            }),
+        }
+    }
+
+    fn notify_account_restore_from_snapshot(&self, _slot: Slot, _account: &StoredAccountMeta) {}
+
+    fn notify_end_of_restore_from_snapshot(&self) {}
+}
@@ -4,13 +4,17 @@
 // Export tokio for test clients
 pub use tokio;
 use {
+    account_deltas::AccountDeltaRecorder,
     async_trait::async_trait,
     base64::{prelude::BASE64_STANDARD, Engine},
     chrono_humanize::{Accuracy, HumanTime, Tense},
     log::*,
-    solana_accounts_db::epoch_accounts_hash::EpochAccountsHash,
+    solana_accounts_db::{
+        accounts_update_notifier_interface::AccountsUpdateNotifier,
+        epoch_accounts_hash::EpochAccountsHash,
+    },
     solana_banks_client::start_client,
-    solana_banks_server::banks_server::start_local_server,
+    solana_banks_server::banks_server::{start_local_server_with_config, BanksServerConfig},
     solana_bpf_loader_program::serialization::serialize_parameters,
     solana_compute_budget::compute_budget::ComputeBudget,
     solana_feature_set::FEATURE_NAMES,
@@ -28,10 +32,11 @@ use {
         runtime_config::RuntimeConfig,
     },
     solana_sdk::{
-        account::{create_account_shared_data_for_test, Account, AccountSharedData},
+        account::{create_account_shared_data_for_test, from_account, Account, AccountSharedData},
         account_info::AccountInfo,
-        clock::{Epoch, Slot},
+        clock::{Clock, Epoch, Slot},
         entrypoint::{deserialize, ProgramResult, SUCCESS},
+        feature::{self, Feature},
         fee_calculator::{FeeRateGovernor, DEFAULT_TARGET_LAMPORTS_PER_SIGNATURE},
         genesis_config::{ClusterType, GenesisConfig},
         hash::Hash,
@@ -40,9 +45,11 @@ use {
         program_error::{ProgramError, UNSUPPORTED_SYSVAR},
         pubkey::Pubkey,
         rent::Rent,
-        signature::{Keypair, Signer},
+        signature::{Keypair, Signature, Signer},
+        slot_hashes::SlotHashes,
         stable_layout::stable_instruction::StableInstruction,
         sysvar::{Sysvar, SysvarId},
+        transaction::{TransactionError, VersionedTransaction},
     },
     solana_timings::ExecuteTimings,
     solana_vote_program::vote_state::{self, VoteState, VoteStateVersions},
@@ -50,6 +57,7 @@ use {
         cell::RefCell,
         collections::{HashMap, HashSet},
         convert::TryFrom,
+        fmt,
         fs::File,
         io::{self, Read},
         mem::transmute,
@@ -66,7 +74,8 @@ use {
 };
 // Export types so test clients can limit their solana crate dependencies
 pub use {
-    solana_banks_client::{BanksClient, BanksClientError},
+    account_deltas::{AccountDelta, TransactionAccountDeltas},
+    solana_banks_client::{BanksClient, BanksClientError, MaxUnitsPreflight},
     solana_banks_interface::BanksTransactionResultWithMetadata,
     solana_program_runtime::invoke_context::InvokeContext,
     solana_sbpf::{
@@ -76,6 +85,7 @@ pub use {
     solana_sdk::transaction_context::IndexOfAccount,
 };
 
+mod account_deltas;
 pub mod programs;
 
 /// Errors from the program test environment
@@ -86,8 +96,60 @@ pub enum ProgramTestError {
     InvalidWarpSlot,
 }
 
+/// Number of trailing log lines included in a [`TransactionFailure`] message
+const TRANSACTION_FAILURE_LOG_LINES: usize = 10;
+
+/// A failed transaction, along with the program which failed it and the
+/// transaction's log messages
+pub struct TransactionFailure {
+    pub error: BanksClientError,
+    /// The program invoked by the failed instruction, if an instruction failed
+    pub program_id: Option<Pubkey>,
+    pub log_messages: Vec<String>,
+}
+
+impl fmt::Display for TransactionFailure {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match (&self.error, self.program_id) {
+            (
+                BanksClientError::TransactionError(TransactionError::InstructionError(index, err)),
+                Some(program_id),
+            ) => write!(
+                f,
+                "instruction {index} of program {program_id} failed: {err}"
+            )?,
+            (error, _) => write!(f, "transaction failed: {error}")?,
+        }
+        let skipped = self
+            .log_messages
+            .len()
+            .saturating_sub(TRANSACTION_FAILURE_LOG_LINES);
+        if skipped > 0 {
+            write!(f, "\n  ... {skipped} earlier log messages")?;
+        }
+        for log in &self.log_messages[skipped..] {
+            write!(f, "\n  {log}")?;
+        }
+        Ok(())
+    }
+}
+
+// Formatted like `Display` so that unwrapping a failed transaction shows the
+// program logs rather than the raw error
+impl fmt::Debug for TransactionFailure {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        fmt::Display::fmt(self, f)
+    }
+}
+
+impl std::error::Error for TransactionFailure {}
+
 thread_local! {
     static INVOKE_CONTEXT: RefCell<Option<usize>> = const { RefCell::new(None) };
+    // A failed CPI whose error can't be returned to the caller as a
+    // ProgramError unwinds the caller, which then fails with this error, the
+    // same way the runtime aborts a program on a failed CPI.
+    static CPI_ERROR: RefCell<Option<InstructionError>> = const { RefCell::new(None) };
 }
 fn set_invoke_context(new: &mut InvokeContext) {
     INVOKE_CONTEXT.with(|invoke_context| unsafe {
@@ -150,7 +212,9 @@ pub fn invoke_builtin_function(
             })?;
         }
         Err(_panic_error) => {
-            let err = InstructionError::ProgramFailedToComplete;
+            let err = CPI_ERROR
+                .with(|cpi_error| cpi_error.take())
+                .unwrap_or(InstructionError::ProgramFailedToComplete);
             stable_log::program_failure(&log_collector, program_id, &err);
             let err: Box<dyn std::error::Error> = Box::new(err);
             Err(err)?;
@@ -333,7 +397,13 @@ impl solana_sdk::program_stubs::SyscallStubs for SyscallStubs {
                 &mut compute_units_consumed,
                 &mut ExecuteTimings::default(),
             )
-            .map_err(|err| ProgramError::try_from(err).unwrap_or_else(|err| panic!("{}", err)))?;
+            .map_err(|err| {
+                ProgramError::try_from(err).unwrap_or_else(|err| {
+                    let message = err.to_string();
+                    CPI_ERROR.with(|cpi_error| cpi_error.replace(Some(err)));
+                    std::panic::resume_unwind(Box::new(message))
+                })
+            })?;
 
         // Copy invoke_context accounts modifications into caller's account_info
         let transaction_context = &invoke_context.transaction_context;
@@ -472,14 +542,30 @@ pub fn read_file<P: AsRef<Path>>(path: P) -> Vec<u8> {
     file_data
 }
 
+fn warn_if_not_rent_exempt(name: &str, lamports: u64) {
+    let minimum_balance = Rent::default().minimum_balance(0);
+    if lamports < minimum_balance {
+        warn!(
+            "{name} balance of {lamports} lamports is below the rent-exempt minimum of \
+             {minimum_balance} lamports"
+        );
+    }
+}
+
 pub struct ProgramTest {
     accounts: Vec<(Pubkey, AccountSharedData)>,
     genesis_accounts: Vec<(Pubkey, AccountSharedData)>,
     builtin_programs: Vec<(Pubkey, &'static str, ProgramCacheEntry)>,
     compute_max_units: Option<u64>,
+    max_instruction_stack_depth: Option<usize>,
     prefer_bpf: bool,
     deactivate_feature_set: HashSet<Pubkey>,
     transaction_account_lock_limit: Option<usize>,
+    payer_lamports: u64,
+    sysvar_accounts: Vec<(Pubkey, AccountSharedData)>,
+    record_account_deltas: bool,
+    account_delta_recorder: Option<Arc<AccountDeltaRecorder>>,
+    max_units_per_transaction: Option<u64>,
 }
 
 impl Default for ProgramTest {
@@ -510,9 +596,15 @@ impl Default for ProgramTest {
             genesis_accounts: vec![],
             builtin_programs: vec![],
             compute_max_units: None,
+            max_instruction_stack_depth: None,
             prefer_bpf,
             deactivate_feature_set: HashSet::default(),
             transaction_account_lock_limit: None,
+            payer_lamports: sol_to_lamports(1_000_000.0),
+            sysvar_accounts: vec![],
+            record_account_deltas: false,
+            account_delta_recorder: None,
+            max_units_per_transaction: None,
         }
     }
 }
@@ -549,11 +641,63 @@ impl ProgramTest {
         self.compute_max_units = Some(compute_max_units);
     }
 
+    /// Override the default maximum instruction stack depth, the top-level
+    /// instruction plus nested CPIs. Deeper invocations fail with
+    /// `InstructionError::CallDepth`.
+    pub fn set_max_instruction_stack_depth(&mut self, max_instruction_stack_depth: usize) {
+        self.max_instruction_stack_depth = Some(max_instruction_stack_depth);
+    }
+
     /// Override the default transaction account lock limit
     pub fn set_transaction_account_lock_limit(&mut self, transaction_account_lock_limit: usize) {
         self.transaction_account_lock_limit = Some(transaction_account_lock_limit);
     }
 
+    /// Reject transactions that consume more than `max_units_per_transaction` compute units
+    ///
+    /// Transactions are simulated before they are processed to measure their units, so processing
+    /// costs twice as much. Those over the ceiling fail with
+    /// `TransactionError::WouldExceedMaxBlockCostLimit`, or with
+    /// [`BanksClientError::ComputeUnitsExceeded`] when sent with
+    /// [`BanksClient::process_transaction_with_max_units`], which can also override the ceiling
+    /// for a single transaction.
+    pub fn set_max_units_per_transaction(&mut self, max_units_per_transaction: u64) {
+        self.max_units_per_transaction = Some(max_units_per_transaction);
+    }
+
+    /// Record the state of each writable account before and after every processed transaction
+    ///
+    /// The recorded deltas are available from [`ProgramTestContext::account_deltas`].  Recording
+    /// keeps a copy of every account in the test environment, so it is off by default.
+    pub fn record_account_deltas(&mut self, record_account_deltas: bool) {
+        self.record_account_deltas = record_account_deltas;
+    }
+
+    /// Override the balance of the payer returned by `start()` and `start_with_context()`
+    ///
+    /// A balance below the rent-exempt minimum is allowed, to test how programs and the runtime
+    /// handle a payer that can't afford fees or rent.
+    pub fn set_payer_balance(&mut self, lamports: u64) {
+        warn_if_not_rent_exempt("Payer", lamports);
+        self.payer_lamports = lamports;
+    }
+
+    /// Add `count` system accounts with `lamports_each` to the test environment's genesis config,
+    /// returning their keypairs for use as additional payers
+    pub fn additional_payers(&mut self, count: usize, lamports_each: u64) -> Vec<Keypair> {
+        warn_if_not_rent_exempt("Additional payer", lamports_each);
+        (0..count)
+            .map(|_| {
+                let payer = Keypair::new();
+                self.add_genesis_account(
+                    payer.pubkey(),
+                    Account::new(lamports_each, 0, &solana_sdk::system_program::id()),
+                );
+                payer
+            })
+            .collect()
+    }
+
     /// Add an account to the test environment's genesis config.
     pub fn add_genesis_account(&mut self, address: Pubkey, account: Account) {
         self.genesis_accounts
@@ -566,6 +710,24 @@ impl ProgramTest {
             .push((address, AccountSharedData::from(account)));
     }
 
+    /// Override a sysvar account in the test environment
+    ///
+    /// The account is stored after the bank has updated its sysvars for the first slot a test
+    /// sees. Sysvars the bank manages are still overwritten when it next updates them: the
+    /// recent blockhashes sysvar whenever a new blockhash is registered, and others such as
+    /// slot hashes when the test advances to a new slot.
+    pub fn add_sysvar_account_data(&mut self, address: Pubkey, account: Account) {
+        assert!(
+            solana_sdk::sysvar::is_sysvar_id(&address),
+            "{address} is not a sysvar"
+        );
+        if address == Clock::id() || address == Rent::id() {
+            warn!("Overriding the {address} sysvar, which the bank also manages");
+        }
+        self.sysvar_accounts
+            .push((address, AccountSharedData::from(account)));
+    }
+
     /// Add an account to the test environment with the account data in the provided `filename`
     pub fn add_account_with_file_data(
         &mut self,
@@ -771,7 +933,8 @@ impl ProgramTest {
 
     /// Deactivate a runtime feature.
     ///
-    /// Note that all features are activated by default.
+    /// Note that all features are activated by default. A deactivated feature can be
+    /// activated part way through a test with [`ProgramTestContext::activate_feature`].
     pub fn deactivate_feature(&mut self, feature_id: Pubkey) {
         self.deactivate_feature_set.insert(feature_id);
     }
@@ -807,7 +970,7 @@ impl ProgramTest {
         let voting_keypair = Keypair::new();
 
         let mut genesis_config = create_genesis_config_with_leader_ex(
-            sol_to_lamports(1_000_000.0),
+            self.payer_lamports,
             &mint_keypair.pubkey(),
             &bootstrap_validator_pubkey,
             &voting_keypair.pubkey(),
@@ -843,6 +1006,9 @@ impl ProgramTest {
         debug!("Payer address: {}", mint_keypair.pubkey());
         debug!("Genesis config: {}", genesis_config);
 
+        self.account_delta_recorder = self
+            .record_account_deltas
+            .then(|| Arc::new(AccountDeltaRecorder::default()));
         let bank = Bank::new_with_paths(
             &genesis_config,
             Arc::new(RuntimeConfig {
@@ -850,6 +1016,7 @@ impl ProgramTest {
                     compute_unit_limit: max_units,
                     ..ComputeBudget::default()
                 }),
+                max_instruction_stack_depth: self.max_instruction_stack_depth,
                 transaction_account_lock_limit: self.transaction_account_lock_limit,
                 ..RuntimeConfig::default()
             }),
@@ -858,7 +1025,9 @@ impl ProgramTest {
             None,
             false,
             None,
-            None,
+            self.account_delta_recorder
+                .clone()
+                .map(|recorder| recorder as AccountsUpdateNotifier),
             None,
             Arc::default(),
             None,
@@ -890,6 +1059,9 @@ impl ProgramTest {
             bank.fill_bank_with_ticks_for_tests();
             let bank = Bank::new_from_parent(bank.clone(), bank.collector_id(), bank.slot() + 1);
             debug!("Bank slot: {}", bank.slot());
+            for (address, account) in self.sysvar_accounts.iter() {
+                bank.set_sysvar_account_for_tests(address, account);
+            }
             bank
         };
         let slot = bank.slot();
@@ -912,14 +1084,23 @@ impl ProgramTest {
         )
     }
 
+    fn banks_server_config(&self) -> BanksServerConfig {
+        BanksServerConfig {
+            max_units_per_transaction: self.max_units_per_transaction,
+            enable_advance_slot: true,
+        }
+    }
+
     pub async fn start(mut self) -> (BanksClient, Keypair, Hash) {
         let (bank_forks, block_commitment_cache, last_blockhash, gci) = self.setup_bank();
         let target_tick_duration = gci.genesis_config.poh_config.target_tick_duration;
         let target_slot_duration = target_tick_duration * gci.genesis_config.ticks_per_slot as u32;
-        let transport = start_local_server(
+        let transport = start_local_server_with_config(
             bank_forks.clone(),
             block_commitment_cache.clone(),
             target_tick_duration,
+            gci.genesis_config.hash(),
+            self.banks_server_config(),
         )
         .await;
         let banks_client = start_client(transport)
@@ -950,10 +1131,12 @@ impl ProgramTest {
     pub async fn start_with_context(mut self) -> ProgramTestContext {
         let (bank_forks, block_commitment_cache, last_blockhash, gci) = self.setup_bank();
         let target_tick_duration = gci.genesis_config.poh_config.target_tick_duration;
-        let transport = start_local_server(
+        let transport = start_local_server_with_config(
             bank_forks.clone(),
             block_commitment_cache.clone(),
             target_tick_duration,
+            gci.genesis_config.hash(),
+            self.banks_server_config(),
         )
         .await;
         let banks_client = start_client(transport)
@@ -966,6 +1149,7 @@ impl ProgramTest {
             banks_client,
             last_blockhash,
             gci,
+            self.account_delta_recorder.take(),
         )
     }
 }
@@ -974,6 +1158,13 @@ impl ProgramTest {
 pub trait ProgramTestBanksClientExt {
     /// Get a new latest blockhash, similar in spirit to RpcClient::get_latest_blockhash()
     async fn get_new_latest_blockhash(&mut self, blockhash: &Hash) -> io::Result<Hash>;
+
+    /// Process a transaction, returning the failed program and the last log
+    /// messages in the error if it fails
+    async fn process_transaction_with_logs<T: Into<VersionedTransaction> + Send>(
+        &mut self,
+        transaction: T,
+    ) -> Result<(), TransactionFailure>;
 }
 
 #[async_trait]
@@ -1002,6 +1193,39 @@ impl ProgramTestBanksClientExt for BanksClient {
             ),
         ))
     }
+
+    async fn process_transaction_with_logs<T: Into<VersionedTransaction> + Send>(
+        &mut self,
+        transaction: T,
+    ) -> Result<(), TransactionFailure> {
+        let transaction = transaction.into();
+        let message = &transaction.message;
+        let program_ids: Vec<Pubkey> = message
+            .instructions()
+            .iter()
+            .map(|instruction| *instruction.program_id(message.static_account_keys()))
+            .collect();
+        let BanksTransactionResultWithMetadata { result, metadata } = self
+            .process_transaction_with_metadata(transaction)
+            .await
+            .map_err(|error| TransactionFailure {
+                error,
+                program_id: None,
+                log_messages: vec![],
+            })?;
+        result.map_err(|err| TransactionFailure {
+            program_id: match err {
+                TransactionError::InstructionError(index, _) => {
+                    program_ids.get(usize::from(index)).copied()
+                }
+                _ => None,
+            },
+            error: BanksClientError::TransactionError(err),
+            log_messages: metadata
+                .map(|metadata| metadata.log_messages)
+                .unwrap_or_default(),
+        })
+    }
 }
 
 struct DroppableTask<T>(Arc<AtomicBool>, JoinHandle<T>);
@@ -1027,6 +1251,7 @@ pub struct ProgramTestContext {
     genesis_config: GenesisConfig,
     bank_forks: Arc<RwLock<BankForks>>,
     block_commitment_cache: Arc<RwLock<BlockCommitmentCache>>,
+    account_delta_recorder: Option<Arc<AccountDeltaRecorder>>,
     _bank_task: DroppableTask<()>,
 }
 
@@ -1037,6 +1262,7 @@ impl ProgramTestContext {
         banks_client: BanksClient,
         last_blockhash: Hash,
         genesis_config_info: GenesisConfigInfo,
+        account_delta_recorder: Option<Arc<AccountDeltaRecorder>>,
     ) -> Self {
         // Run a simulated PohService to provide the client with new blockhashes.  New blockhashes
         // are required when sending multiple otherwise identical transactions in series from a
@@ -1073,6 +1299,7 @@ impl ProgramTestContext {
             genesis_config: genesis_config_info.genesis_config,
             bank_forks,
             block_commitment_cache,
+            account_delta_recorder,
             _bank_task: bank_task,
         }
     }
@@ -1127,6 +1354,23 @@ impl ProgramTestContext {
         bank.set_sysvar_for_tests(sysvar);
     }
 
+    /// Overwrite the hash the SlotHashes sysvar records for `slot`, adding an
+    /// entry if there is none.
+    ///
+    /// This is meant for negative tests of programs which validate recent slot
+    /// hashes, e.g. against a stale hash. Like [`set_sysvar`](Self::set_sysvar),
+    /// it can create states that would not be reachable under normal conditions.
+    pub fn set_slot_hash(&self, slot: Slot, hash: Hash) {
+        let bank_forks = self.bank_forks.read().unwrap();
+        let bank = bank_forks.working_bank();
+        let mut slot_hashes = bank
+            .get_account(&SlotHashes::id())
+            .and_then(|account| from_account::<SlotHashes, _>(&account))
+            .unwrap_or_default();
+        slot_hashes.add(slot, hash);
+        bank.set_sysvar_for_tests(&slot_hashes);
+    }
+
     /// Force the working bank ahead to a new slot
     pub fn warp_to_slot(&mut self, warp_slot: Slot) -> Result<(), ProgramTestError> {
         let mut bank_forks = self.bank_forks.write().unwrap();
@@ -1218,6 +1462,25 @@ impl ProgramTestContext {
         self.warp_to_slot(warp_slot)
     }
 
+    /// Activate a runtime feature, typically one deactivated with
+    /// [`ProgramTest::deactivate_feature`].
+    ///
+    /// Features only take effect at an epoch boundary, so this stores the pending feature
+    /// account and warps to the first slot of the next epoch.
+    pub fn activate_feature(&mut self, feature_id: Pubkey) -> Result<(), ProgramTestError> {
+        let epoch = {
+            let bank_forks = self.bank_forks.read().unwrap();
+            let bank = bank_forks.working_bank();
+            let feature_account = feature::create_account(
+                &Feature::default(),
+                bank.get_minimum_balance_for_rent_exemption(Feature::size_of()),
+            );
+            bank.store_account(&feature_id, &feature_account);
+            bank.epoch()
+        };
+        self.warp_to_epoch(epoch + 1)
+    }
+
     /// warp forward one more slot and force reward interval end
     pub fn warp_forward_force_reward_interval_end(&mut self) -> Result<(), ProgramTestError> {
         let mut bank_forks = self.bank_forks.write().unwrap();
@@ -1267,6 +1530,29 @@ impl ProgramTestContext {
         Ok(blockhash)
     }
 
+    /// The account deltas of every transaction processed so far, oldest first
+    ///
+    /// Panics unless recording was enabled with [`ProgramTest::record_account_deltas`].
+    pub fn account_deltas(&self) -> Vec<TransactionAccountDeltas> {
+        self.account_delta_recorder
+            .as_ref()
+            .expect("account delta recording is not enabled")
+            .transactions()
+    }
+
+    /// The account deltas of the processed transaction with `signature`, if any
+    ///
+    /// Panics unless recording was enabled with [`ProgramTest::record_account_deltas`].
+    pub fn transaction_account_deltas(
+        &self,
+        signature: &Signature,
+    ) -> Option<TransactionAccountDeltas> {
+        self.account_deltas()
+            .into_iter()
+            .rev()
+            .find(|deltas| &deltas.signature == signature)
+    }
+
     /// record a hard fork slot in working bank; should be in the past
     pub fn register_hard_fork(&mut self, hard_fork_slot: Slot) {
         self.bank_forks
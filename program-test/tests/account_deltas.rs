@@ -0,0 +1,69 @@
+use {
+    solana_program_test::ProgramTest,
+    solana_sdk::{
+        pubkey::Pubkey, rent::Rent, signature::Signer, system_instruction, system_program,
+        transaction::Transaction,
+    },
+};
+
+#[tokio::test]
+async fn transfer_account_deltas() {
+    let mut program_test = ProgramTest::default();
+    program_test.record_account_deltas(true);
+    let context = program_test.start_with_context().await;
+
+    let recipient = Pubkey::new_unique();
+    let amount = Rent::default().minimum_balance(0);
+    let transaction = Transaction::new_signed_with_payer(
+        &[system_instruction::transfer(
+            &context.payer.pubkey(),
+            &recipient,
+            amount,
+        )],
+        Some(&context.payer.pubkey()),
+        &[&context.payer],
+        context.last_blockhash,
+    );
+    let fee = context
+        .banks_client
+        .get_fee_for_message(transaction.message().clone())
+        .await
+        .unwrap()
+        .unwrap();
+    let payer_balance = context
+        .banks_client
+        .get_balance(context.payer.pubkey())
+        .await
+        .unwrap();
+    let signature = transaction.signatures[0];
+    context
+        .banks_client
+        .process_transaction(transaction)
+        .await
+        .unwrap();
+
+    let deltas = context.transaction_account_deltas(&signature).unwrap();
+    let changed = deltas.changed().collect::<Vec<_>>();
+    assert_eq!(changed.len(), 2);
+
+    let payer_delta = changed
+        .iter()
+        .find(|delta| delta.pubkey == context.payer.pubkey())
+        .unwrap();
+    assert_eq!(payer_delta.before.as_ref().unwrap().lamports, payer_balance);
+    assert_eq!(payer_delta.after.lamports, payer_balance - amount - fee);
+    assert_eq!(payer_delta.lamports_delta(), -i128::from(amount + fee));
+    assert_eq!(
+        payer_delta.before.as_ref().unwrap().data,
+        payer_delta.after.data
+    );
+
+    let recipient_delta = changed
+        .iter()
+        .find(|delta| delta.pubkey == recipient)
+        .unwrap();
+    assert_eq!(recipient_delta.before, None);
+    assert_eq!(recipient_delta.after.lamports, amount);
+    assert_eq!(recipient_delta.after.owner, system_program::id());
+    assert_eq!(recipient_delta.lamports_delta(), i128::from(amount));
+}
@@ -1,10 +1,19 @@
 use {
     solana_program_test::ProgramTest,
+    solana_runtime::{
+        bank::Bank,
+        bank_client::AsyncBankClient,
+        genesis_utils::{create_genesis_config, GenesisConfigInfo},
+        loader_utils::load_upgradeable_program_nonblocking,
+    },
     solana_sdk::{
-        bpf_loader, feature_set,
+        bpf_loader, bpf_loader_upgradeable,
+        client::NonblockingClient,
+        feature_set,
         instruction::{AccountMeta, Instruction},
+        message::Message,
         pubkey::Pubkey,
-        signature::Signer,
+        signature::{Keypair, Signer},
         transaction::Transaction,
     },
     test_case::test_case,
@@ -90,3 +99,94 @@ async fn test_max_accounts(num_accounts: u8, deactivate_feature: bool, expect_su
             .unwrap_err();
     }
 }
+
+#[tokio::test]
+async fn test_max_accounts_activate_feature() {
+    let program_id = Pubkey::new_unique();
+
+    let mut program_test = ProgramTest::default();
+    program_test.prefer_bpf(true);
+    program_test.add_program("noop_program", program_id, None);
+    program_test.deactivate_feature(feature_set::increase_tx_account_lock_limit::id());
+
+    let mut context = program_test.start_with_context().await;
+
+    let account_metas = (0..63)
+        .map(|_| AccountMeta::new_readonly(Pubkey::new_unique(), false))
+        .collect::<Vec<_>>();
+    let instruction = Instruction::new_with_bytes(program_id, &[], account_metas);
+
+    // 65 accounts is over the limit before the feature is activated
+    let transaction = Transaction::new_signed_with_payer(
+        &[instruction.clone()],
+        Some(&context.payer.pubkey()),
+        &[&context.payer],
+        context.last_blockhash,
+    );
+    context
+        .banks_client
+        .process_transaction(transaction)
+        .await
+        .unwrap_err();
+
+    context
+        .activate_feature(feature_set::increase_tx_account_lock_limit::id())
+        .unwrap();
+
+    // ...and within it afterwards
+    let transaction = Transaction::new_signed_with_payer(
+        &[instruction],
+        Some(&context.payer.pubkey()),
+        &[&context.payer],
+        context.last_blockhash,
+    );
+    context
+        .banks_client
+        .process_transaction(transaction)
+        .await
+        .unwrap();
+}
+
+// Deploys the noop program with the upgradeable loader and invokes it once it is effective, the
+// same way through either client
+async fn deploy_and_invoke_noop_program<C: NonblockingClient>(client: &C, payer: &Keypair) {
+    let program = include_bytes!("fixtures/noop_program.so");
+    let program_id = load_upgradeable_program_nonblocking(client, payer, payer, program)
+        .await
+        .unwrap();
+    let program_account = client.get_account(&program_id).await.unwrap().unwrap();
+    assert_eq!(program_account.owner, bpf_loader_upgradeable::id());
+
+    // A unique account keeps the invocations distinct
+    let invoke = || {
+        let account_metas = vec![AccountMeta::new_readonly(Pubkey::new_unique(), false)];
+        let instruction = Instruction::new_with_bytes(program_id, &[], account_metas);
+        Message::new(&[instruction], Some(&payer.pubkey()))
+    };
+    assert!(client
+        .send_and_confirm_message(&[payer], invoke())
+        .await
+        .is_err());
+    client.advance_slot().await.unwrap();
+    client
+        .send_and_confirm_message(&[payer], invoke())
+        .await
+        .unwrap();
+}
+
+#[tokio::test]
+async fn test_load_upgradeable_program_with_banks_client() {
+    let context = ProgramTest::default().start_with_context().await;
+    deploy_and_invoke_noop_program(&context.banks_client, &context.payer).await;
+}
+
+#[tokio::test]
+async fn test_load_upgradeable_program_with_bank_client() {
+    let GenesisConfigInfo {
+        genesis_config,
+        mint_keypair,
+        ..
+    } = create_genesis_config(1_000_000_000_000);
+    let (_bank, bank_forks) = Bank::new_for_tests(&genesis_config).wrap_with_bank_forks_for_tests();
+    deploy_and_invoke_noop_program(&AsyncBankClient::new(bank_forks), &mint_keypair).await;
+}
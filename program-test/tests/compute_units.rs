@@ -1,11 +1,11 @@
 use {
-    solana_program_test::ProgramTest,
+    solana_program_test::{BanksClientError, MaxUnitsPreflight, ProgramTest},
     solana_sdk::{
         instruction::{AccountMeta, Instruction},
         signature::{Keypair, Signer},
         system_instruction,
         sysvar::rent,
-        transaction::Transaction,
+        transaction::{Transaction, TransactionError},
     },
 };
 
@@ -58,3 +58,114 @@ async fn max_compute_units() {
         .await
         .unwrap();
 }
+
+#[tokio::test]
+async fn max_units_per_transaction() {
+    let max_units = 500;
+    let mut program_test = ProgramTest::default();
+    program_test.set_max_units_per_transaction(max_units);
+    let context = program_test.start_with_context().await;
+
+    // Initializing a token-2022 mint consumes more than the ceiling
+    let token_2022_id = solana_inline_spl::token_2022::id();
+    let mint = Keypair::new();
+    let rent = context.banks_client.get_rent().await.unwrap();
+    let space = 82;
+    let transaction = Transaction::new_signed_with_payer(
+        &[
+            system_instruction::create_account(
+                &context.payer.pubkey(),
+                &mint.pubkey(),
+                rent.minimum_balance(space),
+                space as u64,
+                &token_2022_id,
+            ),
+            Instruction::new_with_bytes(
+                token_2022_id,
+                &[0; 35], // initialize mint
+                vec![
+                    AccountMeta::new(mint.pubkey(), false),
+                    AccountMeta::new_readonly(rent::id(), false),
+                ],
+            ),
+        ],
+        Some(&context.payer.pubkey()),
+        &[&context.payer, &mint],
+        context.last_blockhash,
+    );
+    let units_consumed = context
+        .banks_client
+        .simulate_transaction(transaction.clone())
+        .await
+        .unwrap()
+        .simulation_details
+        .unwrap()
+        .units_consumed;
+    assert!(units_consumed > max_units);
+
+    // Methods that can't report the units consumed still enforce the ceiling
+    let err = context
+        .banks_client
+        .process_transaction(transaction.clone())
+        .await
+        .unwrap_err();
+    assert!(matches!(
+        err,
+        BanksClientError::TransactionError(TransactionError::WouldExceedMaxBlockCostLimit)
+    ));
+    let err = context
+        .banks_client
+        .process_transaction_with_preflight(transaction.clone())
+        .await
+        .unwrap_err();
+    assert!(matches!(
+        err,
+        BanksClientError::SimulationError {
+            err: TransactionError::WouldExceedMaxBlockCostLimit,
+            ..
+        }
+    ));
+    assert_eq!(
+        context
+            .banks_client
+            .get_account(mint.pubkey())
+            .await
+            .unwrap(),
+        None
+    );
+
+    let err = context
+        .banks_client
+        .process_transaction_with_max_units(transaction.clone(), MaxUnitsPreflight::ServerDefault)
+        .await
+        .unwrap_err();
+    assert!(matches!(
+        err,
+        BanksClientError::ComputeUnitsExceeded {
+            units_consumed: actual_units_consumed,
+            max_units: actual_max_units,
+        } if actual_units_consumed == units_consumed && actual_max_units == max_units
+    ));
+    // Rejected transactions are not committed
+    assert_eq!(
+        context
+            .banks_client
+            .get_account(mint.pubkey())
+            .await
+            .unwrap(),
+        None
+    );
+
+    // A per-transaction override takes precedence over the ceiling
+    context
+        .banks_client
+        .process_transaction_with_max_units(transaction, MaxUnitsPreflight::Disabled)
+        .await
+        .unwrap();
+    assert!(context
+        .banks_client
+        .get_account(mint.pubkey())
+        .await
+        .unwrap()
+        .is_some());
+}
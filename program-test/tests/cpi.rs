@@ -3,7 +3,7 @@ use {
     solana_sdk::{
         account_info::{next_account_info, AccountInfo},
         entrypoint::{ProgramResult, MAX_PERMITTED_DATA_INCREASE},
-        instruction::{get_stack_height, AccountMeta, Instruction},
+        instruction::{get_stack_height, AccountMeta, Instruction, InstructionError},
         msg,
         program::invoke,
         pubkey::Pubkey,
@@ -12,7 +12,7 @@ use {
         signer::keypair::Keypair,
         system_instruction, system_program,
         sysvar::Sysvar,
-        transaction::Transaction,
+        transaction::{Transaction, TransactionError},
     },
 };
 
@@ -294,3 +294,71 @@ async fn stack_height() {
         .await
         .unwrap();
 }
+
+// Invokes itself recursively as many times as the input says
+fn recursive_process_instruction(
+    program_id: &Pubkey,
+    _accounts: &[AccountInfo],
+    input: &[u8],
+) -> ProgramResult {
+    if let Some(depth) = input[0].checked_sub(1) {
+        invoke(
+            &Instruction::new_with_bytes(*program_id, &[depth], vec![]),
+            &[],
+        )?;
+    }
+    Ok(())
+}
+
+async fn process_recursive_cpi(
+    max_instruction_stack_depth: Option<usize>,
+    cpi_depths: &[u8],
+) -> Vec<Result<(), TransactionError>> {
+    let program_id = Pubkey::new_unique();
+    let mut program_test = ProgramTest::new(
+        "recursive",
+        program_id,
+        processor!(recursive_process_instruction),
+    );
+    if let Some(max_instruction_stack_depth) = max_instruction_stack_depth {
+        program_test.set_max_instruction_stack_depth(max_instruction_stack_depth);
+    }
+    let context = program_test.start_with_context().await;
+    let mut results = vec![];
+    for cpi_depth in cpi_depths {
+        let transaction = Transaction::new_signed_with_payer(
+            &[Instruction::new_with_bytes(
+                program_id,
+                &[*cpi_depth],
+                vec![],
+            )],
+            Some(&context.payer.pubkey()),
+            &[&context.payer],
+            context.last_blockhash,
+        );
+        let result = context
+            .banks_client
+            .process_transaction(transaction)
+            .await
+            .map_err(|err| err.unwrap());
+        results.push(result);
+    }
+    results
+}
+
+#[tokio::test]
+async fn max_cpi_depth() {
+    let call_depth = Err(TransactionError::InstructionError(
+        0,
+        InstructionError::CallDepth,
+    ));
+    // The default stack depth is the top-level instruction plus 4 CPIs
+    assert_eq!(
+        process_recursive_cpi(None, &[4, 5, u8::MAX]).await,
+        vec![Ok(()), call_depth.clone(), call_depth.clone()]
+    );
+    assert_eq!(
+        process_recursive_cpi(Some(8), &[7, 8]).await,
+        vec![Ok(()), call_depth]
+    );
+}
@@ -0,0 +1,53 @@
+use {
+    solana_program_test::{processor, ProgramTest, ProgramTestBanksClientExt},
+    solana_sdk::{
+        account_info::AccountInfo,
+        entrypoint::ProgramResult,
+        instruction::{Instruction, InstructionError},
+        msg,
+        program_error::ProgramError,
+        pubkey::Pubkey,
+        signature::Signer,
+        transaction::{Transaction, TransactionError},
+    },
+};
+
+fn failing_program(
+    _program_id: &Pubkey,
+    _accounts: &[AccountInfo],
+    _input: &[u8],
+) -> ProgramResult {
+    msg!("Checking the frobnicator");
+    msg!("Frobnicator is out of alignment");
+    Err(ProgramError::Custom(42))
+}
+
+#[tokio::test]
+async fn transaction_failure_includes_program_logs() {
+    let program_id = Pubkey::new_unique();
+    let program_test = ProgramTest::new("failing", program_id, processor!(failing_program));
+    let mut context = program_test.start_with_context().await;
+
+    let instruction = Instruction::new_with_bytes(program_id, &[], vec![]);
+    let transaction = Transaction::new_signed_with_payer(
+        &[instruction],
+        Some(&context.payer.pubkey()),
+        &[&context.payer],
+        context.last_blockhash,
+    );
+    let failure = context
+        .banks_client
+        .process_transaction_with_logs(transaction)
+        .await
+        .unwrap_err();
+
+    assert_eq!(
+        failure.error.unwrap(),
+        TransactionError::InstructionError(0, InstructionError::Custom(42))
+    );
+    assert_eq!(failure.program_id, Some(program_id));
+    let message = failure.to_string();
+    assert!(message.starts_with(&format!("instruction 0 of program {program_id} failed")));
+    assert!(message.contains("Program log: Frobnicator is out of alignment"));
+    assert_eq!(message, format!("{failure:?}"));
+}
@@ -0,0 +1,137 @@
+use {
+    solana_banks_client::BanksClient,
+    solana_program_test::ProgramTest,
+    solana_sdk::{
+        hash::Hash,
+        instruction::InstructionError,
+        nonce::{
+            state::{DurableNonce, Versions},
+            State,
+        },
+        pubkey::Pubkey,
+        signature::{Keypair, Signer},
+        system_instruction::{self, SystemError},
+        transaction::{Transaction, TransactionError},
+    },
+};
+
+async fn get_nonce_hash(banks_client: &BanksClient, nonce_pubkey: &Pubkey) -> Hash {
+    let account = banks_client
+        .get_account(*nonce_pubkey)
+        .await
+        .unwrap()
+        .unwrap();
+    let versions: Versions = bincode::deserialize(&account.data).unwrap();
+    match versions.state() {
+        State::Initialized(data) => data.blockhash(),
+        State::Uninitialized => panic!("nonce account is not initialized"),
+    }
+}
+
+#[tokio::test]
+async fn durable_nonce_reuse_fails() {
+    let mut context = ProgramTest::default().start_with_context().await;
+    let payer = context.payer.insecure_clone();
+
+    let nonce_keypair = Keypair::new();
+    let nonce_pubkey = nonce_keypair.pubkey();
+    let rent = context.banks_client.get_rent().await.unwrap();
+    let transaction = Transaction::new_signed_with_payer(
+        &system_instruction::create_nonce_account(
+            &payer.pubkey(),
+            &nonce_pubkey,
+            &payer.pubkey(),
+            rent.minimum_balance(State::size()),
+        ),
+        Some(&payer.pubkey()),
+        &[&payer, &nonce_keypair],
+        context.last_blockhash,
+    );
+    context
+        .banks_client
+        .process_transaction(transaction)
+        .await
+        .unwrap();
+    let nonce_hash = get_nonce_hash(&context.banks_client, &nonce_pubkey).await;
+
+    // The nonce can't be advanced until the blockhash has moved on
+    loop {
+        let blockhash = context.get_new_latest_blockhash().await.unwrap();
+        if DurableNonce::from_blockhash(&blockhash).as_hash() != &nonce_hash {
+            break;
+        }
+    }
+
+    let nonce_transfer_with = |nonce_hash: Hash, to: &Pubkey| {
+        Transaction::new_signed_with_payer(
+            &[
+                system_instruction::advance_nonce_account(&nonce_pubkey, &payer.pubkey()),
+                system_instruction::transfer(&payer.pubkey(), to, 1_000_000),
+            ],
+            Some(&payer.pubkey()),
+            &[&payer],
+            nonce_hash,
+        )
+    };
+    let nonce_transfer = |to: &Pubkey| nonce_transfer_with(nonce_hash, to);
+    let alice_pubkey = Pubkey::new_unique();
+    let bob_pubkey = Pubkey::new_unique();
+
+    context
+        .banks_client
+        .process_transaction(nonce_transfer(&alice_pubkey))
+        .await
+        .unwrap();
+    let advanced_nonce_hash = get_nonce_hash(&context.banks_client, &nonce_pubkey).await;
+    assert_ne!(advanced_nonce_hash, nonce_hash);
+
+    // Submitting a second transaction with the same nonce is rejected straight
+    // away, rather than waiting for it to expire, with an error that tells it
+    // apart from an expired blockhash
+    let used_nonce = TransactionError::InstructionError(
+        0,
+        InstructionError::Custom(SystemError::NonceUnexpectedBlockhashValue as u32),
+    );
+    let err = context
+        .banks_client
+        .process_transaction(nonce_transfer(&bob_pubkey))
+        .await
+        .unwrap_err();
+    assert_eq!(err.unwrap(), used_nonce.clone());
+    let err = context
+        .banks_client
+        .send_transaction_with_result(nonce_transfer(&bob_pubkey))
+        .await
+        .unwrap_err();
+    assert_eq!(err.unwrap(), used_nonce);
+
+    assert_eq!(
+        context
+            .banks_client
+            .get_balance(alice_pubkey)
+            .await
+            .unwrap(),
+        1_000_000
+    );
+    assert_eq!(
+        context.banks_client.get_balance(bob_pubkey).await.unwrap(),
+        0
+    );
+    assert_eq!(
+        get_nonce_hash(&context.banks_client, &nonce_pubkey).await,
+        advanced_nonce_hash
+    );
+
+    // The advanced nonce is accepted for sending once the blockhash has moved on
+    loop {
+        let blockhash = context.get_new_latest_blockhash().await.unwrap();
+        if DurableNonce::from_blockhash(&blockhash).as_hash() != &advanced_nonce_hash {
+            break;
+        }
+    }
+    context
+        .banks_client
+        .send_transaction_with_result(nonce_transfer_with(advanced_nonce_hash, &bob_pubkey))
+        .await
+        .unwrap();
+}
@@ -0,0 +1,86 @@
+use {
+    solana_program_test::ProgramTest,
+    solana_sdk::{
+        instruction::InstructionError,
+        pubkey::Pubkey,
+        rent::Rent,
+        signature::Signer,
+        system_instruction::{self, SystemError},
+        transaction::{Transaction, TransactionError},
+    },
+};
+
+#[tokio::test]
+async fn payer_cannot_afford_fee() {
+    let mut program_test = ProgramTest::default();
+    program_test.set_payer_balance(1);
+    let context = program_test.start_with_context().await;
+    assert_eq!(
+        context
+            .banks_client
+            .get_balance(context.payer.pubkey())
+            .await
+            .unwrap(),
+        1
+    );
+
+    let transaction = Transaction::new_signed_with_payer(
+        &[system_instruction::transfer(
+            &context.payer.pubkey(),
+            &Pubkey::new_unique(),
+            1,
+        )],
+        Some(&context.payer.pubkey()),
+        &[&context.payer],
+        context.last_blockhash,
+    );
+    let err = context
+        .banks_client
+        .process_transaction(transaction)
+        .await
+        .unwrap_err();
+    assert_eq!(err.unwrap(), TransactionError::InsufficientFundsForFee);
+}
+
+#[tokio::test]
+async fn additional_payer_insufficient_funds() {
+    let mut program_test = ProgramTest::default();
+    let lamports = Rent::default().minimum_balance(0) + 1_000_000;
+    let payers = program_test.additional_payers(2, lamports);
+    assert_eq!(payers.len(), 2);
+    let context = program_test.start_with_context().await;
+    for payer in &payers {
+        assert_eq!(
+            context
+                .banks_client
+                .get_balance(payer.pubkey())
+                .await
+                .unwrap(),
+            lamports
+        );
+    }
+
+    let payer = &payers[0];
+    let transaction = Transaction::new_signed_with_payer(
+        &[system_instruction::transfer(
+            &payer.pubkey(),
+            &Pubkey::new_unique(),
+            lamports,
+        )],
+        Some(&payer.pubkey()),
+        &[payer],
+        context.last_blockhash,
+    );
+    let err = context
+        .banks_client
+        .process_transaction(transaction)
+        .await
+        .unwrap_err();
+    assert_eq!(
+        err.unwrap(),
+        TransactionError::InstructionError(
+            0,
+            InstructionError::Custom(SystemError::ResultWithNegativeLamports as u32)
+        )
+    );
+}
@@ -1,9 +1,23 @@
 use {
     solana_program_test::{processor, ProgramTest},
     solana_sdk::{
-        account_info::AccountInfo, clock::Clock, entrypoint::ProgramResult,
-        epoch_rewards::EpochRewards, epoch_schedule::EpochSchedule, instruction::Instruction, msg,
-        pubkey::Pubkey, rent::Rent, signature::Signer, sysvar::Sysvar, transaction::Transaction,
+        account::Account,
+        account_info::AccountInfo,
+        clock::Clock,
+        entrypoint::ProgramResult,
+        epoch_rewards::EpochRewards,
+        epoch_schedule::EpochSchedule,
+        hash::Hash,
+        instruction::{AccountMeta, Instruction, InstructionError},
+        msg,
+        program_error::ProgramError,
+        pubkey::Pubkey,
+        rent::Rent,
+        signature::Signer,
+        slot_hashes::SlotHashes,
+        slot_history::{self, SlotHistory},
+        sysvar::{self, Sysvar},
+        transaction::{Transaction, TransactionError},
     },
 };
 
@@ -128,3 +142,176 @@ async fn get_epoch_rewards_sysvar() {
         .await
         .unwrap();
 }
+
+#[tokio::test]
+async fn sysvar_helpers_match_genesis() {
+    let context = ProgramTest::default().start_with_context().await;
+    let genesis_config = context.genesis_config();
+
+    let rent = context.banks_client.get_rent().await.unwrap();
+    assert_eq!(rent, genesis_config.rent);
+
+    let epoch_schedule = context.banks_client.get_epoch_schedule().await.unwrap();
+    assert_eq!(epoch_schedule, genesis_config.epoch_schedule);
+    assert_eq!(
+        context
+            .banks_client
+            .get_sysvar::<EpochSchedule>()
+            .await
+            .unwrap(),
+        epoch_schedule
+    );
+}
+
+// SlotHashes is too large for `Sysvar::from_account_info`, which always fails for
+// it, but processors run natively here and can deserialize the whole account
+fn slot_hashes_from_account_info(account_info: &AccountInfo) -> Result<SlotHashes, ProgramError> {
+    bincode::deserialize(&account_info.data.borrow()).map_err(|_| ProgramError::InvalidAccountData)
+}
+
+fn slot_hashes_reader_process_instruction(
+    _program_id: &Pubkey,
+    accounts: &[AccountInfo],
+    input: &[u8],
+) -> ProgramResult {
+    let slot_hashes = slot_hashes_from_account_info(&accounts[0])?;
+    assert_eq!(slot_hashes.len(), 1);
+    assert_eq!(slot_hashes.get(&7).unwrap().as_ref(), input);
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn add_sysvar_account_data() {
+    let program_id = Pubkey::new_unique();
+    let mut program_test = ProgramTest::new(
+        "slot_hashes_reader",
+        program_id,
+        processor!(slot_hashes_reader_process_instruction),
+    );
+    let hash = Hash::new_unique();
+    let slot_hashes = SlotHashes::new(&[(7, hash)]);
+    program_test.add_sysvar_account_data(
+        sysvar::slot_hashes::id(),
+        Account {
+            lamports: 1,
+            data: bincode::serialize(&slot_hashes).unwrap(),
+            owner: sysvar::id(),
+            executable: false,
+            rent_epoch: 0,
+        },
+    );
+
+    let context = program_test.start_with_context().await;
+    let instruction = Instruction::new_with_bytes(
+        program_id,
+        hash.as_ref(),
+        vec![AccountMeta::new_readonly(sysvar::slot_hashes::id(), false)],
+    );
+    let transaction = Transaction::new_signed_with_payer(
+        &[instruction],
+        Some(&context.payer.pubkey()),
+        &[&context.payer],
+        context.last_blockhash,
+    );
+    context
+        .banks_client
+        .process_transaction(transaction)
+        .await
+        .unwrap();
+}
+
+#[test]
+#[should_panic(expected = "is not a sysvar")]
+fn add_sysvar_account_data_rejects_non_sysvar() {
+    ProgramTest::default().add_sysvar_account_data(Pubkey::new_unique(), Account::default());
+}
+
+// Checks that the SlotHashes sysvar records the hash in the instruction data
+// for the slot in the instruction data
+fn slot_hash_checker_process_instruction(
+    _program_id: &Pubkey,
+    accounts: &[AccountInfo],
+    input: &[u8],
+) -> ProgramResult {
+    let (slot, hash) = input.split_at(8);
+    let slot = u64::from_le_bytes(slot.try_into().unwrap());
+    let slot_hashes = slot_hashes_from_account_info(&accounts[0])?;
+    if slot_hashes.get(&slot).map(AsRef::as_ref) != Some(hash) {
+        msg!("slot hash mismatch for slot {}", slot);
+        return Err(ProgramError::InvalidArgument);
+    }
+    Ok(())
+}
+
+#[tokio::test]
+async fn slot_hashes_after_warp() {
+    let program_id = Pubkey::new_unique();
+    let program_test = ProgramTest::new(
+        "slot_hash_checker",
+        program_id,
+        processor!(slot_hash_checker_process_instruction),
+    );
+    let mut context = program_test.start_with_context().await;
+    let warp_slot = 42;
+    context.warp_to_slot(warp_slot).unwrap();
+
+    // As in the runtime, the bank at the warp slot records the hash of its
+    // parent, and the slots skipped by the warp are missing from the history
+    let slot_hashes = context
+        .banks_client
+        .get_sysvar::<SlotHashes>()
+        .await
+        .unwrap();
+    let (slot, hash) = slot_hashes[0];
+    assert_eq!(slot, warp_slot - 1);
+    let slot_history = context
+        .banks_client
+        .get_sysvar::<SlotHistory>()
+        .await
+        .unwrap();
+    assert_eq!(
+        slot_history.check(warp_slot - 1),
+        slot_history::Check::Found
+    );
+    assert_eq!(
+        slot_history.check(warp_slot - 2),
+        slot_history::Check::NotFound
+    );
+
+    let instruction = Instruction::new_with_bytes(
+        program_id,
+        &[&slot.to_le_bytes()[..], hash.as_ref()].concat(),
+        vec![AccountMeta::new_readonly(sysvar::slot_hashes::id(), false)],
+    );
+    let transaction = Transaction::new_signed_with_payer(
+        &[instruction.clone()],
+        Some(&context.payer.pubkey()),
+        &[&context.payer],
+        context.last_blockhash,
+    );
+    context
+        .banks_client
+        .process_transaction(transaction)
+        .await
+        .unwrap();
+
+    // The program rejects the hash once the sysvar records another one
+    context.set_slot_hash(slot, Hash::new_unique());
+    let blockhash = context.get_new_latest_blockhash().await.unwrap();
+    let transaction = Transaction::new_signed_with_payer(
+        &[instruction],
+        Some(&context.payer.pubkey()),
+        &[&context.payer],
+        blockhash,
+    );
+    let err = context
+        .banks_client
+        .process_transaction(transaction)
+        .await
+        .unwrap_err();
+    assert_eq!(
+        err.unwrap(),
+        TransactionError::InstructionError(0, InstructionError::InvalidArgument)
+    );
+}
@@ -12,6 +12,7 @@ use {
         account::Account,
         account_info::{next_account_info, AccountInfo},
         clock::Clock,
+        commitment_config::CommitmentLevel,
         entrypoint::ProgramResult,
         instruction::{AccountMeta, Instruction, InstructionError},
         program_error::ProgramError,
@@ -446,6 +447,52 @@ async fn stake_merge_immediately_after_activation() {
         .unwrap();
 }
 
+#[tokio::test]
+async fn advance_slot_by_one() {
+    let program_test = ProgramTest::default();
+    let mut context = program_test.start_with_context().await;
+
+    let mut slot = context.banks_client.get_root_slot().await.unwrap();
+    let mut block_height = context.banks_client.get_root_block_height().await.unwrap();
+    for _ in 0..3 {
+        let new_slot = context.banks_client.advance_slot().await.unwrap();
+        assert_eq!(new_slot, slot + 1);
+        assert_eq!(
+            context.banks_client.get_root_slot().await.unwrap(),
+            new_slot
+        );
+        assert_eq!(
+            context.banks_client.get_clock().await.unwrap().slot,
+            new_slot
+        );
+        // Unlike a warp, no slot is skipped
+        let new_block_height = context.banks_client.get_root_block_height().await.unwrap();
+        assert_eq!(new_block_height, block_height + 1);
+
+        // Transactions land in the new slot
+        let blockhash = context
+            .banks_client
+            .get_new_latest_blockhash(&context.last_blockhash)
+            .await
+            .unwrap();
+        let mut tx = Transaction::new_with_payer(&[], Some(&context.payer.pubkey()));
+        tx.sign(&[&context.payer], blockhash);
+        let signature = tx.signatures[0];
+        context.banks_client.process_transaction(tx).await.unwrap();
+        let status = context
+            .banks_client
+            .get_transaction_status(signature)
+            .await
+            .unwrap()
+            .unwrap();
+        assert_eq!(status.slot, new_slot);
+
+        context.last_blockhash = blockhash;
+        slot = new_slot;
+        block_height = new_block_height;
+    }
+}
+
 #[tokio::test]
 async fn get_blockhash_post_warp() {
     let program_test = ProgramTest::default();
@@ -472,3 +519,95 @@ async fn get_blockhash_post_warp() {
     tx.sign(&[&context.payer], new_blockhash);
     context.banks_client.process_transaction(tx).await.unwrap();
 }
+
+#[tokio::test]
+async fn block_height_post_warp() {
+    let program_test = ProgramTest::default();
+    let mut context = program_test.start_with_context().await;
+
+    let root_block_height = context.banks_client.get_root_block_height().await.unwrap();
+    let root_slot = context.banks_client.get_root_slot().await.unwrap();
+    assert_eq!(root_block_height, root_slot);
+
+    // Warping skips every slot in between, so only the warped banks add to
+    // the block height
+    context.warp_to_slot(10).unwrap();
+    let warp_root_block_height = context.banks_client.get_root_block_height().await.unwrap();
+    let warp_root_slot = context.banks_client.get_root_slot().await.unwrap();
+    assert_eq!(warp_root_slot, 9);
+    assert!(warp_root_block_height > root_block_height);
+    assert!(warp_root_block_height < warp_root_slot);
+
+    context.warp_to_slot(100).unwrap();
+    let root_block_height = context.banks_client.get_root_block_height().await.unwrap();
+    let root_slot = context.banks_client.get_root_slot().await.unwrap();
+    assert_eq!(root_slot, 99);
+    assert!(root_block_height > warp_root_block_height);
+    assert!(root_block_height < root_slot);
+}
+
+#[tokio::test]
+async fn get_clock_post_warp() {
+    let program_test = ProgramTest::default();
+    let mut context = program_test.start_with_context().await;
+    let epoch_schedule = context.genesis_config().epoch_schedule.clone();
+
+    let clock = context.banks_client.get_clock().await.unwrap();
+    let root_slot = context.banks_client.get_root_slot().await.unwrap();
+    assert_eq!(clock.slot, root_slot);
+    assert_eq!(clock.epoch, epoch_schedule.get_epoch(root_slot));
+
+    let warp_slot = epoch_schedule.get_first_slot_in_epoch(clock.epoch + 1) + 10;
+    context.warp_to_slot(warp_slot).unwrap();
+    let warp_clock = context.banks_client.get_clock().await.unwrap();
+    let root_slot = context.banks_client.get_root_slot().await.unwrap();
+    assert_eq!(warp_clock.slot, root_slot);
+    assert_eq!(warp_clock.epoch, clock.epoch + 1);
+    assert!(warp_clock.unix_timestamp >= clock.unix_timestamp);
+}
+
+#[tokio::test]
+async fn epoch_info_post_warp() {
+    let program_test = ProgramTest::default();
+    let mut context = program_test.start_with_context().await;
+
+    let genesis_hash = context.banks_client.get_genesis_hash().await.unwrap();
+    assert_eq!(genesis_hash, context.genesis_config().hash());
+    let epoch_schedule = context.banks_client.get_epoch_schedule().await.unwrap();
+    assert_eq!(epoch_schedule, context.genesis_config().epoch_schedule);
+
+    let epoch_info = context
+        .banks_client
+        .get_epoch_info_with_commitment(CommitmentLevel::Processed)
+        .await
+        .unwrap();
+    assert_eq!(
+        epoch_info.slots_in_epoch,
+        epoch_schedule.get_slots_in_epoch(epoch_info.epoch)
+    );
+
+    let next_epoch = epoch_info.epoch + 1;
+    let warp_slot = epoch_schedule.get_first_slot_in_epoch(next_epoch) + 1;
+    context.warp_to_slot(warp_slot).unwrap();
+
+    let epoch_info = context
+        .banks_client
+        .get_epoch_info_with_commitment(CommitmentLevel::Processed)
+        .await
+        .unwrap();
+    assert_eq!(epoch_info.epoch, next_epoch);
+    assert_eq!(epoch_info.absolute_slot, warp_slot);
+    assert_eq!(epoch_info.slot_index, 1);
+    assert_eq!(
+        epoch_info.slots_in_epoch,
+        epoch_schedule.get_slots_in_epoch(next_epoch)
+    );
+    // Warping skips every slot in between, so the block height lags the slot
+    assert!(epoch_info.block_height < epoch_info.absolute_slot);
+
+    // The genesis hash is unaffected by warping
+    assert_eq!(
+        context.banks_client.get_genesis_hash().await.unwrap(),
+        genesis_hash
+    );
+}
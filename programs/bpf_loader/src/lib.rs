@@ -25,11 +25,11 @@ use {
     solana_sbpf::{
         declare_builtin_function,
         ebpf::{self, MM_HEAP_START},
-        elf::Executable,
+        elf::{ElfError, Executable},
         error::{EbpfError, ProgramResult},
         memory_region::{AccessType, MemoryCowCallback, MemoryMapping, MemoryRegion},
         program::BuiltinProgram,
-        verifier::RequisiteVerifier,
+        verifier::{RequisiteVerifier, VerifierError},
         vm::{ContextObject, EbpfVm},
     },
     solana_sdk::{
@@ -49,6 +49,7 @@ use {
     solana_type_overrides::sync::{atomic::Ordering, Arc},
     std::{cell::RefCell, mem, rc::Rc},
     syscalls::{create_program_runtime_environment_v1, morph_into_deployment_environment_v1},
+    thiserror::Error,
 };
 
 pub const DEFAULT_LOADER_COMPUTE_UNITS: u64 = 570;
@@ -102,6 +103,45 @@ pub fn load_program_from_bytes(
     Ok(loaded_program)
 }
 
+#[derive(Debug, Error)]
+pub enum VerifyProgramError {
+    #[error("Failed to register syscalls: {0}")]
+    ProgramEnvironment(String),
+    #[error(transparent)]
+    Elf(#[from] ElfError),
+    #[error(transparent)]
+    Verifier(#[from] VerifierError),
+}
+
+/// Load and verify a program the way it is done on deployment, using the stricter deployment
+/// environment derived from `program_runtime_environment`.
+///
+/// No invoke context is needed, so programs can also be checked before they are deployed.
+pub fn verify_program(
+    programdata: &[u8],
+    program_runtime_environment: ProgramRuntimeEnvironment,
+    load_program_metrics: &mut LoadProgramMetrics,
+) -> Result<Executable<InvokeContext<'static>>, VerifyProgramError> {
+    let mut register_syscalls_time = Measure::start("register_syscalls_time");
+    let deployment_program_runtime_environment =
+        morph_into_deployment_environment_v1(program_runtime_environment)
+            .map_err(|err| VerifyProgramError::ProgramEnvironment(err.to_string()))?;
+    register_syscalls_time.stop();
+    load_program_metrics.register_syscalls_us = register_syscalls_time.as_us();
+    let mut load_elf_time = Measure::start("load_elf_time");
+    let executable = Executable::<InvokeContext>::load(
+        programdata,
+        Arc::new(deployment_program_runtime_environment),
+    )?;
+    load_elf_time.stop();
+    load_program_metrics.load_elf_us = load_elf_time.as_us();
+    let mut verify_code_time = Measure::start("verify_code_time");
+    executable.verify::<RequisiteVerifier>()?;
+    verify_code_time.stop();
+    load_program_metrics.verify_code_us = verify_code_time.as_us();
+    Ok(executable)
+}
+
 /// Directly deploy a program using a provided invoke context.
 /// This function should only be invoked from the runtime, since it does not
 /// provide any account loads or checks.
@@ -116,33 +156,23 @@ pub fn deploy_program_internal(
     deployment_slot: Slot,
 ) -> Result<LoadProgramMetrics, InstructionError> {
     let mut load_program_metrics = LoadProgramMetrics::default();
-    let mut register_syscalls_time = Measure::start("register_syscalls_time");
-    let deployment_program_runtime_environment =
-        morph_into_deployment_environment_v1(program_runtime_environment.clone()).map_err(|e| {
-            ic_logger_msg!(log_collector, "Failed to register syscalls: {}", e);
-            InstructionError::ProgramEnvironmentSetupFailure
-        })?;
-    register_syscalls_time.stop();
-    load_program_metrics.register_syscalls_us = register_syscalls_time.as_us();
     // Verify using stricter deployment_program_runtime_environment
-    let mut load_elf_time = Measure::start("load_elf_time");
-    let executable = Executable::<InvokeContext>::load(
+    verify_program(
         programdata,
-        Arc::new(deployment_program_runtime_environment),
+        program_runtime_environment.clone(),
+        &mut load_program_metrics,
     )
     .map_err(|err| {
         ic_logger_msg!(log_collector, "{}", err);
-        InstructionError::InvalidAccountData
-    })?;
-    load_elf_time.stop();
-    load_program_metrics.load_elf_us = load_elf_time.as_us();
-    let mut verify_code_time = Measure::start("verify_code_time");
-    executable.verify::<RequisiteVerifier>().map_err(|err| {
-        ic_logger_msg!(log_collector, "{}", err);
-        InstructionError::InvalidAccountData
+        match err {
+            VerifyProgramError::ProgramEnvironment(_) => {
+                InstructionError::ProgramEnvironmentSetupFailure
+            }
+            VerifyProgramError::Elf(_) | VerifyProgramError::Verifier(_) => {
+                InstructionError::InvalidAccountData
+            }
+        }
     })?;
-    verify_code_time.stop();
-    load_program_metrics.verify_code_us = verify_code_time.as_us();
     // Reload but with program_runtime_environment
     let executor = load_program_from_bytes(
         log_collector,
@@ -17,6 +17,7 @@ use {
         account_utils::StateMut,
         clock::{Clock, Epoch},
         instruction::{checked_add, InstructionError},
+        native_token::Lamports,
         pubkey::Pubkey,
         rent::Rent,
         stake::{
@@ -43,6 +44,24 @@ pub fn stake_from<T: ReadableAccount + StateMut<StakeStateV2>>(account: &T) -> O
     from(account).and_then(|state: StakeStateV2| state.stake())
 }
 
+/// Adds `lamports` to the balance of `account`, failing with `ArithmeticOverflow`
+/// if it would exceed `u64::MAX`
+fn credit_lamports(account: &mut BorrowedAccount, lamports: u64) -> Result<(), InstructionError> {
+    let balance = Lamports::new(account.get_lamports())
+        .checked_add(Lamports::new(lamports))
+        .ok_or(InstructionError::ArithmeticOverflow)?;
+    account.set_lamports(balance.get())
+}
+
+/// Removes `lamports` from the balance of `account`, failing with
+/// `ArithmeticOverflow` if the balance is smaller
+fn debit_lamports(account: &mut BorrowedAccount, lamports: u64) -> Result<(), InstructionError> {
+    let balance = Lamports::new(account.get_lamports())
+        .checked_sub(Lamports::new(lamports))
+        .ok_or(InstructionError::ArithmeticOverflow)?;
+    account.set_lamports(balance.get())
+}
+
 pub fn delegation_from(account: &AccountSharedData) -> Option<Delegation> {
     from(account).and_then(|state: StakeStateV2| state.delegation())
 }
@@ -541,11 +560,11 @@ pub fn split(
 
     let mut split =
         instruction_context.try_borrow_instruction_account(transaction_context, split_index)?;
-    split.checked_add_lamports(lamports)?;
+    credit_lamports(&mut split, lamports)?;
     drop(split);
     let mut stake_account = instruction_context
         .try_borrow_instruction_account(transaction_context, stake_account_index)?;
-    stake_account.checked_sub_lamports(lamports)?;
+    debit_lamports(&mut stake_account, lamports)?;
     Ok(())
 }
 
@@ -609,8 +628,8 @@ pub fn merge(
 
     // Drain the source stake account
     let lamports = source_account.get_lamports();
-    source_account.checked_sub_lamports(lamports)?;
-    stake_account.checked_add_lamports(lamports)?;
+    debit_lamports(&mut source_account, lamports)?;
+    credit_lamports(&mut stake_account, lamports)?;
     Ok(())
 }
 
@@ -730,8 +749,8 @@ pub fn move_stake(
         ))?;
     }
 
-    source_account.checked_sub_lamports(lamports)?;
-    destination_account.checked_add_lamports(lamports)?;
+    debit_lamports(&mut source_account, lamports)?;
+    credit_lamports(&mut destination_account, lamports)?;
 
     // this should be impossible, but because we do all our math with delegations, best to guard it
     if source_account.get_lamports() < source_meta.rent_exempt_reserve
@@ -773,22 +792,22 @@ pub fn move_lamports(
     )?;
 
     let source_free_lamports = match source_merge_kind {
-        MergeKind::FullyActive(source_meta, source_stake) => source_account
-            .get_lamports()
-            .saturating_sub(source_stake.delegation.stake)
-            .saturating_sub(source_meta.rent_exempt_reserve),
-        MergeKind::Inactive(source_meta, source_lamports, _) => {
-            source_lamports.saturating_sub(source_meta.rent_exempt_reserve)
+        MergeKind::FullyActive(source_meta, source_stake) => {
+            Lamports::new(source_account.get_lamports())
+                .saturating_sub(Lamports::new(source_stake.delegation.stake))
+                .saturating_sub(Lamports::new(source_meta.rent_exempt_reserve))
         }
+        MergeKind::Inactive(source_meta, source_lamports, _) => Lamports::new(source_lamports)
+            .saturating_sub(Lamports::new(source_meta.rent_exempt_reserve)),
         _ => return Err(InstructionError::InvalidAccountData),
     };
 
-    if lamports > source_free_lamports {
+    if Lamports::new(lamports) > source_free_lamports {
         return Err(InstructionError::InvalidArgument);
     }
 
-    source_account.checked_sub_lamports(lamports)?;
-    destination_account.checked_add_lamports(lamports)?;
+    debit_lamports(&mut source_account, lamports)?;
+    credit_lamports(&mut destination_account, lamports)?;
 
     Ok(())
 }
@@ -892,11 +911,11 @@ pub fn withdraw(
         stake_account.set_state(&StakeStateV2::Uninitialized)?;
     }
 
-    stake_account.checked_sub_lamports(lamports)?;
+    debit_lamports(&mut stake_account, lamports)?;
     drop(stake_account);
     let mut to =
         instruction_context.try_borrow_instruction_account(transaction_context, to_index)?;
-    to.checked_add_lamports(lamports)?;
+    credit_lamports(&mut to, lamports)?;
     Ok(())
 }
 
@@ -1024,11 +1043,11 @@ fn validate_split_amount(
     // EITHER at least the minimum balance, OR zero (in this case the source
     // account is transferring all lamports to new destination account, and the source
     // account will be closed)
-    let source_minimum_balance = source_meta
-        .rent_exempt_reserve
-        .saturating_add(additional_required_lamports);
-    let source_remaining_balance = source_lamports.saturating_sub(lamports);
-    if source_remaining_balance == 0 {
+    let source_minimum_balance = Lamports::new(source_meta.rent_exempt_reserve)
+        .saturating_add(Lamports::new(additional_required_lamports));
+    let source_remaining_balance =
+        Lamports::new(source_lamports).saturating_sub(Lamports::new(lamports));
+    if source_remaining_balance == Lamports::ZERO {
         // full amount is a withdrawal
         // nothing to do here
     } else if source_remaining_balance < source_minimum_balance {
@@ -1046,7 +1065,7 @@ fn validate_split_amount(
     // 1. the destination account must be prefunded with at least the rent-exempt reserve, or
     // 2. the split must consume 100% of the source
     if source_is_active
-        && source_remaining_balance != 0
+        && source_remaining_balance != Lamports::ZERO
         && destination_lamports < destination_rent_exempt_reserve
     {
         return Err(InstructionError::InsufficientFunds);
@@ -1056,16 +1075,16 @@ fn validate_split_amount(
     // This must handle:
     // 1. The destination account having a different rent exempt reserve due to data size changes
     // 2. The destination account being prefunded, which would lower the minimum split amount
-    let destination_minimum_balance =
-        destination_rent_exempt_reserve.saturating_add(additional_required_lamports);
+    let destination_minimum_balance = Lamports::new(destination_rent_exempt_reserve)
+        .saturating_add(Lamports::new(additional_required_lamports));
     let destination_balance_deficit =
-        destination_minimum_balance.saturating_sub(destination_lamports);
-    if lamports < destination_balance_deficit {
+        destination_minimum_balance.saturating_sub(Lamports::new(destination_lamports));
+    if Lamports::new(lamports) < destination_balance_deficit {
         return Err(InstructionError::InsufficientFunds);
     }
 
     Ok(ValidatedSplitInfo {
-        source_remaining_balance,
+        source_remaining_balance: source_remaining_balance.get(),
         destination_rent_exempt_reserve,
     })
 }
@@ -1454,6 +1473,7 @@ mod tests {
             pubkey::Pubkey,
             stake::state::warmup_cooldown_rate,
             sysvar::{epoch_schedule, SysvarId},
+            transaction_context::InstructionAccount,
         },
         test_case::test_case,
     };
@@ -2227,6 +2247,75 @@ mod tests {
         ));
     }
 
+    #[test_case(true, false; "epoch")]
+    #[test_case(false, true; "unix_timestamp")]
+    #[test_case(true, true; "epoch_and_unix_timestamp")]
+    fn test_meta_set_lockup(lockup_epoch: bool, lockup_unix_timestamp: bool) {
+        let clock = Clock {
+            epoch: 10,
+            unix_timestamp: 10_000,
+            ..Clock::default()
+        };
+        let custodian = Pubkey::new_unique();
+        let withdrawer = Pubkey::new_unique();
+        let stranger = Pubkey::new_unique();
+        let lockup_args = LockupArgs {
+            unix_timestamp: Some(20_000),
+            epoch: Some(20),
+            custodian: Some(Pubkey::new_unique()),
+        };
+
+        // A lockup on both an epoch and a unix timestamp remains in force until both have passed
+        for (epoch_in_force, unix_timestamp_in_force) in
+            [(true, true), (false, false), (true, false), (false, true)]
+        {
+            let lockup = Lockup {
+                unix_timestamp: if lockup_unix_timestamp {
+                    clock.unix_timestamp + i64::from(unix_timestamp_in_force)
+                } else {
+                    0
+                },
+                epoch: if lockup_epoch {
+                    clock.epoch + u64::from(epoch_in_force)
+                } else {
+                    0
+                },
+                custodian,
+            };
+            let in_force = (lockup_epoch && epoch_in_force)
+                || (lockup_unix_timestamp && unix_timestamp_in_force);
+
+            for signer in [custodian, withdrawer, stranger] {
+                // Withdrawals are gated on the same check, with the custodian exempt
+                assert_eq!(
+                    lockup.is_in_force(&clock, Some(&signer)),
+                    in_force && signer != custodian
+                );
+
+                let mut meta = Meta {
+                    authorized: Authorized::auto(&withdrawer),
+                    lockup,
+                    ..Meta::default()
+                };
+                let result = meta.set_lockup(&lockup_args, &HashSet::from([signer]), &clock);
+                if (in_force && signer == custodian) || (!in_force && signer == withdrawer) {
+                    assert_eq!(result, Ok(()));
+                    assert_eq!(
+                        meta.lockup,
+                        Lockup {
+                            unix_timestamp: 20_000,
+                            epoch: 20,
+                            custodian: lockup_args.custodian.unwrap(),
+                        }
+                    );
+                } else {
+                    assert_eq!(result, Err(InstructionError::MissingRequiredSignature));
+                    assert_eq!(meta.lockup, lockup);
+                }
+            }
+        }
+    }
+
     #[test]
     #[ignore]
     #[should_panic]
@@ -2932,6 +3021,59 @@ mod tests {
         }
     }
 
+    #[test]
+    fn test_credit_and_debit_lamports_boundaries() {
+        let transaction_context = TransactionContext::new(
+            vec![
+                (id(), AccountSharedData::default()),
+                (
+                    Pubkey::new_unique(),
+                    AccountSharedData::new(u64::MAX - 1, 0, &id()),
+                ),
+            ],
+            Rent::default(),
+            0,
+            0,
+        );
+        let mut instruction_context = InstructionContext::default();
+        instruction_context.configure(
+            &[0],
+            &[InstructionAccount {
+                index_in_transaction: 1,
+                index_in_caller: 1,
+                index_in_callee: 0,
+                is_signer: false,
+                is_writable: true,
+            }],
+            &[],
+        );
+        let mut account = instruction_context
+            .try_borrow_instruction_account(&transaction_context, 0)
+            .unwrap();
+
+        assert_eq!(
+            credit_lamports(&mut account, 2),
+            Err(InstructionError::ArithmeticOverflow)
+        );
+        assert_eq!(account.get_lamports(), u64::MAX - 1);
+        assert_eq!(credit_lamports(&mut account, 1), Ok(()));
+        assert_eq!(account.get_lamports(), u64::MAX);
+        assert_eq!(
+            credit_lamports(&mut account, 1),
+            Err(InstructionError::ArithmeticOverflow)
+        );
+
+        assert_eq!(debit_lamports(&mut account, u64::MAX - 1), Ok(()));
+        assert_eq!(account.get_lamports(), 1);
+        assert_eq!(
+            debit_lamports(&mut account, 2),
+            Err(InstructionError::ArithmeticOverflow)
+        );
+        assert_eq!(account.get_lamports(), 1);
+        assert_eq!(debit_lamports(&mut account, 1), Ok(()));
+        assert_eq!(account.get_lamports(), 0);
+    }
+
     proptest! {
         #[test]
         fn test_stake_weighted_credits_observed(
@@ -1171,6 +1171,53 @@ mod tests {
         assert_eq!(accounts[3].lamports(), withdraw_lamports);
     }
 
+    #[test]
+    fn test_vote_withdraw_near_max_balance() {
+        let (vote_pubkey, vote_account) = create_test_account();
+        let recipient_pubkey = solana_sdk::pubkey::new_rand();
+        let withdraw_lamports = vote_account.lamports();
+        let instruction_accounts = vec![
+            AccountMeta {
+                pubkey: vote_pubkey,
+                is_signer: true,
+                is_writable: true,
+            },
+            AccountMeta {
+                pubkey: recipient_pubkey,
+                is_signer: false,
+                is_writable: true,
+            },
+        ];
+        let transaction_accounts = |recipient_lamports| {
+            vec![
+                (vote_pubkey, vote_account.clone()),
+                (sysvar::clock::id(), create_default_clock_account()),
+                (sysvar::rent::id(), create_default_rent_account()),
+                (
+                    recipient_pubkey,
+                    AccountSharedData::new(recipient_lamports, 0, &Pubkey::default()),
+                ),
+            ]
+        };
+
+        // should pass, the recipient ends up with exactly u64::MAX
+        let accounts = process_instruction(
+            &serialize(&VoteInstruction::Withdraw(withdraw_lamports)).unwrap(),
+            transaction_accounts(u64::MAX - withdraw_lamports),
+            instruction_accounts.clone(),
+            Ok(()),
+        );
+        assert_eq!(accounts[3].lamports(), u64::MAX);
+
+        // should fail, the recipient's balance would overflow
+        process_instruction(
+            &serialize(&VoteInstruction::Withdraw(withdraw_lamports)).unwrap(),
+            transaction_accounts(u64::MAX - withdraw_lamports + 1),
+            instruction_accounts,
+            Err(InstructionError::ArithmeticOverflow),
+        );
+    }
+
     #[test]
     fn test_vote_state_withdraw() {
         let authorized_withdrawer_pubkey = solana_sdk::pubkey::new_rand();
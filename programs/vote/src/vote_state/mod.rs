@@ -12,6 +12,7 @@ use {
         epoch_schedule::EpochSchedule,
         hash::Hash,
         instruction::InstructionError,
+        native_token::Lamports,
         pubkey::Pubkey,
         rent::Rent,
         slot_hashes::SlotHash,
@@ -966,12 +967,10 @@ pub fn withdraw<S: std::hash::BuildHasher>(
 
     verify_authorized_signer(&vote_state.authorized_withdrawer, signers)?;
 
-    let remaining_balance = vote_account
-        .get_lamports()
-        .checked_sub(lamports)
-        .ok_or(InstructionError::InsufficientFunds)?;
+    let remaining_balance =
+        Lamports::new(vote_account.get_lamports()).try_sub(Lamports::new(lamports))?;
 
-    if remaining_balance == 0 {
+    if remaining_balance == Lamports::ZERO {
         let reject_active_vote_account_close = vote_state
             .epoch_credits
             .last()
@@ -992,16 +991,19 @@ pub fn withdraw<S: std::hash::BuildHasher>(
         }
     } else {
         let min_rent_exempt_balance = rent_sysvar.minimum_balance(vote_account.get_data().len());
-        if remaining_balance < min_rent_exempt_balance {
+        if remaining_balance.get() < min_rent_exempt_balance {
             return Err(InstructionError::InsufficientFunds);
         }
     }
 
-    vote_account.checked_sub_lamports(lamports)?;
+    vote_account.set_lamports(remaining_balance.get())?;
     drop(vote_account);
     let mut to_account = instruction_context
         .try_borrow_instruction_account(transaction_context, to_account_index)?;
-    to_account.checked_add_lamports(lamports)?;
+    let to_balance = Lamports::new(to_account.get_lamports())
+        .checked_add(Lamports::new(lamports))
+        .ok_or(InstructionError::ArithmeticOverflow)?;
+    to_account.set_lamports(to_balance.get())?;
     Ok(())
 }
 
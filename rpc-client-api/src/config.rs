@@ -164,6 +164,11 @@ pub struct RpcProgramAccountsConfig {
     pub account_config: RpcAccountInfoConfig,
     pub with_context: Option<bool>,
     pub sort_results: Option<bool>,
+    /// Maximum number of accounts to return. Paged results are always ordered by pubkey.
+    pub limit: Option<usize>,
+    /// Continue a paged request after this cursor, the pubkey of the last account returned by
+    /// the previous page
+    pub cursor: Option<String>,
 }
 
 #[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
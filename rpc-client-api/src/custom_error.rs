@@ -27,6 +27,10 @@ pub const JSON_RPC_SERVER_ERROR_MIN_CONTEXT_SLOT_NOT_REACHED: i64 = -32016;
 pub const JSON_RPC_SERVER_ERROR_EPOCH_REWARDS_PERIOD_ACTIVE: i64 = -32017;
 pub const JSON_RPC_SERVER_ERROR_SLOT_NOT_EPOCH_BOUNDARY: i64 = -32018;
 pub const JSON_RPC_SERVER_ERROR_LONG_TERM_STORAGE_UNREACHABLE: i64 = -32019;
+pub const JSON_RPC_SERVER_ERROR_SCAN_RESULT_TOO_LARGE: i64 = -32020;
+pub const JSON_RPC_SERVER_ERROR_METHOD_DISABLED: i64 = -32021;
+pub const JSON_RPC_SERVER_ERROR_METHOD_BUSY: i64 = -32022;
+pub const JSON_RPC_SERVER_ERROR_RATE_LIMITED: i64 = -32023;
 
 #[derive(Error, Debug)]
 pub enum RpcCustomError {
@@ -78,6 +82,17 @@ pub enum RpcCustomError {
     SlotNotEpochBoundary { slot: Slot },
     #[error("LongTermStorageUnreachable")]
     LongTermStorageUnreachable,
+    #[error("ScanResultTooLarge")]
+    ScanResultTooLarge {
+        max_accounts: Option<usize>,
+        max_bytes: Option<usize>,
+    },
+    #[error("MethodDisabled")]
+    MethodDisabled { method: String },
+    #[error("MethodBusy")]
+    MethodBusy { method: String },
+    #[error("RateLimited")]
+    RateLimited,
 }
 
 #[derive(Debug, Serialize, Deserialize)]
@@ -92,6 +107,13 @@ pub struct MinContextSlotNotReachedErrorData {
     pub context_slot: Slot,
 }
 
+#[derive(Debug, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ScanResultTooLargeErrorData {
+    pub max_accounts: Option<usize>,
+    pub max_bytes: Option<usize>,
+}
+
 #[derive(Debug, Serialize, Deserialize)]
 #[serde(rename_all = "camelCase")]
 pub struct EpochRewardsPeriodActiveErrorData {
@@ -252,6 +274,34 @@ impl From<RpcCustomError> for Error {
                 message: "Failed to query long-term storage; please try again".to_string(),
                 data: None,
             },
+            RpcCustomError::ScanResultTooLarge {
+                max_accounts,
+                max_bytes,
+            } => Self {
+                code: ErrorCode::ServerError(JSON_RPC_SERVER_ERROR_SCAN_RESULT_TOO_LARGE),
+                message: "Scan result exceeds the node's limits; narrow the request with filters \
+                          or page through it with limit and cursor"
+                    .to_string(),
+                data: Some(serde_json::json!(ScanResultTooLargeErrorData {
+                    max_accounts,
+                    max_bytes,
+                })),
+            },
+            RpcCustomError::MethodDisabled { method } => Self {
+                code: ErrorCode::ServerError(JSON_RPC_SERVER_ERROR_METHOD_DISABLED),
+                message: format!("Method {method} disabled by node operator"),
+                data: None,
+            },
+            RpcCustomError::MethodBusy { method } => Self {
+                code: ErrorCode::ServerError(JSON_RPC_SERVER_ERROR_METHOD_BUSY),
+                message: format!("Too many concurrent {method} requests; please try again"),
+                data: None,
+            },
+            RpcCustomError::RateLimited => Self {
+                code: ErrorCode::ServerError(JSON_RPC_SERVER_ERROR_RATE_LIMITED),
+                message: "Request rate limit exceeded; please try again".to_string(),
+                data: None,
+            },
         }
     }
 }
@@ -24,6 +24,7 @@ pub enum RpcRequest {
     GetEpochSchedule,
     GetFeeForMessage,
     GetFirstAvailableBlock,
+    GetForks,
     GetGenesisHash,
     GetHealth,
     GetIdentity,
@@ -40,6 +41,7 @@ pub enum RpcRequest {
     GetProgramAccounts,
     GetRecentPerformanceSamples,
     GetRecentPrioritizationFees,
+    GetRecentPrioritizationFeeStats,
     GetHighestSnapshotSlot,
     GetSignaturesForAddress,
     GetSignatureStatuses,
@@ -90,6 +92,7 @@ impl fmt::Display for RpcRequest {
             RpcRequest::GetEpochSchedule => "getEpochSchedule",
             RpcRequest::GetFeeForMessage => "getFeeForMessage",
             RpcRequest::GetFirstAvailableBlock => "getFirstAvailableBlock",
+            RpcRequest::GetForks => "getForks",
             RpcRequest::GetGenesisHash => "getGenesisHash",
             RpcRequest::GetHealth => "getHealth",
             RpcRequest::GetIdentity => "getIdentity",
@@ -106,6 +109,7 @@ impl fmt::Display for RpcRequest {
             RpcRequest::GetProgramAccounts => "getProgramAccounts",
             RpcRequest::GetRecentPerformanceSamples => "getRecentPerformanceSamples",
             RpcRequest::GetRecentPrioritizationFees => "getRecentPrioritizationFees",
+            RpcRequest::GetRecentPrioritizationFeeStats => "getRecentPrioritizationFeeStats",
             RpcRequest::GetHighestSnapshotSlot => "getHighestSnapshotSlot",
             RpcRequest::GetSignaturesForAddress => "getSignaturesForAddress",
             RpcRequest::GetSignatureStatuses => "getSignatureStatuses",
@@ -143,13 +147,19 @@ impl fmt::Display for RpcRequest {
 
 // Changing any of these? Update the JSON RPC docs!
 pub const MAX_GET_SIGNATURE_STATUSES_QUERY_ITEMS: usize = 256;
+// Signatures missing from the status cache may each cost a blockstore or
+// bigtable lookup when searchTransactionHistory is set
+pub const MAX_GET_SIGNATURE_STATUSES_HISTORY_QUERY_ITEMS: usize = 64;
 pub const MAX_GET_CONFIRMED_SIGNATURES_FOR_ADDRESS_SLOT_RANGE: u64 = 10_000;
 pub const MAX_GET_CONFIRMED_BLOCKS_RANGE: u64 = 500_000;
 pub const MAX_GET_CONFIRMED_SIGNATURES_FOR_ADDRESS2_LIMIT: usize = 1_000;
 pub const MAX_MULTIPLE_ACCOUNTS: usize = 100;
 pub const NUM_LARGEST_ACCOUNTS: usize = 20;
 pub const MAX_GET_PROGRAM_ACCOUNT_FILTERS: usize = 4;
+// Page size cap of getProgramAccounts when the node sets no scan result limit
+pub const MAX_GET_PROGRAM_ACCOUNTS_PAGE_LIMIT: usize = 10_000;
 pub const MAX_GET_SLOT_LEADERS: usize = 5000;
+pub const MAX_GET_RECENT_PRIORITIZATION_FEE_STATS_SLOTS: usize = 150;
 
 // Limit the length of the `epoch_credits` array for each validator in a `get_vote_accounts`
 // response
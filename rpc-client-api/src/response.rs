@@ -354,6 +354,44 @@ pub struct RpcVote {
     pub signature: String,
 }
 
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub struct RpcForks {
+    /// The node's root slot
+    pub root: Slot,
+
+    /// The slot fork choice considers heaviest
+    pub heaviest_slot: Slot,
+
+    /// The node's most recent vote, if it has voted
+    pub last_vote_slot: Option<Slot>,
+
+    /// Total stake, in lamports, of the heaviest slot's epoch
+    pub total_stake: u64,
+
+    /// The root, every slot that more than one fork builds on, and every frontier slot,
+    /// sorted by slot
+    pub forks: Vec<RpcFork>,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub struct RpcFork {
+    pub slot: Slot,
+
+    /// The nearest ancestor of this slot in `RpcForks::forks`, None for the root
+    pub parent_slot: Option<Slot>,
+
+    /// Stake, in lamports, whose latest vote is for this slot or one of its descendants
+    pub stake: u64,
+
+    /// Whether this is the heaviest slot
+    pub is_heaviest: bool,
+
+    /// Whether the node's tower is locked out from voting for this slot
+    pub is_locked_out: bool,
+}
+
 #[derive(Serialize, Deserialize, Clone, Debug, PartialEq, Eq)]
 #[serde(rename_all = "camelCase")]
 pub struct RpcVoteAccountStatus {
@@ -530,6 +568,16 @@ pub struct RpcPrioritizationFee {
     pub prioritization_fee: u64,
 }
 
+#[derive(Serialize, Deserialize, Clone, Copy, Debug, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub struct RpcPrioritizationFeeStats {
+    pub slot: Slot,
+    pub transaction_count: u64,
+    pub min_prioritization_fee: u64,
+    pub median_prioritization_fee: u64,
+    pub max_prioritization_fee: u64,
+}
+
 #[cfg(test)]
 pub mod tests {
 
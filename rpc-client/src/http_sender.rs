@@ -236,7 +236,57 @@ impl RpcSender for HttpSender {
 
 #[cfg(test)]
 mod tests {
-    use super::*;
+    use {
+        super::*,
+        crate::{nonblocking::rpc_client::RpcClient, rpc_client::RpcClientConfig},
+        assert_matches::assert_matches,
+        solana_rpc_client_api::client_error::ErrorKind,
+        std::{
+            io::{BufRead, BufReader, Read, Write},
+            net::TcpListener,
+            thread::{self, JoinHandle},
+        },
+    };
+
+    /// Serve one connection per response in `responses`, in order
+    fn mock_server(responses: Vec<String>) -> (String, JoinHandle<()>) {
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        let url = format!("http://{}", listener.local_addr().unwrap());
+        let server = thread::spawn(move || {
+            for response in responses {
+                let (mut stream, _) = listener.accept().unwrap();
+                let mut reader = BufReader::new(stream.try_clone().unwrap());
+                let mut content_length = 0;
+                loop {
+                    let mut line = String::new();
+                    reader.read_line(&mut line).unwrap();
+                    if line.trim_end().is_empty() {
+                        break;
+                    }
+                    if let Some((name, value)) = line.split_once(':') {
+                        if name.eq_ignore_ascii_case("content-length") {
+                            content_length = value.trim().parse().unwrap();
+                        }
+                    }
+                }
+                let mut body = vec![0; content_length];
+                reader.read_exact(&mut body).unwrap();
+                stream.write_all(response.as_bytes()).unwrap();
+            }
+        });
+        (url, server)
+    }
+
+    fn http_response(status: &str, headers: &str, body: &str) -> String {
+        format!(
+            "HTTP/1.1 {status}\r\n{headers}Content-Length: {}\r\nConnection: close\r\n\r\n{body}",
+            body.len()
+        )
+    }
+
+    fn too_many_requests() -> String {
+        http_response("429 Too Many Requests", "Retry-After: 0\r\n", "")
+    }
 
     #[tokio::test(flavor = "multi_thread")]
     async fn http_sender_on_tokio_multi_thread() {
@@ -253,4 +303,41 @@ mod tests {
             .send(RpcRequest::GetVersion, serde_json::Value::Null)
             .await;
     }
+
+    #[tokio::test]
+    async fn test_too_many_requests_retried() {
+        let (url, server) = mock_server(vec![
+            too_many_requests(),
+            too_many_requests(),
+            http_response("200 OK", "", r#"{"jsonrpc":"2.0","result":42,"id":0}"#),
+        ]);
+        let http_sender = HttpSender::new(url);
+        let result = http_sender
+            .send(RpcRequest::GetSlot, serde_json::Value::Null)
+            .await
+            .unwrap();
+        assert_eq!(result, serde_json::json!(42));
+        assert_eq!(http_sender.get_transport_stats().request_count, 1);
+        server.join().unwrap();
+    }
+
+    #[tokio::test]
+    async fn test_too_many_requests_exhausted() {
+        // One attempt plus five retries, after which the 429 is returned
+        let (url, server) = mock_server(vec![too_many_requests(); 6]);
+        // The client's retry policy doesn't retry the sender's 429 again
+        let rpc_client = RpcClient::new_sender(
+            HttpSender::new(url),
+            RpcClientConfig {
+                retry_config: Some(Default::default()),
+                ..RpcClientConfig::default()
+            },
+        );
+        assert_matches!(
+            rpc_client.get_slot().await.unwrap_err().kind(),
+            ErrorKind::Reqwest(err) if err.status() == Some(StatusCode::TOO_MANY_REQUESTS)
+        );
+        assert_eq!(rpc_client.get_retry_count(), 0);
+        server.join().unwrap();
+    }
 }
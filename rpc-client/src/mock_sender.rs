@@ -20,9 +20,9 @@ use {
             Response, RpcAccountBalance, RpcBlockProduction, RpcBlockProductionRange, RpcBlockhash,
             RpcConfirmedTransactionStatusWithSignature, RpcContactInfo, RpcIdentity,
             RpcInflationGovernor, RpcInflationRate, RpcInflationReward, RpcKeyedAccount,
-            RpcPerfSample, RpcPrioritizationFee, RpcResponseContext, RpcSimulateTransactionResult,
-            RpcSnapshotSlotInfo, RpcSupply, RpcVersionInfo, RpcVoteAccountInfo,
-            RpcVoteAccountStatus,
+            RpcPerfSample, RpcPrioritizationFee, RpcPrioritizationFeeStats, RpcResponseContext,
+            RpcSimulateTransactionResult, RpcSnapshotSlotInfo, RpcSupply, RpcVersionInfo,
+            RpcVoteAccountInfo, RpcVoteAccountStatus,
         },
     },
     solana_signature::Signature,
@@ -390,6 +390,15 @@ impl RpcSender for MockSender {
                 slot: 123_456_789,
                 prioritization_fee: 10_000,
             }])?,
+            "getRecentPrioritizationFeeStats" => {
+                serde_json::to_value(vec![RpcPrioritizationFeeStats {
+                    slot: 123_456_789,
+                    transaction_count: 3,
+                    min_prioritization_fee: 0,
+                    median_prioritization_fee: 10_000,
+                    max_prioritization_fee: 50_000,
+                }])?
+            }
             "getIdentity" => serde_json::to_value(RpcIdentity {
                 identity: PUBKEY.to_string(),
             })?,
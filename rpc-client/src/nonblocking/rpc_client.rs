@@ -53,11 +53,13 @@ use {
         UiConfirmedBlock, UiTransactionEncoding,
     },
     std::{
+        io,
         net::SocketAddr,
         str::FromStr,
+        sync::atomic::{AtomicUsize, Ordering},
         time::{Duration, Instant},
     },
-    tokio::time::sleep,
+    tokio::time::{sleep, timeout},
 };
 // inlined to avoid a solana_program dep
 const MAX_LOCKOUT_HISTORY: usize = 31;
@@ -149,6 +151,7 @@ static_assertions::const_assert_eq!(
 pub struct RpcClient {
     sender: Box<dyn RpcSender + Send + Sync + 'static>,
     config: RpcClientConfig,
+    retry_count: AtomicUsize,
 }
 
 impl RpcClient {
@@ -165,6 +168,7 @@ impl RpcClient {
         Self {
             sender: Box::new(sender),
             config,
+            retry_count: AtomicUsize::default(),
         }
     }
 
@@ -317,6 +321,7 @@ impl RpcClient {
             RpcClientConfig {
                 commitment_config,
                 confirm_transaction_initial_timeout: Some(confirm_transaction_initial_timeout),
+                ..RpcClientConfig::default()
             },
         )
     }
@@ -1544,6 +1549,12 @@ impl RpcClient {
     /// transactions in recent slots only use the
     /// [`get_signature_statuses`][RpcClient::get_signature_statuses] method.
     ///
+    /// By default, nodes accept at most
+    /// [`MAX_GET_SIGNATURE_STATUSES_HISTORY_QUERY_ITEMS`] signatures per
+    /// request when searching their history.
+    ///
+    /// [`MAX_GET_SIGNATURE_STATUSES_HISTORY_QUERY_ITEMS`]: solana_rpc_client_api::request::MAX_GET_SIGNATURE_STATUSES_HISTORY_QUERY_ITEMS
+    ///
     /// # Errors
     ///
     /// Any individual `TransactionStatus` may have triggered an error during
@@ -3181,6 +3192,39 @@ impl RpcClient {
             .await
     }
 
+    /// Returns the minimum, median and maximum prioritization fee of the transactions in each of
+    /// the `num_slots` most recent blocks, or of all the blocks in the node's prioritization-fee
+    /// cache if `num_slots` is `None`. At most 150 blocks may be requested.
+    ///
+    /// # RPC Reference
+    ///
+    /// This method corresponds directly to the `getRecentPrioritizationFeeStats` RPC method.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use solana_rpc_client_api::client_error::Error;
+    /// # use solana_rpc_client::nonblocking::rpc_client::RpcClient;
+    /// # futures::executor::block_on(async {
+    /// #     let rpc_client = RpcClient::new_mock("succeeds".to_string());
+    /// let fee_stats = rpc_client
+    ///     .get_recent_prioritization_fee_stats(Some(10))
+    ///     .await?;
+    /// #     Ok::<(), Error>(())
+    /// # })?;
+    /// # Ok::<(), Error>(())
+    /// ```
+    pub async fn get_recent_prioritization_fee_stats(
+        &self,
+        num_slots: Option<usize>,
+    ) -> ClientResult<Vec<RpcPrioritizationFeeStats>> {
+        self.send(
+            RpcRequest::GetRecentPrioritizationFeeStats,
+            json!([num_slots]),
+        )
+        .await
+    }
+
     /// Returns the identity pubkey for the current node.
     ///
     /// # RPC Reference
@@ -3593,6 +3637,30 @@ impl RpcClient {
             .await
     }
 
+    /// Get the shape of the fork choice tree, as last seen by replay.
+    ///
+    /// Returns `None` until the node has replayed its first bank.
+    ///
+    /// # RPC Reference
+    ///
+    /// This method corresponds directly to the `getForks` RPC method.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use solana_rpc_client_api::client_error::Error;
+    /// # use solana_rpc_client::nonblocking::rpc_client::RpcClient;
+    /// # futures::executor::block_on(async {
+    /// #     let rpc_client = RpcClient::new_mock("succeeds".to_string());
+    /// let forks = rpc_client.get_forks().await?;
+    /// #     Ok::<(), Error>(())
+    /// # })?;
+    /// # Ok::<(), Error>(())
+    /// ```
+    pub async fn get_forks(&self) -> ClientResult<Option<RpcForks>> {
+        self.send(RpcRequest::GetForks, Value::Null).await
+    }
+
     /// Get the max slot seen from after [shred](https://solana.com/docs/terminology#shred) insert.
     ///
     /// # RPC Reference
@@ -4015,6 +4083,8 @@ impl RpcClient {
     ///     },
     ///     with_context: Some(false),
     ///     sort_results: Some(true),
+    ///     limit: None,
+    ///     cursor: None,
     /// };
     /// let accounts = rpc_client.get_program_accounts_with_config(
     ///     &alice.pubkey(),
@@ -4677,18 +4747,50 @@ impl RpcClient {
     {
         assert!(params.is_array() || params.is_null());
 
-        let response = self
-            .sender
-            .send(request, params)
-            .await
-            .map_err(|err| err.into_with_request(request))?;
+        let mut retries = 0;
+        let response = loop {
+            let result = self.send_once(request, params.clone()).await;
+            match (result, &self.config.retry_config) {
+                (Err(err), Some(retry_config))
+                    if retries < retry_config.max_retries && retry_config.is_retryable(&err) =>
+                {
+                    let backoff = retry_config.backoff(retries);
+                    debug!("{request} failed: {err}, retrying in {backoff:?}");
+                    sleep(backoff).await;
+                    retries += 1;
+                    self.retry_count.fetch_add(1, Ordering::Relaxed);
+                }
+                (result, _) => break result,
+            }
+        }
+        .map_err(|err| err.into_with_request(request))?;
         serde_json::from_value(response)
             .map_err(|err| ClientError::new_with_request(err.into(), request))
     }
 
+    async fn send_once(&self, request: RpcRequest, params: Value) -> ClientResult<Value> {
+        let Some(request_timeout) = self.config.request_timeout else {
+            return self.sender.send(request, params).await;
+        };
+        timeout(request_timeout, self.sender.send(request, params))
+            .await
+            .unwrap_or_else(|_| {
+                Err(io::Error::new(
+                    io::ErrorKind::TimedOut,
+                    format!("request timed out after {request_timeout:?}"),
+                )
+                .into())
+            })
+    }
+
     pub fn get_transport_stats(&self) -> RpcTransportStats {
         self.sender.get_transport_stats()
     }
+
+    /// Number of times requests have been retried, per [`RpcClientConfig::retry_config`]
+    pub fn get_retry_count(&self) -> usize {
+        self.retry_count.load(Ordering::Relaxed)
+    }
 }
 
 fn serialize_and_encode<T>(input: &T, encoding: UiTransactionEncoding) -> ClientResult<String>
@@ -17,6 +17,7 @@ use {
         nonblocking::{self, rpc_client::get_rpc_request_str},
         rpc_sender::*,
     },
+    rand::Rng,
     serde::Serialize,
     serde_json::Value,
     solana_account::{Account, ReadableAccount},
@@ -50,6 +51,10 @@ use {
 pub struct RpcClientConfig {
     pub commitment_config: CommitmentConfig,
     pub confirm_transaction_initial_timeout: Option<Duration>,
+    /// Policy for retrying requests that fail transiently, `None` to never retry
+    pub retry_config: Option<RpcRetryConfig>,
+    /// Timeout for each request, in place of the transport's own timeout
+    pub request_timeout: Option<Duration>,
 }
 
 impl RpcClientConfig {
@@ -61,6 +66,59 @@ impl RpcClientConfig {
     }
 }
 
+/// Policy for retrying RPC requests that fail transiently
+///
+/// Only failures to reach the server are retried. Errors returned by the RPC
+/// method itself, such as invalid params, are returned immediately. Requests
+/// rejected with HTTP 429 Too Many Requests are already retried by
+/// [`HttpSender`](crate::http_sender::HttpSender), honoring the server's
+/// Retry-After, so they aren't retried again here.
+#[derive(Clone, Debug)]
+pub struct RpcRetryConfig {
+    /// Maximum number of times a request is retried
+    pub max_retries: usize,
+    /// Delay before the first retry, doubled for each retry after that
+    pub base_backoff: Duration,
+    /// Upper bound on the random delay added to each backoff
+    pub max_jitter: Duration,
+    /// Whether to retry connection errors, including requests that timed out
+    pub retry_connection_errors: bool,
+}
+
+impl Default for RpcRetryConfig {
+    fn default() -> Self {
+        Self {
+            max_retries: 3,
+            base_backoff: Duration::from_millis(500),
+            max_jitter: Duration::from_millis(250),
+            retry_connection_errors: true,
+        }
+    }
+}
+
+impl RpcRetryConfig {
+    /// Whether a request that failed with `error` should be retried
+    pub fn is_retryable(&self, error: &ClientError) -> bool {
+        match error.kind() {
+            ErrorKind::Io(_) => self.retry_connection_errors,
+            ErrorKind::Reqwest(err) => {
+                self.retry_connection_errors && (err.is_connect() || err.is_timeout())
+            }
+            _ => false,
+        }
+    }
+
+    /// How long to wait before retry number `retry`, counting from 0
+    pub fn backoff(&self, retry: usize) -> Duration {
+        let backoff = self
+            .base_backoff
+            .saturating_mul(1u32 << retry.min(16) as u32);
+        let max_jitter_ms = u64::try_from(self.max_jitter.as_millis()).unwrap_or(u64::MAX);
+        let jitter = Duration::from_millis(rand::thread_rng().gen_range(0..=max_jitter_ms));
+        backoff.saturating_add(jitter)
+    }
+}
+
 /// Trait used to add support for versioned messages to RPC APIs while
 /// retaining backwards compatibility
 pub trait SerializableMessage: Serialize {}
@@ -378,6 +436,7 @@ impl RpcClient {
             RpcClientConfig {
                 commitment_config,
                 confirm_transaction_initial_timeout: Some(confirm_transaction_initial_timeout),
+                ..RpcClientConfig::default()
             },
         )
     }
@@ -1321,6 +1380,12 @@ impl RpcClient {
     /// transactions in recent slots only use the
     /// [`get_signature_statuses`][RpcClient::get_signature_statuses] method.
     ///
+    /// By default, nodes accept at most
+    /// [`MAX_GET_SIGNATURE_STATUSES_HISTORY_QUERY_ITEMS`] signatures per
+    /// request when searching their history.
+    ///
+    /// [`MAX_GET_SIGNATURE_STATUSES_HISTORY_QUERY_ITEMS`]: solana_rpc_client_api::request::MAX_GET_SIGNATURE_STATUSES_HISTORY_QUERY_ITEMS
+    ///
     /// # Errors
     ///
     /// Any individual `TransactionStatus` may have triggered an error during
@@ -2682,6 +2747,30 @@ impl RpcClient {
         self.invoke((self.rpc_client.as_ref()).get_recent_prioritization_fees(addresses))
     }
 
+    /// Returns the minimum, median and maximum prioritization fee of the transactions in each of
+    /// the `num_slots` most recent blocks, or of all the blocks in the node's prioritization-fee
+    /// cache if `num_slots` is `None`. At most 150 blocks may be requested.
+    ///
+    /// # RPC Reference
+    ///
+    /// This method corresponds directly to the `getRecentPrioritizationFeeStats` RPC method.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use solana_rpc_client_api::client_error::Error;
+    /// # use solana_rpc_client::rpc_client::RpcClient;
+    /// # let rpc_client = RpcClient::new_mock("succeeds".to_string());
+    /// let fee_stats = rpc_client.get_recent_prioritization_fee_stats(Some(10))?;
+    /// # Ok::<(), Error>(())
+    /// ```
+    pub fn get_recent_prioritization_fee_stats(
+        &self,
+        num_slots: Option<usize>,
+    ) -> ClientResult<Vec<RpcPrioritizationFeeStats>> {
+        self.invoke((self.rpc_client.as_ref()).get_recent_prioritization_fee_stats(num_slots))
+    }
+
     /// Returns the identity pubkey for the current node.
     ///
     /// # RPC Reference
@@ -3003,6 +3092,27 @@ impl RpcClient {
         self.invoke((self.rpc_client.as_ref()).get_max_retransmit_slot())
     }
 
+    /// Get the shape of the fork choice tree, as last seen by replay.
+    ///
+    /// Returns `None` until the node has replayed its first bank.
+    ///
+    /// # RPC Reference
+    ///
+    /// This method corresponds directly to the `getForks` RPC method.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use solana_rpc_client_api::client_error::Error;
+    /// # use solana_rpc_client::rpc_client::RpcClient;
+    /// # let rpc_client = RpcClient::new_mock("succeeds".to_string());
+    /// let forks = rpc_client.get_forks()?;
+    /// # Ok::<(), Error>(())
+    /// ```
+    pub fn get_forks(&self) -> ClientResult<Option<RpcForks>> {
+        self.invoke((self.rpc_client.as_ref()).get_forks())
+    }
+
     /// Get the max slot seen from after [shred](https://solana.com/docs/terminology#shred) insert.
     ///
     /// # RPC Reference
@@ -3331,6 +3441,8 @@ impl RpcClient {
     ///     },
     ///     with_context: Some(false),
     ///     sort_results: Some(true),
+    ///     limit: None,
+    ///     cursor: None,
     /// };
     /// let accounts = rpc_client.get_program_accounts_with_config(
     ///     &alice.pubkey(),
@@ -3655,6 +3767,11 @@ impl RpcClient {
         (self.rpc_client.as_ref()).get_transport_stats()
     }
 
+    /// Number of times requests have been retried, per [`RpcClientConfig::retry_config`]
+    pub fn get_retry_count(&self) -> usize {
+        (self.rpc_client.as_ref()).get_retry_count()
+    }
+
     pub fn get_feature_activation_slot(&self, feature_id: &Pubkey) -> ClientResult<Option<Slot>> {
         self.get_account_with_commitment(feature_id, self.commitment())
             .and_then(|maybe_feature_account| {
@@ -3727,7 +3844,10 @@ mod tests {
         solana_account_decoder_client_types::UiAccountEncoding,
         solana_instruction::error::InstructionError,
         solana_keypair::Keypair,
-        solana_rpc_client_api::client_error::ErrorKind,
+        solana_rpc_client_api::{
+            client_error::ErrorKind,
+            request::{RpcError, RpcResponseErrorData},
+        },
         solana_signer::Signer,
         solana_system_transaction as system_transaction,
         solana_transaction_error::TransactionError,
@@ -3988,6 +4108,8 @@ mod tests {
                         },
                         with_context: None,
                         sort_results: None,
+                        limit: None,
+                        cursor: None,
                     },
                 )
                 .unwrap();
@@ -4023,10 +4145,181 @@ mod tests {
                         },
                         with_context: Some(true),
                         sort_results: None,
+                        limit: None,
+                        cursor: None,
                     },
                 )
                 .unwrap();
             assert_eq!(expected_result, result);
         }
     }
+
+    struct FlakySender {
+        /// Responses to return, in order, before returning the slot
+        responses: std::sync::Mutex<std::collections::VecDeque<ClientResult<Value>>>,
+        delay: Duration,
+    }
+
+    impl FlakySender {
+        fn new(responses: Vec<ClientResult<Value>>) -> Self {
+            Self {
+                responses: std::sync::Mutex::new(responses.into()),
+                delay: Duration::ZERO,
+            }
+        }
+    }
+
+    #[async_trait::async_trait]
+    impl RpcSender for FlakySender {
+        async fn send(&self, _request: RpcRequest, _params: Value) -> ClientResult<Value> {
+            tokio::time::sleep(self.delay).await;
+            self.responses
+                .lock()
+                .unwrap()
+                .pop_front()
+                .unwrap_or(Ok(json!(42)))
+        }
+
+        fn get_transport_stats(&self) -> RpcTransportStats {
+            RpcTransportStats::default()
+        }
+
+        fn url(&self) -> String {
+            "FlakySender".to_string()
+        }
+    }
+
+    fn connection_reset() -> ClientResult<Value> {
+        Err(io::Error::from(io::ErrorKind::ConnectionReset).into())
+    }
+
+    fn test_retry_config(max_retries: usize) -> RpcRetryConfig {
+        RpcRetryConfig {
+            max_retries,
+            base_backoff: Duration::from_millis(1),
+            max_jitter: Duration::from_millis(1),
+            ..RpcRetryConfig::default()
+        }
+    }
+
+    #[test]
+    fn test_retry_transient_errors() {
+        // Existing constructors don't retry
+        let rpc_client = RpcClient::new_sender(
+            FlakySender::new(vec![connection_reset()]),
+            RpcClientConfig::default(),
+        );
+        assert_matches!(rpc_client.get_slot().unwrap_err().kind(), ErrorKind::Io(_));
+        assert_eq!(rpc_client.get_retry_count(), 0);
+
+        let rpc_client = RpcClient::new_sender(
+            FlakySender::new(vec![connection_reset(), connection_reset()]),
+            RpcClientConfig {
+                retry_config: Some(test_retry_config(3)),
+                ..RpcClientConfig::default()
+            },
+        );
+        assert_eq!(rpc_client.get_slot().unwrap(), 42);
+        assert_eq!(rpc_client.get_retry_count(), 2);
+
+        // Give up after `max_retries`
+        let rpc_client = RpcClient::new_sender(
+            FlakySender::new(vec![
+                connection_reset(),
+                connection_reset(),
+                connection_reset(),
+            ]),
+            RpcClientConfig {
+                retry_config: Some(test_retry_config(2)),
+                ..RpcClientConfig::default()
+            },
+        );
+        assert_matches!(rpc_client.get_slot().unwrap_err().kind(), ErrorKind::Io(_));
+        assert_eq!(rpc_client.get_retry_count(), 2);
+    }
+
+    #[test]
+    fn test_retry_non_retryable_error() {
+        let invalid_params = RpcError::RpcResponseError {
+            code: -32602,
+            message: "Invalid params".to_string(),
+            data: RpcResponseErrorData::Empty,
+        };
+        let rpc_client = RpcClient::new_sender(
+            FlakySender::new(vec![Err(invalid_params.into())]),
+            RpcClientConfig {
+                retry_config: Some(test_retry_config(3)),
+                ..RpcClientConfig::default()
+            },
+        );
+        assert_matches!(
+            rpc_client.get_slot().unwrap_err().kind(),
+            ErrorKind::RpcError(RpcError::RpcResponseError { code: -32602, .. })
+        );
+        assert_eq!(rpc_client.get_retry_count(), 0);
+
+        // Connection errors aren't retried if the policy excludes them
+        let rpc_client = RpcClient::new_sender(
+            FlakySender::new(vec![connection_reset()]),
+            RpcClientConfig {
+                retry_config: Some(RpcRetryConfig {
+                    retry_connection_errors: false,
+                    ..test_retry_config(3)
+                }),
+                ..RpcClientConfig::default()
+            },
+        );
+        assert!(rpc_client.get_slot().is_err());
+        assert_eq!(rpc_client.get_retry_count(), 0);
+    }
+
+    #[test]
+    fn test_request_timeout() {
+        let slow_sender = || FlakySender {
+            delay: Duration::from_secs(10),
+            ..FlakySender::new(vec![])
+        };
+
+        let start = std::time::Instant::now();
+        let rpc_client = RpcClient::new_sender(
+            slow_sender(),
+            RpcClientConfig {
+                request_timeout: Some(Duration::from_millis(10)),
+                ..RpcClientConfig::default()
+            },
+        );
+        assert_matches!(
+            rpc_client.get_slot().unwrap_err().kind(),
+            ErrorKind::Io(err) if err.kind() == io::ErrorKind::TimedOut
+        );
+        assert_eq!(rpc_client.get_retry_count(), 0);
+
+        // Timed out requests are retried like connection errors
+        let rpc_client = RpcClient::new_sender(
+            slow_sender(),
+            RpcClientConfig {
+                retry_config: Some(test_retry_config(1)),
+                request_timeout: Some(Duration::from_millis(10)),
+                ..RpcClientConfig::default()
+            },
+        );
+        assert!(rpc_client.get_slot().is_err());
+        assert_eq!(rpc_client.get_retry_count(), 1);
+        assert!(start.elapsed() < Duration::from_secs(10));
+    }
+
+    #[test]
+    fn test_retry_backoff() {
+        let retry_config = RpcRetryConfig {
+            base_backoff: Duration::from_millis(100),
+            max_jitter: Duration::from_millis(50),
+            ..RpcRetryConfig::default()
+        };
+        for retry in 0..4 {
+            let backoff = retry_config.backoff(retry);
+            let min_backoff = Duration::from_millis(100 << retry);
+            assert!(backoff >= min_backoff);
+            assert!(backoff <= min_backoff + Duration::from_millis(50));
+        }
+    }
 }
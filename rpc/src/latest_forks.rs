@@ -0,0 +1,20 @@
+use {
+    solana_rpc_client_api::response::RpcForks,
+    std::sync::{Arc, RwLock},
+};
+
+/// The most recent view of the fork tree published by replay, served by `getForks`
+#[derive(Default)]
+pub struct LatestForks {
+    forks: RwLock<Option<Arc<RpcForks>>>,
+}
+
+impl LatestForks {
+    pub fn update(&self, forks: RpcForks) {
+        *self.forks.write().unwrap() = Some(Arc::new(forks));
+    }
+
+    pub fn get(&self) -> Option<Arc<RpcForks>> {
+        self.forks.read().unwrap().clone()
+    }
+}
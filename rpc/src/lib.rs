@@ -2,6 +2,7 @@
 pub mod cache_block_meta_service;
 mod cluster_tpu_info;
 pub mod filter;
+pub mod latest_forks;
 pub mod max_slots;
 pub mod optimistically_confirmed_bank_tracker;
 pub mod parsed_token_accounts;
@@ -9,6 +10,7 @@ pub mod rpc;
 mod rpc_cache;
 pub mod rpc_completed_slots_service;
 pub mod rpc_health;
+pub mod rpc_policy;
 pub mod rpc_pubsub;
 pub mod rpc_pubsub_service;
 pub mod rpc_service;
@@ -400,7 +400,12 @@ impl OptimisticallyConfirmedBankTracker {
 mod tests {
     use {
         super::*,
+        crate::{
+            rpc_pubsub::RpcSolPubSubInternal, rpc_pubsub_service,
+            rpc_subscription_tracker::SubscriptionParams,
+        },
         crossbeam_channel::unbounded,
+        serial_test::serial,
         solana_ledger::genesis_utils::{create_genesis_config, GenesisConfigInfo},
         solana_pubkey::Pubkey,
         solana_runtime::{
@@ -667,4 +672,88 @@ mod tests {
         let notifications = get_root_notifications(&receiver);
         assert_eq!(notifications.len(), 1);
     }
+
+    #[test]
+    #[serial]
+    fn test_slot_update_notifications() {
+        let exit = Arc::new(AtomicBool::new(false));
+        let GenesisConfigInfo { genesis_config, .. } = create_genesis_config(100);
+        let bank = Bank::new_for_tests(&genesis_config);
+        let bank_forks = BankForks::new_rw_arc(bank);
+        for slot in 1..=3 {
+            let parent = bank_forks.read().unwrap().get(slot - 1).unwrap();
+            let bank = Bank::new_from_parent(parent, &Pubkey::default(), slot);
+            bank_forks.write().unwrap().insert(bank);
+        }
+
+        let optimistically_confirmed_bank =
+            OptimisticallyConfirmedBank::locked_from_bank_forks_root(&bank_forks);
+        let subscriptions = Arc::new(RpcSubscriptions::new_for_tests(
+            exit,
+            Arc::new(AtomicU64::default()),
+            Arc::new(AtomicU64::default()),
+            bank_forks.clone(),
+            Arc::new(RwLock::new(BlockCommitmentCache::default())),
+            optimistically_confirmed_bank.clone(),
+        ));
+        let (rpc, mut receiver) = rpc_pubsub_service::test_connection(&subscriptions);
+        let sub_id = rpc.slots_updates_subscribe().unwrap();
+        subscriptions
+            .control()
+            .assert_subscribed(&SubscriptionParams::SlotsUpdates);
+
+        let mut pending_optimistically_confirmed_banks = HashSet::new();
+        let mut last_notified_confirmed_slot: Slot = 0;
+        let mut highest_confirmed_slot: Slot = 0;
+        let mut newest_root_slot: Slot = 0;
+        let bank3 = bank_forks.read().unwrap().get(3).unwrap();
+        for notification in [
+            BankNotification::OptimisticallyConfirmed(2),
+            // Slot 3 is confirmed before it is frozen
+            BankNotification::OptimisticallyConfirmed(3),
+            BankNotification::Frozen(bank3.clone()),
+        ] {
+            if matches!(notification, BankNotification::Frozen(_)) {
+                bank3.freeze();
+            }
+            OptimisticallyConfirmedBankTracker::process_notification(
+                notification,
+                &bank_forks,
+                &optimistically_confirmed_bank,
+                &subscriptions,
+                &mut pending_optimistically_confirmed_banks,
+                &mut last_notified_confirmed_slot,
+                &mut highest_confirmed_slot,
+                &mut newest_root_slot,
+                &None,
+                &PrioritizationFeeCache::default(),
+            );
+        }
+        assert_eq!(optimistically_confirmed_bank.read().unwrap().bank.slot(), 3);
+
+        let mut recv_slot_update = || {
+            let response: serde_json::Value = serde_json::from_str(&receiver.recv()).unwrap();
+            assert_eq!(response["method"], "slotsUpdatesNotification");
+            serde_json::from_value::<SlotUpdate>(response["params"]["result"].clone()).unwrap()
+        };
+        assert!(matches!(
+            recv_slot_update(),
+            SlotUpdate::OptimisticConfirmation { slot: 2, .. }
+        ));
+        assert!(matches!(
+            recv_slot_update(),
+            SlotUpdate::OptimisticConfirmation { slot: 3, .. }
+        ));
+        assert!(matches!(
+            recv_slot_update(),
+            SlotUpdate::Frozen { slot: 3, .. }
+        ));
+        // Each event is only notified once
+        assert!(receiver.recv_timeout(Duration::from_millis(200)).is_err());
+
+        rpc.slots_updates_unsubscribe(sub_id).unwrap();
+        subscriptions
+            .control()
+            .assert_unsubscribed(&SubscriptionParams::SlotsUpdates);
+    }
 }
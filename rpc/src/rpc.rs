@@ -1,9 +1,10 @@
 //! The `rpc` module implements the Solana RPC interface.
 use {
     crate::{
-        filter::filter_allows, max_slots::MaxSlots,
+        filter::filter_allows, latest_forks::LatestForks, max_slots::MaxSlots,
         optimistically_confirmed_bank_tracker::OptimisticallyConfirmedBank,
         parsed_token_accounts::*, rpc_cache::LargestAccountsCache, rpc_health::*,
+        rpc_policy::RpcPolicyConfig,
     },
     base64::{prelude::BASE64_STANDARD, Engine},
     bincode::{config::Options, serialize},
@@ -22,7 +23,9 @@ use {
     },
     solana_accounts_db::{
         accounts::AccountAddressFilter,
-        accounts_index::{AccountIndex, AccountSecondaryIndexes, IndexKey, ScanConfig, ScanResult},
+        accounts_index::{
+            AccountIndex, AccountSecondaryIndexes, IndexKey, ScanConfig, ScanPage, ScanResult,
+        },
     },
     solana_client::connection_cache::Protocol,
     solana_entry::entry::Entry,
@@ -31,7 +34,8 @@ use {
     solana_gossip::cluster_info::ClusterInfo,
     solana_inline_spl::{
         token::{SPL_TOKEN_ACCOUNT_MINT_OFFSET, SPL_TOKEN_ACCOUNT_OWNER_OFFSET},
-        token_2022::{self, ACCOUNTTYPE_ACCOUNT},
+        token_2022::ACCOUNTTYPE_ACCOUNT,
+        token_programs,
     },
     solana_ledger::{
         blockstore::{Blockstore, SignatureInfosForAddress},
@@ -48,7 +52,9 @@ use {
         request::{
             TokenAccountsFilter, DELINQUENT_VALIDATOR_SLOT_DISTANCE,
             MAX_GET_CONFIRMED_BLOCKS_RANGE, MAX_GET_CONFIRMED_SIGNATURES_FOR_ADDRESS2_LIMIT,
-            MAX_GET_PROGRAM_ACCOUNT_FILTERS, MAX_GET_SIGNATURE_STATUSES_QUERY_ITEMS,
+            MAX_GET_PROGRAM_ACCOUNTS_PAGE_LIMIT, MAX_GET_PROGRAM_ACCOUNT_FILTERS,
+            MAX_GET_RECENT_PRIORITIZATION_FEE_STATS_SLOTS,
+            MAX_GET_SIGNATURE_STATUSES_HISTORY_QUERY_ITEMS, MAX_GET_SIGNATURE_STATUSES_QUERY_ITEMS,
             MAX_GET_SLOT_LEADERS, MAX_MULTIPLE_ACCOUNTS,
             MAX_RPC_VOTE_ACCOUNT_INFO_EPOCH_CREDITS_HISTORY, NUM_LARGEST_ACCOUNTS,
         },
@@ -101,7 +107,6 @@ use {
             interest_bearing_mint::InterestBearingConfig, BaseStateWithExtensions,
             StateWithExtensions,
         },
-        solana_program::program_pack::Pack,
         state::{Account as TokenAccount, Mint},
     },
     std::{
@@ -109,10 +114,10 @@ use {
         cmp::{max, min, Reverse},
         collections::{BinaryHeap, HashMap, HashSet},
         convert::TryFrom,
-        net::SocketAddr,
+        net::{IpAddr, SocketAddr},
         str::FromStr,
         sync::{
-            atomic::{AtomicBool, AtomicU64, Ordering},
+            atomic::{AtomicBool, AtomicU64, AtomicUsize, Ordering},
             Arc, RwLock,
         },
         time::Duration,
@@ -165,6 +170,9 @@ pub struct JsonRpcConfig {
     pub skip_preflight_health_check: bool,
     pub rpc_bigtable_config: Option<RpcBigtableConfig>,
     pub max_multiple_accounts: Option<usize>,
+    /// Maximum signatures getSignatureStatuses accepts when searching
+    /// transaction history
+    pub max_signature_statuses_history_query_items: Option<usize>,
     pub account_indexes: AccountSecondaryIndexes,
     pub rpc_threads: usize,
     pub rpc_blocking_threads: usize,
@@ -174,6 +182,12 @@ pub struct JsonRpcConfig {
     pub max_request_body_size: Option<usize>,
     /// Disable the health check, used for tests and TestValidator
     pub disable_health_check: bool,
+    /// Maximum number of accounts a getProgramAccounts scan may return
+    pub max_scan_result_accounts: Option<usize>,
+    /// Maximum total account data size a getProgramAccounts scan may return
+    pub max_scan_result_bytes: Option<usize>,
+    /// Operator restrictions on which methods may be called and how often
+    pub policy: RpcPolicyConfig,
 }
 
 impl Default for JsonRpcConfig {
@@ -186,6 +200,7 @@ impl Default for JsonRpcConfig {
             skip_preflight_health_check: bool::default(),
             rpc_bigtable_config: Option::default(),
             max_multiple_accounts: Option::default(),
+            max_signature_statuses_history_query_items: Option::default(),
             account_indexes: AccountSecondaryIndexes::default(),
             rpc_threads: 1,
             rpc_blocking_threads: 1,
@@ -194,6 +209,9 @@ impl Default for JsonRpcConfig {
             rpc_scan_and_fix_roots: Default::default(),
             max_request_body_size: Option::default(),
             disable_health_check: Default::default(),
+            max_scan_result_accounts: Option::default(),
+            max_scan_result_bytes: Option::default(),
+            policy: RpcPolicyConfig::default(),
         }
     }
 }
@@ -248,11 +266,14 @@ pub struct JsonRpcRequestProcessor {
     optimistically_confirmed_bank: Arc<RwLock<OptimisticallyConfirmedBank>>,
     largest_accounts_cache: Arc<RwLock<LargestAccountsCache>>,
     max_slots: Arc<MaxSlots>,
+    latest_forks: Arc<LatestForks>,
     leader_schedule_cache: Arc<LeaderScheduleCache>,
     max_complete_transaction_status_slot: Arc<AtomicU64>,
     max_complete_rewards_slot: Arc<AtomicU64>,
     prioritization_fee_cache: Arc<PrioritizationFeeCache>,
     runtime: Arc<Runtime>,
+    /// Address of the client making the call, if known
+    client_address: Option<IpAddr>,
 }
 impl Metadata for JsonRpcRequestProcessor {}
 
@@ -263,6 +284,17 @@ impl JsonRpcRequestProcessor {
             ..self.clone()
         }
     }
+
+    pub fn with_client_address(self, client_address: Option<IpAddr>) -> JsonRpcRequestProcessor {
+        Self {
+            client_address,
+            ..self
+        }
+    }
+
+    pub fn client_address(&self) -> Option<IpAddr> {
+        self.client_address
+    }
 }
 
 impl JsonRpcRequestProcessor {
@@ -294,35 +326,47 @@ impl JsonRpcRequestProcessor {
             .expect("Failed to spawn blocking task")
     }
 
-    pub async fn get_filtered_indexed_accounts(
+    pub(crate) async fn get_filtered_indexed_accounts(
         &self,
         bank: &Arc<Bank>,
         index_key: &IndexKey,
         program_id: &Pubkey,
         filters: Vec<RpcFilterType>,
-        sort_results: bool,
-    ) -> ScanResult<Vec<TransactionAccount>> {
+        scan: AccountsScan,
+    ) -> RpcCustomResult<Vec<TransactionAccount>> {
         let bank = Arc::clone(bank);
         let index_key = index_key.to_owned();
         let program_id = program_id.to_owned();
         self.runtime
             .spawn_blocking(move || {
-                bank.get_filtered_indexed_accounts(
-                    &index_key,
-                    |account| {
-                        // The program-id account index checks for Account owner on inclusion.
-                        // However, due to the current AccountsDb implementation, an account may
-                        // remain in storage as a zero-lamport AccountSharedData::Default() after
-                        // being wiped and reinitialized in later updates. We include the redundant
-                        // filters here to avoid returning these accounts.
-                        account.owner().eq(&program_id)
-                            && filters
-                                .iter()
-                                .all(|filter_type| filter_allows(filter_type, account))
-                    },
-                    &ScanConfig::new(!sort_results),
-                    bank.byte_limit_for_scans(),
-                )
+                let guard = ScanResultGuard::new(&scan);
+                let filter = |account: &AccountSharedData| {
+                    // The program-id account index checks for Account owner on inclusion.
+                    // However, due to the current AccountsDb implementation, an account may
+                    // remain in storage as a zero-lamport AccountSharedData::Default() after
+                    // being wiped and reinitialized in later updates. We include the redundant
+                    // filters here to avoid returning these accounts.
+                    account.owner().eq(&program_id)
+                        && filters
+                            .iter()
+                            .all(|filter_type| filter_allows(filter_type, account))
+                };
+                let result = match &scan.page {
+                    Some(page) => bank.get_filtered_indexed_accounts_page(
+                        &index_key,
+                        |account| filter(account) && guard.admit(account),
+                        page,
+                        &guard.scan_config(false),
+                        bank.byte_limit_for_scans(),
+                    ),
+                    None => bank.get_filtered_indexed_accounts(
+                        &index_key,
+                        |account| filter(account) && guard.admit(account),
+                        &guard.scan_config(!scan.sort_results),
+                        bank.byte_limit_for_scans(),
+                    ),
+                };
+                guard.finish(result)
             })
             .await
             .expect("Failed to spawn blocking task")
@@ -407,6 +451,7 @@ impl JsonRpcRequestProcessor {
         optimistically_confirmed_bank: Arc<RwLock<OptimisticallyConfirmedBank>>,
         largest_accounts_cache: Arc<RwLock<LargestAccountsCache>>,
         max_slots: Arc<MaxSlots>,
+        latest_forks: Arc<LatestForks>,
         leader_schedule_cache: Arc<LeaderScheduleCache>,
         max_complete_transaction_status_slot: Arc<AtomicU64>,
         max_complete_rewards_slot: Arc<AtomicU64>,
@@ -430,11 +475,13 @@ impl JsonRpcRequestProcessor {
                 optimistically_confirmed_bank,
                 largest_accounts_cache,
                 max_slots,
+                latest_forks,
                 leader_schedule_cache,
                 max_complete_transaction_status_slot,
                 max_complete_rewards_slot,
                 prioritization_fee_cache,
                 runtime,
+                client_address: None,
             },
             transaction_receiver,
         )
@@ -515,11 +562,13 @@ impl JsonRpcRequestProcessor {
             optimistically_confirmed_bank,
             largest_accounts_cache: Arc::new(RwLock::new(LargestAccountsCache::new(30))),
             max_slots: Arc::new(MaxSlots::default()),
+            latest_forks: Arc::new(LatestForks::default()),
             leader_schedule_cache,
             max_complete_transaction_status_slot: Arc::new(AtomicU64::default()),
             max_complete_rewards_slot: Arc::new(AtomicU64::default()),
             prioritization_fee_cache: Arc::new(PrioritizationFeeCache::default()),
             runtime: service_runtime(rpc_threads, rpc_blocking_threads, rpc_niceness_adj),
+            client_address: None,
         }
     }
 
@@ -599,6 +648,7 @@ impl JsonRpcRequestProcessor {
         mut filters: Vec<RpcFilterType>,
         with_context: bool,
         sort_results: bool,
+        page: Option<ScanPage>,
     ) -> Result<OptionalContext<Vec<RpcKeyedAccount>>> {
         let RpcAccountInfoConfig {
             encoding,
@@ -612,6 +662,12 @@ impl JsonRpcRequestProcessor {
         })?;
         let encoding = encoding.unwrap_or(UiAccountEncoding::Binary);
         optimize_filters(&mut filters);
+        let scan = AccountsScan {
+            sort_results,
+            page,
+            max_accounts: self.config.max_scan_result_accounts,
+            max_bytes: self.config.max_scan_result_bytes,
+        };
         let keyed_accounts = {
             if let Some(owner) = get_spl_token_owner_filter(&program_id, &filters) {
                 self.get_filtered_spl_token_accounts_by_owner(
@@ -619,7 +675,7 @@ impl JsonRpcRequestProcessor {
                     program_id,
                     owner,
                     filters,
-                    sort_results,
+                    scan,
                 )
                 .await?
             } else if let Some(mint) = get_spl_token_mint_filter(&program_id, &filters) {
@@ -628,17 +684,12 @@ impl JsonRpcRequestProcessor {
                     program_id,
                     mint,
                     filters,
-                    sort_results,
+                    scan,
                 )
                 .await?
             } else {
-                self.get_filtered_program_accounts(
-                    Arc::clone(&bank),
-                    program_id,
-                    filters,
-                    sort_results,
-                )
-                .await?
+                self.get_filtered_program_accounts(Arc::clone(&bank), program_id, filters, scan)
+                    .await?
             }
         };
         let accounts = if is_known_spl_token_id(&program_id)
@@ -961,6 +1012,10 @@ impl JsonRpcRequestProcessor {
         self.max_slots.shred_insert.load(Ordering::Relaxed)
     }
 
+    fn get_forks(&self) -> Option<RpcForks> {
+        self.latest_forks.get().as_deref().cloned()
+    }
+
     fn get_slot_leader(&self, config: RpcContextConfig) -> Result<String> {
         let bank = self.get_bank_with_config(config)?;
         Ok(bank.collector_id().to_string())
@@ -2070,7 +2125,7 @@ impl JsonRpcRequestProcessor {
                 mint_owner,
                 mint,
                 vec![],
-                true,
+                AccountsScan::all(true),
             )
             .await?
         {
@@ -2140,7 +2195,7 @@ impl JsonRpcRequestProcessor {
                 token_program_id,
                 owner,
                 filters,
-                sort_results,
+                AccountsScan::all(sort_results),
             )
             .await?;
         let accounts = if encoding == UiAccountEncoding::JsonParsed {
@@ -2195,7 +2250,7 @@ impl JsonRpcRequestProcessor {
                 token_program_id,
                 mint,
                 filters,
-                sort_results,
+                AccountsScan::all(sort_results),
             )
             .await?
         } else {
@@ -2205,7 +2260,7 @@ impl JsonRpcRequestProcessor {
                 Arc::clone(&bank),
                 token_program_id,
                 filters,
-                sort_results,
+                AccountsScan::all(sort_results),
             )
             .await?
         };
@@ -2231,7 +2286,7 @@ impl JsonRpcRequestProcessor {
         bank: Arc<Bank>,
         program_id: Pubkey,
         mut filters: Vec<RpcFilterType>,
-        sort_results: bool,
+        scan: AccountsScan,
     ) -> RpcCustomResult<Vec<(Pubkey, AccountSharedData)>> {
         optimize_filters(&mut filters);
         if self
@@ -2249,28 +2304,33 @@ impl JsonRpcRequestProcessor {
                 &IndexKey::ProgramId(program_id),
                 &program_id,
                 filters,
-                sort_results,
+                scan,
             )
             .await
-            .map_err(|e| RpcCustomError::ScanError {
-                message: e.to_string(),
-            })
         } else {
             // this path does not need to provide a mb limit because we only want to support secondary indexes
             self.runtime
                 .spawn_blocking(move || {
-                    bank.get_filtered_program_accounts(
-                        &program_id,
-                        |account: &AccountSharedData| {
-                            filters
-                                .iter()
-                                .all(|filter_type| filter_allows(filter_type, account))
-                        },
-                        &ScanConfig::new(!sort_results),
-                    )
-                    .map_err(|e| RpcCustomError::ScanError {
-                        message: e.to_string(),
-                    })
+                    let guard = ScanResultGuard::new(&scan);
+                    let filter = |account: &AccountSharedData| {
+                        filters
+                            .iter()
+                            .all(|filter_type| filter_allows(filter_type, account))
+                    };
+                    let result = match &scan.page {
+                        Some(page) => bank.get_filtered_program_accounts_page(
+                            &program_id,
+                            |account| filter(account) && guard.admit(account),
+                            page,
+                            &guard.scan_config(false),
+                        ),
+                        None => bank.get_filtered_program_accounts(
+                            &program_id,
+                            |account| filter(account) && guard.admit(account),
+                            &guard.scan_config(!scan.sort_results),
+                        ),
+                    };
+                    guard.finish(result)
                 })
                 .await
                 .expect("Failed to spawn blocking task")
@@ -2284,7 +2344,7 @@ impl JsonRpcRequestProcessor {
         program_id: Pubkey,
         owner_key: Pubkey,
         mut filters: Vec<RpcFilterType>,
-        sort_results: bool,
+        scan: AccountsScan,
     ) -> RpcCustomResult<Vec<(Pubkey, AccountSharedData)>> {
         // The by-owner accounts index checks for Token Account state and Owner address on
         // inclusion. However, due to the current AccountsDb implementation, an account may remain
@@ -2314,14 +2374,11 @@ impl JsonRpcRequestProcessor {
                 &IndexKey::SplTokenOwner(owner_key),
                 &program_id,
                 filters,
-                sort_results,
+                scan,
             )
             .await
-            .map_err(|e| RpcCustomError::ScanError {
-                message: e.to_string(),
-            })
         } else {
-            self.get_filtered_program_accounts(bank, program_id, filters, sort_results)
+            self.get_filtered_program_accounts(bank, program_id, filters, scan)
                 .await
         }
     }
@@ -2333,7 +2390,7 @@ impl JsonRpcRequestProcessor {
         program_id: Pubkey,
         mint_key: Pubkey,
         mut filters: Vec<RpcFilterType>,
-        sort_results: bool,
+        scan: AccountsScan,
     ) -> RpcCustomResult<Vec<(Pubkey, AccountSharedData)>> {
         // The by-mint accounts index checks for Token Account state and Mint address on inclusion.
         // However, due to the current AccountsDb implementation, an account may remain in storage
@@ -2362,14 +2419,11 @@ impl JsonRpcRequestProcessor {
                 &IndexKey::SplTokenMint(mint_key),
                 &program_id,
                 filters,
-                sort_results,
+                scan,
             )
             .await
-            .map_err(|e| RpcCustomError::ScanError {
-                message: e.to_string(),
-            })
         } else {
-            self.get_filtered_program_accounts(bank, program_id, filters, sort_results)
+            self.get_filtered_program_accounts(bank, program_id, filters, scan)
                 .await
         }
     }
@@ -2420,6 +2474,109 @@ impl JsonRpcRequestProcessor {
             })
             .collect())
     }
+
+    fn get_recent_prioritization_fee_stats(
+        &self,
+        num_slots: usize,
+    ) -> Result<Vec<RpcPrioritizationFeeStats>> {
+        Ok(self
+            .prioritization_fee_cache
+            .get_prioritization_fee_stats(num_slots)
+            .into_iter()
+            .map(|(slot, stats)| RpcPrioritizationFeeStats {
+                slot,
+                transaction_count: stats.transaction_count,
+                min_prioritization_fee: stats.min_prioritization_fee,
+                median_prioritization_fee: stats.median_prioritization_fee,
+                max_prioritization_fee: stats.max_prioritization_fee,
+            })
+            .collect())
+    }
+}
+
+/// How a scan serving an RPC request collects its results
+#[derive(Debug, Default, Clone, Copy)]
+pub(crate) struct AccountsScan {
+    sort_results: bool,
+    /// Only return this page of the results, ordered by pubkey
+    page: Option<ScanPage>,
+    /// Fail the request once the results exceed this many accounts
+    max_accounts: Option<usize>,
+    /// Fail the request once the account data in the results exceeds this many bytes
+    max_bytes: Option<usize>,
+}
+
+impl AccountsScan {
+    /// Collect all matching accounts, without limits
+    fn all(sort_results: bool) -> Self {
+        Self {
+            sort_results,
+            ..Self::default()
+        }
+    }
+}
+
+/// Enforces the result limits of an `AccountsScan`, aborting the scan as soon as the
+/// accounts it collected exceed them rather than materializing the full result
+struct ScanResultGuard {
+    max_accounts: Option<usize>,
+    max_bytes: Option<usize>,
+    num_accounts: AtomicUsize,
+    num_bytes: AtomicUsize,
+    exceeded: AtomicBool,
+    abort: Arc<AtomicBool>,
+}
+
+impl ScanResultGuard {
+    fn new(scan: &AccountsScan) -> Self {
+        Self {
+            max_accounts: scan.max_accounts,
+            max_bytes: scan.max_bytes,
+            num_accounts: AtomicUsize::default(),
+            num_bytes: AtomicUsize::default(),
+            exceeded: AtomicBool::default(),
+            abort: Arc::default(),
+        }
+    }
+
+    fn scan_config(&self, collect_all_unsorted: bool) -> ScanConfig {
+        ScanConfig {
+            abort: Some(Arc::clone(&self.abort)),
+            collect_all_unsorted,
+        }
+    }
+
+    /// Counts an account collected by the scan; returns false and aborts the scan once
+    /// the results exceed the limits
+    fn admit(&self, account: &AccountSharedData) -> bool {
+        let data_len = account.data().len();
+        let num_accounts = self.num_accounts.fetch_add(1, Ordering::Relaxed) + 1;
+        let num_bytes = self.num_bytes.fetch_add(data_len, Ordering::Relaxed) + data_len;
+        if self.max_accounts.is_some_and(|max| num_accounts > max)
+            || self.max_bytes.is_some_and(|max| num_bytes > max)
+        {
+            self.exceeded.store(true, Ordering::Relaxed);
+            self.abort.store(true, Ordering::Relaxed);
+            false
+        } else {
+            true
+        }
+    }
+
+    fn finish(
+        &self,
+        result: ScanResult<Vec<TransactionAccount>>,
+    ) -> RpcCustomResult<Vec<TransactionAccount>> {
+        if self.exceeded.load(Ordering::Relaxed) {
+            return Err(RpcCustomError::ScanResultTooLarge {
+                max_accounts: self.max_accounts,
+                max_bytes: self.max_bytes,
+            });
+        }
+        result.map_err(|e| RpcCustomError::ScanError {
+            message: e.to_string(),
+        })
+    }
 }
 
 fn optimize_filters(filters: &mut [RpcFilterType]) {
@@ -2575,24 +2732,22 @@ fn encode_account<T: ReadableAccount>(
 /// NOTE: `optimize_filters()` should almost always be called before using this method because of
 /// the requirement that `Memcmp::raw_bytes_as_ref().is_some()`.
 fn get_spl_token_owner_filter(program_id: &Pubkey, filters: &[RpcFilterType]) -> Option<Pubkey> {
-    if !is_known_spl_token_id(program_id) {
-        return None;
-    }
+    let token_program = token_programs::token_program(program_id)?;
     let mut data_size_filter: Option<u64> = None;
     let mut memcmp_filter: Option<&[u8]> = None;
     let mut owner_key: Option<Pubkey> = None;
     let mut incorrect_owner_len: Option<usize> = None;
     let mut token_account_state_filter = false;
-    let account_packed_len = TokenAccount::get_packed_len();
+    let account_packed_len = token_program.account_len;
     for filter in filters {
         match filter {
             RpcFilterType::DataSize(size) => data_size_filter = Some(*size),
             RpcFilterType::Memcmp(memcmp) => {
                 let offset = memcmp.offset();
                 if let Some(bytes) = memcmp.raw_bytes_as_ref() {
-                    if offset == account_packed_len && *program_id == token_2022::id() {
+                    if offset == account_packed_len && token_program.extensions {
                         memcmp_filter = Some(bytes);
-                    } else if offset == SPL_TOKEN_ACCOUNT_OWNER_OFFSET {
+                    } else if offset == token_program.owner_offset {
                         if bytes.len() == PUBKEY_BYTES {
                             owner_key = Pubkey::try_from(bytes).ok();
                         } else {
@@ -2626,24 +2781,22 @@ fn get_spl_token_owner_filter(program_id: &Pubkey, filters: &[RpcFilterType]) ->
 /// NOTE: `optimize_filters()` should almost always be called before using this method because of
 /// the requirement that `Memcmp::raw_bytes_as_ref().is_some()`.
 fn get_spl_token_mint_filter(program_id: &Pubkey, filters: &[RpcFilterType]) -> Option<Pubkey> {
-    if !is_known_spl_token_id(program_id) {
-        return None;
-    }
+    let token_program = token_programs::token_program(program_id)?;
     let mut data_size_filter: Option<u64> = None;
     let mut memcmp_filter: Option<&[u8]> = None;
     let mut mint: Option<Pubkey> = None;
     let mut incorrect_mint_len: Option<usize> = None;
     let mut token_account_state_filter = false;
-    let account_packed_len = TokenAccount::get_packed_len();
+    let account_packed_len = token_program.account_len;
     for filter in filters {
         match filter {
             RpcFilterType::DataSize(size) => data_size_filter = Some(*size),
             RpcFilterType::Memcmp(memcmp) => {
                 let offset = memcmp.offset();
                 if let Some(bytes) = memcmp.raw_bytes_as_ref() {
-                    if offset == account_packed_len && *program_id == token_2022::id() {
+                    if offset == account_packed_len && token_program.extensions {
                         memcmp_filter = Some(bytes);
-                    } else if offset == SPL_TOKEN_ACCOUNT_MINT_OFFSET {
+                    } else if offset == token_program.mint_offset {
                         if bytes.len() == PUBKEY_BYTES {
                             mint = Pubkey::try_from(bytes).ok();
                         } else {
@@ -3392,16 +3545,19 @@ pub mod rpc_accounts_scan {
             );
             async move {
                 let program_id = verify_pubkey(&program_id_str)?;
-                let (config, filters, with_context, sort_results) = if let Some(config) = config {
-                    (
-                        Some(config.account_config),
-                        config.filters.unwrap_or_default(),
-                        config.with_context.unwrap_or_default(),
-                        config.sort_results.unwrap_or(true),
-                    )
-                } else {
-                    (None, vec![], false, true)
-                };
+                let (config, filters, with_context, sort_results, limit, cursor) =
+                    if let Some(config) = config {
+                        (
+                            Some(config.account_config),
+                            config.filters.unwrap_or_default(),
+                            config.with_context.unwrap_or_default(),
+                            config.sort_results.unwrap_or(true),
+                            config.limit,
+                            config.cursor,
+                        )
+                    } else {
+                        (None, vec![], false, true, None, None)
+                    };
                 if filters.len() > MAX_GET_PROGRAM_ACCOUNT_FILTERS {
                     return Err(Error::invalid_params(format!(
                         "Too many filters provided; max {MAX_GET_PROGRAM_ACCOUNT_FILTERS}"
@@ -3410,8 +3566,34 @@ pub mod rpc_accounts_scan {
                 for filter in &filters {
                     verify_filter(filter)?;
                 }
-                meta.get_program_accounts(program_id, config, filters, with_context, sort_results)
-                    .await
+                let page = match (limit, cursor) {
+                    (None, None) => None,
+                    (None, Some(_)) => {
+                        return Err(Error::invalid_params("cursor requires a limit"));
+                    }
+                    (Some(limit), cursor) => {
+                        let max_limit = meta
+                            .config
+                            .max_scan_result_accounts
+                            .unwrap_or(MAX_GET_PROGRAM_ACCOUNTS_PAGE_LIMIT);
+                        if limit == 0 || limit > max_limit {
+                            return Err(Error::invalid_params(format!(
+                                "Invalid limit; must be between 1 and {max_limit}"
+                            )));
+                        }
+                        let after = cursor.as_deref().map(verify_pubkey).transpose()?;
+                        Some(ScanPage { after, limit })
+                    }
+                };
+                meta.get_program_accounts(
+                    program_id,
+                    config,
+                    filters,
+                    with_context,
+                    sort_results,
+                    page,
+                )
+                .await
             }
             .boxed()
         }
@@ -3703,6 +3885,9 @@ pub mod rpc_full {
         #[rpc(meta, name = "getMaxShredInsertSlot")]
         fn get_max_shred_insert_slot(&self, meta: Self::Metadata) -> Result<Slot>;
 
+        #[rpc(meta, name = "getForks")]
+        fn get_forks(&self, meta: Self::Metadata) -> Result<Option<RpcForks>>;
+
         #[rpc(meta, name = "requestAirdrop")]
         fn request_airdrop(
             &self,
@@ -3827,6 +4012,13 @@ pub mod rpc_full {
             meta: Self::Metadata,
             pubkey_strs: Option<Vec<String>>,
         ) -> Result<Vec<RpcPrioritizationFee>>;
+
+        #[rpc(meta, name = "getRecentPrioritizationFeeStats")]
+        fn get_recent_prioritization_fee_stats(
+            &self,
+            meta: Self::Metadata,
+            num_slots: Option<usize>,
+        ) -> Result<Vec<RpcPrioritizationFeeStats>>;
     }
 
     pub struct FullImpl;
@@ -3938,6 +4130,20 @@ pub mod rpc_full {
                     "Too many inputs provided; max {MAX_GET_SIGNATURE_STATUSES_QUERY_ITEMS}"
                 ))));
             }
+            let max_history_query_items = meta
+                .config
+                .max_signature_statuses_history_query_items
+                .unwrap_or(MAX_GET_SIGNATURE_STATUSES_HISTORY_QUERY_ITEMS);
+            if config
+                .as_ref()
+                .is_some_and(|config| config.search_transaction_history)
+                && signature_strs.len() > max_history_query_items
+            {
+                return Box::pin(future::err(Error::invalid_params(format!(
+                    "Too many inputs provided with searchTransactionHistory; max \
+                     {max_history_query_items}"
+                ))));
+            }
             let mut signatures: Vec<Signature> = vec![];
             for signature_str in signature_strs {
                 match verify_signature(&signature_str) {
@@ -3960,6 +4166,11 @@ pub mod rpc_full {
             Ok(meta.get_max_shred_insert_slot())
         }
 
+        fn get_forks(&self, meta: Self::Metadata) -> Result<Option<RpcForks>> {
+            debug!("get_forks rpc request received");
+            Ok(meta.get_forks())
+        }
+
         fn request_airdrop(
             &self,
             meta: Self::Metadata,
@@ -4224,8 +4435,23 @@ pub mod rpc_full {
                     )));
                 }
 
+                let addresses = config_accounts
+                    .addresses
+                    .iter()
+                    .map(|address_str| verify_pubkey(address_str))
+                    .collect::<Result<Vec<_>>>()?;
+                // Only accounts the simulation could have touched are worth returning
+                if let Some(pubkey) = addresses
+                    .iter()
+                    .find(|pubkey| !account_keys.iter().any(|key| key == *pubkey))
+                {
+                    return Err(Error::invalid_params(format!(
+                        "Account {pubkey} is not referenced by the transaction"
+                    )));
+                }
+
                 if result.is_err() {
-                    Some(vec![None; config_accounts.addresses.len()])
+                    Some(vec![None; addresses.len()])
                 } else {
                     let mut post_simulation_accounts_map = HashMap::new();
                     for (pubkey, data) in post_simulation_accounts {
@@ -4233,14 +4459,12 @@ pub mod rpc_full {
                     }
 
                     Some(
-                        config_accounts
-                            .addresses
+                        addresses
                             .iter()
-                            .map(|address_str| {
-                                let pubkey = verify_pubkey(address_str)?;
+                            .map(|pubkey| {
                                 get_encoded_account(
                                     bank,
-                                    &pubkey,
+                                    pubkey,
                                     accounts_encoding,
                                     None,
                                     Some(&post_simulation_accounts_map),
@@ -4617,6 +4841,24 @@ pub mod rpc_full {
                 .collect::<Result<Vec<_>>>()?;
             meta.get_recent_prioritization_fees(pubkeys)
         }
+
+        fn get_recent_prioritization_fee_stats(
+            &self,
+            meta: Self::Metadata,
+            num_slots: Option<usize>,
+        ) -> Result<Vec<RpcPrioritizationFeeStats>> {
+            debug!(
+                "get_recent_prioritization_fee_stats rpc request received: {:?} slots",
+                num_slots
+            );
+            let num_slots = num_slots.unwrap_or(MAX_GET_RECENT_PRIORITIZATION_FEE_STATS_SLOTS);
+            if num_slots > MAX_GET_RECENT_PRIORITIZATION_FEE_STATS_SLOTS {
+                return Err(Error::invalid_params(format!(
+                    "Too many slots requested; max {MAX_GET_RECENT_PRIORITIZATION_FEE_STATS_SLOTS}"
+                )));
+            }
+            meta.get_recent_prioritization_fee_stats(num_slots)
+        }
     }
 }
 
@@ -4839,6 +5081,7 @@ pub mod tests {
         solana_accounts_db::accounts_db::{AccountsDbConfig, ACCOUNTS_DB_CONFIG_FOR_TESTING},
         solana_entry::entry::next_versioned_entry,
         solana_gossip::{contact_info::ContactInfo, socketaddr},
+        solana_inline_spl::token_2022,
         solana_ledger::{
             blockstore_meta::PerfSampleV2,
             blockstore_processor::fill_blockstore_slot_with_ticks,
@@ -4848,6 +5091,7 @@ pub mod tests {
         solana_rpc_client_api::{
             custom_error::{
                 JSON_RPC_SERVER_ERROR_BLOCK_NOT_AVAILABLE,
+                JSON_RPC_SERVER_ERROR_SCAN_RESULT_TOO_LARGE,
                 JSON_RPC_SERVER_ERROR_TRANSACTION_HISTORY_NOT_AVAILABLE,
                 JSON_RPC_SERVER_ERROR_UNSUPPORTED_TRANSACTION_VERSION,
             },
@@ -4890,7 +5134,7 @@ pub mod tests {
         },
         solana_transaction_status::{
             EncodedConfirmedBlock, EncodedTransaction, EncodedTransactionWithStatusMeta,
-            TransactionDetails,
+            TransactionDetails, UiInnerInstructions,
         },
         solana_vote_program::{
             vote_instruction,
@@ -4903,7 +5147,9 @@ pub mod tests {
                 mint_close_authority::MintCloseAuthority, BaseStateWithExtensionsMut,
                 ExtensionType, StateWithExtensionsMut,
             },
-            solana_program::{program_option::COption, pubkey::Pubkey as SplTokenPubkey},
+            solana_program::{
+                program_option::COption, program_pack::Pack, pubkey::Pubkey as SplTokenPubkey,
+            },
             state::{AccountState as TokenAccountState, Mint},
         },
         std::{borrow::Cow, collections::HashMap, net::Ipv4Addr},
@@ -4966,6 +5212,7 @@ pub mod tests {
         blockstore: Arc<Blockstore>,
         bank_forks: Arc<RwLock<BankForks>>,
         max_slots: Arc<MaxSlots>,
+        latest_forks: Arc<LatestForks>,
         max_complete_transaction_status_slot: Arc<AtomicU64>,
         block_commitment_cache: Arc<RwLock<BlockCommitmentCache>>,
     }
@@ -5002,6 +5249,7 @@ pub mod tests {
                 &socketaddr!(Ipv4Addr::LOCALHOST, 1234),
             ));
             let max_slots = Arc::new(MaxSlots::default());
+            let latest_forks = Arc::new(LatestForks::default());
             // note that this means that slot 0 will always be considered complete
             let max_complete_transaction_status_slot = Arc::new(AtomicU64::new(0));
             let max_complete_rewards_slot = Arc::new(AtomicU64::new(0));
@@ -5028,6 +5276,7 @@ pub mod tests {
                 optimistically_confirmed_bank,
                 Arc::new(RwLock::new(LargestAccountsCache::new(30))),
                 max_slots.clone(),
+                latest_forks.clone(),
                 Arc::new(LeaderScheduleCache::new_from_bank(&bank)),
                 max_complete_transaction_status_slot.clone(),
                 max_complete_rewards_slot,
@@ -5051,6 +5300,7 @@ pub mod tests {
                 bank_forks,
                 blockstore,
                 max_slots,
+                latest_forks,
                 max_complete_transaction_status_slot,
                 block_commitment_cache,
             }
@@ -6159,6 +6409,170 @@ pub mod tests {
         assert_eq!(result.len(), 0);
     }
 
+    #[test]
+    fn test_rpc_get_program_accounts_pagination() {
+        const NUM_ACCOUNTS: usize = 3_000;
+        const DATA_LEN: usize = 8;
+
+        let program_id = Pubkey::new_unique();
+        let account_indexes = [
+            AccountSecondaryIndexes::default(),
+            AccountSecondaryIndexes {
+                keys: None,
+                indexes: HashSet::from([AccountIndex::ProgramId]),
+            },
+        ];
+        for account_indexes in account_indexes {
+            let rpc = RpcHandler::start_with_config(JsonRpcConfig {
+                account_indexes,
+                max_scan_result_accounts: Some(1_000),
+                max_scan_result_bytes: Some(1_000 * DATA_LEN),
+                ..JsonRpcConfig::default()
+            });
+            let bank = rpc.working_bank();
+            let mut pubkeys = (0..NUM_ACCOUNTS)
+                .map(|i| {
+                    let pubkey = Pubkey::new_unique();
+                    let mut account = AccountSharedData::new(42, DATA_LEN, &program_id);
+                    account.data_as_mut_slice()[0] = (i % 3) as u8;
+                    bank.store_account(&pubkey, &account);
+                    (pubkey, (i % 3) as u8)
+                })
+                .collect::<Vec<_>>();
+            pubkeys.sort_unstable();
+
+            let get_page = |params: Value| -> Vec<RpcKeyedAccount> {
+                let request = create_test_request(
+                    "getProgramAccounts",
+                    Some(json!([program_id.to_string(), params])),
+                );
+                parse_success_result(rpc.handle_request_sync(request))
+            };
+
+            // Pages continue from the cursor and together return every account in order
+            let mut cursor = None::<String>;
+            let mut paged = vec![];
+            loop {
+                let page = get_page(json!({"limit": 700, "cursor": cursor}));
+                assert!(page.len() <= 700);
+                let Some(last) = page.last() else {
+                    break;
+                };
+                cursor = Some(last.pubkey.clone());
+                paged.extend(page.into_iter().map(|account| account.pubkey));
+            }
+            let expected = pubkeys
+                .iter()
+                .map(|(pubkey, _)| pubkey.to_string())
+                .collect::<Vec<_>>();
+            assert_eq!(paged, expected);
+
+            // Filters apply before paging
+            let filter = json!([{"memcmp": {
+                "offset": 0,
+                "bytes": bs58::encode(vec![1]).into_string(),
+            }}]);
+            let mut cursor = None::<String>;
+            let mut paged = vec![];
+            loop {
+                let page = get_page(json!({
+                    "filters": filter,
+                    "limit": 300,
+                    "cursor": cursor,
+                    "withContext": false,
+                }));
+                let Some(last) = page.last() else {
+                    break;
+                };
+                cursor = Some(last.pubkey.clone());
+                paged.extend(page.into_iter().map(|account| account.pubkey));
+            }
+            let expected = pubkeys
+                .iter()
+                .filter(|(_, tag)| *tag == 1)
+                .map(|(pubkey, _)| pubkey.to_string())
+                .collect::<Vec<_>>();
+            assert_eq!(paged, expected);
+
+            // Pages are returned with the context they were evaluated at
+            let request = create_test_request(
+                "getProgramAccounts",
+                Some(json!([
+                    program_id.to_string(),
+                    {"limit": 10, "withContext": true},
+                ])),
+            );
+            let result: RpcResponse<Vec<RpcKeyedAccount>> =
+                parse_success_result(rpc.handle_request_sync(request));
+            assert_eq!(result.context.slot, bank.slot());
+            assert_eq!(result.value.len(), 10);
+
+            // Unpaged requests over the guard's limits fail instead of returning everything
+            let request =
+                create_test_request("getProgramAccounts", Some(json!([program_id.to_string()])));
+            let (code, _) = parse_failure_response(rpc.handle_request_sync(request));
+            assert_eq!(code, JSON_RPC_SERVER_ERROR_SCAN_RESULT_TOO_LARGE);
+
+            // A narrow enough filter stays within the limits
+            let request = create_test_request(
+                "getProgramAccounts",
+                Some(json!([
+                    program_id.to_string(),
+                    {"filters": [{"memcmp": {
+                        "offset": 0,
+                        "bytes": bs58::encode(vec![1]).into_string(),
+                    }}, {"memcmp": {
+                        "offset": 1,
+                        "bytes": bs58::encode(vec![1]).into_string(),
+                    }}]},
+                ])),
+            );
+            let result: Vec<RpcKeyedAccount> =
+                parse_success_result(rpc.handle_request_sync(request));
+            assert!(result.is_empty());
+
+            // Page sizes are capped by the guard
+            for params in [
+                json!({"limit": 0}),
+                json!({"limit": 1_001}),
+                json!({"cursor": pubkeys[0].0.to_string()}),
+                json!({"limit": 10, "cursor": "not a pubkey"}),
+            ] {
+                let request = create_test_request(
+                    "getProgramAccounts",
+                    Some(json!([program_id.to_string(), params])),
+                );
+                let (code, _) = parse_failure_response(rpc.handle_request_sync(request));
+                assert_eq!(code, ErrorCode::InvalidParams.code());
+            }
+        }
+
+        // Without a scan result limit, page sizes are still capped
+        let rpc = RpcHandler::start();
+        let request = create_test_request(
+            "getProgramAccounts",
+            Some(json!([
+                program_id.to_string(),
+                {"limit": MAX_GET_PROGRAM_ACCOUNTS_PAGE_LIMIT + 1},
+            ])),
+        );
+        let response = parse_failure_response(rpc.handle_request_sync(request));
+        let expected = (
+            ErrorCode::InvalidParams.code(),
+            format!("Invalid limit; must be between 1 and {MAX_GET_PROGRAM_ACCOUNTS_PAGE_LIMIT}"),
+        );
+        assert_eq!(response, expected);
+        let request = create_test_request(
+            "getProgramAccounts",
+            Some(json!([
+                program_id.to_string(),
+                {"limit": MAX_GET_PROGRAM_ACCOUNTS_PAGE_LIMIT},
+            ])),
+        );
+        let result: Vec<RpcKeyedAccount> = parse_success_result(rpc.handle_request_sync(request));
+        assert!(result.is_empty());
+    }
+
     #[test]
     fn test_rpc_simulate_bundle_happy_path() {
         // 1. setup
@@ -6336,14 +6750,12 @@ pub mod tests {
                      "sigVerify": true,
                      "accounts": {{
                        "encoding": "jsonParsed",
-                       "addresses": ["{}", "{}"]
+                       "addresses": ["{}"]
                      }}
                    }}
                  ]
             }}"#,
-            tx_serialized_encoded,
-            solana_pubkey::new_rand(),
-            bob_pubkey,
+            tx_serialized_encoded, bob_pubkey,
         );
         let res = io.handle_request_sync(&req, meta.clone());
         let expected = json!({
@@ -6352,7 +6764,6 @@ pub mod tests {
                 "context": {"slot": 0, "apiVersion": RpcApiVersion::default()},
                 "value":{
                     "accounts": [
-                        null,
                         {
                             "data": ["", "base64"],
                             "executable": false,
@@ -6417,6 +6828,34 @@ pub mod tests {
             .expect("actual response deserialization");
         assert_eq!(result, expected);
 
+        // Account not referenced by the transaction
+        let unreferenced_pubkey = solana_pubkey::new_rand();
+        let req = format!(
+            r#"{{"jsonrpc":"2.0","id":1,"method":"simulateTransaction","params":["{}", {}]}}"#,
+            tx_serialized_encoded,
+            json!({
+                "accounts": {
+                    "addresses": [bob_pubkey.to_string(), unreferenced_pubkey.to_string()],
+                },
+            })
+        );
+        let res = io.handle_request_sync(&req, meta.clone());
+        let expected = json!({
+            "jsonrpc":"2.0",
+            "error": {
+                "code": error::ErrorCode::InvalidParams.code(),
+                "message": format!(
+                    "Account {unreferenced_pubkey} is not referenced by the transaction"
+                ),
+            },
+            "id":1
+        });
+        let expected: Response =
+            serde_json::from_value(expected).expect("expected response deserialization");
+        let result: Response = serde_json::from_str(&res.expect("actual response"))
+            .expect("actual response deserialization");
+        assert_eq!(result, expected);
+
         // Bad signature with sigVerify=true
         let req = format!(
             r#"{{"jsonrpc":"2.0","id":1,"method":"simulateTransaction","params":["{tx_badsig_serialized_encoded}", {{"sigVerify": true}}]}}"#,
@@ -6587,6 +7026,34 @@ pub mod tests {
         let result: Response = serde_json::from_str(&res.expect("actual response"))
             .expect("actual response deserialization");
         assert_eq!(result, expected);
+
+        // Bad recent blockhash with replaceRecentBlockhash=true, returning the post-simulation
+        // balances of both sides of the transfer
+        let mint_pubkey = rpc.mint_keypair.pubkey();
+        let req = format!(
+            r#"{{"jsonrpc":"2.0","id":1,"method":"simulateTransaction","params":["{}", {}]}}"#,
+            tx_invalid_recent_blockhash,
+            json!({
+                "replaceRecentBlockhash": true,
+                "accounts": {
+                    "addresses": [mint_pubkey.to_string(), bob_pubkey.to_string()],
+                },
+            })
+        );
+        let res = io.handle_request_sync(&req, meta.clone());
+        let result: Value = serde_json::from_str(&res.expect("actual response"))
+            .expect("actual response deserialization");
+        let value = &result["result"]["value"];
+        assert_eq!(value["err"], Value::Null);
+        assert_eq!(
+            value["accounts"][0]["lamports"],
+            json!(
+                bank.get_balance(&mint_pubkey)
+                    - rent_exempt_amount
+                    - bank.get_lamports_per_signature()
+            )
+        );
+        assert_eq!(value["accounts"][1]["lamports"], json!(rent_exempt_amount));
     }
 
     #[test]
@@ -6669,14 +7136,12 @@ pub mod tests {
                      "sigVerify": true,
                      "accounts": {{
                        "encoding": "jsonParsed",
-                       "addresses": ["{}", "{}"]
+                       "addresses": ["{}"]
                      }}
                    }}
                  ]
             }}"#,
-            tx_serialized_encoded,
-            solana_pubkey::new_rand(),
-            token_account_pubkey,
+            tx_serialized_encoded, token_account_pubkey,
         );
         let res = io.handle_request_sync(&req, meta.clone());
         let expected = json!({
@@ -6685,7 +7150,6 @@ pub mod tests {
                 "context": {"slot": 0, "apiVersion": RpcApiVersion::default()},
                 "value":{
                     "accounts": [
-                        null,
                         {
                             "data": {
                                 "parsed": {
@@ -7049,6 +7513,98 @@ pub mod tests {
         );
     }
 
+    #[test]
+    fn test_rpc_get_signature_statuses_history() {
+        let rpc = RpcHandler::start();
+        let signatures = rpc.create_test_transactions_and_populate_blockstore();
+        // Age the transactions out of the status cache, leaving only their
+        // statuses in the rooted ledger
+        rpc.working_bank().clear_signatures();
+
+        let request = |signatures: &[Signature], search_transaction_history: bool| {
+            let signatures: Vec<_> = signatures.iter().map(Signature::to_string).collect();
+            create_test_request(
+                "getSignatureStatuses",
+                Some(json!([
+                    signatures,
+                    {"searchTransactionHistory": search_transaction_history},
+                ])),
+            )
+        };
+        let result: RpcResponse<Vec<Option<TransactionStatus>>> =
+            parse_success_result(rpc.handle_request_sync(request(&signatures[..2], false)));
+        assert_eq!(result.value, vec![None, None]);
+
+        let result: RpcResponse<Vec<Option<TransactionStatus>>> =
+            parse_success_result(rpc.handle_request_sync(request(&signatures[..2], true)));
+        let ix_error = TransactionError::InstructionError(0, InstructionError::Custom(1));
+        assert_eq!(
+            result.value,
+            vec![
+                Some(TransactionStatus {
+                    slot: 0,
+                    confirmations: None,
+                    status: Ok(()),
+                    err: None,
+                    confirmation_status: Some(TransactionConfirmationStatus::Finalized),
+                }),
+                Some(TransactionStatus {
+                    slot: 0,
+                    confirmations: None,
+                    status: Err(ix_error.clone()),
+                    err: Some(ix_error),
+                    confirmation_status: Some(TransactionConfirmationStatus::Finalized),
+                }),
+            ]
+        );
+
+        // History search is limited to fewer signatures per request
+        let unknown_signatures: Vec<_> = (0..=MAX_GET_SIGNATURE_STATUSES_HISTORY_QUERY_ITEMS)
+            .map(|_| Signature::new_unique())
+            .collect();
+        let result: RpcResponse<Vec<Option<TransactionStatus>>> =
+            parse_success_result(rpc.handle_request_sync(request(&unknown_signatures, false)));
+        assert!(result.value.iter().all(Option::is_none));
+        assert_eq!(
+            parse_failure_response(rpc.handle_request_sync(request(&unknown_signatures, true))),
+            (
+                ErrorCode::InvalidParams.code(),
+                format!(
+                    "Too many inputs provided with searchTransactionHistory; max \
+                     {MAX_GET_SIGNATURE_STATUSES_HISTORY_QUERY_ITEMS}"
+                ),
+            )
+        );
+    }
+
+    #[test]
+    fn test_rpc_get_signature_statuses_history_configured_limit() {
+        let rpc = RpcHandler::start_with_config(JsonRpcConfig {
+            max_signature_statuses_history_query_items: Some(2),
+            ..JsonRpcConfig::default()
+        });
+        let request = |num_signatures: usize| {
+            let signatures: Vec<_> = (0..num_signatures)
+                .map(|_| Signature::new_unique().to_string())
+                .collect();
+            create_test_request(
+                "getSignatureStatuses",
+                Some(json!([signatures, {"searchTransactionHistory": true}])),
+            )
+        };
+
+        let result: RpcResponse<Vec<Option<TransactionStatus>>> =
+            parse_success_result(rpc.handle_request_sync(request(2)));
+        assert_eq!(result.value, vec![None, None]);
+        assert_eq!(
+            parse_failure_response(rpc.handle_request_sync(request(3))),
+            (
+                ErrorCode::InvalidParams.code(),
+                "Too many inputs provided with searchTransactionHistory; max 2".to_string(),
+            )
+        );
+    }
+
     #[test]
     fn test_rpc_fail_request_airdrop() {
         let RpcHandler { meta, io, .. } = RpcHandler::start();
@@ -7139,6 +7695,7 @@ pub mod tests {
             optimistically_confirmed_bank,
             Arc::new(RwLock::new(LargestAccountsCache::new(30))),
             Arc::new(MaxSlots::default()),
+            Arc::new(LatestForks::default()),
             Arc::new(LeaderScheduleCache::default()),
             Arc::new(AtomicU64::default()),
             Arc::new(AtomicU64::default()),
@@ -7352,6 +7909,78 @@ pub mod tests {
         assert_eq!(result, 43);
     }
 
+    #[test]
+    fn test_rpc_get_forks() {
+        let rpc = RpcHandler::start();
+        let request = create_test_request("getForks", None);
+        let result: Value = parse_success_result(rpc.handle_request_sync(request));
+        assert_eq!(result, Value::Null);
+
+        rpc.latest_forks.update(RpcForks {
+            root: 0,
+            heaviest_slot: 3,
+            last_vote_slot: Some(2),
+            total_stake: 100,
+            forks: vec![
+                RpcFork {
+                    slot: 0,
+                    parent_slot: None,
+                    stake: 100,
+                    is_heaviest: false,
+                    is_locked_out: false,
+                },
+                RpcFork {
+                    slot: 3,
+                    parent_slot: Some(0),
+                    stake: 60,
+                    is_heaviest: true,
+                    is_locked_out: false,
+                },
+                RpcFork {
+                    slot: 4,
+                    parent_slot: Some(0),
+                    stake: 40,
+                    is_heaviest: false,
+                    is_locked_out: true,
+                },
+            ],
+        });
+        let request = create_test_request("getForks", None);
+        let result: Value = parse_success_result(rpc.handle_request_sync(request));
+        assert_eq!(
+            result,
+            json!({
+                "root": 0,
+                "heaviestSlot": 3,
+                "lastVoteSlot": 2,
+                "totalStake": 100,
+                "forks": [
+                    {
+                        "slot": 0,
+                        "parentSlot": null,
+                        "stake": 100,
+                        "isHeaviest": false,
+                        "isLockedOut": false,
+                    },
+                    {
+                        "slot": 3,
+                        "parentSlot": 0,
+                        "stake": 60,
+                        "isHeaviest": true,
+                        "isLockedOut": false,
+                    },
+                    {
+                        "slot": 4,
+                        "parentSlot": 0,
+                        "stake": 40,
+                        "isHeaviest": false,
+                        "isLockedOut": true,
+                    },
+                ],
+            })
+        );
+    }
+
     #[test]
     fn test_rpc_get_version() {
         let rpc = RpcHandler::start();
@@ -7417,6 +8046,7 @@ pub mod tests {
             optimistically_confirmed_bank,
             Arc::new(RwLock::new(LargestAccountsCache::new(30))),
             Arc::new(MaxSlots::default()),
+            Arc::new(LatestForks::default()),
             Arc::new(LeaderScheduleCache::default()),
             Arc::new(AtomicU64::default()),
             Arc::new(AtomicU64::default()),
@@ -7685,6 +8315,42 @@ pub mod tests {
         assert!(confirmed_block.transactions.is_none());
         assert!(confirmed_block.signatures.is_none());
         assert_eq!(confirmed_block.rewards.unwrap(), vec![]);
+
+        // Enough to summarize a block: the signatures, account keys and fees of its
+        // transactions, but not their logs or instructions
+        let request = create_test_request(
+            "getBlock",
+            Some(json!([
+                0u64,
+                RpcBlockConfig {
+                    encoding: None,
+                    transaction_details: Some(TransactionDetails::Accounts),
+                    rewards: Some(true),
+                    commitment: None,
+                    max_supported_transaction_version: None,
+                },
+            ])),
+        );
+        let result: Option<UiConfirmedBlock> =
+            parse_success_result(rpc.handle_request_sync(request));
+        let confirmed_block = result.unwrap();
+        assert!(confirmed_block.signatures.is_none());
+        assert_eq!(confirmed_block.rewards.unwrap(), vec![]);
+        let transactions = confirmed_block.transactions.unwrap();
+        assert_eq!(transactions.len(), 2);
+        for (transaction, signature) in transactions.iter().zip(&confirmed_block_signatures) {
+            let EncodedTransaction::Accounts(accounts_list) = &transaction.transaction else {
+                panic!("unexpected transaction encoding");
+            };
+            assert_eq!(accounts_list.signatures, vec![signature.to_string()]);
+            assert!(!accounts_list.account_keys.is_empty());
+            let meta = transaction.meta.as_ref().unwrap();
+            assert!(Option::<&Vec<String>>::from(meta.log_messages.as_ref()).is_none());
+            assert!(
+                Option::<&Vec<UiInnerInstructions>>::from(meta.inner_instructions.as_ref())
+                    .is_none()
+            );
+        }
     }
 
     #[test]
@@ -8907,6 +9573,27 @@ pub mod tests {
         )
         .is_none());
 
+        // Upgraded token program ids share the token-v3 layout
+        assert_eq!(
+            get_spl_token_owner_filter(
+                &solana_inline_spl::token::program_v3_4_0::id(),
+                &[
+                    RpcFilterType::Memcmp(Memcmp::new_raw_bytes(32, owner.to_bytes().to_vec())),
+                    RpcFilterType::DataSize(165)
+                ],
+            )
+            .unwrap(),
+            owner
+        );
+        assert!(get_spl_token_owner_filter(
+            &solana_inline_spl::token::program_v3_4_0::id(),
+            &[
+                RpcFilterType::Memcmp(Memcmp::new_raw_bytes(32, owner.to_bytes().to_vec())),
+                RpcFilterType::Memcmp(Memcmp::new_raw_bytes(165, vec![ACCOUNTTYPE_ACCOUNT])),
+            ],
+        )
+        .is_none());
+
         // Filtering on mint instead of owner
         assert!(get_spl_token_owner_filter(
             &solana_inline_spl::token::id(),
@@ -9074,6 +9761,7 @@ pub mod tests {
             optimistically_confirmed_bank.clone(),
             Arc::new(RwLock::new(LargestAccountsCache::new(30))),
             Arc::new(MaxSlots::default()),
+            Arc::new(LatestForks::default()),
             Arc::new(LeaderScheduleCache::default()),
             max_complete_transaction_status_slot,
             max_complete_rewards_slot,
@@ -9559,4 +10247,75 @@ pub mod tests {
             ],
         );
     }
+
+    #[test]
+    fn test_rpc_get_recent_prioritization_fee_stats() {
+        fn prioritized_transfers(prices: &[u64]) -> Vec<Transaction> {
+            prices
+                .iter()
+                .map(|price| {
+                    let payer = Pubkey::new_unique();
+                    Transaction::new_unsigned(Message::new(
+                        &[
+                            system_instruction::transfer(&payer, &Pubkey::new_unique(), 1),
+                            ComputeBudgetInstruction::set_compute_unit_price(*price),
+                        ],
+                        Some(&payer),
+                    ))
+                })
+                .collect()
+        }
+
+        let rpc = RpcHandler::start();
+        let request = create_test_request("getRecentPrioritizationFeeStats", None);
+        let response: Vec<RpcPrioritizationFeeStats> =
+            parse_success_result(rpc.handle_request_sync(request));
+        assert!(response.is_empty());
+
+        let slot0 = rpc.working_bank().slot();
+        rpc.update_prioritization_fee_cache(prioritized_transfers(&[20, 0, 30, 10]));
+        let cache = rpc.get_prioritization_fee_cache();
+        cache.finalize_priority_fee(slot0, rpc.working_bank().bank_id());
+
+        rpc.advance_bank_to_confirmed_slot(1);
+        let slot1 = rpc.working_bank().slot();
+        rpc.update_prioritization_fee_cache(prioritized_transfers(&[100, 5, 7]));
+        let cache = rpc.get_prioritization_fee_cache();
+        cache.finalize_priority_fee(slot1, rpc.working_bank().bank_id());
+        while cache.available_block_count() < 2 {
+            std::thread::sleep(std::time::Duration::from_millis(100));
+        }
+
+        let stats0 = RpcPrioritizationFeeStats {
+            slot: slot0,
+            transaction_count: 4,
+            min_prioritization_fee: 0,
+            median_prioritization_fee: 10,
+            max_prioritization_fee: 30,
+        };
+        let stats1 = RpcPrioritizationFeeStats {
+            slot: slot1,
+            transaction_count: 3,
+            min_prioritization_fee: 5,
+            median_prioritization_fee: 7,
+            max_prioritization_fee: 100,
+        };
+        let request = create_test_request("getRecentPrioritizationFeeStats", None);
+        let response: Vec<RpcPrioritizationFeeStats> =
+            parse_success_result(rpc.handle_request_sync(request));
+        assert_eq!(response, vec![stats0, stats1]);
+
+        let request = create_test_request("getRecentPrioritizationFeeStats", Some(json!([1])));
+        let response: Vec<RpcPrioritizationFeeStats> =
+            parse_success_result(rpc.handle_request_sync(request));
+        assert_eq!(response, vec![stats1]);
+
+        // The window is bounded
+        let request = create_test_request(
+            "getRecentPrioritizationFeeStats",
+            Some(json!([MAX_GET_RECENT_PRIORITIZATION_FEE_STATS_SLOTS + 1])),
+        );
+        let (code, _) = parse_failure_response(rpc.handle_request_sync(request));
+        assert_eq!(code, ErrorCode::InvalidParams.code());
+    }
 }
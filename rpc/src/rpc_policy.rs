@@ -0,0 +1,550 @@
+//! The `rpc_policy` module applies the node operator's restrictions on JSON RPC
+//! calls: disabled methods, per-method concurrency caps and a request rate
+//! limit. Calls are checked by a jsonrpc middleware before any handler runs.
+//!
+//! The request rate is limited per client. The HTTP server does not expose
+//! the peer address of a connection, so clients are told apart by the address
+//! in the header named by [`RpcPolicyConfig::client_address_header`], as set
+//! by the operator's reverse proxy. Calls without that address share a single
+//! limit.
+
+use {
+    crate::rpc::JsonRpcRequestProcessor,
+    jsonrpc_core::{
+        futures::future::{self, BoxFuture, Either, FutureExt},
+        Call, Failure, MethodCall, Middleware, Notification, Output, Response,
+    },
+    solana_metrics::datapoint_info,
+    solana_rpc_client_api::custom_error::RpcCustomError,
+    std::{
+        collections::{HashMap, HashSet},
+        future::Future,
+        net::{IpAddr, SocketAddr},
+        sync::{
+            atomic::{AtomicUsize, Ordering},
+            Arc, Mutex, Weak,
+        },
+        time::{Duration, Instant},
+    },
+    tokio::runtime::Runtime,
+};
+
+/// Methods which are never restricted, so that health checks and monitoring
+/// keep working whatever the policy
+pub const UNRESTRICTED_METHODS: &[&str] = &["getHealth", "getIdentity"];
+
+const REJECTIONS_REPORT_INTERVAL: Duration = Duration::from_secs(10);
+
+/// A client's token bucket is full again once it has been idle this long, so
+/// it can be dropped without changing how the client is limited
+const RATE_LIMIT_IDLE_TIMEOUT: Duration = Duration::from_secs(1);
+
+/// Maximum number of clients with a rate limit of their own. Further clients
+/// share the limit of calls without a client address until buckets are evicted.
+const MAX_RATE_LIMITED_CLIENTS: usize = 100_000;
+
+#[derive(Debug, Default, Clone)]
+pub struct RpcPolicyConfig {
+    /// Methods rejected with a "method disabled by node operator" error
+    pub disabled_methods: HashSet<String>,
+    /// Maximum number of calls of a method handled at once
+    pub max_concurrent_calls: HashMap<String, usize>,
+    /// Maximum number of calls per second, per client
+    pub max_requests_per_second: Option<u32>,
+    /// Header holding the client address, e.g. X-Forwarded-For. Only set this
+    /// when a trusted proxy in front of the node sets the header.
+    pub client_address_header: Option<String>,
+}
+
+/// Returns the client address in the value of a client address header. When
+/// the header lists several addresses, the last one was added by the proxy
+/// closest to the node; the others may have been supplied by the client.
+pub fn client_address_from_header(value: &str) -> Option<IpAddr> {
+    let address = value.rsplit(',').next()?.trim();
+    address
+        .parse()
+        .or_else(|_| address.parse::<SocketAddr>().map(|address| address.ip()))
+        .ok()
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum RpcPolicyRejection {
+    MethodDisabled,
+    MethodBusy,
+    RateLimited,
+}
+
+impl RpcPolicyRejection {
+    fn as_str(&self) -> &'static str {
+        match self {
+            Self::MethodDisabled => "method_disabled",
+            Self::MethodBusy => "method_busy",
+            Self::RateLimited => "rate_limited",
+        }
+    }
+
+    fn into_error(self, method: String) -> jsonrpc_core::Error {
+        match self {
+            Self::MethodDisabled => RpcCustomError::MethodDisabled { method },
+            Self::MethodBusy => RpcCustomError::MethodBusy { method },
+            Self::RateLimited => RpcCustomError::RateLimited,
+        }
+        .into()
+    }
+}
+
+/// Held while a call with a concurrency cap is being handled
+#[must_use]
+pub struct CallPermit(Option<Arc<AtomicUsize>>);
+
+impl Drop for CallPermit {
+    fn drop(&mut self) {
+        if let Some(calls) = &self.0 {
+            calls.fetch_sub(1, Ordering::Relaxed);
+        }
+    }
+}
+
+/// Token bucket holding up to a second's worth of requests
+struct TokenBucket {
+    tokens: f64,
+    last_refill: Instant,
+}
+
+impl TokenBucket {
+    fn try_acquire(&mut self, max_requests_per_second: f64, now: Instant) -> bool {
+        let refill = now.duration_since(self.last_refill).as_secs_f64() * max_requests_per_second;
+        self.tokens = (self.tokens + refill).min(max_requests_per_second);
+        self.last_refill = now;
+        if self.tokens >= 1.0 {
+            self.tokens -= 1.0;
+            true
+        } else {
+            false
+        }
+    }
+}
+
+/// Token buckets of the clients seen recently
+struct RateLimiter {
+    max_requests_per_second: f64,
+    max_clients: usize,
+    buckets: HashMap<Option<IpAddr>, TokenBucket>,
+    last_eviction: Instant,
+}
+
+impl RateLimiter {
+    fn new(max_requests_per_second: u32, max_clients: usize) -> Self {
+        Self {
+            max_requests_per_second: f64::from(max_requests_per_second),
+            max_clients,
+            buckets: HashMap::new(),
+            last_eviction: Instant::now(),
+        }
+    }
+
+    fn try_acquire(&mut self, client: Option<IpAddr>) -> bool {
+        let now = Instant::now();
+        if now.duration_since(self.last_eviction) >= RATE_LIMIT_IDLE_TIMEOUT {
+            self.buckets.retain(|_, bucket| {
+                now.duration_since(bucket.last_refill) < RATE_LIMIT_IDLE_TIMEOUT
+            });
+            self.last_eviction = now;
+        }
+        let client =
+            if self.buckets.len() >= self.max_clients && !self.buckets.contains_key(&client) {
+                None
+            } else {
+                client
+            };
+        let max_requests_per_second = self.max_requests_per_second;
+        self.buckets
+            .entry(client)
+            .or_insert_with(|| TokenBucket {
+                tokens: max_requests_per_second,
+                last_refill: now,
+            })
+            .try_acquire(max_requests_per_second, now)
+    }
+}
+
+#[derive(Default)]
+struct Rejections(HashMap<(RpcPolicyRejection, String), u64>);
+
+impl Rejections {
+    fn report(&mut self) {
+        for ((rejection, method), count) in self.0.drain() {
+            datapoint_info!(
+                "rpc-policy-rejections",
+                "reason" => rejection.as_str(),
+                "method" => method,
+                ("count", count, i64),
+            );
+        }
+    }
+}
+
+impl Drop for Rejections {
+    fn drop(&mut self) {
+        self.report();
+    }
+}
+
+/// Reports the rejections counted since the last report every `interval`,
+/// until the policy counting them is dropped
+async fn report_rejections(rejections: Weak<Mutex<Rejections>>, interval: Duration) {
+    let mut interval = tokio::time::interval_at(tokio::time::Instant::now() + interval, interval);
+    loop {
+        interval.tick().await;
+        let Some(rejections) = rejections.upgrade() else {
+            break;
+        };
+        rejections.lock().unwrap().report();
+    }
+}
+
+pub struct RpcPolicy {
+    disabled_methods: HashSet<String>,
+    concurrent_calls: HashMap<String, (usize, Arc<AtomicUsize>)>,
+    rate_limiter: Option<Mutex<RateLimiter>>,
+    rejections: Arc<Mutex<Rejections>>,
+}
+
+impl RpcPolicy {
+    pub fn new(config: RpcPolicyConfig) -> Self {
+        let RpcPolicyConfig {
+            disabled_methods,
+            max_concurrent_calls,
+            max_requests_per_second,
+            client_address_header: _,
+        } = config;
+        Self {
+            disabled_methods,
+            concurrent_calls: max_concurrent_calls
+                .into_iter()
+                .map(|(method, max_calls)| (method, (max_calls, Arc::default())))
+                .collect(),
+            rate_limiter: max_requests_per_second
+                .map(|max| Mutex::new(RateLimiter::new(max, MAX_RATE_LIMITED_CLIENTS))),
+            rejections: Arc::default(),
+        }
+    }
+
+    /// Report the rejections on `runtime` every 10 seconds, whether or not
+    /// calls keep being rejected
+    pub fn spawn_rejections_reporter(&self, runtime: &Runtime) {
+        runtime.spawn(report_rejections(
+            Arc::downgrade(&self.rejections),
+            REJECTIONS_REPORT_INTERVAL,
+        ));
+    }
+
+    /// Check whether a call of `method` from `client` may be handled now. The
+    /// returned permit must be held until the call is complete.
+    pub fn check_call(
+        &self,
+        method: &str,
+        client: Option<IpAddr>,
+    ) -> Result<CallPermit, RpcPolicyRejection> {
+        if UNRESTRICTED_METHODS.contains(&method) {
+            return Ok(CallPermit(None));
+        }
+        if self.disabled_methods.contains(method) {
+            return Err(self.reject(RpcPolicyRejection::MethodDisabled, method));
+        }
+        // Check the concurrency cap first, so that busy calls don't use up
+        // the client's rate limit. The permit is released if the rate limit
+        // rejects the call.
+        let permit = match self.concurrent_calls.get(method) {
+            Some((max_calls, calls)) => {
+                if calls.fetch_add(1, Ordering::Relaxed) >= *max_calls {
+                    calls.fetch_sub(1, Ordering::Relaxed);
+                    return Err(self.reject(RpcPolicyRejection::MethodBusy, method));
+                }
+                CallPermit(Some(calls.clone()))
+            }
+            None => CallPermit(None),
+        };
+        if let Some(rate_limiter) = &self.rate_limiter {
+            if !rate_limiter.lock().unwrap().try_acquire(client) {
+                return Err(self.reject(RpcPolicyRejection::RateLimited, method));
+            }
+        }
+        Ok(permit)
+    }
+
+    fn reject(&self, rejection: RpcPolicyRejection, method: &str) -> RpcPolicyRejection {
+        *self
+            .rejections
+            .lock()
+            .unwrap()
+            .0
+            .entry((rejection, method.to_string()))
+            .or_default() += 1;
+        rejection
+    }
+}
+
+impl Middleware<JsonRpcRequestProcessor> for RpcPolicy {
+    type Future = BoxFuture<'static, Option<Response>>;
+    type CallFuture = BoxFuture<'static, Option<Output>>;
+
+    fn on_call<F, X>(
+        &self,
+        call: Call,
+        meta: JsonRpcRequestProcessor,
+        next: F,
+    ) -> Either<Self::CallFuture, X>
+    where
+        F: Fn(Call, JsonRpcRequestProcessor) -> X + Send + Sync,
+        X: Future<Output = Option<Output>> + Send + 'static,
+    {
+        let method = match &call {
+            Call::MethodCall(MethodCall { method, .. })
+            | Call::Notification(Notification { method, .. }) => method.clone(),
+            Call::Invalid { .. } => return Either::Right(next(call, meta)),
+        };
+        match self.check_call(&method, meta.client_address()) {
+            Ok(CallPermit(None)) => Either::Right(next(call, meta)),
+            Ok(permit) => Either::Left(
+                next(call, meta)
+                    .map(move |output| {
+                        drop(permit);
+                        output
+                    })
+                    .boxed(),
+            ),
+            Err(rejection) => {
+                // Notifications get no response, rejected or not
+                let output = match call {
+                    Call::MethodCall(MethodCall { jsonrpc, id, .. }) => {
+                        Some(Output::Failure(Failure {
+                            jsonrpc,
+                            error: rejection.into_error(method),
+                            id,
+                        }))
+                    }
+                    _ => None,
+                };
+                Either::Left(future::ready(output).boxed())
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use {
+        super::*,
+        std::{
+            net::{IpAddr, Ipv4Addr},
+            sync::Barrier,
+            thread,
+        },
+    };
+
+    #[test]
+    fn test_disabled_method() {
+        let policy = RpcPolicy::new(RpcPolicyConfig {
+            disabled_methods: HashSet::from([
+                "getProgramAccounts".to_string(),
+                "getHealth".to_string(),
+            ]),
+            ..RpcPolicyConfig::default()
+        });
+        assert_eq!(
+            policy.check_call("getProgramAccounts", None).err(),
+            Some(RpcPolicyRejection::MethodDisabled)
+        );
+        assert!(policy.check_call("getSlot", None).is_ok());
+        // unrestricted methods can't be disabled
+        assert!(policy.check_call("getHealth", None).is_ok());
+    }
+
+    #[test]
+    fn test_concurrency_cap() {
+        let max_calls = 2;
+        let num_threads = 8;
+        let policy = Arc::new(RpcPolicy::new(RpcPolicyConfig {
+            max_concurrent_calls: HashMap::from([("getProgramAccounts".to_string(), max_calls)]),
+            ..RpcPolicyConfig::default()
+        }));
+        let acquired = Arc::new(Barrier::new(num_threads));
+        let results: Vec<_> = (0..num_threads)
+            .map(|_| {
+                let policy = policy.clone();
+                let acquired = acquired.clone();
+                thread::spawn(move || {
+                    let permit = policy.check_call("getProgramAccounts", None);
+                    // hold every permit until all threads have tried
+                    acquired.wait();
+                    permit.map(drop)
+                })
+            })
+            .collect::<Vec<_>>()
+            .into_iter()
+            .map(|thread| thread.join().unwrap())
+            .collect();
+
+        assert_eq!(
+            results.iter().filter(|result| result.is_ok()).count(),
+            max_calls
+        );
+        assert!(results
+            .iter()
+            .filter_map(|result| result.as_ref().err())
+            .all(|rejection| *rejection == RpcPolicyRejection::MethodBusy));
+        // other methods are not capped
+        assert!(policy.check_call("getSlot", None).is_ok());
+
+        // permits are released once the calls complete
+        let permits: Vec<_> = (0..max_calls)
+            .map(|_| policy.check_call("getProgramAccounts", None).unwrap())
+            .collect();
+        assert_eq!(
+            policy.check_call("getProgramAccounts", None).err(),
+            Some(RpcPolicyRejection::MethodBusy)
+        );
+        drop(permits);
+        assert!(policy.check_call("getProgramAccounts", None).is_ok());
+    }
+
+    #[test]
+    fn test_rate_limit_recovers() {
+        let max_requests_per_second = 10;
+        let policy = RpcPolicy::new(RpcPolicyConfig {
+            max_requests_per_second: Some(max_requests_per_second),
+            ..RpcPolicyConfig::default()
+        });
+        for _ in 0..max_requests_per_second {
+            assert!(policy.check_call("getSlot", None).is_ok());
+        }
+        assert_eq!(
+            policy.check_call("getBalance", None).err(),
+            Some(RpcPolicyRejection::RateLimited)
+        );
+        assert!(policy.check_call("getHealth", None).is_ok());
+
+        // a token is refilled every 100ms
+        thread::sleep(Duration::from_millis(200));
+        assert!(policy.check_call("getSlot", None).is_ok());
+    }
+
+    #[test]
+    fn test_rate_limit_per_client() {
+        let max_requests_per_second = 10;
+        let policy = RpcPolicy::new(RpcPolicyConfig {
+            max_requests_per_second: Some(max_requests_per_second),
+            ..RpcPolicyConfig::default()
+        });
+        let client = Some(IpAddr::V4(Ipv4Addr::new(10, 0, 0, 1)));
+        let other_client = Some(IpAddr::V4(Ipv4Addr::new(10, 0, 0, 2)));
+        for _ in 0..max_requests_per_second {
+            assert!(policy.check_call("getSlot", client).is_ok());
+        }
+        assert_eq!(
+            policy.check_call("getSlot", client).err(),
+            Some(RpcPolicyRejection::RateLimited)
+        );
+        // other clients, and calls without a client address, have their own limits
+        assert!(policy.check_call("getSlot", other_client).is_ok());
+        assert!(policy.check_call("getSlot", None).is_ok());
+    }
+
+    #[test]
+    fn test_rate_limit_evicts_idle_clients() {
+        let max_clients = 2;
+        let mut rate_limiter = RateLimiter::new(10, max_clients);
+        let clients: Vec<_> = (1..=3)
+            .map(|i| Some(IpAddr::V4(Ipv4Addr::new(10, 0, 0, i))))
+            .collect();
+        assert!(rate_limiter.try_acquire(clients[0]));
+        assert!(rate_limiter.try_acquire(clients[1]));
+        // beyond max_clients, new clients share the bucket of calls without an address
+        assert!(rate_limiter.try_acquire(clients[2]));
+        assert!(!rate_limiter.buckets.contains_key(&clients[2]));
+        assert!(rate_limiter.buckets.contains_key(&None));
+
+        // idle buckets are full again, so they are evicted
+        thread::sleep(RATE_LIMIT_IDLE_TIMEOUT);
+        assert!(rate_limiter.try_acquire(clients[2]));
+        assert_eq!(
+            rate_limiter.buckets.keys().collect::<Vec<_>>(),
+            vec![&clients[2]]
+        );
+    }
+
+    #[test]
+    fn test_busy_calls_keep_rate_limit() {
+        let max_requests_per_second = 2;
+        let policy = RpcPolicy::new(RpcPolicyConfig {
+            max_concurrent_calls: HashMap::from([("getProgramAccounts".to_string(), 1)]),
+            max_requests_per_second: Some(max_requests_per_second),
+            ..RpcPolicyConfig::default()
+        });
+        let permit = policy.check_call("getProgramAccounts", None).unwrap();
+        for _ in 0..max_requests_per_second {
+            assert_eq!(
+                policy.check_call("getProgramAccounts", None).err(),
+                Some(RpcPolicyRejection::MethodBusy)
+            );
+        }
+        drop(permit);
+        // busy calls took no tokens, so one is left for this call
+        let permit = policy.check_call("getProgramAccounts", None).unwrap();
+        drop(permit);
+
+        // calls rejected by the rate limit don't hold on to their permit
+        assert_eq!(
+            policy.check_call("getProgramAccounts", None).err(),
+            Some(RpcPolicyRejection::RateLimited)
+        );
+        thread::sleep(Duration::from_millis(600));
+        assert!(policy.check_call("getProgramAccounts", None).is_ok());
+    }
+
+    #[test]
+    fn test_rejections_reported_without_further_rejections() {
+        let policy = RpcPolicy::new(RpcPolicyConfig {
+            disabled_methods: HashSet::from(["getProgramAccounts".to_string()]),
+            ..RpcPolicyConfig::default()
+        });
+        assert!(policy.check_call("getProgramAccounts", None).is_err());
+        assert_eq!(policy.rejections.lock().unwrap().0.len(), 1);
+
+        let runtime = Runtime::new().unwrap();
+        runtime.block_on(async {
+            let reporter = tokio::spawn(report_rejections(
+                Arc::downgrade(&policy.rejections),
+                Duration::from_millis(10),
+            ));
+            tokio::time::sleep(Duration::from_millis(50)).await;
+            assert!(policy.rejections.lock().unwrap().0.is_empty());
+
+            // the reporter stops once the policy is dropped
+            drop(policy);
+            tokio::time::timeout(Duration::from_secs(1), reporter)
+                .await
+                .unwrap()
+                .unwrap();
+        });
+    }
+
+    #[test]
+    fn test_client_address_from_header() {
+        let client = IpAddr::V4(Ipv4Addr::new(10, 0, 0, 1));
+        assert_eq!(client_address_from_header("10.0.0.1"), Some(client));
+        assert_eq!(client_address_from_header("10.0.0.1:8899"), Some(client));
+        // the last address was added by the proxy, the others by the client
+        assert_eq!(
+            client_address_from_header("1.2.3.4, 10.0.0.1"),
+            Some(client)
+        );
+        assert_eq!(
+            client_address_from_header("::1"),
+            Some(IpAddr::V6(std::net::Ipv6Addr::LOCALHOST))
+        );
+        assert_eq!(client_address_from_header("unknown"), None);
+        assert_eq!(client_address_from_header(""), None);
+    }
+}
@@ -10,7 +10,11 @@ use {
     },
     dashmap::{mapref::entry::Entry, DashMap},
     jsonrpc_core::IoHandler,
-    soketto::handshake::{server, Server},
+    soketto::{
+        connection::Incoming,
+        data::ByteSlice125,
+        handshake::{server, Server},
+    },
     solana_metrics::TokenCounter,
     solana_rayon_threadlimit::get_thread_count,
     solana_sdk::timing::AtomicInterval,
@@ -24,10 +28,16 @@ use {
             Arc,
         },
         thread::{self, Builder, JoinHandle},
+        time::Duration,
     },
     stream_cancel::{Trigger, Tripwire},
     thiserror::Error,
-    tokio::{net::TcpStream, pin, select, sync::broadcast},
+    tokio::{
+        net::TcpStream,
+        pin, select,
+        sync::broadcast,
+        time::{self, Instant, Interval, MissedTickBehavior},
+    },
     tokio_util::compat::TokioAsyncReadCompatExt,
 };
 
@@ -36,6 +46,8 @@ pub const DEFAULT_QUEUE_CAPACITY_ITEMS: usize = 10_000_000;
 pub const DEFAULT_TEST_QUEUE_CAPACITY_ITEMS: usize = 100;
 pub const DEFAULT_QUEUE_CAPACITY_BYTES: usize = 256 * 1024 * 1024;
 pub const DEFAULT_WORKER_THREADS: usize = 1;
+pub const DEFAULT_PING_INTERVAL: Duration = Duration::from_secs(30);
+pub const DEFAULT_PONG_TIMEOUT: Duration = Duration::from_secs(30);
 
 #[derive(Debug, Clone)]
 pub struct PubSubConfig {
@@ -46,6 +58,10 @@ pub struct PubSubConfig {
     pub queue_capacity_bytes: usize,
     pub worker_threads: usize,
     pub notification_threads: Option<NonZeroUsize>,
+    /// Interval at which idle and active connections are pinged, `None` disables pings
+    pub ping_interval: Option<Duration>,
+    /// Connections which don't answer a ping within this timeout are dropped
+    pub pong_timeout: Duration,
 }
 
 impl Default for PubSubConfig {
@@ -58,6 +74,8 @@ impl Default for PubSubConfig {
             queue_capacity_bytes: DEFAULT_QUEUE_CAPACITY_BYTES,
             worker_threads: DEFAULT_WORKER_THREADS,
             notification_threads: NonZeroUsize::new(get_thread_count()),
+            ping_interval: Some(DEFAULT_PING_INTERVAL),
+            pong_timeout: DEFAULT_PONG_TIMEOUT,
         }
     }
 }
@@ -72,6 +90,8 @@ impl PubSubConfig {
             queue_capacity_bytes: DEFAULT_QUEUE_CAPACITY_BYTES,
             worker_threads: DEFAULT_WORKER_THREADS,
             notification_threads: NonZeroUsize::new(2),
+            ping_interval: Some(DEFAULT_PING_INTERVAL),
+            pong_timeout: DEFAULT_PONG_TIMEOUT,
         }
     }
 }
@@ -381,6 +401,11 @@ async fn handle_connection(
     let mut data = Vec::new();
     let current_subscriptions = Arc::new(DashMap::new());
 
+    let PubSubConfig {
+        ping_interval,
+        pong_timeout,
+        ..
+    } = config;
     let mut json_rpc_handler = IoHandler::new();
     let rpc_impl = RpcSolPubSubImpl::new(
         config,
@@ -389,18 +414,31 @@ async fn handle_connection(
     );
     json_rpc_handler.extend_with(rpc_impl.to_delegate());
     let broadcast_handler = BroadcastHandler::new(current_subscriptions);
+    // Intermediaries drop websockets which look idle, so the connection is pinged
+    // periodically. Clients which don't answer are dropped to free their subscriptions.
+    let mut ping_interval = ping_interval.map(|period| {
+        let mut interval = time::interval_at(Instant::now() + period, period);
+        interval.set_missed_tick_behavior(MissedTickBehavior::Delay);
+        interval
+    });
+    let pong_deadline = time::sleep(pong_timeout);
+    pin!(pong_deadline);
+    let mut awaiting_pong = false;
     loop {
         // Extra block for dropping `receive_future`.
-        {
+        let received_data = {
             // soketto is not cancel safe, so we have to introduce an inner loop to poll
-            // `receive_data` to completion.
-            let receive_future = receiver.receive_data(&mut data);
+            // `receive` to completion.
+            let receive_future = receiver.receive(&mut data);
             pin!(receive_future);
             loop {
                 select! {
                     result = &mut receive_future => match result {
-                        Ok(_) => break,
-                        Err(soketto::connection::Error::Closed) => return Ok(()),
+                        Ok(Incoming::Data(_)) => break true,
+                        Ok(Incoming::Pong(_)) => break false,
+                        Ok(Incoming::Closed(_)) | Err(soketto::connection::Error::Closed) => {
+                            return Ok(())
+                        }
                         Err(err) => return Err(err.into()),
                     },
                     result = broadcast_receiver.recv() => {
@@ -410,6 +448,15 @@ async fn handle_connection(
                             sender.send_text(&*json).await?;
                         }
                     },
+                    _ = tick(&mut ping_interval), if !awaiting_pong => {
+                        sender.send_ping(ByteSlice125::try_from(&[][..]).unwrap()).await?;
+                        awaiting_pong = true;
+                        pong_deadline.as_mut().reset(Instant::now() + pong_timeout);
+                    },
+                    _ = &mut pong_deadline, if awaiting_pong => {
+                        warn!("disconnecting websocket client: no pong within {pong_timeout:?}");
+                        return Ok(())
+                    },
                     _ = &mut tripwire => {
                         warn!("disconnecting websocket client: shutting down");
                         return Ok(())
@@ -417,6 +464,10 @@ async fn handle_connection(
 
                 }
             }
+        };
+        if !received_data {
+            awaiting_pong = false;
+            continue;
         }
         let Ok(data_str) = str::from_utf8(&data) else {
             // Old implementation just closes the connection, so we preserve that behavior
@@ -433,6 +484,15 @@ async fn handle_connection(
     Ok(())
 }
 
+async fn tick(interval: &mut Option<Interval>) {
+    match interval {
+        Some(interval) => {
+            interval.tick().await;
+        }
+        None => std::future::pending().await,
+    }
+}
+
 async fn listen(
     listen_address: SocketAddr,
     config: PubSubConfig,
@@ -473,6 +533,10 @@ mod tests {
     use {
         super::*,
         crate::optimistically_confirmed_bank_tracker::OptimisticallyConfirmedBank,
+        soketto::{
+            connection::{Receiver, Sender},
+            handshake::{Client, ServerResponse},
+        },
         solana_runtime::{
             bank::Bank,
             bank_forks::BankForks,
@@ -486,11 +550,11 @@ mod tests {
                 RwLock,
             },
         },
+        tokio::{net::TcpListener, runtime::Runtime, sync::mpsc, task, time::timeout},
+        tokio_util::compat::Compat,
     };
 
-    #[test]
-    fn test_pubsub_new() {
-        let pubsub_addr = SocketAddr::new(IpAddr::V4(Ipv4Addr::UNSPECIFIED), 0);
+    fn new_test_subscriptions() -> Arc<RpcSubscriptions> {
         let exit = Arc::new(AtomicBool::new(false));
         let max_complete_transaction_status_slot = Arc::new(AtomicU64::default());
         let max_complete_rewards_slot = Arc::new(AtomicU64::default());
@@ -499,17 +563,115 @@ mod tests {
         let bank_forks = BankForks::new_rw_arc(bank);
         let optimistically_confirmed_bank =
             OptimisticallyConfirmedBank::locked_from_bank_forks_root(&bank_forks);
-        let subscriptions = Arc::new(RpcSubscriptions::new_for_tests(
+        Arc::new(RpcSubscriptions::new_for_tests(
             exit,
             max_complete_transaction_status_slot,
             max_complete_rewards_slot,
             bank_forks,
             Arc::new(RwLock::new(BlockCommitmentCache::new_for_tests())),
             optimistically_confirmed_bank,
-        ));
+        ))
+    }
+
+    #[test]
+    fn test_pubsub_new() {
+        let pubsub_addr = SocketAddr::new(IpAddr::V4(Ipv4Addr::UNSPECIFIED), 0);
+        let subscriptions = new_test_subscriptions();
         let (_trigger, pubsub_service) =
             PubSubService::new(PubSubConfig::default(), &subscriptions, pubsub_addr);
         let thread = pubsub_service.thread_hdl.thread();
         assert_eq!(thread.name().unwrap(), "solRpcPubSub");
     }
+
+    // Connects a websocket client to a connection handled by `handle_connection`
+    async fn connect(
+        subscriptions: &RpcSubscriptions,
+        config: PubSubConfig,
+        tripwire: Tripwire,
+    ) -> (
+        task::JoinHandle<Result<(), Error>>,
+        Sender<Compat<TcpStream>>,
+        Receiver<Compat<TcpStream>>,
+    ) {
+        let listener = TcpListener::bind((Ipv4Addr::LOCALHOST, 0)).await.unwrap();
+        let addr = listener.local_addr().unwrap();
+        let subscription_control = subscriptions.control().clone();
+        let connection = tokio::spawn(async move {
+            let (socket, _) = listener.accept().await.unwrap();
+            handle_connection(socket, subscription_control, config, tripwire).await
+        });
+        let socket = TcpStream::connect(addr).await.unwrap();
+        let mut client = Client::new(socket.compat(), "localhost", "/");
+        assert!(matches!(
+            client.handshake().await.unwrap(),
+            ServerResponse::Accepted { .. }
+        ));
+        let (sender, receiver) = client.into_builder().finish();
+        (connection, sender, receiver)
+    }
+
+    fn ping_config(pong_timeout: Duration) -> PubSubConfig {
+        PubSubConfig {
+            ping_interval: Some(pong_timeout / 2),
+            pong_timeout,
+            ..PubSubConfig::default_for_tests()
+        }
+    }
+
+    #[test]
+    fn test_pubsub_drops_connection_without_pong() {
+        let subscriptions = new_test_subscriptions();
+        let pong_timeout = Duration::from_millis(100);
+        let (_trigger, tripwire) = Tripwire::new();
+        Runtime::new().unwrap().block_on(async {
+            let started = Instant::now();
+            let (connection, _sender, mut receiver) =
+                connect(&subscriptions, ping_config(pong_timeout), tripwire).await;
+            // The client doesn't read from the connection, so it never answers pings.
+            let result = timeout(Duration::from_secs(10), connection)
+                .await
+                .expect("connection should be dropped")
+                .unwrap();
+            assert!(result.is_ok());
+            assert!(started.elapsed() >= pong_timeout);
+            let mut data = Vec::new();
+            assert!(receiver.receive_data(&mut data).await.is_err());
+        });
+    }
+
+    #[test]
+    fn test_pubsub_keeps_connection_with_pong() {
+        let subscriptions = new_test_subscriptions();
+        let pong_timeout = Duration::from_millis(100);
+        let (_trigger, tripwire) = Tripwire::new();
+        Runtime::new().unwrap().block_on(async {
+            let (mut connection, mut sender, mut receiver) =
+                connect(&subscriptions, ping_config(pong_timeout), tripwire).await;
+            // soketto answers pings while receiving.
+            let (message_sender, mut message_receiver) = mpsc::unbounded_channel();
+            tokio::spawn(async move {
+                let mut data = Vec::new();
+                while receiver.receive_data(&mut data).await.is_ok() {
+                    let message = String::from_utf8(std::mem::take(&mut data)).unwrap();
+                    if message_sender.send(message).is_err() {
+                        break;
+                    }
+                }
+            });
+            // The connection outlives several pong timeouts.
+            assert!(timeout(pong_timeout * 5, &mut connection).await.is_err());
+            sender
+                .send_text(r#"{"jsonrpc":"2.0","id":1,"method":"slotSubscribe"}"#)
+                .await
+                .unwrap();
+            sender.flush().await.unwrap();
+            let response = timeout(Duration::from_secs(10), message_receiver.recv())
+                .await
+                .unwrap()
+                .unwrap();
+            assert!(response.contains(r#""result":"#), "{response}");
+            assert!(response.contains(r#""id":1"#), "{response}");
+            assert!(!connection.is_finished());
+        });
+    }
 }
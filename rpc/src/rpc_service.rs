@@ -3,11 +3,13 @@
 use {
     crate::{
         cluster_tpu_info::ClusterTpuInfo,
+        latest_forks::LatestForks,
         max_slots::MaxSlots,
         optimistically_confirmed_bank_tracker::OptimisticallyConfirmedBank,
         rpc::{rpc_accounts::*, rpc_accounts_scan::*, rpc_bank::*, rpc_full::*, rpc_minimal::*, *},
         rpc_cache::LargestAccountsCache,
         rpc_health::*,
+        rpc_policy::{client_address_from_header, RpcPolicy},
     },
     crossbeam_channel::unbounded,
     jsonrpc_core::{futures::prelude::*, MetaIoHandler},
@@ -355,6 +357,7 @@ impl JsonRpcService {
         optimistically_confirmed_bank: Arc<RwLock<OptimisticallyConfirmedBank>>,
         send_transaction_service_config: send_transaction_service::Config,
         max_slots: Arc<MaxSlots>,
+        latest_forks: Arc<LatestForks>,
         leader_schedule_cache: Arc<LeaderScheduleCache>,
         connection_cache: Arc<ConnectionCache>,
         max_complete_transaction_status_slot: Arc<AtomicU64>,
@@ -436,6 +439,9 @@ impl JsonRpcService {
             };
 
         let full_api = config.full_api;
+        let policy = RpcPolicy::new(config.policy.clone());
+        policy.spawn_rejections_reporter(&runtime);
+        let client_address_header = config.policy.client_address_header.clone();
         let max_request_body_size = config
             .max_request_body_size
             .unwrap_or(MAX_REQUEST_BODY_SIZE);
@@ -453,6 +459,7 @@ impl JsonRpcService {
             optimistically_confirmed_bank,
             largest_accounts_cache,
             max_slots,
+            latest_forks,
             leader_schedule_cache,
             max_complete_transaction_status_slot,
             max_complete_rewards_slot,
@@ -488,7 +495,7 @@ impl JsonRpcService {
             .spawn(move || {
                 renice_this_thread(rpc_niceness_adj).unwrap();
 
-                let mut io = MetaIoHandler::default();
+                let mut io = MetaIoHandler::with_middleware(policy);
 
                 io.extend_with(rpc_minimal::MinimalImpl.to_delegate());
                 if full_api {
@@ -508,11 +515,17 @@ impl JsonRpcService {
                     io,
                     move |req: &hyper::Request<hyper::Body>| {
                         let xbigtable = req.headers().get("x-bigtable");
-                        if xbigtable.is_some_and(|v| v == "disabled") {
+                        let request_processor = if xbigtable.is_some_and(|v| v == "disabled") {
                             request_processor.clone_without_bigtable()
                         } else {
                             request_processor.clone()
-                        }
+                        };
+                        let client_address = client_address_header
+                            .as_ref()
+                            .and_then(|header| req.headers().get(header))
+                            .and_then(|value| value.to_str().ok())
+                            .and_then(client_address_from_header);
+                        request_processor.with_client_address(client_address)
                     },
                 )
                 .event_loop_executor(runtime.handle().clone())
@@ -609,26 +622,34 @@ pub fn service_runtime(
 mod tests {
     use {
         super::*,
-        crate::rpc::{create_validator_exit, tests::new_test_cluster_info},
+        crate::{
+            rpc::{create_validator_exit, tests::new_test_cluster_info},
+            rpc_policy::RpcPolicyConfig,
+        },
         solana_ledger::{
             genesis_utils::{create_genesis_config, GenesisConfigInfo},
             get_tmp_ledger_path_auto_delete,
         },
-        solana_rpc_client_api::config::RpcContextConfig,
+        solana_rpc_client_api::{
+            config::RpcContextConfig, custom_error::JSON_RPC_SERVER_ERROR_METHOD_DISABLED,
+        },
         solana_runtime::bank::Bank,
         solana_sdk::{
             genesis_config::{ClusterType, DEFAULT_GENESIS_ARCHIVE},
-            signature::Signer,
+            signature::{Keypair, Signer},
         },
         std::{
-            io::Write,
-            net::{IpAddr, Ipv4Addr},
+            collections::HashSet,
+            io::{Read, Write},
+            net::{IpAddr, Ipv4Addr, TcpStream},
         },
         tokio::runtime::Runtime,
     };
 
-    #[test]
-    fn test_rpc_new() {
+    fn start_rpc_service(
+        config: JsonRpcConfig,
+        ledger_path: &Path,
+    ) -> (JsonRpcService, SocketAddr, Keypair) {
         let GenesisConfigInfo {
             genesis_config,
             mint_keypair,
@@ -644,15 +665,14 @@ mod tests {
             solana_net_utils::find_available_port_in_range(ip_addr, (10000, 65535)).unwrap(),
         );
         let bank_forks = BankForks::new_rw_arc(bank);
-        let ledger_path = get_tmp_ledger_path_auto_delete!();
-        let blockstore = Arc::new(Blockstore::open(ledger_path.path()).unwrap());
+        let blockstore = Arc::new(Blockstore::open(ledger_path).unwrap());
         let block_commitment_cache = Arc::new(RwLock::new(BlockCommitmentCache::default()));
         let optimistically_confirmed_bank =
             OptimisticallyConfirmedBank::locked_from_bank_forks_root(&bank_forks);
         let connection_cache = Arc::new(ConnectionCache::new("connection_cache_test"));
         let mut rpc_service = JsonRpcService::new(
             rpc_addr,
-            JsonRpcConfig::default(),
+            config,
             None,
             bank_forks,
             block_commitment_cache,
@@ -672,6 +692,7 @@ mod tests {
                 ..send_transaction_service::Config::default()
             },
             Arc::new(MaxSlots::default()),
+            Arc::new(LatestForks::default()),
             Arc::new(LeaderScheduleCache::default()),
             connection_cache,
             Arc::new(AtomicU64::default()),
@@ -679,6 +700,38 @@ mod tests {
             Arc::new(PrioritizationFeeCache::default()),
         )
         .expect("assume successful JsonRpcService start");
+        (rpc_service, rpc_addr, mint_keypair)
+    }
+
+    // Posts `body` to the service and returns the response status code and body
+    fn post(rpc_addr: &SocketAddr, body: &str) -> (u16, String) {
+        let mut stream = TcpStream::connect((Ipv4Addr::LOCALHOST, rpc_addr.port())).unwrap();
+        write!(
+            stream,
+            "POST / HTTP/1.1\r\nHost: localhost\r\nContent-Type: application/json\r\n\
+             Content-Length: {}\r\nConnection: close\r\n\r\n{body}",
+            body.len()
+        )
+        .unwrap();
+        let mut response = String::new();
+        stream.read_to_string(&mut response).unwrap();
+        let status = response
+            .split_whitespace()
+            .nth(1)
+            .and_then(|status| status.parse().ok())
+            .unwrap();
+        let body = response
+            .split_once("\r\n\r\n")
+            .map(|(_, body)| body.to_string())
+            .unwrap_or_default();
+        (status, body)
+    }
+
+    #[test]
+    fn test_rpc_new() {
+        let ledger_path = get_tmp_ledger_path_auto_delete!();
+        let (mut rpc_service, _rpc_addr, mint_keypair) =
+            start_rpc_service(JsonRpcConfig::default(), ledger_path.path());
         let thread = rpc_service.thread_hdl.thread();
         assert_eq!(thread.name().unwrap(), "solJsonRpcSvc");
 
@@ -694,6 +747,74 @@ mod tests {
         rpc_service.join().unwrap();
     }
 
+    #[test]
+    fn test_rpc_max_request_body_size() {
+        let max_request_body_size = 1024;
+        let ledger_path = get_tmp_ledger_path_auto_delete!();
+        let (mut rpc_service, rpc_addr, _mint_keypair) = start_rpc_service(
+            JsonRpcConfig {
+                max_request_body_size: Some(max_request_body_size),
+                ..JsonRpcConfig::default()
+            },
+            ledger_path.path(),
+        );
+        let request = r#"{"jsonrpc":"2.0","id":1,"method":"getSlot"}"#;
+
+        // A request up to the limit is processed
+        let padded = format!("{request:<max_request_body_size$}");
+        assert_eq!(padded.len(), max_request_body_size);
+        let (status, body) = post(&rpc_addr, &padded);
+        assert_eq!(status, 200);
+        assert!(body.contains(r#""result":0"#), "{body}");
+
+        // A request over the limit is rejected before it is parsed
+        let too_large = max_request_body_size + 1;
+        let padded = format!("{request:<too_large$}");
+        let (status, body) = post(&rpc_addr, &padded);
+        assert_eq!(status, 413);
+        assert!(!body.contains("result"), "{body}");
+
+        rpc_service.exit();
+        rpc_service.join().unwrap();
+    }
+
+    #[test]
+    fn test_rpc_disabled_methods() {
+        let ledger_path = get_tmp_ledger_path_auto_delete!();
+        let (mut rpc_service, rpc_addr, _mint_keypair) = start_rpc_service(
+            JsonRpcConfig {
+                policy: RpcPolicyConfig {
+                    disabled_methods: HashSet::from([
+                        "getSlot".to_string(),
+                        "getIdentity".to_string(),
+                    ]),
+                    ..RpcPolicyConfig::default()
+                },
+                ..JsonRpcConfig::default()
+            },
+            ledger_path.path(),
+        );
+
+        let (status, body) = post(&rpc_addr, r#"{"jsonrpc":"2.0","id":1,"method":"getSlot"}"#);
+        assert_eq!(status, 200);
+        assert!(
+            body.contains(&JSON_RPC_SERVER_ERROR_METHOD_DISABLED.to_string()),
+            "{body}"
+        );
+        assert!(body.contains("disabled by node operator"), "{body}");
+
+        // Other methods are unaffected, and unrestricted methods can't be disabled
+        for method in ["getVersion", "getIdentity"] {
+            let request = format!(r#"{{"jsonrpc":"2.0","id":1,"method":"{method}"}}"#);
+            let (status, body) = post(&rpc_addr, &request);
+            assert_eq!(status, 200);
+            assert!(body.contains("result"), "{body}");
+        }
+
+        rpc_service.exit();
+        rpc_service.join().unwrap();
+    }
+
     fn create_bank_forks() -> Arc<RwLock<BankForks>> {
         let GenesisConfigInfo {
             mut genesis_config, ..
@@ -2,6 +2,7 @@
 //!
 //! This can be expensive since we have to walk the append vecs being cleaned up.
 
+mod progress;
 mod stats;
 #[cfg(feature = "dev-context-only-utils")]
 use qualifier_attr::qualifiers;
@@ -39,6 +40,8 @@ use {
 const INTERVAL_MS: u64 = 100;
 const CLEAN_INTERVAL_BLOCKS: u64 = 100;
 
+pub use progress::{AbsProgress, AbsTask};
+
 pub type SnapshotRequestSender = Sender<SnapshotRequest>;
 pub type SnapshotRequestReceiver = Receiver<SnapshotRequest>;
 pub type DroppedSlotsSender = Sender<(Slot, BankId)>;
@@ -562,6 +565,7 @@ impl AbsRequestHandlers {
 
 pub struct AccountsBackgroundService {
     t_background: JoinHandle<()>,
+    progress: Arc<AbsProgress>,
 }
 
 impl AccountsBackgroundService {
@@ -574,6 +578,8 @@ impl AccountsBackgroundService {
         let mut last_cleaned_block_height = 0;
         let mut removed_slots_count = 0;
         let mut total_remove_slots_time = 0;
+        let progress = Arc::<AbsProgress>::default();
+        let progress_clone = progress.clone();
         let t_background = Builder::new()
             .name("solBgAccounts".to_string())
             .spawn(move || {
@@ -598,6 +604,7 @@ impl AccountsBackgroundService {
                             &mut removed_slots_count,
                             &mut total_remove_slots_time,
                         );
+                    progress_clone.record_completed(AbsTask::PrunedBanks);
 
                     let non_snapshot_time = last_snapshot_end_time
                         .map(|last_snapshot_end_time: Instant| {
@@ -654,6 +661,10 @@ impl AccountsBackgroundService {
                             Ok(snapshot_block_height) => {
                                 assert!(last_cleaned_block_height <= snapshot_block_height);
                                 last_cleaned_block_height = snapshot_block_height;
+                                // Handling a snapshot request also cleans and shrinks
+                                progress_clone.record_completed(AbsTask::Snapshot);
+                                progress_clone.record_completed(AbsTask::Clean);
+                                progress_clone.record_completed(AbsTask::Shrink);
                             }
                             Err(err) => {
                                 error!("Stopping AccountsBackgroundService! Fatal error while handling snapshot requests: {err}");
@@ -672,6 +683,7 @@ impl AccountsBackgroundService {
                             bank.force_flush_accounts_cache();
                             bank.clean_accounts();
                             last_cleaned_block_height = bank.block_height();
+                            progress_clone.record_completed(AbsTask::Clean);
                             // See justification below for why we skip 'shrink' here.
                             if bank.is_startup_verification_complete() {
                                 bank.shrink_ancient_slots();
@@ -686,16 +698,30 @@ impl AccountsBackgroundService {
                         // was in the snapshot itself.
                         if bank.is_startup_verification_complete() {
                             bank.shrink_candidate_slots();
+                            progress_clone.record_completed(AbsTask::Shrink);
                         }
                     }
-                    stats.record_and_maybe_submit(start_time.elapsed());
+                    progress_clone.update_pending(
+                        &request_handlers,
+                        &bank,
+                        last_cleaned_block_height,
+                    );
+                    stats.record_and_maybe_submit(start_time.elapsed(), &progress_clone);
                     sleep(Duration::from_millis(INTERVAL_MS));
                 }
                 info!("AccountsBackgroundService has stopped");
             })
             .unwrap();
 
-        Self { t_background }
+        Self {
+            t_background,
+            progress,
+        }
+    }
+
+    /// The pending work, and when each task last completed
+    pub fn progress(&self) -> &Arc<AbsProgress> {
+        &self.progress
     }
 
     /// Should be called immediately after bank_fork_utils::load_bank_forks(), and as such, there
@@ -824,6 +850,60 @@ mod test {
         assert!(bank0.rc.accounts.scan_slot(0, |_| Some(())).is_empty());
     }
 
+    #[test]
+    fn test_accounts_background_service_progress() {
+        let genesis_config_info = create_genesis_config(10);
+        let bank = Bank::new_for_tests(&genesis_config_info.genesis_config);
+        bank.set_startup_verification_complete();
+        let bank_forks = BankForks::new_rw_arc(bank);
+        let pruned_banks_receiver =
+            AccountsBackgroundService::setup_bank_drop_callback(bank_forks.clone());
+        let (accounts_package_sender, _accounts_package_receiver) = unbounded();
+        let (snapshot_request_sender, snapshot_request_receiver) = unbounded();
+        let request_handlers = AbsRequestHandlers {
+            snapshot_request_handler: SnapshotRequestHandler {
+                snapshot_config: SnapshotConfig::default(),
+                snapshot_request_sender,
+                snapshot_request_receiver,
+                accounts_package_sender,
+            },
+            pruned_banks_request_handler: PrunedBanksRequestHandler {
+                pruned_banks_receiver,
+            },
+        };
+
+        // Queue up work by dropping banks on abandoned forks
+        let bank0 = bank_forks.read().unwrap().root_bank();
+        for _ in 0..3 {
+            drop(Bank::new_from_parent(
+                bank0.clone(),
+                &Pubkey::new_unique(),
+                bank0.slot() + 1,
+            ));
+        }
+        let progress = AbsProgress::default();
+        progress.update_pending(&request_handlers, &bank0, 0);
+        assert_eq!(progress.pending_pruned_banks(), 3);
+        assert_eq!(progress.last_completed(AbsTask::PrunedBanks), None);
+
+        // The pruned banks are purged by the first pass of the service
+        let exit = Arc::new(AtomicBool::new(false));
+        let accounts_background_service =
+            AccountsBackgroundService::new(bank_forks, exit.clone(), request_handlers, false);
+        let progress = accounts_background_service.progress().clone();
+        let start = Instant::now();
+        while progress.last_completed(AbsTask::Shrink).is_none() {
+            assert!(start.elapsed() < Duration::from_secs(10));
+            sleep(Duration::from_millis(INTERVAL_MS));
+        }
+        assert!(progress.last_completed(AbsTask::PrunedBanks).is_some());
+        assert_eq!(progress.pending_pruned_banks(), 0);
+        assert_eq!(progress.pending_snapshot_requests(), 0);
+
+        exit.store(true, Ordering::Relaxed);
+        accounts_background_service.join().unwrap();
+    }
+
     /// Ensure that unhandled snapshot requests are properly re-enqueued or dropped
     ///
     /// The snapshot request handler should be flexible and handle re-queueing unhandled snapshot
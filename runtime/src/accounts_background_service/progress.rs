@@ -0,0 +1,118 @@
+//! Progress of the Accounts Background Service
+
+use {
+    super::AbsRequestHandlers,
+    crate::bank::Bank,
+    solana_sdk::timing::timestamp,
+    std::sync::atomic::{AtomicU64, AtomicUsize, Ordering},
+};
+
+/// The tasks performed by the Accounts Background Service
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum AbsTask {
+    /// Purging the accounts of pruned banks
+    PrunedBanks,
+    /// Handling a snapshot request
+    Snapshot,
+    /// Cleaning accounts
+    Clean,
+    /// Shrinking candidate slots
+    Shrink,
+}
+
+/// The pending work, and when each task last completed, in the Accounts Background Service
+///
+/// Shared with the service so its callers can tell whether it is falling behind.  Timestamps are
+/// in milliseconds since the UNIX epoch, and are `None` if the task has not completed yet.
+#[derive(Debug, Default)]
+pub struct AbsProgress {
+    pending_pruned_banks: AtomicUsize,
+    pending_snapshot_requests: AtomicUsize,
+    pending_shrink_slots: AtomicUsize,
+    blocks_since_clean: AtomicU64,
+    last_pruned_banks_timestamp: AtomicU64,
+    last_snapshot_timestamp: AtomicU64,
+    last_clean_timestamp: AtomicU64,
+    last_shrink_timestamp: AtomicU64,
+}
+
+impl AbsProgress {
+    /// Number of pruned banks waiting to be purged
+    pub fn pending_pruned_banks(&self) -> usize {
+        self.pending_pruned_banks.load(Ordering::Relaxed)
+    }
+
+    /// Number of snapshot requests waiting to be handled
+    pub fn pending_snapshot_requests(&self) -> usize {
+        self.pending_snapshot_requests.load(Ordering::Relaxed)
+    }
+
+    /// Number of slots waiting to be shrunk
+    pub fn pending_shrink_slots(&self) -> usize {
+        self.pending_shrink_slots.load(Ordering::Relaxed)
+    }
+
+    /// Number of blocks rooted since accounts were last cleaned
+    pub fn blocks_since_clean(&self) -> u64 {
+        self.blocks_since_clean.load(Ordering::Relaxed)
+    }
+
+    /// When `task` last completed
+    pub fn last_completed(&self, task: AbsTask) -> Option<u64> {
+        let timestamp = self.last_timestamp(task).load(Ordering::Relaxed);
+        (timestamp != 0).then_some(timestamp)
+    }
+
+    /// Record that `task` has just completed
+    pub(super) fn record_completed(&self, task: AbsTask) {
+        self.last_timestamp(task)
+            .store(timestamp(), Ordering::Relaxed);
+    }
+
+    /// Update the pending work as of the current root `bank`
+    pub(super) fn update_pending(
+        &self,
+        request_handlers: &AbsRequestHandlers,
+        bank: &Bank,
+        last_cleaned_block_height: u64,
+    ) {
+        self.pending_pruned_banks.store(
+            request_handlers
+                .pruned_banks_request_handler
+                .pruned_banks_receiver
+                .len(),
+            Ordering::Relaxed,
+        );
+        self.pending_snapshot_requests.store(
+            request_handlers
+                .snapshot_request_handler
+                .snapshot_request_receiver
+                .len(),
+            Ordering::Relaxed,
+        );
+        self.pending_shrink_slots.store(
+            bank.rc
+                .accounts
+                .accounts_db
+                .shrink_candidate_slots
+                .lock()
+                .unwrap()
+                .len(),
+            Ordering::Relaxed,
+        );
+        self.blocks_since_clean.store(
+            bank.block_height()
+                .saturating_sub(last_cleaned_block_height),
+            Ordering::Relaxed,
+        );
+    }
+
+    fn last_timestamp(&self, task: AbsTask) -> &AtomicU64 {
+        match task {
+            AbsTask::PrunedBanks => &self.last_pruned_banks_timestamp,
+            AbsTask::Snapshot => &self.last_snapshot_timestamp,
+            AbsTask::Clean => &self.last_clean_timestamp,
+            AbsTask::Shrink => &self.last_shrink_timestamp,
+        }
+    }
+}
@@ -1,6 +1,7 @@
 //! Stats for Accounts Background Services
 
 use {
+    super::{AbsProgress, AbsTask},
     solana_metrics::datapoint_info,
     std::time::{Duration, Instant},
 };
@@ -28,13 +29,13 @@ impl StatsManager {
 
     /// Record stats from this iteration, and maybe submit the datapoints based on how long it has
     /// been since the previous submission.
-    pub(super) fn record_and_maybe_submit(&mut self, runtime: Duration) {
+    pub(super) fn record_and_maybe_submit(&mut self, runtime: Duration, progress: &AbsProgress) {
         self.stats.record(runtime);
-        self.maybe_submit();
+        self.maybe_submit(progress);
     }
 
     /// Maybe submit the datapoints based on how long it has been since the previous submission.
-    fn maybe_submit(&mut self) {
+    fn maybe_submit(&mut self, progress: &AbsProgress) {
         let duration_since_previous_submit = Instant::now() - self.previous_submit;
         if duration_since_previous_submit < SUBMIT_INTERVAL {
             return;
@@ -60,6 +61,24 @@ impl StatsManager {
             ),
             ("min_runtime_us", self.stats.min_runtime.as_micros(), i64),
             ("max_runtime_us", self.stats.max_runtime.as_micros(), i64),
+            ("pending_pruned_banks", progress.pending_pruned_banks(), i64),
+            (
+                "pending_snapshot_requests",
+                progress.pending_snapshot_requests(),
+                i64
+            ),
+            ("pending_shrink_slots", progress.pending_shrink_slots(), i64),
+            ("blocks_since_clean", progress.blocks_since_clean(), i64),
+            (
+                "last_clean_timestamp",
+                progress.last_completed(AbsTask::Clean),
+                Option<i64>
+            ),
+            (
+                "last_shrink_timestamp",
+                progress.last_completed(AbsTask::Shrink),
+                Option<i64>
+            ),
         );
 
         // reset the stats back to default
@@ -81,12 +81,13 @@ use {
             AccountHash, AccountsHash, AccountsLtHash, CalcAccountsHashConfig, HashStats,
             IncrementalAccountsHash, MerkleOrLatticeAccountsHash,
         },
-        accounts_index::{IndexKey, ScanConfig, ScanResult},
+        accounts_index::{AccountIndex, IndexKey, ScanConfig, ScanPage, ScanResult},
         accounts_partition::{self, Partition, PartitionIndex},
         accounts_update_notifier_interface::AccountsUpdateNotifier,
         ancestors::{Ancestors, AncestorsForSerialization},
         blockhash_queue::BlockhashQueue,
         epoch_accounts_hash::EpochAccountsHash,
+        secondary_index::SecondaryIndexUsage,
         sorted_storages::SortedStorages,
         storable_accounts::StorableAccounts,
     },
@@ -104,7 +105,8 @@ use {
     solana_lattice_hash::lt_hash::LtHash,
     solana_measure::{meas_dur, measure::Measure, measure_time, measure_us},
     solana_program_runtime::{
-        invoke_context::BuiltinFunctionWithContext, loaded_programs::ProgramCacheEntry,
+        invoke_context::BuiltinFunctionWithContext,
+        loaded_programs::{ProgramCacheEntry, ProgramCacheEntryOwner, ProgramCacheEntryType},
     },
     solana_runtime_transaction::{
         runtime_transaction::RuntimeTransaction, transaction_with_meta::TransactionWithMeta,
@@ -251,7 +253,7 @@ struct RentMetrics {
 pub type BankStatusCache = StatusCache<Result<()>>;
 #[cfg_attr(
     feature = "frozen-abi",
-    frozen_abi(digest = "BHg4qpwegtaJypLUqAdjQYzYeLfEGf6tA4U5cREbHMHi")
+    frozen_abi(digest = "8vZfF44iFkY3uw8HUWP2Sahy1XVxpDQQRDzEjNGKJ2UR")
 )]
 pub type BankSlotDelta = SlotDelta<Result<()>>;
 
@@ -598,13 +600,18 @@ impl PartialEq for Bank {
             transaction_processor: _,
             check_program_modification_slot: _,
             collector_fee_details: _,
+            accounts_purged_by_rent: _,
             compute_budget: _,
+            max_instruction_stack_depth: _,
             transaction_account_lock_limit: _,
             fee_structure: _,
             cache_for_accounts_lt_hash: _,
             stats_for_accounts_lt_hash: _,
             block_id,
             bank_hash_stats: _,
+            #[cfg(feature = "dev-context-only-utils")]
+            scheduled_builtins: _,
+            additional_builtins: _,
             // Ignore new fields explicitly if they do not impact PartialEq.
             // Adding ".." will remove compile-time checks that if a new field
             // is added to the struct, this PartialEq is accordingly updated.
@@ -714,6 +721,23 @@ pub trait DropCallback: fmt::Debug {
 #[derive(Debug, Default)]
 pub struct OptionalDropCallback(Option<Box<dyn DropCallback + Send + Sync>>);
 
+/// A builtin registered with `Bank::add_builtin_at_slot()` which is only added
+/// to banks once they reach `activation_slot`
+#[derive(Clone, Debug)]
+#[cfg(feature = "dev-context-only-utils")]
+struct ScheduledBuiltin {
+    program_id: Pubkey,
+    name: String,
+    builtin_function: BuiltinFunctionWithContext,
+    activation_slot: Slot,
+}
+
+// Stands in for a scheduled builtin until its activation slot
+#[cfg(feature = "dev-context-only-utils")]
+solana_program_runtime::declare_process_instruction!(InactiveBuiltin, 0, |_invoke_context| {
+    Err(solana_sdk::instruction::InstructionError::IncorrectProgramId)
+});
+
 #[derive(Default, Debug, Clone, PartialEq)]
 #[cfg(feature = "dev-context-only-utils")]
 pub struct HashOverrides {
@@ -926,9 +950,17 @@ pub struct Bank {
     /// Collected fee details
     collector_fee_details: RwLock<CollectorFeeDetails>,
 
+    /// Accounts which were deleted by rent collected by transactions committed
+    /// to this bank, so that later transactions paying fees from them fail
+    /// with `AccountPurgedByRent` rather than `AccountNotFound`
+    accounts_purged_by_rent: DashSet<Pubkey>,
+
     /// The compute budget to use for transaction execution.
     compute_budget: Option<ComputeBudget>,
 
+    /// Overrides the maximum instruction stack depth of the compute budget.
+    max_instruction_stack_depth: Option<usize>,
+
     /// The max number of accounts that a transaction may lock.
     transaction_account_lock_limit: Option<usize>,
 
@@ -940,6 +972,14 @@ pub struct Bank {
     #[cfg(feature = "dev-context-only-utils")]
     hash_overrides: Arc<Mutex<HashOverrides>>,
 
+    /// Builtins waiting for their activation slot, shared by all descendant banks
+    #[cfg(feature = "dev-context-only-utils")]
+    scheduled_builtins: Arc<RwLock<Vec<ScheduledBuiltin>>>,
+
+    /// Builtins passed to `finish_init()` on top of `BUILTINS`, whose feature
+    /// transitions are applied in descendant banks as well
+    additional_builtins: Arc<Vec<BuiltinPrototype>>,
+
     /// The lattice hash of all accounts
     ///
     /// The value is only meaningful after freezing.
@@ -1154,11 +1194,16 @@ impl Bank {
             transaction_processor: TransactionBatchProcessor::default(),
             check_program_modification_slot: false,
             collector_fee_details: RwLock::new(CollectorFeeDetails::default()),
+            accounts_purged_by_rent: DashSet::default(),
             compute_budget: None,
+            max_instruction_stack_depth: None,
             transaction_account_lock_limit: None,
             fee_structure: FeeStructure::default(),
             #[cfg(feature = "dev-context-only-utils")]
             hash_overrides: Arc::new(Mutex::new(HashOverrides::default())),
+            #[cfg(feature = "dev-context-only-utils")]
+            scheduled_builtins: Arc::default(),
+            additional_builtins: Arc::default(),
             accounts_lt_hash: Mutex::new(AccountsLtHash(LtHash::identity())),
             cache_for_accounts_lt_hash: DashMap::default(),
             stats_for_accounts_lt_hash: AccountsLtHashStats::default(),
@@ -1196,6 +1241,7 @@ impl Bank {
         let mut bank = Self::default_with_accounts(accounts);
         bank.ancestors = Ancestors::from(vec![bank.slot()]);
         bank.compute_budget = runtime_config.compute_budget;
+        bank.max_instruction_stack_depth = runtime_config.max_instruction_stack_depth;
         bank.transaction_account_lock_limit = runtime_config.transaction_account_lock_limit;
         bank.transaction_debug_keys = debug_keys;
         bank.cluster_type = Some(genesis_config.cluster_type);
@@ -1410,11 +1456,16 @@ impl Bank {
             transaction_processor,
             check_program_modification_slot: false,
             collector_fee_details: RwLock::new(CollectorFeeDetails::default()),
+            accounts_purged_by_rent: DashSet::default(),
             compute_budget: parent.compute_budget,
+            max_instruction_stack_depth: parent.max_instruction_stack_depth,
             transaction_account_lock_limit: parent.transaction_account_lock_limit,
             fee_structure: parent.fee_structure.clone(),
             #[cfg(feature = "dev-context-only-utils")]
             hash_overrides: parent.hash_overrides.clone(),
+            #[cfg(feature = "dev-context-only-utils")]
+            scheduled_builtins: parent.scheduled_builtins.clone(),
+            additional_builtins: parent.additional_builtins.clone(),
             accounts_lt_hash: Mutex::new(parent.accounts_lt_hash.lock().unwrap().clone()),
             cache_for_accounts_lt_hash: DashMap::default(),
             stats_for_accounts_lt_hash: AccountsLtHashStats::default(),
@@ -1448,6 +1499,9 @@ impl Bank {
             new.distribute_partitioned_epoch_rewards();
         });
 
+        #[cfg(feature = "dev-context-only-utils")]
+        new.activate_scheduled_builtins(parent.slot());
+
         let (_epoch, slot_index) = new.epoch_schedule.get_epoch_and_slot_index(new.slot);
         let slots_in_epoch = new.epoch_schedule.get_slots_in_epoch(new.epoch);
 
@@ -1809,11 +1863,16 @@ impl Bank {
             check_program_modification_slot: false,
             // collector_fee_details is not serialized to snapshot
             collector_fee_details: RwLock::new(CollectorFeeDetails::default()),
+            accounts_purged_by_rent: DashSet::default(),
             compute_budget: runtime_config.compute_budget,
+            max_instruction_stack_depth: runtime_config.max_instruction_stack_depth,
             transaction_account_lock_limit: runtime_config.transaction_account_lock_limit,
             fee_structure: FeeStructure::default(),
             #[cfg(feature = "dev-context-only-utils")]
             hash_overrides: Arc::new(Mutex::new(HashOverrides::default())),
+            #[cfg(feature = "dev-context-only-utils")]
+            scheduled_builtins: Arc::default(),
+            additional_builtins: Arc::default(),
             accounts_lt_hash: Mutex::new(AccountsLtHash(LtHash([0xBAD1; LtHash::NUM_ELEMENTS]))),
             cache_for_accounts_lt_hash: DashMap::default(),
             stats_for_accounts_lt_hash: AccountsLtHashStats::default(),
@@ -2208,6 +2267,19 @@ impl Bank {
             .fill_missing_sysvar_cache_entries(self);
     }
 
+    /// Overwrite the sysvar account at `pubkey` with `account`, refreshing the
+    /// sysvar cache so programs observe the new contents
+    ///
+    /// Like any other sysvar update, the account's balance is adjusted to be
+    /// rent-exempt and the capitalization is updated accordingly.
+    pub fn set_sysvar_account_for_tests(&self, pubkey: &Pubkey, account: &AccountSharedData) {
+        assert!(sysvar::is_sysvar_id(pubkey), "{pubkey} is not a sysvar");
+        self.update_sysvar_account(pubkey, |_| account.clone());
+        self.transaction_processor.reset_sysvar_cache();
+        self.transaction_processor
+            .fill_missing_sysvar_cache_entries(self);
+    }
+
     fn update_slot_history(&self) {
         self.update_sysvar_account(&sysvar::slot_history::id(), |account| {
             let mut slot_history = account
@@ -3090,6 +3162,40 @@ impl Bank {
         self.tick_height.fetch_add(1, Relaxed);
     }
 
+    /// Registers the ticks in order, leaving the bank in the same state as
+    /// calling `register_tick()` for each hash. The tick height is advanced
+    /// in at most two steps and only the hash of the tick reaching the block
+    /// boundary is registered as a blockhash.
+    pub fn register_ticks(&self, hashes: &[Hash], scheduler: &InstalledSchedulerRwLock) {
+        assert!(
+            !self.freeze_started(),
+            "register_ticks() working on a bank that is already frozen or is undergoing freezing!"
+        );
+
+        let tick_height = self.tick_height.load(Relaxed);
+        // The block boundary is reached by the tick at this (1-based)
+        // position in the batch, if any.
+        let boundary_tick = self
+            .max_tick_height
+            .checked_sub(tick_height)
+            .and_then(|ticks| usize::try_from(ticks).ok())
+            .filter(|&ticks| 0 < ticks && ticks <= hashes.len());
+        match boundary_tick {
+            Some(boundary_tick) => {
+                self.tick_height
+                    .fetch_add((boundary_tick - 1) as u64, Relaxed);
+                // Same as register_tick(), the blockhash is registered before
+                // the tick height reaches the boundary.
+                self.register_recent_blockhash(&hashes[boundary_tick - 1], scheduler);
+                self.tick_height
+                    .fetch_add((hashes.len() - boundary_tick + 1) as u64, Relaxed);
+            }
+            None => {
+                self.tick_height.fetch_add(hashes.len() as u64, Relaxed);
+            }
+        }
+    }
+
     #[cfg(feature = "dev-context-only-utils")]
     pub fn register_tick_for_test(&self, hash: &Hash) {
         self.register_tick(hash, &BankWithScheduler::no_scheduler_available())
@@ -3302,6 +3408,7 @@ impl Bank {
                 account_overrides: Some(&account_overrides),
                 check_program_modification_slot: self.check_program_modification_slot,
                 compute_budget: self.compute_budget(),
+                max_instruction_stack_depth: self.max_instruction_stack_depth(),
                 log_messages_bytes_limit: None,
                 limit_to_load_programs: true,
                 recording_config: ExecutionRecordingConfig {
@@ -3937,13 +4044,35 @@ impl Bank {
             .collect()
     }
 
+    /// Accumulates the rent collected by committed transactions.
+    ///
+    /// Rent collected while loading a transaction's accounts is applied as
+    /// part of that transaction, so an account deleted this way is gone for
+    /// every transaction after it in the slot, and is remembered so those
+    /// transactions fail with `AccountPurgedByRent`. Accounts the transaction
+    /// itself emptied are not remembered, as rent alone didn't delete them.
+    /// Eager rent collection only happens in `freeze()`, after the last
+    /// transaction is committed.
     fn collect_rent(&self, processing_results: &[TransactionProcessingResult]) {
         let collected_rent = processing_results
             .iter()
             .filter_map(|processing_result| processing_result.processed_transaction())
             .filter_map(|processed_tx| processed_tx.executed_transaction())
             .filter(|executed_tx| executed_tx.was_successful())
-            .map(|executed_tx| executed_tx.loaded_transaction.rent)
+            .map(|executed_tx| {
+                let loaded_tx = &executed_tx.loaded_transaction;
+                loaded_tx
+                    .accounts
+                    .iter()
+                    .filter(|(pubkey, account)| {
+                        account.lamports() == 0
+                            && loaded_tx.rent_debits.get_account_post_balance(pubkey) == Some(0)
+                    })
+                    .for_each(|(pubkey, _)| {
+                        self.accounts_purged_by_rent.insert(*pubkey);
+                    });
+                loaded_tx.rent
+            })
             .sum();
         self.collected_rent.fetch_add(collected_rent, Relaxed);
     }
@@ -4638,6 +4767,7 @@ impl Bank {
                 account_overrides: None,
                 check_program_modification_slot: self.check_program_modification_slot,
                 compute_budget: self.compute_budget(),
+                max_instruction_stack_depth: self.max_instruction_stack_depth(),
                 log_messages_bytes_limit,
                 limit_to_load_programs: false,
                 recording_config,
@@ -4881,6 +5011,7 @@ impl Bank {
     ) {
         self.rewards_pool_pubkeys =
             Arc::new(genesis_config.rewards_pools.keys().cloned().collect());
+        self.additional_builtins = Arc::new(additional_builtins.unwrap_or_default().to_vec());
 
         self.apply_feature_activations(
             ApplyFeatureActivationsCaller::FinishInit,
@@ -4904,10 +5035,7 @@ impl Bank {
         }
 
         if !debug_do_not_add_builtins {
-            for builtin in BUILTINS
-                .iter()
-                .chain(additional_builtins.unwrap_or(&[]).iter())
-            {
+            for builtin in BUILTINS.iter().chain(self.additional_builtins.iter()) {
                 // The builtin should be added if it has no enable feature ID
                 // and it has not been migrated to Core BPF.
                 //
@@ -4918,7 +5046,13 @@ impl Bank {
                         .map(|a| a.owner() == &bpf_loader_upgradeable::id())
                         .unwrap_or(false)
                 };
-                if builtin.enable_feature_id.is_none() && !builtin_is_bpf(&builtin.program_id) {
+                let builtin_is_disabled = builtin
+                    .disable_feature_id
+                    .is_some_and(|feature_id| self.feature_set.is_active(&feature_id));
+                if builtin.enable_feature_id.is_none()
+                    && !builtin_is_bpf(&builtin.program_id)
+                    && !builtin_is_disabled
+                {
                     self.transaction_processor.add_builtin(
                         self,
                         builtin.program_id,
@@ -5054,6 +5188,11 @@ impl Bank {
         self.rc.accounts.load_with_fixed_root(ancestors, pubkey)
     }
 
+    /// Returns the accounts owned by `program_id`.
+    ///
+    /// Like the other account scans, accounts are returned in ascending pubkey
+    /// order unless `config.collect_all_unsorted` is set, and pages are always
+    /// ordered.
     pub fn get_program_accounts(
         &self,
         program_id: &Pubkey,
@@ -5096,10 +5235,66 @@ impl Bank {
         )
     }
 
+    /// Like `get_filtered_program_accounts`, but returns only the accounts in `page`,
+    /// ordered by pubkey
+    pub fn get_filtered_program_accounts_page<F: Fn(&AccountSharedData) -> bool>(
+        &self,
+        program_id: &Pubkey,
+        filter: F,
+        page: &ScanPage,
+        config: &ScanConfig,
+    ) -> ScanResult<Vec<TransactionAccount>> {
+        self.rc.accounts.load_page_by_program_with_filter(
+            &self.ancestors,
+            self.bank_id,
+            program_id,
+            filter,
+            page,
+            config,
+        )
+    }
+
+    /// Like `get_filtered_indexed_accounts`, but returns only the accounts in `page`,
+    /// ordered by pubkey
+    pub fn get_filtered_indexed_accounts_page<F: Fn(&AccountSharedData) -> bool>(
+        &self,
+        index_key: &IndexKey,
+        filter: F,
+        page: &ScanPage,
+        config: &ScanConfig,
+        byte_limit_for_scan: Option<usize>,
+    ) -> ScanResult<Vec<TransactionAccount>> {
+        self.rc.accounts.load_page_by_index_key_with_filter(
+            &self.ancestors,
+            self.bank_id,
+            index_key,
+            filter,
+            page,
+            config,
+            byte_limit_for_scan,
+        )
+    }
+
     pub fn account_indexes_include_key(&self, key: &Pubkey) -> bool {
         self.rc.accounts.account_indexes_include_key(key)
     }
 
+    /// Returns the size of each enabled secondary account index
+    pub fn secondary_index_usage(&self) -> HashMap<AccountIndex, SecondaryIndexUsage> {
+        let accounts_db = &self.rc.accounts.accounts_db;
+        accounts_db
+            .account_indexes
+            .indexes
+            .iter()
+            .map(|index| {
+                (
+                    index.clone(),
+                    accounts_db.accounts_index.get_secondary_index_usage(index),
+                )
+            })
+            .collect()
+    }
+
     /// Returns all the accounts this bank can load
     pub fn get_all_accounts(&self, sort_results: bool) -> ScanResult<Vec<PubkeyAccountSlot>> {
         self.rc
@@ -6616,7 +6811,8 @@ impl Bank {
         only_apply_transitions_for_new_features: bool,
         new_feature_activations: &AHashSet<Pubkey>,
     ) {
-        for builtin in BUILTINS.iter() {
+        let additional_builtins = Arc::clone(&self.additional_builtins);
+        for builtin in BUILTINS.iter().chain(additional_builtins.iter()) {
             // The `builtin_is_bpf` flag is used to handle the case where a
             // builtin is scheduled to be enabled by one feature gate and
             // later migrated to Core BPF by another.
@@ -6660,26 +6856,12 @@ impl Bank {
                 }
             };
 
-            if let Some(feature_id) = builtin.enable_feature_id {
-                let should_enable_builtin_on_feature_transition = !builtin_is_bpf
-                    && if only_apply_transitions_for_new_features {
-                        new_feature_activations.contains(&feature_id)
-                    } else {
-                        self.feature_set.is_active(&feature_id)
-                    };
-
-                if should_enable_builtin_on_feature_transition {
-                    self.transaction_processor.add_builtin(
-                        self,
-                        builtin.program_id,
-                        builtin.name,
-                        ProgramCacheEntry::new_builtin(
-                            self.feature_set.activated_slot(&feature_id).unwrap_or(0),
-                            builtin.name.len(),
-                            builtin.entrypoint,
-                        ),
-                    );
-                }
+            if !builtin_is_bpf {
+                self.apply_builtin_enable_and_disable_transitions(
+                    builtin,
+                    only_apply_transitions_for_new_features,
+                    new_feature_activations,
+                );
             }
         }
 
@@ -6714,6 +6896,75 @@ impl Bank {
         }
     }
 
+    /// Enable or disable `builtin` according to its enable and disable feature
+    /// gates.
+    ///
+    /// A builtin whose disable feature is active is removed from the bank's
+    /// builtins, and is never enabled again, even if its enable feature is
+    /// activated later. When the disable feature is newly activated, the
+    /// program account is purged and the program is tombstoned in the cache
+    /// from this slot on, leaving sibling forks untouched.
+    fn apply_builtin_enable_and_disable_transitions(
+        &mut self,
+        builtin: &BuiltinPrototype,
+        only_apply_transitions_for_new_features: bool,
+        new_feature_activations: &AHashSet<Pubkey>,
+    ) {
+        let should_apply_transition = |feature_id: &Pubkey| {
+            if only_apply_transitions_for_new_features {
+                new_feature_activations.contains(feature_id)
+            } else {
+                self.feature_set.is_active(feature_id)
+            }
+        };
+
+        if let Some(feature_id) = builtin.disable_feature_id {
+            if should_apply_transition(&feature_id) {
+                self.transaction_processor
+                    .builtin_program_ids
+                    .write()
+                    .unwrap()
+                    .remove(&builtin.program_id);
+                if only_apply_transitions_for_new_features {
+                    if let Some(account) = self.get_account_with_fixed_root(&builtin.program_id) {
+                        self.burn_and_purge_account(&builtin.program_id, account);
+                    }
+                    self.transaction_processor
+                        .program_cache
+                        .write()
+                        .unwrap()
+                        .assign_program(
+                            builtin.program_id,
+                            Arc::new(ProgramCacheEntry::new_tombstone(
+                                self.slot,
+                                ProgramCacheEntryOwner::NativeLoader,
+                                ProgramCacheEntryType::Closed,
+                            )),
+                        );
+                }
+                return;
+            }
+            if self.feature_set.is_active(&feature_id) {
+                return;
+            }
+        }
+
+        if let Some(feature_id) = builtin.enable_feature_id {
+            if should_apply_transition(&feature_id) {
+                self.transaction_processor.add_builtin(
+                    self,
+                    builtin.program_id,
+                    builtin.name,
+                    ProgramCacheEntry::new_builtin(
+                        self.feature_set.activated_slot(&feature_id).unwrap_or(0),
+                        builtin.name.len(),
+                        builtin.entrypoint,
+                    ),
+                );
+            }
+        }
+    }
+
     /// Use to replace programs by feature activation
     #[allow(dead_code)]
     fn replace_program_account(
@@ -6844,6 +7095,10 @@ impl Bank {
         self.compute_budget
     }
 
+    pub fn max_instruction_stack_depth(&self) -> Option<usize> {
+        self.max_instruction_stack_depth
+    }
+
     pub fn add_builtin(&self, program_id: Pubkey, name: &str, builtin: ProgramCacheEntry) {
         self.transaction_processor
             .add_builtin(self, program_id, name, builtin)
@@ -6871,6 +7126,10 @@ impl TransactionProcessingCallback for Bank {
             .map(|(acc, _)| acc)
     }
 
+    fn account_purged_by_rent(&self, address: &Pubkey) -> bool {
+        self.accounts_purged_by_rent.contains(address)
+    }
+
     // NOTE: must hold idempotent for the same set of arguments
     /// Add a builtin program account
     fn add_builtin_account(&self, name: &str, program_id: &Pubkey) {
@@ -7172,6 +7431,58 @@ impl Bank {
     pub fn set_hash_overrides(&self, hash_overrides: HashOverrides) {
         *self.hash_overrides.lock().unwrap() = hash_overrides;
     }
+
+    /// Register a builtin program that can only be invoked from banks at or
+    /// past `activation_slot`. Until then the program is added with a stand-in
+    /// which fails every instruction with `IncorrectProgramId`.
+    pub fn add_builtin_at_slot(
+        &self,
+        program_id: Pubkey,
+        name: &str,
+        builtin_function: BuiltinFunctionWithContext,
+        activation_slot: Slot,
+    ) {
+        if activation_slot <= self.slot() {
+            self.add_builtin(
+                program_id,
+                name,
+                ProgramCacheEntry::new_builtin(self.slot(), name.len(), builtin_function),
+            );
+        } else {
+            self.add_builtin(
+                program_id,
+                name,
+                ProgramCacheEntry::new_builtin(self.slot(), name.len(), InactiveBuiltin::vm),
+            );
+            self.scheduled_builtins
+                .write()
+                .unwrap()
+                .push(ScheduledBuiltin {
+                    program_id,
+                    name: name.to_string(),
+                    builtin_function,
+                    activation_slot,
+                });
+        }
+    }
+
+    /// Add the scheduled builtins activated between the parent's slot and this one
+    fn activate_scheduled_builtins(&self, parent_slot: Slot) {
+        let scheduled_builtins = self.scheduled_builtins.read().unwrap();
+        for builtin in scheduled_builtins.iter().filter(|builtin| {
+            parent_slot < builtin.activation_slot && builtin.activation_slot <= self.slot()
+        }) {
+            self.add_builtin(
+                builtin.program_id,
+                &builtin.name,
+                ProgramCacheEntry::new_builtin(
+                    self.slot(),
+                    builtin.name.len(),
+                    builtin.builtin_function,
+                ),
+            );
+        }
+    }
 }
 
 /// Compute how much an account has changed size.  This function is useful when the data size delta
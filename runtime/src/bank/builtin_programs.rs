@@ -1,9 +1,14 @@
 #[cfg(test)]
 mod tests {
     use {
-        crate::bank::*,
+        crate::bank::{tests::new_bank_from_parent_with_bank_forks, *},
         solana_feature_set::FeatureSet,
-        solana_sdk::{ed25519_program, genesis_config::create_genesis_config},
+        solana_sdk::{
+            ed25519_program,
+            genesis_config::create_genesis_config,
+            instruction::{Instruction, InstructionError},
+            signature::Signer,
+        },
     };
 
     #[test]
@@ -60,6 +65,131 @@ mod tests {
         // Simulate starting up from snapshot finishing the initialization for a frozen bank
         bank.finish_init(&genesis_config, None, false);
     }
+
+    #[test]
+    fn test_builtin_enable_and_disable_transitions() {
+        let (genesis_config, mint_keypair) = create_genesis_config(100_000_000);
+        let enable_feature_id = Pubkey::new_unique();
+        let disable_feature_id = Pubkey::new_unique();
+        let builtin_to_enable = BuiltinPrototype {
+            core_bpf_migration_config: None,
+            enable_feature_id: Some(enable_feature_id),
+            disable_feature_id: None,
+            program_id: Pubkey::new_unique(),
+            name: "builtin_to_enable",
+            entrypoint: solana_system_program::system_processor::Entrypoint::vm,
+        };
+        let builtin_to_disable = BuiltinPrototype {
+            core_bpf_migration_config: None,
+            enable_feature_id: None,
+            disable_feature_id: Some(disable_feature_id),
+            program_id: Pubkey::new_unique(),
+            name: "builtin_to_disable",
+            entrypoint: solana_system_program::system_processor::Entrypoint::vm,
+        };
+        let additional_builtins = [builtin_to_enable.clone(), builtin_to_disable.clone()];
+        let is_builtin = |bank: &Bank, program_id: &Pubkey| {
+            bank.transaction_processor
+                .builtin_program_ids
+                .read()
+                .unwrap()
+                .contains(program_id)
+        };
+        // The system program entrypoint rejects empty instruction data, which
+        // shows that the builtin was invoked
+        let invoke = |bank: &Bank, program_id: &Pubkey| {
+            let instruction = Instruction::new_with_bytes(*program_id, &[], vec![]);
+            bank.process_transaction(&Transaction::new_signed_with_payer(
+                &[instruction],
+                Some(&mint_keypair.pubkey()),
+                &[&mint_keypair],
+                bank.last_blockhash(),
+            ))
+        };
+
+        // Replays the ledger from genesis, activating both features mid-ledger
+        // so that they take effect at the start of the next epoch
+        let replay = || {
+            let mut bank = Bank::new_for_tests(&genesis_config);
+            bank.finish_init(&genesis_config, Some(&additional_builtins), false);
+            for feature_id in [&enable_feature_id, &disable_feature_id] {
+                bank.store_account_and_update_capitalization(
+                    feature_id,
+                    &feature::create_account(&feature::Feature::default(), 42),
+                );
+            }
+            let (parent, bank_forks) = bank.wrap_with_bank_forks_for_tests();
+            let first_slot_in_next_epoch = parent.epoch_schedule().get_first_slot_in_epoch(1);
+            let bank = new_bank_from_parent_with_bank_forks(
+                &bank_forks,
+                parent.clone(),
+                &Pubkey::default(),
+                first_slot_in_next_epoch,
+            );
+            (parent, bank, bank_forks)
+        };
+
+        let (parent, bank, bank_forks) = replay();
+        assert!(!is_builtin(&parent, &builtin_to_enable.program_id));
+        assert!(is_builtin(&parent, &builtin_to_disable.program_id));
+        assert!(bank.feature_set.is_active(&enable_feature_id));
+        assert!(bank.feature_set.is_active(&disable_feature_id));
+
+        assert!(is_builtin(&bank, &builtin_to_enable.program_id));
+        assert!(bank.get_account(&builtin_to_enable.program_id).is_some());
+        assert!(!is_builtin(&bank, &builtin_to_disable.program_id));
+        assert!(bank.get_account(&builtin_to_disable.program_id).is_none());
+        assert!(parent.get_account(&builtin_to_disable.program_id).is_some());
+        assert!(bank.calculate_and_verify_capitalization(true));
+
+        assert_eq!(
+            invoke(&bank, &builtin_to_enable.program_id),
+            Err(TransactionError::InstructionError(
+                0,
+                InstructionError::InvalidInstructionData
+            ))
+        );
+        assert_eq!(
+            invoke(&bank, &builtin_to_disable.program_id),
+            Err(TransactionError::ProgramAccountNotFound)
+        );
+
+        // A sibling fork which has not crossed the epoch boundary can still
+        // invoke the disabled builtin
+        let sibling = new_bank_from_parent_with_bank_forks(
+            &bank_forks,
+            parent.clone(),
+            &Pubkey::default(),
+            1,
+        );
+        assert!(is_builtin(&sibling, &builtin_to_disable.program_id));
+        assert_eq!(
+            invoke(&sibling, &builtin_to_disable.program_id),
+            Err(TransactionError::InstructionError(
+                0,
+                InstructionError::InvalidInstructionData
+            ))
+        );
+
+        // A fresh replay of the same ledger produces the same bank hashes
+        bank.freeze();
+        let (replayed_parent, replayed_bank, _replayed_bank_forks) = replay();
+        replayed_bank.freeze();
+        assert_eq!(parent.hash(), replayed_parent.hash());
+        assert_eq!(bank.hash(), replayed_bank.hash());
+
+        // The transitions are not applied again in later epochs
+        let first_slot_in_next_epoch = bank.epoch_schedule().get_first_slot_in_epoch(2);
+        let bank = new_bank_from_parent_with_bank_forks(
+            &bank_forks,
+            bank,
+            &Pubkey::default(),
+            first_slot_in_next_epoch,
+        );
+        assert!(is_builtin(&bank, &builtin_to_enable.program_id));
+        assert!(!is_builtin(&bank, &builtin_to_disable.program_id));
+        assert!(bank.get_account(&builtin_to_disable.program_id).is_none());
+    }
 }
 
 #[cfg(test)]
@@ -6,6 +6,7 @@ use {
     solana_sdk::{
         account::{ReadableAccount, WritableAccount},
         fee::FeeBudgetLimits,
+        native_token::Lamports,
         pubkey::Pubkey,
         reward_info::RewardInfo,
         reward_type::RewardType,
@@ -106,7 +107,12 @@ impl Bank {
         } else {
             (0, 0)
         };
-        (deposit.saturating_add(fee_details.priority_fee), burn)
+        (
+            Lamports::new(deposit)
+                .saturating_add(Lamports::new(fee_details.priority_fee))
+                .get(),
+            burn,
+        )
     }
 
     fn deposit_or_burn_fee(&self, deposit: u64, burn: &mut u64) {
@@ -133,7 +139,9 @@ impl Bank {
                     ("num_lamports", deposit, i64),
                     ("error", err.to_string(), String),
                 );
-                *burn = burn.saturating_add(deposit);
+                *burn = Lamports::new(*burn)
+                    .saturating_add(Lamports::new(deposit))
+                    .get();
             }
         }
     }
@@ -191,7 +199,7 @@ impl Bank {
         vote_accounts: &VoteAccountsHashMap,
         rent_to_be_distributed: u64,
     ) {
-        let mut total_staked = 0;
+        let mut total_staked = Lamports::ZERO;
 
         // Collect the stake associated with each validator.
         // Note that a validator may be present in this vector multiple times if it happens to have
@@ -202,7 +210,7 @@ impl Bank {
                 if *staked == 0 {
                     None
                 } else {
-                    total_staked += *staked;
+                    total_staked = total_staked.saturating_add(Lamports::new(*staked));
                     Some((*account.node_pubkey(), *staked))
                 }
             })
@@ -225,31 +233,35 @@ impl Bank {
             (staked1, pubkey1).cmp(&(staked2, pubkey2)).reverse()
         });
 
-        let mut rent_distributed_in_initial_round = 0;
+        let mut rent_distributed_in_initial_round = Lamports::ZERO;
         let validator_rent_shares = validator_stakes
             .into_iter()
             .map(|(pubkey, staked)| {
                 let rent_share = (((staked as u128) * (rent_to_be_distributed as u128))
-                    / (total_staked as u128))
+                    / (total_staked.get() as u128))
                     .try_into()
                     .unwrap();
-                rent_distributed_in_initial_round += rent_share;
+                rent_distributed_in_initial_round =
+                    rent_distributed_in_initial_round.saturating_add(Lamports::new(rent_share));
                 (pubkey, rent_share)
             })
             .collect::<Vec<(Pubkey, u64)>>();
 
         // Leftover lamports after fraction calculation, will be paid to validators starting from highest stake
         // holder
-        let mut leftover_lamports = rent_to_be_distributed - rent_distributed_in_initial_round;
+        let mut leftover_lamports =
+            Lamports::new(rent_to_be_distributed).saturating_sub(rent_distributed_in_initial_round);
 
-        let mut rent_to_burn: u64 = 0;
+        let mut rent_to_burn = Lamports::ZERO;
         let mut rewards = vec![];
         validator_rent_shares
             .into_iter()
             .for_each(|(pubkey, rent_share)| {
-                let rent_to_be_paid = if leftover_lamports > 0 {
-                    leftover_lamports -= 1;
-                    rent_share + 1
+                let rent_to_be_paid = if leftover_lamports > Lamports::ZERO {
+                    leftover_lamports = leftover_lamports.saturating_sub(Lamports::new(1));
+                    Lamports::new(rent_share)
+                        .saturating_add(Lamports::new(1))
+                        .get()
                 } else {
                     rent_share
                 };
@@ -274,19 +286,20 @@ impl Bank {
 
                             // overflow adding lamports or resulting account is invalid
                             // so burn lamports and track lamports burned per slot
-                            rent_to_burn = rent_to_burn.saturating_add(rent_to_be_paid);
+                            rent_to_burn =
+                                rent_to_burn.saturating_add(Lamports::new(rent_to_be_paid));
                         }
                     }
                 }
             });
         self.rewards.write().unwrap().append(&mut rewards);
 
-        if rent_to_burn > 0 {
-            self.capitalization.fetch_sub(rent_to_burn, Relaxed);
+        if rent_to_burn > Lamports::ZERO {
+            self.capitalization.fetch_sub(rent_to_burn.get(), Relaxed);
             datapoint_warn!(
                 "bank-burned_rent",
                 ("slot", self.slot(), i64),
-                ("num_lamports", rent_to_burn, i64)
+                ("num_lamports", rent_to_burn.get(), i64)
             );
         }
 
@@ -30,7 +30,8 @@ use {
         accounts_db::DEFAULT_ACCOUNTS_SHRINK_RATIO,
         accounts_hash::{AccountsDeltaHash, AccountsHasher},
         accounts_index::{
-            AccountIndex, AccountSecondaryIndexes, IndexKey, ScanConfig, ScanError, ITER_BATCH_SIZE,
+            AccountIndex, AccountSecondaryIndexes, IndexKey, ScanConfig, ScanError, ScanPage,
+            ITER_BATCH_SIZE,
         },
         accounts_partition::{self, PartitionIndex, RentPayingAccountsByPartition},
         ancestors::Ancestors,
@@ -80,7 +81,7 @@ use {
         packet::PACKET_DATA_SIZE,
         poh_config::PohConfig,
         program::MAX_RETURN_DATA,
-        pubkey::Pubkey,
+        pubkey::{Pubkey, PUBKEY_BYTES},
         rent::Rent,
         rent_collector::RENT_EXEMPT_RENT_EPOCH,
         reward_type::RewardType,
@@ -2510,6 +2511,48 @@ fn test_detect_failed_duplicate_transactions() {
     assert_eq!(bank.get_balance(&mint_keypair.pubkey()), 5000);
 }
 
+#[test]
+fn test_get_fee_for_message_matches_charged_fee() {
+    let (mut genesis_config, mint_keypair) = create_genesis_config(LAMPORTS_PER_SOL);
+    genesis_config.fee_rate_governor = FeeRateGovernor::new(5_000, 0);
+    let (bank, _bank_forks) = Bank::new_with_bank_forks_for_tests(&genesis_config);
+    let payer = Keypair::new();
+    let signer = Keypair::new();
+    let recipient = Pubkey::new_unique();
+    bank.transfer(LAMPORTS_PER_SOL / 4, &mint_keypair, &payer.pubkey())
+        .unwrap();
+    bank.transfer(LAMPORTS_PER_SOL / 4, &mint_keypair, &signer.pubkey())
+        .unwrap();
+
+    // Two signatures and a prioritization fee
+    let amount = LAMPORTS_PER_SOL / 10;
+    let message = Message::new_with_blockhash(
+        &[
+            ComputeBudgetInstruction::set_compute_unit_limit(200_000),
+            ComputeBudgetInstruction::set_compute_unit_price(1_000_000),
+            system_instruction::transfer(&payer.pubkey(), &recipient, amount),
+            system_instruction::transfer(&signer.pubkey(), &recipient, amount),
+        ],
+        Some(&payer.pubkey()),
+        &bank.last_blockhash(),
+    );
+    let fee = bank
+        .get_fee_for_message(&new_sanitized_message(message.clone()))
+        .unwrap();
+    assert!(fee > 2 * 5_000);
+
+    let payer_balance = bank.get_balance(&payer.pubkey());
+    let signer_balance = bank.get_balance(&signer.pubkey());
+    let tx = Transaction::new(&[&payer, &signer], message, bank.last_blockhash());
+    assert_eq!(bank.process_transaction(&tx), Ok(()));
+    assert_eq!(
+        bank.get_balance(&payer.pubkey()),
+        payer_balance - amount - fee
+    );
+    assert_eq!(bank.get_balance(&signer.pubkey()), signer_balance - amount);
+    assert_eq!(bank.get_balance(&recipient), 2 * amount);
+}
+
 #[test]
 fn test_account_not_found() {
     solana_logger::setup();
@@ -4225,6 +4268,37 @@ fn test_bank_update_sysvar_account() {
     }
 }
 
+#[test]
+fn test_set_sysvar_account_for_tests() {
+    use sysvar::clock::Clock;
+
+    let (genesis_config, _mint_keypair) = create_genesis_config(500);
+    let bank = Bank::new_for_tests(&genesis_config);
+    let clock = Clock {
+        unix_timestamp: 42,
+        ..bank.clock()
+    };
+    let old_lamports = bank.get_account(&sysvar::clock::id()).unwrap().lamports();
+    let account = create_account(&clock, (old_lamports + 10, INITIAL_RENT_EPOCH));
+
+    assert_capitalization_diff(
+        &bank,
+        || bank.set_sysvar_account_for_tests(&sysvar::clock::id(), &account),
+        |old, new| {
+            assert_eq!(old + 10, new);
+            true
+        },
+    );
+    assert_eq!(bank.clock(), clock);
+    assert_eq!(
+        bank.get_sysvar_cache_for_tests()
+            .get_clock()
+            .unwrap()
+            .as_ref(),
+        &clock
+    );
+}
+
 #[test]
 fn test_bank_epoch_vote_accounts() {
     let leader_pubkey = solana_pubkey::new_rand();
@@ -4831,6 +4905,163 @@ fn test_get_filtered_indexed_accounts() {
     assert_eq!(indexed_accounts[0], (address, new_account));
 }
 
+#[test]
+fn test_program_account_scans_are_ordered() {
+    let (genesis_config, _mint_keypair) = create_genesis_config(500);
+    let mut account_indexes = AccountSecondaryIndexes::default();
+    account_indexes.indexes.insert(AccountIndex::ProgramId);
+    let bank_config = BankTestConfig {
+        accounts_db_config: AccountsDbConfig {
+            account_indexes: Some(account_indexes),
+            ..ACCOUNTS_DB_CONFIG_FOR_TESTING
+        },
+    };
+    let bank = Arc::new(Bank::new_with_config_for_tests(
+        &genesis_config,
+        bank_config,
+    ));
+
+    let program_id = Pubkey::new_unique();
+    let mut expected: Vec<_> = (0..500)
+        .map(|_| {
+            let address = solana_pubkey::new_rand();
+            bank.store_account(&address, &AccountSharedData::new(1, 0, &program_id));
+            address
+        })
+        .collect();
+    expected.sort_unstable();
+
+    // Keep storing accounts, both owned by the program and not, while scanning
+    let exit = Arc::new(AtomicBool::new(false));
+    let store_thread = {
+        let bank = bank.clone();
+        let exit = exit.clone();
+        let expected = expected.clone();
+        Builder::new()
+            .spawn(move || {
+                let mut lamports = 1;
+                while !exit.load(Relaxed) {
+                    lamports += 1;
+                    for address in expected.iter().step_by(7) {
+                        bank.store_account(
+                            address,
+                            &AccountSharedData::new(lamports, 0, &program_id),
+                        );
+                    }
+                    bank.store_account(
+                        &solana_pubkey::new_rand(),
+                        &AccountSharedData::new(lamports, 0, &Pubkey::new_unique()),
+                    );
+                }
+            })
+            .unwrap()
+    };
+
+    let addresses = |accounts: Vec<TransactionAccount>| -> Vec<Pubkey> {
+        accounts.into_iter().map(|(address, _)| address).collect()
+    };
+    let ordered = ScanConfig::new(false);
+    for _ in 0..20 {
+        let accounts = bank.get_program_accounts(&program_id, &ordered).unwrap();
+        assert_eq!(addresses(accounts), expected);
+
+        let accounts = bank
+            .get_filtered_indexed_accounts(
+                &IndexKey::ProgramId(program_id),
+                |_| true,
+                &ordered,
+                None,
+            )
+            .unwrap();
+        assert_eq!(addresses(accounts), expected);
+
+        for index_key in [None, Some(IndexKey::ProgramId(program_id))] {
+            let mut paged = vec![];
+            let mut after = None;
+            loop {
+                let page = ScanPage { after, limit: 64 };
+                // pages are ordered whatever the config
+                let config = ScanConfig::default();
+                let accounts = match &index_key {
+                    Some(index_key) => bank.get_filtered_indexed_accounts_page(
+                        index_key,
+                        |_| true,
+                        &page,
+                        &config,
+                        None,
+                    ),
+                    None => bank.get_filtered_program_accounts_page(
+                        &program_id,
+                        |_| true,
+                        &page,
+                        &config,
+                    ),
+                }
+                .unwrap();
+                let Some((last, _)) = accounts.last() else {
+                    break;
+                };
+                after = Some(*last);
+                paged.extend(addresses(accounts));
+            }
+            assert_eq!(paged, expected);
+        }
+    }
+
+    exit.store(true, Relaxed);
+    store_thread.join().unwrap();
+}
+
+#[test]
+fn test_get_filtered_indexed_accounts_by_token_owner() {
+    let (genesis_config, _mint_keypair) = create_genesis_config(500);
+    let mut account_indexes = AccountSecondaryIndexes::default();
+    account_indexes.indexes.insert(AccountIndex::SplTokenOwner);
+    let bank_config = BankTestConfig {
+        accounts_db_config: AccountsDbConfig {
+            account_indexes: Some(account_indexes),
+            ..ACCOUNTS_DB_CONFIG_FOR_TESTING
+        },
+    };
+    let bank = Arc::new(Bank::new_with_config_for_tests(
+        &genesis_config,
+        bank_config,
+    ));
+
+    // Token accounts for the same owner under the legacy token program and its upgrade
+    let owner = Pubkey::new_unique();
+    let mut data = vec![0; token::Account::get_packed_len()];
+    data[token::SPL_TOKEN_ACCOUNT_MINT_OFFSET..][..PUBKEY_BYTES]
+        .copy_from_slice(Pubkey::new_unique().as_ref());
+    data[token::SPL_TOKEN_ACCOUNT_OWNER_OFFSET..][..PUBKEY_BYTES].copy_from_slice(owner.as_ref());
+    let mut expected = [token::id(), token::program_v3_4_0::id()]
+        .into_iter()
+        .map(|token_id| {
+            let address = Pubkey::new_unique();
+            let account = AccountSharedData::create(1, data.clone(), token_id, false, 0);
+            bank.store_account(&address, &account);
+            (address, account)
+        })
+        .collect::<Vec<_>>();
+    // Accounts of unknown programs are not indexed, even with a token account layout
+    bank.store_account(
+        &Pubkey::new_unique(),
+        &AccountSharedData::create(1, data, Pubkey::new_unique(), false, 0),
+    );
+
+    let mut indexed_accounts = bank
+        .get_filtered_indexed_accounts(
+            &IndexKey::SplTokenOwner(owner),
+            |_| true,
+            &ScanConfig::default(),
+            None,
+        )
+        .unwrap();
+    indexed_accounts.sort_by_key(|(address, _)| *address);
+    expected.sort_by_key(|(address, _)| *address);
+    assert_eq!(indexed_accounts, expected);
+}
+
 #[test]
 fn test_status_cache_ancestors() {
     solana_logger::setup();
@@ -4905,6 +5136,72 @@ fn test_add_builtin() {
     );
 }
 
+#[test]
+fn test_add_builtin_at_slot() {
+    let (genesis_config, mint_keypair) = create_genesis_config_no_tx_fee_no_rent(500);
+    let (bank, bank_forks) = Bank::new_with_bank_forks_for_tests(&genesis_config);
+
+    declare_process_instruction!(MockBuiltin, 1, |_invoke_context| {
+        Err(InstructionError::Custom(42))
+    });
+
+    let program_id = solana_pubkey::new_rand();
+    let activation_slot = 2;
+    bank.add_builtin_at_slot(program_id, "mock_program", MockBuiltin::vm, activation_slot);
+
+    // Each attempt gets its own instruction data, so that a transaction
+    // committed as fees-only is never re-processed
+    let blockhash = bank.last_blockhash();
+    let transaction = |attempt: u8| {
+        Transaction::new_signed_with_payer(
+            &[Instruction::new_with_bytes(program_id, &[attempt], vec![])],
+            Some(&mint_keypair.pubkey()),
+            &[&mint_keypair],
+            blockhash,
+        )
+    };
+
+    // Before the activation slot the program id is invalid
+    let bank = new_bank_from_parent_with_bank_forks(
+        bank_forks.as_ref(),
+        bank,
+        &Pubkey::default(),
+        activation_slot - 1,
+    );
+    assert_eq!(
+        bank.process_transaction(&transaction(0)),
+        Err(TransactionError::InstructionError(
+            0,
+            InstructionError::IncorrectProgramId
+        ))
+    );
+
+    // From the activation slot on it is invoked
+    let bank = new_bank_from_parent_with_bank_forks(
+        bank_forks.as_ref(),
+        bank,
+        &Pubkey::default(),
+        activation_slot,
+    );
+    assert!(bank.get_account(&program_id).is_some());
+    assert_eq!(
+        bank.process_transaction(&transaction(1)),
+        Err(TransactionError::InstructionError(
+            0,
+            InstructionError::Custom(42)
+        ))
+    );
+
+    // Descendants keep the builtin without adding it again
+    let bank = new_bank_from_parent_with_bank_forks(
+        bank_forks.as_ref(),
+        bank,
+        &Pubkey::default(),
+        activation_slot + 1,
+    );
+    assert!(bank.get_account(&program_id).is_some());
+}
+
 #[test]
 fn test_add_duplicate_static_program() {
     let GenesisConfigInfo {
@@ -5119,6 +5416,40 @@ fn test_hash_internal_state_unchanged_with_ticks() {
     assert_eq!(bank1.hash_internal_state(), hash1);
 }
 
+#[test]
+fn test_register_ticks() {
+    let (genesis_config, _) = create_genesis_config(500);
+    let ticks_per_slot = genesis_config.ticks_per_slot;
+    let bank = Arc::new(Bank::new_for_tests(&genesis_config));
+    let hashes: Vec<_> = std::iter::repeat_with(Hash::new_unique)
+        .take(ticks_per_slot as usize + 3)
+        .collect();
+    // Batches which stop short of, reach, and run past the block boundary,
+    // starting from an empty and a partially filled slot.
+    for num_prior_ticks in [0, 2] {
+        for num_ticks in [1, ticks_per_slot - num_prior_ticks, hashes.len() as u64] {
+            let hashes = &hashes[..num_ticks as usize];
+            let make_bank = || {
+                let bank = new_from_parent(bank.clone());
+                for _ in 0..num_prior_ticks {
+                    bank.register_unique_tick();
+                }
+                bank
+            };
+            let expected = make_bank();
+            for hash in hashes {
+                expected.register_tick_for_test(hash);
+            }
+            let actual = make_bank();
+            actual.register_ticks(hashes, &BankWithScheduler::no_scheduler_available());
+            assert_eq!(actual.tick_height(), expected.tick_height());
+            assert_eq!(actual.last_blockhash(), expected.last_blockhash());
+            assert_eq!(actual.is_complete(), expected.is_complete());
+            assert_eq!(actual.hash_internal_state(), expected.hash_internal_state());
+        }
+    }
+}
+
 #[ignore]
 #[test]
 fn test_banks_leak() {
@@ -5470,6 +5801,138 @@ fn test_nonce_transaction() {
     );
 }
 
+#[test_case(false; "advancing_tx_first")]
+#[test_case(true; "advancing_tx_last")]
+fn test_nonce_reuse_in_same_batch(reverse: bool) {
+    let (bank, _mint_keypair, custodian_keypair, nonce_keypair, _bank_forks) =
+        setup_nonce_with_bank(
+            10_000_000,
+            |_| {},
+            5_000_000,
+            250_000,
+            None,
+            FeatureSet::all_enabled(),
+        )
+        .unwrap();
+    let custodian_pubkey = custodian_keypair.pubkey();
+    let nonce_pubkey = nonce_keypair.pubkey();
+    let nonce_hash = get_nonce_blockhash(&bank, &nonce_pubkey).unwrap();
+
+    let nonce_transfer = |to: &Pubkey| {
+        Transaction::new_signed_with_payer(
+            &[
+                system_instruction::advance_nonce_account(&nonce_pubkey, &nonce_pubkey),
+                system_instruction::transfer(&custodian_pubkey, to, 100_000),
+            ],
+            Some(&custodian_pubkey),
+            &[&custodian_keypair, &nonce_keypair],
+            nonce_hash,
+        )
+    };
+    let alice_pubkey = Pubkey::new_unique();
+    let bob_pubkey = Pubkey::new_unique();
+    let mut txs = vec![nonce_transfer(&alice_pubkey), nonce_transfer(&bob_pubkey)];
+    if reverse {
+        txs.reverse();
+    }
+
+    // Both transactions write the nonce account, so only the first one in the
+    // batch is executed and the second is rejected before its nonce is checked
+    assert_eq!(
+        bank.process_transactions(txs.iter()),
+        vec![Ok(()), Err(TransactionError::AccountInUse)]
+    );
+    let (paid, unpaid) = if reverse {
+        (bob_pubkey, alice_pubkey)
+    } else {
+        (alice_pubkey, bob_pubkey)
+    };
+    assert_eq!(bank.get_balance(&paid), 100_000);
+    assert_eq!(bank.get_balance(&unpaid), 0);
+    let advanced_nonce_hash = get_nonce_blockhash(&bank, &nonce_pubkey).unwrap();
+    assert_ne!(advanced_nonce_hash, nonce_hash);
+
+    // Retrying the rejected transaction later in the same slot sees the
+    // advanced nonce, exactly as it would in a later slot
+    assert_eq!(
+        bank.process_transaction(&txs[1]),
+        Err(TransactionError::BlockhashNotFound)
+    );
+    assert_eq!(bank.get_balance(&unpaid), 0);
+    assert_eq!(
+        get_nonce_blockhash(&bank, &nonce_pubkey).unwrap(),
+        advanced_nonce_hash
+    );
+}
+
+#[test]
+fn test_nonce_reuse_across_batches_in_same_slot() {
+    let (bank, _mint_keypair, custodian_keypair, nonce_keypair, _bank_forks) =
+        setup_nonce_with_bank(
+            10_000_000,
+            |_| {},
+            5_000_000,
+            250_000,
+            None,
+            FeatureSet::all_enabled(),
+        )
+        .unwrap();
+    let custodian_pubkey = custodian_keypair.pubkey();
+    let nonce_pubkey = nonce_keypair.pubkey();
+    let nonce_hash = get_nonce_blockhash(&bank, &nonce_pubkey).unwrap();
+
+    let first_tx = Transaction::new_signed_with_payer(
+        &[
+            system_instruction::advance_nonce_account(&nonce_pubkey, &nonce_pubkey),
+            system_instruction::transfer(&custodian_pubkey, &Pubkey::new_unique(), 100_000),
+        ],
+        Some(&custodian_pubkey),
+        &[&custodian_keypair, &nonce_keypair],
+        nonce_hash,
+    );
+    assert_eq!(bank.process_transaction(&first_tx), Ok(()));
+    let advanced_nonce_hash = get_nonce_blockhash(&bank, &nonce_pubkey).unwrap();
+    assert_eq!(advanced_nonce_hash, *bank.next_durable_nonce().as_hash());
+
+    // The old stored hash is not a recent blockhash, so the transaction can
+    // only be treated as a nonce transaction, whose nonce no longer matches
+    let custodian_balance = bank.get_balance(&custodian_pubkey);
+    let stale_tx = Transaction::new_signed_with_payer(
+        &[
+            system_instruction::advance_nonce_account(&nonce_pubkey, &nonce_pubkey),
+            system_instruction::transfer(&custodian_pubkey, &Pubkey::new_unique(), 100_000),
+        ],
+        Some(&custodian_pubkey),
+        &[&custodian_keypair, &nonce_keypair],
+        nonce_hash,
+    );
+    assert!(!bank.is_hash_valid_for_age(&nonce_hash, MAX_PROCESSING_AGE));
+    assert_eq!(
+        bank.process_transaction(&stale_tx),
+        Err(TransactionError::BlockhashNotFound)
+    );
+
+    // The advanced nonce can't be used again until the next slot either
+    let advanced_tx = Transaction::new_signed_with_payer(
+        &[
+            system_instruction::advance_nonce_account(&nonce_pubkey, &nonce_pubkey),
+            system_instruction::transfer(&custodian_pubkey, &Pubkey::new_unique(), 100_000),
+        ],
+        Some(&custodian_pubkey),
+        &[&custodian_keypair, &nonce_keypair],
+        advanced_nonce_hash,
+    );
+    assert_eq!(
+        bank.process_transaction(&advanced_tx),
+        Err(TransactionError::BlockhashNotFound)
+    );
+    assert_eq!(bank.get_balance(&custodian_pubkey), custodian_balance);
+    assert_eq!(
+        get_nonce_blockhash(&bank, &nonce_pubkey).unwrap(),
+        advanced_nonce_hash
+    );
+}
+
 #[test]
 fn test_nonce_transaction_with_tx_wide_caps() {
     let feature_set = FeatureSet::all_enabled();
@@ -11905,6 +12368,121 @@ fn test_accounts_data_size_and_rent_collection(should_collect_rent: bool) {
     assert!(!should_collect_rent || reclaimed_data_size == data_size);
 }
 
+/// Ensure that paying fees from an account deleted by rent collection fails
+/// with `AccountPurgedByRent`, whether the rent was collected by an earlier
+/// transaction in the slot or while loading the fee payer itself
+#[test_case(true; "purged before fee payer")]
+#[test_case(false; "purged by fee payer")]
+fn test_fee_payer_purged_by_rent(purge_first: bool) {
+    let GenesisConfigInfo {
+        mut genesis_config,
+        mint_keypair,
+        ..
+    } = genesis_utils::create_genesis_config(100 * LAMPORTS_PER_SOL);
+    genesis_config.rent = Rent::default();
+    genesis_config
+        .accounts
+        .remove(&solana_feature_set::disable_rent_fees_collection::id());
+    let purged_keypair = Keypair::new();
+    let recipient = Pubkey::new_unique();
+
+    let mut results = vec![];
+    for _ in 0..3 {
+        let bank = Arc::new(Bank::new_for_tests(&genesis_config));
+        let slot = bank.slot() + bank.slot_count_per_normal_epoch();
+        let bank = Arc::new(Bank::new_from_parent(bank, &Pubkey::default(), slot));
+        assert!(bank.should_collect_rent());
+
+        // A rent-paying account whose rent due for the epoch exceeds its balance
+        bank.store_account(
+            &purged_keypair.pubkey(),
+            &AccountSharedData::new(1, 0, &system_program::id()),
+        );
+
+        // Write-locks the rent-paying account without touching its balance
+        let mut instruction =
+            system_instruction::transfer(&mint_keypair.pubkey(), &recipient, LAMPORTS_PER_SOL);
+        instruction
+            .accounts
+            .push(AccountMeta::new(purged_keypair.pubkey(), false));
+        let purge_tx = Transaction::new_signed_with_payer(
+            &[instruction],
+            Some(&mint_keypair.pubkey()),
+            &[&mint_keypair],
+            bank.last_blockhash(),
+        );
+        let fee_payer_tx =
+            system_transaction::transfer(&purged_keypair, &recipient, 1, bank.last_blockhash());
+
+        let (purge_result, fee_payer_result) = if purge_first {
+            let purge_result = bank.process_transaction(&purge_tx);
+            (purge_result, bank.process_transaction(&fee_payer_tx))
+        } else {
+            let fee_payer_result = bank.process_transaction(&fee_payer_tx);
+            (bank.process_transaction(&purge_tx), fee_payer_result)
+        };
+        assert_eq!(purge_result, Ok(()));
+        assert_eq!(fee_payer_result, Err(TransactionError::AccountPurgedByRent));
+        assert_eq!(bank.get_balance(&purged_keypair.pubkey()), 0);
+        assert_eq!(bank.get_balance(&recipient), LAMPORTS_PER_SOL);
+        results.push((purge_result, fee_payer_result, bank.get_balance(&recipient)));
+    }
+    assert!(results.windows(2).all(|w| w[0] == w[1]));
+}
+
+/// Ensure that an account which a transaction empties after paying rent is not
+/// reported as purged by rent to later transactions
+#[test]
+fn test_fee_payer_emptied_after_rent_not_purged_by_rent() {
+    let GenesisConfigInfo {
+        mut genesis_config,
+        mint_keypair,
+        ..
+    } = genesis_utils::create_genesis_config(100 * LAMPORTS_PER_SOL);
+    genesis_config.rent = Rent::default();
+    genesis_config
+        .accounts
+        .remove(&solana_feature_set::disable_rent_fees_collection::id());
+    let bank = Arc::new(Bank::new_for_tests(&genesis_config));
+    let slot = bank.slot() + bank.slot_count_per_normal_epoch();
+    let bank = Arc::new(Bank::new_from_parent(bank, &Pubkey::default(), slot));
+
+    // A rent-paying account which can pay the rent due for the epoch
+    let emptied_keypair = Keypair::new();
+    let account = AccountSharedData::new(100_000, 0, &system_program::id());
+    let rent_due = bank
+        .rent_collector()
+        .collect_from_existing_account(&emptied_keypair.pubkey(), &mut account.clone())
+        .rent_amount;
+    assert!(rent_due > 0 && rent_due < account.lamports());
+    bank.store_account(&emptied_keypair.pubkey(), &account);
+
+    // Transfers whatever is left after rent
+    let empty_tx = Transaction::new_signed_with_payer(
+        &[system_instruction::transfer(
+            &emptied_keypair.pubkey(),
+            &mint_keypair.pubkey(),
+            account.lamports() - rent_due,
+        )],
+        Some(&mint_keypair.pubkey()),
+        &[&mint_keypair, &emptied_keypair],
+        bank.last_blockhash(),
+    );
+    assert_eq!(bank.process_transaction(&empty_tx), Ok(()));
+    assert_eq!(bank.get_balance(&emptied_keypair.pubkey()), 0);
+
+    let fee_payer_tx = system_transaction::transfer(
+        &emptied_keypair,
+        &mint_keypair.pubkey(),
+        1,
+        bank.last_blockhash(),
+    );
+    assert_eq!(
+        bank.process_transaction(&fee_payer_tx),
+        Err(TransactionError::AccountNotFound)
+    );
+}
+
 #[test]
 fn test_accounts_data_size_with_default_bank() {
     let bank = Bank::default_for_tests();
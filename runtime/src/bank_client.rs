@@ -1,9 +1,12 @@
+#[cfg(feature = "dev-context-only-utils")]
+use solana_sdk::clock;
 use {
-    crate::bank::Bank,
+    crate::{bank::Bank, bank_forks::BankForks},
     crossbeam_channel::{unbounded, Receiver, Sender},
     solana_sdk::{
         account::Account,
-        client::{AsyncClient, Client, SyncClient},
+        client::{AsyncClient, Client, NonblockingClient, SyncClient},
+        clock::Slot,
         commitment_config::CommitmentConfig,
         epoch_info::EpochInfo,
         hash::Hash,
@@ -19,13 +22,11 @@ use {
     },
     std::{
         io,
-        sync::{Arc, Mutex},
+        sync::{Arc, Mutex, RwLock},
         thread::{sleep, Builder},
         time::{Duration, Instant},
     },
 };
-#[cfg(feature = "dev-context-only-utils")]
-use {crate::bank_forks::BankForks, solana_sdk::clock, std::sync::RwLock};
 
 pub struct BankClient {
     bank: Arc<Bank>,
@@ -304,12 +305,66 @@ impl BankClient {
     }
 }
 
+/// A client for the working bank of `bank_forks`, which implements [`NonblockingClient`] so that
+/// tests can share code with `BanksClient`. Transactions are processed in place, so its futures
+/// are ready as soon as they are polled.
+pub struct AsyncBankClient {
+    bank_forks: Arc<RwLock<BankForks>>,
+}
+
+impl AsyncBankClient {
+    pub fn new(bank_forks: Arc<RwLock<BankForks>>) -> Self {
+        Self { bank_forks }
+    }
+
+    pub fn working_bank(&self) -> Arc<Bank> {
+        self.bank_forks.read().unwrap().working_bank()
+    }
+
+    pub fn set_sysvar_for_tests<T: Sysvar + SysvarId>(&self, sysvar: &T) {
+        self.working_bank().set_sysvar_for_tests(sysvar);
+    }
+}
+
+impl NonblockingClient for AsyncBankClient {
+    async fn get_latest_blockhash(&self) -> Result<Hash> {
+        Ok(self.working_bank().last_blockhash())
+    }
+
+    async fn get_minimum_balance_for_rent_exemption(&self, data_len: usize) -> Result<u64> {
+        Ok(self
+            .working_bank()
+            .get_minimum_balance_for_rent_exemption(data_len))
+    }
+
+    async fn get_account(&self, pubkey: &Pubkey) -> Result<Option<Account>> {
+        Ok(self.working_bank().get_account(pubkey).map(Account::from))
+    }
+
+    async fn process_transaction(&self, transaction: Transaction) -> Result<()> {
+        Ok(self.working_bank().process_transaction(&transaction)?)
+    }
+
+    async fn advance_slot(&self) -> Result<Slot> {
+        let mut bank_forks = self.bank_forks.write().unwrap();
+        let parent = bank_forks.working_bank();
+        let slot = parent.slot().checked_add(1).ok_or_else(|| {
+            TransportError::Custom(format!("cannot advance past slot {}", parent.slot()))
+        })?;
+        parent.freeze();
+        bank_forks.insert(Bank::new_from_parent(parent, &Pubkey::default(), slot));
+        Ok(slot)
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use {
         super::*,
+        solana_program_runtime::declare_process_instruction,
         solana_sdk::{
-            genesis_config::create_genesis_config, instruction::AccountMeta,
+            genesis_config::create_genesis_config,
+            instruction::{AccountMeta, InstructionError},
             native_token::sol_to_lamports,
         },
     };
@@ -339,4 +394,45 @@ mod tests {
             .unwrap();
         assert_eq!(bank_client.get_balance(&bob_pubkey).unwrap(), amount);
     }
+
+    #[test]
+    fn test_bank_client_advance_slot_updates_clock() {
+        // Fails unless the clock sysvar is at the slot in the instruction data
+        declare_process_instruction!(MockBuiltin, 1, |invoke_context| {
+            let transaction_context = &invoke_context.transaction_context;
+            let instruction_context = transaction_context.get_current_instruction_context()?;
+            let slot = instruction_context
+                .get_instruction_data()
+                .try_into()
+                .map(Slot::from_le_bytes)
+                .map_err(|_| InstructionError::InvalidInstructionData)?;
+            if invoke_context.get_sysvar_cache().get_clock()?.slot != slot {
+                return Err(InstructionError::Custom(0));
+            }
+            Ok(())
+        });
+
+        let (genesis_config, mint_keypair) = create_genesis_config(sol_to_lamports(1.0));
+        let mut bank = Bank::new_for_tests(&genesis_config);
+        let program_id = Pubkey::new_unique();
+        bank.add_mockup_builtin(program_id, MockBuiltin::vm);
+        let (bank, bank_forks) = bank.wrap_with_bank_forks_for_tests();
+        let mut bank_client = BankClient::new_shared(bank);
+        let check_clock = |bank_client: &BankClient, slot: Slot| {
+            // A unique account keeps the signature unique across slots
+            let account_metas = vec![AccountMeta::new_readonly(Pubkey::new_unique(), false)];
+            let instruction =
+                Instruction::new_with_bytes(program_id, &slot.to_le_bytes(), account_metas);
+            bank_client.send_and_confirm_instruction(&mint_keypair, instruction)
+        };
+
+        assert!(check_clock(&bank_client, 0).is_ok());
+        assert!(check_clock(&bank_client, 3).is_err());
+        let bank = bank_client
+            .advance_slot(3, &bank_forks, &Pubkey::default())
+            .unwrap();
+        assert_eq!(bank.slot(), 3);
+        assert!(check_clock(&bank_client, 3).is_ok());
+        assert!(check_clock(&bank_client, 0).is_err());
+    }
 }
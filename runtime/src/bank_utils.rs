@@ -1,18 +1,20 @@
+#[cfg(feature = "dev-context-only-utils")]
+use {
+    crate::genesis_utils::{self, GenesisConfigInfo, ValidatorVoteKeypairs},
+    solana_sdk::signature::Signer,
+};
 use {
-    crate::vote_sender_types::ReplayVoteSender,
+    crate::{bank::Bank, vote_sender_types::ReplayVoteSender},
     solana_runtime_transaction::transaction_with_meta::TransactionWithMeta,
+    solana_sdk::{
+        account::{AccountSharedData, ReadableAccount},
+        pubkey::Pubkey,
+    },
     solana_svm::transaction_commit_result::{
         TransactionCommitResult, TransactionCommitResultExtensions,
     },
     solana_vote::vote_parser,
-};
-#[cfg(feature = "dev-context-only-utils")]
-use {
-    crate::{
-        bank::Bank,
-        genesis_utils::{self, GenesisConfigInfo, ValidatorVoteKeypairs},
-    },
-    solana_sdk::{pubkey::Pubkey, signature::Signer},
+    std::collections::BTreeSet,
 };
 
 #[cfg(feature = "dev-context-only-utils")]
@@ -59,3 +61,102 @@ pub fn find_and_send_votes(
             });
     }
 }
+
+/// How an account differs between two banks
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum AccountDiff {
+    /// The account only exists in the first bank
+    OnlyInA(AccountSharedData),
+    /// The account only exists in the second bank
+    OnlyInB(AccountSharedData),
+    /// The account's lamports, data, or owner differ between the banks
+    Changed {
+        a: AccountSharedData,
+        b: AccountSharedData,
+    },
+}
+
+/// Returns the accounts whose lamports, data, or owner differ between two banks, sorted by pubkey
+///
+/// The banks must share ancestry.  Only the accounts stored in slots that are an ancestor of one
+/// bank but not the other are compared, since the accounts in all other slots are common to both.
+pub fn diff_accounts(bank_a: &Bank, bank_b: &Bank) -> Vec<(Pubkey, AccountDiff)> {
+    let divergent_pubkeys = |bank: &Bank, other: &Bank| {
+        bank.ancestors
+            .keys()
+            .into_iter()
+            .filter(|slot| !other.ancestors.contains_key(slot))
+            .flat_map(|slot| bank.rc.accounts.load_by_program_slot(slot, None))
+            .map(|(pubkey, _account)| pubkey)
+            .collect::<Vec<_>>()
+    };
+    let pubkeys: BTreeSet<_> = divergent_pubkeys(bank_a, bank_b)
+        .into_iter()
+        .chain(divergent_pubkeys(bank_b, bank_a))
+        .collect();
+
+    pubkeys
+        .into_iter()
+        .filter_map(|pubkey| {
+            let diff = match (bank_a.get_account(&pubkey), bank_b.get_account(&pubkey)) {
+                (Some(a), Some(b)) => {
+                    (a.lamports() != b.lamports() || a.data() != b.data() || a.owner() != b.owner())
+                        .then_some(AccountDiff::Changed { a, b })?
+                }
+                (Some(a), None) => AccountDiff::OnlyInA(a),
+                (None, Some(b)) => AccountDiff::OnlyInB(b),
+                (None, None) => return None,
+            };
+            Some((pubkey, diff))
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use {
+        super::*,
+        crate::genesis_utils::create_genesis_config,
+        solana_sdk::{native_token::LAMPORTS_PER_SOL, sysvar},
+        std::sync::Arc,
+    };
+
+    #[test]
+    fn test_diff_accounts() {
+        let genesis_config_info = create_genesis_config(LAMPORTS_PER_SOL);
+        let parent = Arc::new(Bank::new_for_tests(&genesis_config_info.genesis_config));
+        let common_pubkey = Pubkey::new_unique();
+        let common_account = AccountSharedData::new(1_000_000, 0, &Pubkey::default());
+        parent.store_account(&common_pubkey, &common_account);
+        parent.freeze();
+
+        let bank_a = Bank::new_from_parent(parent.clone(), &Pubkey::default(), 1);
+        let bank_b = Bank::new_from_parent(parent, &Pubkey::default(), 2);
+        assert!(diff_accounts(&bank_a, &bank_b)
+            .iter()
+            .all(|(pubkey, _)| sysvar::is_sysvar_id(pubkey)));
+
+        // Write the same account differently on each fork
+        let pubkey = Pubkey::new_unique();
+        let account_a = AccountSharedData::new(1_000_000, 0, &Pubkey::default());
+        let account_b = AccountSharedData::new(2_000_000, 0, &Pubkey::default());
+        bank_a.store_account(&pubkey, &account_a);
+        bank_b.store_account(&pubkey, &account_b);
+
+        // Sysvars are updated by each bank, so are expected to differ as well
+        let diff: Vec<_> = diff_accounts(&bank_a, &bank_b)
+            .into_iter()
+            .filter(|(pubkey, _)| !sysvar::is_sysvar_id(pubkey))
+            .collect();
+        assert_eq!(
+            diff,
+            vec![(
+                pubkey,
+                AccountDiff::Changed {
+                    a: account_a,
+                    b: account_b,
+                }
+            )]
+        );
+    }
+}
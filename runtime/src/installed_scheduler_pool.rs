@@ -423,6 +423,12 @@ impl BankWithScheduler {
         self.inner.bank.register_tick(hash, &self.inner.scheduler);
     }
 
+    pub fn register_ticks(&self, hashes: &[Hash]) {
+        self.inner
+            .bank
+            .register_ticks(hashes, &self.inner.scheduler);
+    }
+
     #[cfg(feature = "dev-context-only-utils")]
     pub fn fill_bank_with_ticks_for_tests(&self) {
         self.do_fill_bank_with_ticks_for_tests(&self.inner.scheduler);
@@ -28,6 +28,7 @@ pub mod snapshot_config;
 pub mod snapshot_hash;
 pub mod snapshot_minimizer;
 pub mod snapshot_package;
+pub mod snapshot_runtime_info;
 pub mod snapshot_utils;
 mod stake_account;
 pub mod stake_history;
@@ -5,14 +5,17 @@ use {
     solana_sdk::{
         account::{AccountSharedData, WritableAccount},
         bpf_loader_upgradeable::{self, UpgradeableLoaderState},
-        client::{Client, SyncClient},
+        client::{Client, NonblockingClient, SyncClient},
         clock::Clock,
         instruction::{AccountMeta, Instruction},
         loader_v4,
         message::Message,
+        packet::PACKET_DATA_SIZE,
         pubkey::Pubkey,
-        signature::{Keypair, Signer},
+        signature::{Keypair, Signature, Signer},
         system_instruction,
+        transaction::Transaction,
+        transport::{Result as TransportResult, TransportError},
     },
     std::{
         env,
@@ -23,8 +26,6 @@ use {
     },
 };
 
-const CHUNK_SIZE: usize = 512; // Size of chunk just needs to fit into tx
-
 pub fn load_program_from_file(name: &str) -> Vec<u8> {
     let mut pathbuf = {
         let current_exe = env::current_exe().unwrap();
@@ -50,6 +51,25 @@ pub fn load_program_from_file(name: &str) -> Vec<u8> {
     program
 }
 
+/// Returns the largest chunk of program data which can be written by the
+/// message `create_msg(offset, chunk)` without exceeding the packet size
+pub fn calculate_max_chunk_size<F>(create_msg: F) -> usize
+where
+    F: Fn(u32, Vec<u8>) -> Message,
+{
+    let baseline_msg = create_msg(0, Vec::new());
+    let tx_size = bincode::serialized_size(&Transaction {
+        signatures: vec![
+            Signature::default();
+            baseline_msg.header.num_required_signatures as usize
+        ],
+        message: baseline_msg,
+    })
+    .unwrap() as usize;
+    // add 1 byte buffer to account for shortvec encoding
+    PACKET_DATA_SIZE.saturating_sub(tx_size).saturating_sub(1)
+}
+
 // Creates an unverified program by bypassing the loader built-in program
 pub fn create_program(bank: &Bank, loader_id: &Pubkey, name: &str) -> Pubkey {
     let program_id = Pubkey::new_unique();
@@ -73,50 +93,138 @@ pub fn load_upgradeable_buffer<T: Client>(
     name: &str,
 ) -> Vec<u8> {
     let program = load_program_from_file(name);
-    let buffer_pubkey = buffer_keypair.pubkey();
-    let buffer_authority_pubkey = buffer_authority_keypair.pubkey();
-    let program_buffer_bytes = UpgradeableLoaderState::size_of_buffer(program.len());
+    write_upgradeable_buffer(
+        bank_client,
+        from_keypair,
+        buffer_keypair,
+        buffer_authority_keypair,
+        &program,
+    )
+    .unwrap();
+    program
+}
 
-    bank_client
-        .send_and_confirm_message(
-            &[from_keypair, buffer_keypair],
-            Message::new(
-                &bpf_loader_upgradeable::create_buffer(
-                    &from_keypair.pubkey(),
-                    &buffer_pubkey,
-                    &buffer_authority_pubkey,
-                    1.max(
-                        bank_client
-                            .get_minimum_balance_for_rent_exemption(program_buffer_bytes)
-                            .unwrap(),
-                    ),
-                    program.len(),
-                )
-                .unwrap(),
-                Some(&from_keypair.pubkey()),
-            ),
+fn write_upgradeable_buffer<T: Client>(
+    bank_client: &T,
+    from_keypair: &Keypair,
+    buffer_keypair: &Keypair,
+    buffer_authority_keypair: &Keypair,
+    program: &[u8],
+) -> TransportResult<()> {
+    let (create_message, write_messages) = upgradeable_buffer_messages(
+        &from_keypair.pubkey(),
+        &buffer_keypair.pubkey(),
+        &buffer_authority_keypair.pubkey(),
+        bank_client.get_minimum_balance_for_rent_exemption(
+            UpgradeableLoaderState::size_of_buffer(program.len()),
+        )?,
+        program,
+    );
+    bank_client.send_and_confirm_message(&[from_keypair, buffer_keypair], create_message)?;
+    for message in write_messages {
+        bank_client.send_and_confirm_message(&[from_keypair, buffer_authority_keypair], message)?;
+    }
+    Ok(())
+}
+
+// Returns the message creating a buffer for `program`, to be signed by the payer and the
+// buffer, and the messages writing `program` to it, to be signed by the payer and the buffer
+// authority
+fn upgradeable_buffer_messages(
+    payer_pubkey: &Pubkey,
+    buffer_pubkey: &Pubkey,
+    buffer_authority_pubkey: &Pubkey,
+    rent_exempt_balance: u64,
+    program: &[u8],
+) -> (Message, Vec<Message>) {
+    let create_message = Message::new(
+        &bpf_loader_upgradeable::create_buffer(
+            payer_pubkey,
+            buffer_pubkey,
+            buffer_authority_pubkey,
+            1.max(rent_exempt_balance),
+            program.len(),
         )
-        .unwrap();
+        .unwrap(),
+        Some(payer_pubkey),
+    );
 
-    let chunk_size = CHUNK_SIZE;
-    let mut offset = 0;
-    for chunk in program.chunks(chunk_size) {
-        let message = Message::new(
+    let create_msg = |offset: u32, bytes: Vec<u8>| {
+        Message::new(
             &[bpf_loader_upgradeable::write(
-                &buffer_pubkey,
-                &buffer_authority_pubkey,
+                buffer_pubkey,
+                buffer_authority_pubkey,
                 offset,
-                chunk.to_vec(),
+                bytes,
             )],
-            Some(&from_keypair.pubkey()),
-        );
-        bank_client
-            .send_and_confirm_message(&[from_keypair, buffer_authority_keypair], message)
-            .unwrap();
-        offset += chunk_size as u32;
+            Some(payer_pubkey),
+        )
+    };
+    let chunk_size = calculate_max_chunk_size(create_msg);
+    let write_messages = program
+        .chunks(chunk_size)
+        .enumerate()
+        .map(|(index, chunk)| create_msg((index * chunk_size) as u32, chunk.to_vec()))
+        .collect();
+    (create_message, write_messages)
+}
+
+/// Deploys `program` with the upgradeable loader through any [`NonblockingClient`], such as
+/// [`AsyncBankClient`](crate::bank_client::AsyncBankClient) or `BanksClient`, and returns its
+/// program id. The program is effective from the next slot.
+pub async fn load_upgradeable_program_nonblocking<C: NonblockingClient>(
+    client: &C,
+    payer_keypair: &Keypair,
+    authority_keypair: &Keypair,
+    program: &[u8],
+) -> TransportResult<Pubkey> {
+    let buffer_keypair = Keypair::new();
+    let program_keypair = Keypair::new();
+    let (create_message, write_messages) = upgradeable_buffer_messages(
+        &payer_keypair.pubkey(),
+        &buffer_keypair.pubkey(),
+        &authority_keypair.pubkey(),
+        client
+            .get_minimum_balance_for_rent_exemption(UpgradeableLoaderState::size_of_buffer(
+                program.len(),
+            ))
+            .await?,
+        program,
+    );
+    client
+        .send_and_confirm_message(&[payer_keypair, &buffer_keypair], create_message)
+        .await?;
+    for message in write_messages {
+        client
+            .send_and_confirm_message(&[payer_keypair, authority_keypair], message)
+            .await?;
     }
 
-    program
+    let message = Message::new(
+        &bpf_loader_upgradeable::deploy_with_max_program_len(
+            &payer_keypair.pubkey(),
+            &program_keypair.pubkey(),
+            &buffer_keypair.pubkey(),
+            &authority_keypair.pubkey(),
+            1.max(
+                client
+                    .get_minimum_balance_for_rent_exemption(
+                        UpgradeableLoaderState::size_of_program(),
+                    )
+                    .await?,
+            ),
+            program.len(),
+        )
+        .unwrap(),
+        Some(&payer_keypair.pubkey()),
+    );
+    client
+        .send_and_confirm_message(
+            &[payer_keypair, &program_keypair, authority_keypair],
+            message,
+        )
+        .await?;
+    Ok(program_keypair.pubkey())
 }
 
 pub fn load_upgradeable_program(
@@ -263,10 +371,27 @@ pub fn instructions_to_load_program_of_loader_v4<T: Client>(
     name: &str,
     program_keypair: Option<Keypair>,
     target_program_id: Option<&Pubkey>,
+) -> (Keypair, Vec<Instruction>) {
+    instructions_to_load_program_data_of_loader_v4(
+        bank_client,
+        payer_keypair,
+        authority_keypair,
+        &load_program_from_file(name),
+        program_keypair,
+        target_program_id,
+    )
+}
+
+fn instructions_to_load_program_data_of_loader_v4<T: Client>(
+    bank_client: &T,
+    payer_keypair: &Keypair,
+    authority_keypair: &Keypair,
+    program: &[u8],
+    program_keypair: Option<Keypair>,
+    target_program_id: Option<&Pubkey>,
 ) -> (Keypair, Vec<Instruction>) {
     let mut instructions = Vec::new();
     let loader_id = &loader_v4::id();
-    let program = load_program_from_file(name);
     let program_keypair = program_keypair.unwrap_or_else(|| {
         let program_keypair = Keypair::new();
         instructions.push(system_instruction::create_account(
@@ -286,7 +411,18 @@ pub fn instructions_to_load_program_of_loader_v4<T: Client>(
         program.len() as u32,
         &payer_keypair.pubkey(),
     ));
-    let chunk_size = CHUNK_SIZE;
+    // Each instruction is sent in its own message, see load_program_of_loader_v4()
+    let chunk_size = calculate_max_chunk_size(|offset, bytes| {
+        Message::new(
+            &[loader_v4::write(
+                &program_keypair.pubkey(),
+                &authority_keypair.pubkey(),
+                offset,
+                bytes,
+            )],
+            Some(&payer_keypair.pubkey()),
+        )
+    });
     let mut offset = 0;
     for chunk in program.chunks(chunk_size) {
         instructions.push(loader_v4::write(
@@ -324,9 +460,32 @@ pub fn load_program_of_loader_v4(
         None,
         None,
     );
+    send_loader_v4_instructions(
+        bank_client,
+        payer_keypair,
+        &program_keypair,
+        authority_keypair,
+        instructions,
+    )
+    .unwrap();
+    let bank = bank_client
+        .advance_slot(1, bank_forks, &Pubkey::default())
+        .expect("Failed to advance the slot");
+    (bank, program_keypair.pubkey())
+}
+
+// Sends each of the instructions from instructions_to_load_program_of_loader_v4()
+// in its own message
+fn send_loader_v4_instructions(
+    bank_client: &BankClient,
+    payer_keypair: &Keypair,
+    program_keypair: &Keypair,
+    authority_keypair: &Keypair,
+    instructions: Vec<Instruction>,
+) -> TransportResult<()> {
     let signers: &[&[&Keypair]] = &[
-        &[payer_keypair, &program_keypair],
-        &[payer_keypair, &program_keypair, authority_keypair],
+        &[payer_keypair, program_keypair],
+        &[payer_keypair, program_keypair, authority_keypair],
         &[payer_keypair, authority_keypair],
     ];
     let signers = std::iter::once(signers[0])
@@ -334,14 +493,84 @@ pub fn load_program_of_loader_v4(
         .chain(std::iter::repeat(signers[2]));
     for (instruction, signers) in instructions.into_iter().zip(signers) {
         let message = Message::new(&[instruction], Some(&payer_keypair.pubkey()));
-        bank_client
-            .send_and_confirm_message(signers, message)
-            .unwrap();
+        bank_client.send_and_confirm_message(signers, message)?;
     }
+    Ok(())
+}
+
+/// Deploys `program` with `loader_id`, which must be the upgradeable loader or
+/// loader-v4, and invokes it with `verify_instruction_data` once it is
+/// effective. Returns the bank the program was invoked in and the program id,
+/// or the error of whichever transaction failed. Any other loader is rejected
+/// with `TransportError::Custom` before anything is sent.
+pub fn load_and_verify_program(
+    bank_client: &mut BankClient,
+    bank_forks: &RwLock<BankForks>,
+    payer_keypair: &Keypair,
+    loader_id: &Pubkey,
+    program: &[u8],
+    verify_instruction_data: &[u8],
+) -> TransportResult<(Arc<Bank>, Pubkey)> {
+    let program_id = if loader_id == &bpf_loader_upgradeable::id() {
+        let program_keypair = Keypair::new();
+        let buffer_keypair = Keypair::new();
+        write_upgradeable_buffer(
+            bank_client,
+            payer_keypair,
+            &buffer_keypair,
+            payer_keypair,
+            program,
+        )?;
+        let message = Message::new(
+            &bpf_loader_upgradeable::deploy_with_max_program_len(
+                &payer_keypair.pubkey(),
+                &program_keypair.pubkey(),
+                &buffer_keypair.pubkey(),
+                &payer_keypair.pubkey(),
+                1.max(bank_client.get_minimum_balance_for_rent_exemption(
+                    UpgradeableLoaderState::size_of_program(),
+                )?),
+                program.len(),
+            )
+            .unwrap(),
+            Some(&payer_keypair.pubkey()),
+        );
+        bank_client.send_and_confirm_message(&[payer_keypair, &program_keypair], message)?;
+        program_keypair.pubkey()
+    } else if loader_id == &loader_v4::id() {
+        let (program_keypair, instructions) = instructions_to_load_program_data_of_loader_v4(
+            bank_client,
+            payer_keypair,
+            payer_keypair,
+            program,
+            None,
+            None,
+        );
+        send_loader_v4_instructions(
+            bank_client,
+            payer_keypair,
+            &program_keypair,
+            payer_keypair,
+            instructions,
+        )?;
+        program_keypair.pubkey()
+    } else {
+        return Err(TransportError::Custom(format!(
+            "Unsupported loader {loader_id}"
+        )));
+    };
+
+    // The program is effective from the slot after it was deployed
     let bank = bank_client
         .advance_slot(1, bank_forks, &Pubkey::default())
-        .expect("Failed to advance the slot");
-    (bank, program_keypair.pubkey())
+        .ok_or_else(|| TransportError::Custom("Failed to advance the slot".to_string()))?;
+    let instruction = Instruction::new_with_bytes(
+        program_id,
+        verify_instruction_data,
+        vec![AccountMeta::new(payer_keypair.pubkey(), true)],
+    );
+    bank_client.send_and_confirm_instruction(payer_keypair, instruction)?;
+    Ok((bank, program_id))
 }
 
 // Return an Instruction that invokes `program_id` with `data` and required
@@ -354,3 +583,123 @@ pub fn create_invoke_instruction<T: Serialize>(
     let account_metas = vec![AccountMeta::new(from_pubkey, true)];
     Instruction::new_with_bincode(program_id, data, account_metas)
 }
+
+#[cfg(test)]
+mod tests {
+    use {
+        super::*,
+        crate::genesis_utils::{create_genesis_config, GenesisConfigInfo},
+        solana_sdk::{
+            account::ReadableAccount, hash::Hash, instruction::InstructionError,
+            transaction::TransactionError, transport::TransportError,
+        },
+    };
+
+    #[test]
+    fn test_calculate_max_chunk_size() {
+        let payer = Keypair::new();
+        let buffer = Pubkey::new_unique();
+        let authority = Keypair::new();
+        let create_msg = |offset: u32, bytes: Vec<u8>| {
+            Message::new(
+                &[bpf_loader_upgradeable::write(
+                    &buffer,
+                    &authority.pubkey(),
+                    offset,
+                    bytes,
+                )],
+                Some(&payer.pubkey()),
+            )
+        };
+        let chunk_size = calculate_max_chunk_size(create_msg);
+        let tx_size = |chunk_size: usize| {
+            let message = create_msg(u32::MAX, vec![0xff; chunk_size]);
+            let tx = Transaction::new(&[&payer, &authority], message, Hash::new_unique());
+            bincode::serialized_size(&tx).unwrap() as usize
+        };
+        assert!(tx_size(chunk_size) <= PACKET_DATA_SIZE);
+        assert!(tx_size(chunk_size + 2) > PACKET_DATA_SIZE);
+    }
+
+    #[test]
+    fn test_load_and_verify_program() {
+        let GenesisConfigInfo {
+            genesis_config,
+            mint_keypair,
+            ..
+        } = create_genesis_config(1_000_000_000_000);
+        let (bank, bank_forks) =
+            Bank::new_for_tests(&genesis_config).wrap_with_bank_forks_for_tests();
+        let mut bank_client = BankClient::new_shared(bank);
+        let elf = include_bytes!("../../programs/bpf_loader/test_elfs/out/noop_aligned.so");
+
+        let (bank, program_id) = load_and_verify_program(
+            &mut bank_client,
+            &bank_forks,
+            &mint_keypair,
+            &bpf_loader_upgradeable::id(),
+            elf,
+            &[],
+        )
+        .unwrap();
+        assert!(bank.get_account(&program_id).unwrap().executable());
+
+        // The ELF header is checked when deploying
+        let mut corrupt_elf = elf.to_vec();
+        corrupt_elf[..4].copy_from_slice(b"\0ELF");
+        assert!(matches!(
+            load_and_verify_program(
+                &mut bank_client,
+                &bank_forks,
+                &mint_keypair,
+                &bpf_loader_upgradeable::id(),
+                &corrupt_elf,
+                &[],
+            ),
+            Err(TransportError::TransactionError(
+                TransactionError::InstructionError(_, InstructionError::InvalidAccountData)
+            ))
+        ));
+
+        // Loaders other than the upgradeable loader and loader-v4 are rejected
+        assert!(matches!(
+            load_and_verify_program(
+                &mut bank_client,
+                &bank_forks,
+                &mint_keypair,
+                &solana_sdk::bpf_loader::id(),
+                elf,
+                &[],
+            ),
+            Err(TransportError::Custom(_))
+        ));
+    }
+
+    #[test]
+    fn test_load_and_verify_program_verification_fails() {
+        let GenesisConfigInfo {
+            genesis_config,
+            mint_keypair,
+            ..
+        } = create_genesis_config(1_000_000_000_000);
+        let (bank, bank_forks) =
+            Bank::new_for_tests(&genesis_config).wrap_with_bank_forks_for_tests();
+        let mut bank_client = BankClient::new_shared(bank);
+        // Deploys fine, but fails with Custom(0) whatever it is invoked with
+        let elf = include_bytes!("../../programs/bpf_loader/test_elfs/out/sbpfv3_return_err.so");
+
+        assert!(matches!(
+            load_and_verify_program(
+                &mut bank_client,
+                &bank_forks,
+                &mint_keypair,
+                &bpf_loader_upgradeable::id(),
+                elf,
+                &[0, 1, 2, 3],
+            ),
+            Err(TransportError::TransactionError(
+                TransactionError::InstructionError(0, InstructionError::Custom(0))
+            ))
+        ));
+    }
+}
@@ -132,6 +132,28 @@ pub enum PrioritizationFeeError {
     BlockIsAlreadyFinalized,
 }
 
+/// Distribution of the prioritization fees of the transactions in a block
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct PrioritizationFeeStats {
+    pub transaction_count: u64,
+    pub min_prioritization_fee: u64,
+    /// The lower of the two middle fees when the transaction count is even
+    pub median_prioritization_fee: u64,
+    pub max_prioritization_fee: u64,
+}
+
+impl PrioritizationFeeStats {
+    fn from_fees(mut fees: Vec<u64>) -> Option<Self> {
+        fees.sort_unstable();
+        Some(Self {
+            transaction_count: fees.len() as u64,
+            min_prioritization_fee: *fees.first()?,
+            median_prioritization_fee: fees[(fees.len() - 1) / 2],
+            max_prioritization_fee: *fees.last()?,
+        })
+    }
+}
+
 /// Block minimum prioritization fee stats, includes the minimum prioritization fee for a transaction in this
 /// block; and the minimum fee for each writable account in all transactions in this block. The only relevant
 /// write account minimum fees are those greater than the block minimum transaction fee, because the minimum fee needed to land
@@ -144,6 +166,12 @@ pub struct PrioritizationFee {
     // The minimum prioritization fee of each writable account in transactions in this block.
     min_writable_account_fees: HashMap<Pubkey, u64>,
 
+    // The prioritization fee of each transaction in this block, until the block is completed and
+    // they are summarized into `transaction_fee_stats`.
+    transaction_fees: Vec<u64>,
+
+    transaction_fee_stats: Option<PrioritizationFeeStats>,
+
     // Default to `false`, set to `true` when a block is completed, therefore the minimum fees recorded
     // are finalized, and can be made available for use (e.g., RPC query)
     is_finalized: bool,
@@ -157,6 +185,8 @@ impl Default for PrioritizationFee {
         PrioritizationFee {
             min_transaction_fee: u64::MAX,
             min_writable_account_fees: HashMap::new(),
+            transaction_fees: Vec::new(),
+            transaction_fee_stats: None,
             is_finalized: false,
             metrics: PrioritizationFeeMetrics::default(),
         }
@@ -180,6 +210,7 @@ impl PrioritizationFee {
                         })
                         .or_insert(transaction_fee);
                 }
+                self.transaction_fees.push(transaction_fee);
 
                 self.metrics
                     .accumulate_total_prioritization_fee(transaction_fee);
@@ -207,6 +238,8 @@ impl PrioritizationFee {
             return Err(PrioritizationFeeError::BlockIsAlreadyFinalized);
         }
         self.prune_irrelevant_writable_accounts();
+        self.transaction_fee_stats =
+            PrioritizationFeeStats::from_fees(std::mem::take(&mut self.transaction_fees));
         self.is_finalized = true;
         Ok(())
     }
@@ -215,6 +248,11 @@ impl PrioritizationFee {
         (self.min_transaction_fee != u64::MAX).then_some(self.min_transaction_fee)
     }
 
+    /// Returns the distribution of transaction fees in the block, once the block is completed
+    pub fn get_transaction_fee_stats(&self) -> Option<PrioritizationFeeStats> {
+        self.transaction_fee_stats
+    }
+
     pub fn get_writable_account_fee(&self, key: &Pubkey) -> Option<u64> {
         self.min_writable_account_fees.get(key).copied()
     }
@@ -374,4 +412,29 @@ mod tests {
         assert!(prioritization_fee.mark_block_completed().is_ok());
         assert!(prioritization_fee.mark_block_completed().is_err());
     }
+
+    #[test]
+    fn test_transaction_fee_stats() {
+        let mut prioritization_fee = PrioritizationFee::default();
+        for fee in [7, 0, 100, 5] {
+            prioritization_fee.update(fee, vec![Pubkey::new_unique()]);
+        }
+        // only available once the block is completed
+        assert!(prioritization_fee.get_transaction_fee_stats().is_none());
+        prioritization_fee.mark_block_completed().unwrap();
+        assert_eq!(
+            prioritization_fee.get_transaction_fee_stats(),
+            Some(PrioritizationFeeStats {
+                transaction_count: 4,
+                min_prioritization_fee: 0,
+                median_prioritization_fee: 5,
+                max_prioritization_fee: 100,
+            })
+        );
+        assert!(prioritization_fee.transaction_fees.is_empty());
+
+        let mut prioritization_fee = PrioritizationFee::default();
+        prioritization_fee.mark_block_completed().unwrap();
+        assert!(prioritization_fee.get_transaction_fee_stats().is_none());
+    }
 }
@@ -429,6 +429,25 @@ impl PrioritizationFeeCache {
             })
             .collect()
     }
+
+    /// Returns the distribution of transaction fees in each of the most recent `num_slots` blocks,
+    /// in slot order
+    pub fn get_prioritization_fee_stats(
+        &self,
+        num_slots: usize,
+    ) -> Vec<(Slot, PrioritizationFeeStats)> {
+        let cache = self.cache.read().unwrap();
+        let mut stats: Vec<_> = cache
+            .iter()
+            .rev()
+            .take(num_slots)
+            .filter_map(|(slot, slot_prioritization_fee)| {
+                Some((*slot, slot_prioritization_fee.get_transaction_fee_stats()?))
+            })
+            .collect();
+        stats.reverse();
+        stats
+    }
 }
 
 #[cfg(test)]
@@ -859,6 +878,55 @@ mod tests {
         }
     }
 
+    #[test]
+    fn test_get_prioritization_fee_stats() {
+        let GenesisConfigInfo { genesis_config, .. } = create_genesis_config(10_000);
+        let bank0 = Bank::new_for_benches(&genesis_config);
+        let bank_forks = BankForks::new_rw_arc(bank0);
+        let bank = bank_forks.read().unwrap().working_bank();
+        let collector = solana_pubkey::new_rand();
+        let prioritization_fee_cache = PrioritizationFeeCache::default();
+        assert!(prioritization_fee_cache
+            .get_prioritization_fee_stats(MAX_NUM_RECENT_BLOCKS as usize)
+            .is_empty());
+
+        for (slot, fees) in [(1, vec![20, 0, 30, 10]), (2, vec![100, 5, 7]), (3, vec![1])] {
+            let bank = Arc::new(Bank::new_from_parent(bank.clone(), &collector, slot));
+            let txs: Vec<_> = fees
+                .into_iter()
+                .map(|fee| {
+                    build_sanitized_transaction_for_test(
+                        fee,
+                        &Pubkey::new_unique(),
+                        &Pubkey::new_unique(),
+                    )
+                })
+                .collect();
+            sync_update(&prioritization_fee_cache, bank.clone(), txs.iter());
+            sync_finalize_priority_fee_for_test(&prioritization_fee_cache, slot, bank.bank_id());
+        }
+
+        let stats = |transaction_count, min, median, max| PrioritizationFeeStats {
+            transaction_count,
+            min_prioritization_fee: min,
+            median_prioritization_fee: median,
+            max_prioritization_fee: max,
+        };
+        assert_eq!(
+            prioritization_fee_cache.get_prioritization_fee_stats(MAX_NUM_RECENT_BLOCKS as usize),
+            vec![
+                (1, stats(4, 0, 10, 30)),
+                (2, stats(3, 5, 7, 100)),
+                (3, stats(1, 1, 1, 1)),
+            ]
+        );
+        // only the most recent slots
+        assert_eq!(
+            prioritization_fee_cache.get_prioritization_fee_stats(2),
+            vec![(2, stats(3, 5, 7, 100)), (3, stats(1, 1, 1, 1))]
+        );
+    }
+
     #[test]
     fn test_purge_duplicated_bank() {
         // duplicated bank can exists for same slot before OC.
@@ -540,8 +540,10 @@ fn bank_fields_from_snapshots(
     deserialize_snapshot_data_files(&snapshot_root_paths, |snapshot_streams| {
         Ok(
             match incremental_snapshot_version.unwrap_or(full_snapshot_version) {
-                SnapshotVersion::V1_2_0 => fields_from_streams(snapshot_streams)
-                    .map(|(bank_fields, _accountsdb_fields)| bank_fields.collapse_into()),
+                SnapshotVersion::V1_2_0 | SnapshotVersion::V1_3_0 => {
+                    fields_from_streams(snapshot_streams)
+                        .map(|(bank_fields, _accountsdb_fields)| bank_fields.collapse_into())
+                }
             }?,
         )
     })
@@ -620,7 +622,7 @@ fn rebuild_bank_from_unarchived_snapshots(
     let (bank, info) = deserialize_snapshot_data_files(&snapshot_root_paths, |snapshot_streams| {
         Ok(
             match incremental_snapshot_version.unwrap_or(full_snapshot_version) {
-                SnapshotVersion::V1_2_0 => bank_from_streams(
+                SnapshotVersion::V1_2_0 | SnapshotVersion::V1_3_0 => bank_from_streams(
                     snapshot_streams,
                     account_paths,
                     storage_and_next_append_vec_id,
@@ -1232,6 +1234,76 @@ mod tests {
         assert_eq!(original_bank, roundtrip_bank);
     }
 
+    /// Test that a snapshot of storages holding compressed account data takes snapshot version
+    /// 1.3.0, so that older versions do not misread it, and that it loads without compression
+    #[test]
+    fn test_roundtrip_bank_to_and_from_full_snapshot_compressed_data() {
+        let genesis_config = GenesisConfig::default();
+        let original_bank = Bank::new_with_config_for_tests(
+            &genesis_config,
+            BankTestConfig {
+                accounts_db_config: AccountsDbConfig {
+                    append_vec_data_compression_threshold: Some(1024),
+                    ..ACCOUNTS_DB_CONFIG_FOR_TESTING
+                },
+            },
+        );
+        let pubkey = Pubkey::new_unique();
+        let account = solana_sdk::account::AccountSharedData::new(
+            LAMPORTS_PER_SOL,
+            64 * 1024,
+            &Pubkey::new_unique(),
+        );
+        original_bank.store_account(&pubkey, &account);
+        original_bank.set_capitalization();
+        while !original_bank.is_complete() {
+            original_bank.register_unique_tick();
+        }
+
+        let (_tmp_dir, accounts_dir) = create_tmp_accounts_dir_for_tests();
+        let bank_snapshots_dir = tempfile::TempDir::new().unwrap();
+        let full_snapshot_archives_dir = tempfile::TempDir::new().unwrap();
+        let incremental_snapshot_archives_dir = tempfile::TempDir::new().unwrap();
+        let snapshot_archive_info = bank_to_full_snapshot_archive(
+            &bank_snapshots_dir,
+            &original_bank,
+            None,
+            full_snapshot_archives_dir.path(),
+            incremental_snapshot_archives_dir.path(),
+            ArchiveFormat::Tar,
+        )
+        .unwrap();
+        let version_path = get_bank_snapshot_dir(&bank_snapshots_dir, original_bank.slot())
+            .join(snapshot_utils::SNAPSHOT_VERSION_FILENAME);
+        assert_eq!(
+            fs::read_to_string(version_path).unwrap(),
+            SnapshotVersion::V1_3_0.as_str()
+        );
+
+        let (roundtrip_bank, _) = bank_from_snapshot_archives(
+            &[accounts_dir],
+            bank_snapshots_dir.path(),
+            &snapshot_archive_info,
+            None,
+            &genesis_config,
+            &RuntimeConfig::default(),
+            None,
+            None,
+            None,
+            false,
+            false,
+            false,
+            false,
+            Some(ACCOUNTS_DB_CONFIG_FOR_TESTING),
+            None,
+            Arc::default(),
+        )
+        .unwrap();
+        roundtrip_bank.wait_for_initial_accounts_hash_verification_completed_for_tests();
+        assert_eq!(original_bank, roundtrip_bank);
+        assert_eq!(roundtrip_bank.get_account(&pubkey), Some(account));
+    }
+
     /// Test roundtrip of bank to a full snapshot, then back again.  This test is more involved
     /// than the simple version above; creating multiple banks over multiple slots and doing
     /// multiple transfers.  So this full snapshot should contain more data.
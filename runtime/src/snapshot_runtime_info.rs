@@ -0,0 +1,114 @@
+//! Information about the snapshots generated while the node is running
+
+use {
+    crate::{snapshot_archive_info::SnapshotArchiveInfo, snapshot_package::SnapshotKind},
+    log::*,
+    solana_sdk::clock::Slot,
+    std::{fs, sync::RwLock, time::Duration},
+};
+
+/// How long it took to generate a snapshot archive, and how big it is
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub struct SnapshotGenerationInfo {
+    pub slot: Slot,
+
+    /// Time spent serializing and archiving the snapshot
+    pub duration: Duration,
+
+    /// Size of the snapshot archive, in bytes
+    pub archive_size: u64,
+}
+
+/// The most recent full and incremental snapshot generations
+#[derive(Debug, Default)]
+pub struct SnapshotRuntimeInfo {
+    latest_full: RwLock<Option<SnapshotGenerationInfo>>,
+    latest_incremental: RwLock<Option<SnapshotGenerationInfo>>,
+}
+
+impl SnapshotRuntimeInfo {
+    /// Record that the archive described by `snapshot_archive_info` took `duration` to generate
+    pub fn record_generation(
+        &self,
+        snapshot_kind: SnapshotKind,
+        snapshot_archive_info: &SnapshotArchiveInfo,
+        duration: Duration,
+    ) {
+        let archive_size = fs::metadata(&snapshot_archive_info.path)
+            .map(|metadata| metadata.len())
+            .unwrap_or_else(|err| {
+                warn!(
+                    "Unable to get the size of snapshot archive {}: {err}",
+                    snapshot_archive_info.path.display()
+                );
+                0
+            });
+        let generation_info = SnapshotGenerationInfo {
+            slot: snapshot_archive_info.slot,
+            duration,
+            archive_size,
+        };
+        let latest = match snapshot_kind {
+            SnapshotKind::FullSnapshot => &self.latest_full,
+            SnapshotKind::IncrementalSnapshot(_) => &self.latest_incremental,
+        };
+        *latest.write().unwrap() = Some(generation_info);
+    }
+
+    /// The most recently generated full snapshot, if any
+    pub fn latest_full(&self) -> Option<SnapshotGenerationInfo> {
+        *self.latest_full.read().unwrap()
+    }
+
+    /// The most recently generated incremental snapshot, if any
+    pub fn latest_incremental(&self) -> Option<SnapshotGenerationInfo> {
+        *self.latest_incremental.read().unwrap()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use {
+        super::*,
+        crate::{snapshot_hash::SnapshotHash, snapshot_utils::ArchiveFormat},
+        solana_sdk::hash::Hash,
+        std::time::Instant,
+    };
+
+    #[test]
+    fn test_record_generation() {
+        let archives_dir = tempfile::TempDir::new().unwrap();
+        let snapshot_runtime_info = SnapshotRuntimeInfo::default();
+        assert_eq!(snapshot_runtime_info.latest_full(), None);
+        assert_eq!(snapshot_runtime_info.latest_incremental(), None);
+
+        // Simulate generating a full snapshot archive at slot 100
+        let start = Instant::now();
+        let path = archives_dir
+            .path()
+            .join(format!("snapshot-100-{}.tar.zst", Hash::default()));
+        fs::write(&path, vec![0u8; 1234]).unwrap();
+        let snapshot_archive_info = SnapshotArchiveInfo {
+            path,
+            slot: 100,
+            hash: SnapshotHash(Hash::default()),
+            archive_format: ArchiveFormat::TarZstd,
+        };
+        let duration = start.elapsed();
+        snapshot_runtime_info.record_generation(
+            SnapshotKind::FullSnapshot,
+            &snapshot_archive_info,
+            duration,
+        );
+
+        assert_eq!(
+            snapshot_runtime_info.latest_full(),
+            Some(SnapshotGenerationInfo {
+                slot: 100,
+                duration,
+                archive_size: 1234,
+            })
+        );
+        assert_eq!(snapshot_runtime_info.latest_incremental(), None);
+    }
+}
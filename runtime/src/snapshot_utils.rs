@@ -74,6 +74,7 @@ pub const SNAPSHOT_FULL_SNAPSHOT_SLOT_FILENAME: &str = "full_snapshot_slot";
 pub const MAX_SNAPSHOT_DATA_FILE_SIZE: u64 = 32 * 1024 * 1024 * 1024; // 32 GiB
 const MAX_SNAPSHOT_VERSION_FILE_SIZE: u64 = 8; // byte
 const VERSION_STRING_V1_2_0: &str = "1.2.0";
+const VERSION_STRING_V1_3_0: &str = "1.3.0";
 pub const TMP_SNAPSHOT_ARCHIVE_PREFIX: &str = "tmp-snapshot-archive-";
 pub const BANK_SNAPSHOT_PRE_FILENAME_EXTENSION: &str = "pre";
 // The following unsafes are
@@ -91,6 +92,9 @@ pub const INCREMENTAL_SNAPSHOT_ARCHIVE_FILENAME_REGEX: &str = r"^incremental-sna
 pub enum SnapshotVersion {
     #[default]
     V1_2_0,
+    /// Same as 1.2.0, but append vecs may hold lz4 compressed account data, which older versions
+    /// would misread. Snapshots take this version whenever one of their storages does.
+    V1_3_0,
 }
 
 impl fmt::Display for SnapshotVersion {
@@ -103,6 +107,7 @@ impl From<SnapshotVersion> for &'static str {
     fn from(snapshot_version: SnapshotVersion) -> &'static str {
         match snapshot_version {
             SnapshotVersion::V1_2_0 => VERSION_STRING_V1_2_0,
+            SnapshotVersion::V1_3_0 => VERSION_STRING_V1_3_0,
         }
     }
 }
@@ -122,6 +127,7 @@ impl FromStr for SnapshotVersion {
         };
         match version_string {
             VERSION_STRING_V1_2_0 => Ok(SnapshotVersion::V1_2_0),
+            VERSION_STRING_V1_3_0 => Ok(SnapshotVersion::V1_3_0),
             _ => Err("unsupported snapshot version"),
         }
     }
@@ -839,6 +845,14 @@ fn serialize_snapshot(
     write_version: StoredMetaWriteVersion,
 ) -> Result<BankSnapshotInfo> {
     let slot = bank_fields.slot;
+    let snapshot_version = if snapshot_storages
+        .iter()
+        .any(|storage| storage.accounts.has_compressed_data())
+    {
+        SnapshotVersion::V1_3_0
+    } else {
+        snapshot_version
+    };
 
     // this lambda function is to facilitate converting between
     // the AddBankSnapshotError and SnapshotError types
@@ -192,7 +192,7 @@ impl SnapshotStorageRebuilder {
         let snapshot_file = File::open(snapshot_file_path).unwrap();
         let mut snapshot_stream = BufReader::new(snapshot_file);
         match snapshot_version {
-            SnapshotVersion::V1_2_0 => {
+            SnapshotVersion::V1_2_0 | SnapshotVersion::V1_3_0 => {
                 let (_bank_fields, accounts_fields) =
                     serde_snapshot::fields_from_stream(&mut snapshot_stream)?;
 
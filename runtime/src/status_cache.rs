@@ -163,6 +163,31 @@ impl<T: Serialize + Clone> StatusCache<T> {
         None
     }
 
+    /// Every status recorded for a key, under any blockhash and on any fork, sorted by slot.
+    /// Meant for debugging, e.g. finding everywhere a transaction has been processed.
+    pub fn get_statuses_all_forks<K: AsRef<[u8]>>(&self, key: K) -> Vec<(Slot, Hash, T)> {
+        let max_key_index = key.as_ref().len().saturating_sub(CACHED_KEY_SIZE + 1);
+        let mut statuses: Vec<_> = self
+            .cache
+            .iter()
+            .filter_map(|(blockhash, (_, index, keymap))| {
+                let index = (*index).min(max_key_index);
+                let key_slice: &[u8; CACHED_KEY_SIZE] =
+                    arrayref::array_ref![key.as_ref(), index, CACHED_KEY_SIZE];
+                keymap
+                    .get(key_slice)
+                    .map(|stored_forks| (blockhash, stored_forks))
+            })
+            .flat_map(|(blockhash, stored_forks)| {
+                stored_forks
+                    .iter()
+                    .map(|(slot, res)| (*slot, *blockhash, res.clone()))
+            })
+            .collect();
+        statuses.sort_unstable_by_key(|(slot, blockhash, _)| (*slot, *blockhash));
+        statuses
+    }
+
     /// Add a known root fork.  Roots are always valid ancestors.
     /// After MAX_CACHE_ENTRIES, roots are removed, and any old keys are cleared.
     pub fn add_root(&mut self, fork: Slot) {
@@ -357,6 +382,24 @@ mod tests {
         assert_eq!(status_cache.get_status_any_blockhash(sig, &ancestors), None);
     }
 
+    #[test]
+    fn test_get_statuses_all_forks() {
+        let sig = Signature::default();
+        let mut status_cache = BankStatusCache::default();
+        let blockhash0 = hash(Hash::default().as_ref());
+        let blockhash1 = hash(blockhash0.as_ref());
+        assert_eq!(status_cache.get_statuses_all_forks(sig), vec![]);
+
+        // Processed on two forks, under different blockhashes, neither of which is rooted
+        status_cache.insert(&blockhash1, sig, 2, ());
+        status_cache.insert(&blockhash0, sig, 1, ());
+        status_cache.insert(&blockhash0, Signature::from([1; 64]), 3, ());
+        assert_eq!(
+            status_cache.get_statuses_all_forks(sig),
+            vec![(1, blockhash0, ()), (2, blockhash1, ())]
+        );
+    }
+
     #[test]
     fn test_find_sig_with_root_ancestor_fork() {
         let sig = Signature::default();
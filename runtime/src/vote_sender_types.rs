@@ -1,7 +1,121 @@
 use {
-    crossbeam_channel::{Receiver, Sender},
+    crossbeam_channel::{Receiver, SendError, Sender, TrySendError},
     solana_vote::vote_parser::ParsedVote,
+    std::sync::{
+        atomic::{AtomicU64, Ordering},
+        Arc,
+    },
 };
 
 pub type ReplayVoteSender = Sender<ParsedVote>;
 pub type ReplayVoteReceiver = Receiver<ParsedVote>;
+
+/// Create a replay vote channel that holds at most `capacity` votes.
+pub fn bounded_replay_vote_channel(
+    capacity: usize,
+) -> (BoundedReplayVoteSender, ReplayVoteReceiver) {
+    let (sender, receiver) = crossbeam_channel::bounded(capacity);
+    (
+        BoundedReplayVoteSender {
+            sender,
+            dropped_count: Arc::default(),
+        },
+        receiver,
+    )
+}
+
+/// Sending half of a bounded replay vote channel.
+///
+/// Sends never block: a vote that doesn't fit because the receiver has fallen
+/// behind, or that can't be delivered because the receiver is gone, is dropped
+/// and counted, so a slow consumer can't stall replay or grow memory without
+/// bound.
+#[derive(Clone, Debug)]
+pub struct BoundedReplayVoteSender {
+    sender: Sender<ParsedVote>,
+    dropped_count: Arc<AtomicU64>,
+}
+
+impl BoundedReplayVoteSender {
+    /// Same signature as [`ReplayVoteSender::send`], so that this can be used
+    /// in its place. Fails if the vote was dropped, whatever the reason.
+    pub fn send(&self, vote: ParsedVote) -> Result<(), SendError<ParsedVote>> {
+        self.try_send(vote)
+            .map_err(|err| SendError(err.into_inner()))
+    }
+
+    /// Like [`Self::send`], but tells apart a full channel from a
+    /// disconnected one
+    pub fn try_send(&self, vote: ParsedVote) -> Result<(), TrySendError<ParsedVote>> {
+        self.sender.try_send(vote).inspect_err(|_| {
+            self.dropped_count.fetch_add(1, Ordering::Relaxed);
+        })
+    }
+
+    /// Number of votes dropped by this sender and its clones
+    pub fn dropped_count(&self) -> u64 {
+        self.dropped_count.load(Ordering::Relaxed)
+    }
+
+    /// Number of votes waiting in the channel
+    pub fn len(&self) -> usize {
+        self.sender.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.sender.is_empty()
+    }
+
+    pub fn capacity(&self) -> Option<usize> {
+        self.sender.capacity()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use {
+        super::*,
+        solana_sdk::{hash::Hash, pubkey::Pubkey, signature::Signature},
+        solana_vote_program::vote_state::Vote,
+    };
+
+    fn new_parsed_vote(slot: u64) -> ParsedVote {
+        (
+            Pubkey::new_unique(),
+            Vote::new(vec![slot], Hash::new_unique()).into(),
+            None,
+            Signature::new_unique(),
+        )
+    }
+
+    #[test]
+    fn test_bounded_replay_vote_channel() {
+        let (sender, receiver) = bounded_replay_vote_channel(2);
+        assert_eq!(sender.capacity(), Some(2));
+
+        assert!(sender.send(new_parsed_vote(1)).is_ok());
+        assert!(sender.clone().send(new_parsed_vote(2)).is_ok());
+        assert_eq!(sender.len(), 2);
+        assert_eq!(sender.dropped_count(), 0);
+
+        // The channel is full, so further votes are dropped instead of blocking
+        assert!(matches!(
+            sender.try_send(new_parsed_vote(3)),
+            Err(TrySendError::Full(_))
+        ));
+        assert!(sender.send(new_parsed_vote(3)).is_err());
+        assert_eq!(sender.dropped_count(), 2);
+
+        // Draining the channel makes room again
+        assert_eq!(receiver.recv().unwrap().1.slots(), vec![1]);
+        assert!(sender.send(new_parsed_vote(4)).is_ok());
+        assert_eq!(sender.dropped_count(), 2);
+
+        drop(receiver);
+        assert!(matches!(
+            sender.try_send(new_parsed_vote(5)),
+            Err(TrySendError::Disconnected(_))
+        ));
+        assert_eq!(sender.dropped_count(), 3);
+    }
+}
@@ -15,6 +15,7 @@ fmt_dirs=(
   programs/sbf
   sdk/cargo-build-sbf/tests/crates/fail
   sdk/cargo-build-sbf/tests/crates/noop
+  sdk/cargo-build-sbf/tests/crates/writable-data
   storage-bigtable/build-proto
 )
 
@@ -5,8 +5,12 @@ use {
     itertools::Itertools,
     log::*,
     regex::Regex,
+    solana_bpf_loader_program::syscalls::create_program_runtime_environment_v1,
+    solana_compute_budget::compute_budget::ComputeBudget,
+    solana_feature_set::FeatureSet,
     solana_file_download::download_file,
     solana_keypair::{write_keypair_file, Keypair},
+    solana_program_runtime::loaded_programs::LoadProgramMetrics,
     std::{
         borrow::Cow,
         collections::{HashMap, HashSet},
@@ -17,6 +21,7 @@ use {
         path::{Path, PathBuf},
         process::{exit, Command, Stdio},
         str::FromStr,
+        sync::Arc,
     },
     tar::Archive,
 };
@@ -44,6 +49,7 @@ struct Config<'a> {
     workspace: bool,
     jobs: Option<String>,
     arch: &'a str,
+    no_verify: bool,
 }
 
 impl Default for Config<'_> {
@@ -74,6 +80,7 @@ impl Default for Config<'_> {
             workspace: false,
             jobs: None,
             arch: "sbfv1",
+            no_verify: false,
         }
     }
 }
@@ -909,6 +916,9 @@ fn build_solana_package(
         }
 
         check_undefined_symbols(config, &program_so);
+        if !config.no_verify {
+            verify_program(&program_so);
+        }
 
         info!("To deploy this program:");
         info!("  $ solana program deploy {}", program_so.display());
@@ -919,6 +929,50 @@ fn build_solana_package(
     }
 }
 
+// Load and verify the program the same way the loader does on deployment, so that programs the
+// cluster would reject fail the build instead
+fn verify_program(program: &Path) {
+    let programdata = fs::read(program).unwrap_or_else(|err| {
+        error!("Unable to read {}: {}", program.display(), err);
+        exit(1);
+    });
+    let program_runtime_environment = create_program_runtime_environment_v1(
+        &FeatureSet::all_enabled(),
+        &ComputeBudget::default(),
+        true,  // reject_deployment_of_broken_elfs
+        false, // debugging_features
+    )
+    .unwrap_or_else(|err| {
+        error!("Unable to create the program runtime environment: {}", err);
+        exit(1);
+    });
+    match solana_bpf_loader_program::verify_program(
+        &programdata,
+        Arc::new(program_runtime_environment),
+        &mut LoadProgramMetrics::default(),
+    ) {
+        Ok(executable) => {
+            info!(
+                "Verified {}: entrypoint at instruction {}, {} bytes of read-only data",
+                program.display(),
+                executable.get_entrypoint_instruction_offset(),
+                executable.get_ro_section().len(),
+            );
+        }
+        Err(err) => {
+            error!(
+                "{} would be rejected by the loader: {}",
+                program.display(),
+                err
+            );
+            error!(
+                "Use --dump to locate the offending instruction, or --no-verify to skip this check"
+            );
+            exit(1);
+        }
+    }
+}
+
 // allow user to set proper `rustc` into RUSTC or into PATH
 fn check_solana_target_installed(target: &str) {
     let rustc = env::var("RUSTC").unwrap_or("rustc".to_owned());
@@ -1139,6 +1193,12 @@ fn main() {
                 .default_value("sbfv1")
                 .help("Build for the given target architecture"),
         )
+        .arg(
+            Arg::new("no_verify")
+                .long("no-verify")
+                .takes_value(false)
+                .help("Skip verifying the program the way the loader does on deployment"),
+        )
         .get_matches_from(args);
 
     let sbf_sdk: PathBuf = matches.value_of_t_or_exit("sbf_sdk");
@@ -1208,6 +1268,7 @@ fn main() {
         workspace: matches.is_present("workspace"),
         jobs: matches.value_of_t("jobs").ok(),
         arch: matches.value_of("arch").unwrap(),
+        no_verify: matches.is_present("no_verify"),
     };
     let manifest_path: Option<PathBuf> = matches.value_of_t("manifest_path").ok();
     if config.verbose {
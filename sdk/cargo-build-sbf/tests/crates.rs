@@ -60,6 +60,15 @@ fn test_build() {
     clean_target("noop");
 }
 
+#[test]
+#[serial]
+fn test_verify() {
+    // Writable static data is rejected by the loader, so the build fails unless the check is skipped
+    run_cargo_build("writable-data", &[], true);
+    run_cargo_build("writable-data", &["--no-verify"], false);
+    clean_target("writable-data");
+}
+
 #[test]
 #[serial]
 fn test_dump() {
@@ -0,0 +1,20 @@
+//! Example Rust-based SBF program with writable static data, which the loader rejects
+
+use solana_program::{account_info::AccountInfo, entrypoint::ProgramResult, msg, pubkey::Pubkey};
+
+static mut INVOCATIONS: u64 = 0;
+
+solana_program::entrypoint!(process_instruction);
+fn process_instruction(
+    _program_id: &Pubkey,
+    _accounts: &[AccountInfo],
+    _instruction_data: &[u8],
+) -> ProgramResult {
+    // error to make verification fail: writable static data
+    let invocations = unsafe {
+        INVOCATIONS += 1;
+        INVOCATIONS
+    };
+    msg!("invocations: {}", invocations);
+    Ok(())
+}
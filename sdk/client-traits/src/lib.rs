@@ -6,6 +6,9 @@
 //!
 //! Asynchronous implementations are expected to create transactions, sign them, and send
 //! them but without waiting to see if the server accepted it.
+//!
+//! Nonblocking implementations are expected to process transactions like synchronous ones, but
+//! return futures rather than blocking the calling thread.
 
 use {
     solana_account::Account,
@@ -21,6 +24,7 @@ use {
     solana_system_interface::instruction::transfer,
     solana_transaction::{versioned::VersionedTransaction, Transaction},
     solana_transaction_error::{TransactionResult, TransportResult as Result},
+    std::future::Future,
 };
 
 pub trait Client: SyncClient + AsyncClient {
@@ -196,3 +200,41 @@ pub trait AsyncClient {
         self.async_send_instruction(keypair, transfer_instruction, recent_blockhash)
     }
 }
+
+/// A client which processes transactions like a [`SyncClient`], but returns futures, so that the
+/// same test code can run against an in-process bank or a banks server.
+pub trait NonblockingClient {
+    /// Get last known blockhash
+    fn get_latest_blockhash(&self) -> impl Future<Output = Result<Hash>>;
+
+    fn get_minimum_balance_for_rent_exemption(
+        &self,
+        data_len: usize,
+    ) -> impl Future<Output = Result<u64>>;
+
+    /// Get an account or None if not found.
+    fn get_account(&self, pubkey: &Pubkey) -> impl Future<Output = Result<Option<Account>>>;
+
+    /// Process a signed transaction, resolving once it has been processed.
+    fn process_transaction(&self, transaction: Transaction) -> impl Future<Output = Result<()>>;
+
+    /// Freeze the current bank and continue in a child bank at the next slot, returning the new
+    /// slot.
+    fn advance_slot(&self) -> impl Future<Output = Result<u64>>;
+
+    /// Create a transaction from the given message, sign it with the latest blockhash, and
+    /// process it.
+    fn send_and_confirm_message<T: Signers + ?Sized>(
+        &self,
+        keypairs: &T,
+        message: Message,
+    ) -> impl Future<Output = Result<Signature>> {
+        async move {
+            let blockhash = self.get_latest_blockhash().await?;
+            let transaction = Transaction::new(keypairs, message, blockhash);
+            let signature = transaction.signatures.first().cloned().unwrap_or_default();
+            self.process_transaction(transaction).await?;
+            Ok(signature)
+        }
+    }
+}
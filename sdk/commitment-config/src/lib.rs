@@ -1,6 +1,9 @@
 //! Definitions of commitment levels.
+#![cfg_attr(feature = "frozen-abi", feature(min_specialization))]
 
 use core::{fmt, str::FromStr};
+#[cfg(feature = "frozen-abi")]
+use solana_frozen_abi_macro::{AbiEnumVisitor, AbiExample};
 
 #[cfg_attr(
     feature = "serde",
@@ -72,6 +75,7 @@ impl FromStr for CommitmentConfig {
     }
 }
 
+#[cfg_attr(feature = "frozen-abi", derive(AbiExample, AbiEnumVisitor))]
 #[cfg_attr(
     feature = "serde",
     derive(serde_derive::Serialize, serde_derive::Deserialize),
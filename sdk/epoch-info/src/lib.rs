@@ -3,7 +3,9 @@
 //! As returned by the [`getEpochInfo`] RPC method.
 //!
 //! [`getEpochInfo`]: https://solana.com/docs/rpc/http/getepochinfo
+#![cfg_attr(feature = "frozen-abi", feature(min_specialization))]
 
+#[cfg_attr(feature = "frozen-abi", derive(solana_frozen_abi_macro::AbiExample))]
 #[cfg_attr(
     feature = "serde",
     derive(serde_derive::Deserialize, serde_derive::Serialize),
@@ -2,53 +2,273 @@
 
 use {
     lazy_static::lazy_static,
-    std::sync::{Arc, RwLock},
+    std::{
+        collections::HashMap,
+        fmt::Display,
+        io::{self, Write},
+        sync::{Arc, Mutex, RwLock},
+        time::{Duration, Instant},
+    },
 };
 
 lazy_static! {
     static ref LOGGER: Arc<RwLock<env_logger::Logger>> =
         Arc::new(RwLock::new(env_logger::Logger::from_default_env()));
+    static ref RATE_LIMITER: RwLock<Option<RateLimiter>> = RwLock::new(None);
+    static ref CAPTURE: RwLock<Option<CaptureHandle>> = RwLock::new(None);
+    static ref CONFIGURE: RwLock<Option<Configure>> = RwLock::new(None);
 }
 
+/// Applies the format and output of a logger, so that it can be rebuilt with a new filter
+type Configure = Box<dyn Fn(&mut env_logger::Builder) -> io::Result<()> + Send + Sync>;
+
 pub const DEFAULT_FILTER: &str = "solana=info,agave=info";
 
 struct LoggerShim {}
 
 impl log::Log for LoggerShim {
     fn enabled(&self, metadata: &log::Metadata) -> bool {
-        LOGGER.read().unwrap().enabled(metadata)
+        CAPTURE.read().unwrap().is_some() || LOGGER.read().unwrap().enabled(metadata)
     }
 
     fn log(&self, record: &log::Record) {
-        LOGGER.read().unwrap().log(record);
+        if let Some(capture) = CAPTURE.read().unwrap().as_ref() {
+            capture.push(record);
+            return;
+        }
+        let logger = LOGGER.read().unwrap();
+        match RATE_LIMITER.read().unwrap().as_ref() {
+            Some(rate_limiter) => log_rate_limited(&*logger, rate_limiter, record, Instant::now()),
+            None => logger.log(record),
+        }
     }
 
     fn flush(&self) {}
 }
 
-fn replace_logger(logger: env_logger::Logger) {
+fn setup_logger(env: env_logger::Env, configure: Configure) -> io::Result<()> {
+    let mut builder = env_logger::Builder::from_env(env);
+    configure(&mut builder)?;
+    let logger = builder.build();
     log::set_max_level(logger.filter());
     *LOGGER.write().unwrap() = logger;
+    *RATE_LIMITER.write().unwrap() = None;
+    *CAPTURE.write().unwrap() = None;
+    *CONFIGURE.write().unwrap() = Some(configure);
     let _ = log::set_boxed_logger(Box::new(LoggerShim {}));
+    Ok(())
+}
+
+fn format_default(builder: &mut env_logger::Builder) -> io::Result<()> {
+    builder.format_timestamp_nanos();
+    Ok(())
+}
+
+/// Records captured by `setup_capture`
+#[derive(Clone, Default)]
+pub struct CaptureHandle {
+    records: Arc<Mutex<Vec<(log::Level, String)>>>,
+}
+
+impl CaptureHandle {
+    fn push(&self, record: &log::Record) {
+        self.records
+            .lock()
+            .unwrap()
+            .push((record.level(), record.args().to_string()));
+    }
+
+    /// Returns the records captured so far, in the order they were logged, and clears them
+    pub fn drain(&self) -> Vec<(log::Level, String)> {
+        std::mem::take(&mut *self.records.lock().unwrap())
+    }
+}
+
+/// Records logged and suppressed for a target in the current one second window
+struct TargetWindow {
+    start: Instant,
+    logged: u64,
+    suppressed: u64,
+}
+
+struct RateLimiter {
+    per_target_per_sec: u64,
+    targets: Mutex<HashMap<String, TargetWindow>>,
+}
+
+impl RateLimiter {
+    fn new(per_target_per_sec: u64) -> Self {
+        Self {
+            per_target_per_sec,
+            targets: Mutex::default(),
+        }
+    }
+
+    /// Returns `None` if a record from `target` should be suppressed, otherwise the number of
+    /// records from `target` suppressed since the last one logged
+    fn admit(&self, target: &str, now: Instant) -> Option<u64> {
+        let mut targets = self.targets.lock().unwrap();
+        if !targets.contains_key(target) {
+            targets.insert(
+                target.to_string(),
+                TargetWindow {
+                    start: now,
+                    logged: 0,
+                    suppressed: 0,
+                },
+            );
+        }
+        let window = targets.get_mut(target).unwrap();
+        if now.saturating_duration_since(window.start) >= Duration::from_secs(1) {
+            window.start = now;
+            window.logged = 0;
+        }
+        if window.logged >= self.per_target_per_sec {
+            window.suppressed += 1;
+            return None;
+        }
+        window.logged += 1;
+        Some(std::mem::take(&mut window.suppressed))
+    }
+}
+
+fn log_rate_limited(
+    logger: &dyn log::Log,
+    rate_limiter: &RateLimiter,
+    record: &log::Record,
+    now: Instant,
+) {
+    if !logger.enabled(record.metadata()) {
+        return;
+    }
+    match rate_limiter.admit(record.target(), now) {
+        None => {}
+        Some(0) => logger.log(record),
+        Some(suppressed) => logger.log(
+            &log::Record::builder()
+                .metadata(record.metadata().clone())
+                .args(format_args!("{} ({suppressed} suppressed)", record.args()))
+                .module_path(record.module_path())
+                .file(record.file())
+                .line(record.line())
+                .build(),
+        ),
+    }
 }
 
 // Configures logging with a specific filter overriding RUST_LOG.  _RUST_LOG is used instead
 // so if set it takes precedence.
 // May be called at any time to re-configure the log filter
 pub fn setup_with(filter: &str) {
-    let logger =
-        env_logger::Builder::from_env(env_logger::Env::new().filter_or("_RUST_LOG", filter))
-            .format_timestamp_nanos()
-            .build();
-    replace_logger(logger);
+    setup_logger(
+        env_logger::Env::new().filter_or("_RUST_LOG", filter),
+        Box::new(format_default),
+    )
+    .expect("the default format can't fail");
+}
+
+// Changes the filter of the logger set up last, keeping its format, output and rate limit.
+// Unlike the setup functions, `filter` is not overridden by RUST_LOG or _RUST_LOG.  If any
+// directive of `filter` is invalid the filter is left unchanged
+pub fn set_filter(filter: &str) -> Result<(), String> {
+    validate_filter(filter)?;
+    let configure = CONFIGURE.read().unwrap();
+    let mut builder = env_logger::Builder::new();
+    builder.parse_filters(filter);
+    match configure.as_ref() {
+        Some(configure) => configure(&mut builder),
+        None => format_default(&mut builder),
+    }
+    .map_err(|err| format!("unable to reconfigure the logger: {err}"))?;
+    let logger = builder.build();
+    // Capturing keeps records of all levels
+    if CAPTURE.read().unwrap().is_none() {
+        log::set_max_level(logger.filter());
+    }
+    *LOGGER.write().unwrap() = logger;
+    let _ = log::set_boxed_logger(Box::new(LoggerShim {}));
+    Ok(())
+}
+
+// Rejects the directives which env_logger would otherwise warn about and ignore.  A regex
+// following the directives is not checked
+fn validate_filter(filter: &str) -> Result<(), String> {
+    let mut parts = filter.split('/');
+    let directives = parts.next().unwrap_or_default();
+    if parts.nth(1).is_some() {
+        return Err(format!("invalid log filter \"{filter}\": too many '/'s"));
+    }
+    for directive in directives.split(',').map(str::trim) {
+        let mut parts = directive.split('=');
+        match (parts.next(), parts.next().map(str::trim), parts.next()) {
+            (_, None | Some(""), None) => {}
+            (_, Some(level), None) if level.parse::<log::LevelFilter>().is_ok() => {}
+            _ => return Err(format!("invalid log filter directive \"{directive}\"")),
+        }
+    }
+    Ok(())
+}
+
+// Configures logging like `setup_with`, but formats each record as a single-line JSON object
+// with `timestamp`, `level`, `target` and `message` fields, for log aggregators
+pub fn setup_json_with_filter(filter: &str) {
+    setup_logger(
+        env_logger::Env::new().filter_or("_RUST_LOG", filter),
+        Box::new(|builder| {
+            format_json(builder);
+            Ok(())
+        }),
+    )
+    .expect("the JSON format can't fail");
+}
+
+fn format_json(builder: &mut env_logger::Builder) {
+    builder.format(|buf, record| {
+        let timestamp = buf.timestamp_nanos();
+        write_json_record(buf, timestamp, record)
+    });
+}
+
+fn write_json_record(
+    writer: &mut impl Write,
+    timestamp: impl Display,
+    record: &log::Record,
+) -> io::Result<()> {
+    let json = serde_json::json!({
+        "timestamp": timestamp.to_string(),
+        "level": record.level().as_str(),
+        "target": record.target(),
+        "message": record.args().to_string(),
+    });
+    writeln!(writer, "{json}")
+}
+
+// Configures logging like `setup_with`, but logs at most `per_target_per_sec` records per second
+// from each target.  The number of records suppressed is appended to the next record logged from
+// the same target
+pub fn setup_with_rate_limit(filter: &str, per_target_per_sec: u64) {
+    setup_with(filter);
+    *RATE_LIMITER.write().unwrap() = Some(RateLimiter::new(per_target_per_sec));
+}
+
+// Captures records of all levels in memory instead of logging them, so that tests can assert on
+// them.  The logger is global, so capturing replaces whatever sink was set up before, and is in
+// turn replaced by any later setup call
+pub fn setup_capture() -> CaptureHandle {
+    let capture = CaptureHandle::default();
+    *CAPTURE.write().unwrap() = Some(capture.clone());
+    log::set_max_level(log::LevelFilter::Trace);
+    let _ = log::set_boxed_logger(Box::new(LoggerShim {}));
+    capture
 }
 
 // Configures logging with a default filter if RUST_LOG is not set
 pub fn setup_with_default(filter: &str) {
-    let logger = env_logger::Builder::from_env(env_logger::Env::new().default_filter_or(filter))
-        .format_timestamp_nanos()
-        .build();
-    replace_logger(logger);
+    setup_logger(
+        env_logger::Env::new().default_filter_or(filter),
+        Box::new(format_default),
+    )
+    .expect("the default format can't fail");
 }
 
 // Configures logging with the `DEFAULT_FILTER` if RUST_LOG is not set
@@ -61,17 +281,176 @@ pub fn setup() {
     setup_with_default("error");
 }
 
-// Configures file logging with a default filter if RUST_LOG is not set
-pub fn setup_file_with_default(logfile: &str, filter: &str) {
+// Configures file logging with a default filter if RUST_LOG is not set.  Fails if `logfile` can't
+// be opened, leaving the logger unchanged
+pub fn setup_file_with_default(logfile: &str, filter: &str) -> io::Result<()> {
     use std::fs::OpenOptions;
-    let file = OpenOptions::new()
-        .create(true)
-        .append(true)
-        .open(logfile)
-        .unwrap();
-    let logger = env_logger::Builder::from_env(env_logger::Env::new().default_filter_or(filter))
-        .format_timestamp_nanos()
-        .target(env_logger::Target::Pipe(Box::new(file)))
-        .build();
-    replace_logger(logger);
+    let file = OpenOptions::new().create(true).append(true).open(logfile)?;
+    setup_logger(
+        env_logger::Env::new().default_filter_or(filter),
+        Box::new(move |builder| {
+            builder
+                .format_timestamp_nanos()
+                .target(env_logger::Target::Pipe(Box::new(file.try_clone()?)));
+            Ok(())
+        }),
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use {super::*, std::sync::Mutex};
+
+    #[derive(Clone, Default)]
+    struct SharedBuffer(Arc<Mutex<Vec<u8>>>);
+
+    impl Write for SharedBuffer {
+        fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+            self.0.lock().unwrap().write(buf)
+        }
+
+        fn flush(&mut self) -> io::Result<()> {
+            Ok(())
+        }
+    }
+
+    #[test]
+    fn test_rate_limit() {
+        let buffer = SharedBuffer::default();
+        let logger = env_logger::Builder::new()
+            .parse_filters("info")
+            .format(|buf, record| writeln!(buf, "{}: {}", record.target(), record.args()))
+            .target(env_logger::Target::Pipe(Box::new(buffer.clone())))
+            .build();
+        let rate_limiter = RateLimiter::new(5);
+        let emit = |target: &str, i: usize, now: Instant| {
+            log_rate_limited(
+                &logger,
+                &rate_limiter,
+                &log::Record::builder()
+                    .level(log::Level::Info)
+                    .target(target)
+                    .args(format_args!("record {i}"))
+                    .build(),
+                now,
+            )
+        };
+
+        let start = Instant::now();
+        for i in 0..20 {
+            emit("noisy", i, start);
+        }
+        // other targets are limited separately
+        emit("quiet", 0, start);
+        // the next window reports how many records were suppressed
+        emit("noisy", 20, start + Duration::from_secs(1));
+
+        let output = String::from_utf8(buffer.0.lock().unwrap().clone()).unwrap();
+        assert_eq!(
+            output.lines().collect::<Vec<_>>(),
+            vec![
+                "noisy: record 0",
+                "noisy: record 1",
+                "noisy: record 2",
+                "noisy: record 3",
+                "noisy: record 4",
+                "quiet: record 0",
+                "noisy: record 20 (15 suppressed)",
+            ]
+        );
+    }
+
+    #[test]
+    fn test_capture() {
+        let capture = setup_capture();
+        log::info!("first");
+        log::debug!("second {}", 2);
+        log::trace!(target: "solana_test", "third");
+        log::warn!("fourth");
+        assert_eq!(
+            capture.drain(),
+            vec![
+                (log::Level::Info, "first".to_string()),
+                (log::Level::Debug, "second 2".to_string()),
+                (log::Level::Trace, "third".to_string()),
+                (log::Level::Warn, "fourth".to_string()),
+            ]
+        );
+        assert!(capture.drain().is_empty());
+
+        log::error!("fifth");
+        assert_eq!(
+            capture.drain(),
+            vec![(log::Level::Error, "fifth".to_string())]
+        );
+
+        // setting up another sink stops the capture
+        setup_with("error");
+        log::error!("not captured");
+        assert!(capture.drain().is_empty());
+    }
+
+    #[test]
+    fn test_validate_filter() {
+        for filter in [
+            "",
+            "info",
+            "solana=info,agave=debug",
+            "solana_core::replay_stage=trace, solana_metrics",
+            "solana=",
+            "info/replay",
+        ] {
+            assert_eq!(validate_filter(filter), Ok(()), "{filter}");
+        }
+        for filter in ["solana=loud", "solana=info=debug", "info/a/b"] {
+            assert!(validate_filter(filter).is_err(), "{filter}");
+        }
+    }
+
+    #[test]
+    fn test_setup_file_error() {
+        // A file can't be created below another file
+        let logfile = concat!(env!("CARGO_MANIFEST_DIR"), "/Cargo.toml/solana.log");
+        assert!(setup_file_with_default(logfile, "info").is_err());
+    }
+
+    #[test]
+    fn test_json_format() {
+        let buffer = SharedBuffer::default();
+        let mut builder = env_logger::Builder::new();
+        format_json(builder.parse_filters("info"));
+        let logger = builder
+            .target(env_logger::Target::Pipe(Box::new(buffer.clone())))
+            .build();
+
+        log::Log::log(
+            &logger,
+            &log::Record::builder()
+                .level(log::Level::Warn)
+                .target("solana_test")
+                .args(format_args!("a \"quoted\" message"))
+                .build(),
+        );
+        // filtered out
+        log::Log::log(
+            &logger,
+            &log::Record::builder()
+                .level(log::Level::Debug)
+                .target("solana_test")
+                .args(format_args!("not logged"))
+                .build(),
+        );
+
+        let output = String::from_utf8(buffer.0.lock().unwrap().clone()).unwrap();
+        let lines = output.lines().collect::<Vec<_>>();
+        assert_eq!(lines.len(), 1);
+        let json: serde_json::Value = serde_json::from_str(lines[0]).unwrap();
+        assert_eq!(json["level"], "WARN");
+        assert_eq!(json["target"], "solana_test");
+        assert_eq!(json["message"], "a \"quoted\" message");
+        // RFC 3339 with nanoseconds, e.g. 2024-01-01T00:00:00.123456789Z
+        let timestamp = json["timestamp"].as_str().unwrap();
+        let (_, fraction) = timestamp.trim_end_matches('Z').split_once('.').unwrap();
+        assert_eq!(fraction.len(), 9);
+    }
 }
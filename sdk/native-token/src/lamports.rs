@@ -0,0 +1,174 @@
+//! A lamport amount with explicit overflow handling.
+
+use {
+    crate::{lamports_to_sol, sol_to_lamports},
+    solana_instruction::error::InstructionError,
+};
+
+/// An amount of lamports.
+///
+/// `Lamports` deliberately does not implement the arithmetic operators, so
+/// every operation on a balance spells out what happens when it would overflow
+/// or underflow: `checked_*` returns `None`, `saturating_*` clamps, and
+/// `try_*` returns the [`InstructionError`] a program should fail with.
+#[cfg_attr(feature = "frozen-abi", derive(solana_frozen_abi_macro::AbiExample))]
+#[cfg_attr(
+    feature = "serde",
+    derive(serde_derive::Deserialize, serde_derive::Serialize),
+    serde(transparent)
+)]
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct Lamports(u64);
+
+impl Lamports {
+    pub const ZERO: Self = Self(0);
+    pub const MAX: Self = Self(u64::MAX);
+
+    pub const fn new(lamports: u64) -> Self {
+        Self(lamports)
+    }
+
+    pub const fn get(self) -> u64 {
+        self.0
+    }
+
+    /// Approximately convert native tokens (SOL) into lamports
+    pub fn from_sol(sol: f64) -> Self {
+        Self(sol_to_lamports(sol))
+    }
+
+    /// Approximately convert into native tokens (SOL)
+    pub fn to_sol(self) -> f64 {
+        lamports_to_sol(self.0)
+    }
+
+    pub const fn checked_add(self, other: Self) -> Option<Self> {
+        match self.0.checked_add(other.0) {
+            Some(lamports) => Some(Self(lamports)),
+            None => None,
+        }
+    }
+
+    pub const fn checked_sub(self, other: Self) -> Option<Self> {
+        match self.0.checked_sub(other.0) {
+            Some(lamports) => Some(Self(lamports)),
+            None => None,
+        }
+    }
+
+    pub const fn saturating_add(self, other: Self) -> Self {
+        Self(self.0.saturating_add(other.0))
+    }
+
+    pub const fn saturating_sub(self, other: Self) -> Self {
+        Self(self.0.saturating_sub(other.0))
+    }
+
+    /// Adds `other`, failing with `ArithmeticOverflow` if the sum does not fit
+    /// in a `u64`
+    pub fn try_add(self, other: Self) -> Result<Self, InstructionError> {
+        self.checked_add(other)
+            .ok_or(InstructionError::ArithmeticOverflow)
+    }
+
+    /// Subtracts `other`, failing with `InsufficientFunds` if `other` is
+    /// larger than `self`
+    pub fn try_sub(self, other: Self) -> Result<Self, InstructionError> {
+        self.checked_sub(other)
+            .ok_or(InstructionError::InsufficientFunds)
+    }
+}
+
+impl From<u64> for Lamports {
+    fn from(lamports: u64) -> Self {
+        Self(lamports)
+    }
+}
+
+impl From<Lamports> for u64 {
+    fn from(lamports: Lamports) -> Self {
+        lamports.0
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use {super::*, crate::LAMPORTS_PER_SOL};
+
+    #[test]
+    fn test_add_near_max() {
+        let one = Lamports(1);
+        let below_max = Lamports(u64::MAX - 1);
+
+        assert_eq!(below_max.checked_add(one), Some(Lamports::MAX));
+        assert_eq!(
+            Lamports::MAX.checked_add(Lamports::ZERO),
+            Some(Lamports::MAX)
+        );
+        assert_eq!(Lamports::MAX.checked_add(one), None);
+        assert_eq!(below_max.checked_add(Lamports(2)), None);
+
+        assert_eq!(below_max.saturating_add(one), Lamports::MAX);
+        assert_eq!(Lamports::MAX.saturating_add(one), Lamports::MAX);
+        assert_eq!(Lamports::MAX.saturating_add(Lamports::MAX), Lamports::MAX);
+
+        assert_eq!(below_max.try_add(one), Ok(Lamports::MAX));
+        assert_eq!(
+            Lamports::MAX.try_add(one),
+            Err(InstructionError::ArithmeticOverflow)
+        );
+        assert_eq!(
+            below_max.try_add(Lamports(2)),
+            Err(InstructionError::ArithmeticOverflow)
+        );
+    }
+
+    #[test]
+    fn test_sub_near_zero_and_max() {
+        let one = Lamports(1);
+
+        assert_eq!(one.checked_sub(one), Some(Lamports::ZERO));
+        assert_eq!(Lamports::ZERO.checked_sub(one), None);
+        assert_eq!(
+            Lamports::MAX.checked_sub(Lamports::MAX),
+            Some(Lamports::ZERO)
+        );
+        assert_eq!(Lamports(u64::MAX - 1).checked_sub(Lamports::MAX), None);
+
+        assert_eq!(Lamports::ZERO.saturating_sub(one), Lamports::ZERO);
+        assert_eq!(
+            Lamports(u64::MAX - 1).saturating_sub(Lamports::MAX),
+            Lamports::ZERO
+        );
+        assert_eq!(Lamports::MAX.saturating_sub(one), Lamports(u64::MAX - 1));
+
+        assert_eq!(Lamports::MAX.try_sub(Lamports::MAX), Ok(Lamports::ZERO));
+        assert_eq!(
+            Lamports::ZERO.try_sub(one),
+            Err(InstructionError::InsufficientFunds)
+        );
+        assert_eq!(
+            Lamports(u64::MAX - 1).try_sub(Lamports::MAX),
+            Err(InstructionError::InsufficientFunds)
+        );
+    }
+
+    #[test]
+    fn test_conversions() {
+        assert_eq!(Lamports::from(42), Lamports::new(42));
+        assert_eq!(u64::from(Lamports(42)), 42);
+        assert_eq!(Lamports(42).get(), 42);
+        assert_eq!(Lamports::from_sol(1.5), Lamports(3 * LAMPORTS_PER_SOL / 2));
+        assert_eq!(Lamports(LAMPORTS_PER_SOL).to_sol(), 1.0);
+    }
+
+    #[test]
+    fn test_serde() {
+        let json = serde_json::to_string(&Lamports::MAX).unwrap();
+        assert_eq!(json, u64::MAX.to_string());
+        assert_eq!(
+            serde_json::from_str::<Lamports>(&json).unwrap(),
+            Lamports::MAX
+        );
+    }
+}
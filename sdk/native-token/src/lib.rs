@@ -1,6 +1,11 @@
 //! Definitions for the native SOL token and its fractional lamports.
 
 #![allow(clippy::arithmetic_side_effects)]
+#![cfg_attr(feature = "frozen-abi", feature(min_specialization))]
+
+mod lamports;
+
+pub use lamports::Lamports;
 
 /// There are 10^9 lamports in one SOL
 pub const LAMPORTS_PER_SOL: u64 = 1_000_000_000;
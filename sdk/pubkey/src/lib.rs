@@ -306,6 +306,14 @@ impl Pubkey {
     }
 
     /// Decode a string into a Pubkey, usable in a const context
+    ///
+    /// Strings that don't decode to exactly 32 bytes are rejected when the
+    /// const is evaluated, so a malformed id fails to compile:
+    ///
+    /// ```compile_fail
+    /// # use solana_pubkey::Pubkey;
+    /// const TOO_SHORT: Pubkey = Pubkey::from_str_const("9h1HyLCW5dZnBVap8C5egQ9Z6pHyjsh5MNy83iPq");
+    /// ```
     pub const fn from_str_const(s: &str) -> Self {
         let id_array = five8_const::decode_32_const(s);
         Pubkey::new_from_array(id_array)
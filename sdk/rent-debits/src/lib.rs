@@ -35,6 +35,12 @@ impl RentDebits {
             .unwrap_or_default()
     }
 
+    /// Returns the balance of `address` right after rent was collected from
+    /// it, or `None` if no rent was collected
+    pub fn get_account_post_balance(&self, address: &Pubkey) -> Option<u64> {
+        self.0.get(address).map(|r| r.post_balance)
+    }
+
     // These functions/fields are only usable from a dev context (i.e. tests and benches)
     #[cfg(feature = "dev-context-only-utils")]
     pub fn len(&self) -> usize {
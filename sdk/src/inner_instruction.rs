@@ -3,6 +3,7 @@ use {
     serde::{Deserialize, Serialize},
 };
 
+#[cfg_attr(feature = "frozen-abi", derive(AbiExample))]
 #[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
 #[serde(rename_all = "camelCase")]
 pub struct InnerInstruction {
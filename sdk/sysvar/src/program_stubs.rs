@@ -6,14 +6,147 @@ use {
     base64::{prelude::BASE64_STANDARD, Engine},
     solana_account_info::AccountInfo,
     solana_instruction::{error::UNSUPPORTED_SYSVAR, Instruction},
-    solana_program_error::ProgramResult,
+    solana_program_error::{ProgramError, ProgramResult},
     solana_program_memory::stubs,
     solana_pubkey::Pubkey,
-    std::sync::{Arc, RwLock},
+    std::{
+        collections::HashMap,
+        sync::{Arc, RwLock},
+    },
 };
 
 lazy_static::lazy_static! {
     static ref SYSCALL_STUBS: Arc<RwLock<Box<dyn SyscallStubs>>> = Arc::new(RwLock::new(Box::new(DefaultSyscallStubs {})));
+    static ref CPI_HANDLERS: RwLock<HashMap<Pubkey, Arc<CpiHandler>>> = RwLock::default();
+}
+
+/// A Rust stand-in for an on-chain program, called with the program id, the
+/// instruction accounts and the instruction data of a cross-program invocation.
+pub type CpiHandler = dyn Fn(&Pubkey, &[AccountInfo], &[u8]) -> ProgramResult + Send + Sync;
+
+/// Register `handler` to process cross-program invocations of `program_id`
+/// made through the default syscall stubs, returning any handler it replaces.
+pub fn register_cpi_handler<F>(program_id: Pubkey, handler: F) -> Option<Arc<CpiHandler>>
+where
+    F: Fn(&Pubkey, &[AccountInfo], &[u8]) -> ProgramResult + Send + Sync + 'static,
+{
+    CPI_HANDLERS
+        .write()
+        .unwrap()
+        .insert(program_id, Arc::new(handler))
+}
+
+/// Remove the handler registered for `program_id`, if any.
+pub fn unregister_cpi_handler(program_id: &Pubkey) -> Option<Arc<CpiHandler>> {
+    CPI_HANDLERS.write().unwrap().remove(program_id)
+}
+
+/// Dispatch `instruction` to the handler registered for its program id.
+///
+/// The handler is given its own copy of every instruction account, with signer
+/// and writable flags taken from the instruction's account metas. Once it
+/// succeeds, lamports and data of the accounts it was given writable access to
+/// are copied back into the caller's `account_infos`; changes to read-only
+/// accounts are discarded. Returns `None` if no handler is registered.
+pub fn invoke_cpi_handler(
+    instruction: &Instruction,
+    account_infos: &[AccountInfo],
+) -> Option<ProgramResult> {
+    let handler = CPI_HANDLERS
+        .read()
+        .unwrap()
+        .get(&instruction.program_id)
+        .cloned()?;
+    Some(process_cpi(handler.as_ref(), instruction, account_infos))
+}
+
+fn process_cpi(
+    handler: &CpiHandler,
+    instruction: &Instruction,
+    account_infos: &[AccountInfo],
+) -> ProgramResult {
+    // Resolve the instruction accounts against the caller's account infos,
+    // letting duplicate metas share a single callee account
+    let mut caller_infos: Vec<&AccountInfo> = Vec::with_capacity(instruction.accounts.len());
+    let mut indices = Vec::with_capacity(instruction.accounts.len());
+    for meta in &instruction.accounts {
+        let index = match caller_infos
+            .iter()
+            .position(|info| info.key == &meta.pubkey)
+        {
+            Some(index) => index,
+            None => {
+                let info = account_infos
+                    .iter()
+                    .find(|info| info.key == &meta.pubkey)
+                    .ok_or(ProgramError::NotEnoughAccountKeys)?;
+                caller_infos.push(info);
+                caller_infos.len().saturating_sub(1)
+            }
+        };
+        indices.push(index);
+    }
+
+    let mut lamports = caller_infos
+        .iter()
+        .map(|info| info.lamports())
+        .collect::<Vec<_>>();
+    let mut data = caller_infos
+        .iter()
+        .map(|info| info.try_borrow_data().map(|data| data.to_vec()))
+        .collect::<Result<Vec<_>, _>>()?;
+    {
+        let callee_infos = caller_infos
+            .iter()
+            .zip(lamports.iter_mut())
+            .zip(data.iter_mut())
+            .map(|((info, lamports), data)| {
+                AccountInfo::new(
+                    info.key,
+                    false,
+                    false,
+                    lamports,
+                    data,
+                    info.owner,
+                    info.executable,
+                    info.rent_epoch,
+                )
+            })
+            .collect::<Vec<_>>();
+        let instruction_infos = instruction
+            .accounts
+            .iter()
+            .zip(indices)
+            .map(|(meta, index)| {
+                let mut info = callee_infos[index].clone();
+                info.is_signer = meta.is_signer;
+                info.is_writable = meta.is_writable;
+                info
+            })
+            .collect::<Vec<_>>();
+        handler(
+            &instruction.program_id,
+            &instruction_infos,
+            &instruction.data,
+        )?;
+    }
+
+    for ((info, lamports), data) in caller_infos.into_iter().zip(lamports).zip(data) {
+        let is_writable = instruction
+            .accounts
+            .iter()
+            .any(|meta| &meta.pubkey == info.key && meta.is_writable);
+        if !is_writable {
+            continue;
+        }
+        let mut caller_data = info.try_borrow_mut_data()?;
+        if caller_data.len() != data.len() {
+            return Err(ProgramError::InvalidRealloc);
+        }
+        caller_data.copy_from_slice(&data);
+        **info.try_borrow_mut_lamports()? = lamports;
+    }
+    Ok(())
 }
 
 // The default syscall stubs may not do much, but `set_syscalls()` can be used
@@ -35,12 +168,14 @@ pub trait SyscallStubs: Sync + Send {
     }
     fn sol_invoke_signed(
         &self,
-        _instruction: &Instruction,
-        _account_infos: &[AccountInfo],
+        instruction: &Instruction,
+        account_infos: &[AccountInfo],
         _signers_seeds: &[&[&[u8]]],
     ) -> ProgramResult {
-        sol_log("SyscallStubs: sol_invoke_signed() not available");
-        Ok(())
+        invoke_cpi_handler(instruction, account_infos).unwrap_or_else(|| {
+            sol_log("SyscallStubs: sol_invoke_signed() not available");
+            Ok(())
+        })
     }
     fn sol_get_sysvar(
         &self,
@@ -223,3 +358,96 @@ pub(crate) fn sol_get_epoch_rewards_sysvar(var_addr: *mut u8) -> u64 {
         .unwrap()
         .sol_get_epoch_rewards_sysvar(var_addr)
 }
+
+#[cfg(test)]
+mod tests {
+    use {super::*, serial_test::serial, solana_clock::Epoch, solana_instruction::AccountMeta};
+
+    #[test]
+    #[serial]
+    fn test_sol_invoke_signed_dispatches_to_handler() {
+        let program_id = Pubkey::new_unique();
+        register_cpi_handler(program_id, |_program_id, accounts, data| {
+            let amount = u64::from_le_bytes(data.try_into().unwrap());
+            **accounts[0].try_borrow_mut_lamports()? -= amount;
+            **accounts[1].try_borrow_mut_lamports()? += amount;
+            accounts[1].try_borrow_mut_data()?[0] = 1;
+            Ok(())
+        });
+
+        let owner = Pubkey::new_unique();
+        let from_key = Pubkey::new_unique();
+        let to_key = Pubkey::new_unique();
+        let mut from_lamports = 100;
+        let mut to_lamports = 0;
+        let mut from_data = vec![];
+        let mut to_data = vec![0];
+        let account_infos = [
+            AccountInfo::new(
+                &from_key,
+                true,
+                true,
+                &mut from_lamports,
+                &mut from_data,
+                &owner,
+                false,
+                Epoch::default(),
+            ),
+            AccountInfo::new(
+                &to_key,
+                false,
+                true,
+                &mut to_lamports,
+                &mut to_data,
+                &owner,
+                false,
+                Epoch::default(),
+            ),
+        ];
+        let instruction = Instruction::new_with_bytes(
+            program_id,
+            &42u64.to_le_bytes(),
+            vec![
+                AccountMeta::new(from_key, true),
+                AccountMeta::new(to_key, false),
+            ],
+        );
+
+        assert_eq!(sol_invoke_signed(&instruction, &account_infos, &[]), Ok(()));
+        assert_eq!(account_infos[0].lamports(), 58);
+        assert_eq!(account_infos[1].lamports(), 42);
+        assert_eq!(account_infos[1].data.borrow()[0], 1);
+
+        // Modifications to read-only accounts are not copied back
+        let instruction = Instruction::new_with_bytes(
+            program_id,
+            &8u64.to_le_bytes(),
+            vec![
+                AccountMeta::new(from_key, true),
+                AccountMeta::new_readonly(to_key, false),
+            ],
+        );
+        assert_eq!(sol_invoke_signed(&instruction, &account_infos, &[]), Ok(()));
+        assert_eq!(account_infos[0].lamports(), 50);
+        assert_eq!(account_infos[1].lamports(), 42);
+
+        // Instruction accounts must be provided by the caller
+        let instruction = Instruction::new_with_bytes(
+            program_id,
+            &8u64.to_le_bytes(),
+            vec![
+                AccountMeta::new(from_key, true),
+                AccountMeta::new(Pubkey::new_unique(), false),
+            ],
+        );
+        assert_eq!(
+            sol_invoke_signed(&instruction, &account_infos, &[]),
+            Err(ProgramError::NotEnoughAccountKeys)
+        );
+
+        assert!(unregister_cpi_handler(&program_id).is_some());
+        let instruction = Instruction::new_with_bytes(program_id, &[], vec![]);
+        assert_eq!(sol_invoke_signed(&instruction, &account_infos, &[]), Ok(()));
+        assert_eq!(account_infos[0].lamports(), 50);
+    }
+}
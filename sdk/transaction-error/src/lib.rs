@@ -137,6 +137,9 @@ pub enum TransactionError {
 
     /// Program cache hit max limit.
     ProgramCacheHitMaxLimit,
+
+    /// The fee payer existed at the start of the slot but was deleted by rent collection
+    AccountPurgedByRent,
 }
 
 impl std::error::Error for TransactionError {}
@@ -220,6 +223,8 @@ impl fmt::Display for TransactionError {
              => f.write_str("Sum of account balances before and after transaction do not match"),
             Self::ProgramCacheHitMaxLimit
              => f.write_str("Program cache hit max limit"),
+            Self::AccountPurgedByRent
+             => f.write_str("Attempt to debit an account that was deleted by rent collection"),
         }
     }
 }
@@ -0,0 +1,458 @@
+//! A builder for [`Transaction`]s.
+//!
+//! [`Message::new`] already merges the privileges of accounts that appear in
+//! more than one instruction and places the fee payer first, but it is easy
+//! to get the rest wrong by hand: passing the same signer twice, forgetting
+//! the fee payer, or producing a transaction that only turns out to be too
+//! large to fit in a packet when it is sent. [`TransactionBuilder`] takes
+//! care of all of these, and checks the size of the transaction before
+//! asking any signer to sign it.
+//!
+//! ```
+//! # use solana_hash::Hash;
+//! # use solana_keypair::Keypair;
+//! # use solana_pubkey::Pubkey;
+//! # use solana_signer::Signer;
+//! # use solana_system_interface::instruction as system_instruction;
+//! use solana_transaction::builder::TransactionBuilder;
+//!
+//! let payer = Keypair::new();
+//! let transaction = TransactionBuilder::new()
+//!     .add_instruction(system_instruction::transfer(
+//!         &payer.pubkey(),
+//!         &Pubkey::new_unique(),
+//!         1,
+//!     ))
+//!     .signers(&[&payer, &payer])
+//!     .build(Hash::default())?;
+//! assert_eq!(transaction.signatures.len(), 1);
+//! # Ok::<(), solana_transaction::builder::TransactionBuilderError>(())
+//! ```
+
+use {
+    crate::{Transaction, PACKET_DATA_SIZE},
+    solana_hash::Hash,
+    solana_instruction::Instruction,
+    solana_message::Message,
+    solana_pubkey::Pubkey,
+    solana_signer::{Signer, SignerError},
+    std::fmt,
+};
+
+/// Errors from [`TransactionBuilder::build`] and the other signing methods
+#[derive(Debug, PartialEq, Eq)]
+pub enum TransactionBuilderError {
+    /// No fee payer was set, and there are no signers to default to
+    MissingFeePayer,
+    /// The serialized transaction would not fit in a packet
+    TooLarge {
+        /// Size of the serialized transaction, in bytes
+        size: usize,
+        /// Maximum size of a serialized transaction, in bytes
+        max_size: usize,
+    },
+    /// The signers don't match the signatures the transaction requires
+    Signer(SignerError),
+}
+
+impl TransactionBuilderError {
+    /// The number of bytes by which a transaction is too large, if it is
+    pub fn overflow(&self) -> Option<usize> {
+        match self {
+            Self::TooLarge { size, max_size } => Some(size.saturating_sub(*max_size)),
+            _ => None,
+        }
+    }
+}
+
+impl std::error::Error for TransactionBuilderError {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            Self::MissingFeePayer => None,
+            Self::TooLarge { .. } => None,
+            Self::Signer(e) => Some(e),
+        }
+    }
+}
+
+impl fmt::Display for TransactionBuilderError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Self::MissingFeePayer => f.write_str("transaction has no fee payer"),
+            Self::TooLarge { size, max_size } => write!(
+                f,
+                "transaction is {size} bytes, {} bytes more than the maximum of {max_size}",
+                size.saturating_sub(*max_size)
+            ),
+            Self::Signer(e) => write!(f, "{e}"),
+        }
+    }
+}
+
+impl From<SignerError> for TransactionBuilderError {
+    fn from(err: SignerError) -> Self {
+        Self::Signer(err)
+    }
+}
+
+/// Builds a [`Transaction`] from instructions and signers
+///
+/// See the [module documentation](self) for details.
+#[derive(Clone, Default)]
+pub struct TransactionBuilder<'a> {
+    instructions: Vec<Instruction>,
+    fee_payer: Option<Pubkey>,
+    nonce: Option<(Pubkey, Pubkey)>,
+    signers: Vec<&'a dyn Signer>,
+}
+
+impl<'a> TransactionBuilder<'a> {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Append an instruction
+    pub fn add_instruction(mut self, instruction: Instruction) -> Self {
+        self.instructions.push(instruction);
+        self
+    }
+
+    /// Append several instructions, in order
+    pub fn add_instructions(mut self, instructions: impl IntoIterator<Item = Instruction>) -> Self {
+        self.instructions.extend(instructions);
+        self
+    }
+
+    /// Set the account that pays the transaction fee
+    ///
+    /// Defaults to the first signer.
+    pub fn fee_payer(mut self, fee_payer: Pubkey) -> Self {
+        self.fee_payer = Some(fee_payer);
+        self
+    }
+
+    /// Use the durable nonce in `nonce_account` instead of a recent blockhash
+    ///
+    /// An instruction advancing the nonce, signed by `nonce_authority`, is
+    /// placed before the other instructions, as [`Message::new_with_nonce`]
+    /// does.
+    pub fn nonce(mut self, nonce_account: &Pubkey, nonce_authority: &Pubkey) -> Self {
+        self.nonce = Some((*nonce_account, *nonce_authority));
+        self
+    }
+
+    /// Add a signer, ignoring it if a signer with the same pubkey was already added
+    pub fn signer(mut self, signer: &'a dyn Signer) -> Self {
+        let pubkey = signer.pubkey();
+        if !self.signers.iter().any(|s| s.pubkey() == pubkey) {
+            self.signers.push(signer);
+        }
+        self
+    }
+
+    /// Add signers, ignoring any with the same pubkey as a signer already added
+    pub fn signers(self, signers: &[&'a dyn Signer]) -> Self {
+        signers
+            .iter()
+            .fold(self, |builder, signer| builder.signer(*signer))
+    }
+
+    fn resolved_fee_payer(&self) -> Option<Pubkey> {
+        self.fee_payer
+            .or_else(|| self.signers.first().map(|signer| signer.pubkey()))
+    }
+
+    /// The message the transaction will sign, with a default blockhash
+    ///
+    /// Accounts used by several instructions appear once, with the union of
+    /// their privileges, and the fee payer is always the first account.
+    pub fn message(&self) -> Message {
+        let fee_payer = self.resolved_fee_payer();
+        match self.nonce {
+            Some((nonce_account, nonce_authority)) => Message::new_with_nonce(
+                self.instructions.clone(),
+                fee_payer.as_ref(),
+                &nonce_account,
+                &nonce_authority,
+            ),
+            None => Message::new(&self.instructions, fee_payer.as_ref()),
+        }
+    }
+
+    /// The size of the serialized transaction, in bytes
+    ///
+    /// Signatures have a fixed size, so this is exact, and doesn't need the
+    /// transaction to be signed.
+    pub fn estimate_size(&self) -> usize {
+        serialized_size(&self.message())
+    }
+
+    /// Build and sign the transaction
+    ///
+    /// Fails without signing if there is no fee payer or the transaction is
+    /// too large to fit in a packet, and if the signers don't provide
+    /// exactly the signatures the transaction requires.
+    pub fn build(&self, recent_blockhash: Hash) -> Result<Transaction, TransactionBuilderError> {
+        if self.resolved_fee_payer().is_none() {
+            return Err(TransactionBuilderError::MissingFeePayer);
+        }
+        self.sign_message(self.message(), recent_blockhash)
+    }
+
+    /// Sign `message` with the builder's signers
+    ///
+    /// For a message compiled by [`message`](Self::message) and then adjusted,
+    /// eg with a simulated compute unit limit. Fails without signing if the
+    /// transaction is too large to fit in a packet.
+    pub fn sign_message(
+        &self,
+        message: Message,
+        recent_blockhash: Hash,
+    ) -> Result<Transaction, TransactionBuilderError> {
+        check_message_size(&message)?;
+        let mut transaction = Transaction::new_unsigned(message);
+        transaction.try_sign(&self.signers, recent_blockhash)?;
+        Ok(transaction)
+    }
+
+    /// Like [`sign_message`](Self::sign_message), but leaves the signatures of
+    /// signers the builder doesn't have empty, for signing offline
+    pub fn partial_sign_message(
+        &self,
+        message: Message,
+        recent_blockhash: Hash,
+    ) -> Result<Transaction, TransactionBuilderError> {
+        check_message_size(&message)?;
+        let mut transaction = Transaction::new_unsigned(message);
+        transaction.try_partial_sign(&self.signers, recent_blockhash)?;
+        Ok(transaction)
+    }
+}
+
+fn serialized_size(message: &Message) -> usize {
+    let transaction = Transaction::new_unsigned(message.clone());
+    bincode::serialized_size(&transaction).unwrap() as usize
+}
+
+/// Check that a transaction signing `message` fits in a packet
+pub fn check_message_size(message: &Message) -> Result<(), TransactionBuilderError> {
+    let size = serialized_size(message);
+    if size > PACKET_DATA_SIZE {
+        return Err(TransactionBuilderError::TooLarge {
+            size,
+            max_size: PACKET_DATA_SIZE,
+        });
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use {
+        super::*, solana_instruction::AccountMeta, solana_keypair::Keypair,
+        solana_signature::Signature, solana_system_interface::instruction as system_instruction,
+    };
+
+    #[test]
+    fn test_privilege_merging() {
+        let payer = Keypair::new();
+        let authority = Keypair::new();
+        let account = Pubkey::new_unique();
+        let program_id = Pubkey::new_unique();
+        let transaction = TransactionBuilder::new()
+            .add_instruction(Instruction::new_with_bytes(
+                program_id,
+                &[],
+                vec![
+                    AccountMeta::new_readonly(account, false),
+                    AccountMeta::new_readonly(authority.pubkey(), true),
+                ],
+            ))
+            .add_instruction(Instruction::new_with_bytes(
+                program_id,
+                &[],
+                vec![
+                    AccountMeta::new(account, false),
+                    AccountMeta::new(authority.pubkey(), false),
+                ],
+            ))
+            .fee_payer(payer.pubkey())
+            .signers(&[&authority, &payer])
+            .build(Hash::new_unique())
+            .unwrap();
+
+        let message = &transaction.message;
+        assert_eq!(
+            message.account_keys,
+            vec![payer.pubkey(), authority.pubkey(), account, program_id]
+        );
+        // payer and authority sign, and both are writable
+        assert_eq!(message.header.num_required_signatures, 2);
+        assert_eq!(message.header.num_readonly_signed_accounts, 0);
+        // account is writable, and only the program is readonly
+        assert_eq!(message.header.num_readonly_unsigned_accounts, 1);
+        assert!(message.is_maybe_writable(2, None));
+        transaction.verify().unwrap();
+    }
+
+    #[test]
+    fn test_duplicate_signers() {
+        let payer = Keypair::new();
+        let to = Pubkey::new_unique();
+        let builder = TransactionBuilder::new()
+            .add_instruction(system_instruction::transfer(&payer.pubkey(), &to, 1))
+            .signers(&[&payer, &payer])
+            .signer(&payer);
+        assert_eq!(builder.signers.len(), 1);
+
+        let transaction = builder.build(Hash::new_unique()).unwrap();
+        assert_eq!(transaction.message.account_keys[0], payer.pubkey());
+        assert_eq!(transaction.signatures.len(), 1);
+        transaction.verify().unwrap();
+    }
+
+    #[test]
+    fn test_fee_payer_first() {
+        let from = Keypair::new();
+        let payer = Keypair::new();
+        let transaction = TransactionBuilder::new()
+            .add_instruction(system_instruction::transfer(
+                &from.pubkey(),
+                &Pubkey::new_unique(),
+                1,
+            ))
+            .fee_payer(payer.pubkey())
+            .signers(&[&from, &payer])
+            .build(Hash::new_unique())
+            .unwrap();
+        assert_eq!(
+            &transaction.message.account_keys[..2],
+            &[payer.pubkey(), from.pubkey()]
+        );
+        transaction.verify().unwrap();
+    }
+
+    #[test]
+    fn test_signer_errors() {
+        let payer = Keypair::new();
+        let instruction = system_instruction::transfer(&payer.pubkey(), &Pubkey::new_unique(), 1);
+        assert_eq!(
+            TransactionBuilder::new()
+                .add_instruction(instruction.clone())
+                .build(Hash::new_unique())
+                .unwrap_err(),
+            TransactionBuilderError::MissingFeePayer,
+        );
+        assert_eq!(
+            TransactionBuilder::new()
+                .add_instruction(instruction.clone())
+                .fee_payer(payer.pubkey())
+                .build(Hash::new_unique())
+                .unwrap_err(),
+            TransactionBuilderError::Signer(SignerError::NotEnoughSigners),
+        );
+        assert_eq!(
+            TransactionBuilder::new()
+                .add_instruction(instruction)
+                .signers(&[&payer, &Keypair::new()])
+                .build(Hash::new_unique())
+                .unwrap_err(),
+            TransactionBuilderError::Signer(SignerError::KeypairPubkeyMismatch),
+        );
+    }
+
+    #[test]
+    fn test_size_boundary() {
+        let payer = Keypair::new();
+        let program_id = Pubkey::new_unique();
+        let builder_with_data = |len| {
+            TransactionBuilder::new()
+                .add_instruction(Instruction::new_with_bytes(
+                    program_id,
+                    &vec![0; len],
+                    vec![AccountMeta::new(payer.pubkey(), true)],
+                ))
+                .signers(&[&payer])
+        };
+
+        // find the largest instruction data that fits
+        let base_size = builder_with_data(0).estimate_size();
+        let mut len = PACKET_DATA_SIZE - base_size;
+        while builder_with_data(len).estimate_size() > PACKET_DATA_SIZE {
+            len -= 1;
+        }
+        let builder = builder_with_data(len);
+        assert_eq!(builder.estimate_size(), PACKET_DATA_SIZE);
+        let transaction = builder.build(Hash::new_unique()).unwrap();
+        assert_eq!(
+            bincode::serialized_size(&transaction).unwrap() as usize,
+            PACKET_DATA_SIZE
+        );
+
+        let builder = builder_with_data(len + 1);
+        assert_eq!(builder.estimate_size(), PACKET_DATA_SIZE + 1);
+        let err = builder.build(Hash::new_unique()).unwrap_err();
+        assert_eq!(
+            err,
+            TransactionBuilderError::TooLarge {
+                size: PACKET_DATA_SIZE + 1,
+                max_size: PACKET_DATA_SIZE,
+            }
+        );
+        assert_eq!(err.overflow(), Some(1));
+    }
+
+    #[test]
+    fn test_dyn_signers() {
+        let payer = Keypair::new();
+        let authority = Keypair::new();
+        let signers: Vec<&dyn Signer> = vec![&payer, &authority];
+        let builder = TransactionBuilder::new()
+            .add_instruction(Instruction::new_with_bytes(
+                Pubkey::new_unique(),
+                &[],
+                vec![AccountMeta::new_readonly(authority.pubkey(), true)],
+            ))
+            .signers(&signers);
+        builder.build(Hash::new_unique()).unwrap().verify().unwrap();
+    }
+
+    #[test]
+    fn test_sign_message() {
+        let payer = Keypair::new();
+        let offline = Keypair::new();
+        let builder = TransactionBuilder::new()
+            .add_instruction(Instruction::new_with_bytes(
+                Pubkey::new_unique(),
+                &[0; 8],
+                vec![AccountMeta::new_readonly(offline.pubkey(), true)],
+            ))
+            .fee_payer(payer.pubkey())
+            .signer(&payer);
+
+        // an adjusted message is signed as is
+        let mut message = builder.message();
+        message.instructions[0].data = vec![1; 8];
+        let blockhash = Hash::new_unique();
+        assert_eq!(
+            builder
+                .sign_message(message.clone(), blockhash)
+                .unwrap_err(),
+            TransactionBuilderError::Signer(SignerError::NotEnoughSigners),
+        );
+        let transaction = builder
+            .partial_sign_message(message.clone(), blockhash)
+            .unwrap();
+        assert_eq!(transaction.message.instructions[0].data, vec![1; 8]);
+        assert_eq!(transaction.message.recent_blockhash, blockhash);
+        assert_ne!(transaction.signatures[0], Signature::default());
+        assert_eq!(transaction.signatures[1], Signature::default());
+
+        // the size is checked before signing
+        message.instructions[0].data = vec![0; PACKET_DATA_SIZE];
+        assert!(matches!(
+            builder.partial_sign_message(message, blockhash),
+            Err(TransactionBuilderError::TooLarge { .. })
+        ));
+    }
+}
@@ -136,6 +136,8 @@ use {
     std::result,
 };
 
+#[cfg(feature = "bincode")]
+pub mod builder;
 pub mod sanitized;
 pub mod simple_vote_transaction_checker;
 pub mod versioned;
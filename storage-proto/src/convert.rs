@@ -852,6 +852,7 @@ impl TryFrom<tx_by_addr::TransactionError> for TransactionError {
             34 => TransactionError::ResanitizationNeeded,
             36 => TransactionError::UnbalancedTransaction,
             37 => TransactionError::ProgramCacheHitMaxLimit,
+            38 => TransactionError::AccountPurgedByRent,
             _ => return Err("Invalid TransactionError"),
         })
     }
@@ -973,6 +974,9 @@ impl From<TransactionError> for tx_by_addr::TransactionError {
                 TransactionError::ProgramCacheHitMaxLimit => {
                     tx_by_addr::TransactionErrorType::ProgramCacheHitMaxLimit
                 }
+                TransactionError::AccountPurgedByRent => {
+                    tx_by_addr::TransactionErrorType::AccountPurgedByRent
+                }
             } as i32,
             instruction_error: match transaction_error {
                 TransactionError::InstructionError(index, ref instruction_error) => {
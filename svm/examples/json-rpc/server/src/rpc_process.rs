@@ -325,6 +325,7 @@ impl JsonRpcRequestProcessor {
                 account_overrides: Some(&account_overrides),
                 check_program_modification_slot: false,
                 compute_budget: Some(ComputeBudget::default()),
+                max_instruction_stack_depth: None,
                 log_messages_bytes_limit: None,
                 limit_to_load_programs: true,
                 recording_config: ExecutionRecordingConfig {
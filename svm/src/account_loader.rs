@@ -110,6 +110,11 @@ pub(crate) struct AccountLoader<'a, CB: TransactionProcessingCallback> {
     pub(crate) feature_set: Arc<FeatureSet>,
 }
 impl<'a, CB: TransactionProcessingCallback> AccountLoader<'a, CB> {
+    /// Whether the account was deleted by rent collection earlier in the slot
+    pub(crate) fn account_purged_by_rent(&self, account_key: &Pubkey) -> bool {
+        self.callbacks.account_purged_by_rent(account_key)
+    }
+
     pub fn new_with_account_cache_capacity(
         account_overrides: Option<&'a AccountOverrides>,
         program_cache: ProgramCacheForTxBatch,
@@ -12,6 +12,7 @@ impl ::solana_frozen_abi::abi_example::AbiExample for RuntimeConfig {
 #[derive(Debug, Default, Clone)]
 pub struct RuntimeConfig {
     pub compute_budget: Option<ComputeBudget>,
+    pub max_instruction_stack_depth: Option<usize>,
     pub log_messages_bytes_limit: Option<usize>,
     pub transaction_account_lock_limit: Option<usize>,
 }
@@ -7,6 +7,7 @@ pub struct TransactionErrorMetrics {
     pub too_many_account_locks: Saturating<usize>,
     pub account_loaded_twice: Saturating<usize>,
     pub account_not_found: Saturating<usize>,
+    pub account_purged_by_rent: Saturating<usize>,
     pub blockhash_not_found: Saturating<usize>,
     pub blockhash_too_old: Saturating<usize>,
     pub call_chain_too_deep: Saturating<usize>,
@@ -39,6 +40,7 @@ impl TransactionErrorMetrics {
         self.too_many_account_locks += other.too_many_account_locks;
         self.account_loaded_twice += other.account_loaded_twice;
         self.account_not_found += other.account_not_found;
+        self.account_purged_by_rent += other.account_purged_by_rent;
         self.blockhash_not_found += other.blockhash_not_found;
         self.blockhash_too_old += other.blockhash_too_old;
         self.call_chain_too_deep += other.call_chain_too_deep;
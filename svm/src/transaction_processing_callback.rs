@@ -14,6 +14,12 @@ pub trait TransactionProcessingCallback {
     fn get_current_epoch_vote_account_stake(&self, _vote_address: &Pubkey) -> u64 {
         0
     }
+
+    /// Whether the account existed at the start of the current slot and was
+    /// deleted by rent collected from it by a transaction committed since
+    fn account_purged_by_rent(&self, _address: &Pubkey) -> bool {
+        false
+    }
 }
 
 /// The state the account is in initially, before transaction processing
@@ -118,6 +118,9 @@ pub struct TransactionProcessingConfig<'a> {
     pub check_program_modification_slot: bool,
     /// The compute budget to use for transaction execution.
     pub compute_budget: Option<ComputeBudget>,
+    /// Overrides the maximum instruction stack depth of the compute budget,
+    /// leaving the rest of it as requested by the transaction.
+    pub max_instruction_stack_depth: Option<usize>,
     /// The maximum number of bytes that log messages can consume.
     pub log_messages_bytes_limit: Option<usize>,
     /// Whether to limit the number of programs loaded for the transaction
@@ -588,6 +591,10 @@ impl<FG: ForkGraph> TransactionBatchProcessor<FG> {
         let Some(mut loaded_fee_payer) =
             account_loader.load_account(fee_payer_address, AccountUsagePattern::Writable)
         else {
+            if account_loader.account_purged_by_rent(fee_payer_address) {
+                error_counters.account_purged_by_rent += 1;
+                return Err(TransactionError::AccountPurgedByRent);
+            }
             error_counters.account_not_found += 1;
             return Err(TransactionError::AccountNotFound);
         };
@@ -600,6 +607,10 @@ impl<FG: ForkGraph> TransactionBatchProcessor<FG> {
             &mut loaded_fee_payer.account,
         )
         .rent_amount;
+        if loaded_fee_payer.rent_collected > 0 && loaded_fee_payer.account.lamports() == 0 {
+            error_counters.account_purged_by_rent += 1;
+            return Err(TransactionError::AccountPurgedByRent);
+        }
 
         let CheckedTransactionDetails {
             nonce,
@@ -955,9 +966,12 @@ impl<FG: ForkGraph> TransactionBatchProcessor<FG> {
         let lamports_before_tx =
             transaction_accounts_lamports_sum(&transaction_accounts, tx).unwrap_or(0);
 
-        let compute_budget = config
+        let mut compute_budget = config
             .compute_budget
             .unwrap_or_else(|| ComputeBudget::from(loaded_transaction.compute_budget_limits));
+        if let Some(max_instruction_stack_depth) = config.max_instruction_stack_depth {
+            compute_budget.max_instruction_stack_depth = max_instruction_stack_depth;
+        }
 
         let mut transaction_context = TransactionContext::new(
             transaction_accounts,
@@ -2327,6 +2341,53 @@ mod tests {
         assert_eq!(result, Err(TransactionError::AccountNotFound));
     }
 
+    #[test]
+    fn test_validate_transaction_fee_payer_purged_by_rent() {
+        let lamports_per_signature = 5000;
+        let message =
+            new_unchecked_sanitized_message(Message::new(&[], Some(&Pubkey::new_unique())));
+        let fee_payer_address = message.fee_payer();
+        let mut rent_collector = RentCollector::default();
+        rent_collector.rent.lamports_per_byte_year = 1_000_000;
+        let fee_payer_account = AccountSharedData::new(1, 0, &Pubkey::default());
+        assert!(
+            rent_collector
+                .get_rent_due(
+                    fee_payer_account.lamports(),
+                    fee_payer_account.data().len(),
+                    fee_payer_account.rent_epoch(),
+                )
+                .lamports()
+                >= fee_payer_account.lamports()
+        );
+
+        let mut mock_accounts = HashMap::new();
+        mock_accounts.insert(*fee_payer_address, fee_payer_account);
+        let mock_bank = MockBankCallback {
+            account_shared_data: Arc::new(RwLock::new(mock_accounts)),
+            ..Default::default()
+        };
+        let mut account_loader = (&mock_bank).into();
+        let mut error_counters = TransactionErrorMetrics::default();
+        let result =
+            TransactionBatchProcessor::<TestForkGraph>::validate_transaction_nonce_and_fee_payer(
+                &mut account_loader,
+                &message,
+                CheckedTransactionDetails {
+                    nonce: None,
+                    lamports_per_signature,
+                },
+                &Hash::default(),
+                FeeStructure::default().lamports_per_signature,
+                &rent_collector,
+                &mut error_counters,
+            );
+
+        assert_eq!(error_counters.account_purged_by_rent.0, 1);
+        assert_eq!(error_counters.account_not_found.0, 0);
+        assert_eq!(result, Err(TransactionError::AccountPurgedByRent));
+    }
+
     #[test]
     fn test_validate_transaction_fee_payer_insufficient_funds() {
         let lamports_per_signature = 5000;
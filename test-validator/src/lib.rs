@@ -988,6 +988,7 @@ impl TestValidator {
                     compute_unit_limit,
                     ..ComputeBudget::default()
                 }),
+            max_instruction_stack_depth: None,
             log_messages_bytes_limit: config.log_messages_bytes_limit,
             transaction_account_lock_limit: config.transaction_account_lock_limit,
         };
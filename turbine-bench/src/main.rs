@@ -0,0 +1,572 @@
+//! Measures how long it takes the shreds of a slot to propagate through the turbine
+//! retransmit tree.
+//!
+//! Every node runs the real retransmitter and inserts the shreds it receives into its own
+//! blockstore, which recovers missing data shreds from coding shreds like the window service
+//! does. Slots are broadcast one at a time by the scheduled leader and each node records when
+//! its blockstore has the full slot. Packet loss and delay are simulated in process on the
+//! receive side, so no root or tc is required.
+#![allow(clippy::arithmetic_side_effects)]
+
+use {
+    clap::{crate_description, crate_name, Arg, Command},
+    crossbeam_channel::{unbounded, Sender},
+    log::*,
+    rand::{thread_rng, Rng},
+    solana_entry::entry::Entry,
+    solana_gossip::{
+        cluster_info::ClusterInfo,
+        contact_info::{ContactInfo, Protocol},
+    },
+    solana_ledger::{
+        blockstore::Blockstore,
+        leader_schedule_cache::LeaderScheduleCache,
+        shred::{ProcessShredsStats, ReedSolomonCache, Shred, Shredder},
+    },
+    solana_net_utils::bind_to_unspecified,
+    solana_runtime::{
+        bank::Bank,
+        bank_forks::BankForks,
+        genesis_utils::{
+            create_genesis_config_with_vote_accounts, GenesisConfigInfo, ValidatorVoteKeypairs,
+        },
+    },
+    solana_sdk::{
+        clock::Slot,
+        genesis_config::ClusterType,
+        hash::Hash,
+        native_token::LAMPORTS_PER_SOL,
+        packet::PACKET_DATA_SIZE,
+        pubkey::Pubkey,
+        signature::{Keypair, Signer},
+        system_transaction,
+        timing::timestamp,
+    },
+    solana_streamer::socket::SocketAddrSpace,
+    solana_turbine::{
+        broadcast_stage::BroadcastStage, cluster_nodes::ClusterNodes,
+        retransmit_stage::retransmitter,
+    },
+    std::{
+        collections::{HashSet, VecDeque},
+        fs::File,
+        io::{self, BufWriter, Write},
+        iter::repeat_with,
+        net::{Ipv4Addr, UdpSocket},
+        path::Path,
+        sync::{
+            atomic::{AtomicBool, Ordering},
+            Arc, RwLock,
+        },
+        thread::{Builder, JoinHandle},
+        time::{Duration, Instant},
+    },
+};
+
+const RECV_TIMEOUT: Duration = Duration::from_millis(1);
+const MAX_RECV_BATCH: usize = 1024;
+// A serialized transfer is ~215 bytes, so about four of them fill a data shred.
+const TRANSFERS_PER_DATA_SHRED: usize = 4;
+
+#[derive(Clone, Debug)]
+struct TurbineBenchConfig {
+    num_nodes: usize,
+    num_slots: u64,
+    // Approximate number of data shreds broadcast per slot.
+    data_shreds_per_slot: usize,
+    // Probability that a node drops a received packet.
+    loss: f64,
+    // Delay added to every packet a node receives.
+    delay: Duration,
+    slot_timeout: Duration,
+}
+
+struct SlotStats {
+    slot: Slot,
+    leader: usize,
+    num_data_shreds: usize,
+    num_coding_shreds: usize,
+    // Time from the start of the broadcast until each node had the full slot; None for the
+    // leader and for nodes which did not complete the slot before the timeout.
+    completion_times: Vec<Option<Duration>>,
+}
+
+// Wraps a node's receive socket to drop and delay packets in process.
+struct LossySocket {
+    socket: UdpSocket,
+    loss: f64,
+    delay: Duration,
+    // Received packets and when they are due. The delay is constant, so these are in order.
+    delayed: VecDeque<(Instant, Vec<u8>)>,
+}
+
+impl LossySocket {
+    fn new(socket: UdpSocket, loss: f64, delay: Duration) -> io::Result<Self> {
+        socket.set_read_timeout(Some(RECV_TIMEOUT))?;
+        Ok(Self {
+            socket,
+            loss,
+            delay,
+            delayed: VecDeque::new(),
+        })
+    }
+
+    // Receives the pending packets, dropping each with probability `loss`, and returns the
+    // packets whose delay has elapsed.
+    fn recv<R: Rng>(&mut self, rng: &mut R) -> Vec<Vec<u8>> {
+        let mut buf = [0u8; PACKET_DATA_SIZE];
+        for _ in 0..MAX_RECV_BATCH {
+            let Ok(size) = self.socket.recv(&mut buf) else {
+                break;
+            };
+            if !rng.gen_bool(self.loss) {
+                self.delayed
+                    .push_back((Instant::now() + self.delay, buf[..size].to_vec()));
+            }
+        }
+        let now = Instant::now();
+        let num_due = self
+            .delayed
+            .iter()
+            .take_while(|(due, _)| *due <= now)
+            .count();
+        self.delayed
+            .drain(..num_due)
+            .map(|(_, packet)| packet)
+            .collect()
+    }
+}
+
+fn spawn_node(
+    index: usize,
+    mut socket: LossySocket,
+    blockstore: Blockstore,
+    leader_schedule_cache: Arc<LeaderScheduleCache>,
+    retransmit_sender: Sender<Vec<Vec<u8>>>,
+    completion_sender: Sender<(Slot, /*node:*/ usize, Instant)>,
+    exit: Arc<AtomicBool>,
+) -> JoinHandle<()> {
+    Builder::new()
+        .name(format!("solTurbineNd{index:02}"))
+        .spawn(move || {
+            let mut rng = thread_rng();
+            let mut completed_slots = HashSet::new();
+            while !exit.load(Ordering::Relaxed) {
+                let packets = socket.recv(&mut rng);
+                if packets.is_empty() {
+                    continue;
+                }
+                let _ = retransmit_sender.send(packets.clone());
+                let shreds: Vec<_> = packets
+                    .into_iter()
+                    .filter_map(|packet| Shred::new_from_serialized_shred(packet).ok())
+                    .collect();
+                let slots: HashSet<Slot> = shreds.iter().map(Shred::slot).collect();
+                if let Err(err) =
+                    blockstore.insert_shreds(shreds, Some(&leader_schedule_cache), false)
+                {
+                    error!("node {index} failed to insert shreds: {err:?}");
+                }
+                let now = Instant::now();
+                for slot in slots {
+                    if !completed_slots.contains(&slot) && blockstore.is_full(slot) {
+                        completed_slots.insert(slot);
+                        let _ = completion_sender.send((slot, index, now));
+                    }
+                }
+            }
+        })
+        .unwrap()
+}
+
+fn make_entries(num_data_shreds: usize) -> Vec<Entry> {
+    let from = Keypair::new();
+    let transfer = system_transaction::transfer(&from, &Pubkey::new_unique(), 1, Hash::default());
+    repeat_with(|| {
+        Entry::new(
+            &Hash::default(),
+            1,
+            vec![transfer.clone(); TRANSFERS_PER_DATA_SHRED],
+        )
+    })
+    .take(num_data_shreds)
+    .collect()
+}
+
+fn run_turbine_bench(config: &TurbineBenchConfig) -> (Vec<Pubkey>, Vec<SlotStats>) {
+    let validator_keypairs: Vec<_> = repeat_with(ValidatorVoteKeypairs::new_rand)
+        .take(config.num_nodes)
+        .collect();
+    let pubkeys: Vec<Pubkey> = validator_keypairs
+        .iter()
+        .map(|keypairs| keypairs.node_keypair.pubkey())
+        .collect();
+    let GenesisConfigInfo { genesis_config, .. } = create_genesis_config_with_vote_accounts(
+        1_000_000 * LAMPORTS_PER_SOL,
+        &validator_keypairs,
+        vec![100 * LAMPORTS_PER_SOL; config.num_nodes],
+    );
+    let bank = Bank::new_for_benches(&genesis_config);
+    let leader_schedule_cache = Arc::new(LeaderScheduleCache::new_from_bank(&bank));
+    let bank_forks = BankForks::new_rw_arc(bank);
+    let root_bank = bank_forks.read().unwrap().root_bank();
+
+    let sockets: Vec<_> = repeat_with(|| bind_to_unspecified().unwrap())
+        .take(config.num_nodes)
+        .collect();
+    let contact_infos: Vec<_> = pubkeys
+        .iter()
+        .zip(&sockets)
+        .map(|(pubkey, socket)| {
+            let mut contact_info = ContactInfo::new_localhost(pubkey, timestamp());
+            let port = socket.local_addr().unwrap().port();
+            contact_info.set_tvu((Ipv4Addr::LOCALHOST, port)).unwrap();
+            contact_info
+        })
+        .collect();
+    let cluster_infos: Vec<_> = validator_keypairs
+        .iter()
+        .zip(&contact_infos)
+        .map(|(keypairs, contact_info)| {
+            let cluster_info = ClusterInfo::new(
+                contact_info.clone(),
+                Arc::new(keypairs.node_keypair.insecure_clone()),
+                SocketAddrSpace::Unspecified,
+            );
+            for other in &contact_infos {
+                if other.pubkey() != contact_info.pubkey() {
+                    cluster_info.insert_info(other.clone());
+                }
+            }
+            Arc::new(cluster_info)
+        })
+        .collect();
+
+    let exit = Arc::new(AtomicBool::new(false));
+    let (completion_sender, completion_receiver) = unbounded();
+    let mut ledger_dirs = Vec::with_capacity(config.num_nodes);
+    let mut node_threads = Vec::with_capacity(config.num_nodes);
+    let mut retransmit_threads = Vec::with_capacity(config.num_nodes);
+    for (index, (socket, cluster_info)) in sockets.into_iter().zip(&cluster_infos).enumerate() {
+        let ledger_dir = tempfile::tempdir().unwrap();
+        let blockstore = Blockstore::open(ledger_dir.path()).unwrap();
+        ledger_dirs.push(ledger_dir);
+        let (retransmit_sender, retransmit_receiver) = unbounded();
+        let (quic_endpoint_sender, _quic_endpoint_receiver) =
+            tokio::sync::mpsc::channel(/*capacity:*/ 128);
+        retransmit_threads.push(retransmitter(
+            Arc::new(vec![bind_to_unspecified().unwrap()]),
+            quic_endpoint_sender,
+            bank_forks.clone(),
+            leader_schedule_cache.clone(),
+            cluster_info.clone(),
+            retransmit_receiver,
+            Arc::default(), // solana_rpc::max_slots::MaxSlots
+            None,
+            None,
+            Arc::new(RwLock::new(None)),
+        ));
+        node_threads.push(spawn_node(
+            index,
+            LossySocket::new(socket, config.loss, config.delay).unwrap(),
+            blockstore,
+            leader_schedule_cache.clone(),
+            retransmit_sender,
+            completion_sender.clone(),
+            exit.clone(),
+        ));
+    }
+
+    let entries = make_entries(config.data_shreds_per_slot);
+    let reed_solomon_cache = ReedSolomonCache::default();
+    let broadcast_socket = bind_to_unspecified().unwrap();
+    let mut slot_stats = Vec::with_capacity(config.num_slots as usize);
+    for slot in 1..=config.num_slots {
+        let Some(leader) = leader_schedule_cache
+            .slot_leader_at(slot, Some(&root_bank))
+            .and_then(|leader| pubkeys.iter().position(|pubkey| *pubkey == leader))
+        else {
+            warn!("no leader scheduled for slot {slot}");
+            break;
+        };
+        let epoch = root_bank.epoch_schedule().get_epoch(slot);
+        let stakes = root_bank.epoch_staked_nodes(epoch).unwrap();
+        let cluster_nodes = ClusterNodes::<BroadcastStage>::new(
+            &cluster_infos[leader],
+            ClusterType::Development,
+            &stakes,
+        );
+        let shredder = Shredder::new(slot, slot - 1, 0, 0).unwrap();
+        let (data_shreds, coding_shreds) = shredder.entries_to_shreds(
+            &validator_keypairs[leader].node_keypair,
+            &entries,
+            true, // is_last_in_slot
+            None, // chained_merkle_root
+            0,    // next_shred_index
+            0,    // next_code_index
+            true, // merkle_variant
+            &reed_solomon_cache,
+            &mut ProcessShredsStats::default(),
+        );
+
+        let start = Instant::now();
+        for shred in data_shreds.iter().chain(&coding_shreds) {
+            let Some(addr) = cluster_nodes
+                .get_broadcast_peer(&shred.id())
+                .and_then(|node| node.tvu(Protocol::UDP))
+            else {
+                continue;
+            };
+            if let Err(err) = broadcast_socket.send_to(shred.payload(), addr) {
+                warn!("failed to broadcast shred to {addr}: {err}");
+            }
+        }
+
+        let mut completion_times = vec![None; config.num_nodes];
+        let mut num_completed = 0;
+        while num_completed < config.num_nodes - 1 {
+            let Some(timeout) = config.slot_timeout.checked_sub(start.elapsed()) else {
+                break;
+            };
+            let Ok((completed_slot, node, now)) = completion_receiver.recv_timeout(timeout) else {
+                break;
+            };
+            if completed_slot == slot && node != leader && completion_times[node].is_none() {
+                completion_times[node] = Some(now.duration_since(start));
+                num_completed += 1;
+            }
+        }
+        info!(
+            "slot {slot}: {num_completed}/{} nodes completed",
+            config.num_nodes - 1
+        );
+        slot_stats.push(SlotStats {
+            slot,
+            leader,
+            num_data_shreds: data_shreds.len(),
+            num_coding_shreds: coding_shreds.len(),
+            completion_times,
+        });
+    }
+
+    // Node threads hold the retransmitters' senders, so the retransmitters exit once the node
+    // threads do.
+    exit.store(true, Ordering::Relaxed);
+    for thread in node_threads.into_iter().chain(retransmit_threads) {
+        thread.join().unwrap();
+    }
+    (pubkeys, slot_stats)
+}
+
+// Returns the p-th percentile of sorted, non-empty values.
+fn percentile(sorted: &[Duration], p: usize) -> Duration {
+    sorted[(sorted.len() - 1) * p / 100]
+}
+
+fn print_summary(slot_stats: &[SlotStats]) {
+    let mut times: Vec<Duration> = slot_stats
+        .iter()
+        .flat_map(|stats| stats.completion_times.iter().flatten().copied())
+        .collect();
+    times.sort_unstable();
+    let num_expected: usize = slot_stats
+        .iter()
+        .map(|stats| stats.completion_times.len() - 1)
+        .sum();
+    let num_data_shreds: usize = slot_stats.iter().map(|stats| stats.num_data_shreds).sum();
+    let num_coding_shreds: usize = slot_stats.iter().map(|stats| stats.num_coding_shreds).sum();
+    println!(
+        "{} slots, {num_data_shreds} data shreds, {num_coding_shreds} coding shreds",
+        slot_stats.len()
+    );
+    println!("completed {}/{num_expected} node slots", times.len());
+    if times.is_empty() {
+        return;
+    }
+    for (name, p) in [("p50", 50), ("p90", 90), ("p99", 99), ("max", 100)] {
+        println!(
+            "time to complete slot {name}: {:.3}ms",
+            percentile(&times, p).as_secs_f64() * 1000.0
+        );
+    }
+}
+
+fn write_csv(path: &Path, pubkeys: &[Pubkey], slot_stats: &[SlotStats]) -> io::Result<()> {
+    let mut file = BufWriter::new(File::create(path)?);
+    writeln!(
+        file,
+        "slot,leader,node,num_data_shreds,num_coding_shreds,completion_us"
+    )?;
+    for stats in slot_stats {
+        for (node, completion_time) in stats.completion_times.iter().enumerate() {
+            if node == stats.leader {
+                continue;
+            }
+            writeln!(
+                file,
+                "{},{},{},{},{},{}",
+                stats.slot,
+                pubkeys[stats.leader],
+                pubkeys[node],
+                stats.num_data_shreds,
+                stats.num_coding_shreds,
+                completion_time
+                    .map(|time| time.as_micros().to_string())
+                    .unwrap_or_default(),
+            )?;
+        }
+    }
+    file.flush()
+}
+
+fn main() {
+    solana_logger::setup();
+
+    let matches = Command::new(crate_name!())
+        .about(crate_description!())
+        .version(solana_version::version!())
+        .arg(
+            Arg::new("num_nodes")
+                .long("num-nodes")
+                .takes_value(true)
+                .value_name("NUM")
+                .default_value("8")
+                .validator(|s| match s.parse::<usize>() {
+                    Ok(n) if n >= 2 => Ok(()),
+                    _ => Err(String::from("must be an integer of at least 2")),
+                })
+                .help("Number of in-process nodes, including the leaders"),
+        )
+        .arg(
+            Arg::new("num_slots")
+                .long("num-slots")
+                .takes_value(true)
+                .value_name("NUM")
+                .default_value("10")
+                .help("Number of slots to broadcast, each from its scheduled leader"),
+        )
+        .arg(
+            Arg::new("shreds_per_slot")
+                .long("shreds-per-slot")
+                .takes_value(true)
+                .value_name("NUM")
+                .default_value("64")
+                .help("Approximate number of data shreds per slot"),
+        )
+        .arg(
+            Arg::new("loss")
+                .long("loss")
+                .takes_value(true)
+                .value_name("PERCENT")
+                .default_value("0")
+                .validator(|s| match s.parse::<f64>() {
+                    Ok(loss) if (0.0..=100.0).contains(&loss) => Ok(()),
+                    _ => Err(String::from("must be a percentage between 0 and 100")),
+                })
+                .help("Percentage of packets each node drops on receive"),
+        )
+        .arg(
+            Arg::new("delay")
+                .long("delay")
+                .takes_value(true)
+                .value_name("MILLISECONDS")
+                .default_value("0")
+                .help("Delay added to every packet each node receives"),
+        )
+        .arg(
+            Arg::new("slot_timeout")
+                .long("slot-timeout")
+                .takes_value(true)
+                .value_name("MILLISECONDS")
+                .default_value("5000")
+                .help("How long to wait for all nodes to complete a slot"),
+        )
+        .arg(
+            Arg::new("csv")
+                .long("csv")
+                .takes_value(true)
+                .value_name("PATH")
+                .help("Write the time each node took to complete each slot as CSV"),
+        )
+        .get_matches();
+
+    let config = TurbineBenchConfig {
+        num_nodes: matches.value_of_t_or_exit("num_nodes"),
+        num_slots: matches.value_of_t_or_exit("num_slots"),
+        data_shreds_per_slot: matches.value_of_t_or_exit("shreds_per_slot"),
+        loss: matches.value_of_t_or_exit::<f64>("loss") / 100.0,
+        delay: Duration::from_millis(matches.value_of_t_or_exit("delay")),
+        slot_timeout: Duration::from_millis(matches.value_of_t_or_exit("slot_timeout")),
+    };
+    info!("{config:?}");
+
+    let (pubkeys, slot_stats) = run_turbine_bench(&config);
+    print_summary(&slot_stats);
+    if let Some(path) = matches.value_of("csv") {
+        write_csv(Path::new(path), &pubkeys, &slot_stats).unwrap_or_else(|err| {
+            eprintln!("Failed to write {path}: {err}");
+            std::process::exit(1);
+        });
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use {super::*, std::thread::sleep};
+
+    #[test]
+    fn test_lossy_socket() {
+        let sender = bind_to_unspecified().unwrap();
+        let mut rng = thread_rng();
+        let send = |socket: &LossySocket| {
+            let addr = (
+                Ipv4Addr::LOCALHOST,
+                socket.socket.local_addr().unwrap().port(),
+            );
+            for i in 0..10u8 {
+                sender.send_to(&[i], addr).unwrap();
+            }
+        };
+
+        let mut socket =
+            LossySocket::new(bind_to_unspecified().unwrap(), 1.0, Duration::ZERO).unwrap();
+        send(&socket);
+        sleep(Duration::from_millis(50));
+        assert!(socket.recv(&mut rng).is_empty());
+
+        let mut socket = LossySocket::new(
+            bind_to_unspecified().unwrap(),
+            0.0,
+            Duration::from_millis(200),
+        )
+        .unwrap();
+        send(&socket);
+        sleep(Duration::from_millis(50));
+        assert!(socket.recv(&mut rng).is_empty());
+        sleep(Duration::from_millis(200));
+        let packets = socket.recv(&mut rng);
+        assert_eq!(packets, (0..10u8).map(|i| vec![i]).collect::<Vec<_>>());
+    }
+
+    #[test]
+    fn test_turbine_bench_zero_loss() {
+        solana_logger::setup();
+        let config = TurbineBenchConfig {
+            num_nodes: 4,
+            num_slots: 3,
+            data_shreds_per_slot: 16,
+            loss: 0.0,
+            delay: Duration::ZERO,
+            slot_timeout: Duration::from_secs(10),
+        };
+        let (pubkeys, slot_stats) = run_turbine_bench(&config);
+        assert_eq!(pubkeys.len(), 4);
+        assert_eq!(slot_stats.len(), 3);
+        for stats in &slot_stats {
+            assert!(stats.num_data_shreds > 0);
+            for (node, completion_time) in stats.completion_times.iter().enumerate() {
+                assert_eq!(completion_time.is_none(), node == stats.leader);
+            }
+        }
+    }
+}
@@ -6,6 +6,7 @@ use {
         broadcast_fake_shreds_run::BroadcastFakeShredsRun,
         broadcast_metrics::*,
         fail_entry_verification_broadcast_run::FailEntryVerificationBroadcastRun,
+        fault_injection_broadcast_run::{FaultInjectionBroadcastRun, FaultInjectionConfig},
         standard_broadcast_run::StandardBroadcastRun,
     },
     crate::cluster_nodes::{self, ClusterNodes, ClusterNodesCache},
@@ -51,6 +52,7 @@ mod broadcast_fake_shreds_run;
 pub mod broadcast_metrics;
 pub(crate) mod broadcast_utils;
 mod fail_entry_verification_broadcast_run;
+pub mod fault_injection_broadcast_run;
 mod standard_broadcast_run;
 
 const_assert_eq!(CLUSTER_NODES_CACHE_NUM_EPOCH_CAP, 5);
@@ -103,6 +105,7 @@ pub enum BroadcastStageType {
     FailEntryVerification,
     BroadcastFakeShreds,
     BroadcastDuplicates(BroadcastDuplicatesConfig),
+    BroadcastFaultInjection(FaultInjectionConfig),
 }
 
 impl BroadcastStageType {
@@ -172,6 +175,19 @@ impl BroadcastStageType {
                 BroadcastDuplicatesRun::new(shred_version, config.clone()),
                 Arc::new(RwLock::new(None)),
             ),
+
+            BroadcastStageType::BroadcastFaultInjection(config) => BroadcastStage::new(
+                sock,
+                cluster_info,
+                receiver,
+                retransmit_slots_receiver,
+                exit_sender,
+                blockstore,
+                bank_forks,
+                quic_endpoint_sender,
+                FaultInjectionBroadcastRun::new(shred_version, config.clone()),
+                Arc::new(RwLock::new(None)),
+            ),
         }
     }
 }
@@ -552,7 +568,7 @@ pub mod test {
 
     #[allow(clippy::implicit_hasher)]
     #[allow(clippy::type_complexity)]
-    fn make_transmit_shreds(
+    pub(super) fn make_transmit_shreds(
         slot: Slot,
         num: u64,
     ) -> (
@@ -5,6 +5,7 @@ use {
     itertools::Itertools,
     solana_entry::entry::Entry,
     solana_ledger::shred::{ProcessShredsStats, ReedSolomonCache, Shredder},
+    solana_runtime::bank::Bank,
     solana_sdk::{
         hash::Hash,
         signature::{Keypair, Signature, Signer},
@@ -22,6 +23,37 @@ pub enum ClusterPartition {
     Pubkey(Vec<Pubkey>),
 }
 
+impl ClusterPartition {
+    /// Returns the nodes in the partition for the given slot. A stake
+    /// partition is sampled from the nodes other than `self_pubkey`.
+    pub(super) fn pubkeys(
+        &self,
+        root_bank: &Bank,
+        slot: Slot,
+        self_pubkey: &Pubkey,
+    ) -> HashSet<Pubkey> {
+        match self {
+            ClusterPartition::Stake(partition_total_stake) => {
+                let mut cumulative_stake = 0;
+                let epoch = root_bank.get_leader_schedule_epoch(slot);
+                root_bank
+                    .epoch_staked_nodes(epoch)
+                    .unwrap()
+                    .iter()
+                    .filter(|(pubkey, _)| *pubkey != self_pubkey)
+                    .sorted_by_key(|(pubkey, stake)| (**stake, **pubkey))
+                    .take_while(|(_, stake)| {
+                        cumulative_stake += *stake;
+                        cumulative_stake <= *partition_total_stake
+                    })
+                    .map(|(pubkey, _)| *pubkey)
+                    .collect()
+            }
+            ClusterPartition::Pubkey(pubkeys) => pubkeys.iter().cloned().collect(),
+        }
+    }
+}
+
 #[derive(Clone, Debug)]
 pub struct BroadcastDuplicatesConfig {
     /// Amount of stake (excluding the leader) or a set of validator pubkeys
@@ -309,29 +341,11 @@ impl BroadcastRun for BroadcastDuplicatesRun {
             let bank_forks = bank_forks.read().unwrap();
             (bank_forks.root_bank(), bank_forks.working_bank())
         };
-        let self_pubkey = cluster_info.id();
         // Create cluster partition.
-        let cluster_partition: HashSet<Pubkey> = {
-            match &self.config.partition {
-                ClusterPartition::Stake(partition_total_stake) => {
-                    let mut cumulative_stake = 0;
-                    let epoch = root_bank.get_leader_schedule_epoch(slot);
-                    root_bank
-                        .epoch_staked_nodes(epoch)
-                        .unwrap()
-                        .iter()
-                        .filter(|(pubkey, _)| **pubkey != self_pubkey)
-                        .sorted_by_key(|(pubkey, stake)| (**stake, **pubkey))
-                        .take_while(|(_, stake)| {
-                            cumulative_stake += *stake;
-                            cumulative_stake <= *partition_total_stake
-                        })
-                        .map(|(pubkey, _)| *pubkey)
-                        .collect()
-                }
-                ClusterPartition::Pubkey(pubkeys) => pubkeys.iter().cloned().collect(),
-            }
-        };
+        let cluster_partition = self
+            .config
+            .partition
+            .pubkeys(&root_bank, slot, &cluster_info.id());
 
         // Broadcast data
         let cluster_nodes =
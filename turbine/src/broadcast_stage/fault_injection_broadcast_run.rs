@@ -0,0 +1,336 @@
+//! Broadcast runs which shred like the standard broadcast run but misbehave
+//! when transmitting the shreds of selected slots, so that local-cluster tests
+//! can exercise how the rest of the cluster copes with a faulty leader.
+use {
+    super::{broadcast_duplicates_run::ClusterPartition, *},
+    crate::cluster_nodes::ClusterNodesCache,
+    solana_sdk::hash::hashv,
+    standard_broadcast_run::StandardBroadcastRun,
+};
+
+/// Slots for which a fault is injected.
+#[derive(Clone, Debug)]
+pub enum FaultSlots {
+    /// Exactly these slots.
+    Slots(HashSet<Slot>),
+    /// Roughly `percent` of all slots, picked deterministically from the slot
+    /// and the seed so that every transmit thread agrees on a slot.
+    Rate { percent: u8, seed: u64 },
+}
+
+impl FaultSlots {
+    pub fn contains(&self, slot: Slot) -> bool {
+        match self {
+            FaultSlots::Slots(slots) => slots.contains(&slot),
+            FaultSlots::Rate { percent, seed } => sample_percent(*seed, slot, u32::MAX) < *percent,
+        }
+    }
+}
+
+#[derive(Clone, Debug)]
+pub enum BroadcastFault {
+    /// Withholds roughly `withhold_percent` of the data shreds from the
+    /// partition. A data shred is withheld if the partition node is the root
+    /// of its turbine tree, so the whole subtree below the node misses it too
+    /// and has to repair it.
+    PartialBroadcast {
+        partition: ClusterPartition,
+        withhold_percent: u8,
+    },
+    /// Holds the shreds for `delay` before sending them.
+    DelayedBroadcast { delay: Duration },
+}
+
+#[derive(Clone, Debug)]
+pub struct FaultInjectionConfig {
+    pub fault: BroadcastFault,
+    pub slots: FaultSlots,
+}
+
+#[derive(Clone)]
+pub(super) struct FaultInjectionBroadcastRun {
+    config: FaultInjectionConfig,
+    standard_broadcast_run: StandardBroadcastRun,
+    cluster_nodes_cache: Arc<ClusterNodesCache<BroadcastStage>>,
+}
+
+impl FaultInjectionBroadcastRun {
+    pub(super) fn new(shred_version: u16, config: FaultInjectionConfig) -> Self {
+        let cluster_nodes_cache = Arc::new(ClusterNodesCache::<BroadcastStage>::new(
+            CLUSTER_NODES_CACHE_NUM_EPOCH_CAP,
+            CLUSTER_NODES_CACHE_TTL,
+        ));
+        Self {
+            config,
+            standard_broadcast_run: StandardBroadcastRun::new(shred_version),
+            cluster_nodes_cache,
+        }
+    }
+
+    // Drops the data shreds which are withheld from the partition, i.e. the
+    // sampled ones whose broadcast peer is in the partition.
+    fn withhold_data_shreds(
+        shreds: &[Shred],
+        withhold_percent: u8,
+        seed: u64,
+        mut sent_to_partition: impl FnMut(&Shred) -> bool,
+    ) -> Vec<Shred> {
+        shreds
+            .iter()
+            .filter(|shred| {
+                let withheld = shred.is_data()
+                    && sample_percent(seed, shred.slot(), shred.index()) < withhold_percent
+                    && sent_to_partition(shred);
+                if withheld {
+                    debug!(
+                        "Withholding data shred index {}, slot {} from partition",
+                        shred.index(),
+                        shred.slot(),
+                    );
+                }
+                !withheld
+            })
+            .cloned()
+            .collect()
+    }
+}
+
+impl BroadcastRun for FaultInjectionBroadcastRun {
+    fn run(
+        &mut self,
+        keypair: &Keypair,
+        blockstore: &Blockstore,
+        receiver: &Receiver<WorkingBankEntry>,
+        socket_sender: &Sender<(Arc<Vec<Shred>>, Option<BroadcastShredBatchInfo>)>,
+        blockstore_sender: &Sender<(Arc<Vec<Shred>>, Option<BroadcastShredBatchInfo>)>,
+    ) -> Result<()> {
+        self.standard_broadcast_run.run(
+            keypair,
+            blockstore,
+            receiver,
+            socket_sender,
+            blockstore_sender,
+        )
+    }
+    fn transmit(
+        &mut self,
+        receiver: &TransmitReceiver,
+        cluster_info: &ClusterInfo,
+        sock: &UdpSocket,
+        bank_forks: &RwLock<BankForks>,
+        quic_endpoint_sender: &AsyncSender<(SocketAddr, Bytes)>,
+        shred_receiver_address: &Arc<RwLock<Option<SocketAddr>>>,
+    ) -> Result<()> {
+        let (mut shreds, batch_info) = receiver.recv()?;
+        // Shreds retransmitted on request don't carry batch info, and are
+        // sent as is.
+        let slot = shreds.first().map(Shred::slot);
+        if let Some(slot) =
+            slot.filter(|&slot| batch_info.is_some() && self.config.slots.contains(slot))
+        {
+            match &self.config.fault {
+                BroadcastFault::PartialBroadcast {
+                    partition,
+                    withhold_percent,
+                } => {
+                    let (root_bank, working_bank) = {
+                        let bank_forks = bank_forks.read().unwrap();
+                        (bank_forks.root_bank(), bank_forks.working_bank())
+                    };
+                    let cluster_partition = partition.pubkeys(&root_bank, slot, &cluster_info.id());
+                    let cluster_nodes =
+                        self.cluster_nodes_cache
+                            .get(slot, &root_bank, &working_bank, cluster_info);
+                    let seed = match self.config.slots {
+                        FaultSlots::Slots(_) => 0,
+                        FaultSlots::Rate { seed, .. } => seed,
+                    };
+                    shreds = Arc::new(Self::withhold_data_shreds(
+                        &shreds,
+                        *withhold_percent,
+                        seed,
+                        |shred| {
+                            cluster_nodes
+                                .get_broadcast_peer(&shred.id())
+                                .map(|node| cluster_partition.contains(node.pubkey()))
+                                .unwrap_or_default()
+                        },
+                    ));
+                }
+                BroadcastFault::DelayedBroadcast { delay } => {
+                    info!(
+                        "Delaying {} shreds of slot {slot} by {delay:?}",
+                        shreds.len()
+                    );
+                    thread::sleep(*delay);
+                }
+            }
+        }
+        self.standard_broadcast_run.broadcast(
+            sock,
+            cluster_info,
+            shreds,
+            batch_info,
+            bank_forks,
+            quic_endpoint_sender,
+            &shred_receiver_address.read().unwrap(),
+        )
+    }
+    fn record(&mut self, receiver: &RecordReceiver, blockstore: &Blockstore) -> Result<()> {
+        self.standard_broadcast_run.record(receiver, blockstore)
+    }
+}
+
+// Uniformly maps (seed, slot, index) to [0, 100).
+fn sample_percent(seed: u64, slot: Slot, index: u32) -> u8 {
+    let hash = hashv(&[
+        &seed.to_le_bytes(),
+        &slot.to_le_bytes(),
+        &index.to_le_bytes(),
+    ]);
+    let sample = u64::from_le_bytes(hash.as_ref()[..8].try_into().unwrap());
+    (sample % 100) as u8
+}
+
+#[cfg(test)]
+mod tests {
+    use {
+        super::{super::test::make_transmit_shreds, *},
+        crossbeam_channel::unbounded,
+        solana_gossip::{cluster_info::Node, contact_info::ContactInfo},
+        solana_ledger::genesis_utils::create_genesis_config,
+        solana_net_utils::bind_to_unspecified,
+        solana_runtime::bank::Bank,
+        solana_sdk::signature::Signer,
+        std::collections::HashSet,
+    };
+
+    #[test]
+    fn test_fault_slots() {
+        let slots = FaultSlots::Slots(HashSet::from([3, 5]));
+        assert!(slots.contains(3));
+        assert!(slots.contains(5));
+        assert!(!slots.contains(4));
+
+        assert!((0..1000).all(|slot| !FaultSlots::Rate {
+            percent: 0,
+            seed: 7
+        }
+        .contains(slot)));
+        assert!((0..1000).all(|slot| FaultSlots::Rate {
+            percent: 100,
+            seed: 7
+        }
+        .contains(slot)));
+        let rate = FaultSlots::Rate {
+            percent: 20,
+            seed: 7,
+        };
+        let faulty: Vec<Slot> = (0..1000).filter(|&slot| rate.contains(slot)).collect();
+        assert!((150..250).contains(&faulty.len()), "{}", faulty.len());
+        // The same slots are picked every time, but not for another seed.
+        assert!(faulty.iter().all(|&slot| rate.contains(slot)));
+        let other = FaultSlots::Rate {
+            percent: 20,
+            seed: 8,
+        };
+        assert!(faulty.iter().any(|&slot| !other.contains(slot)));
+    }
+
+    #[test]
+    fn test_partial_broadcast_withholds_data_shreds() {
+        let (data_shreds, coding_shreds, _, _) = make_transmit_shreds(1, 200);
+        let shreds: Vec<Shred> = data_shreds.iter().chain(&coding_shreds).cloned().collect();
+        let num_shreds = shreds.len();
+
+        // Nothing is withheld if no shred is sent to the partition.
+        let sent = FaultInjectionBroadcastRun::withhold_data_shreds(&shreds, 100, 0, |_| false);
+        assert_eq!(sent.len(), num_shreds);
+        // Or if no data shred is sampled.
+        let sent = FaultInjectionBroadcastRun::withhold_data_shreds(&shreds, 0, 0, |_| true);
+        assert_eq!(sent.len(), num_shreds);
+        // All data shreds sent to the partition are withheld, but never
+        // coding shreds.
+        let to_partition = |shred: &Shred| shred.index() % 2 == 0;
+        let sent = FaultInjectionBroadcastRun::withhold_data_shreds(&shreds, 100, 0, to_partition);
+        assert!(sent
+            .iter()
+            .all(|shred| shred.is_code() || !to_partition(shred)));
+        assert_eq!(
+            sent.iter().filter(|shred| shred.is_code()).count(),
+            coding_shreds.len()
+        );
+        let num_to_partition = data_shreds
+            .iter()
+            .filter(|&shred| to_partition(shred))
+            .count();
+        assert_eq!(sent.len(), num_shreds - num_to_partition);
+        // A percentage of the data shreds, the same ones every time.
+        let sent = FaultInjectionBroadcastRun::withhold_data_shreds(&shreds, 50, 0, |_| true);
+        let num_withheld = num_shreds - sent.len();
+        assert!(
+            (data_shreds.len() / 4..data_shreds.len() * 3 / 4).contains(&num_withheld),
+            "{num_withheld} of {}",
+            data_shreds.len()
+        );
+        assert_eq!(
+            sent,
+            FaultInjectionBroadcastRun::withhold_data_shreds(&shreds, 50, 0, |_| true)
+        );
+    }
+
+    #[test]
+    fn test_delayed_broadcast() {
+        let leader_keypair = Arc::new(Keypair::new());
+        let leader_info = Node::new_localhost_with_pubkey(&leader_keypair.pubkey());
+        let cluster_info = ClusterInfo::new(
+            leader_info.info,
+            leader_keypair,
+            SocketAddrSpace::Unspecified,
+        );
+        cluster_info.insert_info(ContactInfo::new_localhost(
+            &solana_sdk::pubkey::new_rand(),
+            timestamp(),
+        ));
+        let bank = Bank::new_for_tests(&create_genesis_config(10_000).genesis_config);
+        let bank_forks = BankForks::new_rw_arc(bank);
+        let socket = bind_to_unspecified().unwrap();
+        let (quic_endpoint_sender, _quic_endpoint_receiver) = tokio::sync::mpsc::channel(1024);
+        let shred_receiver_address = Arc::new(RwLock::new(None));
+
+        let delay = Duration::from_millis(200);
+        let mut run = FaultInjectionBroadcastRun::new(
+            0,
+            FaultInjectionConfig {
+                fault: BroadcastFault::DelayedBroadcast { delay },
+                slots: FaultSlots::Slots(HashSet::from([1])),
+            },
+        );
+        let (sender, receiver) = unbounded();
+        let mut transmit = |slot| {
+            let (data_shreds, _, _, _) = make_transmit_shreds(slot, 1);
+            let batch_info = BroadcastShredBatchInfo {
+                slot,
+                num_expected_batches: None,
+                slot_start_ts: Instant::now(),
+                was_interrupted: false,
+            };
+            sender
+                .send((Arc::new(data_shreds), Some(batch_info)))
+                .unwrap();
+            let now = Instant::now();
+            run.transmit(
+                &receiver,
+                &cluster_info,
+                &socket,
+                &bank_forks,
+                &quic_endpoint_sender,
+                &shred_receiver_address,
+            )
+            .unwrap();
+            now.elapsed()
+        };
+        assert!(transmit(1) >= delay);
+        assert!(transmit(2) < delay);
+    }
+}
@@ -408,7 +408,7 @@ impl StandardBroadcastRun {
         insert_shreds_stats.update(new_insertion_shreds_stats, broadcast_shred_batch_info);
     }
 
-    fn broadcast(
+    pub(super) fn broadcast(
         &mut self,
         sock: &UdpSocket,
         cluster_info: &ClusterInfo,
@@ -451,13 +451,17 @@ mod tests {
             genesis_utils::create_genesis_config_with_leader,
             shred::{Shred, ShredFlags},
         },
-        solana_perf::packet::Packet,
+        solana_perf::packet::{Packet, PacketFlags},
         solana_runtime::bank::Bank,
         solana_sdk::signature::{Keypair, Signer},
+        test_case::test_case,
     };
 
-    #[test]
-    fn test_sigverify_shreds_verify_batches() {
+    // Repair responses skip the retransmitter signature check, but must still
+    // be signed by the slot leader.
+    #[test_case(false; "turbine")]
+    #[test_case(true; "repair")]
+    fn test_sigverify_shreds_verify_batches(repair: bool) {
         let leader_keypair = Arc::new(Keypair::new());
         let leader_pubkey = leader_keypair.pubkey();
         let bank = Bank::new_for_tests(
@@ -499,6 +503,12 @@ mod tests {
         batches[0][1].buffer_mut()[..shred.payload().len()].copy_from_slice(shred.payload());
         batches[0][1].meta_mut().size = shred.payload().len();
 
+        if repair {
+            for packet in batches[0].iter_mut() {
+                packet.meta_mut().flags |= PacketFlags::REPAIR;
+            }
+        }
+
         let cache = RwLock::new(LruCache::new(/*capacity:*/ 128));
         let thread_pool = ThreadPoolBuilder::new().num_threads(3).build().unwrap();
         let working_bank = bank_forks.read().unwrap().working_bank();
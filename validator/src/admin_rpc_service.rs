@@ -434,8 +434,7 @@ impl AdminRpc for AdminRpcImpl {
 
     fn set_log_filter(&self, filter: String) -> Result<()> {
         debug!("set_log_filter admin rpc request received");
-        solana_logger::setup_with(&filter);
-        Ok(())
+        solana_logger::set_filter(&filter).map_err(jsonrpc_core::error::Error::invalid_params)
     }
 
     fn start_time(&self, meta: Self::Metadata) -> Result<SystemTime> {
@@ -873,6 +872,7 @@ fn rpc_account_index_from_account_index(account_index: &AccountIndex) -> RpcAcco
 // Start the Admin RPC interface
 pub fn run(ledger_path: &Path, metadata: AdminRpcRequestMetadata) {
     let admin_rpc_path = admin_rpc_path(ledger_path);
+    let ledger_path = ledger_path.to_path_buf();
 
     let event_loop = tokio::runtime::Builder::new_multi_thread()
         .thread_name("solAdminRpcEl")
@@ -887,6 +887,15 @@ pub fn run(ledger_path: &Path, metadata: AdminRpcRequestMetadata) {
             let mut io = MetaIoHandler::default();
             io.extend_with(AdminRpcImpl.to_delegate());
 
+            if let Err(err) = create_admin_rpc_dir(&admin_rpc_path) {
+                warn!(
+                    "Unable to create a private directory for {}, not starting admin rpc \
+                     service: {err}",
+                    admin_rpc_path.display()
+                );
+                return;
+            }
+
             let validator_exit = metadata.validator_exit.clone();
             let server = ServerBuilder::with_meta_extractor(io, move |_req: &RequestContext| {
                 metadata.clone()
@@ -900,6 +909,12 @@ pub fn run(ledger_path: &Path, metadata: AdminRpcRequestMetadata) {
                 }
                 Ok(server) => {
                     info!("started admin rpc service!");
+                    if let Err(err) = link_legacy_admin_rpc_path(&ledger_path) {
+                        warn!(
+                            "Unable to link {} to the admin rpc socket: {err}",
+                            legacy_admin_rpc_path(&ledger_path).display()
+                        );
+                    }
                     let close_handle = server.close_handle();
                     validator_exit
                         .write()
@@ -931,13 +946,77 @@ fn admin_rpc_path(ledger_path: &Path) -> PathBuf {
     }
     #[cfg(not(target_family = "windows"))]
     {
-        ledger_path.join("admin.rpc")
+        ledger_path.join("admin-rpc").join("admin.rpc")
+    }
+}
+
+// Where the socket was bound before it moved into a private directory, and where the CLI of
+// older releases and other tools still look for it
+fn legacy_admin_rpc_path(ledger_path: &Path) -> PathBuf {
+    ledger_path.join("admin.rpc")
+}
+
+// Point the legacy path at the socket. Connecting through the link still requires entering
+// the private directory, so it grants no one else access.
+#[cfg(unix)]
+fn link_legacy_admin_rpc_path(ledger_path: &Path) -> std::io::Result<()> {
+    use std::{fs, io::ErrorKind, os::unix::fs::symlink};
+    let legacy_admin_rpc_path = legacy_admin_rpc_path(ledger_path);
+    match fs::remove_file(&legacy_admin_rpc_path) {
+        Err(err) if err.kind() != ErrorKind::NotFound => return Err(err),
+        _ => {}
+    }
+    symlink(
+        Path::new("admin-rpc").join("admin.rpc"),
+        legacy_admin_rpc_path,
+    )
+}
+
+#[cfg(not(unix))]
+fn link_legacy_admin_rpc_path(_ledger_path: &Path) -> std::io::Result<()> {
+    Ok(())
+}
+
+// Only the validator's user may connect to the Admin RPC interface. The socket is created with
+// the process umask, so it is bound inside a directory only that user can enter; setting its
+// mode after binding would leave a window in which others could connect.
+#[cfg(unix)]
+fn create_admin_rpc_dir(admin_rpc_path: &Path) -> std::io::Result<()> {
+    use std::{
+        fs,
+        io::{Error, ErrorKind},
+        os::unix::fs::{DirBuilderExt, PermissionsExt},
+    };
+    let admin_rpc_dir = admin_rpc_path
+        .parent()
+        .expect("admin rpc path is in the ledger directory");
+    match fs::DirBuilder::new().mode(0o700).create(admin_rpc_dir) {
+        Err(err) if err.kind() == ErrorKind::AlreadyExists => {
+            if !fs::symlink_metadata(admin_rpc_dir)?.is_dir() {
+                return Err(Error::new(
+                    ErrorKind::AlreadyExists,
+                    format!("{} is not a directory", admin_rpc_dir.display()),
+                ));
+            }
+            // Fails unless the directory belongs to the validator's user
+            fs::set_permissions(admin_rpc_dir, fs::Permissions::from_mode(0o700))
+        }
+        result => result,
     }
 }
 
+#[cfg(not(unix))]
+fn create_admin_rpc_dir(_admin_rpc_path: &Path) -> std::io::Result<()> {
+    Ok(())
+}
+
 // Connect to the Admin RPC interface
 pub async fn connect(ledger_path: &Path) -> std::result::Result<gen_client::Client, RpcError> {
-    let admin_rpc_path = admin_rpc_path(ledger_path);
+    let mut admin_rpc_path = admin_rpc_path(ledger_path);
+    if !admin_rpc_path.exists() && cfg!(unix) {
+        // Validators from before the socket moved only bind the legacy path
+        admin_rpc_path = legacy_admin_rpc_path(ledger_path);
+    }
     if !admin_rpc_path.exists() {
         Err(RpcError::Client(format!(
             "{} does not exist",
@@ -993,6 +1072,7 @@ mod tests {
     use {
         super::*,
         serde_json::Value,
+        serial_test::serial,
         solana_accounts_db::{
             accounts_db::{AccountsDbConfig, ACCOUNTS_DB_CONFIG_FOR_TESTING},
             accounts_index::AccountSecondaryIndexes,
@@ -1483,6 +1563,36 @@ mod tests {
         );
     }
 
+    // The log filter is global, so the tests which set it or the logger are serialized
+    #[test]
+    #[serial]
+    fn test_set_log_filter() {
+        let RpcHandler { io, meta, .. } = RpcHandler::start_with_config(TestConfig::default());
+        let target = "solana_validator::test_set_log_filter";
+        let set_log_filter = |filter: &str| {
+            let request = format!(
+                r#"{{"jsonrpc":"2.0","id":1,"method":"setLogFilter","params":["{filter}"]}}"#,
+            );
+            let response = io.handle_request_sync(&request, meta.clone()).unwrap();
+            serde_json::from_str::<Value>(&response).unwrap()
+        };
+
+        let response = set_log_filter("error");
+        assert_eq!(response["error"], Value::Null);
+        assert!(!log_enabled!(target: target, Level::Debug));
+
+        let response = set_log_filter(&format!("error,{target}=debug"));
+        assert_eq!(response["error"], Value::Null);
+        assert!(log_enabled!(target: target, Level::Debug));
+        assert!(!log_enabled!(target: target, Level::Trace));
+        assert!(!log_enabled!(target: "solana_validator", Level::Debug));
+
+        // An invalid filter is rejected, leaving the filter unchanged
+        let response = set_log_filter(&format!("{target}=loud"));
+        assert_eq!(response["error"]["code"], ErrorCode::InvalidParams.code());
+        assert!(log_enabled!(target: target, Level::Debug));
+    }
+
     struct TestValidatorWithAdminRpc {
         meta: AdminRpcRequestMetadata,
         io: MetaIoHandler<AdminRpcRequestMetadata>,
@@ -1573,6 +1683,87 @@ mod tests {
         }
     }
 
+    #[cfg(unix)]
+    #[test]
+    #[serial]
+    fn test_admin_rpc_socket() {
+        use std::os::unix::fs::PermissionsExt;
+
+        let ledger_path = tempfile::tempdir().unwrap();
+        let rpc = RpcHandler::start_with_config(TestConfig::default());
+        *rpc.meta.start_progress.write().unwrap() = ValidatorStartProgress::Running;
+        run(ledger_path.path(), rpc.meta.clone());
+
+        // The server is started on its own thread, and links the legacy path once it is up
+        let admin_rpc_path = admin_rpc_path(ledger_path.path());
+        let legacy_admin_rpc_path = legacy_admin_rpc_path(ledger_path.path());
+        for _ in 0..500 {
+            if legacy_admin_rpc_path.exists() {
+                break;
+            }
+            thread::sleep(Duration::from_millis(10));
+        }
+        assert!(admin_rpc_path.exists());
+        assert!(legacy_admin_rpc_path.exists());
+        // Only the validator's user can reach the socket
+        let dir_mode = std::fs::metadata(admin_rpc_path.parent().unwrap())
+            .unwrap()
+            .permissions()
+            .mode();
+        assert_eq!(dir_mode & 0o777, 0o700);
+
+        let runtime = Runtime::new().unwrap();
+        runtime.block_on(async {
+            let admin_client = connect(ledger_path.path()).await.unwrap();
+            assert_eq!(
+                admin_client.start_progress().await.unwrap(),
+                ValidatorStartProgress::Running
+            );
+            admin_client
+                .set_log_filter("solana=info".to_string())
+                .await
+                .unwrap();
+
+            // Tools connecting to the legacy path reach the same socket
+            let legacy_client = ipc::connect::<_, gen_client::Client>(&format!(
+                "{}",
+                legacy_admin_rpc_path.display()
+            ))
+            .await
+            .unwrap();
+            assert_eq!(
+                legacy_client.start_progress().await.unwrap(),
+                ValidatorStartProgress::Running
+            );
+        });
+
+        // Exiting the validator closes the admin rpc service
+        rpc.meta.validator_exit.write().unwrap().exit();
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn test_create_admin_rpc_dir() {
+        use std::{fs, os::unix::fs::PermissionsExt};
+
+        let ledger_path = tempfile::tempdir().unwrap();
+        let admin_rpc_path = admin_rpc_path(ledger_path.path());
+        let admin_rpc_dir = admin_rpc_path.parent().unwrap();
+        let mode = || fs::metadata(admin_rpc_dir).unwrap().permissions().mode() & 0o777;
+
+        create_admin_rpc_dir(&admin_rpc_path).unwrap();
+        assert_eq!(mode(), 0o700);
+
+        // A directory left with other permissions is made private again
+        fs::set_permissions(admin_rpc_dir, fs::Permissions::from_mode(0o755)).unwrap();
+        create_admin_rpc_dir(&admin_rpc_path).unwrap();
+        assert_eq!(mode(), 0o700);
+
+        fs::remove_dir(admin_rpc_dir).unwrap();
+        fs::write(admin_rpc_dir, b"").unwrap();
+        assert!(create_admin_rpc_dir(&admin_rpc_path).is_err());
+    }
+
     // This test checks that `set_identity` call works with working validator and client.
     #[test]
     fn test_set_identity_with_validator() {
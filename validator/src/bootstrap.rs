@@ -1376,7 +1376,7 @@ fn get_snapshot_hashes_for_node(cluster_info: &ClusterInfo, node: &Pubkey) -> Op
 
 #[cfg(test)]
 mod tests {
-    use super::*;
+    use {super::*, serial_test::serial};
 
     impl PeerSnapshotHash {
         fn new(
@@ -1399,6 +1399,7 @@ mod tests {
     }
 
     #[test]
+    #[serial]
     fn test_build_known_snapshot_hashes() {
         solana_logger::setup();
         let full_snapshot_hash1 = (400_000, Hash::new_unique());
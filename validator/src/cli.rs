@@ -20,15 +20,23 @@ use {
         keypair::SKIP_SEED_PHRASE_VALIDATION_ARG,
     },
     solana_core::{
+        banking_stage::forward_tracker::ForwarderConfig,
         banking_trace::{DirByteLimit, BANKING_TRACE_DIR_DEFAULT_BYTE_LIMIT},
         validator::{BlockProductionMethod, BlockVerificationMethod},
     },
+    solana_entry::entry::PohVerifyDevice,
     solana_faucet::faucet::{self, FAUCET_PORT},
     solana_ledger::use_snapshot_archives_at_startup,
     solana_net_utils::{MINIMUM_VALIDATOR_PORT_RANGE_WIDTH, VALIDATOR_PORT_RANGE},
     solana_rayon_threadlimit::get_thread_count,
-    solana_rpc::{rpc::MAX_REQUEST_BODY_SIZE, rpc_pubsub_service::PubSubConfig},
-    solana_rpc_client_api::request::{DELINQUENT_VALIDATOR_SLOT_DISTANCE, MAX_MULTIPLE_ACCOUNTS},
+    solana_rpc::{
+        rpc::MAX_REQUEST_BODY_SIZE, rpc_policy::UNRESTRICTED_METHODS,
+        rpc_pubsub_service::PubSubConfig,
+    },
+    solana_rpc_client_api::request::{
+        DELINQUENT_VALIDATOR_SLOT_DISTANCE, MAX_GET_SIGNATURE_STATUSES_HISTORY_QUERY_ITEMS,
+        MAX_MULTIPLE_ACCOUNTS,
+    },
     solana_runtime::{
         snapshot_bank_utils::{
             DEFAULT_FULL_SNAPSHOT_ARCHIVE_INTERVAL_SLOTS,
@@ -366,6 +374,17 @@ pub fn app<'a>(version: &'a str, default_args: &'a DefaultArgs) -> App<'a, 'a> {
                      JSON RPC method",
                 ),
         )
+        .arg(
+            Arg::with_name("rpc_max_signature_statuses_history_query_items")
+                .long("rpc-max-signature-statuses-history-query-items")
+                .value_name("MAX SIGNATURES")
+                .takes_value(true)
+                .default_value(&default_args.rpc_max_signature_statuses_history_query_items)
+                .help(
+                    "Override the default maximum signatures accepted by the \
+                     getSignatureStatuses JSON RPC method when searching transaction history",
+                ),
+        )
         .arg(
             Arg::with_name("health_check_slot_distance")
                 .long("health-check-slot-distance")
@@ -933,6 +952,19 @@ pub fn app<'a>(version: &'a str, default_args: &'a DefaultArgs) -> App<'a, 'a> {
                      other validators. [default: all validators]",
                 ),
         )
+        .arg(
+            Arg::with_name("cluster_slots_root_retention")
+                .hidden(hidden_unless_forced())
+                .long("cluster-slots-root-retention")
+                .value_name("SLOTS")
+                .takes_value(true)
+                .validator(is_parsable::<Slot>)
+                .default_value(&default_args.cluster_slots_root_retention)
+                .help(
+                    "Number of slots at or before the root for which to keep track of which \
+                     validators have completed them",
+                ),
+        )
         .arg(
             Arg::with_name("gossip_validators")
                 .long("gossip-validator")
@@ -990,6 +1022,18 @@ pub fn app<'a>(version: &'a str, default_args: &'a DefaultArgs) -> App<'a, 'a> {
                 .hidden(hidden_unless_forced())
                 .help("Controls the rate of the clients connections per IpAddr per minute."),
         )
+        .arg(
+            Arg::with_name("tpu_max_forwarded_packets_per_slot")
+                .long("tpu-max-forwarded-packets-per-slot")
+                .takes_value(true)
+                .default_value(&default_args.tpu_max_forwarded_packets_per_slot)
+                .validator(is_parsable::<usize>)
+                .hidden(hidden_unless_forced())
+                .help(
+                    "Controls the number of packets forwarded to the upcoming leaders per slot, \
+                     0 for no limit.",
+                ),
+        )
         .arg(
             Arg::with_name("vote_use_quic")
                 .long("vote-use-quic")
@@ -1176,6 +1220,30 @@ pub fn app<'a>(version: &'a str, default_args: &'a DefaultArgs) -> App<'a, 'a> {
                      all connections.",
                 ),
         )
+        .arg(
+            Arg::with_name("rpc_pubsub_ping_interval_secs")
+                .long("rpc-pubsub-ping-interval-secs")
+                .takes_value(true)
+                .value_name("SECONDS")
+                .validator(is_parsable::<u64>)
+                .default_value(&default_args.rpc_pubsub_ping_interval_secs)
+                .help(
+                    "Interval at which RPC PubSub pings websocket connections to keep them \
+                     alive. 0 will disable pings",
+                ),
+        )
+        .arg(
+            Arg::with_name("rpc_pubsub_pong_timeout_secs")
+                .long("rpc-pubsub-pong-timeout-secs")
+                .takes_value(true)
+                .value_name("SECONDS")
+                .validator(is_parsable::<u64>)
+                .default_value(&default_args.rpc_pubsub_pong_timeout_secs)
+                .help(
+                    "RPC PubSub drops websocket connections which don't answer a ping within \
+                     this timeout",
+                ),
+        )
         .arg(
             Arg::with_name("rpc_pubsub_notification_threads")
                 .long("rpc-pubsub-notification-threads")
@@ -1298,6 +1366,88 @@ pub fn app<'a>(version: &'a str, default_args: &'a DefaultArgs) -> App<'a, 'a> {
                 .default_value(&default_args.rpc_max_request_body_size)
                 .help("The maximum request body size accepted by rpc service"),
         )
+        .arg(
+            Arg::with_name("rpc_max_scan_result_accounts")
+                .long("rpc-max-scan-result-accounts")
+                .value_name("NUMBER")
+                .takes_value(true)
+                .validator(is_parsable::<usize>)
+                .help(
+                    "Fail getProgramAccounts requests which would return more than this many \
+                     accounts, and cap the page size of paginated requests [default: unlimited]",
+                ),
+        )
+        .arg(
+            Arg::with_name("rpc_max_scan_result_bytes")
+                .long("rpc-max-scan-result-bytes")
+                .value_name("BYTES")
+                .takes_value(true)
+                .validator(is_parsable::<usize>)
+                .help(
+                    "Fail getProgramAccounts requests which would return more than this many \
+                     bytes of account data [default: unlimited]",
+                ),
+        )
+        .arg(
+            Arg::with_name("rpc_disable_method")
+                .long("rpc-disable-method")
+                .value_name("METHOD")
+                .takes_value(true)
+                .multiple(true)
+                .validator(rpc_method_validator)
+                .help(
+                    "Reject calls of this RPC method with a \"method disabled by node operator\" \
+                     error. May be specified multiple times",
+                ),
+        )
+        .arg(
+            Arg::with_name("rpc_max_concurrent_calls")
+                .long("rpc-max-concurrent-calls")
+                .value_name("METHOD=NUMBER")
+                .takes_value(true)
+                .multiple(true)
+                .validator(rpc_method_limit_validator)
+                .help(
+                    "Reject calls of this RPC method with a busy error while this many calls of \
+                     it are being handled. May be specified multiple times",
+                ),
+        )
+        .arg(
+            Arg::with_name("rpc_max_requests_per_second")
+                .long("rpc-max-requests-per-second")
+                .value_name("NUMBER")
+                .takes_value(true)
+                .validator(|value| {
+                    value
+                        .parse::<u32>()
+                        .map_err(|err| format!("error parsing '{value}': {err}"))
+                        .and_then(|requests| {
+                            if requests > 0 {
+                                Ok(())
+                            } else {
+                                Err("value must be >= 1".to_string())
+                            }
+                        })
+                })
+                .help(
+                    "Reject RPC calls with a rate limited error beyond this many calls per \
+                     second from a client. Clients are told apart by \
+                     --rpc-client-address-header; calls without a client address share one \
+                     limit [default: unlimited]",
+                ),
+        )
+        .arg(
+            Arg::with_name("rpc_client_address_header")
+                .long("rpc-client-address-header")
+                .value_name("HEADER")
+                .takes_value(true)
+                .requires("rpc_max_requests_per_second")
+                .help(
+                    "Take the address of RPC clients from this HTTP header, e.g. \
+                     X-Forwarded-For. Only use this behind a reverse proxy which sets the \
+                     header, as clients can set it themselves",
+                ),
+        )
         .arg(
             Arg::with_name("geyser_plugin_config")
                 .long("geyser-plugin-config")
@@ -1377,6 +1527,19 @@ pub fn app<'a>(version: &'a str, default_args: &'a DefaultArgs) -> App<'a, 'a> {
                 .value_name("NUM")
                 .help("Specify hashes per batch in PoH service"),
         )
+        .arg(
+            Arg::with_name("poh_verify_device")
+                .long("poh-verify-device")
+                .hidden(hidden_unless_forced())
+                .value_name("DEVICE")
+                .takes_value(true)
+                .possible_values(PohVerifyDevice::cli_names())
+                .default_value("auto")
+                .help(
+                    "Where to verify the PoH of ledger entries while processing the local \
+                     ledger at startup. \"auto\" uses the GPU when perf libs are available",
+                ),
+        )
         .arg(
             Arg::with_name("process_ledger_before_services")
                 .long("process-ledger-before-services")
@@ -1414,6 +1577,18 @@ pub fn app<'a>(version: &'a str, default_args: &'a DefaultArgs) -> App<'a, 'a> {
                      This overrides --account-index-exclude-key.",
                 ),
         )
+        .arg(
+            Arg::with_name("account_index_key_soft_limit")
+                .long("account-index-key-soft-limit")
+                .takes_value(true)
+                .validator(is_parsable::<usize>)
+                .value_name("NUMBER")
+                .help(
+                    "When account indexes are enabled, log and report index keys with more than \
+                     this many accounts. Keys are still indexed in full; use \
+                     --account-index-exclude-key to stop indexing a key.",
+                ),
+        )
         .arg(
             Arg::with_name("accounts_db_verify_refcounts")
                 .long("accounts-db-verify-refcounts")
@@ -1502,6 +1677,21 @@ pub fn app<'a>(version: &'a str, default_args: &'a DefaultArgs) -> App<'a, 'a> {
                 .help("The number of ancient storages the ancient slot combining should converge to.")
                 .hidden(hidden_unless_forced()),
         )
+        .arg(
+            Arg::with_name("accounts_db_compress_data_threshold")
+                .long("accounts-db-compress-data-threshold")
+                .value_name("BYTES")
+                .validator(is_parsable::<usize>)
+                .takes_value(true)
+                .help(
+                    "Store the data of accounts of at least this many bytes lz4 compressed in \
+                     append vecs, trading decompression on load for disk space. Accounts hashes \
+                     are unaffected, but snapshots of storages holding compressed data are \
+                     written with snapshot version 1.3.0, which older validators cannot load. \
+                     Off by default.",
+                )
+                .hidden(hidden_unless_forced()),
+        )
         .arg(
             Arg::with_name("accounts_db_hash_calculation_pubkey_bins")
                 .long("accounts-db-hash-calculation-pubkey-bins")
@@ -2473,14 +2663,18 @@ pub struct DefaultArgs {
 
     pub genesis_archive_unpacked_size: String,
     pub health_check_slot_distance: String,
+    pub cluster_slots_root_retention: String,
     pub tower_storage: String,
     pub etcd_domain_name: String,
     pub send_transaction_service_config: send_transaction_service::Config,
 
     pub rpc_max_multiple_accounts: String,
+    pub rpc_max_signature_statuses_history_query_items: String,
     pub rpc_pubsub_max_active_subscriptions: String,
     pub rpc_pubsub_queue_capacity_items: String,
     pub rpc_pubsub_queue_capacity_bytes: String,
+    pub rpc_pubsub_ping_interval_secs: String,
+    pub rpc_pubsub_pong_timeout_secs: String,
     pub rpc_send_transaction_retry_ms: String,
     pub rpc_send_transaction_batch_ms: String,
     pub rpc_send_transaction_leader_forward_count: String,
@@ -2520,6 +2714,7 @@ pub struct DefaultArgs {
     pub accounts_shrink_ratio: String,
     pub tpu_connection_pool_size: String,
     pub tpu_max_connections_per_ipaddr_per_minute: String,
+    pub tpu_max_forwarded_packets_per_slot: String,
     pub num_quic_endpoints: String,
     pub vote_use_quic: String,
 
@@ -2549,7 +2744,10 @@ impl DefaultArgs {
             maximum_local_snapshot_age: "2500".to_string(),
             genesis_archive_unpacked_size: MAX_GENESIS_ARCHIVE_UNPACKED_SIZE.to_string(),
             rpc_max_multiple_accounts: MAX_MULTIPLE_ACCOUNTS.to_string(),
+            rpc_max_signature_statuses_history_query_items:
+                MAX_GET_SIGNATURE_STATUSES_HISTORY_QUERY_ITEMS.to_string(),
             health_check_slot_distance: DELINQUENT_VALIDATOR_SLOT_DISTANCE.to_string(),
+            cluster_slots_root_retention: "0".to_string(),
             tower_storage: "file".to_string(),
             etcd_domain_name: "localhost".to_string(),
             rpc_pubsub_max_active_subscriptions: PubSubConfig::default()
@@ -2561,6 +2759,15 @@ impl DefaultArgs {
             rpc_pubsub_queue_capacity_bytes: PubSubConfig::default()
                 .queue_capacity_bytes
                 .to_string(),
+            rpc_pubsub_ping_interval_secs: PubSubConfig::default()
+                .ping_interval
+                .map(|ping_interval| ping_interval.as_secs())
+                .unwrap_or_default()
+                .to_string(),
+            rpc_pubsub_pong_timeout_secs: PubSubConfig::default()
+                .pong_timeout
+                .as_secs()
+                .to_string(),
             send_transaction_service_config: send_transaction_service::Config::default(),
             rpc_send_transaction_retry_ms: default_send_transaction_service_config
                 .retry_rate_ms
@@ -2614,6 +2821,9 @@ impl DefaultArgs {
             tpu_connection_pool_size: DEFAULT_TPU_CONNECTION_POOL_SIZE.to_string(),
             tpu_max_connections_per_ipaddr_per_minute:
                 DEFAULT_MAX_CONNECTIONS_PER_IPADDR_PER_MINUTE.to_string(),
+            tpu_max_forwarded_packets_per_slot: ForwarderConfig::default()
+                .max_forwarded_packets_per_slot
+                .to_string(),
             vote_use_quic: DEFAULT_VOTE_USE_QUIC.to_string(),
             num_quic_endpoints: DEFAULT_QUIC_ENDPOINTS.to_string(),
             rpc_max_request_body_size: MAX_REQUEST_BODY_SIZE.to_string(),
@@ -2664,6 +2874,25 @@ fn hash_validator(hash: String) -> Result<(), String> {
         .map_err(|e| format!("{e:?}"))
 }
 
+fn rpc_method_validator(method: String) -> Result<(), String> {
+    if UNRESTRICTED_METHODS.contains(&method.as_str()) {
+        Err(format!("{method} cannot be restricted"))
+    } else {
+        Ok(())
+    }
+}
+
+fn rpc_method_limit_validator(method_limit: String) -> Result<(), String> {
+    let (method, limit) = method_limit
+        .split_once('=')
+        .ok_or_else(|| format!("expected METHOD=NUMBER, got {method_limit}"))?;
+    rpc_method_validator(method.to_string())?;
+    limit
+        .parse::<usize>()
+        .map(|_| ())
+        .map_err(|err| format!("invalid limit for {method}: {err}"))
+}
+
 /// Test validator
 
 pub fn test_app<'a>(version: &'a str, default_args: &'a DefaultTestArgs) -> App<'a, 'a> {
@@ -41,6 +41,7 @@ use {
             ValidatorConfig, ValidatorError, ValidatorStartProgress, ValidatorTpuConfig,
         },
     },
+    solana_entry::entry::PohVerifyDevice,
     solana_gossip::{
         cluster_info::{Node, NodeConfig},
         contact_info::ContactInfo,
@@ -57,6 +58,7 @@ use {
     solana_poh::poh_service,
     solana_rpc::{
         rpc::{JsonRpcConfig, RpcBigtableConfig},
+        rpc_policy::RpcPolicyConfig,
         rpc_pubsub_service::PubSubConfig,
     },
     solana_rpc_client::rpc_client::RpcClient,
@@ -1421,6 +1423,8 @@ pub fn main() {
         value_t!(matches, "accounts_index_scan_results_limit_mb", usize)
             .ok()
             .map(|mb| mb * MB);
+    accounts_index_config.secondary_index_entry_soft_limit =
+        value_t!(matches, "account_index_key_soft_limit", usize).ok();
 
     let account_shrink_paths: Option<Vec<PathBuf>> =
         values_t!(matches, "account_shrink_path", String)
@@ -1523,6 +1527,12 @@ pub fn main() {
             .is_present("accounts_db_test_skip_rewrites"),
         storage_access,
         scan_filter_for_shrinking,
+        append_vec_data_compression_threshold: value_t!(
+            matches,
+            "accounts_db_compress_data_threshold",
+            usize
+        )
+        .ok(),
         enable_experimental_accumulator_hash: matches
             .is_present("accounts_db_experimental_accumulator_hash"),
         verify_experimental_accumulator_hash: matches
@@ -1681,6 +1691,11 @@ pub fn main() {
                 "rpc_max_multiple_accounts",
                 usize
             )),
+            max_signature_statuses_history_query_items: Some(value_t_or_exit!(
+                matches,
+                "rpc_max_signature_statuses_history_query_items",
+                usize
+            )),
             health_check_slot_distance: value_t_or_exit!(
                 matches,
                 "health_check_slot_distance",
@@ -1698,6 +1713,23 @@ pub fn main() {
                 usize
             )),
             skip_preflight_health_check: matches.is_present("skip_preflight_health_check"),
+            max_scan_result_accounts: value_t!(matches, "rpc_max_scan_result_accounts", usize).ok(),
+            max_scan_result_bytes: value_t!(matches, "rpc_max_scan_result_bytes", usize).ok(),
+            policy: RpcPolicyConfig {
+                disabled_methods: values_t!(matches, "rpc_disable_method", String)
+                    .map(HashSet::from_iter)
+                    .unwrap_or_default(),
+                max_concurrent_calls: values_t!(matches, "rpc_max_concurrent_calls", String)
+                    .unwrap_or_default()
+                    .into_iter()
+                    .map(|method_limit| {
+                        let (method, limit) = method_limit.split_once('=').unwrap();
+                        (method.to_string(), limit.parse().unwrap())
+                    })
+                    .collect(),
+                max_requests_per_second: value_t!(matches, "rpc_max_requests_per_second", u32).ok(),
+                client_address_header: value_t!(matches, "rpc_client_address_header", String).ok(),
+            },
         },
         on_start_geyser_plugin_config_files,
         geyser_plugin_always_enabled: matches.is_present("geyser_plugin_always_enabled"),
@@ -1732,12 +1764,28 @@ pub fn main() {
             notification_threads: value_t!(matches, "rpc_pubsub_notification_threads", usize)
                 .ok()
                 .and_then(NonZeroUsize::new),
+            ping_interval: Some(Duration::from_secs(value_t_or_exit!(
+                matches,
+                "rpc_pubsub_ping_interval_secs",
+                u64
+            )))
+            .filter(|ping_interval| !ping_interval.is_zero()),
+            pong_timeout: Duration::from_secs(value_t_or_exit!(
+                matches,
+                "rpc_pubsub_pong_timeout_secs",
+                u64
+            )),
         },
         voting_disabled: matches.is_present("no_voting") || restricted_repair_only_mode,
         wait_for_supermajority: value_t!(matches, "wait_for_supermajority", Slot).ok(),
         known_validators,
         repair_validators,
         repair_whitelist,
+        cluster_slots_root_retention: value_t_or_exit!(
+            matches,
+            "cluster_slots_root_retention",
+            Slot
+        ),
         gossip_validators,
         max_ledger_shreds,
         blockstore_options,
@@ -1777,6 +1825,7 @@ pub fn main() {
             .unwrap_or(poh_service::DEFAULT_PINNED_CPU_CORE),
         poh_hashes_per_batch: value_of(&matches, "poh_hashes_per_batch")
             .unwrap_or(poh_service::DEFAULT_HASHES_PER_BATCH),
+        poh_verify_device: value_t_or_exit!(matches, "poh_verify_device", PohVerifyDevice),
         process_ledger_before_services: matches.is_present("process_ledger_before_services"),
         accounts_db_test_hash_calculation: matches.is_present("accounts_db_test_hash_calculation"),
         accounts_db_config,
@@ -2067,6 +2116,10 @@ pub fn main() {
     )
     .unwrap_or_default();
     validator_config.enable_block_production_forwarding = staked_nodes_overrides_path.is_some();
+    validator_config
+        .forwarder_config
+        .max_forwarded_packets_per_slot =
+        value_t_or_exit!(matches, "tpu_max_forwarded_packets_per_slot", usize);
     validator_config.unified_scheduler_handler_threads =
         value_t!(matches, "unified_scheduler_handler_threads", usize).ok();
 
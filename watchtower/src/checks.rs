@@ -0,0 +1,452 @@
+//! Health checks evaluated for each monitored validator identity
+
+use {
+    solana_cli_output::display::format_labeled_address,
+    solana_native_token::Sol,
+    solana_pubkey::Pubkey,
+    solana_rpc_client::rpc_client::RpcClient,
+    solana_rpc_client_api::{
+        client_error,
+        response::{RpcContactInfo, RpcVoteAccountInfo, RpcVoteAccountStatus},
+    },
+    std::collections::{hash_map::Entry, HashMap},
+};
+
+/// A sanity failure reported by a single check
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Failure {
+    /// Name of the check that failed
+    pub test: &'static str,
+    /// Identifies the failing condition across iterations
+    pub key: String,
+    pub message: String,
+}
+
+impl Failure {
+    /// A cluster-wide failure, keyed by the name of the check
+    pub fn new(test: &'static str, message: String) -> Self {
+        Self {
+            test,
+            key: test.to_string(),
+            message,
+        }
+    }
+
+    fn for_validator(test: &'static str, validator_identity: &Pubkey, message: String) -> Self {
+        Self {
+            test,
+            key: format!("{test}-{validator_identity}"),
+            message,
+        }
+    }
+}
+
+pub struct ValidatorCheckConfig {
+    pub minimum_validator_identity_balance: u64,
+    /// Consecutive iterations a vote account may go without earning credits
+    pub vote_credits_stall_threshold: usize,
+    pub monitor_version_skew: bool,
+}
+
+/// Evaluates the per-validator checks, tracking the vote credits observed on
+/// previous iterations
+pub struct ValidatorChecker {
+    config: ValidatorCheckConfig,
+    // identity -> (last observed credits, consecutive iterations without new credits)
+    vote_credits: HashMap<Pubkey, (u64, usize)>,
+}
+
+impl ValidatorChecker {
+    pub fn new(config: ValidatorCheckConfig) -> Self {
+        Self {
+            config,
+            vote_credits: HashMap::new(),
+        }
+    }
+
+    pub fn check(
+        &mut self,
+        rpc_client: &RpcClient,
+        vote_accounts: &RpcVoteAccountStatus,
+        validator_identities: &[Pubkey],
+        address_labels: &HashMap<String, String>,
+    ) -> client_error::Result<Vec<Failure>> {
+        let mut balances = HashMap::new();
+        for validator_identity in validator_identities {
+            balances.insert(
+                *validator_identity,
+                rpc_client.get_balance(validator_identity)?,
+            );
+        }
+        let cluster_nodes = if self.config.monitor_version_skew {
+            rpc_client.get_cluster_nodes()?
+        } else {
+            vec![]
+        };
+        let majority_version = majority_version(&cluster_nodes, vote_accounts);
+
+        let mut failures = vec![];
+        for validator_identity in validator_identities {
+            let formatted_validator_identity =
+                format_labeled_address(&validator_identity.to_string(), address_labels);
+            failures.extend(
+                check_delinquency(vote_accounts, validator_identity).map(|message| {
+                    Failure::for_validator(
+                        "delinquent",
+                        validator_identity,
+                        format!("{formatted_validator_identity} {message}"),
+                    )
+                }),
+            );
+            failures.extend(
+                check_balance(
+                    balances[validator_identity],
+                    self.config.minimum_validator_identity_balance,
+                )
+                .map(|message| {
+                    Failure::for_validator(
+                        "balance",
+                        validator_identity,
+                        format!("{formatted_validator_identity} has {message}"),
+                    )
+                }),
+            );
+            failures.extend(
+                self.check_vote_credits(vote_accounts, validator_identity)
+                    .map(|message| {
+                        Failure::for_validator(
+                            "vote-credits",
+                            validator_identity,
+                            format!("{formatted_validator_identity} {message}"),
+                        )
+                    }),
+            );
+            if let Some(majority_version) = &majority_version {
+                failures.extend(
+                    check_version(&cluster_nodes, validator_identity, majority_version).map(
+                        |message| {
+                            Failure::for_validator(
+                                "version",
+                                validator_identity,
+                                format!("{formatted_validator_identity} {message}"),
+                            )
+                        },
+                    ),
+                );
+            }
+        }
+        Ok(failures)
+    }
+
+    fn check_vote_credits(
+        &mut self,
+        vote_accounts: &RpcVoteAccountStatus,
+        validator_identity: &Pubkey,
+    ) -> Option<String> {
+        let Some(vote_account) = find_vote_account(vote_accounts, validator_identity) else {
+            // Credits can't be observed without the vote account, so a stall carries on
+            let (_last_credits, stalled) = self.vote_credits.get(validator_identity)?;
+            return self.stalled_vote_credits(*stalled);
+        };
+        let credits = vote_account
+            .epoch_credits
+            .last()
+            .map(|(_epoch, credits, _prev_credits)| *credits)
+            .unwrap_or_default();
+        match self.vote_credits.entry(*validator_identity) {
+            Entry::Vacant(entry) => {
+                entry.insert((credits, 0));
+                None
+            }
+            Entry::Occupied(mut entry) => {
+                let (last_credits, stalled) = entry.get_mut();
+                if credits > *last_credits {
+                    *stalled = 0;
+                } else {
+                    *stalled += 1;
+                }
+                *last_credits = credits;
+                let stalled = *stalled;
+                self.stalled_vote_credits(stalled)
+            }
+        }
+    }
+
+    fn stalled_vote_credits(&self, stalled: usize) -> Option<String> {
+        (stalled > self.config.vote_credits_stall_threshold)
+            .then(|| format!("has not earned vote credits for {stalled} intervals"))
+    }
+}
+
+fn find_vote_account<'a>(
+    vote_accounts: &'a RpcVoteAccountStatus,
+    validator_identity: &Pubkey,
+) -> Option<&'a RpcVoteAccountInfo> {
+    let validator_identity = validator_identity.to_string();
+    vote_accounts
+        .current
+        .iter()
+        .chain(vote_accounts.delinquent.iter())
+        .find(|vai| vai.node_pubkey == validator_identity)
+}
+
+fn check_delinquency(
+    vote_accounts: &RpcVoteAccountStatus,
+    validator_identity: &Pubkey,
+) -> Option<String> {
+    let validator_identity = validator_identity.to_string();
+    if vote_accounts
+        .delinquent
+        .iter()
+        .any(|vai| vai.node_pubkey == validator_identity)
+    {
+        Some("delinquent".to_string())
+    } else if !vote_accounts
+        .current
+        .iter()
+        .any(|vai| vai.node_pubkey == validator_identity)
+    {
+        Some("missing".to_string())
+    } else {
+        None
+    }
+}
+
+fn check_balance(balance: u64, minimum_balance: u64) -> Option<String> {
+    (balance < minimum_balance).then(|| Sol(balance).to_string())
+}
+
+fn parse_version(version: &str) -> Option<semver::Version> {
+    // Node versions may carry a trailing commit hash, eg "2.2.0 c375ce1f"
+    semver::Version::parse(version.split_whitespace().next()?).ok()
+}
+
+/// The version run by the largest share of activated stake
+fn majority_version(
+    cluster_nodes: &[RpcContactInfo],
+    vote_accounts: &RpcVoteAccountStatus,
+) -> Option<semver::Version> {
+    let mut stake_by_version = HashMap::<semver::Version, u64>::new();
+    for node in cluster_nodes {
+        let Some(version) = node.version.as_deref().and_then(parse_version) else {
+            continue;
+        };
+        let stake: u64 = vote_accounts
+            .current
+            .iter()
+            .chain(vote_accounts.delinquent.iter())
+            .filter(|vai| vai.node_pubkey == node.pubkey)
+            .map(|vai| vai.activated_stake)
+            .sum();
+        *stake_by_version.entry(version).or_default() += stake;
+    }
+    stake_by_version
+        .into_iter()
+        .filter(|(_version, stake)| *stake > 0)
+        .max_by(|(version_a, stake_a), (version_b, stake_b)| {
+            stake_a.cmp(stake_b).then(version_a.cmp(version_b))
+        })
+        .map(|(version, _stake)| version)
+}
+
+fn check_version(
+    cluster_nodes: &[RpcContactInfo],
+    validator_identity: &Pubkey,
+    majority_version: &semver::Version,
+) -> Option<String> {
+    let validator_identity = validator_identity.to_string();
+    let version = cluster_nodes
+        .iter()
+        .find(|node| node.pubkey == validator_identity)?
+        .version
+        .as_deref()
+        .and_then(parse_version)?;
+    (version < *majority_version)
+        .then(|| format!("runs {version}, behind cluster majority {majority_version}"))
+}
+
+#[cfg(test)]
+mod tests {
+    use {
+        super::*, serde_json::json, solana_rpc_client::mock_sender::Mocks,
+        solana_rpc_client_api::request::RpcRequest,
+    };
+
+    fn vote_account_info(
+        validator_identity: &Pubkey,
+        activated_stake: u64,
+        credits: u64,
+    ) -> RpcVoteAccountInfo {
+        RpcVoteAccountInfo {
+            vote_pubkey: Pubkey::new_unique().to_string(),
+            node_pubkey: validator_identity.to_string(),
+            activated_stake,
+            commission: 0,
+            epoch_vote_account: true,
+            epoch_credits: vec![(1, credits, 0)],
+            last_vote: 0,
+            root_slot: 0,
+        }
+    }
+
+    fn mock_rpc_client(balance: u64, cluster_nodes: serde_json::Value) -> RpcClient {
+        let mut mocks = Mocks::new();
+        mocks.insert(
+            RpcRequest::GetBalance,
+            json!({"context": {"slot": 1}, "value": balance}),
+        );
+        mocks.insert(RpcRequest::GetClusterNodes, cluster_nodes);
+        RpcClient::new_mock_with_mocks("succeeds".to_string(), mocks)
+    }
+
+    fn checker() -> ValidatorChecker {
+        ValidatorChecker::new(ValidatorCheckConfig {
+            minimum_validator_identity_balance: 10,
+            vote_credits_stall_threshold: 1,
+            monitor_version_skew: true,
+        })
+    }
+
+    #[test]
+    fn test_check_healthy_validator() {
+        let validator_identity = Pubkey::new_unique();
+        let vote_accounts = RpcVoteAccountStatus {
+            current: vec![vote_account_info(&validator_identity, 100, 5)],
+            delinquent: vec![],
+        };
+        let rpc_client = mock_rpc_client(
+            10,
+            json!([{"pubkey": validator_identity.to_string(), "version": "2.2.0 c375ce1f"}]),
+        );
+        let failures = checker()
+            .check(
+                &rpc_client,
+                &vote_accounts,
+                &[validator_identity],
+                &HashMap::new(),
+            )
+            .unwrap();
+        assert_eq!(failures, vec![]);
+    }
+
+    #[test]
+    fn test_check_delinquent_and_low_balance() {
+        let validator_identity = Pubkey::new_unique();
+        let vote_accounts = RpcVoteAccountStatus {
+            current: vec![],
+            delinquent: vec![vote_account_info(&validator_identity, 100, 5)],
+        };
+        let rpc_client = mock_rpc_client(9, json!([]));
+        let failures = checker()
+            .check(
+                &rpc_client,
+                &vote_accounts,
+                &[validator_identity],
+                &HashMap::new(),
+            )
+            .unwrap();
+        assert_eq!(
+            failures
+                .iter()
+                .map(|failure| failure.test)
+                .collect::<Vec<_>>(),
+            vec!["delinquent", "balance"]
+        );
+        assert_eq!(
+            failures[0].message,
+            format!("{validator_identity} delinquent")
+        );
+        assert_eq!(failures[0].key, format!("delinquent-{validator_identity}"));
+
+        let vote_accounts = RpcVoteAccountStatus {
+            current: vec![],
+            delinquent: vec![],
+        };
+        assert_eq!(
+            check_delinquency(&vote_accounts, &validator_identity),
+            Some("missing".to_string())
+        );
+    }
+
+    #[test]
+    fn test_check_vote_credits() {
+        let validator_identity = Pubkey::new_unique();
+        let mut checker = checker();
+        let vote_accounts = |credits| RpcVoteAccountStatus {
+            current: vec![vote_account_info(&validator_identity, 100, credits)],
+            delinquent: vec![],
+        };
+
+        assert_eq!(
+            checker.check_vote_credits(&vote_accounts(5), &validator_identity),
+            None
+        );
+        assert_eq!(
+            checker.check_vote_credits(&vote_accounts(6), &validator_identity),
+            None
+        );
+        // A single stalled interval is within the threshold
+        assert_eq!(
+            checker.check_vote_credits(&vote_accounts(6), &validator_identity),
+            None
+        );
+        assert_eq!(
+            checker.check_vote_credits(&vote_accounts(6), &validator_identity),
+            Some("has not earned vote credits for 2 intervals".to_string())
+        );
+        // A vote account missing from the cluster's list doesn't clear the stall
+        let no_vote_accounts = RpcVoteAccountStatus {
+            current: vec![],
+            delinquent: vec![],
+        };
+        assert_eq!(
+            checker.check_vote_credits(&no_vote_accounts, &validator_identity),
+            Some("has not earned vote credits for 2 intervals".to_string())
+        );
+        assert_eq!(
+            checker.check_vote_credits(&vote_accounts(7), &validator_identity),
+            None
+        );
+        assert_eq!(
+            checker.check_vote_credits(&no_vote_accounts, &validator_identity),
+            None
+        );
+    }
+
+    #[test]
+    fn test_check_version_skew() {
+        let validator_identity = Pubkey::new_unique();
+        let other_identity = Pubkey::new_unique();
+        let vote_accounts = RpcVoteAccountStatus {
+            current: vec![
+                vote_account_info(&validator_identity, 100, 5),
+                vote_account_info(&other_identity, 200, 5),
+            ],
+            delinquent: vec![],
+        };
+        let rpc_client = mock_rpc_client(
+            10,
+            json!([
+                {"pubkey": validator_identity.to_string(), "version": "2.1.9"},
+                {"pubkey": other_identity.to_string(), "version": "2.2.0 c375ce1f"},
+                {"pubkey": Pubkey::new_unique().to_string(), "version": "2.3.0"},
+            ]),
+        );
+        let failures = checker()
+            .check(
+                &rpc_client,
+                &vote_accounts,
+                &[validator_identity],
+                &HashMap::new(),
+            )
+            .unwrap();
+        assert_eq!(
+            failures,
+            vec![Failure::for_validator(
+                "version",
+                &validator_identity,
+                format!("{validator_identity} runs 2.1.9, behind cluster majority 2.2.0"),
+            )]
+        );
+    }
+}
@@ -2,29 +2,30 @@
 #![allow(clippy::arithmetic_side_effects)]
 
 use {
-    clap::{crate_description, crate_name, value_t, value_t_or_exit, App, Arg},
+    crate::{
+        checks::{Failure, ValidatorCheckConfig, ValidatorChecker},
+        notification::{Dispatcher, ExitSink, Hysteresis, LogSink, NotifierSink, WebhookSink},
+    },
+    clap::{crate_description, crate_name, value_t, value_t_or_exit, values_t, App, Arg},
     log::*,
     solana_clap_utils::{
         hidden_unless_forced,
         input_parsers::pubkeys_of,
         input_validators::{is_parsable, is_pubkey_or_keypair, is_url, is_valid_percentage},
     },
-    solana_cli_output::display::format_labeled_address,
     solana_hash::Hash,
     solana_metrics::{datapoint_error, datapoint_info},
     solana_native_token::{sol_to_lamports, Sol},
-    solana_notifier::{NotificationType, Notifier},
+    solana_notifier::Notifier,
     solana_pubkey::Pubkey,
     solana_rpc_client::rpc_client::RpcClient,
     solana_rpc_client_api::{client_error, response::RpcVoteAccountStatus},
-    std::{
-        collections::HashMap,
-        error,
-        thread::sleep,
-        time::{Duration, Instant},
-    },
+    std::{collections::HashMap, error, thread::sleep, time::Duration},
 };
 
+mod checks;
+mod notification;
+
 struct Config {
     address_labels: HashMap<String, String>,
     ignore_http_bad_gateway: bool,
@@ -35,8 +36,13 @@ struct Config {
     monitor_active_stake: bool,
     active_stake_alert_threshold: u8,
     unhealthy_threshold: usize,
+    healthy_threshold: usize,
+    vote_credits_stall_threshold: usize,
+    monitor_version_skew: bool,
     validator_identity_pubkeys: Vec<Pubkey>,
     name_suffix: String,
+    webhook_urls: Vec<String>,
+    exit_code_on_failure: Option<i32>,
 }
 
 fn get_config() -> Config {
@@ -110,6 +116,15 @@ fn get_config() -> Config {
                 .default_value("1")
                 .help("How many consecutive failures must occur to trigger a notification")
         )
+        .arg(
+            Arg::with_name("healthy_threshold")
+                .long("healthy-threshold")
+                .value_name("COUNT")
+                .takes_value(true)
+                .default_value("1")
+                .validator(is_parsable::<usize>)
+                .help("How many consecutive successes must occur to resolve a notification")
+        )
         .arg(
             Arg::with_name("validator_identities")
                 .long("validator-identity")
@@ -128,6 +143,23 @@ fn get_config() -> Config {
                 .validator(is_parsable::<f64>)
                 .help("Alert when the validator identity balance is less than this amount of SOL")
         )
+        .arg(
+            Arg::with_name("vote_credits_stall_threshold")
+                .long("vote-credits-stall-threshold")
+                .value_name("COUNT")
+                .takes_value(true)
+                .default_value("2")
+                .validator(is_parsable::<usize>)
+                .help("Alert when a validator's vote account has not earned credits for more than \
+                    this many consecutive intervals")
+        )
+        .arg(
+            Arg::with_name("monitor_version_skew")
+                .long("monitor-version-skew")
+                .takes_value(false)
+                .help("Alert when a validator runs a version older than the one run by the \
+                    majority of the cluster stake")
+        )
         .arg(
             // Deprecated parameter, now always enabled
             Arg::with_name("no_duplicate_notifications")
@@ -166,6 +198,24 @@ fn get_config() -> Config {
                 .default_value("")
                 .help("Add this string into all notification messages after \"agave-watchtower\"")
         )
+        .arg(
+            Arg::with_name("webhook_urls")
+                .long("webhook-url")
+                .value_name("URL")
+                .takes_value(true)
+                .multiple(true)
+                .validator(is_url)
+                .help("POST a JSON payload describing each notification to this URL")
+        )
+        .arg(
+            Arg::with_name("exit_code_on_failure")
+                .long("exit-code-on-failure")
+                .value_name("CODE")
+                .takes_value(true)
+                .validator(is_parsable::<i32>)
+                .help("Exit with this code once a notification has been triggered, \
+                    allowing a supervisor such as systemd to raise its own alert")
+        )
         .get_matches();
 
     let config = if let Some(config_file) = matches.value_of("config_file") {
@@ -176,6 +226,10 @@ fn get_config() -> Config {
 
     let interval = Duration::from_secs(value_t_or_exit!(matches, "interval", u64));
     let unhealthy_threshold = value_t_or_exit!(matches, "unhealthy_threshold", usize);
+    let healthy_threshold = value_t_or_exit!(matches, "healthy_threshold", usize);
+    let vote_credits_stall_threshold =
+        value_t_or_exit!(matches, "vote_credits_stall_threshold", usize);
+    let monitor_version_skew = matches.is_present("monitor_version_skew");
     let minimum_validator_identity_balance = sol_to_lamports(value_t_or_exit!(
         matches,
         "minimum_validator_identity_balance",
@@ -196,6 +250,8 @@ fn get_config() -> Config {
     let ignore_http_bad_gateway = matches.is_present("ignore_http_bad_gateway");
 
     let name_suffix = value_t_or_exit!(matches, "name_suffix", String);
+    let webhook_urls = values_t!(matches, "webhook_urls", String).unwrap_or_default();
+    let exit_code_on_failure = value_t!(matches, "exit_code_on_failure", i32).ok();
 
     let config = Config {
         address_labels: config.address_labels,
@@ -207,8 +263,13 @@ fn get_config() -> Config {
         monitor_active_stake,
         active_stake_alert_threshold,
         unhealthy_threshold,
+        healthy_threshold,
+        vote_credits_stall_threshold,
+        monitor_version_skew,
         validator_identity_pubkeys,
         name_suffix,
+        webhook_urls,
+        exit_code_on_failure,
     };
 
     info!("RPC URL: {}", config.json_rpc_url);
@@ -220,27 +281,12 @@ fn get_config() -> Config {
 }
 
 fn get_cluster_info(
-    config: &Config,
     rpc_client: &RpcClient,
-) -> client_error::Result<(u64, Hash, RpcVoteAccountStatus, HashMap<Pubkey, u64>)> {
+) -> client_error::Result<(u64, Hash, RpcVoteAccountStatus)> {
     let transaction_count = rpc_client.get_transaction_count()?;
     let recent_blockhash = rpc_client.get_latest_blockhash()?;
     let vote_accounts = rpc_client.get_vote_accounts()?;
-
-    let mut validator_balances = HashMap::new();
-    for validator_identity in &config.validator_identity_pubkeys {
-        validator_balances.insert(
-            *validator_identity,
-            rpc_client.get_balance(validator_identity)?,
-        );
-    }
-
-    Ok((
-        transaction_count,
-        recent_blockhash,
-        vote_accounts,
-        validator_balances,
-    ))
+    Ok((transaction_count, recent_blockhash, vote_accounts))
 }
 
 fn main() -> Result<(), Box<dyn error::Error>> {
@@ -250,17 +296,44 @@ fn main() -> Result<(), Box<dyn error::Error>> {
     let config = get_config();
 
     let rpc_client = RpcClient::new_with_timeout(config.json_rpc_url.clone(), config.rpc_timeout);
-    let notifier = Notifier::default();
+    let mut validator_checker = ValidatorChecker::new(ValidatorCheckConfig {
+        minimum_validator_identity_balance: config.minimum_validator_identity_balance,
+        vote_credits_stall_threshold: config.vote_credits_stall_threshold,
+        monitor_version_skew: config.monitor_version_skew,
+    });
+    let mut hysteresis = Hysteresis::new(config.unhealthy_threshold, config.healthy_threshold);
+    let mut dispatcher = Dispatcher::new(format!("agave-watchtower{}", config.name_suffix));
+    dispatcher.add_sink(Box::new(LogSink));
+    dispatcher.add_sink(Box::new(NotifierSink::new(Notifier::default())));
+    for webhook_url in &config.webhook_urls {
+        dispatcher.add_sink(Box::new(WebhookSink::new(webhook_url.clone())));
+    }
+    // Exit last so that every other sink has seen the notification
+    if let Some(exit_code) = config.exit_code_on_failure {
+        dispatcher.add_sink(Box::new(ExitSink::new(exit_code)));
+    }
     let mut last_transaction_count = 0;
     let mut last_recent_blockhash = Hash::default();
-    let mut last_notification_msg = "".into();
-    let mut num_consecutive_failures = 0;
-    let mut last_success = Instant::now();
-    let mut incident = Hash::new_unique();
 
     loop {
-        let failure = match get_cluster_info(&config, &rpc_client) {
-            Ok((transaction_count, recent_blockhash, vote_accounts, validator_balances)) => {
+        let cluster_info = get_cluster_info(&rpc_client).and_then(
+            |(transaction_count, recent_blockhash, vote_accounts)| {
+                let validator_failures = validator_checker.check(
+                    &rpc_client,
+                    &vote_accounts,
+                    &config.validator_identity_pubkeys,
+                    &config.address_labels,
+                )?;
+                Ok((
+                    transaction_count,
+                    recent_blockhash,
+                    vote_accounts,
+                    validator_failures,
+                ))
+            },
+        );
+        let (failures, complete) = match cluster_info {
+            Ok((transaction_count, recent_blockhash, vote_accounts, validator_failures)) => {
                 info!("Current transaction count: {}", transaction_count);
                 info!("Recent blockhash: {}", recent_blockhash);
                 info!("Current validator count: {}", vote_accounts.current.len());
@@ -295,7 +368,7 @@ fn main() -> Result<(), Box<dyn error::Error>> {
                 if transaction_count > last_transaction_count {
                     last_transaction_count = transaction_count;
                 } else {
-                    failures.push((
+                    failures.push(Failure::new(
                         "transaction-count",
                         format!(
                             "Transaction count is not advancing: {transaction_count} <= {last_transaction_count}"
@@ -306,7 +379,7 @@ fn main() -> Result<(), Box<dyn error::Error>> {
                 if recent_blockhash != last_recent_blockhash {
                     last_recent_blockhash = recent_blockhash;
                 } else {
-                    failures.push((
+                    failures.push(Failure::new(
                         "recent-blockhash",
                         format!("Unable to get new blockhash: {recent_blockhash}"),
                     ));
@@ -315,53 +388,21 @@ fn main() -> Result<(), Box<dyn error::Error>> {
                 if config.monitor_active_stake
                     && current_stake_percent < config.active_stake_alert_threshold as f64
                 {
-                    failures.push((
+                    failures.push(Failure::new(
                         "current-stake",
                         format!("Current stake is {current_stake_percent:.2}%"),
                     ));
                 }
 
-                let mut validator_errors = vec![];
-                for validator_identity in config.validator_identity_pubkeys.iter() {
-                    let formatted_validator_identity = format_labeled_address(
-                        &validator_identity.to_string(),
-                        &config.address_labels,
-                    );
-                    if vote_accounts
-                        .delinquent
-                        .iter()
-                        .any(|vai| vai.node_pubkey == *validator_identity.to_string())
-                    {
-                        validator_errors.push(format!("{formatted_validator_identity} delinquent"));
-                    } else if !vote_accounts
-                        .current
-                        .iter()
-                        .any(|vai| vai.node_pubkey == *validator_identity.to_string())
-                    {
-                        validator_errors.push(format!("{formatted_validator_identity} missing"));
-                    }
-
-                    if let Some(balance) = validator_balances.get(validator_identity) {
-                        if *balance < config.minimum_validator_identity_balance {
-                            failures.push((
-                                "balance",
-                                format!("{} has {}", formatted_validator_identity, Sol(*balance)),
-                            ));
-                        }
-                    }
-                }
-
-                if !validator_errors.is_empty() {
-                    failures.push(("delinquent", validator_errors.join(",")));
-                }
+                failures.extend(validator_failures);
 
                 for failure in failures.iter() {
-                    error!("{} sanity failure: {}", failure.0, failure.1);
+                    error!("{} sanity failure: {}", failure.test, failure.message);
                 }
-                failures.into_iter().next() // Only report the first failure if any
+                (failures, true)
             }
             Err(err) => {
-                let mut failure = Some(("rpc-error", err.to_string()));
+                let mut failures = vec![Failure::new("rpc-error", err.to_string())];
 
                 if let client_error::ErrorKind::Reqwest(reqwest_err) = err.kind() {
                     if let Some(client_error::reqwest::StatusCode::BAD_GATEWAY) =
@@ -369,59 +410,29 @@ fn main() -> Result<(), Box<dyn error::Error>> {
                     {
                         if config.ignore_http_bad_gateway {
                             warn!("Error suppressed: {}", err);
-                            failure = None;
+                            failures.clear();
                         }
                     }
                 }
-                failure
+                // The checks didn't run, so their conditions must not be resolved
+                (failures, false)
             }
         };
 
-        if let Some((failure_test_name, failure_error_message)) = &failure {
-            let notification_msg = format!(
-                "agave-watchtower{}: Error: {}: {}",
-                config.name_suffix, failure_test_name, failure_error_message
+        datapoint_info!("watchtower-sanity", ("ok", failures.is_empty(), bool));
+        for failure in &failures {
+            datapoint_error!(
+                "watchtower-sanity-failure",
+                ("test", failure.test, String),
+                ("err", failure.message, String)
             );
-            num_consecutive_failures += 1;
-            if num_consecutive_failures > config.unhealthy_threshold {
-                datapoint_info!("watchtower-sanity", ("ok", false, bool));
-                if last_notification_msg != notification_msg {
-                    notifier.send(&notification_msg, &NotificationType::Trigger { incident });
-                }
-                datapoint_error!(
-                    "watchtower-sanity-failure",
-                    ("test", failure_test_name, String),
-                    ("err", failure_error_message, String)
-                );
-                last_notification_msg = notification_msg;
-            } else {
-                info!(
-                    "Failure {} of {}: {}",
-                    num_consecutive_failures, config.unhealthy_threshold, notification_msg
-                );
-            }
-        } else {
-            datapoint_info!("watchtower-sanity", ("ok", true, bool));
-            if !last_notification_msg.is_empty() {
-                let alarm_duration = Instant::now().duration_since(last_success);
-                let alarm_duration = alarm_duration - config.interval; // Subtract the period before the first error
-                let alarm_duration = Duration::from_secs(alarm_duration.as_secs()); // Drop milliseconds in message
-
-                let all_clear_msg = format!(
-                    "All clear after {}",
-                    humantime::format_duration(alarm_duration)
-                );
-                info!("{}", all_clear_msg);
-                notifier.send(
-                    &format!("agave-watchtower{}: {}", config.name_suffix, all_clear_msg),
-                    &NotificationType::Resolve { incident },
-                );
-            }
-            last_notification_msg = "".into();
-            last_success = Instant::now();
-            num_consecutive_failures = 0;
-            incident = Hash::new_unique();
         }
+        let notifications = if complete {
+            hysteresis.update(&failures)
+        } else {
+            hysteresis.update_incomplete(&failures)
+        };
+        dispatcher.dispatch(&notifications);
         sleep(config.interval);
     }
 }
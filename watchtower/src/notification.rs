@@ -0,0 +1,443 @@
+//! Notification sinks, and the hysteresis applied before failures are dispatched to them
+
+use {
+    crate::checks::Failure,
+    log::*,
+    reqwest::blocking::Client,
+    serde_json::json,
+    solana_hash::Hash,
+    solana_notifier::{NotificationType, Notifier},
+    std::{
+        collections::HashMap,
+        sync::Mutex,
+        time::{Duration, Instant},
+    },
+};
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum NotificationKind {
+    Trigger,
+    Resolve,
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Notification {
+    pub kind: NotificationKind,
+    pub test: &'static str,
+    pub key: String,
+    pub message: String,
+}
+
+impl Notification {
+    /// JSON body POSTed by [`WebhookSink`]
+    pub fn to_json(&self, source: &str) -> serde_json::Value {
+        json!({
+            "source": source,
+            "event": match self.kind {
+                NotificationKind::Trigger => "trigger",
+                NotificationKind::Resolve => "resolve",
+            },
+            "test": self.test,
+            "key": self.key,
+            "message": self.message,
+        })
+    }
+}
+
+pub trait NotificationSink {
+    fn send(&self, source: &str, notification: &Notification);
+}
+
+/// Writes every notification to the log
+pub struct LogSink;
+
+impl NotificationSink for LogSink {
+    fn send(&self, source: &str, notification: &Notification) {
+        match notification.kind {
+            NotificationKind::Trigger => error!(
+                "{source}: Error: {}: {}",
+                notification.test, notification.message
+            ),
+            NotificationKind::Resolve => info!("{source}: {}", notification.message),
+        }
+    }
+}
+
+/// Forwards notifications to the Slack, Discord, PagerDuty, Telegram and Twilio
+/// channels configured through the environment, one incident per failure key
+pub struct NotifierSink {
+    notifier: Notifier,
+    incidents: Mutex<HashMap<String, Hash>>,
+}
+
+impl NotifierSink {
+    pub fn new(notifier: Notifier) -> Self {
+        Self {
+            notifier,
+            incidents: Mutex::default(),
+        }
+    }
+}
+
+impl NotificationSink for NotifierSink {
+    fn send(&self, source: &str, notification: &Notification) {
+        let mut incidents = self.incidents.lock().unwrap();
+        match notification.kind {
+            NotificationKind::Trigger => {
+                let incident = *incidents
+                    .entry(notification.key.clone())
+                    .or_insert_with(Hash::new_unique);
+                self.notifier.send(
+                    &format!(
+                        "{source}: Error: {}: {}",
+                        notification.test, notification.message
+                    ),
+                    &NotificationType::Trigger { incident },
+                );
+            }
+            NotificationKind::Resolve => {
+                let incident = incidents
+                    .remove(&notification.key)
+                    .unwrap_or_else(Hash::new_unique);
+                self.notifier.send(
+                    &format!("{source}: {}", notification.message),
+                    &NotificationType::Resolve { incident },
+                );
+            }
+        }
+    }
+}
+
+/// POSTs a JSON payload describing each notification to a URL
+pub struct WebhookSink {
+    client: Client,
+    url: String,
+}
+
+impl WebhookSink {
+    pub fn new(url: String) -> Self {
+        Self {
+            client: Client::new(),
+            url,
+        }
+    }
+}
+
+impl NotificationSink for WebhookSink {
+    fn send(&self, source: &str, notification: &Notification) {
+        let payload = notification.to_json(source);
+        if let Err(err) = self.client.post(&self.url).json(&payload).send() {
+            warn!(
+                "Failed to send webhook notification to {}: {:?}",
+                self.url, err
+            );
+        }
+    }
+}
+
+/// Terminates the process with `exit_code` when a failure is triggered, so a
+/// supervisor such as systemd can restart the watchtower and raise its own alert
+pub struct ExitSink {
+    exit_code: i32,
+    exit: Box<dyn Fn(i32)>,
+}
+
+impl ExitSink {
+    pub fn new(exit_code: i32) -> Self {
+        Self::new_with_exit(
+            exit_code,
+            Box::new(|exit_code| std::process::exit(exit_code)),
+        )
+    }
+
+    fn new_with_exit(exit_code: i32, exit: Box<dyn Fn(i32)>) -> Self {
+        Self { exit_code, exit }
+    }
+}
+
+impl NotificationSink for ExitSink {
+    fn send(&self, _source: &str, notification: &Notification) {
+        if notification.kind == NotificationKind::Trigger {
+            (self.exit)(self.exit_code);
+        }
+    }
+}
+
+/// Sends notifications to every sink, in the order the sinks were added
+pub struct Dispatcher {
+    source: String,
+    sinks: Vec<Box<dyn NotificationSink>>,
+}
+
+impl Dispatcher {
+    pub fn new(source: String) -> Self {
+        Self {
+            source,
+            sinks: vec![],
+        }
+    }
+
+    pub fn add_sink(&mut self, sink: Box<dyn NotificationSink>) {
+        self.sinks.push(sink);
+    }
+
+    pub fn dispatch(&self, notifications: &[Notification]) {
+        for notification in notifications {
+            for sink in &self.sinks {
+                sink.send(&self.source, notification);
+            }
+        }
+    }
+}
+
+struct ConditionState {
+    consecutive_failures: usize,
+    consecutive_successes: usize,
+    triggered: bool,
+    first_failure: Instant,
+}
+
+/// Debounces failures so that flapping conditions don't spam the sinks.
+///
+/// A condition is triggered once it fails on more than `unhealthy_threshold`
+/// consecutive iterations, and is only resolved after passing on
+/// `healthy_threshold` consecutive iterations.
+pub struct Hysteresis {
+    unhealthy_threshold: usize,
+    healthy_threshold: usize,
+    conditions: HashMap<String, ConditionState>,
+}
+
+impl Hysteresis {
+    pub fn new(unhealthy_threshold: usize, healthy_threshold: usize) -> Self {
+        Self {
+            unhealthy_threshold,
+            healthy_threshold,
+            conditions: HashMap::new(),
+        }
+    }
+
+    /// Record the failures of one iteration, returning the notifications to send
+    pub fn update(&mut self, failures: &[Failure]) -> Vec<Notification> {
+        self.update_at(failures, Instant::now(), true)
+    }
+
+    /// Like `update`, for an iteration whose checks could not all run, eg because the
+    /// RPC node was unreachable. A condition missing from `failures` was not checked
+    /// rather than found healthy, so nothing is resolved.
+    pub fn update_incomplete(&mut self, failures: &[Failure]) -> Vec<Notification> {
+        self.update_at(failures, Instant::now(), false)
+    }
+
+    fn update_at(
+        &mut self,
+        failures: &[Failure],
+        now: Instant,
+        complete: bool,
+    ) -> Vec<Notification> {
+        let mut notifications = vec![];
+        for failure in failures {
+            let state = self
+                .conditions
+                .entry(failure.key.clone())
+                .or_insert_with(|| ConditionState {
+                    consecutive_failures: 0,
+                    consecutive_successes: 0,
+                    triggered: false,
+                    first_failure: now,
+                });
+            state.consecutive_failures += 1;
+            state.consecutive_successes = 0;
+            if !state.triggered {
+                if state.consecutive_failures > self.unhealthy_threshold {
+                    state.triggered = true;
+                    notifications.push(Notification {
+                        kind: NotificationKind::Trigger,
+                        test: failure.test,
+                        key: failure.key.clone(),
+                        message: failure.message.clone(),
+                    });
+                } else {
+                    info!(
+                        "Failure {} of {}: {}: {}",
+                        state.consecutive_failures,
+                        self.unhealthy_threshold,
+                        failure.test,
+                        failure.message
+                    );
+                }
+            }
+        }
+
+        let healthy_threshold = self.healthy_threshold;
+        self.conditions.retain(|key, state| {
+            if !complete || failures.iter().any(|failure| &failure.key == key) {
+                return true;
+            }
+            if !state.triggered {
+                return false;
+            }
+            state.consecutive_failures = 0;
+            state.consecutive_successes += 1;
+            if state.consecutive_successes < healthy_threshold {
+                return true;
+            }
+            // Drop milliseconds in message
+            let alarm_duration =
+                Duration::from_secs(now.duration_since(state.first_failure).as_secs());
+            notifications.push(Notification {
+                kind: NotificationKind::Resolve,
+                test: "",
+                key: key.clone(),
+                message: format!(
+                    "All clear after {}: {key}",
+                    humantime::format_duration(alarm_duration)
+                ),
+            });
+            false
+        });
+        notifications
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use {
+        super::*,
+        std::{cell::RefCell, rc::Rc},
+    };
+
+    struct RecordingSink(Rc<RefCell<Vec<(String, Notification)>>>);
+
+    impl NotificationSink for RecordingSink {
+        fn send(&self, source: &str, notification: &Notification) {
+            self.0
+                .borrow_mut()
+                .push((source.to_string(), notification.clone()));
+        }
+    }
+
+    fn failure(key: &str) -> Failure {
+        Failure {
+            test: "balance",
+            key: key.to_string(),
+            message: format!("{key} is low"),
+        }
+    }
+
+    #[test]
+    fn test_hysteresis() {
+        let mut hysteresis = Hysteresis::new(1, 2);
+        let start = Instant::now();
+
+        // The first failure is within the unhealthy threshold
+        assert_eq!(hysteresis.update_at(&[failure("a")], start, true), vec![]);
+        let notifications = hysteresis.update_at(&[failure("a"), failure("b")], start, true);
+        assert_eq!(
+            notifications,
+            vec![Notification {
+                kind: NotificationKind::Trigger,
+                test: "balance",
+                key: "a".to_string(),
+                message: "a is low".to_string(),
+            }]
+        );
+        // A triggered condition is not re-sent while it keeps failing, and "b"
+        // never triggered so clearing it is silent
+        assert_eq!(hysteresis.update_at(&[failure("a")], start, true), vec![]);
+
+        // "a" must pass on two consecutive iterations to be resolved
+        assert_eq!(hysteresis.update_at(&[], start, true), vec![]);
+        assert_eq!(hysteresis.update_at(&[failure("a")], start, true), vec![]);
+        assert_eq!(hysteresis.update_at(&[], start, true), vec![]);
+        let notifications = hysteresis.update_at(&[], start + Duration::from_millis(61_500), true);
+        assert_eq!(notifications.len(), 1);
+        assert_eq!(notifications[0].kind, NotificationKind::Resolve);
+        assert_eq!(notifications[0].key, "a");
+        assert_eq!(notifications[0].message, "All clear after 1m 1s: a");
+
+        assert_eq!(hysteresis.update_at(&[], start, true), vec![]);
+        assert!(hysteresis.conditions.is_empty());
+    }
+
+    #[test]
+    fn test_hysteresis_incomplete_iteration() {
+        let mut hysteresis = Hysteresis::new(0, 1);
+        let start = Instant::now();
+        let rpc_error = Failure::new("rpc-error", "connection refused".to_string());
+
+        let notifications = hysteresis.update_at(&[failure("a")], start, true);
+        assert_eq!(notifications.len(), 1);
+        assert_eq!(notifications[0].kind, NotificationKind::Trigger);
+
+        // "a" was not checked while the RPC node was unreachable, so it stays triggered
+        let notifications = hysteresis.update_at(&[rpc_error], start, false);
+        assert_eq!(notifications.len(), 1);
+        assert_eq!(notifications[0].key, "rpc-error");
+        assert_eq!(hysteresis.update_at(&[], start, false), vec![]);
+
+        // Only a completed iteration without the failure resolves it
+        let notifications = hysteresis.update_at(&[failure("a")], start, true);
+        assert_eq!(
+            notifications
+                .iter()
+                .map(|notification| (notification.kind, notification.key.as_str()))
+                .collect::<Vec<_>>(),
+            vec![(NotificationKind::Resolve, "rpc-error")]
+        );
+        let notifications = hysteresis.update_at(&[], start, true);
+        assert_eq!(
+            notifications
+                .iter()
+                .map(|notification| (notification.kind, notification.key.as_str()))
+                .collect::<Vec<_>>(),
+            vec![(NotificationKind::Resolve, "a")]
+        );
+    }
+
+    #[test]
+    fn test_dispatch() {
+        let sent = Rc::new(RefCell::new(vec![]));
+        let exit_codes = Rc::new(RefCell::new(vec![]));
+        let mut dispatcher = Dispatcher::new("agave-watchtower-test".to_string());
+        dispatcher.add_sink(Box::new(LogSink));
+        dispatcher.add_sink(Box::new(RecordingSink(sent.clone())));
+        dispatcher.add_sink(Box::new(ExitSink::new_with_exit(3, {
+            let exit_codes = exit_codes.clone();
+            Box::new(move |exit_code| exit_codes.borrow_mut().push(exit_code))
+        })));
+
+        let mut hysteresis = Hysteresis::new(0, 1);
+        dispatcher.dispatch(&hysteresis.update(&[failure("a")]));
+        assert_eq!(sent.borrow().len(), 1);
+        assert_eq!(sent.borrow()[0].0, "agave-watchtower-test");
+        assert_eq!(sent.borrow()[0].1.kind, NotificationKind::Trigger);
+        assert_eq!(*exit_codes.borrow(), vec![3]);
+
+        // Resolving does not exit
+        dispatcher.dispatch(&hysteresis.update(&[]));
+        assert_eq!(sent.borrow().len(), 2);
+        assert_eq!(sent.borrow()[1].1.kind, NotificationKind::Resolve);
+        assert_eq!(*exit_codes.borrow(), vec![3]);
+    }
+
+    #[test]
+    fn test_webhook_payload() {
+        let notification = Notification {
+            kind: NotificationKind::Trigger,
+            test: "delinquent",
+            key: "delinquent-x".to_string(),
+            message: "x delinquent".to_string(),
+        };
+        assert_eq!(
+            notification.to_json("agave-watchtower"),
+            json!({
+                "source": "agave-watchtower",
+                "event": "trigger",
+                "test": "delinquent",
+                "key": "delinquent-x",
+                "message": "x delinquent",
+            })
+        );
+    }
+}